@@ -0,0 +1,116 @@
+//! Latency benchmarks for the tool pipeline.
+//!
+//! A mock `pop` script replays canned output so the numbers cover arg building,
+//! process spawn overhead and output parsing without Pop CLI doing any work.
+//!
+//! Run with `cargo bench --bench latency`.
+#![allow(missing_docs, clippy::expect_used, clippy::unwrap_used)]
+
+use std::path::{Path, PathBuf};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use pop_mcp_server::deployments::Registry;
+use pop_mcp_server::resources::{read_resource, TYPE_HINTS_URI};
+use pop_mcp_server::rpc::RpcPool;
+use pop_mcp_server::tools::call::chain::{call_chain, CallChainParams};
+use pop_mcp_server::tools::up::chain::{up_ink_node, UpInkNodeParams};
+use pop_mcp_server::{CommandExecutor, PopExecutor};
+use tempfile::TempDir;
+
+/// Canned `pop up ink-node` output, including ANSI codes and box-drawing characters.
+const UP_INK_NODE_OUTPUT: &str = "\
+┌   Pop CLI : Launch a local Ink! node
+│
+⚙  Local node started successfully:
+\x1b[2m│  url: ws://localhost:9944/\x1b[0m
+│  logs: tail -f /tmp/.tmpDGAoYa
+│
+└  ✅ Ink! node bootstrapped successfully. Run `kill -9 11040 11253` to terminate it.";
+
+/// Canned `pop call chain` storage query output.
+const CALL_CHAIN_OUTPUT: &str = "\
+┌   Pop CLI : Call a chain
+│
+◇  Storage value: AccountInfo { nonce: 0, consumers: 0, providers: 1, sufficients: 0 }
+│
+└  Call complete.";
+
+/// Write an executable mock `pop` that dispatches on its first argument.
+fn write_mock_pop(dir: &Path) -> PathBuf {
+    let script = format!(
+        "#!/bin/sh\ncase \"$1\" in\n  up) cat <<'EOF'\n{UP_INK_NODE_OUTPUT}\nEOF\n  ;;\n  call) cat <<'EOF'\n{CALL_CHAIN_OUTPUT}\nEOF\n  ;;\n  *) echo \"pop-cli 0.0.0-mock\" ;;\nesac\n"
+    );
+    let path = dir.join("pop");
+    std::fs::write(&path, script).expect("write mock pop");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+            .expect("chmod mock pop");
+    }
+    path
+}
+
+fn bench_tools(c: &mut Criterion) {
+    let dir = TempDir::new().expect("tempdir");
+    let mock = write_mock_pop(dir.path());
+    std::env::set_var("POP_CLI_PATH", &mock);
+    let executor = PopExecutor::new();
+    let rt = tokio::runtime::Runtime::new().expect("tokio runtime");
+
+    c.bench_function("executor/version", |b| {
+        b.iter(|| {
+            rt.block_on(executor.execute(black_box(&["--version"])))
+                .unwrap()
+        });
+    });
+
+    c.bench_function("tools/up_ink_node_parse", |b| {
+        b.iter(|| {
+            rt.block_on(up_ink_node(
+                &executor,
+                UpInkNodeParams {
+                    ink_node_port: None,
+                    eth_rpc_port: None,
+                    name: None,
+                    detach: None,
+                },
+            ))
+            .unwrap()
+        });
+    });
+
+    c.bench_function("tools/call_chain_query", |b| {
+        b.iter(|| {
+            rt.block_on(call_chain(
+                &executor,
+                &RpcPool::new(),
+                CallChainParams {
+                    url: "ws://localhost:9944".to_owned(),
+                    pallet: Some("System".to_owned()),
+                    function: Some("Account".to_owned()),
+                    args: Some(vec![
+                        "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_owned()
+                    ]),
+                    sudo: None,
+                    execute: None,
+                    use_wallet: None,
+                    metadata: None,
+                    dry_run: None,
+                    at_block: None,
+                },
+            ))
+            .unwrap()
+        });
+    });
+}
+
+fn bench_resources(c: &mut Criterion) {
+    let deployments = Registry::new("deployments.json");
+    c.bench_function("resources/read_type_hints", |b| {
+        b.iter(|| read_resource(black_box(TYPE_HINTS_URI), &[], &deployments).unwrap());
+    });
+}
+
+criterion_group!(benches, bench_tools, bench_resources);
+criterion_main!(benches);
@@ -0,0 +1,255 @@
+//! Dev account derivation and the session address book.
+//!
+//! Accounts are derived from the well-known Substrate dev phrase the same way
+//! `//Alice` is (sr25519, hard junctions), so Pop CLI can sign with their SURI
+//! directly. Derived accounts are recorded in a process-wide address book, published
+//! as the [`ADDRESS_BOOK_URI`] resource. [`keypair_from_suri`] derives a signing key
+//! for transactions the server signs itself.
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+
+use codec::Encode;
+use schemars::JsonSchema;
+use schnorrkel::derive::{ChainCode, Derivation};
+use schnorrkel::{ExpansionMode, Keypair, MiniSecretKey, SecretKey};
+use serde::{Deserialize, Serialize};
+
+use crate::encoder::blake2_256;
+
+/// Mnemonic of the Substrate dev accounts (`//Alice`, `//Bob`, ...).
+pub const DEV_PHRASE: &str =
+    "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+
+/// SS58 prefix of generic Substrate addresses.
+pub const DEFAULT_SS58_PREFIX: u16 = 42;
+
+/// URI of the address book resource.
+pub const ADDRESS_BOOK_URI: &str = "pop://accounts";
+
+/// Prefix hashed with an SS58 payload to form its checksum.
+const SS58_CHECKSUM_PREFIX: &[u8] = b"SS58PRE";
+
+/// Accounts recorded this session, by name.
+static ADDRESS_BOOK: Mutex<BTreeMap<String, Account>> = Mutex::new(BTreeMap::new());
+
+/// A named account usable as a signer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Account {
+    /// Name in the address book.
+    pub name: String,
+    /// SURI to sign with (e.g. `//Test//0`).
+    pub suri: String,
+    /// SS58 address.
+    pub address: String,
+    /// Public key (0x-prefixed hex).
+    pub public_key: String,
+}
+
+/// sr25519 public key of the dev account at `path` (e.g. `//Alice`).
+///
+/// Only hard (`//`) and soft (`/`) junctions are supported; passwords (`///`) are not,
+/// as dev accounts have none.
+pub fn derive_dev_public_key(path: &str) -> Result<[u8; 32], String> {
+    Ok(derive(dev_root()?, path)?.to_public().to_bytes())
+}
+
+/// sr25519 keypair for `suri`: a mnemonic, a `0x` hex seed or nothing (the dev phrase),
+/// followed by an optional derivation path (e.g. `//Alice`).
+pub fn keypair_from_suri(suri: &str) -> Result<Keypair, String> {
+    let suri = suri.trim();
+    let (phrase, path) = suri.find('/').map_or((suri, ""), |i| suri.split_at(i));
+    let phrase = phrase.trim();
+    let root = if phrase.is_empty() {
+        dev_root()?
+    } else if let Some(hex) = phrase.strip_prefix("0x") {
+        let seed = crate::encoder::decode_hex(hex)?;
+        MiniSecretKey::from_bytes(&seed)
+            .map_err(|_| "Hex seeds must be 32 bytes".to_owned())?
+            .expand(ExpansionMode::Ed25519)
+    } else {
+        phrase_root(phrase)?
+    };
+    let secret = if path.is_empty() {
+        root
+    } else {
+        derive(root, path)?
+    };
+    Ok(secret.to_keypair())
+}
+
+/// `secret` derived along `path`.
+fn derive(mut secret: SecretKey, path: &str) -> Result<SecretKey, String> {
+    for (hard, junction) in junctions(path)? {
+        let code = ChainCode(chain_code(junction));
+        secret = if hard {
+            derive_hard(&secret, code)
+        } else {
+            secret.derived_key_simple(code, []).0
+        };
+    }
+    Ok(secret)
+}
+
+/// Root secret of an English BIP-39 mnemonic, without password.
+fn phrase_root(phrase: &str) -> Result<SecretKey, String> {
+    let mnemonic = bip39::Mnemonic::parse_in(bip39::Language::English, phrase)
+        .map_err(|e| format!("Invalid mnemonic: {}", e))?;
+    let mini = substrate_bip39::mini_secret_from_entropy(&mnemonic.to_entropy(), "")
+        .map_err(|e| format!("{:?}", e))?;
+    Ok(mini.expand(ExpansionMode::Ed25519))
+}
+
+/// Root secret of the dev phrase, computed once (seed stretching is deliberately slow).
+fn dev_root() -> Result<SecretKey, String> {
+    static ROOT: OnceLock<Result<SecretKey, String>> = OnceLock::new();
+    ROOT.get_or_init(|| phrase_root(DEV_PHRASE)).clone()
+}
+
+/// Hard-derived child of `secret`, as Substrate derives `//junction`.
+fn derive_hard(secret: &SecretKey, code: ChainCode) -> SecretKey {
+    let (mini, _): (MiniSecretKey, _) = secret.hard_derive_mini_secret_key(Some(code), b"");
+    mini.expand(ExpansionMode::Ed25519)
+}
+
+/// Junctions of `path` as (hard, name) pairs.
+fn junctions(path: &str) -> Result<Vec<(bool, &str)>, String> {
+    if path.contains("///") {
+        return Err("Password junctions (///) are not supported".to_owned());
+    }
+    let Some(mut rest) = path.strip_prefix('/') else {
+        return Err(format!("Derivation path must start with '/': {}", path));
+    };
+    let mut junctions = Vec::new();
+    loop {
+        let hard = rest.starts_with('/');
+        let name_start = usize::from(hard);
+        let end = rest[name_start..]
+            .find('/')
+            .map_or(rest.len(), |i| i + name_start);
+        let name = &rest[name_start..end];
+        if name.is_empty() {
+            return Err(format!("Empty junction in derivation path: {}", path));
+        }
+        junctions.push((hard, name));
+        match rest[end..].strip_prefix('/') {
+            Some(next) => rest = next,
+            None => return Ok(junctions),
+        }
+    }
+}
+
+/// Chain code of a junction: SCALE-encoded number or string, padded or hashed to 32 bytes.
+fn chain_code(junction: &str) -> [u8; 32] {
+    let encoded = match junction.parse::<u64>() {
+        Ok(n) => n.encode(),
+        Err(_) => junction.encode(),
+    };
+    if encoded.len() > 32 {
+        return blake2_256(&encoded);
+    }
+    let mut code = [0u8; 32];
+    code[..encoded.len()].copy_from_slice(&encoded);
+    code
+}
+
+/// SS58 address of `public_key` with network `prefix`.
+pub fn ss58_address(public_key: &[u8; 32], prefix: u16) -> String {
+    let mut payload = match prefix {
+        0..=63 => vec![prefix as u8],
+        _ => {
+            // Two-byte form: lower six bits of the first byte are bits 2..8 of the prefix.
+            let first = ((prefix & 0b1111_1100) >> 2) as u8 | 0b0100_0000;
+            let second = ((prefix >> 8) as u8) | ((prefix & 0b11) << 6) as u8;
+            vec![first, second]
+        }
+    };
+    payload.extend_from_slice(public_key);
+    let mut preimage = SS58_CHECKSUM_PREFIX.to_vec();
+    preimage.extend_from_slice(&payload);
+    let checksum = blake2b_512(&preimage);
+    payload.extend_from_slice(&checksum[..2]);
+    bs58::encode(payload).into_string()
+}
+
+/// BLAKE2b-512 of `data`, used for SS58 checksums.
+fn blake2b_512(data: &[u8]) -> [u8; 64] {
+    use blake2::Digest;
+    blake2::Blake2b512::digest(data).into()
+}
+
+/// Record `accounts` in the address book, replacing entries with the same name.
+pub fn register(accounts: &[Account]) {
+    if let Ok(mut book) = ADDRESS_BOOK.lock() {
+        for account in accounts {
+            book.insert(account.name.clone(), account.clone());
+        }
+    }
+}
+
+/// Accounts in the address book, by name.
+pub fn address_book() -> Vec<Account> {
+    ADDRESS_BOOK
+        .lock()
+        .map(|book| book.values().cloned().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_well_known_dev_accounts() {
+        let alice = derive_dev_public_key("//Alice");
+        assert_eq!(
+            alice.map(|key| ss58_address(&key, DEFAULT_SS58_PREFIX)),
+            Ok("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_owned())
+        );
+        let bob_stash = derive_dev_public_key("//Bob//stash");
+        assert_eq!(
+            bob_stash.map(|key| ss58_address(&key, DEFAULT_SS58_PREFIX)),
+            Ok("5HpG9w8EBLe5XCrbczpwq5TSXvedjrBGCwqxK1iQ7qUsSWFc".to_owned())
+        );
+    }
+
+    #[test]
+    fn ss58_address_supports_two_byte_prefixes() -> Result<(), String> {
+        let alice = derive_dev_public_key("//Alice")?;
+        assert_eq!(
+            ss58_address(&alice, 0),
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        );
+        let decoded = bs58::decode(ss58_address(&alice, 4242)).into_vec();
+        assert_eq!(decoded.map(|bytes| bytes.len()).ok(), Some(2 + 32 + 2));
+        Ok(())
+    }
+
+    #[test]
+    fn keypair_from_suri_accepts_dev_paths_phrases_and_seeds() -> Result<(), String> {
+        let alice = derive_dev_public_key("//Alice")?;
+        let from_path = keypair_from_suri("//Alice")?;
+        assert_eq!(from_path.public.to_bytes(), alice);
+        let from_phrase = keypair_from_suri(&format!("{}//Alice", DEV_PHRASE))?;
+        assert_eq!(from_phrase.public.to_bytes(), alice);
+        let seed = format!("0x{}", "01".repeat(32));
+        assert_eq!(
+            keypair_from_suri(&seed)?.public.to_bytes(),
+            keypair_from_suri(&format!(" {} ", seed))?.public.to_bytes()
+        );
+        assert!(keypair_from_suri("0x0102").is_err());
+        assert!(keypair_from_suri("not a mnemonic//Alice").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn junctions_reject_malformed_paths() {
+        assert_eq!(
+            junctions("//Test//0/soft"),
+            Ok(vec![(true, "Test"), (true, "0"), (false, "soft")])
+        );
+        assert!(junctions("Alice").is_err());
+        assert!(junctions("//Alice///password").is_err());
+        assert!(junctions("//Alice//").is_err());
+    }
+}
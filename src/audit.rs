@@ -0,0 +1,141 @@
+//! Audit trail of Pop CLI invocations.
+//!
+//! Every command [`crate::PopExecutor`] runs is recorded with its arguments (secrets
+//! masked), duration, exit status and the tail of its output, so sessions driven by an
+//! AI can be reviewed afterwards through `list_command_history` or the
+//! [`AUDIT_URI`] resource.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// URI of the audit trail resource.
+pub const AUDIT_URI: &str = "audit://commands";
+
+/// Commands kept; older ones are dropped first.
+const MAX_ENTRIES: usize = 500;
+
+/// Output kept per command, in characters (the end, where errors are).
+const MAX_OUTPUT_CHARS: usize = 2000;
+
+/// Id of the next recorded command.
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Recorded commands, oldest first.
+static ENTRIES: Mutex<VecDeque<AuditEntry>> = Mutex::new(VecDeque::new());
+
+/// How a command ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandStatus {
+    /// Exited successfully.
+    Succeeded,
+    /// Exited with a failure status.
+    Failed,
+    /// Could not be started, timed out or was cancelled.
+    Interrupted,
+}
+
+/// One recorded Pop CLI invocation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AuditEntry {
+    /// Sequence number, starting at 1.
+    pub id: u64,
+    /// Start time, in seconds since the Unix epoch.
+    pub started_at: u64,
+    /// Command line, with secrets masked.
+    pub command: String,
+    /// Wall-clock duration in milliseconds.
+    pub duration_ms: u64,
+    /// How the command ended.
+    pub status: CommandStatus,
+    /// End of the command output, with secrets masked.
+    pub output: String,
+}
+
+/// Record a command that started at `started` and ran for `duration`.
+///
+/// `command` and `output` must already have secrets masked.
+pub fn record(
+    command: String,
+    started: SystemTime,
+    duration: Duration,
+    status: CommandStatus,
+    output: &str,
+) {
+    let entry = AuditEntry {
+        id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        started_at: started
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs()),
+        command,
+        duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+        status,
+        output: tail(output, MAX_OUTPUT_CHARS),
+    };
+    if let Ok(mut entries) = ENTRIES.lock() {
+        if entries.len() == MAX_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+/// Recorded commands, oldest first.
+pub fn entries() -> Vec<AuditEntry> {
+    ENTRIES
+        .lock()
+        .map(|entries| entries.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Last `max` characters of `text`, marked when cut.
+fn tail(text: &str, max: usize) -> String {
+    let text = text.trim();
+    let len = text.chars().count();
+    if len <= max {
+        return text.to_owned();
+    }
+    let kept: String = text.chars().skip(len - max).collect();
+    format!("[... {} characters omitted]\n{}", len - max, kept)
+}
+
+#[cfg(test)]
+#[allow(clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_keeps_masked_command_and_output_tail() {
+        let output = format!("{}Error: Module error", "x".repeat(3000));
+        record(
+            "pop audit-test --suri [REDACTED]".to_owned(),
+            SystemTime::now(),
+            Duration::from_millis(1500),
+            CommandStatus::Failed,
+            &output,
+        );
+        let entries = entries();
+        let Some(entry) = entries
+            .iter()
+            .rev()
+            .find(|e| e.command.starts_with("pop audit-test"))
+        else {
+            panic!("entry not recorded");
+        };
+        assert_eq!(entry.duration_ms, 1500);
+        assert_eq!(entry.status, CommandStatus::Failed);
+        assert!(entry.output.starts_with("[... 1019 characters omitted]"));
+        assert!(entry.output.ends_with("Error: Module error"));
+    }
+
+    #[test]
+    fn tail_is_char_safe() {
+        assert_eq!(tail("héllo", 10), "héllo");
+        assert_eq!(tail("héllo", 4), "[... 1 characters omitted]\néllo");
+    }
+}
@@ -0,0 +1,346 @@
+//! Fake Pop CLI for hermetic tests.
+//!
+//! Replays canned Pop CLI output for each subcommand so the tool surface can be
+//! exercised without installing Pop CLI or compiling contracts. Commands that point
+//! at a missing `--path` (or contract directory) fail the way Pop CLI does, and
+//! commands whose `--path` holds a [`HANG_MARKER`] file print a line and hang, with a
+//! [`HANG_WORKER`] child standing in for the cargo process a stuck build would leave.
+//! `up` answers `--output json` with JSON; other commands reject the flag like an
+//! older Pop CLI.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::ExitCode;
+
+/// File that makes commands run against its directory hang, for timeout tests.
+const HANG_MARKER: &str = ".fake-pop-hang";
+
+/// Argument running fake-pop as the worker of a hung command. Its PID is written to
+/// [`HANG_WORKER_PID`] next to the marker, so tests can check it was killed too.
+const HANG_WORKER: &str = "--fake-pop-hang-worker";
+
+/// File a hung command writes its worker's PID to.
+const HANG_WORKER_PID: &str = ".fake-pop-hang-worker.pid";
+
+const VERSION: &str = "pop-cli 0.0.0-fake";
+
+const HELP: &str = "\
+An all-in-one tool for Polkadot development.
+
+Usage: pop <COMMAND>
+
+Commands:
+  new      Generate a new chain or smart contract
+  build    Build a chain or smart contract
+  call     Call a chain or smart contract
+  up       Launch a local network or deploy a smart contract
+  test     Test a chain or smart contract
+  clean    Remove generated/cached artifacts
+  convert  Convert between different formats
+  help     Print this message or the help of the given subcommand(s)";
+
+const UP_INK_NODE: &str = "\
+┌   Pop CLI : Launch a local Ink! node
+│
+⚙  Local node started successfully:
+│  portal: https://polkadot.js.org/apps/?rpc=ws://localhost:{ink_port}/#/explorer
+│  url: ws://localhost:{ink_port}/
+│  logs: tail -f /tmp/.tmpFakeInkNode
+│
+⚙  Ethereum RPC node started successfully:
+│  url: ws://localhost:{eth_port}
+│  logs: tail -f /tmp/.tmpFakeEthRpc
+│
+└  ✅ Ink! node bootstrapped successfully. Run `kill -9 424242 424243` to terminate it.";
+
+const UP_CONTRACT: &str = "\
+┌   Pop CLI : Deploy a smart contract
+│
+◇  Contract deployed and instantiated:
+│  The contract address is \"0x5801b439a678d9d3a68b8019da6a4abfa507de11\"
+│  The contract code hash is \"0x4f1d9d5cbc4e0b6d0e3b1c5a6f0e9d0a7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a29\"
+│  Extrinsic hash: 0x9a3f5e1c2b7d8e6f4a0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f
+│
+└  🚀 Deployment complete";
+
+const UP_CONTRACT_JSON: &str = r#"{"address":"0x5801b439a678d9d3a68b8019da6a4abfa507de11","code_hash":"0x4f1d9d5cbc4e0b6d0e3b1c5a6f0e9d0a7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a29","extrinsic_hash":"0x9a3f5e1c2b7d8e6f4a0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f"}"#;
+
+const WALLET_PORTAL: &str = "\
+◇  Wallet signing portal started at http://127.0.0.1:9090.
+◒  Waiting for signature... Press Ctrl+C to terminate early.";
+
+const UP_NETWORK: &str = "\
+┌   Pop CLI : Launch a local network
+│
+◇  🚀 Network launched successfully - Ctrl+C to terminate
+│  ⛓️ paseo-local
+│       alice: ws://127.0.0.1:9944
+│  base dir: /tmp/zombie-fake
+│  zombie.json: /tmp/zombie-fake/zombie.json
+│
+└  Network detached.";
+
+const CALL_CONTRACT: &str = "\
+┌   Pop CLI : Call a contract
+│
+⚙  Result: Ok(false)
+│
+└  Call completed successfully!";
+
+const CALL_CONTRACT_DRY_RUN: &str = "\
+┌   Pop CLI : Call a contract
+│
+◇  Gas limit: Weight { ref_time: 1112520, proof_size: 16689 }
+│  Storage deposit: Charge(4000)
+⚙  Result: Ok(false)
+│
+└  Call completed successfully!";
+
+const CALL_CHAIN_METADATA: &str = "\
+┌   Pop CLI : Call a chain
+│
+◇  Available pallets:
+│  System, Balances, Timestamp, Sudo, Revive
+│
+└  Metadata fetched.";
+
+const CALL_CHAIN: &str = "\
+┌   Pop CLI : Call a chain
+│
+◇  Storage value: AccountInfo { nonce: 0, consumers: 0, providers: 1, sufficients: 0 }
+│
+└  Call complete.";
+
+const CONVERT_ADDRESS: &str = "\
+┌   Pop CLI : Convert an address
+│
+└  Converted address: 0x9621dde636de098b43efb0fa9b61facfe328f99d";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    if args == [HANG_WORKER] {
+        sleep_forever();
+    }
+
+    match respond(&args) {
+        Ok(output) if args.contains(&"--use-wallet") => {
+            let _ = writeln!(std::io::stdout(), "{}\n{}", WALLET_PORTAL, output);
+            ExitCode::SUCCESS
+        }
+        Ok(output) => {
+            let _ = writeln!(std::io::stdout(), "{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            let _ = writeln!(std::io::stderr(), "{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Produce canned output for the given argv, or an error message.
+fn respond(args: &[&str]) -> Result<String, String> {
+    if args.contains(&"--help") {
+        return Ok(HELP.to_owned());
+    }
+    if let Some(path) = flag_value(args, "--path") {
+        ensure_exists(path)?;
+        if Path::new(path).join(HANG_MARKER).exists() {
+            hang(Path::new(path));
+        }
+    }
+
+    if let Some(format) = flag_value(args, "--output") {
+        return respond_json(args, format);
+    }
+
+    match args {
+        [] => Ok(HELP.to_owned()),
+        ["--version", ..] => Ok(VERSION.to_owned()),
+        ["new", kind, name, ..] => {
+            scaffold(kind, name)?;
+            Ok(format!("└  Generated {} \"{}\" successfully.", kind, name))
+        }
+        ["build", ..] => {
+            if let Some(path) = flag_value(args, "--path") {
+                let profile =
+                    flag_value(args, "--profile").unwrap_or(if args.contains(&"--release") {
+                        "release"
+                    } else {
+                        "debug"
+                    });
+                write_artifacts(Path::new(path), profile)?;
+            }
+            Ok("└  Build completed successfully!".to_owned())
+        }
+        ["test", ..] => Ok("test result: ok. 1 passed; 0 failed".to_owned()),
+        ["up", "ink-node", ..] => Ok(UP_INK_NODE
+            .replace("{ink_port}", flag_value(args, "-i").unwrap_or("9944"))
+            .replace("{eth_port}", flag_value(args, "-e").unwrap_or("8545"))),
+        ["up", "network", path, ..] => {
+            ensure_exists(path)?;
+            Ok(UP_NETWORK.to_owned())
+        }
+        ["up", "paseo" | "kusama" | "polkadot" | "westend", ..] => Ok(UP_NETWORK.to_owned()),
+        ["up", path, ..] => {
+            ensure_exists(path)?;
+            Ok(UP_CONTRACT.to_owned())
+        }
+        ["call", "contract", ..] if args.contains(&"--execute") => Ok(CALL_CONTRACT.to_owned()),
+        ["call", "contract", ..] => Ok(CALL_CONTRACT_DRY_RUN.to_owned()),
+        ["call", "chain", ..] if args.contains(&"--metadata") => Ok(CALL_CHAIN_METADATA.to_owned()),
+        ["call", "chain", ..] => Ok(CALL_CHAIN.to_owned()),
+        ["clean", "node", ..] => Ok("└  Nodes terminated.".to_owned()),
+        ["clean", "network", ..] => Ok("└  Network stopped.".to_owned()),
+        ["convert", "address", _, ..] => Ok(CONVERT_ADDRESS.to_owned()),
+        [command, ..] => Err(format!(
+            "error: unrecognized subcommand '{}'\n\nUsage: pop <COMMAND>",
+            command
+        )),
+    }
+}
+
+/// Answer `--output <format>`, which only `up` understands.
+fn respond_json(args: &[&str], format: &str) -> Result<String, String> {
+    match args {
+        ["up", "ink-node", ..] if format == "json" => Ok(format!(
+            r#"{{"url":"ws://localhost:{}","eth_rpc_url":"ws://localhost:{}","pids":[424242,424243]}}"#,
+            flag_value(args, "-i").unwrap_or("9944"),
+            flag_value(args, "-e").unwrap_or("8545")
+        )),
+        ["up", path, ..] if format == "json" => {
+            ensure_exists(path)?;
+            Ok(UP_CONTRACT_JSON.to_owned())
+        }
+        _ => Err("error: unexpected argument '--output' found\n\nUsage: pop <COMMAND>".to_owned()),
+    }
+}
+
+/// Skeleton of the flipper contract the standard template produces.
+const FLIPPER: &str = "#[ink::contract]\nmod flipper {\n    #[ink(storage)]\n    pub struct Flipper {\n        value: bool,\n    }\n}\n";
+
+/// Lay out the files real templates produce, so fixture checks find them.
+fn scaffold(kind: &str, name: &str) -> Result<(), String> {
+    let root = Path::new(name);
+    let contract = format!(
+        "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nauthors = [\"[your_name] <[your_email]>\"]\nedition = \"2021\"\n\n[dependencies]\nink = {{ version = \"6.0.0-alpha\", default-features = false }}\n",
+        name
+    );
+    let files: Vec<(&str, String)> = match kind {
+        "contract" => vec![("Cargo.toml", contract), ("lib.rs", FLIPPER.to_owned())],
+        "chain" => vec![
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"runtime\"]\n\n[workspace.package]\nauthors = [\"Anonymous\"]\nlicense = \"Unlicense\"\nedition = \"2021\"\n".to_owned(),
+            ),
+            (
+                "runtime/Cargo.toml",
+                "[package]\nname = \"parachain-template-runtime\"\nauthors.workspace = true\nlicense.workspace = true\nedition.workspace = true\n".to_owned(),
+            ),
+        ],
+        _ => Vec::new(),
+    };
+    for (file, contents) in files {
+        let path = root.join(file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        fs::write(&path, contents).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Create the artifact directory `pop build` leaves behind, under `CARGO_TARGET_DIR`
+/// when set. Contracts with a `Cargo.toml` also get their bundle, metadata and code,
+/// and chains with a `runtime` package its wasm.
+fn write_artifacts(project: &Path, profile: &str) -> Result<(), String> {
+    let target = std::env::var_os("CARGO_TARGET_DIR")
+        .map_or_else(|| project.join("target"), std::path::PathBuf::from);
+    let contract = project.join("lib.rs").exists();
+    let dir = target.join(if contract { "ink" } else { profile });
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    if let Some(runtime) = package_name(&project.join("runtime")).filter(|_| !contract) {
+        let wbuild = dir.join("wbuild").join(&runtime);
+        fs::create_dir_all(&wbuild).map_err(|e| e.to_string())?;
+        let wasm = format!("{}.compact.compressed.wasm", runtime.replace('-', "_"));
+        return fs::write(wbuild.join(wasm), b"\0asm").map_err(|e| e.to_string());
+    }
+    let name = package_name(project).map(|name| name.replace('-', "_"));
+    let Some(name) = name.filter(|_| contract) else {
+        return Ok(());
+    };
+    let metadata = format!(
+        "{{\"source\": {{\"hash\": \"0x{}\"}}, \"contract\": {{\"name\": \"{}\"}}, {}}}",
+        "ab".repeat(32),
+        name,
+        FLIPPER_SPEC
+    );
+    for (extension, contents) in [
+        ("contract", metadata.clone()),
+        ("json", metadata),
+        ("polkavm", "PVM".to_owned()),
+    ] {
+        fs::write(dir.join(format!("{}.{}", name, extension)), contents)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Messages and types of the flipper metadata: `get` returns `Result<bool, LangError>`.
+const FLIPPER_SPEC: &str = r#""spec": {"constructors": [{"label": "new", "selector": "0x9bae9d5e", "args": [{"label": "init_value", "type": {"displayName": ["bool"], "type": 0}}]}], "messages": [{"label": "flip", "selector": "0x633aa551", "args": [], "returnType": {"type": 2}}, {"label": "get", "selector": "0x2f865bd9", "args": [], "returnType": {"type": 1}}]}, "types": [{"id": 0, "type": {"def": {"primitive": "bool"}}}, {"id": 1, "type": {"path": ["Result"], "def": {"variant": {"variants": [{"name": "Ok", "index": 0, "fields": [{"type": 0}]}, {"name": "Err", "index": 1, "fields": [{"type": 3}]}]}}}}, {"id": 2, "type": {"path": ["Result"], "def": {"variant": {"variants": [{"name": "Ok", "index": 0, "fields": [{"type": 4}]}, {"name": "Err", "index": 1, "fields": [{"type": 3}]}]}}}}, {"id": 3, "type": {"path": ["ink_primitives", "LangError"], "def": {"variant": {"variants": [{"name": "CouldNotReadInput", "index": 1}]}}}}, {"id": 4, "type": {"def": {"tuple": []}}}]"#;
+
+/// Package name in the `Cargo.toml` under `dir`, if any.
+fn package_name(dir: &Path) -> Option<String> {
+    fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("name = "))
+        .map(|name| name.trim_matches('"').to_owned())
+}
+
+/// Print progress and never finish, like a stuck build, with a worker child running
+/// whose PID goes to [`HANG_WORKER_PID`] in `dir`.
+fn hang(dir: &Path) -> ! {
+    let worker = std::env::current_exe().and_then(|exe| {
+        std::process::Command::new(exe)
+            .arg(HANG_WORKER)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+    });
+    if let Ok(worker) = worker {
+        let _ = fs::write(dir.join(HANG_WORKER_PID), worker.id().to_string());
+    }
+    let mut stdout = std::io::stdout();
+    let _ = writeln!(stdout, "   Compiling hang v0.1.0");
+    let _ = stdout.flush();
+    sleep_forever();
+}
+
+/// Block until killed.
+fn sleep_forever() -> ! {
+    loop {
+        std::thread::sleep(std::time::Duration::from_secs(60));
+    }
+}
+
+/// Value following `flag` in argv, if any.
+fn flag_value<'a>(args: &[&'a str], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| *a == flag)
+        .and_then(|i| args.get(i + 1).copied())
+}
+
+fn ensure_exists(path: &str) -> Result<(), String> {
+    if Path::new(path).exists() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Error: No such file or directory (os error 2): {}",
+            path
+        ))
+    }
+}
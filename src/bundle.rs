@@ -0,0 +1,253 @@
+//! Debug bundles: files gathered for a bug report, written as one zip.
+//!
+//! The zip is uncompressed ("stored"): bundles are small text files, and writing the
+//! format by hand keeps a compression crate out of the server. Every bundle starts
+//! with `manifest.json`, listing the files it holds and the sections that could not
+//! be gathered, so a maintainer sees at a glance what is (and is not) there.
+
+use serde::Serialize;
+
+/// Name of the manifest inside the zip.
+pub const MANIFEST: &str = "manifest.json";
+
+/// A file of the bundle, as listed in the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BundleFile {
+    /// Path inside the zip.
+    pub name: String,
+    /// What the file holds.
+    pub description: String,
+    /// Size in bytes.
+    pub bytes: usize,
+}
+
+/// A section that could not be gathered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BundleError {
+    /// What was being gathered.
+    pub section: String,
+    /// Why it failed.
+    pub error: String,
+}
+
+#[derive(Serialize)]
+struct Manifest<'a> {
+    created_at: u64,
+    pop_mcp_version: &'static str,
+    url: Option<&'a str>,
+    files: &'a [BundleFile],
+    errors: &'a [BundleError],
+}
+
+/// Files and errors collected for one bundle.
+#[derive(Debug, Default)]
+pub struct Bundle {
+    files: Vec<BundleFile>,
+    contents: Vec<Vec<u8>>,
+    errors: Vec<BundleError>,
+}
+
+impl Bundle {
+    /// Create an empty bundle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add file `name` holding `contents`.
+    pub fn add(&mut self, name: &str, description: &str, contents: impl Into<Vec<u8>>) {
+        let contents = contents.into();
+        self.files.push(BundleFile {
+            name: name.to_owned(),
+            description: description.to_owned(),
+            bytes: contents.len(),
+        });
+        self.contents.push(contents);
+    }
+
+    /// Record that `section` could not be gathered.
+    pub fn fail(&mut self, section: &str, error: impl ToString) {
+        self.errors.push(BundleError {
+            section: section.to_owned(),
+            error: error.to_string(),
+        });
+    }
+
+    /// Files added so far.
+    pub fn files(&self) -> &[BundleFile] {
+        &self.files
+    }
+
+    /// Sections that failed so far.
+    pub fn errors(&self) -> &[BundleError] {
+        &self.errors
+    }
+
+    /// The zip: the manifest, then the files in the order they were added.
+    pub fn to_zip(&self, created_at: u64, url: Option<&str>) -> Result<Vec<u8>, String> {
+        let manifest = serde_json::to_vec_pretty(&Manifest {
+            created_at,
+            pop_mcp_version: env!("CARGO_PKG_VERSION"),
+            url,
+            files: &self.files,
+            errors: &self.errors,
+        })
+        .map_err(|e| e.to_string())?;
+        let mut entries = vec![(MANIFEST, manifest.as_slice())];
+        entries.extend(
+            self.files
+                .iter()
+                .map(|file| file.name.as_str())
+                .zip(self.contents.iter().map(Vec::as_slice)),
+        );
+        write_zip(&entries, dos_datetime(created_at))
+    }
+}
+
+/// Stored zip archive of `entries` (name, contents), all dated `(time, date)`.
+fn write_zip(entries: &[(&str, &[u8])], (time, date): (u16, u16)) -> Result<Vec<u8>, String> {
+    /// Version 2.0: the oldest that reads stored entries.
+    const VERSION: u16 = 20;
+    /// General purpose flag: names are UTF-8.
+    const UTF8_NAMES: u16 = 1 << 11;
+
+    let too_large = |what: &str| format!("{} is too large for a zip", what);
+    let mut zip = Vec::new();
+    let mut central = Vec::new();
+    for (name, contents) in entries {
+        let size = u32::try_from(contents.len()).map_err(|_| too_large(name))?;
+        let name_len = u16::try_from(name.len()).map_err(|_| too_large(name))?;
+        let offset = u32::try_from(zip.len()).map_err(|_| too_large("The bundle"))?;
+        let crc = crc32(contents);
+
+        zip.extend(0x0403_4b50_u32.to_le_bytes());
+        for field in [VERSION, UTF8_NAMES, 0, time, date] {
+            zip.extend(field.to_le_bytes());
+        }
+        for field in [crc, size, size] {
+            zip.extend(field.to_le_bytes());
+        }
+        zip.extend(name_len.to_le_bytes());
+        zip.extend(0_u16.to_le_bytes());
+        zip.extend(name.as_bytes());
+        zip.extend(*contents);
+
+        central.extend(0x0201_4b50_u32.to_le_bytes());
+        for field in [VERSION, VERSION, UTF8_NAMES, 0, time, date] {
+            central.extend(field.to_le_bytes());
+        }
+        for field in [crc, size, size] {
+            central.extend(field.to_le_bytes());
+        }
+        // Name length, extra, comment, disk, internal attributes.
+        for field in [name_len, 0, 0, 0, 0] {
+            central.extend(field.to_le_bytes());
+        }
+        // External attributes, then the local header offset.
+        for field in [0, offset] {
+            central.extend(field.to_le_bytes());
+        }
+        central.extend(name.as_bytes());
+    }
+    let count = u16::try_from(entries.len()).map_err(|_| too_large("The file list"))?;
+    let central_size = u32::try_from(central.len()).map_err(|_| too_large("The file list"))?;
+    let central_offset = u32::try_from(zip.len()).map_err(|_| too_large("The bundle"))?;
+    zip.extend(central);
+    zip.extend(0x0605_4b50_u32.to_le_bytes());
+    for field in [0, 0, count, count] {
+        zip.extend(field.to_le_bytes());
+    }
+    for field in [central_size, central_offset] {
+        zip.extend(field.to_le_bytes());
+    }
+    zip.extend(0_u16.to_le_bytes());
+    Ok(zip)
+}
+
+/// CRC-32 (IEEE) lookup table.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0_u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                0xEDB8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// CRC-32 of `data`, as zip stores it.
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(u32::MAX, |crc, byte| {
+        CRC_TABLE[usize::from((crc as u8) ^ byte)] ^ (crc >> 8)
+    })
+}
+
+/// MS-DOS `(time, date)` of `secs` since the Unix epoch, in UTC.
+fn dos_datetime(secs: u64) -> (u16, u16) {
+    let days = i64::try_from(secs / 86_400).unwrap_or(0);
+    let of_day = secs % 86_400;
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    // DOS dates start in 1980.
+    let years = u16::try_from((year - 1980).clamp(0, 127)).unwrap_or(0);
+    let date = (years << 9) | ((month as u16) << 5) | day as u16;
+    let time = ((of_day / 3600) << 11) | ((of_day % 3600 / 60) << 5) | ((of_day % 60) / 2);
+    (u16::try_from(time).unwrap_or(0), date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc_and_dates_match_the_zip_format() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+        // 2024-02-29 13:45:30 UTC.
+        assert_eq!(
+            dos_datetime(1_709_214_330),
+            ((13 << 11) | (45 << 5) | 15, (44 << 9) | (2 << 5) | 29)
+        );
+    }
+
+    #[test]
+    fn bundles_start_with_their_manifest() -> anyhow::Result<()> {
+        let mut bundle = Bundle::new();
+        bundle.add("versions.txt", "Versions", "pop 0.9.0\n");
+        bundle.fail("chain-spec.json", "sync_state_genSyncSpec failed");
+        let zip = bundle
+            .to_zip(1_709_214_330, Some("ws://localhost:9944"))
+            .map_err(anyhow::Error::msg)?;
+
+        assert_eq!(zip[..4], 0x0403_4b50_u32.to_le_bytes());
+        let name_len = usize::from(u16::from_le_bytes([zip[26], zip[27]]));
+        assert_eq!(&zip[30..30 + name_len], MANIFEST.as_bytes());
+        let size = u32::from_le_bytes([zip[18], zip[19], zip[20], zip[21]]) as usize;
+        let manifest: serde_json::Value =
+            serde_json::from_slice(&zip[30 + name_len..30 + name_len + size])?;
+        assert_eq!(manifest["files"][0]["name"], "versions.txt");
+        assert_eq!(manifest["errors"][0]["section"], "chain-spec.json");
+
+        let end = &zip[zip.len() - 22..];
+        assert_eq!(end[..4], 0x0605_4b50_u32.to_le_bytes());
+        assert_eq!(u16::from_le_bytes([end[10], end[11]]), 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+//! Processes launched by the server.
+//!
+//! Nodes started with `up_ink_node` and networks started with `up_network` are tracked
+//! until a tool stops them, and whatever still runs when the server shuts down is
+//! stopped with it. Launches made with `detach` are not tracked and keep running.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::executor::CommandExecutor;
+use crate::tools::{clean_network, stop_pids, CleanNetworkParams};
+
+/// Longest time shutdown waits for Pop CLI to stop everything.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Default)]
+struct Tracked {
+    pids: BTreeSet<u32>,
+    networks: BTreeSet<String>,
+}
+
+/// Node pids and network `zombie.json` paths to stop on shutdown.
+#[derive(Debug)]
+pub struct Children<E: CommandExecutor> {
+    executor: E,
+    inner: Mutex<Tracked>,
+}
+
+impl<E: CommandExecutor> Children<E> {
+    /// Track nothing yet, stopping children through `executor`.
+    pub fn new(executor: E) -> Self {
+        Self {
+            executor,
+            inner: Mutex::new(Tracked::default()),
+        }
+    }
+
+    /// Stop the processes `pids` on shutdown.
+    pub fn track_pids(&self, pids: &[u32]) {
+        if let Ok(mut tracked) = self.inner.lock() {
+            tracked.pids.extend(pids);
+        }
+    }
+
+    /// Stop the network described by `zombie_json` on shutdown.
+    pub fn track_network(&self, zombie_json: &str) {
+        if let Ok(mut tracked) = self.inner.lock() {
+            tracked.networks.insert(zombie_json.to_owned());
+        }
+    }
+
+    /// Stop tracking `pids`, which were stopped already.
+    pub fn forget_pids(&self, pids: &[u32]) {
+        if let Ok(mut tracked) = self.inner.lock() {
+            for pid in pids {
+                tracked.pids.remove(pid);
+            }
+        }
+    }
+
+    /// Stop tracking the network at `path` (its `zombie.json` or base dir), or every
+    /// network when `path` is `None`.
+    pub fn forget_network(&self, path: Option<&str>) {
+        let Ok(mut tracked) = self.inner.lock() else {
+            return;
+        };
+        let Some(path) = path else {
+            tracked.networks.clear();
+            return;
+        };
+        let path = Path::new(path.trim());
+        tracked.networks.retain(|network| {
+            let network = Path::new(network);
+            network != path && network.parent() != Some(path)
+        });
+    }
+
+    /// Tracked node pids.
+    pub fn pids(&self) -> Vec<u32> {
+        self.inner
+            .lock()
+            .map(|tracked| tracked.pids.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Tracked network `zombie.json` paths.
+    pub fn networks(&self) -> Vec<String> {
+        self.inner
+            .lock()
+            .map(|tracked| tracked.networks.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Stop every tracked node and network. Failures are ignored: the processes may
+    /// have exited on their own.
+    pub async fn shutdown(&self) {
+        let tracked = match self.inner.lock() {
+            Ok(mut tracked) => std::mem::take(&mut *tracked),
+            Err(_) => return,
+        };
+        let stop = async {
+            if !tracked.pids.is_empty() {
+                let pids: Vec<u32> = tracked.pids.into_iter().collect();
+                let _ = stop_pids(&self.executor, &pids).await;
+            }
+            for network in tracked.networks {
+                let params = CleanNetworkParams {
+                    path: Some(network),
+                    all: None,
+                    keep_state: None,
+                };
+                let _ = clean_network(&self.executor, params).await;
+            }
+        };
+        let _ = tokio::time::timeout(SHUTDOWN_TIMEOUT, stop).await;
+    }
+}
+
+impl<E: CommandExecutor> Drop for Children<E> {
+    fn drop(&mut self) {
+        if self.pids().is_empty() && self.networks().is_empty() {
+            return;
+        }
+        // Drop may run on a runtime thread, which cannot block on the shutdown itself.
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build();
+                if let Ok(runtime) = runtime {
+                    runtime.block_on(self.shutdown());
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutor;
+
+    #[tokio::test]
+    async fn shutdown_stops_what_is_still_tracked() {
+        let executor = MockExecutor::new()
+            .on(&["clean", "node"], "cleaned")
+            .on(&["clean", "network"], "cleaned");
+        let children = Children::new(executor.clone());
+        children.track_pids(&[10, 11]);
+        children.track_pids(&[20]);
+        children.forget_pids(&[11]);
+        children.track_network("/tmp/zombie-a/zombie.json");
+        children.track_network("/tmp/zombie-b/zombie.json");
+        children.forget_network(Some("/tmp/zombie-b/"));
+
+        children.shutdown().await;
+        let calls: Vec<Vec<String>> = executor.calls().into_iter().map(|c| c.args).collect();
+        assert_eq!(
+            calls,
+            vec![
+                vec!["clean", "node", "--pid", "10", "20"],
+                vec!["clean", "network", "/tmp/zombie-a/zombie.json"],
+            ]
+        );
+        assert!(children.pids().is_empty() && children.networks().is_empty());
+    }
+
+    #[test]
+    fn dropping_stops_tracked_children() {
+        let executor = MockExecutor::new().on(&["clean", "node"], "cleaned");
+        let children = Children::new(executor.clone());
+        children.track_pids(&[42]);
+        drop(children);
+        assert_eq!(executor.calls().len(), 1);
+    }
+}
@@ -0,0 +1,197 @@
+//! Persistent registry of deployed contracts.
+//!
+//! Every successful `deploy_contract` (with `execute`) is appended to a JSON state file
+//! so addresses survive the conversation and server restarts. The registry is listed
+//! by `list_deployments`, pruned by `forget_deployment` and published as the
+//! [`DEPLOYMENTS_URI`] resource.
+//!
+//! The registry is shared: every MCP session of a server (over HTTP and SSE too) lists
+//! and forgets the same deployments, and so does the next server run.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PopMcpError, PopMcpResult};
+
+/// URI of the deployment registry resource.
+pub const DEPLOYMENTS_URI: &str = "deployments://";
+
+/// Env var overriding the registry file.
+pub const DEPLOYMENTS_FILE_ENV: &str = "POP_MCP_DEPLOYMENTS_FILE";
+
+/// A contract deployed through the server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Deployment {
+    /// Contract directory it was built from.
+    pub path: String,
+    /// WebSocket URL of the network it lives on.
+    pub url: String,
+    /// Contract address.
+    pub address: String,
+    /// Code hash, when reported.
+    pub code_hash: Option<String>,
+    /// Constructor it was instantiated with.
+    pub constructor: String,
+    /// Deployment time, in seconds since the Unix epoch.
+    pub deployed_at: u64,
+}
+
+/// The deployment registry in a state file.
+///
+/// Clones (one per MCP session) share the update lock, so that concurrent sessions
+/// don't drop each other's records between reading and rewriting the file.
+#[derive(Debug, Clone)]
+pub struct Registry {
+    file: PathBuf,
+    updates: Arc<Mutex<()>>,
+}
+
+impl Registry {
+    /// Registry kept in `file`.
+    pub fn new(file: impl Into<PathBuf>) -> Self {
+        Self {
+            file: file.into(),
+            updates: Arc::default(),
+        }
+    }
+
+    /// Deployments recorded in the registry, oldest first.
+    pub fn load(&self) -> PopMcpResult<Vec<Deployment>> {
+        load(&self.file)
+    }
+
+    /// Append `deployment` to the registry.
+    pub fn record(&self, deployment: Deployment) -> PopMcpResult<()> {
+        let _update = self.updates.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut deployments = load(&self.file)?;
+        deployments.push(deployment);
+        store(&self.file, &deployments)
+    }
+
+    /// Remove the deployments of `address` (on `url` only, when given), returning them.
+    pub fn forget(&self, address: &str, url: Option<&str>) -> PopMcpResult<Vec<Deployment>> {
+        let _update = self.updates.lock().unwrap_or_else(PoisonError::into_inner);
+        let (forgotten, kept): (Vec<Deployment>, Vec<Deployment>) =
+            load(&self.file)?.into_iter().partition(|deployment| {
+                deployment.address.eq_ignore_ascii_case(address)
+                    && url.is_none_or(|url| same_url(&deployment.url, url))
+            });
+        if !forgotten.is_empty() {
+            store(&self.file, &kept)?;
+        }
+        Ok(forgotten)
+    }
+}
+
+/// Registry file: `$POP_MCP_DEPLOYMENTS_FILE`, else `~/.config/pop-mcp/deployments.json`.
+pub fn deployments_file() -> PathBuf {
+    if let Ok(file) = std::env::var(DEPLOYMENTS_FILE_ENV) {
+        return PathBuf::from(file);
+    }
+    std::env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::temp_dir())
+        .join(".config/pop-mcp/deployments.json")
+}
+
+/// Deployments recorded in `file`, oldest first. A missing file holds none.
+fn load(file: &Path) -> PopMcpResult<Vec<Deployment>> {
+    let json = match std::fs::read_to_string(file) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(PopMcpError::Internal(format!(
+                "Failed to read {}: {}",
+                file.display(),
+                e
+            )))
+        }
+    };
+    serde_json::from_str(&json).map_err(|e| {
+        PopMcpError::Internal(format!(
+            "Deployment registry {} is corrupt: {}",
+            file.display(),
+            e
+        ))
+    })
+}
+
+/// Replace the contents of `file` with `deployments`.
+fn store(file: &Path, deployments: &[Deployment]) -> PopMcpResult<()> {
+    if let Some(dir) = file.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| {
+            PopMcpError::Internal(format!("Failed to create {}: {}", dir.display(), e))
+        })?;
+    }
+    let json = serde_json::to_string_pretty(deployments)
+        .map_err(|e| PopMcpError::Internal(e.to_string()))?;
+    // Write a sibling first so a crash never leaves a half-written registry.
+    let partial = file.with_extension("json.partial");
+    std::fs::write(&partial, json)
+        .and_then(|()| std::fs::rename(&partial, file))
+        .map_err(|e| PopMcpError::Internal(format!("Failed to write {}: {}", file.display(), e)))
+}
+
+/// Whether `a` and `b` name the same endpoint, ignoring a trailing slash.
+pub fn same_url(a: &str, b: &str) -> bool {
+    a.trim_end_matches('/') == b.trim_end_matches('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployment(address: &str, url: &str) -> Deployment {
+        Deployment {
+            path: "./flipper".to_owned(),
+            url: url.to_owned(),
+            address: address.to_owned(),
+            code_hash: None,
+            constructor: "new".to_owned(),
+            deployed_at: 1,
+        }
+    }
+
+    #[test]
+    fn deployments_persist_until_forgotten() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let registry = Registry::new(dir.path().join("state/deployments.json"));
+        assert!(registry.load()?.is_empty());
+
+        registry.record(deployment("0xAB", "ws://localhost:9944"))?;
+        registry.record(deployment("0xab", "ws://localhost:9955"))?;
+        registry.record(deployment("0xcd", "ws://localhost:9944"))?;
+        assert_eq!(registry.load()?.len(), 3);
+
+        let forgotten = registry.forget("0xab", Some("ws://localhost:9944/"))?;
+        assert_eq!(forgotten, vec![deployment("0xAB", "ws://localhost:9944")]);
+        let left: Vec<String> = registry.load()?.into_iter().map(|d| d.address).collect();
+        assert_eq!(left, vec!["0xab", "0xcd"]);
+        assert!(registry.forget("0xef", None)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn sessions_and_restarts_share_the_registry() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("deployments.json");
+        let server = Registry::new(&file);
+        let session = server.clone();
+
+        session.record(deployment("0xaa", "ws://localhost:9944"))?;
+        assert_eq!(
+            server.load()?,
+            vec![deployment("0xaa", "ws://localhost:9944")]
+        );
+
+        // The next server run lists and forgets it too.
+        let restarted = Registry::new(&file);
+        assert_eq!(restarted.load()?.len(), 1);
+        assert_eq!(restarted.forget("0xaa", None)?.len(), 1);
+        assert!(session.load()?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,417 @@
+//! Dev loops: rebuild and redeploy a contract whenever its sources change.
+//!
+//! Each loop polls the contract directory (ignoring `target/` and hidden entries) and,
+//! after a change, builds the contract and deploys a fresh instance to its node. The
+//! newest address is recorded under the loop's alias in the contract address book,
+//! published as the [`CONTRACTS_URI`] resource, and every outcome is reported as a
+//! [`DevLoopEvent`].
+
+use std::collections::BTreeMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rmcp::model::{
+    LoggingLevel, LoggingMessageNotificationParam, ResourceUpdatedNotificationParam,
+};
+use rmcp::{Peer, RoleServer};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::executor::CommandExecutor;
+use crate::logging;
+use crate::outputs::DeployOutput;
+use crate::tools::common::extract_text;
+use crate::tools::{build_contract, deploy_contract, BuildContractParams, DeployContractParams};
+
+/// URI of the contract address book resource.
+pub const CONTRACTS_URI: &str = "pop://contracts";
+
+/// Longest error excerpt carried by a failure event, in characters.
+const MAX_ERROR_LEN: usize = 600;
+
+/// The latest deployment recorded under an alias.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ContractAlias {
+    /// Alias of the contract.
+    pub alias: String,
+    /// Address of the latest instance.
+    pub address: String,
+    /// Code hash of the latest instance, when reported.
+    pub code_hash: Option<String>,
+    /// Contract directory.
+    pub path: String,
+    /// WebSocket URL of the node it is deployed on.
+    pub url: String,
+    /// Deployment time, in seconds since the Unix epoch.
+    pub deployed_at: u64,
+}
+
+/// Contracts deployed by the dev loops of one session, by alias.
+type ContractBook = Arc<Mutex<BTreeMap<String, ContractAlias>>>;
+
+/// What a dev loop builds and deploys.
+#[derive(Debug, Clone)]
+pub struct DevLoopConfig {
+    /// Alias the latest address is recorded under.
+    pub alias: String,
+    /// Contract directory.
+    pub path: PathBuf,
+    /// WebSocket URL of the node to deploy to.
+    pub url: String,
+    /// Constructor to instantiate with.
+    pub constructor: Option<String>,
+    /// Constructor arguments, space-separated.
+    pub args: Option<String>,
+    /// Delay between checks for changes.
+    pub interval: Duration,
+}
+
+/// Outcome of one rebuild.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum DevLoopEvent {
+    /// The contract was rebuilt and deployed.
+    Deployed {
+        /// Alias of the loop.
+        alias: String,
+        /// Address of the new instance.
+        address: String,
+        /// Code hash of the new instance, when reported.
+        code_hash: Option<String>,
+    },
+    /// The build or deployment failed; the previous address stays recorded.
+    Failed {
+        /// Alias of the loop.
+        alias: String,
+        /// `build` or `deploy`.
+        stage: String,
+        /// End of the error output.
+        error: String,
+    },
+}
+
+/// Progress of a running loop.
+#[derive(Debug, Clone, Default)]
+pub struct DevLoopStatus {
+    /// Rebuilds attempted.
+    pub rebuilds: u32,
+    /// Outcome of the latest rebuild.
+    pub last: Option<DevLoopEvent>,
+}
+
+struct DevLoop {
+    config: DevLoopConfig,
+    status: Arc<Mutex<DevLoopStatus>>,
+    cancel: CancellationToken,
+}
+
+/// Running dev loops by alias, with the addresses they deployed.
+///
+/// Each MCP session has its own loops, which stop when the session ends.
+#[derive(Default)]
+pub struct DevLoops {
+    inner: Mutex<BTreeMap<String, DevLoop>>,
+    book: ContractBook,
+}
+
+impl DevLoops {
+    /// Create an empty set of loops.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a loop for `config`, replacing any loop with the same alias. `notify` is
+    /// called with the outcome of every rebuild, the first one included.
+    pub fn start<E, N>(&self, executor: E, config: DevLoopConfig, notify: N)
+    where
+        E: CommandExecutor,
+        N: Fn(&DevLoopEvent) + Send + Sync + 'static,
+    {
+        let status = Arc::new(Mutex::new(DevLoopStatus::default()));
+        let cancel = CancellationToken::new();
+        tokio::spawn(run(
+            executor,
+            config.clone(),
+            Arc::clone(&self.book),
+            Arc::clone(&status),
+            cancel.clone(),
+            notify,
+        ));
+        if let Ok(mut loops) = self.inner.lock() {
+            let replaced = loops.insert(
+                config.alias.clone(),
+                DevLoop {
+                    config,
+                    status,
+                    cancel,
+                },
+            );
+            if let Some(replaced) = replaced {
+                replaced.cancel.cancel();
+            }
+        }
+    }
+
+    /// Stop the loop for `alias`, returning its config.
+    pub fn stop(&self, alias: &str) -> Option<DevLoopConfig> {
+        let dev_loop = self.inner.lock().ok()?.remove(alias)?;
+        dev_loop.cancel.cancel();
+        Some(dev_loop.config)
+    }
+
+    /// Contracts deployed by these loops, by alias.
+    pub fn contract_book(&self) -> Vec<ContractAlias> {
+        self.book
+            .lock()
+            .map(|book| book.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Running loops with their progress, by alias.
+    pub fn list(&self) -> Vec<(DevLoopConfig, DevLoopStatus)> {
+        self.inner
+            .lock()
+            .map(|loops| {
+                loops
+                    .values()
+                    .map(|dev_loop| {
+                        let status = dev_loop
+                            .status
+                            .lock()
+                            .map(|status| status.clone())
+                            .unwrap_or_default();
+                        (dev_loop.config.clone(), status)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for DevLoops {
+    fn drop(&mut self) {
+        if let Ok(loops) = self.inner.lock() {
+            for dev_loop in loops.values() {
+                dev_loop.cancel.cancel();
+            }
+        }
+    }
+}
+
+/// Send each event to `peer` as a `dev_loop` log message, and announce the updated
+/// contract address book after a deployment.
+pub fn notifier(peer: Peer<RoleServer>) -> impl Fn(&DevLoopEvent) + Send + Sync + 'static {
+    let messages = logging::forward(peer.clone());
+    move |event: &DevLoopEvent| {
+        let deployed = matches!(event, DevLoopEvent::Deployed { .. });
+        let message = LoggingMessageNotificationParam {
+            level: if deployed {
+                LoggingLevel::Info
+            } else {
+                LoggingLevel::Error
+            },
+            logger: Some("dev_loop".to_owned()),
+            data: serde_json::to_value(event).unwrap_or_default(),
+        };
+        // A client that went away just misses the update; the loop runs on.
+        messages.send(message);
+        if deployed {
+            let peer = peer.clone();
+            tokio::spawn(async move {
+                let _ = peer
+                    .notify_resource_updated(ResourceUpdatedNotificationParam {
+                        uri: CONTRACTS_URI.to_owned(),
+                    })
+                    .await;
+            });
+        }
+    }
+}
+
+/// Fingerprint of the sources under `dir`: names, sizes and modification times of
+/// every file outside `target/` and hidden entries.
+pub fn source_fingerprint(dir: &Path) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut entries: Vec<_> = entries.filter_map(Result::ok).collect();
+        entries.sort_by_key(std::fs::DirEntry::file_name);
+        for entry in entries {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with('.') || name == "target" {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(entry.path());
+                continue;
+            }
+            entry.path().hash(&mut hasher);
+            metadata.len().hash(&mut hasher);
+            metadata.modified().ok().hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// Rebuild and redeploy whenever the sources change, until `cancel` fires.
+async fn run<E, N>(
+    executor: E,
+    config: DevLoopConfig,
+    book: ContractBook,
+    status: Arc<Mutex<DevLoopStatus>>,
+    cancel: CancellationToken,
+    notify: N,
+) where
+    E: CommandExecutor,
+    N: Fn(&DevLoopEvent),
+{
+    let mut built = None;
+    loop {
+        let fingerprint = source_fingerprint(&config.path);
+        if built != Some(fingerprint) {
+            built = Some(fingerprint);
+            let event = tokio::select! {
+                event = rebuild(&executor, &config, &book) => event,
+                () = cancel.cancelled() => break,
+            };
+            if let Ok(mut status) = status.lock() {
+                status.rebuilds += 1;
+                status.last = Some(event.clone());
+            }
+            notify(&event);
+        }
+        tokio::select! {
+            () = tokio::time::sleep(config.interval) => {}
+            () = cancel.cancelled() => break,
+        }
+    }
+}
+
+/// Build and deploy once, recording the new address.
+async fn rebuild<E: CommandExecutor>(
+    executor: &E,
+    config: &DevLoopConfig,
+    book: &ContractBook,
+) -> DevLoopEvent {
+    let failed = |stage: &str, error: String| {
+        let skip = error.chars().count().saturating_sub(MAX_ERROR_LEN);
+        DevLoopEvent::Failed {
+            alias: config.alias.clone(),
+            stage: stage.to_owned(),
+            error: error.chars().skip(skip).collect(),
+        }
+    };
+    let path = config.path.display().to_string();
+
+    let build = build_contract(
+        executor,
+        BuildContractParams {
+            path: path.clone(),
+            release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
+        },
+    )
+    .await;
+    match build {
+        Ok(result) if result.is_error != Some(true) => {}
+        Ok(result) => return failed("build", extract_text(&result).unwrap_or_default()),
+        Err(e) => return failed("build", e.to_string()),
+    }
+
+    let deploy = deploy_contract(
+        executor,
+        DeployContractParams {
+            path: path.clone(),
+            constructor: config.constructor.clone(),
+            args: config.args.clone(),
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: Some(true),
+            upload_only: None,
+            url: Some(config.url.clone()),
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
+        },
+        None,
+    )
+    .await;
+    let result = match deploy {
+        Ok(result) if result.is_error != Some(true) => result,
+        Ok(result) => return failed("deploy", extract_text(&result).unwrap_or_default()),
+        Err(e) => return failed("deploy", e.to_string()),
+    };
+    let Some(deployed) = result
+        .structured_content
+        .and_then(|value| serde_json::from_value::<DeployOutput>(value).ok())
+    else {
+        return failed(
+            "deploy",
+            "Deployment succeeded but reported no contract address".to_owned(),
+        );
+    };
+
+    if let Ok(mut book) = book.lock() {
+        book.insert(
+            config.alias.clone(),
+            ContractAlias {
+                alias: config.alias.clone(),
+                address: deployed.address.clone(),
+                code_hash: deployed.code_hash.clone(),
+                path,
+                url: config.url.clone(),
+                deployed_at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |since| since.as_secs()),
+            },
+        );
+    }
+    DevLoopEvent::Deployed {
+        alias: config.alias.clone(),
+        address: deployed.address,
+        code_hash: deployed.code_hash,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_ignores_build_output() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("lib.rs"), "fn a() {}")?;
+        let before = source_fingerprint(dir.path());
+
+        std::fs::create_dir_all(dir.path().join("target/ink"))?;
+        std::fs::write(dir.path().join("target/ink/flipper.json"), "{}")?;
+        std::fs::write(dir.path().join(".swap"), "x")?;
+        assert_eq!(source_fingerprint(dir.path()), before);
+
+        std::fs::write(dir.path().join("lib.rs"), "fn a() { b() }")?;
+        assert_ne!(source_fingerprint(dir.path()), before);
+        Ok(())
+    }
+}
@@ -0,0 +1,445 @@
+//! Summaries of compiler errors in failed build output.
+//!
+//! A failing build prints every warning, note and help line rustc has, often tens of
+//! kilobytes. [`compile_errors`] keeps the distinct errors with their code, span and
+//! first source snippet, so a client can fix them without reading the whole log.
+//! Errors ink! developers hit often also get a suggested fix and a documentation link.
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::parsing::strip_ansi;
+use crate::tools::common::{with_structured, with_summary};
+
+/// Most errors kept from one output.
+const MAX_ERRORS: usize = 50;
+
+/// Most errors listed in a summary.
+const MAX_SUMMARY_ERRORS: usize = 10;
+
+/// Most snippet lines kept per error.
+const MAX_SNIPPET_LINES: usize = 8;
+
+/// One compiler error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CompileError {
+    /// Error code, e.g. `E0425`, when rustc gave one.
+    pub code: Option<String>,
+    /// Error message.
+    pub message: String,
+    /// File the error points at.
+    pub file: Option<String>,
+    /// Line in `file`.
+    pub line: Option<u32>,
+    /// Column in `file`.
+    pub column: Option<u32>,
+    /// Source snippet rustc printed under the error.
+    pub snippet: Option<String>,
+    /// Suggested fix, for a known ink! error.
+    pub hint: Option<String>,
+    /// Documentation about the fix.
+    pub docs: Option<String>,
+}
+
+/// A frequent ink! error and how to fix it.
+struct KnownError {
+    /// Error code it comes with, when it has one.
+    code: Option<&'static str>,
+    /// Any of these in the error's message, notes or help marks it.
+    patterns: &'static [&'static str],
+    hint: &'static str,
+    docs: &'static str,
+}
+
+/// Frequent ink! errors, most specific first.
+const KNOWN_ERRORS: &[KnownError] = &[
+    KnownError {
+        code: None,
+        patterns: &[
+            "two different versions of crate",
+            "multiple different versions of crate",
+        ],
+        hint: "Two versions of the same crate (often `ink` or `parity-scale-codec`) are linked. \
+               Align the versions in Cargo.toml so every dependency uses the one `ink` pulls in, \
+               then run `cargo update`.",
+        docs: "https://use.ink/faq",
+    },
+    KnownError {
+        code: Some("E0514"),
+        patterns: &["incompatible version of rustc"],
+        hint: "Dependencies were compiled by another rustc. Run `cargo clean` and build again \
+               with the toolchain from rust-toolchain.toml.",
+        docs: "https://use.ink/faq",
+    },
+    KnownError {
+        code: None,
+        patterns: &["failed to select a version for"],
+        hint: "Cargo cannot resolve the ink! dependency versions. Use the same `ink` and \
+               `ink_e2e` version everywhere, and versions of `scale`/`scale-info` compatible \
+               with it.",
+        docs: "https://use.ink/faq",
+    },
+    KnownError {
+        code: None,
+        patterns: &["#[ink(message)]", "at least one ink! message"],
+        hint: "An ink! contract needs at least one public method annotated with \
+               `#[ink(message)]` inside its `impl` block.",
+        docs: "https://use.ink/basics/contract-template",
+    },
+    KnownError {
+        code: None,
+        patterns: &["#[ink(constructor)]", "at least one ink! constructor"],
+        hint: "An ink! contract needs at least one `#[ink(constructor)]` returning `Self`.",
+        docs: "https://use.ink/basics/contract-template",
+    },
+    KnownError {
+        code: None,
+        patterns: &["#[ink(storage)]"],
+        hint: "An ink! contract needs exactly one struct annotated with `#[ink(storage)]` \
+               in its `#[ink::contract]` module.",
+        docs: "https://use.ink/basics/storing-values",
+    },
+    KnownError {
+        code: Some("E0277"),
+        patterns: &[
+            "StorageLayout",
+            "Packed",
+            "Storable",
+            "StorableHint",
+            "AutoStorableHint",
+        ],
+        hint: "A type stored in the contract lacks the ink! storage traits. Derive them with \
+               `#[ink::scale_derive(Encode, Decode, TypeInfo)]` and \
+               `#[cfg_attr(feature = \"std\", derive(ink::storage::traits::StorageLayout))]`, \
+               or wrap collections in `ink::storage::Mapping`/`Lazy`.",
+        docs: "https://use.ink/datastructures/custom-datastructure",
+    },
+    KnownError {
+        code: Some("E0277"),
+        patterns: &[
+            "Encode",
+            "Decode",
+            "TypeInfo",
+            "WrapperTypeEncode",
+            "EncodeLike",
+        ],
+        hint: "A type used in a message, event or storage cannot be SCALE encoded. Add \
+               `#[ink::scale_derive(Encode, Decode, TypeInfo)]` to it.",
+        docs: "https://use.ink/datastructures/custom-datastructure",
+    },
+    KnownError {
+        code: Some("E0599"),
+        patterns: &["Ref`", "CallBuilder"],
+        hint: "The method is missing on the contract reference: mark it `#[ink(message)]` \
+               in the called contract, and depend on that contract with the \
+               `ink-as-dependency` feature.",
+        docs: "https://use.ink/basics/cross-contract-calling",
+    },
+    KnownError {
+        code: Some("E0433"),
+        patterns: &["`ink`", "`ink_e2e`"],
+        hint: "The crate is not a dependency. Add `ink` (and `ink_e2e` under \
+               dev-dependencies for e2e tests) to Cargo.toml.",
+        docs: "https://use.ink/basics/contract-template",
+    },
+];
+
+/// The known ink! error `error` is, given the text of its whole block.
+fn known_error(error: &CompileError, block: &str) -> Option<&'static KnownError> {
+    KNOWN_ERRORS.iter().find(|known| {
+        known
+            .code
+            .is_none_or(|code| error.code.as_deref() == Some(code))
+            && known.patterns.iter().any(|pattern| block.contains(pattern))
+    })
+}
+
+/// Compiler errors of a failed command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CompileErrors {
+    /// Distinct errors, in the order reported.
+    pub errors: Vec<CompileError>,
+}
+
+/// `(code, message)` of a rustc `error[E0425]: ...` or `error: ...` header line.
+fn error_header(line: &str) -> Option<(Option<String>, String)> {
+    let rest = line.strip_prefix("error")?;
+    let (code, rest) = match rest.strip_prefix('[') {
+        Some(rest) => {
+            let (code, rest) = rest.split_once(']')?;
+            (Some(code.to_owned()), rest)
+        }
+        None => (None, rest),
+    };
+    let message = rest.strip_prefix(':')?.trim();
+    // Cargo's closing lines repeat what the errors already say.
+    if message.is_empty()
+        || message.starts_with("could not compile")
+        || message.starts_with("aborting due to")
+    {
+        return None;
+    }
+    Some((code, message.to_owned()))
+}
+
+/// `(file, line, column)` of a ` --> file:line:column` span.
+fn span(location: &str) -> (Option<String>, Option<u32>, Option<u32>) {
+    let mut parts = location.rsplitn(3, ':');
+    let column = parts.next().and_then(|c| c.parse().ok());
+    let line = parts.next().and_then(|l| l.parse().ok());
+    match (parts.next(), line, column) {
+        (Some(file), Some(line), Some(column)) => (Some(file.to_owned()), Some(line), Some(column)),
+        _ => (Some(location.to_owned()), None, None),
+    }
+}
+
+/// Whether `line` is part of a source snippet (` 10 |     x`, `    |  ^`).
+fn is_snippet_line(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    let digits = trimmed.trim_start_matches(|c: char| c.is_ascii_digit());
+    digits.trim_start().starts_with('|')
+}
+
+/// Distinct compiler errors in `output`, in the order reported.
+pub fn compile_errors(output: &str) -> Vec<CompileError> {
+    let mut errors: Vec<CompileError> = Vec::new();
+    let mut current: Option<CompileError> = None;
+    let mut snippet: Vec<String> = Vec::new();
+    let mut snippet_done = false;
+    // Every line of the current error, for matching known errors.
+    let mut block = String::new();
+
+    let mut finish =
+        |current: &mut Option<CompileError>, snippet: &mut Vec<String>, block: &mut String| {
+            if let Some(mut error) = current.take() {
+                if !snippet.is_empty() {
+                    error.snippet = Some(snippet.join("\n"));
+                }
+                if let Some(known) = known_error(&error, block) {
+                    error.hint = Some(known.hint.to_owned());
+                    error.docs = Some(known.docs.to_owned());
+                }
+                let seen = errors.iter().any(|e| {
+                    e.code == error.code
+                        && e.message == error.message
+                        && e.file == error.file
+                        && e.line == error.line
+                });
+                if !seen && errors.len() < MAX_ERRORS {
+                    errors.push(error);
+                }
+            }
+            snippet.clear();
+            block.clear();
+        };
+
+    for raw in output.lines() {
+        let line = strip_ansi(raw);
+        let line = line.trim_end();
+        let text = line.trim_start_matches(|c: char| c == '│' || c.is_whitespace());
+        // The first line may carry the executor's prefix ("Command execution error: ").
+        let text = match text.find(": error") {
+            Some(at) if !text.starts_with("error") => &text[at + 2..],
+            _ => text,
+        };
+        if let Some((code, message)) = error_header(text) {
+            finish(&mut current, &mut snippet, &mut block);
+            snippet_done = false;
+            current = Some(CompileError {
+                code,
+                message,
+                file: None,
+                line: None,
+                column: None,
+                snippet: None,
+                hint: None,
+                docs: None,
+            });
+            block.push_str(text);
+            block.push('\n');
+            continue;
+        }
+        let Some(error) = current.as_mut() else {
+            continue;
+        };
+        if !text.starts_with("warning") {
+            block.push_str(text);
+            block.push('\n');
+        }
+        if text.starts_with("warning") || text.is_empty() {
+            finish(&mut current, &mut snippet, &mut block);
+        } else if let Some(location) = text.strip_prefix("-->") {
+            if error.file.is_none() {
+                (error.file, error.line, error.column) = span(location.trim());
+            }
+        } else if !snippet_done && is_snippet_line(line) {
+            if snippet.len() < MAX_SNIPPET_LINES {
+                snippet.push(line.to_owned());
+            }
+        } else if !snippet.is_empty() {
+            snippet_done = true;
+        }
+    }
+    finish(&mut current, &mut snippet, &mut block);
+    errors
+}
+
+/// Short listing of `errors`, one error per entry with its location.
+pub fn summary(heading: &str, errors: &[CompileError]) -> String {
+    let mut lines = vec![format!("{} with {} error(s):", heading, errors.len())];
+    for error in errors.iter().take(MAX_SUMMARY_ERRORS) {
+        let code = error
+            .code
+            .as_ref()
+            .map(|code| format!("[{}]", code))
+            .unwrap_or_default();
+        lines.push(format!("- error{}: {}", code, error.message));
+        if let Some(file) = &error.file {
+            let position = match (error.line, error.column) {
+                (Some(line), Some(column)) => format!(":{}:{}", line, column),
+                _ => String::new(),
+            };
+            lines.push(format!("  --> {}{}", file, position));
+        }
+        if let Some(snippet) = &error.snippet {
+            lines.extend(snippet.lines().map(|line| format!("  {}", line)));
+        }
+        if let Some(hint) = &error.hint {
+            lines.push(format!("  hint: {}", hint));
+        }
+        if let Some(docs) = &error.docs {
+            lines.push(format!("  docs: {}", docs));
+        }
+    }
+    if errors.len() > MAX_SUMMARY_ERRORS {
+        lines.push(format!(
+            "... and {} more (see structured content)",
+            errors.len() - MAX_SUMMARY_ERRORS
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Put a summary of the compiler errors in `output` before the content of `result`
+/// and attach them as structured content. `result` is returned as is without errors.
+pub(crate) fn with_compile_errors(
+    result: CallToolResult,
+    heading: &str,
+    output: &str,
+) -> CallToolResult {
+    let errors = compile_errors(output);
+    if errors.is_empty() {
+        return result;
+    }
+    let result = with_summary(result, summary(heading, &errors));
+    with_structured(result, &CompileErrors { errors })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARGO_OUTPUT: &str = "\
+   Compiling flipper v0.1.0 (/tmp/flipper)
+warning: unused import: `core::fmt`
+ --> lib.rs:3:5
+  |
+3 | use core::fmt;
+  |     ^^^^^^^^^
+  |
+  = note: `#[warn(unused_imports)]` on by default
+
+error[E0425]: cannot find value `x` in this scope
+  --> lib.rs:10:9
+   |
+10 |         x
+   |         ^ not found in this scope
+
+error[E0425]: cannot find value `x` in this scope
+  --> lib.rs:10:9
+   |
+10 |         x
+   |         ^ not found in this scope
+
+\u{1b}[0m\u{1b}[1m\u{1b}[38;5;9merror\u{1b}[0m: expected `;`, found `}`
+  --> lib.rs:21:14
+   |
+21 |         self.value
+   |              ^^^^^ help: add `;` here
+   = note: see more
+error: could not compile `flipper` (lib) due to 2 previous errors";
+
+    #[test]
+    fn compile_errors_keep_distinct_errors_with_spans_and_snippets() {
+        let errors = compile_errors(CARGO_OUTPUT);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0],
+            CompileError {
+                code: Some("E0425".to_owned()),
+                message: "cannot find value `x` in this scope".to_owned(),
+                file: Some("lib.rs".to_owned()),
+                line: Some(10),
+                column: Some(9),
+                snippet: Some(
+                    "   |\n10 |         x\n   |         ^ not found in this scope".to_owned()
+                ),
+                hint: None,
+                docs: None,
+            }
+        );
+        assert_eq!(errors[1].code, None);
+        assert_eq!(errors[1].message, "expected `;`, found `}`");
+        assert_eq!(errors[1].line, Some(21));
+    }
+
+    #[test]
+    fn summary_lists_codes_and_locations() {
+        let text = summary("Build failed", &compile_errors(CARGO_OUTPUT));
+        assert!(text.starts_with("Build failed with 2 error(s):"));
+        assert!(text.contains("- error[E0425]: cannot find value `x` in this scope"));
+        assert!(text.contains("  --> lib.rs:21:14"));
+        assert!(!text.contains("unused import"));
+    }
+
+    #[test]
+    fn known_ink_errors_get_a_hint_and_docs() {
+        let output = "\
+error[E0277]: the trait bound `Balances: StorageLayout` is not satisfied
+  --> lib.rs:8:9
+   |
+8  |         balances: Balances,
+   |         ^^^^^^^^ the trait `StorageLayout` is not implemented for `Balances`
+
+error[E0277]: the trait bound `Token: WrapperTypeEncode` is not satisfied
+  --> lib.rs:30:9
+   |
+note: there are multiple different versions of crate `parity_scale_codec` in the dependency graph
+
+error: missing #[ink(message)]: ink! modules need at least one message
+ --> lib.rs:1:1";
+        let errors = compile_errors(output);
+        let docs: Vec<Option<&str>> = errors.iter().map(|e| e.docs.as_deref()).collect();
+        assert_eq!(
+            docs,
+            vec![
+                Some("https://use.ink/datastructures/custom-datastructure"),
+                Some("https://use.ink/faq"),
+                Some("https://use.ink/basics/contract-template"),
+            ]
+        );
+        let text = summary("Build failed", &errors);
+        assert!(
+            text.contains("  hint: A type stored in the contract lacks the ink! storage traits.")
+        );
+        assert!(compile_errors(CARGO_OUTPUT)
+            .iter()
+            .all(|e| e.hint.is_none()));
+    }
+
+    #[test]
+    fn compile_errors_ignore_other_failures() {
+        assert!(compile_errors("Error: failed to connect to ws://localhost:9944").is_empty());
+    }
+}
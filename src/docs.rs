@@ -0,0 +1,306 @@
+//! Embedded guides, split into chapter resources.
+//!
+//! The guides under `docs/` are tens of kilobytes each: too much to read in one go
+//! when only one topic matters. Each is served as an index (`pop://docs/<guide>`)
+//! listing its chapters, and one `pop://docs/<guide>/<chapter>` resource per chapter.
+//! Chapters are split at `#` headings (outside code fences) or, for the XCM guides,
+//! at titles framed by `=====` lines; a chapter still larger than
+//! [`MAX_CHAPTER_BYTES`] is split again at its `##`, then `###` headings.
+
+use std::sync::OnceLock;
+
+/// Prefix of the guide and chapter URIs.
+pub const DOCS_URI_PREFIX: &str = "pop://docs/";
+
+/// Size above which a chapter is split at its subheadings.
+pub const MAX_CHAPTER_BYTES: usize = 16 * 1024;
+
+/// Deepest markdown heading level chapters are split at.
+const MAX_LEVEL: usize = 3;
+
+/// An embedded guide.
+#[derive(Debug)]
+pub struct Guide {
+    /// Name in the URI.
+    pub name: &'static str,
+    /// Human-readable title.
+    pub title: &'static str,
+    text: &'static str,
+}
+
+/// Guides served as resources.
+pub const GUIDES: &[Guide] = &[
+    Guide {
+        name: "ink",
+        title: "ink! v6 smart contracts",
+        text: include_str!("../docs/ink-llms.txt"),
+    },
+    Guide {
+        name: "pop-cli",
+        title: "Pop CLI",
+        text: include_str!("../docs/pop-cli-llms.txt"),
+    },
+    Guide {
+        name: "xcm",
+        title: "XCM (cross-consensus messaging)",
+        text: include_str!("../docs/xcm-comprehensive-guide.txt"),
+    },
+    Guide {
+        name: "xcm-ink-examples",
+        title: "XCM in ink! contracts: examples",
+        text: include_str!("../docs/xcm-ink-examples-guide.txt"),
+    },
+];
+
+/// A chapter of a guide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chapter {
+    /// Resource URI.
+    pub uri: String,
+    /// Title, with the titles of enclosing chapters it was split from.
+    pub title: String,
+    /// Text of the chapter.
+    pub text: &'static str,
+}
+
+impl Guide {
+    /// URI of the guide's index.
+    pub fn uri(&self) -> String {
+        format!("{}{}", DOCS_URI_PREFIX, self.name)
+    }
+
+    /// Chapters of the guide, in order.
+    pub fn chapters(&self) -> &'static [Chapter] {
+        static CHAPTERS: OnceLock<Vec<Vec<Chapter>>> = OnceLock::new();
+        let all = CHAPTERS.get_or_init(|| GUIDES.iter().map(Self::split).collect());
+        GUIDES
+            .iter()
+            .position(|guide| guide.name == self.name)
+            .and_then(|i| all.get(i))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// The index: a list of the chapters with their URIs and sizes.
+    pub fn index(&self) -> String {
+        let mut index = format!(
+            "# {}\n\nChapters (read each as a resource):\n\n",
+            self.title
+        );
+        for chapter in self.chapters() {
+            index.push_str(&format!(
+                "- {} <{}> ({} bytes)\n",
+                chapter.title,
+                chapter.uri,
+                chapter.text.len()
+            ));
+        }
+        index
+    }
+
+    fn split(&self) -> Vec<Chapter> {
+        let level = if self.text.lines().next().is_some_and(is_banner) {
+            0
+        } else {
+            1
+        };
+        let mut parts = Vec::new();
+        split(self.text, level, None, &mut parts);
+        let mut slugs: Vec<String> = Vec::new();
+        parts
+            .into_iter()
+            .map(|(title, text)| {
+                let base = slug(&title);
+                let mut unique = base.clone();
+                let mut n = 2;
+                while slugs.contains(&unique) {
+                    unique = format!("{}-{}", base, n);
+                    n += 1;
+                }
+                slugs.push(unique.clone());
+                Chapter {
+                    uri: format!("{}/{}", self.uri(), unique),
+                    title,
+                    text,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The guide at `uri`, if it is a guide index.
+pub fn guide(uri: &str) -> Option<&'static Guide> {
+    let name = uri.strip_prefix(DOCS_URI_PREFIX)?;
+    GUIDES.iter().find(|guide| guide.name == name)
+}
+
+/// The chapter at `uri`.
+pub fn chapter(uri: &str) -> Option<&'static Chapter> {
+    let (name, _) = uri.strip_prefix(DOCS_URI_PREFIX)?.split_once('/')?;
+    let guide = GUIDES.iter().find(|guide| guide.name == name)?;
+    guide.chapters().iter().find(|chapter| chapter.uri == uri)
+}
+
+/// Whether `line` is a `=====` banner framing XCM guide titles.
+fn is_banner(line: &str) -> bool {
+    let line = line.trim_end();
+    line.len() >= 20 && line.bytes().all(|b| b == b'=')
+}
+
+/// Byte offset and title of each heading of `text` at `level`: banner-framed titles
+/// for level 0, markdown headings outside code fences otherwise.
+fn headings(text: &str, level: usize) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        lines.push((offset, line.trim_end()));
+        offset += line.len();
+    }
+    let marker = "#".repeat(level);
+    let mut found = Vec::new();
+    let mut fenced = false;
+    for (i, &(offset, line)) in lines.iter().enumerate() {
+        if level == 0 {
+            let framed = i > 0
+                && lines.get(i - 1).is_some_and(|(_, prev)| is_banner(prev))
+                && lines.get(i + 1).is_some_and(|(_, next)| is_banner(next));
+            if framed && !line.is_empty() && !is_banner(line) {
+                found.push((lines[i - 1].0, line.trim().to_owned()));
+            }
+        } else if line.trim_start().starts_with("```") {
+            fenced = !fenced;
+        } else if !fenced {
+            if let Some(title) = line
+                .strip_prefix(marker.as_str())
+                .and_then(|rest| rest.strip_prefix(' '))
+            {
+                found.push((offset, title.trim().to_owned()));
+            }
+        }
+    }
+    found
+}
+
+/// Split `text` at its headings of `level` into `(title, text)` parts, splitting
+/// parts larger than [`MAX_CHAPTER_BYTES`] again at the next level.
+fn split(
+    text: &'static str,
+    level: usize,
+    parent: Option<&str>,
+    parts: &mut Vec<(String, &'static str)>,
+) {
+    let found = headings(text, level);
+    let mut sections = Vec::new();
+    let first = found.first().map_or(text.len(), |(offset, _)| *offset);
+    let preamble = text[..first].trim();
+    // A lone heading line before the first subheading stays with it.
+    let merged = !found.is_empty() && !preamble.contains('\n');
+    if !preamble.is_empty() && !merged {
+        sections.push(("Overview".to_owned(), &text[..first]));
+    }
+    for (i, (start, title)) in found.iter().enumerate() {
+        let start = if i == 0 && merged { 0 } else { *start };
+        let end = found.get(i + 1).map_or(text.len(), |(offset, _)| *offset);
+        sections.push((title.clone(), &text[start..end]));
+    }
+    // A single section is the text itself (often just its title), not a chapter.
+    let single = sections.len() == 1;
+    for (title, section) in sections {
+        let title = match parent {
+            Some(parent) if single => parent.to_owned(),
+            Some(parent) => format!("{} / {}", parent, title),
+            None => title,
+        };
+        if section.len() > MAX_CHAPTER_BYTES && level < MAX_LEVEL {
+            let next = if level == 0 { 2 } else { level + 1 };
+            let parent = if single { parent } else { Some(title.as_str()) };
+            split(section, next, parent, parts);
+        } else {
+            parts.push((title, section));
+        }
+    }
+}
+
+/// Lowercase URI segment of `title`: letters and digits, other runs become `-`.
+fn slug(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "chapter".to_owned()
+    } else {
+        slug.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chapters_cover_each_guide_in_order() {
+        for guide in GUIDES {
+            let chapters = guide.chapters();
+            assert!(chapters.len() > 3, "{} has too few chapters", guide.name);
+            let joined: String = chapters.iter().map(|chapter| chapter.text).collect();
+            assert_eq!(joined.trim(), guide.text.trim(), "{} lost text", guide.name);
+            assert!(chapters
+                .iter()
+                .all(|chapter| chapter.uri.starts_with(&format!("{}/", guide.uri()))));
+        }
+    }
+
+    #[test]
+    fn large_chapters_are_split_at_subheadings() {
+        let text =
+            "# Guide\n\nIntro\n\n## Small\n\nshort\n\n## Large\n\n```\n# not a heading\n```\n";
+        let large = format!(
+            "{}{}",
+            text,
+            "### Part\n\ntext\n".repeat(MAX_CHAPTER_BYTES / 10)
+        );
+        let large: &'static str = Box::leak(large.into_boxed_str());
+        let mut parts = Vec::new();
+        split(large, 1, None, &mut parts);
+        let titles: Vec<&str> = parts.iter().map(|(title, _)| title.as_str()).collect();
+        assert_eq!(titles[..3], ["Overview", "Small", "Large / Overview"]);
+        assert_eq!(titles[3], "Large / Part");
+        assert!(parts[2].1.contains("# not a heading"));
+        // A lone heading is kept with the first subsection rather than on its own.
+        let mut parts = Vec::new();
+        split("## Basics\n\n### Storage\n\ntext\n", 2, None, &mut parts);
+        assert_eq!(parts.len(), 1);
+        split(
+            "## Basics\n\n### Storage\n\ntext\n",
+            3,
+            Some("Basics"),
+            &mut parts,
+        );
+        assert_eq!(
+            parts[1],
+            ("Basics".to_owned(), "## Basics\n\n### Storage\n\ntext\n")
+        );
+    }
+
+    #[test]
+    fn banner_titles_start_chapters() {
+        let guide = GUIDES.iter().find(|guide| guide.name == "xcm");
+        let chapters = guide.map_or(&[][..], Guide::chapters);
+        assert!(chapters
+            .iter()
+            .any(|chapter| chapter.uri == "pop://docs/xcm/1-what-is-xcm"));
+        assert!(chapters
+            .iter()
+            .all(|chapter| chapter.text.len() <= MAX_CHAPTER_BYTES
+                || !chapter.text.contains("\n## ")));
+        assert_eq!(
+            slug("5. MULTILOCATION - ADDRESSING SYSTEM"),
+            "5-multilocation-addressing-system"
+        );
+    }
+}
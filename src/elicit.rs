@@ -0,0 +1,442 @@
+//! Asking the client for missing contract parameters (MCP elicitation).
+//!
+//! `deploy_contract` without a constructor, or `deploy_contract`/`call_contract`
+//! without the arguments the constructor or message takes, would otherwise fail with
+//! whatever pop prints. When the contract has been built, its metadata in
+//! `target/ink` lists the constructors and messages with their arguments, so the
+//! server can ask the user for exactly what is missing. Clients without elicitation
+//! support, and users who decline, get an error naming what to pass instead.
+
+use std::path::Path;
+use std::time::Duration;
+
+use rmcp::model::{
+    CallToolResult, CreateElicitationRequestParam, ElicitationAction, ElicitationSchema,
+};
+use rmcp::{Peer, RoleServer};
+use serde_json::{Map, Value};
+
+use crate::tools::common::error_result;
+use crate::tools::{CallContractParams, DeployContractParams};
+
+/// Constructor pop calls when none is given.
+pub const DEFAULT_CONSTRUCTOR: &str = "new";
+
+/// How long the user has to answer.
+const ANSWER_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Argument of a constructor or message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arg {
+    /// Argument name.
+    pub label: String,
+    /// Last segment of the argument's type name (e.g. `AccountId`).
+    pub type_name: String,
+}
+
+/// Constructor or message of a contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Method {
+    /// Constructor or message name.
+    pub label: String,
+    /// Arguments, in order.
+    pub args: Vec<Arg>,
+}
+
+impl Method {
+    /// `label(arg: Type, ..)`.
+    pub fn signature(&self) -> String {
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| format!("{}: {}", arg.label, arg.type_name))
+            .collect();
+        format!("{}({})", self.label, args.join(", "))
+    }
+}
+
+/// Constructors and messages of a built contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractSpec {
+    /// Constructors, in metadata order.
+    pub constructors: Vec<Method>,
+    /// Messages, in metadata order.
+    pub messages: Vec<Method>,
+}
+
+/// Spec of the contract built at `path`, from the first metadata file in
+/// `target/ink`, or `None` when it has not been built.
+pub fn contract_spec(path: &str) -> Option<ContractSpec> {
+    let mut files: Vec<_> = std::fs::read_dir(Path::new(path).join("target/ink"))
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+        .collect();
+    files.sort();
+    files.iter().find_map(|file| {
+        let json = std::fs::read_to_string(file).ok()?;
+        parse_spec(&serde_json::from_str(&json).ok()?)
+    })
+}
+
+/// Spec in ink! metadata JSON.
+fn parse_spec(metadata: &Value) -> Option<ContractSpec> {
+    let spec = metadata.get("spec")?;
+    let methods = |key: &str| -> Option<Vec<Method>> {
+        spec.get(key)?
+            .as_array()?
+            .iter()
+            .map(|method| {
+                let args = method
+                    .get("args")
+                    .and_then(Value::as_array)
+                    .map(|args| {
+                        args.iter()
+                            .filter_map(|arg| {
+                                let type_name = arg
+                                    .pointer("/type/displayName")
+                                    .and_then(Value::as_array)
+                                    .and_then(|path| path.last())
+                                    .and_then(Value::as_str)
+                                    .unwrap_or("?");
+                                Some(Arg {
+                                    label: arg.get("label")?.as_str()?.to_owned(),
+                                    type_name: type_name.to_owned(),
+                                })
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Some(Method {
+                    label: method.get("label")?.as_str()?.to_owned(),
+                    args,
+                })
+            })
+            .collect()
+    };
+    Some(ContractSpec {
+        constructors: methods("constructors")?,
+        messages: methods("messages")?,
+    })
+}
+
+/// Signatures of `methods`, comma-separated.
+fn signatures(methods: &[Method]) -> String {
+    methods
+        .iter()
+        .map(Method::signature)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A request for user input.
+#[derive(Debug, Clone)]
+pub struct Question {
+    /// What is asked, for the user.
+    pub message: String,
+    /// Fields the user fills in.
+    pub schema: ElicitationSchema,
+}
+
+/// Question choosing one of the contract's constructors.
+pub fn constructor_question(spec: &ContractSpec) -> Question {
+    let labels = spec.constructors.iter().map(|c| c.label.clone()).collect();
+    Question {
+        message: format!(
+            "Which constructor should deploy the contract? Constructors: {}",
+            signatures(&spec.constructors)
+        ),
+        schema: ElicitationSchema::builder()
+            .required_enum("constructor", labels)
+            .build_unchecked(),
+    }
+}
+
+/// Question asking for every argument of `method`, a `kind` ("constructor" or
+/// "message").
+pub fn args_question(kind: &str, method: &Method) -> Question {
+    let schema = method
+        .args
+        .iter()
+        .fold(ElicitationSchema::builder(), |schema, arg| {
+            let description = arg.type_name.clone();
+            schema.required_string_property(&arg.label, |s| s.description(description))
+        })
+        .build_unchecked();
+    Question {
+        message: format!("Arguments for {} {}", kind, method.signature()),
+        schema,
+    }
+}
+
+/// The space-separated `args` value of the user's answers, in argument order, or
+/// the names of the arguments left empty.
+pub fn answered_args(method: &Method, answers: &Map<String, Value>) -> Result<String, String> {
+    let mut values = Vec::new();
+    let mut missing = Vec::new();
+    for arg in &method.args {
+        match answers.get(&arg.label) {
+            Some(Value::String(value)) if !value.trim().is_empty() => {
+                values.push(value.trim().to_owned());
+            }
+            Some(Value::Null) | Some(Value::String(_)) | None => missing.push(arg.label.as_str()),
+            Some(value) => values.push(value.to_string()),
+        }
+    }
+    if missing.is_empty() {
+        Ok(values.join(" "))
+    } else {
+        Err(missing.join(", "))
+    }
+}
+
+/// How the user answered.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    /// The user filled in the fields.
+    Accepted(Map<String, Value>),
+    /// The user chose not to answer.
+    Declined,
+    /// The user cancelled the operation.
+    Cancelled,
+}
+
+/// Ask the user `question`, or `None` when the client cannot ask or does not answer.
+pub async fn ask(peer: &Peer<RoleServer>, question: Question) -> Option<Answer> {
+    if !peer.supports_elicitation() {
+        return None;
+    }
+    let request = peer.create_elicitation(CreateElicitationRequestParam {
+        message: question.message,
+        requested_schema: question.schema,
+    });
+    let result = tokio::time::timeout(ANSWER_TIMEOUT, request)
+        .await
+        .ok()?
+        .ok()?;
+    Some(match result.action {
+        ElicitationAction::Accept => match result.content {
+            Some(Value::Object(answers)) => Answer::Accepted(answers),
+            _ => Answer::Declined,
+        },
+        ElicitationAction::Decline => Answer::Declined,
+        ElicitationAction::Cancel => Answer::Cancelled,
+    })
+}
+
+/// Fill in `params.args` for `method` by asking the user, or explain what to pass.
+async fn ask_args(
+    peer: &Peer<RoleServer>,
+    kind: &str,
+    method: &Method,
+    operation: &str,
+) -> Result<String, CallToolResult> {
+    let missing = |names: &str| {
+        error_result(format!(
+            "{} {} takes arguments: {}. Pass values for {} in args (space-separated, in order) or args_file.",
+            capitalize(kind),
+            method.label,
+            method.signature(),
+            names
+        ))
+    };
+    match ask(peer, args_question(kind, method)).await {
+        Some(Answer::Accepted(answers)) => {
+            answered_args(method, &answers).map_err(|names| missing(&names))
+        }
+        Some(Answer::Cancelled) => Err(error_result(format!("{} cancelled", operation))),
+        Some(Answer::Declined) | None => {
+            let names: Vec<&str> = method.args.iter().map(|a| a.label.as_str()).collect();
+            Err(missing(&names.join(", ")))
+        }
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Complete deploy_contract parameters from the contract metadata, asking the user
+/// for the constructor and its arguments when missing.
+///
+/// Returns the error result to report instead of deploying when they stay missing.
+/// Contracts that have not been built are left to pop.
+pub async fn complete_deploy(
+    peer: &Peer<RoleServer>,
+    params: &mut DeployContractParams,
+) -> Result<(), CallToolResult> {
+    let Some(spec) = contract_spec(&params.path) else {
+        return Ok(());
+    };
+    let has_default = spec
+        .constructors
+        .iter()
+        .any(|c| c.label == DEFAULT_CONSTRUCTOR);
+    if params.constructor.is_none() {
+        match spec.constructors.as_slice() {
+            [only] if !has_default => params.constructor = Some(only.label.clone()),
+            [] | [_] => {}
+            _ => match ask(peer, constructor_question(&spec)).await {
+                Some(Answer::Accepted(answers)) => {
+                    params.constructor = answers
+                        .get("constructor")
+                        .and_then(Value::as_str)
+                        .map(str::to_owned);
+                }
+                Some(Answer::Cancelled) => return Err(error_result("Deployment cancelled")),
+                Some(Answer::Declined) | None if !has_default => {
+                    return Err(error_result(format!(
+                        "The contract has no '{}' constructor: pass constructor. Constructors: {}",
+                        DEFAULT_CONSTRUCTOR,
+                        signatures(&spec.constructors)
+                    )));
+                }
+                Some(Answer::Declined) | None => {}
+            },
+        }
+    }
+    let label = params.constructor.as_deref().unwrap_or(DEFAULT_CONSTRUCTOR);
+    let Some(constructor) = spec.constructors.iter().find(|c| c.label == label) else {
+        return Err(error_result(format!(
+            "The contract has no constructor '{}'. Constructors: {}",
+            label,
+            signatures(&spec.constructors)
+        )));
+    };
+    if params.args.is_none() && params.args_file.is_none() && !constructor.args.is_empty() {
+        params.args = Some(ask_args(peer, "constructor", constructor, "Deployment").await?);
+    }
+    Ok(())
+}
+
+/// Complete call_contract parameters from the contract metadata, asking the user
+/// for the message's arguments when missing.
+///
+/// Returns the error result to report instead of calling when they stay missing.
+/// Contracts that have not been built are left to pop.
+pub async fn complete_call(
+    peer: &Peer<RoleServer>,
+    params: &mut CallContractParams,
+) -> Result<(), CallToolResult> {
+    let Some(spec) = contract_spec(&params.path) else {
+        return Ok(());
+    };
+    let Some(message) = spec.messages.iter().find(|m| m.label == params.message) else {
+        return Err(error_result(format!(
+            "The contract has no message '{}'. Messages: {}",
+            params.message,
+            signatures(&spec.messages)
+        )));
+    };
+    if params.args.is_none() && params.args_file.is_none() && !message.args.is_empty() {
+        params.args = Some(ask_args(peer, "message", message, "Call").await?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn metadata() -> Value {
+        json!({
+            "source": { "hash": "0x00" },
+            "spec": {
+                "constructors": [
+                    {
+                        "label": "new",
+                        "args": [{ "label": "init_value", "type": { "displayName": ["bool"], "type": 0 } }],
+                        "default": false
+                    },
+                    { "label": "default", "args": [], "default": true }
+                ],
+                "messages": [
+                    { "label": "flip", "args": [] },
+                    {
+                        "label": "transfer",
+                        "args": [
+                            { "label": "to", "type": { "displayName": ["ink", "primitives", "AccountId"], "type": 1 } },
+                            { "label": "value", "type": { "displayName": ["Balance"], "type": 2 } }
+                        ]
+                    }
+                ]
+            }
+        })
+    }
+
+    #[test]
+    fn specs_are_read_from_built_metadata() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().display().to_string();
+        assert_eq!(contract_spec(&path), None);
+
+        std::fs::create_dir_all(dir.path().join("target/ink"))?;
+        std::fs::write(
+            dir.path().join("target/ink/flipper.json"),
+            metadata().to_string(),
+        )?;
+        let Some(spec) = contract_spec(&path) else {
+            anyhow::bail!("expected the metadata to parse");
+        };
+        assert_eq!(
+            signatures(&spec.constructors),
+            "new(init_value: bool), default()"
+        );
+        assert_eq!(
+            spec.messages[1].signature(),
+            "transfer(to: AccountId, value: Balance)"
+        );
+        assert_eq!(parse_spec(&json!({ "version": 5 })), None);
+        Ok(())
+    }
+
+    #[test]
+    fn questions_ask_for_exactly_what_is_missing() -> anyhow::Result<()> {
+        let Some(spec) = parse_spec(&metadata()) else {
+            anyhow::bail!("expected the metadata to parse");
+        };
+        let schema = serde_json::to_value(constructor_question(&spec).schema)?;
+        assert_eq!(
+            schema.pointer("/properties/constructor/enum"),
+            Some(&json!(["new", "default"]))
+        );
+
+        let transfer = &spec.messages[1];
+        let question = args_question("message", transfer);
+        assert_eq!(
+            question.message,
+            "Arguments for message transfer(to: AccountId, value: Balance)"
+        );
+        let schema = serde_json::to_value(question.schema)?;
+        assert_eq!(schema.get("required"), Some(&json!(["to", "value"])));
+        assert_eq!(
+            schema.pointer("/properties/value/description"),
+            Some(&json!("Balance"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn answers_become_args_in_order() -> anyhow::Result<()> {
+        let Some(spec) = parse_spec(&metadata()) else {
+            anyhow::bail!("expected the metadata to parse");
+        };
+        let transfer = &spec.messages[1];
+        let answers = |value: Value| value.as_object().cloned().unwrap_or_default();
+        assert_eq!(
+            answered_args(transfer, &answers(json!({ "value": 100, "to": " 5Grw " }))),
+            Ok("5Grw 100".to_owned())
+        );
+        assert_eq!(
+            answered_args(transfer, &answers(json!({ "to": "5Grw", "value": "" }))),
+            Err("value".to_owned())
+        );
+        Ok(())
+    }
+}
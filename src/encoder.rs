@@ -0,0 +1,1874 @@
+//! Metadata-driven SCALE encoding of chain calls.
+//!
+//! Tools that compose extrinsics (batches, scheduled calls, preimages) need the encoded
+//! call bytes rather than a single `pop call chain --pallet --function` invocation.
+//! This module fetches runtime metadata over the RPC pool and encodes calls from the
+//! same space-separated argument strings `call_chain` accepts. The resulting call data
+//! is submitted through Pop CLI (`pop call chain --call 0x...`). Runtime API parameters
+//! are encoded the same way for `state_call`.
+
+use codec::{Compact, Decode, Encode};
+use frame_metadata::v14::{StorageEntryType, StorageHasher};
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use scale_value::scale::PortableRegistry;
+use scale_value::stringify::custom_parsers;
+use scale_value::Value;
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::rpc::RpcPool;
+
+/// A pallet as seen by the encoder.
+#[derive(Debug, Clone)]
+struct PalletInfo {
+    name: String,
+    index: u8,
+    /// Type id of the pallet's call enum, if it has calls.
+    call_ty: Option<u32>,
+    storage: Vec<StorageItem>,
+    constants: Vec<Constant>,
+}
+
+/// A storage item: its name, map keys (hasher and type, none for plain values), value
+/// type and SCALE-encoded default.
+#[derive(Debug, Clone)]
+struct StorageItem {
+    name: String,
+    keys: Vec<(StorageHasher, u32)>,
+    ty: u32,
+    default: Vec<u8>,
+}
+
+/// A pallet constant: its name, type and SCALE-encoded value.
+#[derive(Debug, Clone)]
+struct Constant {
+    name: String,
+    ty: u32,
+    value: Vec<u8>,
+}
+
+/// A runtime API method callable through `state_call`.
+#[derive(Debug, Clone)]
+struct RuntimeApiMethod {
+    /// Trait the method belongs to (e.g. `TransactionPaymentApi`).
+    api: String,
+    name: String,
+    /// Parameter names and type ids, in order.
+    inputs: Vec<(String, u32)>,
+    output: u32,
+}
+
+/// Types of the signed part of an extrinsic.
+#[derive(Debug, Clone, Default)]
+struct ExtrinsicInfo {
+    address_ty: Option<u32>,
+    signature_ty: Option<u32>,
+    /// Signed extensions, in order.
+    extensions: Vec<SignedExtension>,
+}
+
+/// A signed extension: its identifier, the type of the data it adds to the extrinsic
+/// and the type of the data it adds only to the signed payload.
+#[derive(Debug, Clone)]
+struct SignedExtension {
+    name: String,
+    ty: u32,
+    additional_ty: u32,
+}
+
+/// A V4 extrinsic split into its SCALE-encoded parts.
+struct ExtrinsicParts {
+    /// Address and per-extension data of signed extrinsics.
+    signed: Option<SignedParts>,
+    call: Vec<u8>,
+}
+
+/// The signed part of an extrinsic, without the signature itself.
+struct SignedParts {
+    address: Vec<u8>,
+    extensions: Vec<Vec<u8>>,
+}
+
+/// Chain values a re-signed extrinsic commits to, with the new tip and nonce.
+#[derive(Debug, Clone)]
+pub struct Resign {
+    /// Tip in the chain's smallest unit.
+    pub tip: u128,
+    /// Nonce to use instead of the original one.
+    pub nonce: Option<u64>,
+    /// Runtime `specVersion`.
+    pub spec_version: u32,
+    /// Runtime `transactionVersion`.
+    pub transaction_version: u32,
+    /// Hash of block 0.
+    pub genesis_hash: [u8; 32],
+}
+
+/// A transaction decoded against runtime metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedExtrinsic {
+    /// Signer address, for signed transactions.
+    pub signer: Option<String>,
+    /// Signed extension data with non-empty values (nonce, mortality, tip...).
+    pub extensions: Vec<(String, String)>,
+    /// The call, as `Pallet.function { args }`.
+    pub call: String,
+}
+
+/// Runtime metadata reduced to what call encoding needs.
+#[derive(Debug, Clone)]
+pub struct ChainMetadata {
+    types: PortableRegistry,
+    pallets: Vec<PalletInfo>,
+    /// Runtime API methods (metadata V15 and later).
+    runtime_apis: Vec<RuntimeApiMethod>,
+    extrinsic: ExtrinsicInfo,
+}
+
+impl ChainMetadata {
+    /// Fetch and decode the runtime metadata of the chain at `url`.
+    pub async fn fetch(pool: &RpcPool, url: &str) -> PopMcpResult<Self> {
+        Self::fetch_at(pool, url, None).await
+    }
+
+    /// Fetch and decode the runtime metadata in effect at block hash `at` (default: best).
+    pub async fn fetch_at(pool: &RpcPool, url: &str, at: Option<&str>) -> PopMcpResult<Self> {
+        let params = at
+            .map(|hash| vec![serde_json::Value::String(hash.to_owned())])
+            .unwrap_or_default();
+        let value = pool.request(url, "state_getMetadata", params).await?;
+        let hex = value
+            .as_str()
+            .ok_or_else(|| PopMcpError::Rpc("state_getMetadata returned no data".to_owned()))?;
+        let bytes = decode_hex(hex).map_err(PopMcpError::Rpc)?;
+        Self::from_bytes(&bytes).map_err(PopMcpError::Rpc)
+    }
+
+    /// Decode metadata from `RuntimeMetadataPrefixed` bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let prefixed = RuntimeMetadataPrefixed::decode(&mut &bytes[..])
+            .map_err(|e| format!("Failed to decode metadata: {}", e))?;
+
+        macro_rules! reduce {
+            ($metadata:expr, $runtime_apis:expr, $extrinsic:expr) => {
+                Self {
+                    pallets: $metadata
+                        .pallets
+                        .iter()
+                        .map(|p| PalletInfo {
+                            name: p.name.clone(),
+                            index: p.index,
+                            call_ty: p.calls.as_ref().map(|c| c.ty.id),
+                            storage: p
+                                .storage
+                                .iter()
+                                .flat_map(|s| &s.entries)
+                                .map(|e| {
+                                    let (keys, ty) = match &e.ty {
+                                        StorageEntryType::Plain(ty) => (Vec::new(), ty.id),
+                                        StorageEntryType::Map {
+                                            hashers,
+                                            key,
+                                            value,
+                                        } => {
+                                            (map_keys(&$metadata.types, hashers, key.id), value.id)
+                                        }
+                                    };
+                                    StorageItem {
+                                        name: e.name.clone(),
+                                        keys,
+                                        ty,
+                                        default: e.default.clone(),
+                                    }
+                                })
+                                .collect(),
+                            constants: p
+                                .constants
+                                .iter()
+                                .map(|c| Constant {
+                                    name: c.name.clone(),
+                                    ty: c.ty.id,
+                                    value: c.value.clone(),
+                                })
+                                .collect(),
+                        })
+                        .collect(),
+                    runtime_apis: $runtime_apis,
+                    extrinsic: $extrinsic,
+                    types: $metadata.types,
+                }
+            };
+        }
+
+        match prefixed.1 {
+            RuntimeMetadata::V14(metadata) => {
+                // V14 only names the UncheckedExtrinsic type; its generic parameters
+                // carry the address and signature types.
+                let param = |name: &str| {
+                    metadata
+                        .types
+                        .resolve(metadata.extrinsic.ty.id)
+                        .and_then(|ty| ty.type_params.iter().find(|p| p.name == name))
+                        .and_then(|p| p.ty)
+                        .map(|ty| ty.id)
+                };
+                let extrinsic = ExtrinsicInfo {
+                    address_ty: param("Address"),
+                    signature_ty: param("Signature"),
+                    extensions: metadata
+                        .extrinsic
+                        .signed_extensions
+                        .iter()
+                        .map(|e| SignedExtension {
+                            name: e.identifier.clone(),
+                            ty: e.ty.id,
+                            additional_ty: e.additional_signed.id,
+                        })
+                        .collect(),
+                };
+                Ok(reduce!(metadata, Vec::new(), extrinsic))
+            }
+            RuntimeMetadata::V15(metadata) => {
+                let runtime_apis = metadata
+                    .apis
+                    .iter()
+                    .flat_map(|api| {
+                        api.methods.iter().map(|m| RuntimeApiMethod {
+                            api: api.name.clone(),
+                            name: m.name.clone(),
+                            inputs: m.inputs.iter().map(|i| (i.name.clone(), i.ty.id)).collect(),
+                            output: m.output.id,
+                        })
+                    })
+                    .collect();
+                let extrinsic = ExtrinsicInfo {
+                    address_ty: Some(metadata.extrinsic.address_ty.id),
+                    signature_ty: Some(metadata.extrinsic.signature_ty.id),
+                    extensions: metadata
+                        .extrinsic
+                        .signed_extensions
+                        .iter()
+                        .map(|e| SignedExtension {
+                            name: e.identifier.clone(),
+                            ty: e.ty.id,
+                            additional_ty: e.additional_signed.id,
+                        })
+                        .collect(),
+                };
+                Ok(reduce!(metadata, runtime_apis, extrinsic))
+            }
+            other => Err(format!("Unsupported metadata version {}", other.version())),
+        }
+    }
+
+    /// Whether the runtime includes `pallet` (case-insensitive).
+    pub fn has_pallet(&self, pallet: &str) -> bool {
+        self.pallet(pallet).is_some()
+    }
+
+    /// Whether `pallet` exposes the dispatchable `function`.
+    pub fn has_call(&self, pallet: &str, function: &str) -> bool {
+        self.call_variant(pallet, function).is_ok()
+    }
+
+    /// Whether `pallet` has the plain storage value `item`.
+    pub fn has_storage(&self, pallet: &str, item: &str) -> bool {
+        self.storage_item(pallet, item).is_some()
+    }
+
+    /// Whether the runtime exposes `api.method`.
+    pub fn has_runtime_api(&self, api: &str, method: &str) -> bool {
+        self.runtime_apis
+            .iter()
+            .any(|m| m.api.eq_ignore_ascii_case(api) && m.name.eq_ignore_ascii_case(method))
+    }
+
+    fn storage_item(&self, pallet: &str, item: &str) -> Option<&StorageItem> {
+        self.pallet(pallet)?
+            .storage
+            .iter()
+            .find(|s| s.name.eq_ignore_ascii_case(item))
+    }
+
+    /// Whether `pallet` declares the constant `name` (case-insensitive).
+    pub fn has_constant(&self, pallet: &str, name: &str) -> bool {
+        self.constant(pallet, name).is_some()
+    }
+
+    fn constant(&self, pallet: &str, name: &str) -> Option<&Constant> {
+        self.pallet(pallet)?
+            .constants
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Render the constant `pallet.name`.
+    pub fn decode_constant(&self, pallet: &str, name: &str) -> Result<String, String> {
+        let constant = self
+            .constant(pallet, name)
+            .ok_or_else(|| format!("Constant {}.{} not found", pallet, name))?;
+        self.render(&constant.value, constant.ty)
+            .map_err(|e| format!("Failed to decode {}.{}: {}", pallet, name, e))
+    }
+
+    /// Storage key of `pallet.item`, with one argument per map key (none for plain
+    /// values). Arguments use the same syntax as [`Self::encode_call`].
+    pub fn storage_key(
+        &self,
+        pallet: &str,
+        item: &str,
+        args: &[String],
+    ) -> Result<Vec<u8>, String> {
+        let entry = self
+            .storage_item(pallet, item)
+            .ok_or_else(|| format!("Storage item {}.{} not found", pallet, item))?;
+        if entry.keys.len() != args.len() {
+            return Err(format!(
+                "{}.{} takes {} key(s), got {}",
+                pallet,
+                item,
+                entry.keys.len(),
+                args.len()
+            ));
+        }
+        let pallet_name = self.pallet(pallet).map_or(pallet, |p| p.name.as_str());
+        let mut key = storage_value_key(pallet_name, &entry.name);
+        for (position, ((hasher, ty), arg)) in entry.keys.iter().zip(args).enumerate() {
+            let encoded = self.encode_text(arg, *ty).map_err(|e| {
+                format!(
+                    "Key {} ('{}') of {}.{} could not be encoded: {}",
+                    position + 1,
+                    arg,
+                    pallet,
+                    item,
+                    e
+                )
+            })?;
+            key.extend(hash_key(hasher, &encoded));
+        }
+        Ok(key)
+    }
+
+    /// Render the storage value `pallet.item` from its raw bytes, or from the
+    /// metadata default when the entry is unset. Byte arrays are shown as hex.
+    pub fn decode_storage(
+        &self,
+        pallet: &str,
+        item: &str,
+        bytes: Option<&[u8]>,
+    ) -> Result<String, String> {
+        let entry = self
+            .storage_item(pallet, item)
+            .ok_or_else(|| format!("Storage item {}.{} not found", pallet, item))?;
+        self.render(bytes.unwrap_or(&entry.default), entry.ty)
+            .map_err(|e| format!("Failed to decode {}.{}: {}", pallet, item, e))
+    }
+
+    /// Decode storage item `pallet.item` like [`Self::decode_storage`] and render only
+    /// the field at `path`: names or indices separated by dots, e.g. `data.free` of
+    /// `System.Account`. Variants are looked through, so `0` is the value of `Some(..)`.
+    pub fn decode_storage_field(
+        &self,
+        pallet: &str,
+        item: &str,
+        bytes: Option<&[u8]>,
+        path: &str,
+    ) -> Result<String, String> {
+        let entry = self
+            .storage_item(pallet, item)
+            .ok_or_else(|| format!("Storage item {}.{} not found", pallet, item))?;
+        let mut bytes = bytes.unwrap_or(&entry.default);
+        let value = scale_value::scale::decode_as_type(&mut bytes, entry.ty, &self.types)
+            .map_err(|e| format!("Failed to decode {}.{}: {}", pallet, item, e))?;
+        let mut field = &value;
+        for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+            field = child(field, segment).ok_or_else(|| {
+                format!("{}.{} has no field '{}' ({})", pallet, item, segment, path)
+            })?;
+        }
+        stringify(field)
+    }
+
+    /// Decode `bytes` as type `ty` and render it as text, showing byte arrays as hex.
+    fn render(&self, mut bytes: &[u8], ty: u32) -> Result<String, String> {
+        self.take(&mut bytes, ty)
+    }
+
+    /// Decode a value of type `ty` from the front of `bytes`, advancing past it, and
+    /// render it like [`Self::render`].
+    fn take(&self, bytes: &mut &[u8], ty: u32) -> Result<String, String> {
+        let value = scale_value::scale::decode_as_type(bytes, ty, &self.types)
+            .map_err(|e| e.to_string())?;
+        stringify(&value)
+    }
+
+    /// Decode a length-prefixed V4 extrinsic, as returned by `author_pendingExtrinsics`.
+    pub fn decode_extrinsic(&self, bytes: &[u8]) -> Result<DecodedExtrinsic, String> {
+        let parts = self.split_extrinsic(bytes)?;
+        let mut signer = None;
+        let mut extensions = Vec::new();
+        if let Some(signed) = &parts.signed {
+            if let Some(address_ty) = self.extrinsic.address_ty {
+                signer = Some(self.render(&signed.address, address_ty)?);
+            }
+            for (extension, data) in self.extrinsic.extensions.iter().zip(&signed.extensions) {
+                let value = self.render(data, extension.ty)?;
+                if value != "()" {
+                    extensions.push((extension.name.clone(), value));
+                }
+            }
+        }
+        let (pallet, call_ty) = self.call_pallet(&parts.call)?;
+        let call = self
+            .render(&parts.call[1..], call_ty)
+            .map_err(|e| format!("Failed to decode {} call: {}", pallet, e))?;
+        Ok(DecodedExtrinsic {
+            signer,
+            extensions,
+            call: format!("{}.{}", pallet, call),
+        })
+    }
+
+    /// Re-sign the signed extrinsic `bytes` with a new tip (and nonce), as an immortal
+    /// transaction of the same signer. `sign` signs the payload with the signer's sr25519
+    /// key, whose public key is `public_key`.
+    pub fn resign_extrinsic(
+        &self,
+        bytes: &[u8],
+        public_key: &[u8; 32],
+        resign: &Resign,
+        sign: impl Fn(&[u8]) -> [u8; 64],
+    ) -> Result<Vec<u8>, String> {
+        let parts = self.split_extrinsic(bytes)?;
+        let Some(SignedParts {
+            address,
+            extensions: data,
+        }) = parts.signed
+        else {
+            return Err("Only signed extrinsics can be re-signed".to_owned());
+        };
+        if address != self.account_address(public_key)? {
+            return Err("The extrinsic was signed by a different account".to_owned());
+        }
+
+        let mut extra = Vec::new();
+        let mut additional = Vec::new();
+        for (extension, data) in self.extrinsic.extensions.iter().zip(data) {
+            match extension.name.as_str() {
+                "CheckMortality" | "CheckEra" => extra.push(0),
+                "CheckNonce" => match resign.nonce {
+                    Some(nonce) => extra.extend(self.replace_field(
+                        &data,
+                        extension.ty,
+                        "nonce",
+                        Value::u128(u128::from(nonce)),
+                    )?),
+                    None => extra.extend(data),
+                },
+                "ChargeTransactionPayment" | "ChargeAssetTxPayment" => extra.extend(
+                    self.replace_field(&data, extension.ty, "tip", Value::u128(resign.tip))?,
+                ),
+                _ => extra.extend(data),
+            }
+            if self.is_unit(extension.additional_ty) {
+                continue;
+            }
+            match extension.name.as_str() {
+                "CheckSpecVersion" => resign.spec_version.encode_to(&mut additional),
+                "CheckTxVersion" => resign.transaction_version.encode_to(&mut additional),
+                // Immortal transactions commit to the genesis hash.
+                "CheckGenesis" | "CheckMortality" | "CheckEra" => {
+                    additional.extend(resign.genesis_hash);
+                }
+                // Mode 0 (disabled) signs no metadata hash.
+                "CheckMetadataHash" => additional.push(0),
+                other => {
+                    return Err(format!(
+                        "Signed extension '{}' is not supported for re-signing",
+                        other
+                    ))
+                }
+            }
+        }
+
+        let mut payload = parts.call.clone();
+        payload.extend(&extra);
+        payload.extend(&additional);
+        let signature = if payload.len() > 256 {
+            sign(&blake2_256(&payload))
+        } else {
+            sign(&payload)
+        };
+
+        let mut body = vec![0x84];
+        body.extend(address);
+        body.push(self.sr25519_signature_index()?);
+        body.extend(signature);
+        body.extend(extra);
+        body.extend(parts.call);
+        let mut extrinsic = Compact(body.len() as u32).encode();
+        extrinsic.extend(body);
+        Ok(extrinsic)
+    }
+
+    /// Split a length-prefixed V4 extrinsic into its parts.
+    fn split_extrinsic(&self, bytes: &[u8]) -> Result<ExtrinsicParts, String> {
+        let mut cursor = bytes;
+        Compact::<u32>::decode(&mut cursor)
+            .map_err(|e| format!("Invalid extrinsic length prefix: {}", e))?;
+        let [version, rest @ ..] = cursor else {
+            return Err("Extrinsic is empty".to_owned());
+        };
+        cursor = rest;
+        if version & 0x7f != 4 {
+            return Err(format!("Unsupported extrinsic version {}", version & 0x7f));
+        }
+
+        let mut signed = None;
+        if version & 0x80 != 0 {
+            let (Some(address_ty), Some(signature_ty)) =
+                (self.extrinsic.address_ty, self.extrinsic.signature_ty)
+            else {
+                return Err("Runtime metadata does not describe signed extrinsics".to_owned());
+            };
+            let address = self
+                .skip(&mut cursor, address_ty)
+                .map_err(|e| format!("Failed to decode signer: {}", e))?;
+            self.skip(&mut cursor, signature_ty)
+                .map_err(|e| format!("Failed to decode signature: {}", e))?;
+            let data = self
+                .extrinsic
+                .extensions
+                .iter()
+                .map(|extension| {
+                    self.skip(&mut cursor, extension.ty)
+                        .map_err(|e| format!("Failed to decode {}: {}", extension.name, e))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            signed = Some(SignedParts {
+                address,
+                extensions: data,
+            });
+        }
+        Ok(ExtrinsicParts {
+            signed,
+            call: cursor.to_vec(),
+        })
+    }
+
+    /// Name and call type of the pallet an encoded call belongs to.
+    fn call_pallet(&self, call: &[u8]) -> Result<(&str, u32), String> {
+        let pallet_index = call
+            .first()
+            .ok_or_else(|| "Extrinsic has no call".to_owned())?;
+        let pallet = self
+            .pallets
+            .iter()
+            .find(|p| p.index == *pallet_index)
+            .ok_or_else(|| format!("No pallet with index {} in runtime metadata", pallet_index))?;
+        let call_ty = pallet
+            .call_ty
+            .ok_or_else(|| format!("Pallet '{}' has no dispatchable calls", pallet.name))?;
+        Ok((&pallet.name, call_ty))
+    }
+
+    /// Bytes of a value of type `ty` at the front of `bytes`, advancing past it.
+    fn skip(&self, bytes: &mut &[u8], ty: u32) -> Result<Vec<u8>, String> {
+        let start = *bytes;
+        scale_value::scale::decode_as_type(bytes, ty, &self.types).map_err(|e| e.to_string())?;
+        Ok(start[..start.len() - bytes.len()].to_vec())
+    }
+
+    /// Re-encode `data` (of type `ty`) with `field` set to `value`. Newtypes and bare
+    /// values are replaced whole.
+    fn replace_field(
+        &self,
+        data: &[u8],
+        ty: u32,
+        field: &str,
+        value: Value<()>,
+    ) -> Result<Vec<u8>, String> {
+        let old = scale_value::scale::decode_as_type(&mut &data[..], ty, &self.types)
+            .map_err(|e| e.to_string())?
+            .remove_context();
+        let new = match old.value {
+            scale_value::ValueDef::Composite(scale_value::Composite::Named(fields)) => {
+                Value::named_composite(fields.into_iter().map(|(name, old)| {
+                    let replaced = if name == field { value.clone() } else { old };
+                    (name, replaced)
+                }))
+            }
+            scale_value::ValueDef::Composite(scale_value::Composite::Unnamed(fields))
+                if fields.len() == 1 =>
+            {
+                Value::unnamed_composite([value])
+            }
+            scale_value::ValueDef::Primitive(_) => value,
+            _ => return Err(format!("Cannot set '{}' in this signed extension", field)),
+        };
+        let mut encoded = Vec::new();
+        scale_value::scale::encode_as_type(&new, ty, &self.types, &mut encoded)
+            .map_err(|e| e.to_string())?;
+        Ok(encoded)
+    }
+
+    /// Whether `ty` encodes to nothing (e.g. `()`).
+    fn is_unit(&self, ty: u32) -> bool {
+        match self.types.resolve(ty).map(|t| &t.type_def) {
+            Some(scale_info::TypeDef::Tuple(tuple)) => tuple.fields.is_empty(),
+            Some(scale_info::TypeDef::Composite(composite)) => composite.fields.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Encoded address of the account with `public_key` (`MultiAddress::Id` or a bare
+    /// `AccountId32`).
+    fn account_address(&self, public_key: &[u8; 32]) -> Result<Vec<u8>, String> {
+        let ty = self
+            .extrinsic
+            .address_ty
+            .and_then(|ty| self.types.resolve(ty))
+            .ok_or_else(|| "Runtime metadata does not describe signed extrinsics".to_owned())?;
+        let mut address = Vec::new();
+        if let scale_info::TypeDef::Variant(variants) = &ty.type_def {
+            let id = variants
+                .variants
+                .iter()
+                .find(|v| v.name == "Id")
+                .ok_or_else(|| "Unsupported address type".to_owned())?;
+            address.push(id.index);
+        }
+        address.extend(public_key);
+        Ok(address)
+    }
+
+    /// Variant index of sr25519 signatures in the runtime's signature type.
+    fn sr25519_signature_index(&self) -> Result<u8, String> {
+        self.extrinsic
+            .signature_ty
+            .and_then(|ty| self.types.resolve(ty))
+            .and_then(|ty| match &ty.type_def {
+                scale_info::TypeDef::Variant(variants) => variants
+                    .variants
+                    .iter()
+                    .find(|v| v.name == "Sr25519")
+                    .map(|v| v.index),
+                _ => None,
+            })
+            .ok_or_else(|| "The runtime does not accept sr25519 signatures".to_owned())
+    }
+
+    fn runtime_api_method(&self, api: &str, method: &str) -> Result<&RuntimeApiMethod, String> {
+        if self.runtime_apis.is_empty() {
+            return Err(
+                "The runtime exposes no runtime API metadata (metadata V15 or later is required)"
+                    .to_owned(),
+            );
+        }
+        let methods: Vec<&RuntimeApiMethod> = self
+            .runtime_apis
+            .iter()
+            .filter(|m| m.api.eq_ignore_ascii_case(api))
+            .collect();
+        if methods.is_empty() {
+            let mut apis: Vec<&str> = self.runtime_apis.iter().map(|m| m.api.as_str()).collect();
+            apis.dedup();
+            return Err(format!(
+                "Runtime API '{}' not found. Available APIs: {}",
+                api,
+                apis.join(", ")
+            ));
+        }
+        methods
+            .iter()
+            .find(|m| m.name.eq_ignore_ascii_case(method))
+            .copied()
+            .ok_or_else(|| {
+                format!(
+                    "Method '{}' not found in {}. Available methods: {}",
+                    method,
+                    methods[0].api,
+                    methods
+                        .iter()
+                        .map(|m| m.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+
+    /// Encode a runtime API call as its `state_call` method name (`Api_method`) and
+    /// SCALE-encoded parameters. Arguments use the same syntax as [`Self::encode_call`].
+    pub fn encode_runtime_api_call(
+        &self,
+        api: &str,
+        method: &str,
+        args: &[String],
+    ) -> Result<(String, Vec<u8>), String> {
+        let entry = self.runtime_api_method(api, method)?;
+        if entry.inputs.len() != args.len() {
+            let names: Vec<&str> = entry.inputs.iter().map(|(n, _)| n.as_str()).collect();
+            return Err(format!(
+                "{}.{} expects {} argument(s) ({}), got {}",
+                entry.api,
+                entry.name,
+                entry.inputs.len(),
+                names.join(", "),
+                args.len()
+            ));
+        }
+        let mut encoded = Vec::new();
+        for ((name, ty), arg) in entry.inputs.iter().zip(args) {
+            let bytes = self.encode_text(arg, *ty).map_err(|e| {
+                format!(
+                    "Argument '{}' ('{}') of {}.{} could not be encoded: {}",
+                    name, arg, entry.api, entry.name, e
+                )
+            })?;
+            encoded.extend(bytes);
+        }
+        Ok((format!("{}_{}", entry.api, entry.name), encoded))
+    }
+
+    /// Render the SCALE-encoded result of a runtime API call.
+    pub fn decode_runtime_api_output(
+        &self,
+        api: &str,
+        method: &str,
+        bytes: &[u8],
+    ) -> Result<String, String> {
+        let entry = self.runtime_api_method(api, method)?;
+        self.render(bytes, entry.output).map_err(|e| {
+            format!(
+                "Failed to decode {}.{} output: {}",
+                entry.api, entry.name, e
+            )
+        })
+    }
+
+    fn pallet(&self, pallet: &str) -> Option<&PalletInfo> {
+        self.pallets
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(pallet))
+    }
+
+    /// Encode a textual argument as type `ty`; see [`encode_text`].
+    fn encode_text(&self, text: &str, ty: u32) -> Result<Vec<u8>, scale_value::scale::EncodeError> {
+        encode_text(text, ty, &self.types)
+    }
+
+    /// Resolve `pallet.function` to (pallet index, call index, field type ids).
+    fn call_variant(&self, pallet: &str, function: &str) -> Result<(u8, u8, Vec<u32>), String> {
+        let info = self
+            .pallet(pallet)
+            .ok_or_else(|| format!("Pallet '{}' not found in runtime metadata", pallet))?;
+        let call_ty = info
+            .call_ty
+            .ok_or_else(|| format!("Pallet '{}' has no dispatchable calls", info.name))?;
+        let ty = self
+            .types
+            .resolve(call_ty)
+            .ok_or_else(|| format!("Call type for pallet '{}' missing", info.name))?;
+        let scale_info::TypeDef::Variant(variants) = &ty.type_def else {
+            return Err(format!(
+                "Call type for pallet '{}' is not an enum",
+                info.name
+            ));
+        };
+        let normalized = function.replace('-', "_");
+        let variant = variants
+            .variants
+            .iter()
+            .find(|v| v.name.eq_ignore_ascii_case(&normalized))
+            .ok_or_else(|| {
+                format!(
+                    "Call with name {} not found in pallet {}",
+                    function, info.name
+                )
+            })?;
+        Ok((
+            info.index,
+            variant.index,
+            variant.fields.iter().map(|f| f.ty.id).collect(),
+        ))
+    }
+
+    /// Field names of the struct taken as argument `position` of `pallet.function`,
+    /// for building values whose shape differs between runtimes (e.g. `IdentityInfo`).
+    pub fn arg_fields(
+        &self,
+        pallet: &str,
+        function: &str,
+        position: usize,
+    ) -> Result<Vec<String>, String> {
+        let (_, _, fields) = self.call_variant(pallet, function)?;
+        let ty = fields
+            .get(position)
+            .and_then(|ty| self.types.resolve(*ty))
+            .ok_or_else(|| format!("{}.{} has no argument {}", pallet, function, position + 1))?;
+        let scale_info::TypeDef::Composite(composite) = &ty.type_def else {
+            return Err(format!(
+                "Argument {} of {}.{} is not a struct",
+                position + 1,
+                pallet,
+                function
+            ));
+        };
+        Ok(composite
+            .fields
+            .iter()
+            .filter_map(|f| f.name.clone())
+            .collect())
+    }
+
+    /// SCALE-encode `pallet.function(args...)` as a `RuntimeCall`.
+    ///
+    /// Arguments use the same syntax as `call_chain`: numbers, `0x` hex, SS58 addresses,
+    /// `Variant(..)`, `{ field: value }`, `[a, b]`. Bare words are treated as strings.
+    pub fn encode_call(
+        &self,
+        pallet: &str,
+        function: &str,
+        args: &[String],
+    ) -> Result<Vec<u8>, String> {
+        let args: Vec<CallArg<'_>> = args.iter().map(|a| CallArg::Text(a)).collect();
+        self.encode_call_with(pallet, function, &args)
+    }
+
+    /// Encode a call given either as raw `call_data` hex or as `pallet.function(args)`.
+    pub fn resolve_call(
+        &self,
+        call_data: Option<&str>,
+        pallet: Option<&str>,
+        function: Option<&str>,
+        args: &[String],
+    ) -> Result<Vec<u8>, String> {
+        match (call_data, pallet, function) {
+            (Some(data), None, None) if args.is_empty() => decode_hex(data),
+            (None, Some(pallet), Some(function)) => self.encode_call(pallet, function, args),
+            _ => Err(
+                "Provide either 'call_data' or both 'pallet' and 'function' for the call"
+                    .to_owned(),
+            ),
+        }
+    }
+
+    /// Encode a call whose single argument is a list of already-encoded calls
+    /// (e.g. `Utility.batch_all`).
+    pub fn encode_call_with_calls(
+        &self,
+        pallet: &str,
+        function: &str,
+        calls: &[Vec<u8>],
+    ) -> Result<Vec<u8>, String> {
+        let mut list = Vec::new();
+        Compact(calls.len() as u32).encode_to(&mut list);
+        for call in calls {
+            list.extend_from_slice(call);
+        }
+        self.encode_call_with(pallet, function, &[CallArg::Raw(&list)])
+    }
+
+    /// Encode a call from a mix of textual and pre-encoded arguments.
+    pub fn encode_call_with(
+        &self,
+        pallet: &str,
+        function: &str,
+        args: &[CallArg<'_>],
+    ) -> Result<Vec<u8>, String> {
+        let (pallet_index, call_index, fields) = self.call_variant(pallet, function)?;
+        if fields.len() != args.len() {
+            return Err(format!(
+                "{}.{} expects {} argument(s), got {}",
+                pallet,
+                function,
+                fields.len(),
+                args.len()
+            ));
+        }
+
+        let mut encoded = vec![pallet_index, call_index];
+        for (position, (arg, ty)) in args.iter().zip(fields).enumerate() {
+            match arg {
+                CallArg::Raw(bytes) => encoded.extend_from_slice(bytes),
+                CallArg::Text(text) => {
+                    let bytes = self.encode_text(text, ty).map_err(|e| {
+                        format!(
+                            "Argument {} ('{}') of {}.{} could not be encoded: {}",
+                            position + 1,
+                            text,
+                            pallet,
+                            function,
+                            e
+                        )
+                    })?;
+                    encoded.extend(bytes);
+                }
+            }
+        }
+        Ok(encoded)
+    }
+}
+
+/// A call argument for [`ChainMetadata::encode_call_with`].
+#[derive(Debug, Clone, Copy)]
+pub enum CallArg<'a> {
+    /// CLI-style value, encoded against the parameter's type.
+    Text(&'a str),
+    /// Bytes already SCALE-encoded for the parameter (e.g. a nested `RuntimeCall`).
+    Raw(&'a [u8]),
+}
+
+/// Render `value` as text, showing byte arrays as hex.
+fn stringify<T>(value: &Value<T>) -> Result<String, String> {
+    let mut rendered = String::new();
+    scale_value::stringify::to_writer_custom()
+        .add_custom_formatter(|v, w| scale_value::stringify::custom_formatters::format_hex(v, w))
+        .write(value, &mut rendered)
+        .map_err(|e| e.to_string())?;
+    Ok(rendered)
+}
+
+/// Field `segment` (a name or an index) of a composite or variant value.
+fn child<'a, T>(value: &'a Value<T>, segment: &str) -> Option<&'a Value<T>> {
+    let fields = match &value.value {
+        scale_value::ValueDef::Composite(fields) => fields,
+        scale_value::ValueDef::Variant(variant) => &variant.values,
+        _ => return None,
+    };
+    match fields {
+        scale_value::Composite::Named(fields) => fields
+            .iter()
+            .find(|(name, _)| name == segment)
+            .map(|(_, value)| value),
+        scale_value::Composite::Unnamed(values) => values.get(segment.parse::<usize>().ok()?),
+    }
+}
+
+/// Convert a CLI-style value `text` (e.g. `Ok(false)`) of type `ty` of `types` to
+/// typed JSON, by encoding it as the type and decoding it back.
+///
+/// Variants become `{"Name": fields}` (unit variants just `"Name"`), integers become
+/// numbers when they fit in 64 bits and strings otherwise, and byte arrays become hex.
+pub fn typed_json(
+    text: &str,
+    ty: u32,
+    types: &PortableRegistry,
+) -> Result<serde_json::Value, String> {
+    let bytes = encode_text(text, ty, types)
+        .or_else(|e| encode_text(&with_unit_parens(text), ty, types).map_err(|_| e))
+        .map_err(|e| e.to_string())?;
+    decode_json(&mut &bytes[..], ty, types)
+}
+
+/// Decode a value of type `ty` of `types` from the start of `bytes` as typed JSON,
+/// like [`typed_json`].
+pub fn decode_json(
+    bytes: &mut &[u8],
+    ty: u32,
+    types: &PortableRegistry,
+) -> Result<serde_json::Value, String> {
+    let value = scale_value::scale::decode_as_type(bytes, ty, types).map_err(|e| e.to_string())?;
+    Ok(json_of(&value, types))
+}
+
+/// `text` with `()` after nested unit variant names (`Err(NotOwner)` becomes
+/// `Err(NotOwner())`), which the value parser only accepts at the top level.
+fn with_unit_parens(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.char_indices().peekable();
+    let mut quoted = false;
+    while let Some((start, c)) = chars.next() {
+        if quoted || !c.is_ascii_alphanumeric() {
+            if c == '"' {
+                quoted = !quoted;
+            }
+            out.push(c);
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        while let Some(&(i, next)) = chars.peek() {
+            if !(next.is_ascii_alphanumeric() || next == '_') {
+                break;
+            }
+            end = i + next.len_utf8();
+            chars.next();
+        }
+        let word = &text[start..end];
+        out.push_str(word);
+        let follows = text[end..].trim_start().chars().next();
+        if is_variant_name(word) && !matches!(follows, Some('(' | '{' | ':')) {
+            out.push_str("()");
+        }
+    }
+    out
+}
+
+/// JSON form of a decoded `value`, see [`typed_json`].
+fn json_of(value: &Value<u32>, types: &PortableRegistry) -> serde_json::Value {
+    use scale_info::{TypeDef, TypeDefPrimitive};
+    use scale_value::{Composite, Primitive, ValueDef};
+    use serde_json::Value as Json;
+
+    let fields = |composite: &Composite<u32>| match composite {
+        Composite::Named(fields) if !fields.is_empty() => Json::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), json_of(value, types)))
+                .collect(),
+        ),
+        Composite::Named(_) => Json::Null,
+        Composite::Unnamed(values) => match values.as_slice() {
+            [] => Json::Null,
+            [value] => json_of(value, types),
+            values => Json::Array(values.iter().map(|v| json_of(v, types)).collect()),
+        },
+    };
+    let ty = types.resolve(value.context).map(|ty| &ty.type_def);
+    match &value.value {
+        ValueDef::Composite(composite) => {
+            let element = match ty {
+                Some(TypeDef::Sequence(seq)) => Some(seq.type_param.id),
+                Some(TypeDef::Array(array)) => Some(array.type_param.id),
+                _ => None,
+            };
+            let is_u8 = element
+                .and_then(|id| types.resolve(id))
+                .is_some_and(|ty| matches!(ty.type_def, TypeDef::Primitive(TypeDefPrimitive::U8)));
+            let values: Vec<&Value<u32>> = composite.values().collect();
+            if is_u8 {
+                let bytes: Option<Vec<u8>> = values
+                    .iter()
+                    .map(|v| v.as_u128().and_then(|n| u8::try_from(n).ok()))
+                    .collect();
+                if let Some(bytes) = bytes {
+                    return Json::String(to_hex(&bytes));
+                }
+            }
+            match ty {
+                // Sequences and arrays stay lists, even with one element.
+                Some(TypeDef::Sequence(_) | TypeDef::Array(_)) => {
+                    Json::Array(values.iter().map(|v| json_of(v, types)).collect())
+                }
+                _ => fields(composite),
+            }
+        }
+        ValueDef::Variant(variant) => {
+            if variant.values.is_empty() {
+                return Json::String(variant.name.clone());
+            }
+            let mut object = serde_json::Map::new();
+            object.insert(variant.name.clone(), fields(&variant.values));
+            Json::Object(object)
+        }
+        ValueDef::Primitive(primitive) => match primitive {
+            Primitive::Bool(b) => Json::Bool(*b),
+            Primitive::Char(c) => Json::String(c.to_string()),
+            Primitive::String(s) => Json::String(s.clone()),
+            Primitive::U128(n) => u64::try_from(*n)
+                .map(Json::from)
+                .unwrap_or_else(|_| Json::String(n.to_string())),
+            Primitive::I128(n) => i64::try_from(*n)
+                .map(Json::from)
+                .unwrap_or_else(|_| Json::String(n.to_string())),
+            Primitive::U256(bytes) | Primitive::I256(bytes) => Json::String(to_hex(bytes)),
+        },
+        ValueDef::BitSequence(bits) => Json::Array(bits.iter().map(Json::Bool).collect()),
+    }
+}
+
+/// Read the raw bytes of a storage entry, if set.
+pub async fn fetch_storage(pool: &RpcPool, url: &str, key: &[u8]) -> PopMcpResult<Option<Vec<u8>>> {
+    fetch_storage_at(pool, url, key, None).await
+}
+
+/// Read the raw bytes of a storage entry at block hash `at` (default: best), if set.
+pub async fn fetch_storage_at(
+    pool: &RpcPool,
+    url: &str,
+    key: &[u8],
+    at: Option<&str>,
+) -> PopMcpResult<Option<Vec<u8>>> {
+    let mut params = vec![serde_json::Value::String(to_hex(key))];
+    if let Some(hash) = at {
+        params.push(serde_json::Value::String(hash.to_owned()));
+    }
+    let value = pool.request(url, "state_getStorage", params).await?;
+    match value.as_str() {
+        Some(hex) => decode_hex(hex).map(Some).map_err(PopMcpError::Rpc),
+        None => Ok(None),
+    }
+}
+
+/// Encode a CLI-style argument as type `ty` of `types`, retrying as a plain string
+/// (e.g. a `Vec<u8>` name that happens to look like a variant).
+pub fn encode_text(
+    text: &str,
+    ty: u32,
+    types: &PortableRegistry,
+) -> Result<Vec<u8>, scale_value::scale::EncodeError> {
+    let mut bytes = Vec::new();
+    let value = parse_value(text);
+    match scale_value::scale::encode_as_type(&value, ty, types, &mut bytes) {
+        Ok(()) => Ok(bytes),
+        Err(e) => {
+            let fallback = Value::string(text);
+            if value == fallback {
+                return Err(e);
+            }
+            bytes.clear();
+            scale_value::scale::encode_as_type(&fallback, ty, types, &mut bytes)
+                .map(|()| bytes)
+                .map_err(|_| e)
+        }
+    }
+}
+
+/// Parse a CLI-style argument into a value, falling back to a plain string.
+pub fn parse_value(arg: &str) -> Value<()> {
+    let (parsed, rest) = scale_value::stringify::from_str_custom()
+        .add_custom_parser(custom_parsers::parse_hex)
+        .add_custom_parser(custom_parsers::parse_ss58)
+        .parse(arg.trim());
+    match parsed {
+        Ok(value) if rest.trim().is_empty() => value,
+        // Unit variants such as `None` or `Root` don't parse without `()`.
+        _ if is_variant_name(arg.trim()) => {
+            Value::unnamed_variant(arg.trim(), Vec::<Value<()>>::new())
+        }
+        _ => Value::string(arg),
+    }
+}
+
+fn is_variant_name(s: &str) -> bool {
+    s.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// 256-bit BLAKE2b hash, as used for preimage and call hashes.
+pub fn blake2_256(data: &[u8]) -> [u8; 32] {
+    use blake2::digest::{consts::U32, Digest};
+    blake2::Blake2b::<U32>::digest(data).into()
+}
+
+/// 128-bit xxHash (`twox_128`), as used for storage prefixes.
+pub fn twox_128(data: &[u8]) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    out[..8].copy_from_slice(&xxhash_rust::xxh64::xxh64(data, 0).to_le_bytes());
+    out[8..].copy_from_slice(&xxhash_rust::xxh64::xxh64(data, 1).to_le_bytes());
+    out
+}
+
+/// Storage key of a plain (non-map) storage value.
+pub fn storage_value_key(pallet: &str, item: &str) -> Vec<u8> {
+    let mut key = twox_128(pallet.as_bytes()).to_vec();
+    key.extend_from_slice(&twox_128(item.as_bytes()));
+    key
+}
+
+/// Hashers and types of the keys of a storage map whose key type is `key`.
+///
+/// Maps with several hashers (double and n-maps) key by a tuple of their key types.
+fn map_keys(
+    types: &PortableRegistry,
+    hashers: &[StorageHasher],
+    key: u32,
+) -> Vec<(StorageHasher, u32)> {
+    let tuple = match types.resolve(key).map(|ty| &ty.type_def) {
+        Some(scale_info::TypeDef::Tuple(tuple)) if hashers.len() > 1 => {
+            tuple.fields.iter().map(|field| field.id).collect()
+        }
+        _ => vec![key],
+    };
+    hashers.iter().cloned().zip(tuple).collect()
+}
+
+/// A storage map key part: `encoded` hashed (and appended, for concat hashers).
+fn hash_key(hasher: &StorageHasher, encoded: &[u8]) -> Vec<u8> {
+    let blake2_128 = || {
+        use blake2::digest::{consts::U16, Digest};
+        blake2::Blake2b::<U16>::digest(encoded).to_vec()
+    };
+    let twox_64 = || xxhash_rust::xxh64::xxh64(encoded, 0).to_le_bytes().to_vec();
+    match hasher {
+        StorageHasher::Blake2_128 => blake2_128(),
+        StorageHasher::Blake2_256 => blake2_256(encoded).to_vec(),
+        StorageHasher::Blake2_128Concat => [blake2_128(), encoded.to_vec()].concat(),
+        StorageHasher::Twox128 => twox_128(encoded).to_vec(),
+        StorageHasher::Twox256 => {
+            let mut out = twox_128(encoded).to_vec();
+            out.extend_from_slice(&xxhash_rust::xxh64::xxh64(encoded, 2).to_le_bytes());
+            out.extend_from_slice(&xxhash_rust::xxh64::xxh64(encoded, 3).to_le_bytes());
+            out
+        }
+        StorageHasher::Twox64Concat => [twox_64(), encoded.to_vec()].concat(),
+        StorageHasher::Identity => encoded.to_vec(),
+    }
+}
+
+/// Hex-encode bytes with a `0x` prefix.
+pub fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Decode `0x`-prefixed (or bare) hex.
+pub fn decode_hex(input: &str) -> Result<Vec<u8>, String> {
+    let trimmed = input.trim();
+    hex::decode(trimmed.strip_prefix("0x").unwrap_or(trimmed))
+        .map_err(|e| format!("Invalid hex '{}': {}", trimmed, e))
+}
+
+#[cfg(test)]
+#[allow(clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_value_understands_cli_syntax() {
+        assert_eq!(parse_value("42"), Value::u128(42));
+        assert_eq!(parse_value("true"), Value::bool(true));
+        assert_eq!(
+            parse_value("0x0102"),
+            Value::unnamed_composite(vec![Value::u128(1), Value::u128(2)])
+        );
+        assert_eq!(parse_value("hello world"), Value::string("hello world"));
+        assert_eq!(
+            parse_value("None"),
+            Value::unnamed_variant("None", Vec::<Value<()>>::new())
+        );
+    }
+
+    #[allow(dead_code, non_camel_case_types)]
+    #[derive(scale_info::TypeInfo)]
+    enum BalancesCall {
+        transfer_keep_alive { dest: [u8; 32], value: u128 },
+        force_set_balance { who: [u8; 32], new_free: u128 },
+    }
+
+    #[allow(dead_code)]
+    #[derive(scale_info::TypeInfo)]
+    enum MultiAddress {
+        Id([u8; 32]),
+    }
+
+    #[allow(dead_code)]
+    #[derive(scale_info::TypeInfo)]
+    enum MultiSignature {
+        Sr25519([u8; 64]),
+    }
+
+    fn metadata() -> ChainMetadata {
+        let mut registry = scale_info::Registry::new();
+        let call_ty = registry
+            .register_type(&scale_info::meta_type::<BalancesCall>())
+            .id;
+        let moment_ty = registry.register_type(&scale_info::meta_type::<u64>()).id;
+        let account_ty = registry
+            .register_type(&scale_info::meta_type::<[u8; 32]>())
+            .id;
+        let nonce_ty = registry.register_type(&scale_info::meta_type::<u32>()).id;
+        let address_ty = registry
+            .register_type(&scale_info::meta_type::<MultiAddress>())
+            .id;
+        let signature_ty = registry
+            .register_type(&scale_info::meta_type::<MultiSignature>())
+            .id;
+        let unit_ty = registry.register_type(&scale_info::meta_type::<()>()).id;
+        let compact_nonce_ty = registry
+            .register_type(&scale_info::meta_type::<Compact<u32>>())
+            .id;
+        let compact_tip_ty = registry
+            .register_type(&scale_info::meta_type::<Compact<u128>>())
+            .id;
+        ChainMetadata {
+            types: registry.into(),
+            runtime_apis: vec![RuntimeApiMethod {
+                api: "AccountNonceApi".to_owned(),
+                name: "account_nonce".to_owned(),
+                inputs: vec![("account".to_owned(), account_ty)],
+                output: nonce_ty,
+            }],
+            extrinsic: ExtrinsicInfo {
+                address_ty: Some(address_ty),
+                signature_ty: Some(signature_ty),
+                extensions: vec![
+                    SignedExtension {
+                        name: "CheckSpecVersion".to_owned(),
+                        ty: unit_ty,
+                        additional_ty: nonce_ty,
+                    },
+                    SignedExtension {
+                        name: "CheckNonce".to_owned(),
+                        ty: compact_nonce_ty,
+                        additional_ty: unit_ty,
+                    },
+                    SignedExtension {
+                        name: "ChargeTransactionPayment".to_owned(),
+                        ty: compact_tip_ty,
+                        additional_ty: unit_ty,
+                    },
+                ],
+            },
+            pallets: vec![
+                PalletInfo {
+                    name: "Balances".to_owned(),
+                    index: 10,
+                    call_ty: Some(call_ty),
+                    storage: Vec::new(),
+                    constants: Vec::new(),
+                },
+                PalletInfo {
+                    name: "Timestamp".to_owned(),
+                    index: 3,
+                    call_ty: None,
+                    storage: vec![StorageItem {
+                        name: "Now".to_owned(),
+                        keys: Vec::new(),
+                        ty: moment_ty,
+                        default: vec![0; 8],
+                    }],
+                    constants: Vec::new(),
+                },
+                PalletInfo {
+                    name: "System".to_owned(),
+                    index: 0,
+                    call_ty: None,
+                    storage: vec![StorageItem {
+                        name: "Account".to_owned(),
+                        keys: vec![(StorageHasher::Blake2_128Concat, account_ty)],
+                        ty: nonce_ty,
+                        default: vec![0; 4],
+                    }],
+                    constants: vec![Constant {
+                        name: "SS58Prefix".to_owned(),
+                        ty: nonce_ty,
+                        value: 42u32.encode(),
+                    }],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn decode_extrinsic_renders_signer_extensions_and_call() {
+        let metadata = metadata();
+        let Ok(call) = metadata.encode_call(
+            "Balances",
+            "transfer_keep_alive",
+            &["0x".to_owned() + &"11".repeat(32), "1000".to_owned()],
+        ) else {
+            panic!("call should encode");
+        };
+        let mut body = vec![0x84, 0];
+        body.extend([0xd4; 32]);
+        body.push(0);
+        body.extend([0; 64]);
+        Compact(5u32).encode_to(&mut body);
+        Compact(0u128).encode_to(&mut body);
+        body.extend(&call);
+        let mut signed = Compact(body.len() as u32).encode();
+        signed.extend(&body);
+        let Ok(decoded) = metadata.decode_extrinsic(&signed) else {
+            panic!("signed extrinsic should decode");
+        };
+        assert_eq!(decoded.signer, Some(format!("Id (0x{})", "D4".repeat(32))));
+        assert_eq!(
+            decoded.extensions,
+            vec![
+                ("CheckNonce".to_owned(), "5".to_owned()),
+                ("ChargeTransactionPayment".to_owned(), "0".to_owned())
+            ]
+        );
+        assert_eq!(
+            decoded.call,
+            format!(
+                "Balances.transfer_keep_alive {{ dest: 0x{}, value: 1000 }}",
+                "11".repeat(32)
+            )
+        );
+
+        let mut unsigned = Compact(call.len() as u32 + 1).encode();
+        unsigned.push(0x04);
+        unsigned.extend(&call);
+        let Ok(decoded) = metadata.decode_extrinsic(&unsigned) else {
+            panic!("unsigned extrinsic should decode");
+        };
+        assert_eq!(decoded.signer, None);
+        assert!(decoded.extensions.is_empty());
+        assert!(metadata.decode_extrinsic(&[8, 0x05, 10]).is_err());
+    }
+
+    #[test]
+    fn resign_extrinsic_sets_tip_and_nonce() -> Result<(), String> {
+        let metadata = metadata();
+        let keypair = crate::accounts::keypair_from_suri("//Alice")?;
+        let public_key = keypair.public.to_bytes();
+        let call = metadata.encode_call(
+            "Balances",
+            "transfer_keep_alive",
+            &["0x".to_owned() + &"11".repeat(32), "1000".to_owned()],
+        )?;
+        let mut body = vec![0x84, 0];
+        body.extend(public_key);
+        body.push(0);
+        body.extend([0; 64]);
+        Compact(5u32).encode_to(&mut body);
+        Compact(0u128).encode_to(&mut body);
+        body.extend(&call);
+        let mut stuck = Compact(body.len() as u32).encode();
+        stuck.extend(&body);
+
+        let resign = Resign {
+            tip: 7,
+            nonce: Some(9),
+            spec_version: 100,
+            transaction_version: 1,
+            genesis_hash: [0; 32],
+        };
+        let sign = |payload: &[u8]| keypair.sign_simple(b"substrate", payload).to_bytes();
+        let resigned = metadata.resign_extrinsic(&stuck, &public_key, &resign, sign)?;
+        let decoded = metadata.decode_extrinsic(&resigned)?;
+        assert_eq!(
+            decoded.extensions,
+            vec![
+                ("CheckNonce".to_owned(), "9".to_owned()),
+                ("ChargeTransactionPayment".to_owned(), "7".to_owned())
+            ]
+        );
+
+        let mut payload = call.clone();
+        Compact(9u32).encode_to(&mut payload);
+        Compact(7u128).encode_to(&mut payload);
+        100u32.encode_to(&mut payload);
+        // Length prefix, version byte, MultiAddress::Id and the signature variant byte.
+        let signature_at = resigned.len() - body.len() + 1 + 33 + 1;
+        let signature =
+            schnorrkel::Signature::from_bytes(&resigned[signature_at..signature_at + 64])
+                .map_err(|e| e.to_string())?;
+        assert!(keypair
+            .public
+            .verify_simple(b"substrate", &payload, &signature)
+            .is_ok());
+
+        let bob = crate::accounts::keypair_from_suri("//Bob")?
+            .public
+            .to_bytes();
+        assert!(metadata
+            .resign_extrinsic(&stuck, &bob, &resign, sign)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn encode_call_prefixes_pallet_and_call_index() {
+        let encoded = metadata().encode_call(
+            "balances",
+            "transfer_keep_alive",
+            &[
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_owned(),
+                "1000".to_owned(),
+            ],
+        );
+        let Ok(encoded) = encoded else {
+            panic!("encode failed: {encoded:?}");
+        };
+        assert_eq!(&encoded[..2], &[10, 0]);
+        assert_eq!(encoded.len(), 2 + 32 + 16);
+        assert_eq!(&encoded[34..36], &[0xe8, 0x03]);
+    }
+
+    #[test]
+    fn runtime_api_call_round_trip() {
+        let metadata = metadata();
+        let encoded = metadata.encode_runtime_api_call(
+            "accountnonceapi",
+            "account_nonce",
+            &["0x".to_owned() + &"01".repeat(32)],
+        );
+        let Ok((method, params)) = encoded else {
+            panic!("encode failed: {encoded:?}");
+        };
+        assert_eq!(method, "AccountNonceApi_account_nonce");
+        assert_eq!(params, vec![1; 32]);
+        assert!(metadata.has_runtime_api("AccountNonceApi", "account_nonce"));
+        assert!(!metadata.has_runtime_api("ReviveApi", "get_storage"));
+        assert_eq!(
+            metadata.decode_runtime_api_output("AccountNonceApi", "account_nonce", &[7, 0, 0, 0]),
+            Ok("7".to_owned())
+        );
+    }
+
+    #[test]
+    fn runtime_api_call_reports_unknown_names_and_arity() {
+        let metadata = metadata();
+        let Err(api) = metadata.encode_runtime_api_call("Core", "version", &[]) else {
+            panic!("unknown API accepted");
+        };
+        assert!(api.contains("Available APIs: AccountNonceApi"));
+        let Err(method) = metadata.encode_runtime_api_call("AccountNonceApi", "nonce", &[]) else {
+            panic!("unknown method accepted");
+        };
+        assert!(method.contains("Available methods: account_nonce"));
+        let Err(arity) = metadata.encode_runtime_api_call("AccountNonceApi", "account_nonce", &[])
+        else {
+            panic!("missing argument accepted");
+        };
+        assert!(arity.contains("expects 1 argument(s) (account), got 0"));
+    }
+
+    #[test]
+    fn encode_call_validates_names_and_arity() {
+        let metadata = metadata();
+        assert!(metadata.has_call("Balances", "force_set_balance"));
+        assert!(!metadata.has_call("Timestamp", "set"));
+        assert!(!metadata.has_pallet("Scheduler"));
+        assert!(metadata
+            .encode_call("Balances", "transfer_keep_alive", &["1".to_owned()])
+            .is_err());
+        assert!(metadata.encode_call("Balances", "burn", &[]).is_err());
+    }
+
+    #[test]
+    fn encode_call_with_calls_builds_compact_list() {
+        let mut registry = scale_info::Registry::new();
+        #[allow(dead_code, non_camel_case_types)]
+        #[derive(scale_info::TypeInfo)]
+        enum UtilityCall {
+            batch_all { calls: Vec<u8> },
+        }
+        let call_ty = registry
+            .register_type(&scale_info::meta_type::<UtilityCall>())
+            .id;
+        let metadata = ChainMetadata {
+            types: registry.into(),
+            runtime_apis: Vec::new(),
+            extrinsic: ExtrinsicInfo::default(),
+            pallets: vec![PalletInfo {
+                name: "Utility".to_owned(),
+                index: 40,
+                call_ty: Some(call_ty),
+                storage: Vec::new(),
+                constants: Vec::new(),
+            }],
+        };
+        let encoded =
+            metadata.encode_call_with_calls("Utility", "batch_all", &[vec![1, 2], vec![3, 4]]);
+        assert_eq!(encoded, Ok(vec![40, 0, 8, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn encode_call_with_mixes_text_and_raw_args() {
+        #[allow(dead_code, non_camel_case_types)]
+        #[derive(scale_info::TypeInfo)]
+        enum SchedulerCall {
+            schedule_after {
+                after: u32,
+                maybe_periodic: Option<(u32, u32)>,
+                priority: u8,
+                call: Vec<u8>,
+            },
+        }
+        let mut registry = scale_info::Registry::new();
+        let call_ty = registry
+            .register_type(&scale_info::meta_type::<SchedulerCall>())
+            .id;
+        let metadata = ChainMetadata {
+            types: registry.into(),
+            runtime_apis: Vec::new(),
+            extrinsic: ExtrinsicInfo::default(),
+            pallets: vec![PalletInfo {
+                name: "Scheduler".to_owned(),
+                index: 1,
+                call_ty: Some(call_ty),
+                storage: Vec::new(),
+                constants: Vec::new(),
+            }],
+        };
+        let inner = [9, 9];
+        let once = metadata.encode_call_with(
+            "Scheduler",
+            "schedule_after",
+            &[
+                CallArg::Text("5"),
+                CallArg::Text("None"),
+                CallArg::Text("127"),
+                CallArg::Raw(&inner),
+            ],
+        );
+        assert_eq!(once, Ok(vec![1, 0, 5, 0, 0, 0, 0, 127, 9, 9]));
+        let periodic = metadata.encode_call_with(
+            "Scheduler",
+            "schedule_after",
+            &[
+                CallArg::Text("5"),
+                CallArg::Text("Some((10, 3))"),
+                CallArg::Text("0"),
+                CallArg::Raw(&inner),
+            ],
+        );
+        assert_eq!(
+            periodic,
+            Ok(vec![1, 0, 5, 0, 0, 0, 1, 10, 0, 0, 0, 3, 0, 0, 0, 0, 9, 9])
+        );
+    }
+
+    #[test]
+    fn arg_fields_lists_struct_fields() {
+        #[allow(dead_code)]
+        #[derive(scale_info::TypeInfo)]
+        enum Data {
+            None,
+            Raw2([u8; 2]),
+        }
+        #[allow(dead_code)]
+        #[derive(scale_info::TypeInfo)]
+        struct IdentityInfo {
+            additional: Vec<(Data, Data)>,
+            display: Data,
+            web: Data,
+        }
+        #[allow(dead_code, non_camel_case_types)]
+        #[derive(scale_info::TypeInfo)]
+        enum IdentityCall {
+            set_identity { info: IdentityInfo },
+        }
+        let mut registry = scale_info::Registry::new();
+        let call_ty = registry
+            .register_type(&scale_info::meta_type::<IdentityCall>())
+            .id;
+        let metadata = ChainMetadata {
+            types: registry.into(),
+            runtime_apis: Vec::new(),
+            extrinsic: ExtrinsicInfo::default(),
+            pallets: vec![PalletInfo {
+                name: "Identity".to_owned(),
+                index: 7,
+                call_ty: Some(call_ty),
+                storage: Vec::new(),
+                constants: Vec::new(),
+            }],
+        };
+        assert_eq!(
+            metadata.arg_fields("Identity", "set_identity", 0),
+            Ok(vec![
+                "additional".to_owned(),
+                "display".to_owned(),
+                "web".to_owned()
+            ])
+        );
+        assert!(metadata.arg_fields("Identity", "set_identity", 1).is_err());
+        let encoded = metadata.encode_call(
+            "Identity",
+            "set_identity",
+            &["{ additional: (), display: Raw2(0x6869), web: None() }".to_owned()],
+        );
+        assert_eq!(encoded, Ok(vec![7, 0, 0, 1, 0x68, 0x69, 0]));
+    }
+
+    #[test]
+    fn decode_storage_falls_back_to_default() {
+        let metadata = metadata();
+        assert!(metadata.has_storage("Timestamp", "now"));
+        assert_eq!(
+            metadata.decode_storage("Timestamp", "Now", None),
+            Ok("0".to_owned())
+        );
+        assert_eq!(
+            metadata.decode_storage("Timestamp", "Now", Some(&42u64.encode())),
+            Ok("42".to_owned())
+        );
+        assert!(metadata
+            .decode_storage("Balances", "TotalIssuance", None)
+            .is_err());
+    }
+
+    #[test]
+    fn decode_storage_field_renders_nested_fields() {
+        #[derive(scale_info::TypeInfo, Encode)]
+        struct AccountData {
+            free: u128,
+            reserved: u128,
+        }
+        #[derive(scale_info::TypeInfo, Encode)]
+        struct AccountInfo {
+            nonce: u32,
+            data: AccountData,
+        }
+
+        let mut registry = scale_info::Registry::new();
+        let info_ty = registry
+            .register_type(&scale_info::meta_type::<AccountInfo>())
+            .id;
+        let metadata = ChainMetadata {
+            types: registry.into(),
+            runtime_apis: Vec::new(),
+            extrinsic: ExtrinsicInfo::default(),
+            pallets: vec![PalletInfo {
+                name: "System".to_owned(),
+                index: 0,
+                call_ty: None,
+                storage: vec![StorageItem {
+                    name: "Account".to_owned(),
+                    keys: Vec::new(),
+                    ty: info_ty,
+                    default: vec![0; 36],
+                }],
+                constants: Vec::new(),
+            }],
+        };
+        let info = AccountInfo {
+            nonce: 3,
+            data: AccountData {
+                free: 1_500,
+                reserved: 20,
+            },
+        }
+        .encode();
+        assert_eq!(
+            metadata.decode_storage_field("System", "Account", Some(&info), "data.free"),
+            Ok("1500".to_owned())
+        );
+        assert_eq!(
+            metadata.decode_storage_field("System", "Account", None, "nonce"),
+            Ok("0".to_owned())
+        );
+        assert!(metadata
+            .decode_storage_field("System", "Account", Some(&info), "data.frozen")
+            .is_err());
+    }
+
+    #[test]
+    fn storage_key_hashes_map_keys() {
+        let metadata = metadata();
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_owned();
+        assert_eq!(
+            metadata
+                .storage_key("system", "account", &[alice])
+                .map(|key| to_hex(&key)),
+            Ok(concat!(
+                "0x26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da9",
+                "de1e86a9a8c739864cf3cc5ec2bea59f",
+                "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+            )
+            .to_owned())
+        );
+        assert_eq!(
+            metadata.storage_key("Timestamp", "Now", &[]),
+            Ok(storage_value_key("Timestamp", "Now"))
+        );
+        assert!(metadata.storage_key("System", "Account", &[]).is_err());
+    }
+
+    #[test]
+    fn decode_constant_renders_value() {
+        let metadata = metadata();
+        assert!(metadata.has_constant("system", "ss58prefix"));
+        assert_eq!(
+            metadata.decode_constant("System", "SS58Prefix"),
+            Ok("42".to_owned())
+        );
+        assert!(metadata
+            .decode_constant("System", "BlockHashCount")
+            .is_err());
+    }
+
+    #[test]
+    fn resolve_call_requires_one_form() {
+        let metadata = metadata();
+        assert_eq!(
+            metadata.resolve_call(Some("0x0a00"), None, None, &[]),
+            Ok(vec![10, 0])
+        );
+        assert!(metadata
+            .resolve_call(Some("0x0a00"), Some("Balances"), None, &[])
+            .is_err());
+        assert!(metadata.resolve_call(None, None, None, &[]).is_err());
+    }
+
+    #[test]
+    fn typed_json_structures_return_values() {
+        #[allow(dead_code)]
+        #[derive(scale_info::TypeInfo)]
+        enum Error {
+            InsufficientBalance,
+            Custom { code: u32 },
+        }
+
+        let mut registry = scale_info::Registry::new();
+        let flag_ty = registry
+            .register_type(&scale_info::meta_type::<Result<bool, u8>>())
+            .id;
+        let balance_ty = registry
+            .register_type(&scale_info::meta_type::<Result<Result<u128, Error>, u8>>())
+            .id;
+        let unit_ty = registry
+            .register_type(&scale_info::meta_type::<Result<(), u8>>())
+            .id;
+        let owner_ty = registry
+            .register_type(&scale_info::meta_type::<Option<[u8; 20]>>())
+            .id;
+        let list_ty = registry
+            .register_type(&scale_info::meta_type::<Vec<u32>>())
+            .id;
+        let types: PortableRegistry = registry.into();
+
+        let json = |text: &str, ty| typed_json(text, ty, &types);
+        assert_eq!(
+            json("Ok(false)", flag_ty),
+            Ok(serde_json::json!({ "Ok": false }))
+        );
+        assert_eq!(
+            json("Ok(Ok(100))", balance_ty),
+            Ok(serde_json::json!({ "Ok": { "Ok": 100 } }))
+        );
+        assert_eq!(
+            json(&format!("Ok(Ok({}))", u128::MAX), balance_ty),
+            Ok(serde_json::json!({ "Ok": { "Ok": u128::MAX.to_string() } }))
+        );
+        assert_eq!(
+            json("Ok(Err(InsufficientBalance))", balance_ty),
+            Ok(serde_json::json!({ "Ok": { "Err": "InsufficientBalance" } }))
+        );
+        assert_eq!(
+            json("Ok(Err(Custom { code: 7 }))", balance_ty),
+            Ok(serde_json::json!({ "Ok": { "Err": { "Custom": { "code": 7 } } } }))
+        );
+        assert_eq!(
+            json("Ok(())", unit_ty),
+            Ok(serde_json::json!({ "Ok": null }))
+        );
+        let address = format!("0x{}", "ab".repeat(20));
+        assert_eq!(
+            json(&format!("Some({})", address), owner_ty),
+            Ok(serde_json::json!({ "Some": address }))
+        );
+        assert_eq!(json("None", owner_ty), Ok(serde_json::json!("None")));
+        assert_eq!(json("(5)", list_ty), Ok(serde_json::json!([5])));
+        assert!(json("Maybe(true)", flag_ty).is_err());
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        assert_eq!(to_hex(&[0xde, 0xad]), "0xdead");
+        assert_eq!(decode_hex("0xdead"), Ok(vec![0xde, 0xad]));
+        assert_eq!(decode_hex("dead"), Ok(vec![0xde, 0xad]));
+        assert!(decode_hex("0xzz").is_err());
+    }
+
+    #[test]
+    fn hashes_match_known_vectors() {
+        // twox_128("System") ++ twox_128("Number")
+        assert_eq!(
+            to_hex(&storage_value_key("System", "Number")),
+            "0x26aa394eea5630e07c48ae0c9558cef702a5c1b19ab7a04f536c519aca4983ac"
+        );
+        assert_eq!(
+            to_hex(&blake2_256(b"")),
+            "0x0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+        );
+    }
+
+    #[test]
+    fn from_bytes_rejects_garbage() {
+        assert!(ChainMetadata::from_bytes(&[0, 1, 2]).is_err());
+    }
+}
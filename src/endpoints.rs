@@ -0,0 +1,284 @@
+//! Alternative RPC endpoints for public testnets.
+//!
+//! Public RPC providers drop connections routinely. When a deploy or call cannot reach
+//! a known endpoint, [`with_failover`] retries it against the network's other providers
+//! and reports which one answered.
+
+use std::future::Future;
+
+use crate::error::PopMcpResult;
+use crate::executor::CommandExecutor;
+use crate::parsing;
+
+/// A public network and the RPC endpoints serving it, preferred first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Network {
+    /// Display name.
+    pub name: &'static str,
+    /// WebSocket endpoints of independent providers.
+    pub endpoints: &'static [&'static str],
+}
+
+/// Known public test networks.
+pub const NETWORKS: &[Network] = &[
+    Network {
+        name: "Paseo",
+        endpoints: &[
+            "wss://paseo.rpc.amforc.com",
+            "wss://paseo-rpc.dwellir.com",
+            "wss://rpc.ibp.network/paseo",
+            "wss://paseo.dotters.network",
+        ],
+    },
+    Network {
+        name: "Paseo Asset Hub",
+        endpoints: &[
+            "wss://asset-hub-paseo-rpc.dwellir.com",
+            "wss://sys.ibp.network/asset-hub-paseo",
+            "wss://asset-hub-paseo.dotters.network",
+        ],
+    },
+    Network {
+        name: "Passet Hub",
+        endpoints: &["wss://testnet-passet-hub.polkadot.io"],
+    },
+    Network {
+        name: "Pop Testnet",
+        endpoints: &[
+            "wss://rpc1.paseo.popnetwork.xyz",
+            "wss://rpc2.paseo.popnetwork.xyz",
+            "wss://rpc3.paseo.popnetwork.xyz",
+        ],
+    },
+    Network {
+        name: "Westend",
+        endpoints: &[
+            "wss://westend-rpc.polkadot.io",
+            "wss://westend-rpc.dwellir.com",
+            "wss://rpc.ibp.network/westend",
+            "wss://westend.dotters.network",
+        ],
+    },
+    Network {
+        name: "Westend Asset Hub",
+        endpoints: &[
+            "wss://westend-asset-hub-rpc.polkadot.io",
+            "wss://asset-hub-westend-rpc.dwellir.com",
+            "wss://sys.ibp.network/asset-hub-westend",
+        ],
+    },
+];
+
+/// `url` without a trailing slash, for comparing endpoints.
+fn normalize(url: &str) -> &str {
+    url.trim().trim_end_matches('/')
+}
+
+/// The known network served by `url`, if any.
+pub fn network_for(url: &str) -> Option<&'static Network> {
+    let url = normalize(url);
+    NETWORKS.iter().find(|network| {
+        network
+            .endpoints
+            .iter()
+            .any(|e| e.eq_ignore_ascii_case(url))
+    })
+}
+
+/// Endpoints to try for `url`: `url` itself, then the other endpoints of its network.
+pub fn candidates(url: &str) -> Vec<String> {
+    let mut urls = vec![url.to_owned()];
+    if let Some(network) = network_for(url) {
+        urls.extend(
+            network
+                .endpoints
+                .iter()
+                .filter(|e| !e.eq_ignore_ascii_case(normalize(url)))
+                .map(|e| (*e).to_owned()),
+        );
+    }
+    urls
+}
+
+/// Outcome of a command run with [`with_failover`].
+#[derive(Debug)]
+pub struct Failover {
+    /// Result from the last endpoint tried.
+    pub outcome: PopMcpResult<String>,
+    /// Endpoint that produced `outcome`.
+    pub url: String,
+    /// Endpoints that failed to connect before it.
+    pub unreachable: Vec<String>,
+}
+
+impl Failover {
+    /// Note for the tool result when another endpoint than the requested one was used.
+    pub fn note(&self) -> Option<String> {
+        (!self.unreachable.is_empty()).then(|| {
+            format!(
+                "Endpoint {} unreachable; used {} instead.",
+                self.unreachable.join(", "),
+                self.url
+            )
+        })
+    }
+}
+
+/// Run `attempt` against `url`, moving on to the next endpoint of its network while
+/// the command fails to connect. Unknown URLs are tried once.
+pub async fn with_failover<F, Fut>(url: &str, mut attempt: F) -> Failover
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = PopMcpResult<String>>,
+{
+    let mut unreachable = Vec::new();
+    let mut urls = candidates(url).into_iter().peekable();
+    loop {
+        let url = urls.next().unwrap_or_default();
+        let outcome = attempt(url.clone()).await;
+        let transient = match &outcome {
+            Ok(output) | Err(crate::error::PopMcpError::CommandExecution(output)) => {
+                parsing::is_transient(output)
+            }
+            Err(_) => false,
+        };
+        if !transient || urls.peek().is_none() {
+            return Failover {
+                outcome,
+                url,
+                unreachable,
+            };
+        }
+        unreachable.push(url);
+    }
+}
+
+/// Run the pop command `args` with retries, failing over to other endpoints of the
+/// `--url` network. `json` asks for JSON output as [`CommandExecutor::execute_json`]
+/// does. Returns the outcome and a [`Failover::note`] if another endpoint was used.
+pub async fn execute_with_failover<E: CommandExecutor>(
+    executor: &E,
+    args: &[String],
+    json: bool,
+) -> (PopMcpResult<String>, Option<String>) {
+    let run = |args: Vec<String>| async move {
+        let refs: Vec<&str> = args.iter().map(String::as_str).collect();
+        if json {
+            executor.execute_json_with_retry(&refs).await
+        } else {
+            executor.execute_with_retry(&refs).await
+        }
+    };
+    let url = args
+        .iter()
+        .position(|arg| arg == "--url")
+        .and_then(|pos| args.get(pos + 1));
+    let Some(url) = url else {
+        return (run(args.to_vec()).await, None);
+    };
+    let failover = with_failover(url, |url| run(with_url(args, &url))).await;
+    let note = failover.note();
+    (failover.outcome, note)
+}
+
+/// `args` with the value of `--url` replaced by `url`.
+pub fn with_url(args: &[String], url: &str) -> Vec<String> {
+    let mut args = args.to_vec();
+    if let Some(pos) = args.iter().position(|arg| arg == "--url") {
+        if let Some(value) = args.get_mut(pos + 1) {
+            url.clone_into(value);
+        }
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::PopMcpError;
+
+    #[test]
+    fn candidates_start_with_requested_endpoint() {
+        let urls = candidates("wss://rpc2.paseo.popnetwork.xyz/");
+        assert_eq!(
+            urls,
+            [
+                "wss://rpc2.paseo.popnetwork.xyz/",
+                "wss://rpc1.paseo.popnetwork.xyz",
+                "wss://rpc3.paseo.popnetwork.xyz"
+            ]
+        );
+        assert_eq!(candidates("ws://localhost:9944"), ["ws://localhost:9944"]);
+    }
+
+    #[tokio::test]
+    async fn with_failover_moves_on_after_connection_errors() {
+        let failover = with_failover("wss://paseo.rpc.amforc.com", |url| async move {
+            if url.contains("amforc") {
+                Err(PopMcpError::CommandExecution(
+                    "Error: Not connected".to_owned(),
+                ))
+            } else {
+                Ok(format!("called via {}", url))
+            }
+        })
+        .await;
+        assert_eq!(failover.url, "wss://paseo-rpc.dwellir.com");
+        assert_eq!(
+            failover.outcome.ok().as_deref(),
+            Some("called via wss://paseo-rpc.dwellir.com")
+        );
+        assert_eq!(failover.unreachable, ["wss://paseo.rpc.amforc.com"]);
+    }
+
+    #[tokio::test]
+    async fn with_failover_stops_on_real_errors() {
+        let mut tried = 0;
+        let failover = with_failover("wss://westend-rpc.polkadot.io", |_| {
+            tried += 1;
+            async {
+                Err(PopMcpError::CommandExecution(
+                    "Error: Module error".to_owned(),
+                ))
+            }
+        })
+        .await;
+        assert_eq!(tried, 1);
+        assert!(failover.note().is_none());
+    }
+
+    #[tokio::test]
+    async fn execute_with_failover_rewrites_url_arg() {
+        use crate::executor::RetryPolicy;
+        use crate::test_utils::MockExecutor;
+
+        let executor = MockExecutor::new()
+            .with_retry(RetryPolicy::none())
+            .on(&["call", "chain", "--url"], "Chain call completed")
+            .on_failure(
+                &["call", "chain", "--url", "wss://rpc1.paseo.popnetwork.xyz"],
+                "Error: Not connected",
+            );
+        let args: Vec<String> = ["call", "chain", "--url", "wss://rpc1.paseo.popnetwork.xyz"]
+            .map(str::to_owned)
+            .to_vec();
+        let (outcome, note) = execute_with_failover(&executor, &args, false).await;
+        assert_eq!(outcome.ok().as_deref(), Some("Chain call completed"));
+        assert_eq!(
+            note.as_deref(),
+            Some(
+                "Endpoint wss://rpc1.paseo.popnetwork.xyz unreachable; used wss://rpc2.paseo.popnetwork.xyz instead."
+            )
+        );
+        assert_eq!(
+            executor.calls()[1].args[3],
+            "wss://rpc2.paseo.popnetwork.xyz"
+        );
+    }
+
+    #[test]
+    fn with_url_replaces_url_value() {
+        let args = vec!["call".to_owned(), "--url".to_owned(), "wss://a".to_owned()];
+        assert_eq!(with_url(&args, "wss://b"), ["call", "--url", "wss://b"]);
+    }
+}
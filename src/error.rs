@@ -12,6 +12,8 @@ pub enum PopMcpError {
     CommandExecution(String),
     /// Invalid input parameters
     InvalidInput(String),
+    /// Error from a JSON-RPC request to a chain node
+    Rpc(String),
     /// Internal server error
     Internal(String),
 }
@@ -21,6 +23,7 @@ impl fmt::Display for PopMcpError {
         match self {
             Self::CommandExecution(msg) => write!(f, "Command execution error: {}", msg),
             Self::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+            Self::Rpc(msg) => write!(f, "RPC error: {}", msg),
             Self::Internal(msg) => write!(f, "Internal error: {}", msg),
         }
     }
@@ -1,9 +1,50 @@
 //! Command execution for Pop CLI
 
+use std::collections::{BTreeMap, BTreeSet};
+use std::future::Future;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::Stdio;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
 
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Command;
+use tokio_util::sync::CancellationToken;
+
+use crate::audit::{self, CommandStatus};
 use crate::error::{PopMcpError, PopMcpResult};
+use crate::logging::CommandLog;
+use crate::parsing;
+use crate::redact::Secrets;
+use crate::stream;
+
+/// Env var naming the Pop CLI binary to run: a path, or a command looked up on PATH.
+pub const POP_BIN_ENV: &str = "POP_BIN";
+
+/// Flag asking pop for machine-readable output.
+pub const JSON_OUTPUT_FLAG: [&str; 2] = ["--output", "json"];
+
+/// Commands whose pop rejected [`JSON_OUTPUT_FLAG`], so they are not probed again.
+static TEXT_ONLY: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// The subcommand part of `args` (everything before the first flag).
+fn command_key(args: &[&str]) -> String {
+    args.iter()
+        .take_while(|arg| !arg.starts_with('-'))
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn is_text_only(key: &str) -> bool {
+    TEXT_ONLY.lock().is_ok_and(|set| set.contains(key))
+}
+
+fn mark_text_only(key: String) {
+    if let Ok(mut set) = TEXT_ONLY.lock() {
+        set.insert(key);
+    }
+}
 
 /// Output from command execution.
 #[derive(Debug, Clone)]
@@ -16,7 +57,184 @@ struct CommandOutput {
     success: bool,
 }
 
+/// How network-bound commands are retried after transient connection failures.
+///
+/// See [`parsing::is_transient`] for what counts as transient; anything else is
+/// returned straight away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first; `1` disables retries.
+    pub attempts: u32,
+    /// Delay before the first retry, doubled for each further one.
+    pub backoff: Duration,
+    /// Upper bound for the delay between attempts.
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Run commands once.
+    pub const fn none() -> Self {
+        Self {
+            attempts: 1,
+            backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        }
+    }
+
+    /// Delay before attempt `attempt + 1`, where `attempt` starts at 1.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, 500ms apart and then 1s, enough for a just-started node.
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(4),
+        }
+    }
+}
+
+/// Run `attempt` until it succeeds, fails for a non-transient reason or `policy` runs out.
+///
+/// Output that reports a transient failure is retried even when pop exited successfully.
+async fn retrying<E, F, Fut>(executor: &E, policy: RetryPolicy, attempt: F) -> PopMcpResult<String>
+where
+    E: CommandExecutor,
+    F: Fn() -> Fut,
+    Fut: Future<Output = PopMcpResult<String>>,
+{
+    let mut tried = 1;
+    loop {
+        let result = attempt().await;
+        let transient = match &result {
+            Ok(output) | Err(PopMcpError::CommandExecution(output)) => {
+                parsing::is_transient(output)
+            }
+            Err(_) => false,
+        };
+        if !transient || tried >= policy.attempts || executor.is_cancelled() {
+            return result;
+        }
+        tokio::time::sleep(policy.delay(tried)).await;
+        tried += 1;
+    }
+}
+
+/// Receives each output line of running commands, e.g. to forward as progress.
+///
+/// Bounded: when the receiver falls behind, the oldest lines are dropped.
+pub type LineSink = stream::Sender<String>;
+
+/// Read `pipe` to the end into `buf`, sending each complete non-empty line to `sink`
+/// normalized and with `secrets` masked.
+///
+/// Bytes are appended as they arrive, so `buf` holds everything read even if the
+/// future is dropped midway.
+async fn drain(
+    mut pipe: impl AsyncRead + Unpin,
+    buf: &mut Vec<u8>,
+    sink: Option<&LineSink>,
+    secrets: &Secrets,
+) -> std::io::Result<()> {
+    let mut chunk = [0u8; 8192];
+    let mut line_start = buf.len();
+    loop {
+        let read = pipe.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..read]);
+        let Some(sink) = sink else {
+            continue;
+        };
+        while let Some(offset) = buf[line_start..].iter().position(|b| *b == b'\n') {
+            let line = parsing::normalize(&String::from_utf8_lossy(
+                &buf[line_start..line_start + offset],
+            ));
+            if !line.trim().is_empty() {
+                // A closed receiver only means nobody is listening any more.
+                sink.send(secrets.mask(&line));
+            }
+            line_start += offset + 1;
+        }
+    }
+    if let Some(sink) = sink {
+        let rest = parsing::normalize(&String::from_utf8_lossy(&buf[line_start..]));
+        if !rest.trim().is_empty() {
+            sink.send(secrets.mask(rest.trim_end()));
+        }
+    }
+    Ok(())
+}
+
+/// Message for a command killed after exceeding `timeout`.
+fn timed_out(timeout: Duration) -> String {
+    format!("timed out after {}s", timeout.as_secs_f64())
+}
+
+/// Process group of a running `pop`, killed when interrupted or dropped.
+///
+/// `pop` runs in its own group, so killing the group also stops the cargo, rustc and
+/// node processes it spawned; killing `pop` alone would leave them running. Once the
+/// command has finished the group is left alone, so detached nodes keep running.
+struct ProcessGroup(Option<u32>);
+
+impl ProcessGroup {
+    /// Send SIGKILL to the group, once. A no-op off Unix, where only `pop` is killed.
+    fn kill(&mut self) {
+        #[cfg(unix)]
+        if let Some(pid) = self
+            .0
+            .take()
+            .and_then(|id| i32::try_from(id).ok())
+            .and_then(rustix::process::Pid::from_raw)
+        {
+            // Best effort: the whole group may already be gone.
+            let _ = rustix::process::kill_process_group(pid, rustix::process::Signal::KILL);
+        }
+    }
+
+    /// The command finished: leave whatever it left behind running.
+    fn finished(&mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for ProcessGroup {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+/// Error for a command killed before finishing, carrying whatever it printed.
+fn interrupted_error(args: &[&str], reason: &str, stdout: &[u8], stderr: &[u8]) -> PopMcpError {
+    let secrets = Secrets::for_args(args);
+    let mut message = format!("pop {} {}", secrets.mask_args(args), reason);
+    let partial = CommandOutput::masked(stdout, stderr, false, &secrets);
+    if !partial.stdout.is_empty() || !partial.stderr.is_empty() {
+        message.push_str("\n\nPartial output:\n");
+        message.push_str(&partial.combined());
+    }
+    PopMcpError::CommandExecution(message)
+}
+
 impl CommandOutput {
+    /// Decode raw command output, normalized (see [`parsing::normalize`]) and with
+    /// `secrets` masked so they never reach tool results.
+    fn masked(stdout: &[u8], stderr: &[u8], success: bool, secrets: &Secrets) -> Self {
+        let clean = |raw: &[u8]| secrets.mask(&parsing::normalize(&String::from_utf8_lossy(raw)));
+        Self {
+            stdout: clean(stdout),
+            stderr: clean(stderr),
+            success,
+        }
+    }
+
     /// Get combined output, preferring stderr for Pop CLI
     fn combined(&self) -> String {
         let mut result = String::new();
@@ -40,14 +258,183 @@ impl CommandOutput {
     }
 }
 
+/// Runs Pop CLI commands on behalf of tools.
+///
+/// Tools and [`crate::PopMcpServer`] are generic over this trait. [`PopExecutor`] runs
+/// the real binary; `test_utils::MockExecutor` (behind the `testing` feature) returns
+/// canned output so tools can be tested without Pop CLI installed.
+pub trait CommandExecutor: Clone + Send + Sync + 'static {
+    /// Execute a Pop CLI command, returning its output or an error carrying it.
+    fn execute(&self, args: &[&str]) -> impl Future<Output = PopMcpResult<String>> + Send;
+
+    /// Copy of this executor that runs commands in `dir`.
+    fn in_dir(&self, dir: &Path) -> Self;
+
+    /// Directory commands run in, if not the server's working directory.
+    fn working_dir(&self) -> Option<&Path> {
+        None
+    }
+
+    /// Kill commands that run longer than `timeout` and fail them with their partial output.
+    fn with_timeout(self, timeout: Duration) -> Self;
+
+    /// Set `env` on commands, on top of the server's own environment.
+    fn with_env(self, env: BTreeMap<String, String>) -> Self;
+
+    /// Send each output line of running commands to `sink`.
+    fn with_line_sink(self, _sink: LineSink) -> Self {
+        self
+    }
+
+    /// Kill running commands when `token` is cancelled, e.g. by an MCP cancellation.
+    fn with_cancellation(self, _token: CancellationToken) -> Self {
+        self
+    }
+
+    /// Report each command to the MCP client through `log`.
+    fn with_command_log(self, _log: CommandLog) -> Self {
+        self
+    }
+
+    /// Retry network-bound commands (see [`Self::execute_with_retry`]) per `policy`.
+    fn with_retry(self, _policy: RetryPolicy) -> Self {
+        self
+    }
+
+    /// Policy used by [`Self::execute_with_retry`]; no retries unless overridden.
+    fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::none()
+    }
+
+    /// Whether this executor's cancellation token has fired.
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+
+    /// Copy of this executor that ignores cancellation, for cleaning up after it.
+    fn detached(&self) -> Self {
+        self.clone()
+    }
+
+    /// Execute a Pop CLI command in `dir` instead of the executor's working directory.
+    fn execute_in_dir(
+        &self,
+        dir: &Path,
+        args: &[&str],
+    ) -> impl Future<Output = PopMcpResult<String>> + Send {
+        let executor = self.in_dir(dir);
+        async move { executor.execute(args).await }
+    }
+
+    /// Execute a command asking for JSON output, falling back to plain text on pop
+    /// versions that reject [`JSON_OUTPUT_FLAG`].
+    ///
+    /// The parsers in [`crate::parsing`] read either form. A rejection is remembered
+    /// per subcommand for the life of the process, so old versions pay for it once.
+    fn execute_json(&self, args: &[&str]) -> impl Future<Output = PopMcpResult<String>> + Send {
+        async move {
+            let key = command_key(args);
+            if is_text_only(&key) {
+                return self.execute(args).await;
+            }
+            let mut with_json = args.to_vec();
+            with_json.extend(JSON_OUTPUT_FLAG);
+            match self.execute(&with_json).await {
+                Err(PopMcpError::CommandExecution(output))
+                    if parsing::rejects_flag(&output, JSON_OUTPUT_FLAG[0]) =>
+                {
+                    mark_text_only(key);
+                    self.execute(args).await
+                }
+                result => result,
+            }
+        }
+    }
+
+    /// Execute a command that talks to a node, retrying transient connection failures
+    /// (e.g. against a node that is still starting) per [`Self::retry_policy`].
+    fn execute_with_retry(
+        &self,
+        args: &[&str],
+    ) -> impl Future<Output = PopMcpResult<String>> + Send {
+        retrying(self, self.retry_policy(), move || self.execute(args))
+    }
+
+    /// [`Self::execute_json`] with retries, as in [`Self::execute_with_retry`].
+    fn execute_json_with_retry(
+        &self,
+        args: &[&str],
+    ) -> impl Future<Output = PopMcpResult<String>> + Send {
+        retrying(self, self.retry_policy(), move || self.execute_json(args))
+    }
+
+    /// Copy of this executor with the per-call `env` tool param applied.
+    ///
+    /// Variables only apply to the commands of this call; the server process
+    /// environment is left untouched.
+    fn with_env_vars(&self, env: Option<&BTreeMap<String, String>>) -> PopMcpResult<Self> {
+        let Some(env) = env.filter(|env| !env.is_empty()) else {
+            return Ok(self.clone());
+        };
+        if let Some(name) = env
+            .keys()
+            .find(|name| name.is_empty() || name.contains(['=', '\0']))
+        {
+            return Err(PopMcpError::InvalidInput(format!(
+                "Invalid environment variable name: '{}'",
+                name
+            )));
+        }
+        if let Some(name) = env
+            .iter()
+            .find(|(_, value)| value.contains('\0'))
+            .map(|(n, _)| n)
+        {
+            return Err(PopMcpError::InvalidInput(format!(
+                "Value of environment variable '{}' contains a NUL byte",
+                name
+            )));
+        }
+        Ok(self.clone().with_env(env.clone()))
+    }
+
+    /// Copy of this executor with the per-call `timeout_secs` tool param applied.
+    ///
+    /// `None` keeps the executor's own timeout.
+    fn with_timeout_secs(&self, timeout_secs: Option<u64>) -> PopMcpResult<Self> {
+        match timeout_secs {
+            None => Ok(self.clone()),
+            Some(0) => Err(PopMcpError::InvalidInput(
+                "timeout_secs must be greater than 0".to_owned(),
+            )),
+            Some(secs) => Ok(self.clone().with_timeout(Duration::from_secs(secs))),
+        }
+    }
+}
+
 /// Real implementation of Pop CLI command executor.
 ///
-/// When the `pop-e2e` feature is enabled, the executor supports optional
-/// working directory override for test isolation.
+/// The executor supports an optional working directory override, used for test
+/// isolation and for scaffolding fixtures outside the server's cwd.
 #[derive(Debug, Clone, Default)]
 pub struct PopExecutor {
-    #[cfg(feature = "pop-e2e")]
     cwd: Option<PathBuf>,
+    /// Explicit Pop CLI binary, bypassing `resolve_pop_binary`.
+    binary: Option<PathBuf>,
+    /// Kill commands that run longer than this.
+    timeout: Option<Duration>,
+    /// Receives output lines while commands run.
+    lines: Option<LineSink>,
+    /// Kills running commands once cancelled.
+    cancel: Option<CancellationToken>,
+    /// Retries for network-bound commands.
+    retry: RetryPolicy,
+    /// Extra environment variables of commands.
+    env: BTreeMap<String, String>,
+    /// Client log commands are reported to.
+    log: CommandLog,
+    #[cfg(feature = "fault-injection")]
+    faults: Option<std::sync::Arc<crate::faults::FaultPlan>>,
 }
 
 impl PopExecutor {
@@ -57,37 +444,179 @@ impl PopExecutor {
     }
 
     /// Create an executor with a working directory override.
-    #[cfg(feature = "pop-e2e")]
     pub fn with_cwd(cwd: PathBuf) -> Self {
-        Self { cwd: Some(cwd) }
+        Self {
+            cwd: Some(cwd),
+            ..Self::default()
+        }
+    }
+
+    /// Use the given binary instead of resolving `pop` from the environment.
+    pub fn with_binary(mut self, binary: impl Into<PathBuf>) -> Self {
+        self.binary = Some(binary.into());
+        self
     }
 
-    fn execute_raw(&self, args: &[&str]) -> PopMcpResult<CommandOutput> {
-        let mut cmd = Command::new(resolve_pop_binary());
-        cmd.args(args);
+    /// Kill commands that run longer than `timeout` and fail them with their partial output.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Send every stdout/stderr line to `sink` as commands run.
+    pub fn with_line_sink(mut self, sink: LineSink) -> Self {
+        self.lines = Some(sink);
+        self
+    }
+
+    /// Kill running commands when `token` is cancelled, e.g. by an MCP cancellation.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Retry network-bound commands per `policy` (default: [`RetryPolicy::default`]).
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Report each command to the MCP client through `log`.
+    pub fn with_command_log(mut self, log: CommandLog) -> Self {
+        self.log = log;
+        self
+    }
+
+    /// Set `env` on commands, on top of the server's own environment.
+    pub fn with_env(mut self, env: BTreeMap<String, String>) -> Self {
+        self.env.extend(env);
+        self
+    }
+
+    /// Inject failures from `plan` into matching subcommands.
+    #[cfg(feature = "fault-injection")]
+    pub fn with_faults(mut self, plan: crate::faults::FaultPlan) -> Self {
+        self.faults = Some(std::sync::Arc::new(plan));
+        self
+    }
+
+    async fn execute_raw(&self, args: &[&str]) -> PopMcpResult<CommandOutput> {
+        #[cfg(feature = "fault-injection")]
+        let fault = self.faults.as_ref().and_then(|plan| plan.fault_for(args));
+        #[cfg(feature = "fault-injection")]
+        if let Some(crate::faults::Fault::Timeout(after)) = fault {
+            tokio::time::sleep(*after).await;
+            return Err(interrupted_error(args, &timed_out(*after), &[], &[]));
+        }
+
+        let binary = self.binary.clone().unwrap_or_else(resolve_pop_binary);
+        let mut cmd = Command::new(binary);
+        // Cancelled tool calls must not leave pop (or what it spawned) running in the
+        // background; see `ProcessGroup`.
+        cmd.args(args)
+            .envs(&self.env)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        #[cfg(unix)]
+        cmd.process_group(0);
 
-        #[cfg(feature = "pop-e2e")]
         if let Some(ref cwd) = self.cwd {
             cmd.current_dir(cwd);
         }
 
-        let output = cmd.output().map_err(|e| {
+        let spawn_error = |e: std::io::Error| {
             PopMcpError::CommandExecution(format!("Failed to execute pop command: {}", e))
-        })?;
+        };
+        let mut child = cmd.spawn().map_err(spawn_error)?;
+        let mut group = ProcessGroup(child.id());
+        let secrets = Secrets::for_args(args);
+        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+        let (Some(out_pipe), Some(err_pipe)) = (child.stdout.take(), child.stderr.take()) else {
+            return Err(PopMcpError::Internal(
+                "pop output pipes unavailable".to_owned(),
+            ));
+        };
+        // Reads append to the buffers as they go, so a timed-out run keeps what it printed.
+        let run = async {
+            let (out, err, status) = tokio::join!(
+                drain(out_pipe, &mut stdout, self.lines.as_ref(), &secrets),
+                drain(err_pipe, &mut stderr, self.lines.as_ref(), &secrets),
+                child.wait()
+            );
+            out.and(err).and(status)
+        };
+        let deadline = async {
+            match self.timeout {
+                Some(timeout) => tokio::time::sleep(timeout).await,
+                None => std::future::pending().await,
+            }
+        };
+        let cancelled = async {
+            match &self.cancel {
+                Some(token) => token.cancelled().await,
+                None => std::future::pending().await,
+            }
+        };
+        let finished = tokio::select! {
+            status = run => Ok(status),
+            () = deadline => Err(self.timeout.map(timed_out).unwrap_or_default()),
+            () = cancelled => Err("was cancelled".to_owned()),
+        };
+        let status = match finished {
+            Ok(status) => {
+                group.finished();
+                status.map_err(spawn_error)?
+            }
+            Err(reason) => {
+                // Timed-out builds must not leave cargo holding the target-dir lock.
+                group.kill();
+                // Best effort: the child may have exited between the interrupt and the kill.
+                let _ = child.kill().await;
+                return Err(interrupted_error(args, &reason, &stdout, &stderr));
+            }
+        };
 
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let success = status.success();
+
+        // Faults operate on raw bytes so corrupted output still goes through decoding.
+        #[cfg(feature = "fault-injection")]
+        let (stdout, stderr, success) = match fault {
+            Some(fault) => {
+                let raw = fault.apply(crate::faults::RawOutput {
+                    stdout,
+                    stderr,
+                    success,
+                });
+                (raw.stdout, raw.stderr, raw.success)
+            }
+            None => (stdout, stderr, success),
+        };
 
-        Ok(CommandOutput {
-            stdout,
-            stderr,
-            success: output.status.success(),
-        })
+        Ok(CommandOutput::masked(&stdout, &stderr, success, &secrets))
     }
 
-    /// Execute a Pop CLI command with the given arguments
-    pub fn execute(&self, args: &[&str]) -> PopMcpResult<String> {
-        let output = self.execute_raw(args)?;
+    /// Run a command, failing with its combined output when it exits unsuccessfully.
+    /// Every run is recorded in the [`audit`] trail and reported to the client log.
+    ///
+    /// The process is awaited without blocking the runtime, so long builds don't stall
+    /// other tool calls.
+    async fn run(&self, args: &[&str]) -> PopMcpResult<String> {
+        let command = format!("pop {}", Secrets::for_args(args).mask_args(args));
+        self.log.started(&command);
+        let started = SystemTime::now();
+        let clock = Instant::now();
+        let result = self.execute_raw(args).await;
+        let (status, output) = match &result {
+            Ok(output) if output.success => (CommandStatus::Succeeded, output.combined()),
+            Ok(output) => (CommandStatus::Failed, output.combined()),
+            Err(e) => (CommandStatus::Interrupted, e.to_string()),
+        };
+        let duration = clock.elapsed();
+        self.log.finished(&command, status, duration, &output);
+        audit::record(command, started, duration, status, &output);
+        let output = result?;
 
         if output.success {
             Ok(output.combined())
@@ -108,7 +637,81 @@ impl PopExecutor {
     }
 }
 
+impl CommandExecutor for PopExecutor {
+    async fn execute(&self, args: &[&str]) -> PopMcpResult<String> {
+        self.run(args).await
+    }
+
+    fn in_dir(&self, dir: &Path) -> Self {
+        Self {
+            cwd: Some(dir.to_path_buf()),
+            ..self.clone()
+        }
+    }
+
+    fn working_dir(&self) -> Option<&Path> {
+        self.cwd.as_deref()
+    }
+
+    fn with_timeout(self, timeout: Duration) -> Self {
+        Self::with_timeout(self, timeout)
+    }
+
+    fn with_env(self, env: BTreeMap<String, String>) -> Self {
+        Self::with_env(self, env)
+    }
+
+    fn with_line_sink(self, sink: LineSink) -> Self {
+        Self::with_line_sink(self, sink)
+    }
+
+    fn with_cancellation(self, token: CancellationToken) -> Self {
+        Self::with_cancellation(self, token)
+    }
+
+    fn with_command_log(self, log: CommandLog) -> Self {
+        Self::with_command_log(self, log)
+    }
+
+    fn with_retry(self, policy: RetryPolicy) -> Self {
+        Self::with_retry(self, policy)
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        self.retry
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    fn detached(&self) -> Self {
+        Self {
+            cancel: None,
+            ..self.clone()
+        }
+    }
+}
+
+/// The Pop CLI binary used when no explicit binary is set.
+///
+/// [`POP_BIN_ENV`] wins and is used as given, so a pinned version that is missing
+/// fails loudly instead of silently running another `pop`. Otherwise `POP_CLI_PATH`,
+/// `pop` on PATH and the usual install locations are tried in turn.
 fn resolve_pop_binary() -> PathBuf {
+    if let Some(bin) = std::env::var_os(POP_BIN_ENV).filter(|bin| !bin.is_empty()) {
+        let bin = PathBuf::from(bin);
+        // Bare names such as `pop-0.9` are commands; anything with a separator is a path.
+        if bin.components().count() == 1 {
+            if let Some(found) = bin.to_str().and_then(find_in_path) {
+                return found;
+            }
+        }
+        return bin;
+    }
+
     if let Ok(path) = std::env::var("POP_CLI_PATH") {
         let candidate = PathBuf::from(path);
         if candidate.exists() {
@@ -178,6 +781,96 @@ mod tests {
         assert!(output.combined().contains("stdout content"));
     }
 
+    #[tokio::test]
+    async fn drain_sends_complete_lines_and_keeps_bytes() {
+        let (sink, mut lines) = stream::bounded(stream::DEFAULT_CAPACITY);
+        let mut buf = Vec::new();
+        let input: &[u8] = b"Compiling a\r\n\nCompiling b\nFinished";
+        assert!(drain(input, &mut buf, Some(&sink), &Secrets::default())
+            .await
+            .is_ok());
+        drop(sink);
+        let mut received = Vec::new();
+        while let Some((line, _)) = lines.recv().await {
+            received.push(line);
+        }
+        assert_eq!(received, ["Compiling a", "Compiling b", "Finished"]);
+        assert_eq!(buf, input);
+    }
+
+    #[tokio::test]
+    async fn drain_masks_secrets_in_sent_lines() {
+        let (sink, mut lines) = stream::bounded(stream::DEFAULT_CAPACITY);
+        let mut buf = Vec::new();
+        let secrets = Secrets::new(["0xdeadbeef".to_owned()]);
+        let input: &[u8] = b"signing with 0xdeadbeef\n";
+        assert!(drain(input, &mut buf, Some(&sink), &secrets).await.is_ok());
+        drop(sink);
+        assert_eq!(
+            lines.recv().await,
+            Some(("signing with [REDACTED]".to_owned(), 0))
+        );
+    }
+
+    #[test]
+    fn interrupted_error_masks_suri() {
+        let error = interrupted_error(
+            &["call", "chain", "--suri", "0xdeadbeef"],
+            "timed out after 1s",
+            b"using 0xdeadbeef",
+            b"",
+        );
+        let message = error.to_string();
+        assert!(!message.contains("0xdeadbeef"));
+        assert!(message.contains("--suri [REDACTED] timed out"));
+        assert!(message.contains("using [REDACTED]"));
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay(1), Duration::from_millis(500));
+        assert_eq!(policy.delay(2), Duration::from_secs(1));
+        assert_eq!(policy.delay(10), Duration::from_secs(4));
+    }
+
+    #[tokio::test]
+    async fn execute_with_retry_retries_only_transient_failures() {
+        use crate::test_utils::MockExecutor;
+
+        let policy = RetryPolicy {
+            attempts: 3,
+            backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+        };
+        let executor = MockExecutor::new()
+            .with_retry(policy)
+            .on_failure(&["call", "chain"], "Error: Not connected")
+            .on_failure(&["call", "contract"], "Error: Contract not found");
+        assert!(executor
+            .execute_with_retry(&["call", "chain"])
+            .await
+            .is_err());
+        assert_eq!(executor.calls().len(), 3);
+        assert!(executor
+            .execute_with_retry(&["call", "contract"])
+            .await
+            .is_err());
+        assert_eq!(executor.calls().len(), 4);
+        let once = executor.clone().with_retry(RetryPolicy::none());
+        assert!(once.execute_with_retry(&["call", "chain"]).await.is_err());
+        assert_eq!(executor.calls().len(), 5);
+    }
+
+    #[test]
+    fn command_key_stops_at_first_flag() {
+        assert_eq!(
+            command_key(&["up", "ink-node", "-y", "--detach"]),
+            "up ink-node"
+        );
+        assert_eq!(command_key(&["--version"]), "");
+    }
+
     #[test]
     fn command_output_empty() {
         let output = CommandOutput {
@@ -215,6 +908,7 @@ mod tests {
             }
         }
 
+        let _env = crate::test_utils::ENV_LOCK.lock();
         let prev = std::env::var_os("POP_CLI_PATH");
         std::env::set_var("POP_CLI_PATH", &pop_path);
         let resolved = resolve_pop_binary();
@@ -251,6 +945,7 @@ mod tests {
             }
         }
 
+        let _env = crate::test_utils::ENV_LOCK.lock();
         let prev_pop_cli_path = std::env::var_os("POP_CLI_PATH");
         let prev_path = std::env::var_os("PATH");
         std::env::remove_var("POP_CLI_PATH");
@@ -271,4 +966,26 @@ mod tests {
 
         assert_eq!(resolved, pop_path);
     }
+
+    #[test]
+    fn resolve_pop_binary_prefers_pop_bin() {
+        let _env = crate::test_utils::ENV_LOCK.lock();
+        let prev_pop_bin = std::env::var_os(POP_BIN_ENV);
+        let prev_pop_cli_path = std::env::var_os("POP_CLI_PATH");
+        std::env::set_var("POP_CLI_PATH", "/usr/bin/env");
+        std::env::set_var(POP_BIN_ENV, "/opt/pop-0.9/bin/pop");
+
+        let resolved = resolve_pop_binary();
+
+        match prev_pop_bin {
+            Some(value) => std::env::set_var(POP_BIN_ENV, value),
+            None => std::env::remove_var(POP_BIN_ENV),
+        }
+        match prev_pop_cli_path {
+            Some(value) => std::env::set_var("POP_CLI_PATH", value),
+            None => std::env::remove_var("POP_CLI_PATH"),
+        }
+
+        assert_eq!(resolved, PathBuf::from("/opt/pop-0.9/bin/pop"));
+    }
 }
@@ -0,0 +1,137 @@
+//! Failure injection for resilience testing.
+//!
+//! A [`FaultPlan`] attached to a [`PopExecutor`](crate::PopExecutor) rewrites what a
+//! matching subcommand produces: it can hang and time out, cut output short, corrupt it
+//! with non-UTF8 bytes, or die mid-stream. This lets tests drive error reporting, retry
+//! and cleanup paths deterministically instead of waiting for real Pop CLI failures.
+
+use std::time::Duration;
+
+/// A failure to inject into a subcommand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// Hang for the given duration without spawning, then fail as timed out.
+    Timeout(Duration),
+    /// Run normally but keep only the first `bytes` of each stream.
+    PartialOutput {
+        /// Bytes kept per stream.
+        bytes: usize,
+    },
+    /// Run normally but splice invalid UTF-8 into stdout.
+    NonUtf8,
+    /// Keep the first `after_bytes` of each stream and report an abnormal exit.
+    ProcessDeath {
+        /// Bytes emitted before the process died.
+        after_bytes: usize,
+    },
+}
+
+/// Faults keyed by subcommand prefix (e.g. `"build"`, `"up ink-node"`).
+#[derive(Debug, Clone, Default)]
+pub struct FaultPlan {
+    faults: Vec<(Vec<String>, Fault)>,
+}
+
+/// Raw process output a fault is applied to.
+#[derive(Debug, Clone)]
+pub(crate) struct RawOutput {
+    pub(crate) stdout: Vec<u8>,
+    pub(crate) stderr: Vec<u8>,
+    pub(crate) success: bool,
+}
+
+impl FaultPlan {
+    /// Create an empty plan.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inject `fault` into every command whose args start with `subcommand`.
+    pub fn inject(mut self, subcommand: &str, fault: Fault) -> Self {
+        let prefix = subcommand.split_whitespace().map(str::to_owned).collect();
+        self.faults.push((prefix, fault));
+        self
+    }
+
+    /// Fault for `args`, preferring the most specific (longest) matching prefix.
+    pub fn fault_for(&self, args: &[&str]) -> Option<&Fault> {
+        self.faults
+            .iter()
+            .filter(|(prefix, _)| {
+                prefix.len() <= args.len() && prefix.iter().zip(args).all(|(p, a)| p == a)
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, fault)| fault)
+    }
+}
+
+impl Fault {
+    /// Apply the fault to captured output.
+    pub(crate) fn apply(&self, mut output: RawOutput) -> RawOutput {
+        match self {
+            Self::Timeout(_) => output,
+            Self::PartialOutput { bytes } => {
+                output.stdout.truncate(*bytes);
+                output.stderr.truncate(*bytes);
+                output
+            }
+            Self::NonUtf8 => {
+                let mid = output.stdout.len() / 2;
+                output.stdout.splice(mid..mid, [0xff, 0xfe, 0xc3]);
+                output
+            }
+            Self::ProcessDeath { after_bytes } => {
+                output.stdout.truncate(*after_bytes);
+                output.stderr.truncate(*after_bytes);
+                output.success = false;
+                output
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(stdout: &str) -> RawOutput {
+        RawOutput {
+            stdout: stdout.as_bytes().to_vec(),
+            stderr: Vec::new(),
+            success: true,
+        }
+    }
+
+    #[test]
+    fn fault_for_matches_longest_prefix() {
+        let plan = FaultPlan::new()
+            .inject("up", Fault::NonUtf8)
+            .inject("up ink-node", Fault::PartialOutput { bytes: 4 });
+        assert_eq!(
+            plan.fault_for(&["up", "ink-node", "-y"]),
+            Some(&Fault::PartialOutput { bytes: 4 })
+        );
+        assert_eq!(plan.fault_for(&["up", "./flipper"]), Some(&Fault::NonUtf8));
+        assert_eq!(plan.fault_for(&["build", "--path", "."]), None);
+    }
+
+    #[test]
+    fn partial_output_truncates_streams() {
+        let faulted = Fault::PartialOutput { bytes: 5 }.apply(output("url: ws://localhost"));
+        assert_eq!(faulted.stdout, b"url: ");
+        assert!(faulted.success);
+    }
+
+    #[test]
+    fn non_utf8_corrupts_stdout() {
+        let faulted = Fault::NonUtf8.apply(output("build ok"));
+        assert!(String::from_utf8(faulted.stdout).is_err());
+    }
+
+    #[test]
+    fn process_death_marks_failure() {
+        let faulted = Fault::ProcessDeath { after_bytes: 3 }.apply(output("deploying"));
+        assert_eq!(faulted.stdout, b"dep");
+        assert!(!faulted.success);
+    }
+}
@@ -0,0 +1,346 @@
+//! Shared prebuilt fixtures.
+//!
+//! Creating and building a contract (or chain) from scratch dominates e2e test time.
+//! Fixtures are scaffolded once into a cache directory keyed by the Pop CLI version,
+//! reused by every later caller (in this process or another), and rebuilt only when
+//! their artifacts are missing. A lock file next to each fixture serialises its
+//! creation across processes. The matching shared ink! node lives in
+//! `crate::testing` (behind the `testing` feature).
+
+use std::path::{Path, PathBuf};
+
+use tokio::sync::Mutex;
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::tools::common::extract_text;
+use crate::tools::{
+    build_chain, build_contract, create_chain, create_contract, BuildChainParams,
+    BuildContractParams, CreateChainParams, CreateContractParams,
+};
+
+/// Env var overriding the fixture cache root.
+pub const FIXTURES_DIR_ENV: &str = "POP_MCP_FIXTURES_DIR";
+
+const CONTRACT_NAME: &str = "shared_contract";
+const CHAIN_NAME: &str = "shared_chain";
+
+/// Serialises fixture creation within this process.
+static FIXTURE_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Kind of prebuilt fixture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureKind {
+    /// ink! contract from the `standard` (flipper) template.
+    Contract,
+    /// Parachain from the `r0gue-io/base-parachain` template.
+    Chain,
+}
+
+impl FixtureKind {
+    /// Parse a fixture kind from its tool-facing name.
+    pub fn parse(kind: &str) -> PopMcpResult<Self> {
+        match kind.trim().to_ascii_lowercase().as_str() {
+            "contract" => Ok(Self::Contract),
+            "chain" => Ok(Self::Chain),
+            other => Err(PopMcpError::InvalidInput(format!(
+                "Unknown fixture kind '{}'. Use 'contract' or 'chain'",
+                other
+            ))),
+        }
+    }
+
+    fn project_name(self) -> &'static str {
+        match self {
+            Self::Contract => CONTRACT_NAME,
+            Self::Chain => CHAIN_NAME,
+        }
+    }
+
+    /// Files that must exist for a scaffolded project to be reusable.
+    fn scaffold_markers(self) -> &'static [&'static str] {
+        match self {
+            Self::Contract => &["Cargo.toml", "lib.rs"],
+            Self::Chain => &["Cargo.toml", "runtime"],
+        }
+    }
+
+    /// Directory that exists once the project has been built.
+    fn artifact_dir(self, release: bool) -> &'static str {
+        match (self, release) {
+            (Self::Contract, _) => "target/ink",
+            (Self::Chain, true) => "target/release",
+            (Self::Chain, false) => "target/debug",
+        }
+    }
+}
+
+/// A scaffolded (and optionally built) fixture project.
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    /// Kind of fixture.
+    pub kind: FixtureKind,
+    /// Absolute path to the project.
+    pub path: PathBuf,
+    /// Whether build artifacts are present.
+    pub built: bool,
+}
+
+/// Root of the fixture cache for the Pop CLI version behind `executor`.
+pub async fn cache_dir<E: CommandExecutor>(executor: &E) -> PopMcpResult<PathBuf> {
+    let root = std::env::var(FIXTURES_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::temp_dir().join("pop-mcp-fixtures"));
+    let version = executor.execute(&["--version"]).await?;
+    Ok(root.join(version_key(&version)))
+}
+
+/// Get a fixture of `kind`, scaffolding and building it on first use.
+///
+/// `release` only affects how a missing build is produced; existing artifacts are reused.
+pub async fn ensure_fixture<E: CommandExecutor>(
+    executor: &E,
+    kind: FixtureKind,
+    build: bool,
+    release: Option<bool>,
+) -> PopMcpResult<Fixture> {
+    let _guard = FIXTURE_LOCK.lock().await;
+
+    let root = cache_dir(executor).await?;
+    std::fs::create_dir_all(&root).map_err(|e| {
+        PopMcpError::Internal(format!(
+            "Failed to create fixture cache {}: {}",
+            root.display(),
+            e
+        ))
+    })?;
+
+    // Held until the fixture is ready; a process that waited on it finds the winner's
+    // project (and build) in place and reuses them.
+    let _lock = lock_fixture(&root, kind).await?;
+    let path = root.join(kind.project_name());
+    if !is_scaffolded(kind, &path) {
+        scaffold(executor, kind, &root).await?;
+    }
+
+    let artifact_dir = kind.artifact_dir(release.unwrap_or(false));
+    let mut built = path.join(artifact_dir).exists();
+    if build && !built {
+        build_fixture(executor, kind, &path, release).await?;
+        built = path.join(artifact_dir).exists();
+        if !built {
+            return Err(PopMcpError::CommandExecution(format!(
+                "Fixture build finished without artifacts in {}",
+                artifact_dir
+            )));
+        }
+    }
+
+    Ok(Fixture { kind, path, built })
+}
+
+/// Take the cross-process lock on the fixture of `kind` in `root`, released on drop.
+async fn lock_fixture(root: &Path, kind: FixtureKind) -> PopMcpResult<std::fs::File> {
+    let path = root.join(format!(".{}.lock", kind.project_name()));
+    let locked = tokio::task::spawn_blocking(move || {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)?;
+        file.lock()?;
+        Ok::<_, std::io::Error>(file)
+    })
+    .await
+    .map_err(|e| PopMcpError::Internal(e.to_string()))?;
+    locked.map_err(|e| PopMcpError::Internal(format!("Failed to lock fixture cache: {}", e)))
+}
+
+fn is_scaffolded(kind: FixtureKind, path: &Path) -> bool {
+    kind.scaffold_markers()
+        .iter()
+        .all(|marker| path.join(marker).exists())
+}
+
+/// Scaffold into a private staging dir, then move into place so readers never observe
+/// a half-written project. Callers hold the fixture lock.
+async fn scaffold<E: CommandExecutor>(
+    executor: &E,
+    kind: FixtureKind,
+    root: &Path,
+) -> PopMcpResult<()> {
+    let name = kind.project_name();
+    let staging = root.join(format!(".{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::create_dir_all(&staging)
+        .map_err(|e| PopMcpError::Internal(format!("Failed to create staging dir: {}", e)))?;
+
+    let executor = executor.in_dir(&staging);
+    let result = match kind {
+        FixtureKind::Contract => {
+            create_contract(
+                &executor,
+                CreateContractParams {
+                    name: name.to_owned(),
+                    template: "standard".to_owned(),
+                    template_url: None,
+                    ink_version: None,
+                    with_e2e_tests: None,
+                    with_frontend: None,
+                    cwd: None,
+                    license: None,
+                    repository: None,
+                    authors: None,
+                },
+            )
+            .await?
+        }
+        FixtureKind::Chain => {
+            create_chain(
+                &executor,
+                CreateChainParams {
+                    name: name.to_owned(),
+                    provider: "pop".to_owned(),
+                    template: "r0gue-io/base-parachain".to_owned(),
+                    symbol: None,
+                    decimals: None,
+                    cwd: None,
+                    license: None,
+                    repository: None,
+                    authors: None,
+                },
+            )
+            .await?
+        }
+    };
+    if result.is_error == Some(true) {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(PopMcpError::CommandExecution(
+            extract_text(&result).unwrap_or_else(|| format!("Failed to scaffold {}", name)),
+        ));
+    }
+
+    // Only a leftover from an interrupted scaffold can be in the way, as the lock keeps
+    // other callers out.
+    let target = root.join(name);
+    let _ = std::fs::remove_dir_all(&target);
+    let moved = std::fs::rename(staging.join(name), &target);
+    let _ = std::fs::remove_dir_all(&staging);
+    moved.map_err(|e| {
+        PopMcpError::Internal(format!(
+            "Failed to move fixture into {}: {}",
+            target.display(),
+            e
+        ))
+    })
+}
+
+async fn build_fixture<E: CommandExecutor>(
+    executor: &E,
+    kind: FixtureKind,
+    path: &Path,
+    release: Option<bool>,
+) -> PopMcpResult<()> {
+    let path = path.display().to_string();
+    let result = match kind {
+        FixtureKind::Contract => {
+            build_contract(
+                executor,
+                BuildContractParams {
+                    path,
+                    release,
+                    timeout_secs: None,
+                    env: None,
+                    toolchain: None,
+                    verifiable: None,
+                    force: None,
+                },
+            )
+            .await?
+        }
+        FixtureKind::Chain => {
+            build_chain(
+                executor,
+                BuildChainParams {
+                    path,
+                    release,
+                    timeout_secs: None,
+                    env: None,
+                    toolchain: None,
+                    features: None,
+                    profile: None,
+                    mode: None,
+                },
+            )
+            .await?
+        }
+    };
+    if result.is_error == Some(true) {
+        return Err(PopMcpError::CommandExecution(
+            extract_text(&result).unwrap_or_else(|| "Fixture build failed".to_owned()),
+        ));
+    }
+    Ok(())
+}
+
+/// Turn `pop --version` output into a directory-safe cache key.
+fn version_key(version: &str) -> String {
+    let key: String = version
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if key.is_empty() {
+        "unknown".to_owned()
+    } else {
+        key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixture_kind_parses_known_names() {
+        assert_eq!(
+            FixtureKind::parse("contract").ok(),
+            Some(FixtureKind::Contract)
+        );
+        assert_eq!(FixtureKind::parse(" Chain ").ok(), Some(FixtureKind::Chain));
+        assert!(FixtureKind::parse("network").is_err());
+    }
+
+    #[test]
+    fn version_key_is_path_safe() {
+        assert_eq!(version_key("pop-cli 0.12.1\n"), "pop-cli_0.12.1");
+        assert_eq!(version_key("pop 1.0 (abc/def)"), "pop_1.0__abc_def_");
+        assert_eq!(version_key("  "), "unknown");
+    }
+
+    #[tokio::test]
+    async fn fixture_lock_excludes_other_processes_until_dropped() -> anyhow::Result<()> {
+        let root = tempfile::tempdir()?;
+        let lock = lock_fixture(root.path(), FixtureKind::Contract).await?;
+        // A separate open of the lock file stands in for another process.
+        let other = std::fs::File::open(root.path().join(".shared_contract.lock"))?;
+        assert!(other.try_lock().is_err());
+        assert!(lock_fixture(root.path(), FixtureKind::Chain).await.is_ok());
+        drop(lock);
+        assert!(other.try_lock().is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn chain_artifacts_follow_release_mode() {
+        assert_eq!(FixtureKind::Chain.artifact_dir(true), "target/release");
+        assert_eq!(FixtureKind::Chain.artifact_dir(false), "target/debug");
+        assert_eq!(FixtureKind::Contract.artifact_dir(true), "target/ink");
+    }
+}
@@ -0,0 +1,204 @@
+//! Pinning of contract artifacts to IPFS.
+//!
+//! Files are uploaded through the Kubo RPC API (`/api/v0/add`), which local IPFS
+//! daemons and most hosted pinning services (Filebase, Infura, 4EVERLAND) expose.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::error::{PopMcpError, PopMcpResult};
+
+/// Env var naming the IPFS RPC API URL.
+pub const IPFS_API_URL_ENV: &str = "POP_MCP_IPFS_API_URL";
+
+/// Env var holding the bearer token sent to the IPFS RPC API.
+pub const IPFS_TOKEN_ENV: &str = "POP_MCP_IPFS_TOKEN";
+
+/// Env var naming the gateway used to build links to pinned files.
+pub const IPFS_GATEWAY_ENV: &str = "POP_MCP_IPFS_GATEWAY";
+
+/// RPC API of a local IPFS daemon.
+pub const DEFAULT_API_URL: &str = "http://127.0.0.1:5001";
+
+/// Public gateway used for links when none is configured.
+pub const DEFAULT_GATEWAY: &str = "https://ipfs.io";
+
+/// Give up on an upload that doesn't complete within this time.
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Multipart boundary of upload requests.
+const BOUNDARY: &str = "pop-mcp-ipfs-boundary";
+
+/// Response of `/api/v0/add`.
+#[derive(Debug, Deserialize)]
+struct AddResponse {
+    #[serde(rename = "Hash")]
+    hash: String,
+}
+
+/// Uploads and pins files on an IPFS node or pinning service.
+#[derive(Debug, Clone)]
+pub struct Ipfs {
+    api_url: String,
+    token: Option<String>,
+    gateway: String,
+    client: reqwest::Client,
+}
+
+impl Default for Ipfs {
+    fn default() -> Self {
+        Self::new(DEFAULT_API_URL)
+    }
+}
+
+impl Ipfs {
+    /// Client of the IPFS RPC API at `api_url`.
+    pub fn new(api_url: impl Into<String>) -> Self {
+        Self {
+            api_url: api_url.into().trim_end_matches('/').to_owned(),
+            token: None,
+            gateway: DEFAULT_GATEWAY.to_owned(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Authenticate with `token` as a bearer token.
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Build links to pinned files on `gateway`.
+    pub fn with_gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.gateway = gateway.into().trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// Link to `cid` on the configured gateway.
+    pub fn gateway_url(&self, cid: &str) -> String {
+        format!("{}/ipfs/{}", self.gateway, cid)
+    }
+
+    /// Upload and pin `content` as `name`, returning its CID.
+    pub async fn add(&self, name: &str, content: &[u8]) -> PopMcpResult<String> {
+        let mut request = self
+            .client
+            .post(format!(
+                "{}/api/v0/add?pin=true&cid-version=1",
+                self.api_url
+            ))
+            .timeout(UPLOAD_TIMEOUT)
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={}", BOUNDARY),
+            )
+            .body(multipart_body(name, content));
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.map_err(|e| {
+            PopMcpError::Internal(format!("IPFS request to {} failed: {}", self.api_url, e))
+        })?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| PopMcpError::Internal(format!("IPFS response unreadable: {}", e)))?;
+        if !status.is_success() {
+            return Err(PopMcpError::Internal(format!(
+                "IPFS API returned {}: {}",
+                status,
+                body.trim()
+            )));
+        }
+        parse_cid(&body)
+    }
+}
+
+/// `content` as a single-file `multipart/form-data` body.
+fn multipart_body(name: &str, content: &[u8]) -> Vec<u8> {
+    let mut body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+        BOUNDARY,
+        name.replace('"', "")
+    )
+    .into_bytes();
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+    body
+}
+
+/// CID from an `/api/v0/add` response. Some nodes report progress first, one JSON
+/// object per line; the last one names the file.
+fn parse_cid(body: &str) -> PopMcpResult<String> {
+    body.lines()
+        .rev()
+        .find_map(|line| serde_json::from_str::<AddResponse>(line).ok())
+        .map(|added| added.hash)
+        .ok_or_else(|| PopMcpError::Internal(format!("Unexpected IPFS response: {}", body.trim())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn parse_cid_takes_last_object() {
+        let body = "{\"Name\":\"flipper.json\",\"Bytes\":1024}\n{\"Name\":\"flipper.json\",\"Hash\":\"bafkreia\",\"Size\":\"1031\"}\n";
+        assert_eq!(parse_cid(body).ok().as_deref(), Some("bafkreia"));
+        assert!(parse_cid("<html>Bad Gateway</html>").is_err());
+    }
+
+    #[test]
+    fn gateway_url_joins_cid() {
+        let ipfs = Ipfs::default().with_gateway("https://gateway.pinata.cloud/");
+        assert_eq!(
+            ipfs.gateway_url("bafkreia"),
+            "https://gateway.pinata.cloud/ipfs/bafkreia"
+        );
+    }
+
+    #[tokio::test]
+    async fn add_uploads_file_with_token() -> std::io::Result<()> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await?;
+            let mut request = Vec::new();
+            let mut chunk = [0u8; 4096];
+            // Read until the closing multipart boundary.
+            while !request.ends_with(b"--\r\n") {
+                let read = socket.read(&mut chunk).await?;
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&chunk[..read]);
+            }
+            let body = r#"{"Name":"flipper.contract","Hash":"bafkreib","Size":"12"}"#;
+            socket
+                .write_all(
+                    format!(
+                        "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                    .as_bytes(),
+                )
+                .await?;
+            Ok::<_, std::io::Error>(String::from_utf8_lossy(&request).into_owned())
+        });
+
+        let cid = Ipfs::new(url)
+            .with_token("secret")
+            .add("flipper.contract", b"{\"source\":{}}")
+            .await;
+        assert_eq!(cid.ok().as_deref(), Some("bafkreib"));
+        let request = server.await.map_err(std::io::Error::other)??;
+        assert!(request.starts_with("POST /api/v0/add?pin=true"));
+        assert!(request.contains("authorization: Bearer secret"));
+        assert!(request.contains("filename=\"flipper.contract\""));
+        Ok(())
+    }
+}
@@ -2,14 +2,52 @@
 //!
 //! This library provides MCP (Model Context Protocol) tools for interacting
 //! with Pop CLI, enabling AI assistants to help with smart contract development.
+pub mod accounts;
+pub mod audit;
+pub mod bundle;
+pub mod children;
+pub mod deployments;
+pub mod devloop;
+pub mod diagnostics;
+pub mod docs;
+pub mod elicit;
+pub mod encoder;
+pub mod endpoints;
 pub mod error;
 pub mod executor;
+#[cfg(feature = "fault-injection")]
+pub mod faults;
+pub mod fixtures;
+pub mod ipfs;
+pub mod logging;
+pub mod nodes;
+pub mod outputs;
+pub mod parsing;
+pub mod profiles;
+pub mod progress;
+pub mod prompts;
+pub mod queries;
+pub mod redact;
 pub mod resources;
+pub mod roots;
+pub mod rpc;
 pub mod server;
+pub mod spill;
+pub mod stream;
+#[cfg(any(test, feature = "testing"))]
+pub mod test_utils;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod tokens;
+pub mod toolchain;
 pub mod tools;
+pub mod wallet;
+pub mod watch;
+pub mod webhook;
 
 pub use error::{PopMcpError, PopMcpResult};
-pub use executor::PopExecutor;
+pub use executor::{CommandExecutor, PopExecutor, RetryPolicy};
+pub use rpc::RpcPool;
 pub use server::PopMcpServer;
 
 /// SURI from PRIVATE_KEY env var.
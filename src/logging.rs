@@ -0,0 +1,253 @@
+//! Pop CLI invocations forwarded to the client as MCP log messages.
+//!
+//! Each command is announced at `debug` level when it starts and reported with its
+//! status, duration and the end of its output when it exits (`info` on success,
+//! `error` on failure, `warning` when interrupted). Clients with a logging pane can
+//! follow builds there without the output ending up in tool results. Messages below
+//! the level the client set with `logging/setLevel` (default `info`) are dropped.
+//!
+//! Messages wait for the client in a bounded queue (see [`crate::stream`]); when the
+//! client falls behind, the oldest are dropped and the next message carries their
+//! count in `dropped_lines`. Watches and dev loops send their messages through
+//! [`forward`] the same way.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rmcp::model::{LoggingLevel, LoggingMessageNotificationParam};
+use rmcp::{Peer, RoleServer};
+
+use crate::audit::CommandStatus;
+use crate::stream::{self, Receiver, Sender};
+
+/// Logger name of command messages.
+pub const LOGGER: &str = "pop";
+
+/// Output lines kept in a command's log message (the end, where errors are).
+const SUMMARY_LINES: usize = 10;
+
+/// Longest output summary, in characters.
+const SUMMARY_CHARS: usize = 1000;
+
+#[derive(Debug)]
+struct State {
+    /// Messages for the connected client, if any.
+    messages: Option<Sender<LoggingMessageNotificationParam>>,
+    /// Least severe level sent.
+    level: LoggingLevel,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            messages: None,
+            level: LoggingLevel::Info,
+        }
+    }
+}
+
+/// Where the commands of one MCP session are logged.
+///
+/// Clones share the connection. Nothing is sent until [`Self::connect`] is called.
+#[derive(Debug, Clone, Default)]
+pub struct CommandLog {
+    state: Arc<Mutex<State>>,
+}
+
+impl CommandLog {
+    /// Create a log with no client connected.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send messages to `peer` from now on, in order.
+    pub fn connect(&self, peer: Peer<RoleServer>) {
+        deliver(peer, self.channel());
+    }
+
+    /// Replace the client with a channel receiving its messages.
+    fn channel(&self) -> Receiver<LoggingMessageNotificationParam> {
+        let (messages, receiver) = stream::bounded(stream::DEFAULT_CAPACITY);
+        if let Ok(mut state) = self.state.lock() {
+            state.messages = Some(messages);
+        }
+        receiver
+    }
+
+    /// Drop messages less severe than `level`.
+    pub fn set_level(&self, level: LoggingLevel) {
+        if let Ok(mut state) = self.state.lock() {
+            state.level = level;
+        }
+    }
+
+    fn send(&self, level: LoggingLevel, data: serde_json::Value) {
+        self.log(LOGGER, level, data);
+    }
+
+    /// Send `data` from `logger` at `level`, unless it is less severe than the level
+    /// the client set.
+    pub fn log(&self, logger: &str, level: LoggingLevel, data: serde_json::Value) {
+        let Ok(state) = self.state.lock() else {
+            return;
+        };
+        let Some(messages) = &state.messages else {
+            return;
+        };
+        if severity(level) < severity(state.level) {
+            return;
+        }
+        messages.send(LoggingMessageNotificationParam {
+            level,
+            logger: Some(logger.to_owned()),
+            data,
+        });
+    }
+
+    /// Announce that `command` (secrets masked) started.
+    pub fn started(&self, command: &str) {
+        self.send(
+            LoggingLevel::Debug,
+            serde_json::json!({ "event": "started", "command": command }),
+        );
+    }
+
+    /// Report how `command` ended, with the end of its `output` (secrets masked).
+    pub fn finished(&self, command: &str, status: CommandStatus, duration: Duration, output: &str) {
+        let level = match status {
+            CommandStatus::Succeeded => LoggingLevel::Info,
+            CommandStatus::Failed => LoggingLevel::Error,
+            CommandStatus::Interrupted => LoggingLevel::Warning,
+        };
+        self.send(
+            level,
+            serde_json::json!({
+                "event": "finished",
+                "command": command,
+                "status": status,
+                "duration_ms": u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+                "output": summary(output),
+            }),
+        );
+    }
+}
+
+/// Send the messages queued on the returned sender to `peer` as log messages, in
+/// order, until every clone of the sender is dropped or the client goes away.
+pub fn forward(peer: Peer<RoleServer>) -> Sender<LoggingMessageNotificationParam> {
+    let (messages, receiver) = stream::bounded(stream::DEFAULT_CAPACITY);
+    deliver(peer, receiver);
+    messages
+}
+
+fn deliver(peer: Peer<RoleServer>, mut messages: Receiver<LoggingMessageNotificationParam>) {
+    tokio::spawn(async move {
+        while let Some((message, dropped)) = messages.recv().await {
+            if peer
+                .notify_logging_message(with_dropped(message, dropped))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+}
+
+/// `message` with the number of messages `dropped` before it in `dropped_lines`.
+fn with_dropped(
+    mut message: LoggingMessageNotificationParam,
+    dropped: u64,
+) -> LoggingMessageNotificationParam {
+    if dropped > 0 {
+        if let Some(data) = message.data.as_object_mut() {
+            data.insert("dropped_lines".to_owned(), dropped.into());
+        }
+    }
+    message
+}
+
+/// Rank of `level`, least severe first.
+fn severity(level: LoggingLevel) -> u8 {
+    match level {
+        LoggingLevel::Debug => 0,
+        LoggingLevel::Info => 1,
+        LoggingLevel::Notice => 2,
+        LoggingLevel::Warning => 3,
+        LoggingLevel::Error => 4,
+        LoggingLevel::Critical => 5,
+        LoggingLevel::Alert => 6,
+        LoggingLevel::Emergency => 7,
+    }
+}
+
+/// Last non-empty lines of `output`, capped in length.
+fn summary(output: &str) -> String {
+    let lines: Vec<&str> = output
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    let kept = lines[lines.len().saturating_sub(SUMMARY_LINES)..].join("\n");
+    let len = kept.chars().count();
+    if len <= SUMMARY_CHARS {
+        return kept;
+    }
+    kept.chars().skip(len - SUMMARY_CHARS).collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_are_logged_at_or_above_the_client_level() {
+        let log = CommandLog::new();
+        // Nothing is sent before a client connects.
+        log.finished("pop build", CommandStatus::Succeeded, Duration::ZERO, "ok");
+
+        let mut messages = log.channel();
+        log.started("pop build --path ./flipper");
+        log.finished(
+            "pop build --path ./flipper",
+            CommandStatus::Failed,
+            Duration::from_millis(1500),
+            &format!(
+                "{}error: could not compile `flipper`\n",
+                "Compiling\n".repeat(20)
+            ),
+        );
+        let Some((message, _)) = messages.try_recv() else {
+            panic!("expected the failure to be logged");
+        };
+        assert_eq!(message.level, LoggingLevel::Error);
+        assert_eq!(message.logger.as_deref(), Some(LOGGER));
+        assert_eq!(message.data["status"], "failed");
+        assert_eq!(message.data["duration_ms"], 1500);
+        let output = message.data["output"].as_str().unwrap_or_default();
+        assert_eq!(output.lines().count(), SUMMARY_LINES);
+        assert!(output.ends_with("could not compile `flipper`"));
+        assert!(messages.try_recv().is_none());
+
+        log.set_level(LoggingLevel::Debug);
+        log.started("pop up ink-node");
+        let Some((message, _)) = messages.try_recv() else {
+            panic!("expected the start to be logged");
+        };
+        assert_eq!(message.data["command"], "pop up ink-node");
+    }
+
+    #[test]
+    fn messages_after_a_gap_count_the_dropped_ones() {
+        let message = |data| LoggingMessageNotificationParam {
+            level: LoggingLevel::Info,
+            logger: Some(LOGGER.to_owned()),
+            data,
+        };
+        let counted = with_dropped(message(serde_json::json!({ "event": "finished" })), 12);
+        assert_eq!(counted.data["dropped_lines"], 12);
+        let complete = with_dropped(message(serde_json::json!({ "event": "finished" })), 0);
+        assert!(complete.data.get("dropped_lines").is_none());
+    }
+}
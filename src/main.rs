@@ -1,19 +1,262 @@
 //! Pop MCP Server binary entry point
 
+use std::net::SocketAddr;
+use std::time::Duration;
+
 use anyhow::Result;
-use pop_mcp_server::PopMcpServer;
+use pop_mcp_server::ipfs::{Ipfs, IPFS_API_URL_ENV, IPFS_GATEWAY_ENV, IPFS_TOKEN_ENV};
+use pop_mcp_server::webhook::WEBHOOK_URL_ENV;
+use pop_mcp_server::{PopMcpServer, RetryPolicy};
+use rmcp::transport::sse_server::{SseServer, SseServerConfig};
+use rmcp::transport::streamable_http_server::{
+    session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
+};
 use rmcp::{transport::stdio, ServiceExt};
+use tokio_util::sync::CancellationToken;
+
+/// Env var enabling ASCII-only results.
+const ASCII_OUTPUT_ENV: &str = "POP_MCP_ASCII_OUTPUT";
+
+/// Env var setting the default Pop CLI command timeout, in seconds.
+const COMMAND_TIMEOUT_ENV: &str = "POP_MCP_COMMAND_TIMEOUT_SECS";
+
+/// Env var setting the maximum size of a tool result text, in bytes (0 disables truncation).
+const OUTPUT_LIMIT_ENV: &str = "POP_MCP_OUTPUT_LIMIT_BYTES";
+
+/// Env var setting how many times network-bound commands are attempted (1 disables retries).
+const RETRY_ATTEMPTS_ENV: &str = "POP_MCP_RETRY_ATTEMPTS";
+
+/// Address the HTTP and SSE transports bind to by default.
+const DEFAULT_BIND: &str = "127.0.0.1:8080";
+
+/// Path the HTTP transport serves MCP on.
+const HTTP_PATH: &str = "/mcp";
+
+/// Path SSE clients open their event stream on.
+const SSE_PATH: &str = "/sse";
+
+/// Path SSE clients post their messages to.
+const SSE_POST_PATH: &str = "/message";
+
+/// How clients reach the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Transport {
+    /// One client over stdin/stdout.
+    Stdio,
+    /// Any number of clients over streamable HTTP at `http://<addr>/mcp`.
+    Http(SocketAddr),
+    /// Any number of clients over the legacy SSE transport at `http://<addr>/sse`.
+    Sse(SocketAddr),
+}
+
+/// Parse `--transport stdio|http|sse` and `--bind <addr>` (HTTP and SSE only).
+fn parse_transport(args: impl IntoIterator<Item = String>) -> Result<Transport> {
+    let mut transport = None;
+    let mut bind = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_owned(), Some(value.to_owned())),
+            None => (arg, None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| anyhow::anyhow!("{} needs a value", flag))
+        };
+        match flag.as_str() {
+            "--transport" => transport = Some(value()?),
+            "--bind" => bind = Some(value()?),
+            _ => anyhow::bail!(
+                "Unknown argument '{}'. Usage: pop-mcp-server [--transport stdio|http|sse] [--bind <addr>]",
+                flag
+            ),
+        }
+    }
+    let addr = || -> Result<SocketAddr> {
+        let bind = bind.as_deref().unwrap_or(DEFAULT_BIND);
+        bind.parse().map_err(|_| {
+            anyhow::anyhow!("Invalid --bind address '{}' (e.g., {})", bind, DEFAULT_BIND)
+        })
+    };
+    match transport.as_deref().unwrap_or("stdio") {
+        "stdio" if bind.is_some() => {
+            anyhow::bail!("--bind only applies to --transport http or sse")
+        }
+        "stdio" => Ok(Transport::Stdio),
+        "http" => Ok(Transport::Http(addr()?)),
+        "sse" => Ok(Transport::Sse(addr()?)),
+        other => anyhow::bail!("Unknown transport '{}': use stdio, http or sse", other),
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let transport = parse_transport(std::env::args().skip(1))?;
+
     // Create MCP server with Pop CLI tools
-    let server = PopMcpServer::new();
+    let ascii = std::env::var(ASCII_OUTPUT_ENV).is_ok_and(|v| matches!(v.as_str(), "1" | "true"));
+    let mut server = PopMcpServer::new().with_ascii_output(ascii);
+    if let Some(secs) = std::env::var(COMMAND_TIMEOUT_ENV)
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+    {
+        server = server.with_command_timeout(Duration::from_secs(secs));
+    }
+    if let Some(attempts) = std::env::var(RETRY_ATTEMPTS_ENV)
+        .ok()
+        .and_then(|v| v.parse::<u32>().ok())
+        .filter(|attempts| *attempts > 0)
+    {
+        server = server.with_retry(RetryPolicy {
+            attempts,
+            ..RetryPolicy::default()
+        });
+    }
+    if let Some(bytes) = std::env::var(OUTPUT_LIMIT_ENV)
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+    {
+        server = server.with_output_limit(bytes);
+    }
+    if let Some(url) = std::env::var(WEBHOOK_URL_ENV)
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+    {
+        server = server.with_webhook(url);
+    }
+    let env = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+    let mut ipfs = env(IPFS_API_URL_ENV).map_or_else(Ipfs::default, Ipfs::new);
+    if let Some(token) = env(IPFS_TOKEN_ENV) {
+        ipfs = ipfs.with_token(token);
+    }
+    if let Some(gateway) = env(IPFS_GATEWAY_ENV) {
+        ipfs = ipfs.with_gateway(gateway);
+    }
+    server = server.with_ipfs(ipfs);
 
-    // Serve over stdio
+    // Serve until the client disconnects (stdio) or a termination signal arrives, then
+    // stop the nodes and networks launched without `detach`
+    let outcome = match transport {
+        Transport::Stdio => serve_stdio(server.clone()).await,
+        // Remote clients can't reach pop's wallet signing portal on this machine.
+        Transport::Http(addr) => serve_http(server.clone().with_wallet_signing(false), addr).await,
+        Transport::Sse(addr) => serve_sse(server.clone().with_wallet_signing(false), addr).await,
+    };
+    server.shutdown().await;
+    outcome
+}
+
+/// Serve one client over stdin/stdout.
+async fn serve_stdio(server: PopMcpServer) -> Result<()> {
     let service = server.serve(stdio()).await?;
+    tokio::select! {
+        outcome = service.waiting() => outcome.map(|_| ())?,
+        () = shutdown_signal() => {}
+    }
+    Ok(())
+}
 
-    // Wait for shutdown
-    service.waiting().await?;
+/// Serve clients over streamable HTTP at `http://<addr>/mcp`. Sessions share launched
+/// nodes and caches, but each selects its own node (see [`PopMcpServer::session`]).
+async fn serve_http(server: PopMcpServer, addr: SocketAddr) -> Result<()> {
+    let service = StreamableHttpService::new(
+        move || Ok(server.session()),
+        LocalSessionManager::default().into(),
+        StreamableHttpServerConfig::default(),
+    );
+    let router = axum::Router::new().nest_service(HTTP_PATH, service);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+    Ok(())
+}
 
+/// Serve clients over SSE: each opens an event stream at `http://<addr>/sse` and
+/// posts to `/message`. Every connection is its own session, as with HTTP.
+async fn serve_sse(server: PopMcpServer, addr: SocketAddr) -> Result<()> {
+    let ct = CancellationToken::new();
+    let (sse, router) = SseServer::new(SseServerConfig {
+        bind: addr,
+        sse_path: SSE_PATH.to_owned(),
+        post_path: SSE_POST_PATH.to_owned(),
+        ct: ct.clone(),
+        sse_keep_alive: None,
+    });
+    sse.with_service(move || server.session());
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    // Event streams stay open until the client leaves, so don't wait for them to
+    // drain: stop accepting and let the runtime close them on exit.
+    tokio::select! {
+        outcome = axum::serve(listener, router) => outcome?,
+        () = shutdown_signal() => ct.cancel(),
+    }
     Ok(())
 }
+
+/// Resolve on Ctrl+C, or SIGTERM on Unix.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+            }
+            return;
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Transport> {
+        parse_transport(args.iter().map(|arg| (*arg).to_owned()))
+    }
+
+    #[test]
+    fn stdio_is_the_default_transport() -> Result<()> {
+        assert_eq!(parse(&[])?, Transport::Stdio);
+        assert_eq!(parse(&["--transport", "stdio"])?, Transport::Stdio);
+        Ok(())
+    }
+
+    #[test]
+    fn http_binds_to_the_given_address() -> Result<()> {
+        assert_eq!(
+            parse(&["--transport", "http"])?,
+            Transport::Http("127.0.0.1:8080".parse()?)
+        );
+        assert_eq!(
+            parse(&["--transport=http", "--bind=0.0.0.0:9000"])?,
+            Transport::Http("0.0.0.0:9000".parse()?)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn sse_binds_like_http() -> Result<()> {
+        assert_eq!(
+            parse(&["--transport", "sse", "--bind", "127.0.0.1:9000"])?,
+            Transport::Sse("127.0.0.1:9000".parse()?)
+        );
+        assert!(parse(&["--transport=sse", "--bind=localhost"]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        assert!(parse(&["--transport", "websocket"]).is_err());
+        assert!(parse(&["--bind", "127.0.0.1:1"]).is_err());
+        assert!(parse(&["--transport", "http", "--bind", "localhost"]).is_err());
+        assert!(parse(&["--transport"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+    }
+}
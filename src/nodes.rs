@@ -0,0 +1,214 @@
+//! Registry of local nodes launched by the server.
+//!
+//! Every `up_ink_node` launch is recorded under a name (by default `node-<port>`) with
+//! its URL, pids and launch time. One node is selected at a time; tools that take an
+//! optional `url` fall back to it. Launching a node selects it. The selection is kept
+//! per MCP session.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::outputs::NodeInfo;
+
+/// A node launched by the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisteredNode {
+    /// Name the node is registered under.
+    pub name: String,
+    /// WebSocket URL of the node.
+    pub url: String,
+    /// Process ids to pass to `clean_nodes`.
+    pub pids: Vec<u32>,
+    /// Launch time, in seconds since the Unix epoch.
+    pub launched_at: u64,
+    /// Log files: the node's, then the Ethereum RPC node's when one was launched.
+    pub logs: Vec<String>,
+}
+
+/// Launched nodes by name, with the selected one.
+///
+/// The nodes are shared by every MCP session of the server; the selection is not, so
+/// that concurrent clients each keep using the node they picked (see [`Self::session`]).
+#[derive(Debug, Default)]
+pub struct NodeRegistry {
+    nodes: Arc<Mutex<BTreeMap<String, RegisteredNode>>>,
+    selected: Mutex<Option<String>>,
+}
+
+impl NodeRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A view of the same nodes for another MCP session, with nothing selected yet.
+    pub fn session(&self) -> Self {
+        Self {
+            nodes: Arc::clone(&self.nodes),
+            selected: Mutex::default(),
+        }
+    }
+
+    /// Record a launched node under `name` (default: `node-<port>`) and select it,
+    /// replacing any node of the same name. Returns the name used.
+    pub fn register(&self, node: &NodeInfo, name: Option<&str>) -> String {
+        let name = match name.map(str::trim).filter(|n| !n.is_empty()) {
+            Some(name) => name.to_owned(),
+            None => default_name(&node.url),
+        };
+        let launched_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        if let Ok(mut nodes) = self.nodes.lock() {
+            nodes.insert(
+                name.clone(),
+                RegisteredNode {
+                    name: name.clone(),
+                    url: node.url.clone(),
+                    pids: node.pids.clone(),
+                    launched_at,
+                    logs: node.logs.clone(),
+                },
+            );
+        }
+        if let Ok(mut selected) = self.selected.lock() {
+            *selected = Some(name.clone());
+        }
+        name
+    }
+
+    /// URL of the selected node.
+    pub fn selected_url(&self) -> Option<String> {
+        self.selected().map(|node| node.url)
+    }
+
+    /// The selected node.
+    pub fn selected(&self) -> Option<RegisteredNode> {
+        let name = self.selected.lock().ok()?.clone()?;
+        self.nodes.lock().ok()?.get(&name).cloned()
+    }
+
+    /// Registered nodes, by name.
+    pub fn list(&self) -> Vec<RegisteredNode> {
+        self.nodes
+            .lock()
+            .map(|nodes| nodes.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Select the node named `name`.
+    pub fn select(&self, name: &str) -> Option<RegisteredNode> {
+        let node = self.nodes.lock().ok()?.get(name).cloned()?;
+        *self.selected.lock().ok()? = Some(node.name.clone());
+        Some(node)
+    }
+
+    /// Forget the node named `name`. If this session had it selected, the most
+    /// recently launched remaining node is selected instead.
+    pub fn remove(&self, name: &str) -> Option<RegisteredNode> {
+        let (node, latest) = {
+            let mut nodes = self.nodes.lock().ok()?;
+            let node = nodes.remove(name)?;
+            let latest = nodes
+                .values()
+                .max_by_key(|node| node.launched_at)
+                .map(|node| node.name.clone());
+            (node, latest)
+        };
+        if let Ok(mut selected) = self.selected.lock() {
+            if selected.as_deref() == Some(name) {
+                *selected = latest;
+            }
+        }
+        Some(node)
+    }
+}
+
+/// `node-<port>` for `url`, or `node` when it has no port.
+fn default_name(url: &str) -> String {
+    let port = url
+        .rsplit(':')
+        .next()
+        .map(|rest| rest.trim_end_matches('/'))
+        .filter(|port| !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()));
+    match port {
+        Some(port) => format!("node-{}", port),
+        None => "node".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(url: &str, pid: u32) -> NodeInfo {
+        NodeInfo {
+            url: url.to_owned(),
+            pids: vec![pid],
+            logs: Vec::new(),
+            port: None,
+            eth_rpc_port: None,
+        }
+    }
+
+    #[test]
+    fn launches_select_the_latest_node() {
+        let registry = NodeRegistry::new();
+        assert_eq!(registry.selected_url(), None);
+        let first = registry.register(&node("ws://localhost:9944", 1), None);
+        let second = registry.register(&node("ws://localhost:9955/", 2), Some("other"));
+        assert_eq!(first, "node-9944");
+        assert_eq!(second, "other");
+        assert_eq!(
+            registry.selected_url(),
+            Some("ws://localhost:9955/".to_owned())
+        );
+        assert!(registry.select("node-9944").is_some());
+        assert!(registry.select("missing").is_none());
+        assert_eq!(
+            registry.selected_url(),
+            Some("ws://localhost:9944".to_owned())
+        );
+        assert_eq!(registry.list().len(), 2);
+    }
+
+    #[test]
+    fn removing_the_selected_node_selects_another() {
+        let registry = NodeRegistry::new();
+        registry.register(&node("ws://localhost:9944", 1), None);
+        registry.register(&node("ws://localhost:9955", 2), None);
+        assert_eq!(registry.remove("node-9955").map(|n| n.pids), Some(vec![2]));
+        assert_eq!(
+            registry.selected().map(|n| n.name),
+            Some("node-9944".to_owned())
+        );
+        assert!(registry.remove("node-9944").is_some());
+        assert_eq!(registry.selected_url(), None);
+    }
+
+    #[test]
+    fn default_name_uses_the_port() {
+        assert_eq!(default_name("ws://127.0.0.1:9944"), "node-9944");
+        assert_eq!(default_name("ws://127.0.0.1:9944/"), "node-9944");
+        assert_eq!(default_name("wss://rpc.example.com"), "node");
+    }
+
+    #[test]
+    fn sessions_share_nodes_but_not_the_selection() {
+        let registry = NodeRegistry::new();
+        let other = registry.session();
+        registry.register(&node("ws://localhost:9944", 1), None);
+        other.register(&node("ws://localhost:9955", 2), None);
+        assert_eq!(registry.list().len(), 2);
+        assert_eq!(
+            registry.selected_url(),
+            Some("ws://localhost:9944".to_owned())
+        );
+        assert_eq!(other.selected_url(), Some("ws://localhost:9955".to_owned()));
+
+        assert!(other.remove("node-9944").is_some());
+        assert_eq!(registry.selected_url(), None);
+        assert_eq!(other.selected_url(), Some("ws://localhost:9955".to_owned()));
+    }
+}
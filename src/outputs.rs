@@ -0,0 +1,426 @@
+//! Machine-readable shapes of tool results.
+//!
+//! Tool results are text, but the values clients need from them (contract addresses,
+//! node URLs, build artifacts) have stable shapes. Each shape is published as a JSON
+//! Schema resource (`schema://<tool>`) and is returned as the tool result's
+//! `structured_content`, so client developers can code against it.
+
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostics::CompileError;
+use crate::parsing;
+
+/// URI scheme of output schema resources.
+pub const SCHEMA_URI_PREFIX: &str = "schema://";
+
+/// Result of `deploy_contract`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct DeployOutput {
+    /// Address of the instantiated contract (0x-prefixed H160).
+    pub address: String,
+    /// Hash of the uploaded contract code, when reported.
+    pub code_hash: Option<String>,
+    /// Hash of the instantiation extrinsic, when reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    /// Block explorer page of the contract, on networks with a known explorer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explorer_url: Option<String>,
+}
+
+impl DeployOutput {
+    /// Parse Pop CLI deployment output.
+    pub fn parse(output: &str) -> Option<Self> {
+        Some(Self {
+            address: parsing::contract_address(output)?,
+            code_hash: parsing::code_hash(output),
+            tx_hash: parsing::extrinsic_hash(output),
+            explorer_url: None,
+        })
+    }
+
+    /// The fields as `label: value` lines, to put before the full output.
+    pub fn describe(&self) -> String {
+        let mut lines = vec![format!("Address: {}", self.address)];
+        if let Some(hash) = &self.code_hash {
+            lines.push(format!("Code hash: {}", hash));
+        }
+        if let Some(hash) = &self.tx_hash {
+            lines.push(format!("Extrinsic hash: {}", hash));
+        }
+        if let Some(url) = &self.explorer_url {
+            lines.push(format!("Explorer: {}", url));
+        }
+        lines.join("\n")
+    }
+}
+
+/// A build artifact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ArtifactFile {
+    /// Path of the file (the project path joined with `target/ink/<file>`).
+    pub path: String,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+/// Result of `build_contract`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct BuildOutput {
+    /// Path of the built project.
+    pub path: String,
+    /// Whether this was a release build.
+    pub release: bool,
+    /// Whether this was a verifiable (deterministic, Docker-based) build.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub verifiable: bool,
+    /// Whether the sources were unchanged since the last build, so pop was not run.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cached: bool,
+    /// Artifact file names under `target/ink`.
+    pub artifacts: Vec<String>,
+    /// Contract bundle (`.contract`: code and metadata), to deploy or upload.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle: Option<ArtifactFile>,
+    /// Contract metadata (`.json`), to call the contract or generate bindings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ArtifactFile>,
+    /// Contract code (`.polkavm`, or `.wasm` for ink! v5).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob: Option<ArtifactFile>,
+    /// Code hash recorded in the contract metadata.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_hash: Option<String>,
+    /// Compiler errors of a failed build.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<CompileError>,
+}
+
+impl BuildOutput {
+    /// Describe a completed build of the project at `path`.
+    ///
+    /// When `target/ink` holds several contracts (e.g. cross-contract calls), the
+    /// bundle, metadata, blob and code hash are those of the project's own package.
+    pub fn collect(path: &str, release: bool) -> Self {
+        let dir = Path::new(path).join("target/ink");
+        let mut artifacts: Vec<String> = std::fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter(|e| e.path().is_file())
+                    .map(|e| e.file_name().to_string_lossy().into_owned())
+                    .collect()
+            })
+            .unwrap_or_default();
+        artifacts.sort();
+        let stem = package_name(path)
+            .filter(|name| {
+                artifacts.iter().any(|file| {
+                    file.strip_prefix(name.as_str())
+                        .is_some_and(|rest| rest.starts_with('.'))
+                })
+            })
+            .or_else(|| {
+                [".contract", ".json"].iter().find_map(|suffix| {
+                    artifacts
+                        .iter()
+                        .find_map(|file| file.strip_suffix(suffix))
+                        .map(str::to_owned)
+                })
+            });
+        let file = |extensions: &[&str]| {
+            let stem = stem.as_deref()?;
+            extensions.iter().find_map(|extension| {
+                let path = dir.join(format!("{}.{}", stem, extension));
+                let size = std::fs::metadata(&path).ok()?.len();
+                Some(ArtifactFile {
+                    path: path.display().to_string(),
+                    size,
+                })
+            })
+        };
+        let metadata = file(&["json"]);
+        let code_hash = metadata.as_ref().and_then(|metadata| {
+            let json: serde_json::Value =
+                serde_json::from_str(&std::fs::read_to_string(&metadata.path).ok()?).ok()?;
+            json.pointer("/source/hash")
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_owned)
+        });
+        Self {
+            path: path.to_owned(),
+            release,
+            verifiable: false,
+            cached: false,
+            artifacts,
+            bundle: file(&["contract"]),
+            metadata,
+            blob: file(&["polkavm", "wasm"]),
+            code_hash,
+            errors: Vec::new(),
+        }
+    }
+}
+
+/// Package name in the `Cargo.toml` at `path`, with `-` as `_` like artifact names.
+fn package_name(path: &str) -> Option<String> {
+    let manifest = std::fs::read_to_string(Path::new(path).join("Cargo.toml")).ok()?;
+    let doc: toml_edit::DocumentMut = manifest.parse().ok()?;
+    doc.get("package")?
+        .get("name")?
+        .as_str()
+        .map(|name| name.replace('-', "_"))
+}
+
+/// Result of `up_ink_node`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct NodeInfo {
+    /// WebSocket URL of the node.
+    pub url: String,
+    /// Process ids to pass to `clean_nodes`.
+    pub pids: Vec<u32>,
+    /// Log files: the node's, then the Ethereum RPC node's when one was launched.
+    #[serde(default)]
+    pub logs: Vec<String>,
+    /// Port of the node's WebSocket RPC.
+    pub port: Option<u16>,
+    /// Port of the Ethereum RPC node, when one was launched.
+    pub eth_rpc_port: Option<u16>,
+}
+
+impl NodeInfo {
+    /// Parse Pop CLI `up ink-node` output.
+    pub fn parse(output: &str) -> Option<Self> {
+        let url = parsing::ws_url(output)?;
+        Some(Self {
+            port: parsing::url_port(&url),
+            eth_rpc_port: parsing::eth_rpc_url(output).and_then(|url| parsing::url_port(&url)),
+            url,
+            pids: parsing::pids(output).unwrap_or_default(),
+            logs: parsing::log_paths(output),
+        })
+    }
+}
+
+/// Result of `call_contract`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CallOutput {
+    /// Decoded return value (e.g. `Ok(false)`), when reported.
+    pub result: Option<String>,
+    /// `result` typed by the message's return type in the contract metadata (e.g.
+    /// `{"Ok": false}`), when the metadata is available.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+    /// Whether the call was submitted as an extrinsic rather than dry-run.
+    pub executed: bool,
+}
+
+impl CallOutput {
+    /// Parse Pop CLI contract call output.
+    pub fn parse(output: &str, executed: bool) -> Self {
+        Self {
+            result: parsing::call_result(output),
+            value: None,
+            executed,
+        }
+    }
+}
+
+/// Outcome of one call of `call_contract_batch`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct BatchCallResult {
+    /// Message called.
+    pub message: String,
+    /// Whether the call succeeded.
+    pub ok: bool,
+    /// Whether the call was submitted as an extrinsic rather than dry-run.
+    pub executed: bool,
+    /// Decoded return value (e.g. `Ok(())`), when reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    /// `result` typed by the message's return type, as in `call_contract`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+    /// Output of a failed call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result of `call_contract_batch`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ContractBatchOutput {
+    /// Calls that ran, in order.
+    pub calls: Vec<BatchCallResult>,
+    /// Calls not run because an earlier one failed.
+    pub skipped: usize,
+}
+
+/// Result of `query_contract_storage`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ContractStorageOutput {
+    /// Field read, or none for the whole root storage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    /// Contract storage key of the cell read (hex).
+    pub key: String,
+    /// Decoded value, typed like `call_contract`'s `value`; none when the cell is unset.
+    pub value: Option<serde_json::Value>,
+    /// Raw SCALE bytes of the cell (hex), when set.
+    pub raw: Option<String>,
+}
+
+/// Result of `estimate_gas`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GasEstimate {
+    /// Estimated computation weight (`ref_time`), when reported.
+    pub ref_time: Option<u64>,
+    /// Estimated proof size, in bytes, when reported.
+    pub proof_size: Option<u64>,
+    /// Storage deposit the call charges, in planck, when reported.
+    pub storage_deposit: Option<u128>,
+    /// Storage deposit the call refunds, in planck, when reported.
+    pub storage_refund: Option<u128>,
+    /// Decoded return value of the dry run (e.g. `Ok(())`), when reported.
+    pub result: Option<String>,
+}
+
+impl GasEstimate {
+    /// Parse the output of a Pop CLI contract call dry run.
+    pub fn parse(output: &str) -> Self {
+        let weight =
+            |key| parsing::labelled_number(output, key).and_then(|value| u64::try_from(value).ok());
+        let (storage_deposit, storage_refund) = parsing::storage_deposit(output);
+        Self {
+            ref_time: weight("ref_time"),
+            proof_size: weight("proof_size"),
+            storage_deposit,
+            storage_refund,
+            result: parsing::call_result(output),
+        }
+    }
+
+    /// Whether pop reported any gas or deposit figure.
+    pub fn is_empty(&self) -> bool {
+        self.ref_time.is_none()
+            && self.proof_size.is_none()
+            && self.storage_deposit.is_none()
+            && self.storage_refund.is_none()
+    }
+}
+
+/// A file pinned by `pin_to_ipfs`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PinnedFile {
+    /// Artifact file name (e.g. `flipper.contract`).
+    pub name: String,
+    /// Content identifier of the file.
+    pub cid: String,
+    /// Link to the file on the configured gateway.
+    pub url: String,
+}
+
+/// Result of `pin_to_ipfs`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PinOutput {
+    /// Pinned artifacts, in file name order.
+    pub files: Vec<PinnedFile>,
+}
+
+/// Tools with a published output schema.
+pub const SCHEMA_TOOLS: &[&str] = &[
+    "build_contract",
+    "call_contract",
+    "call_contract_batch",
+    "deploy_contract",
+    "estimate_gas",
+    "pin_to_ipfs",
+    "query_contract_storage",
+    "up_ink_node",
+];
+
+/// JSON Schema of `tool`'s structured output.
+pub fn output_schema(tool: &str) -> Option<serde_json::Value> {
+    let schema = match tool {
+        "build_contract" => schemars::schema_for!(BuildOutput),
+        "call_contract" => schemars::schema_for!(CallOutput),
+        "call_contract_batch" => schemars::schema_for!(ContractBatchOutput),
+        "deploy_contract" => schemars::schema_for!(DeployOutput),
+        "estimate_gas" => schemars::schema_for!(GasEstimate),
+        "pin_to_ipfs" => schemars::schema_for!(PinOutput),
+        "query_contract_storage" => schemars::schema_for!(ContractStorageOutput),
+        "up_ink_node" => schemars::schema_for!(NodeInfo),
+        _ => return None,
+    };
+    serde_json::to_value(schema).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deploy_output_parses_address_and_code_hash() {
+        let output = "◇  Contract deployed and instantiated:\n\
+                      │  The contract address is \"0x5801b439a678d9d3a68b8019da6a4abfa507de11\"\n\
+                      │  The contract code hash is \"0x4f1d\"\n\
+                      │  Extrinsic hash: 0x9a3f";
+        let deployed = DeployOutput::parse(output);
+        assert_eq!(
+            deployed,
+            Some(DeployOutput {
+                address: "0x5801b439a678d9d3a68b8019da6a4abfa507de11".to_owned(),
+                code_hash: Some("0x4f1d".to_owned()),
+                tx_hash: Some("0x9a3f".to_owned()),
+                explorer_url: None,
+            })
+        );
+        assert_eq!(
+            deployed.map(|d| d.describe()).unwrap_or_default(),
+            "Address: 0x5801b439a678d9d3a68b8019da6a4abfa507de11\nCode hash: 0x4f1d\nExtrinsic hash: 0x9a3f"
+        );
+        assert_eq!(DeployOutput::parse("Deployment complete"), None);
+    }
+
+    #[test]
+    fn build_output_reports_the_package_artifacts() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let ink = dir.path().join("target/ink");
+        std::fs::create_dir_all(&ink)?;
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"my-token\"\n",
+        )?;
+        for stem in ["dependency", "my_token"] {
+            std::fs::write(ink.join(format!("{}.contract", stem)), "bundle")?;
+            std::fs::write(ink.join(format!("{}.polkavm", stem)), "code")?;
+            std::fs::write(
+                ink.join(format!("{}.json", stem)),
+                format!(r#"{{"source": {{"hash": "0x{}"}}}}"#, stem.len()),
+            )?;
+        }
+        let path = dir.path().display().to_string();
+        let build = BuildOutput::collect(&path, true);
+        assert_eq!(build.artifacts.len(), 6);
+        assert_eq!(
+            build.bundle,
+            Some(ArtifactFile {
+                path: ink.join("my_token.contract").display().to_string(),
+                size: 6,
+            })
+        );
+        assert_eq!(build.blob.map(|blob| blob.size), Some(4));
+        assert_eq!(build.code_hash.as_deref(), Some("0x8"));
+        Ok(())
+    }
+
+    #[test]
+    fn output_schema_covers_schema_tools() {
+        for tool in SCHEMA_TOOLS {
+            assert!(output_schema(tool).is_some());
+        }
+        assert!(output_schema("call_chain").is_none());
+    }
+}
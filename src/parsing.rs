@@ -0,0 +1,697 @@
+//! Extraction of values from Pop CLI output.
+//!
+//! Pop CLI prints human-oriented text whose wording shifts between releases. Every
+//! phrase tools depend on lives here, and each value has fallbacks (alternate
+//! wordings, then JSON objects in the output) so an upstream rewording degrades one
+//! pattern instead of breaking a tool.
+
+use serde_json::Value;
+
+/// Phrases that mean a command failed even when pop exited with status 0.
+const ERROR_INDICATORS: &[&str] = &["Error:", "error:", "Failed to", "failed to", "Unable to"];
+
+/// Connection failures that happen before anything is submitted, worth retrying.
+const TRANSIENT_INDICATORS: &[&str] = &[
+    "not connected",
+    "connection refused",
+    "failed to connect",
+    "error trying to connect",
+];
+
+/// Leading decoration pop prints in front of values (gutters, spinners, status symbols).
+const GUTTER: &[char] = &['│', '┌', '└', '◇', '◆', '●', '○', '⚙', '✔', '✅', '>', '|'];
+
+/// Remove ANSI escape sequences (colours, cursor movement) from `input`.
+pub fn strip_ansi(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch == '\u{1b}' {
+            match chars.peek() {
+                // CSI: colours, cursor movement, line erasing.
+                Some('[') => {
+                    chars.next();
+                    for next in chars.by_ref() {
+                        if ('@'..='~').contains(&next) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: window titles and hyperlinks, ended by BEL or ESC \.
+                Some(']') => {
+                    chars.next();
+                    while let Some(next) = chars.next() {
+                        if next == '\u{7}' {
+                            break;
+                        }
+                        if next == '\u{1b}' && chars.peek() == Some(&'\\') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// Spinner frames pop redraws in place while work is running.
+fn is_spinner_frame(c: char) -> bool {
+    matches!(c, '\u{2800}'..='\u{28FF}' | '◒' | '◐' | '◓' | '◑')
+}
+
+/// Normalize raw command output for models and parsers.
+///
+/// Strips ANSI sequences, keeps only the final state of lines redrawn with `\r`
+/// (progress bars, spinners), drops bare spinner frames and collapses runs of
+/// identical lines. Box-drawing gutters are kept; the parsers skip them.
+pub fn normalize(output: &str) -> String {
+    let stripped = strip_ansi(output);
+    let mut lines: Vec<&str> = Vec::new();
+    for line in stripped.split('\n') {
+        let line = line
+            .split('\r')
+            .rev()
+            .find(|segment| !segment.trim().is_empty())
+            .unwrap_or_default()
+            .trim_end();
+        let spinner_only = !line.is_empty()
+            && line
+                .chars()
+                .all(|c| is_spinner_frame(c) || c.is_whitespace());
+        if spinner_only || (!line.is_empty() && lines.last() == Some(&line)) {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// Output lines without ANSI codes or leading decoration.
+fn clean_lines(output: &str) -> impl Iterator<Item = String> + '_ {
+    output.lines().map(|line| {
+        strip_ansi(line)
+            .trim()
+            .trim_start_matches(|c: char| GUTTER.contains(&c) || c.is_whitespace())
+            .trim_end()
+            .to_owned()
+    })
+}
+
+/// Top-level `keys` of the JSON objects in `output`, first match wins.
+///
+/// Looks at the whole output and at every line starting with `{`, so JSON mixed into
+/// log output is found too.
+fn json_field(output: &str, keys: &[&str]) -> Option<Value> {
+    let whole = std::iter::once(output.trim().to_owned());
+    whole
+        .chain(clean_lines(output).filter(|line| line.starts_with('{')))
+        .filter_map(|text| serde_json::from_str::<Value>(&text).ok())
+        .find_map(|json| keys.iter().find_map(|key| json.get(*key).cloned()))
+}
+
+/// First `0x`-prefixed hex token in `text`.
+fn first_hex(text: &str) -> Option<String> {
+    let start = text.find("0x")?;
+    let hex: String = text[start + 2..]
+        .chars()
+        .take_while(char::is_ascii_hexdigit)
+        .collect();
+    (!hex.is_empty()).then(|| format!("0x{}", hex))
+}
+
+/// First 0x-prefixed hex token on the first line mentioning `label` (case-insensitive).
+pub fn labelled_hex(output: &str, label: &str) -> Option<String> {
+    clean_lines(output)
+        .filter(|line| line.to_ascii_lowercase().contains(label))
+        .find_map(|line| first_hex(&line))
+}
+
+/// WebSocket URL of a launched node.
+///
+/// Tries, in order: a `url: ws://...` line, a JSON `url`/`ws_url` field, the `rpc=`
+/// parameter of a polkadot.js portal link, and finally any bare `ws://` token outside
+/// JSON.
+pub fn ws_url(output: &str) -> Option<String> {
+    let tidy = |url: &str| url.trim_end_matches('/').to_owned();
+    let token = |text: &str, scheme: &str| {
+        let start = text.find(scheme)?;
+        text[start..]
+            .split(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '#' | '&'))
+            .next()
+            .map(tidy)
+    };
+
+    let labelled = clean_lines(output).find_map(|line| {
+        let rest = line
+            .strip_prefix("url:")
+            .or_else(|| line.strip_prefix("endpoint:"))?;
+        token(rest, "ws://").or_else(|| token(rest, "wss://"))
+    });
+    labelled
+        .or_else(|| {
+            json_field(output, &["url", "ws_url"])
+                .and_then(|url| url.as_str().map(tidy))
+                .filter(|url| url.starts_with("ws"))
+        })
+        .or_else(|| {
+            clean_lines(output).find_map(|line| {
+                let (_, rest) = line.split_once("rpc=")?;
+                token(rest, "ws://")
+            })
+        })
+        .or_else(|| {
+            // Truncated JSON is skipped rather than mined for a possibly cut-off URL.
+            clean_lines(output)
+                .filter(|line| !line.contains("rpc=") && !line.starts_with('{'))
+                .find_map(|line| token(&line, "ws://"))
+        })
+}
+
+/// URL of the Ethereum RPC node launched alongside an ink! node.
+///
+/// Reads a JSON `eth_rpc_url` field, then the first `url:` line after pop's
+/// "Ethereum RPC node started" heading.
+pub fn eth_rpc_url(output: &str) -> Option<String> {
+    if let Some(url) = json_field(output, &["eth_rpc_url"]) {
+        return url.as_str().map(|url| url.trim_end_matches('/').to_owned());
+    }
+    clean_lines(output)
+        .skip_while(|line| !line.to_ascii_lowercase().contains("ethereum rpc"))
+        .find_map(|line| {
+            line.strip_prefix("url:")
+                .map(|url| url.trim().trim_end_matches('/').to_owned())
+        })
+}
+
+/// Port of `url`, e.g. 9944 for `ws://localhost:9944/`.
+pub fn url_port(url: &str) -> Option<u16> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split('/').next()?;
+    let (_, port) = authority.rsplit_once(':')?;
+    port.parse().ok()
+}
+
+/// Process ids of a launched node.
+///
+/// Reads the `kill -9 <pids>` (or `kill <pids>`) hint pop prints, then falls back to a
+/// JSON `pids` array and `pid: <n>` lines.
+pub fn pids(output: &str) -> Option<Vec<u32>> {
+    let numbers = |text: &str| -> Vec<u32> {
+        text.split_whitespace()
+            .map(|token| token.trim_matches(|c: char| !c.is_ascii_digit()))
+            .filter(|token| !token.is_empty())
+            .map_while(|token| token.parse::<u32>().ok())
+            .collect()
+    };
+
+    let hinted = clean_lines(output).find_map(|line| {
+        let (_, after) = line
+            .split_once("kill -9")
+            .or_else(|| line.split_once("kill "))?;
+        Some(numbers(after)).filter(|pids| !pids.is_empty())
+    });
+    hinted
+        .or_else(|| {
+            let pids: Vec<u32> = json_field(output, &["pids"])?
+                .as_array()?
+                .iter()
+                .filter_map(|pid| pid.as_u64().and_then(|pid| u32::try_from(pid).ok()))
+                .collect();
+            (!pids.is_empty()).then_some(pids)
+        })
+        .or_else(|| {
+            let pids: Vec<u32> = clean_lines(output)
+                .filter_map(|line| {
+                    let lower = line.to_ascii_lowercase();
+                    let rest = lower
+                        .strip_prefix("pid:")
+                        .or_else(|| lower.strip_prefix("pid "))?
+                        .to_owned();
+                    numbers(&rest).first().copied()
+                })
+                .collect();
+            (!pids.is_empty()).then_some(pids)
+        })
+}
+
+/// Log files of processes launched by `pop up`, in launch order.
+///
+/// Reads the `logs: tail -f <path>` lines pop prints, then falls back to a JSON `logs`
+/// array.
+pub fn log_paths(output: &str) -> Vec<String> {
+    let paths: Vec<String> = clean_lines(output)
+        .filter_map(|line| {
+            let rest = line.strip_prefix("logs:")?.trim();
+            let path = rest.strip_prefix("tail -f").unwrap_or(rest).trim();
+            (!path.is_empty()).then(|| path.to_owned())
+        })
+        .collect();
+    if !paths.is_empty() {
+        return paths;
+    }
+    json_field(output, &["logs"])
+        .and_then(|logs| {
+            logs.as_array().map(|logs| {
+                logs.iter()
+                    .filter_map(|path| path.as_str().map(str::to_owned))
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Path of the `zombie.json` describing a network launched by `pop up network`.
+pub fn zombie_json_path(output: &str) -> Option<String> {
+    clean_lines(output).find_map(|line| {
+        let path = line.strip_prefix("zombie.json:")?.trim();
+        (!path.is_empty()).then(|| path.to_owned())
+    })
+}
+
+/// Address of a deployed contract.
+pub fn contract_address(output: &str) -> Option<String> {
+    labelled_hex(output, "contract address")
+        .or_else(|| labelled_hex(output, "address"))
+        .or_else(|| {
+            json_field(output, &["address", "contract"])
+                .and_then(|value| value.as_str().and_then(first_hex))
+        })
+}
+
+/// Address of the contract in an `Instantiated` event (`{ deployer: 0x.., contract: 0x.. }`).
+pub fn instantiated_address(output: &str) -> Option<String> {
+    clean_lines(output)
+        .filter_map(|line| {
+            let (_, event) = line.split_once("Instantiated")?;
+            let (_, contract) = event.split_once("contract")?;
+            first_hex(contract)
+        })
+        .next()
+}
+
+/// Code hash of an uploaded contract.
+pub fn code_hash(output: &str) -> Option<String> {
+    // JSON first: a one-line JSON result also matches the label, but its first hex
+    // value is the address.
+    json_field(output, &["code_hash", "codeHash"])
+        .and_then(|value| value.as_str().and_then(first_hex))
+        .or_else(|| labelled_hex(output, "code hash"))
+        .or_else(|| labelled_hex(output, "code_hash"))
+}
+
+/// Hash of the extrinsic that carried a deployment or call.
+pub fn extrinsic_hash(output: &str) -> Option<String> {
+    ["extrinsic hash", "transaction hash", "tx hash"]
+        .iter()
+        .find_map(|label| labelled_hex(output, label))
+        .or_else(|| {
+            json_field(
+                output,
+                &["extrinsic_hash", "extrinsicHash", "tx_hash", "txHash"],
+            )
+            .and_then(|value| value.as_str().and_then(first_hex))
+        })
+}
+
+/// URL of the local signing portal pop serves for `--use-wallet`, e.g. from
+/// "Wallet signing portal started at http://127.0.0.1:9090.".
+pub fn signing_portal(output: &str) -> Option<String> {
+    clean_lines(output)
+        .filter(|line| line.to_ascii_lowercase().contains("signing portal"))
+        .find_map(|line| {
+            let start = line.find("http://").or_else(|| line.find("https://"))?;
+            line[start..]
+                .split(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '`'))
+                .next()
+                .map(|url| url.trim_end_matches(['.', ',', ')']).to_owned())
+        })
+}
+
+/// Whether `output` reports that the signer has no mapped Ethereum address, which
+/// revive chains require before deploying or calling contracts.
+pub fn account_unmapped(output: &str) -> bool {
+    let output = output.to_lowercase();
+    output.contains("accountunmapped")
+        || output.contains("account is not mapped")
+        || output.contains("account not mapped")
+}
+
+/// Value returned by a contract call (`Result: Ok(false)` prints `Ok(false)`).
+pub fn call_result(output: &str) -> Option<String> {
+    clean_lines(output)
+        .find_map(|line| {
+            ["Result:", "Return value:", "Returned:"]
+                .iter()
+                .find_map(|label| line.split_once(label))
+                .map(|(_, value)| value.trim().to_owned())
+        })
+        .or_else(|| {
+            json_field(output, &["result", "data"]).map(|value| match value {
+                Value::String(text) => text,
+                other => other.to_string(),
+            })
+        })
+}
+
+/// Number following `key` in `output` (e.g. `ref_time: 1_112_520` or
+/// `"proof_size":16689`), ignoring digit separators. Case-insensitive.
+pub fn labelled_number(output: &str, key: &str) -> Option<u128> {
+    let lower = strip_ansi(output).to_lowercase();
+    let key = key.to_lowercase();
+    lower.match_indices(&key).find_map(|(start, _)| {
+        let rest = lower[start + key.len()..]
+            .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, ':' | '=' | '"' | '('));
+        let digits: String = rest
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || matches!(c, '_' | ','))
+            .filter(char::is_ascii_digit)
+            .collect();
+        digits.parse().ok()
+    })
+}
+
+/// Storage deposit of a contract call: `(charged, refunded)`, from lines such as
+/// `Storage deposit: Charge(4000)` or a JSON `storage_deposit` field.
+pub fn storage_deposit(output: &str) -> (Option<u128>, Option<u128>) {
+    let line = clean_lines(output)
+        .map(|line| line.to_lowercase())
+        .find(|line| {
+            ["storage deposit", "storage_deposit", "storagedeposit"]
+                .iter()
+                .any(|label| line.contains(label))
+        });
+    let Some(line) = line else {
+        return (None, None);
+    };
+    (
+        labelled_number(&line, "charge"),
+        labelled_number(&line, "refund"),
+    )
+}
+
+/// Whether `output` is a command-line parser error rejecting `flag`.
+///
+/// Covers the wording of current and older clap releases.
+pub fn rejects_flag(output: &str, flag: &str) -> bool {
+    [
+        format!("unexpected argument '{}'", flag),
+        format!("Found argument '{}' which wasn't expected", flag),
+        format!("unrecognized option '{}'", flag),
+    ]
+    .iter()
+    .any(|phrase| output.contains(phrase.as_str()))
+}
+
+/// Whether `output` reports a failure, using the common indicators plus `extra`.
+pub fn has_error(output: &str, extra: &[&str]) -> bool {
+    ERROR_INDICATORS
+        .iter()
+        .chain(extra)
+        .any(|indicator| output.contains(indicator))
+}
+
+/// Whether `output` reports a transient connection failure (e.g. a node that is still
+/// starting) rather than a real error.
+pub fn is_transient(output: &str) -> bool {
+    let output = output.to_lowercase();
+    TRANSIENT_INDICATORS
+        .iter()
+        .any(|indicator| output.contains(indicator))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `pop up ink-node` as printed by pop 0.8 (substrate-contracts-node era).
+    const INK_NODE_V0_8: &str = "\
+┌   Pop CLI : Launch a local Ink! node
+│
+◇  Local node started successfully:
+│  portal: https://polkadot.js.org/apps/?rpc=ws://127.0.0.1:9944#/explorer
+│  url: ws://127.0.0.1:9944
+│  logs: tail -f /tmp/.tmpA1b2C3
+│
+└  ✅ Node started. Run `kill -9 5120` to terminate it.";
+
+    /// `pop up ink-node` as printed by pop 0.9, with the Ethereum RPC node and colours.
+    const INK_NODE_V0_9: &str = "\
+\u{1b}[2m┌   Pop CLI : Launch a local Ink! node\u{1b}[0m
+│
+⚙  Local node started successfully:
+│  portal: https://polkadot.js.org/apps/?rpc=ws://localhost:9944/#/explorer
+│  \u{1b}[1murl: ws://localhost:9944/\u{1b}[0m
+│  logs: tail -f /var/folders/32/T/.tmpDGAoYa
+│
+⚙  Ethereum RPC node started successfully:
+│  url: ws://localhost:8545
+│
+└  ✅ Ink! node bootstrapped successfully. Run `kill -9 11040 11253` to terminate it.";
+
+    /// A reworded launch that only keeps the portal link and a plain `kill` hint.
+    const INK_NODE_REWORDED: &str = "\
+◇  Node is live at https://polkadot.js.org/apps/?rpc=ws://localhost:9950#/explorer
+└  Stop it with: kill 777";
+
+    /// JSON launch report.
+    const INK_NODE_JSON: &str = r#"{"url":"ws://localhost:9944","pids":[41,42]}"#;
+
+    /// `pop up <contract>` as printed by pop 0.8.
+    const DEPLOY_V0_8: &str = "\
+◇  Contract deployed and instantiated: The Contract Address is \"0x5801b439a678d9d3a68b8019da6a4abfa507de11\"
+│  The contract code hash is \"0x4f1d9d5c\"";
+
+    /// `pop up <contract>` as printed by pop 0.9.
+    const DEPLOY_V0_9: &str = "\
+◇  Contract deployed and instantiated:
+│  address: 0x5801b439a678d9d3a68b8019da6a4abfa507de11
+│  code hash: 0x4f1d9d5c";
+
+    #[test]
+    fn ws_url_handles_captured_versions() {
+        assert_eq!(
+            ws_url(INK_NODE_V0_8),
+            Some("ws://127.0.0.1:9944".to_owned())
+        );
+        assert_eq!(
+            ws_url(INK_NODE_V0_9),
+            Some("ws://localhost:9944".to_owned())
+        );
+        assert_eq!(
+            ws_url(INK_NODE_REWORDED),
+            Some("ws://localhost:9950".to_owned())
+        );
+        assert_eq!(
+            ws_url(INK_NODE_JSON),
+            Some("ws://localhost:9944".to_owned())
+        );
+        assert_eq!(ws_url("Some error occurred"), None);
+    }
+
+    #[test]
+    fn log_paths_handle_captured_versions() {
+        assert_eq!(log_paths(INK_NODE_V0_8), vec!["/tmp/.tmpA1b2C3"]);
+        assert_eq!(
+            log_paths(INK_NODE_V0_9),
+            vec!["/var/folders/32/T/.tmpDGAoYa"]
+        );
+        assert_eq!(
+            log_paths(r#"{"url":"ws://localhost:9944","logs":["/tmp/a","/tmp/b"]}"#),
+            vec!["/tmp/a", "/tmp/b"]
+        );
+        assert!(log_paths(INK_NODE_REWORDED).is_empty());
+    }
+
+    #[test]
+    fn zombie_json_path_reads_the_launch_summary() {
+        let output = "◇  🚀 Network launched successfully - Ctrl+C to terminate\n│  base dir: /tmp/zombie-1\n│  zombie.json: /tmp/zombie-1/zombie.json\n└  Network detached.";
+        assert_eq!(
+            zombie_json_path(output),
+            Some("/tmp/zombie-1/zombie.json".to_owned())
+        );
+        assert_eq!(zombie_json_path("Network launch failed"), None);
+    }
+
+    #[test]
+    fn eth_rpc_url_handles_captured_versions() {
+        assert_eq!(
+            eth_rpc_url(INK_NODE_V0_9),
+            Some("ws://localhost:8545".to_owned())
+        );
+        assert_eq!(eth_rpc_url(INK_NODE_V0_8), None);
+        assert_eq!(
+            eth_rpc_url(r#"{"url":"ws://localhost:9944","eth_rpc_url":"ws://localhost:8550"}"#),
+            Some("ws://localhost:8550".to_owned())
+        );
+        assert_eq!(url_port("ws://localhost:9944/"), Some(9944));
+        assert_eq!(url_port("ws://[::1]:9955"), Some(9955));
+        assert_eq!(url_port("wss://rpc.polkadot.io"), None);
+    }
+
+    #[test]
+    fn pids_handle_captured_versions() {
+        assert_eq!(pids(INK_NODE_V0_8), Some(vec![5120]));
+        assert_eq!(pids(INK_NODE_V0_9), Some(vec![11040, 11253]));
+        assert_eq!(pids(INK_NODE_REWORDED), Some(vec![777]));
+        assert_eq!(pids(INK_NODE_JSON), Some(vec![41, 42]));
+        assert_eq!(pids("pid: 9001\npid: 9002"), Some(vec![9001, 9002]));
+        assert_eq!(pids("no node"), None);
+    }
+
+    #[test]
+    fn contract_address_and_code_hash_handle_captured_versions() {
+        for output in [DEPLOY_V0_8, DEPLOY_V0_9] {
+            assert_eq!(
+                contract_address(output),
+                Some("0x5801b439a678d9d3a68b8019da6a4abfa507de11".to_owned())
+            );
+            assert_eq!(code_hash(output), Some("0x4f1d9d5c".to_owned()));
+        }
+        let json = r#"{"contract":"0xabc","codeHash":"0xdef"}"#;
+        assert_eq!(contract_address(json), Some("0xabc".to_owned()));
+        assert_eq!(code_hash(json), Some("0xdef".to_owned()));
+        assert_eq!(contract_address("Deployment complete"), None);
+    }
+
+    #[test]
+    fn extrinsic_hash_reads_labels_and_json() {
+        assert_eq!(
+            extrinsic_hash("│  Extrinsic hash: 0x9a3f\n│  code hash: 0x4f"),
+            Some("0x9a3f".to_owned())
+        );
+        assert_eq!(
+            extrinsic_hash(r#"{"address":"0x58","tx_hash":"0x9a3f"}"#),
+            Some("0x9a3f".to_owned())
+        );
+        assert_eq!(extrinsic_hash(DEPLOY_V0_9), None);
+    }
+
+    #[test]
+    fn signing_portal_reads_the_wallet_url() {
+        assert_eq!(
+            signing_portal("◇  Wallet signing portal started at http://127.0.0.1:9090.\n◒  Waiting for signature..."),
+            Some("http://127.0.0.1:9090".to_owned())
+        );
+        assert_eq!(
+            signing_portal("Contract deployed at http://127.0.0.1:9090"),
+            None
+        );
+    }
+
+    #[test]
+    fn account_unmapped_matches_the_revive_error() {
+        assert!(account_unmapped(
+            "Error: Module error: Revive::AccountUnmapped"
+        ));
+        assert!(account_unmapped(
+            "Account not mapped: call map_account first"
+        ));
+        assert!(!account_unmapped(
+            "Error: Module error: Revive::AccountAlreadyMapped"
+        ));
+    }
+
+    #[test]
+    fn gas_figures_are_read_from_weights_and_deposits() {
+        let output = "◇  Gas limit: Weight { ref_time: 1_112_520, proof_size: 16689 }\n\
+                      │  Storage deposit: Charge(4000)";
+        assert_eq!(labelled_number(output, "ref_time"), Some(1_112_520));
+        assert_eq!(labelled_number(output, "proof_size"), Some(16689));
+        assert_eq!(labelled_number(output, "pov"), None);
+        assert_eq!(storage_deposit(output), (Some(4000), None));
+        assert_eq!(
+            storage_deposit(r#"{"storage_deposit":{"Refund":120}}"#),
+            (None, Some(120))
+        );
+        assert_eq!(storage_deposit("Result: Ok(false)"), (None, None));
+    }
+
+    #[test]
+    fn code_hash_reads_json_before_labels() {
+        assert_eq!(
+            code_hash(r#"{"address":"0x58","code_hash":"0x4f"}"#),
+            Some("0x4f".to_owned())
+        );
+    }
+
+    #[test]
+    fn instantiated_address_reads_the_event() {
+        let output =
+            "Events:\n  Revive::Instantiated { deployer: 0xd43593c7, contract: 0x5801b439 }";
+        assert_eq!(instantiated_address(output), Some("0x5801b439".to_owned()));
+        assert_eq!(
+            instantiated_address("Revive::CodeStored { code_hash: 0x4f }"),
+            None
+        );
+    }
+
+    #[test]
+    fn call_result_handles_labels_and_json() {
+        assert_eq!(
+            call_result("⚙  Result: Ok(false)\n└  done"),
+            Some("Ok(false)".to_owned())
+        );
+        assert_eq!(call_result("│  Return value: 42"), Some("42".to_owned()));
+        assert_eq!(
+            call_result(r#"{"result":{"Ok":true}}"#),
+            Some(r#"{"Ok":true}"#.to_owned())
+        );
+        assert_eq!(call_result("no result"), None);
+    }
+
+    #[test]
+    fn rejects_flag_matches_clap_wordings() {
+        assert!(rejects_flag(
+            "error: unexpected argument '--output' found",
+            "--output"
+        ));
+        assert!(rejects_flag(
+            "error: Found argument '--output' which wasn't expected, or isn't valid in this context",
+            "--output"
+        ));
+        assert!(!rejects_flag(
+            "error: unexpected argument '-y' found",
+            "--output"
+        ));
+    }
+
+    #[test]
+    fn has_error_uses_common_and_extra_indicators() {
+        assert!(has_error("Error: Invalid value", &[]));
+        assert!(has_error("Contract not found", &["Contract not found"]));
+        assert!(!has_error(
+            "Call completed successfully!",
+            &["Contract not found"]
+        ));
+    }
+
+    #[test]
+    fn is_transient_only_matches_connection_failures() {
+        assert!(is_transient(
+            "Error: Networking or low-level protocol error: Not connected"
+        ));
+        assert!(is_transient("error: Connection refused (os error 111)"));
+        assert!(!is_transient(
+            "Error: Module(ModuleError { name: \"Balances\" })"
+        ));
+    }
+
+    #[test]
+    fn normalize_collapses_progress_redraws() {
+        let raw = "\u{1b}[32m◒  Building\u{1b}[0m\r◐  Building\r✔  Built\n\
+                   [=>   ] 10%\r[===> ] 60%\r[=====] 100%\n\
+                   ⠋\n\
+                   Compiling flipper\nCompiling flipper\n\
+                   \u{1b}]8;;https://use.ink\u{7}docs\u{1b}]8;;\u{7}\n";
+        assert_eq!(
+            normalize(raw),
+            "✔  Built\n[=====] 100%\nCompiling flipper\ndocs\n"
+        );
+    }
+}
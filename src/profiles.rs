@@ -0,0 +1,128 @@
+//! Named network profiles.
+//!
+//! A profile is a saved set of `up_network` parameters (config path or relay chain,
+//! parachains and overrides), stored as `<name>.json` in the profiles directory so it
+//! survives server restarts.
+
+use std::path::{Path, PathBuf};
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::tools::UpNetworkParams;
+
+/// Env var overriding the profiles directory.
+pub const PROFILES_DIR_ENV: &str = "POP_MCP_PROFILES_DIR";
+
+/// Directory holding saved profiles: `$POP_MCP_PROFILES_DIR`, else
+/// `~/.config/pop-mcp/network-profiles`.
+pub fn profiles_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var(PROFILES_DIR_ENV) {
+        return PathBuf::from(dir);
+    }
+    std::env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::temp_dir())
+        .join(".config/pop-mcp/network-profiles")
+}
+
+/// Check that `name` is usable as a profile name (letters, digits, `-` and `_`).
+pub fn validate_name(name: &str) -> PopMcpResult<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(PopMcpError::InvalidInput(format!(
+            "Profile name '{}' may only contain letters, digits, '-' and '_'",
+            name
+        )));
+    }
+    Ok(())
+}
+
+fn profile_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.json", name))
+}
+
+/// Save `params` as profile `name` in `dir`, replacing any profile of that name.
+pub async fn save(dir: &Path, name: &str, params: &UpNetworkParams) -> PopMcpResult<PathBuf> {
+    validate_name(name)?;
+    tokio::fs::create_dir_all(dir)
+        .await
+        .map_err(|e| PopMcpError::Internal(format!("Failed to create {}: {}", dir.display(), e)))?;
+    let json =
+        serde_json::to_string_pretty(params).map_err(|e| PopMcpError::Internal(e.to_string()))?;
+    let path = profile_path(dir, name);
+    tokio::fs::write(&path, json)
+        .await
+        .map_err(|e| PopMcpError::Internal(format!("Failed to write {}: {}", path.display(), e)))?;
+    Ok(path)
+}
+
+/// Load profile `name` from `dir`, or `None` when there is no such profile.
+pub async fn load(dir: &Path, name: &str) -> PopMcpResult<Option<UpNetworkParams>> {
+    validate_name(name)?;
+    let path = profile_path(dir, name);
+    let json = match tokio::fs::read_to_string(&path).await {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(PopMcpError::Internal(format!(
+                "Failed to read {}: {}",
+                path.display(),
+                e
+            )))
+        }
+    };
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|e| PopMcpError::Internal(format!("Profile {} is corrupt: {}", path.display(), e)))
+}
+
+/// Names of the profiles saved in `dir`, sorted.
+pub fn list(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            name.strip_suffix(".json").map(str::to_owned)
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn profiles_round_trip() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let params = UpNetworkParams {
+            path: None,
+            chain: Some("paseo".to_owned()),
+            verbose: None,
+            parachain: Some(vec!["asset-hub".to_owned()]),
+            overrides: None,
+            detach: None,
+        };
+        save(dir.path(), "relay-assets", &params).await?;
+        let loaded = load(dir.path(), "relay-assets").await?;
+        assert_eq!(
+            loaded.and_then(|p| p.parachain),
+            Some(vec!["asset-hub".to_owned()])
+        );
+        assert!(load(dir.path(), "missing").await?.is_none());
+        assert_eq!(list(dir.path()), vec!["relay-assets"]);
+        Ok(())
+    }
+
+    #[test]
+    fn validate_name_rejects_paths() {
+        assert!(validate_name("relay_assets-2").is_ok());
+        assert!(validate_name("../evil").is_err());
+        assert!(validate_name("").is_err());
+    }
+}
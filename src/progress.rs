@@ -0,0 +1,53 @@
+//! MCP progress notifications for long-running commands.
+//!
+//! When a client sends a progress token with a tool call, each output line of the
+//! running Pop CLI command is forwarded as a `notifications/progress` message so the
+//! client isn't left blind during multi-minute builds.
+
+use rmcp::model::{ProgressNotificationParam, ProgressToken};
+use rmcp::{Peer, RoleServer};
+use tokio::task::JoinHandle;
+
+use crate::executor::LineSink;
+use crate::stream;
+use crate::tools::common::OutputStyle;
+
+/// Forward lines sent to the returned sink as progress notifications for `token`,
+/// rendered in `style`.
+///
+/// The task ends once every clone of the sink is dropped; await it so all
+/// notifications are sent before the tool result. Lines the client is too slow for
+/// are dropped, oldest first, and counted in the message of the next notification.
+pub fn forward(
+    peer: Peer<RoleServer>,
+    token: ProgressToken,
+    style: OutputStyle,
+) -> (LineSink, JoinHandle<()>) {
+    let (sink, mut lines) = stream::bounded::<String>(stream::DEFAULT_CAPACITY);
+    let handle = tokio::spawn(async move {
+        let mut progress = 0.0;
+        while let Some((line, dropped)) = lines.recv().await {
+            // Dropped lines still count, so progress tracks the command's output.
+            progress += 1.0 + dropped as f64;
+            let notification = ProgressNotificationParam {
+                progress_token: token.clone(),
+                progress,
+                total: None,
+                message: Some(style.render(with_dropped(line, dropped))),
+            };
+            // A client that went away just stops receiving progress; the command runs on.
+            if peer.notify_progress(notification).await.is_err() {
+                break;
+            }
+        }
+    });
+    (sink, handle)
+}
+
+/// `line` preceded by a note on the `dropped` lines before it, if any.
+fn with_dropped(line: String, dropped: u64) -> String {
+    if dropped == 0 {
+        return line;
+    }
+    format!("[{} lines dropped] {}", dropped, line)
+}
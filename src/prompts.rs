@@ -0,0 +1,256 @@
+//! MCP prompts: guided ink! workflows.
+//!
+//! Each prompt expands into a user message that walks the client through one workflow
+//! with this server's tools, filled in with the contract, template and network given
+//! as arguments.
+
+use rmcp::model::{
+    GetPromptResult, JsonObject, Prompt, PromptArgument, PromptMessage, PromptMessageRole,
+};
+
+use crate::error::{PopMcpError, PopMcpResult};
+
+/// Prompt: create, build and deploy a contract.
+pub const CREATE_BUILD_DEPLOY: &str = "create-build-deploy-contract";
+
+/// Prompt: find and fix the errors of a failed build.
+pub const DEBUG_FAILED_BUILD: &str = "debug-failed-build";
+
+/// Prompt: write end-to-end tests for a contract.
+pub const WRITE_E2E_TESTS: &str = "write-e2e-tests";
+
+/// Contract templates accepted by `create_contract`.
+const TEMPLATES: &[&str] = &[
+    "standard",
+    "erc20",
+    "erc721",
+    "erc1155",
+    "dns",
+    "cross-contract-calls",
+    "multisig",
+];
+
+fn argument(name: &str, description: &str, required: bool) -> PromptArgument {
+    PromptArgument {
+        name: name.to_owned(),
+        title: None,
+        description: Some(description.to_owned()),
+        required: Some(required),
+    }
+}
+
+fn network_argument() -> PromptArgument {
+    argument(
+        "network",
+        "WebSocket URL of the network to deploy to (default: a local ink! node launched with up_ink_node)",
+        false,
+    )
+}
+
+/// All prompts, with their arguments.
+pub fn list_prompts() -> Vec<Prompt> {
+    vec![
+        Prompt {
+            title: Some("Create, build and deploy an ink! contract".to_owned()),
+            ..Prompt::new(
+                CREATE_BUILD_DEPLOY,
+                Some("Scaffold a contract from a template, build it, deploy it and call it once"),
+                Some(vec![
+                    argument("name", "Name of the new contract (e.g., my_token)", true),
+                    argument(
+                        "template",
+                        &format!("Contract template (default: standard): {}", TEMPLATES.join(", ")),
+                        false,
+                    ),
+                    network_argument(),
+                ]),
+            )
+        },
+        Prompt {
+            title: Some("Debug a failed build".to_owned()),
+            ..Prompt::new(
+                DEBUG_FAILED_BUILD,
+                Some("Rebuild a contract, read the summarized compiler errors and fix them one at a time"),
+                Some(vec![argument("path", "Path to the contract directory", true)]),
+            )
+        },
+        Prompt {
+            title: Some("Write e2e tests".to_owned()),
+            ..Prompt::new(
+                WRITE_E2E_TESTS,
+                Some("Add ink! end-to-end tests covering a contract's messages and run them"),
+                Some(vec![argument("path", "Path to the contract directory", true)]),
+            )
+        },
+    ]
+}
+
+/// Non-empty string argument `name`.
+fn optional<'a>(arguments: Option<&'a JsonObject>, name: &str) -> Option<&'a str> {
+    arguments?
+        .get(name)?
+        .as_str()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn required<'a>(arguments: Option<&'a JsonObject>, name: &str) -> PopMcpResult<&'a str> {
+    optional(arguments, name)
+        .ok_or_else(|| PopMcpError::InvalidInput(format!("Argument '{}' is required", name)))
+}
+
+/// The step that gets a node to deploy to, and how later steps refer to it.
+fn network_steps(network: Option<&str>) -> (String, String) {
+    match network {
+        Some(url) => (
+            format!("Check that {} is reachable with node_health.", url),
+            format!("url \"{}\"", url),
+        ),
+        None => (
+            "Launch a local node with up_ink_node (skip it if list_nodes shows one running)."
+                .to_owned(),
+            "the selected node (leave url unset)".to_owned(),
+        ),
+    }
+}
+
+fn create_build_deploy(arguments: Option<&JsonObject>) -> PopMcpResult<String> {
+    let name = required(arguments, "name")?;
+    let template = optional(arguments, "template").unwrap_or("standard");
+    if !TEMPLATES.contains(&template) {
+        return Err(PopMcpError::InvalidInput(format!(
+            "Unknown template '{}'. Use one of: {}",
+            template,
+            TEMPLATES.join(", ")
+        )));
+    }
+    let (node_step, target) = network_steps(optional(arguments, "network"));
+    Ok(format!(
+        "Create, build and deploy the ink! contract \"{name}\" from the \"{template}\" template.\n\n\
+         1. Create it with create_contract (name \"{name}\", template \"{template}\").\n\
+         2. Build it with build_contract (path \"./{name}\"). If the build fails, fix the \
+         errors listed first in the result and build again.\n\
+         3. {node_step}\n\
+         4. Deploy it with deploy_contract (path \"./{name}\", execute true) to {target}. \
+         Read the constructor and its arguments from the contract first.\n\
+         5. Call one read-only message with call_contract to check the deployment, and \
+         report the contract address."
+    ))
+}
+
+fn debug_failed_build(arguments: Option<&JsonObject>) -> PopMcpResult<String> {
+    let path = required(arguments, "path")?;
+    Ok(format!(
+        "The ink! contract at \"{path}\" does not build. Fix it.\n\n\
+         1. Run build_contract (path \"{path}\"). A failed build starts with a summary of \
+         the distinct compiler errors: code, message, file:line:column and a snippet.\n\
+         2. Fix the first error only: read the file around the reported line; \
+         `rustc --explain <code>` describes unfamiliar error codes.\n\
+         3. Build again; later errors are often caused by the first one.\n\
+         4. Repeat until the build succeeds, then run test_contract (path \"{path}\") to \
+         check the fixes did not break behaviour.\n\
+         5. Summarize each fix in one line."
+    ))
+}
+
+fn write_e2e_tests(arguments: Option<&JsonObject>) -> PopMcpResult<String> {
+    let path = required(arguments, "path")?;
+    Ok(format!(
+        "Write ink! end-to-end tests for the contract at \"{path}\".\n\n\
+         1. Read the contract's constructors and messages, and the existing tests.\n\
+         2. Add an `e2e_tests` module gated by `#[cfg(all(test, feature = \"e2e-tests\"))]` \
+         with one `#[ink_e2e::test]` per message: instantiate the contract, call the \
+         message and assert on the returned value, the storage read back and emitted events. \
+         Cover one failing call (wrong caller or invalid input) per message that can fail.\n\
+         3. Make sure `ink_e2e` is a dev-dependency and an `e2e-tests` feature exists in \
+         Cargo.toml.\n\
+         4. Run test_contract (path \"{path}\", e2e true); the tests start their own node. \
+         Fix failures until every test passes, then list the tests you added."
+    ))
+}
+
+/// Expand prompt `name` with `arguments`.
+pub fn get_prompt(name: &str, arguments: Option<&JsonObject>) -> PopMcpResult<GetPromptResult> {
+    let (description, text) = match name {
+        CREATE_BUILD_DEPLOY => (
+            "Create, build and deploy an ink! contract",
+            create_build_deploy(arguments)?,
+        ),
+        DEBUG_FAILED_BUILD => (
+            "Debug a failed contract build",
+            debug_failed_build(arguments)?,
+        ),
+        WRITE_E2E_TESTS => (
+            "Write e2e tests for an ink! contract",
+            write_e2e_tests(arguments)?,
+        ),
+        _ => {
+            return Err(PopMcpError::InvalidInput(format!(
+                "Unknown prompt '{}'. Available: {}, {}, {}",
+                name, CREATE_BUILD_DEPLOY, DEBUG_FAILED_BUILD, WRITE_E2E_TESTS
+            )))
+        }
+    };
+    Ok(GetPromptResult {
+        description: Some(description.to_owned()),
+        messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::panic)]
+mod tests {
+    use super::*;
+    use rmcp::model::PromptMessageContent;
+
+    fn text(result: &GetPromptResult) -> &str {
+        match &result.messages[0].content {
+            PromptMessageContent::Text { text } => text,
+            _ => panic!("expected a text message"),
+        }
+    }
+
+    fn arguments(pairs: &[(&str, &str)]) -> JsonObject {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_owned(), serde_json::Value::from(*v)))
+            .collect()
+    }
+
+    #[test]
+    fn every_listed_prompt_expands() {
+        let args = arguments(&[("name", "token"), ("path", "./token")]);
+        for prompt in list_prompts() {
+            assert!(
+                get_prompt(&prompt.name, Some(&args)).is_ok(),
+                "{}",
+                prompt.name
+            );
+        }
+    }
+
+    #[test]
+    fn prompts_are_filled_in_with_template_and_network() -> PopMcpResult<()> {
+        let args = arguments(&[
+            ("name", "token"),
+            ("template", "erc20"),
+            ("network", "wss://testnet.example"),
+        ]);
+        let result = get_prompt(CREATE_BUILD_DEPLOY, Some(&args))?;
+        assert!(text(&result).contains("create_contract (name \"token\", template \"erc20\")"));
+        assert!(text(&result).contains("to url \"wss://testnet.example\""));
+
+        let local = get_prompt(CREATE_BUILD_DEPLOY, Some(&arguments(&[("name", "token")])))?;
+        assert!(text(&local).contains("template \"standard\""));
+        assert!(text(&local).contains("up_ink_node"));
+        Ok(())
+    }
+
+    #[test]
+    fn prompts_reject_missing_arguments_and_unknown_templates() {
+        assert!(get_prompt(DEBUG_FAILED_BUILD, None).is_err());
+        let args = arguments(&[("name", "token"), ("template", "erc9999")]);
+        assert!(get_prompt(CREATE_BUILD_DEPLOY, Some(&args)).is_err());
+        assert!(get_prompt("deploy-everything", None).is_err());
+    }
+}
@@ -0,0 +1,238 @@
+//! Saved chain queries.
+//!
+//! A saved query is a named set of `call_chain` parameters (pallet, function, args and
+//! optionally a node URL), stored as `<name>.json` in the queries directory. Args and
+//! the URL may contain `{placeholder}`s (e.g. `{address}`) filled in when the query is
+//! run, so a team can keep its standard checks ("validator set", "treasury balance")
+//! in a shared directory and run them in one call.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PopMcpError, PopMcpResult};
+
+/// Env var overriding the queries directory.
+pub const QUERIES_DIR_ENV: &str = "POP_MCP_QUERIES_DIR";
+
+/// Directory holding saved queries: `$POP_MCP_QUERIES_DIR`, else
+/// `~/.config/pop-mcp/queries`.
+pub fn queries_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var(QUERIES_DIR_ENV) {
+        return PathBuf::from(dir);
+    }
+    std::env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::temp_dir())
+        .join(".config/pop-mcp/queries")
+}
+
+/// A saved `call_chain` parameter set.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SavedQuery {
+    /// What the query checks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Node to query, unless given when run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Pallet name.
+    pub pallet: String,
+    /// Storage item, constant or extrinsic name.
+    pub function: String,
+    /// Call arguments, possibly with placeholders.
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+impl SavedQuery {
+    /// Names of the `{placeholder}`s in the URL and args.
+    pub fn placeholders(&self) -> BTreeSet<String> {
+        self.url
+            .iter()
+            .chain(&self.args)
+            .flat_map(|text| placeholders_in(text))
+            .collect()
+    }
+
+    /// The URL and args with every placeholder replaced by its value in `values`.
+    ///
+    /// Fails naming the placeholders without a value, or the values matching no
+    /// placeholder.
+    pub fn fill(
+        &self,
+        values: &BTreeMap<String, String>,
+    ) -> Result<(Option<String>, Vec<String>), String> {
+        let placeholders = self.placeholders();
+        let missing: Vec<&str> = placeholders
+            .iter()
+            .filter(|name| !values.contains_key(*name))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!("Missing values for: {}", missing.join(", ")));
+        }
+        let unknown: Vec<&str> = values
+            .keys()
+            .filter(|name| !placeholders.contains(*name))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            return Err(format!(
+                "The query has no placeholder named: {}",
+                unknown.join(", ")
+            ));
+        }
+        let fill = |text: &str| {
+            values.iter().fold(text.to_owned(), |text, (name, value)| {
+                text.replace(&format!("{{{}}}", name), value)
+            })
+        };
+        Ok((
+            self.url.as_deref().map(fill),
+            self.args.iter().map(|arg| fill(arg)).collect(),
+        ))
+    }
+}
+
+/// Names of the `{placeholder}`s in `text`. Braces around anything but letters,
+/// digits and `_` (e.g. a struct literal argument) are not placeholders.
+fn placeholders_in(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find(['{', '}']) else {
+            break;
+        };
+        let name = &rest[..end];
+        if rest[end..].starts_with('}')
+            && !name.is_empty()
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            names.push(name.to_owned());
+        }
+        rest = &rest[end..];
+    }
+    names
+}
+
+/// Check that `name` is usable as a query name (letters, digits, `-` and `_`).
+pub fn validate_name(name: &str) -> PopMcpResult<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(PopMcpError::InvalidInput(format!(
+            "Query name '{}' may only contain letters, digits, '-' and '_'",
+            name
+        )));
+    }
+    Ok(())
+}
+
+fn query_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.json", name))
+}
+
+/// Save `query` as `name` in `dir`, replacing any query of that name.
+pub async fn save(dir: &Path, name: &str, query: &SavedQuery) -> PopMcpResult<PathBuf> {
+    validate_name(name)?;
+    tokio::fs::create_dir_all(dir)
+        .await
+        .map_err(|e| PopMcpError::Internal(format!("Failed to create {}: {}", dir.display(), e)))?;
+    let json =
+        serde_json::to_string_pretty(query).map_err(|e| PopMcpError::Internal(e.to_string()))?;
+    let path = query_path(dir, name);
+    tokio::fs::write(&path, json)
+        .await
+        .map_err(|e| PopMcpError::Internal(format!("Failed to write {}: {}", path.display(), e)))?;
+    Ok(path)
+}
+
+/// Load query `name` from `dir`, or `None` when there is no such query.
+pub async fn load(dir: &Path, name: &str) -> PopMcpResult<Option<SavedQuery>> {
+    validate_name(name)?;
+    let path = query_path(dir, name);
+    let json = match tokio::fs::read_to_string(&path).await {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(PopMcpError::Internal(format!(
+                "Failed to read {}: {}",
+                path.display(),
+                e
+            )))
+        }
+    };
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|e| PopMcpError::Internal(format!("Query {} is corrupt: {}", path.display(), e)))
+}
+
+/// Names of the queries saved in `dir`, sorted.
+pub fn list(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            name.strip_suffix(".json").map(str::to_owned)
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balance_query() -> SavedQuery {
+        SavedQuery {
+            description: Some("Free balance of an account".to_owned()),
+            url: None,
+            pallet: "System".to_owned(),
+            function: "Account".to_owned(),
+            args: vec!["{address}".to_owned(), "{ free: 0 }".to_owned()],
+        }
+    }
+
+    #[tokio::test]
+    async fn queries_round_trip() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        save(dir.path(), "balance", &balance_query()).await?;
+        assert_eq!(load(dir.path(), "balance").await?, Some(balance_query()));
+        assert!(load(dir.path(), "missing").await?.is_none());
+        assert_eq!(list(dir.path()), vec!["balance"]);
+        assert!(load(dir.path(), "../balance").await.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn placeholders_are_filled_from_values() {
+        let query = balance_query();
+        assert_eq!(
+            query.placeholders().into_iter().collect::<Vec<_>>(),
+            vec!["address"]
+        );
+
+        let values = BTreeMap::from([("address".to_owned(), "5Grw".to_owned())]);
+        assert_eq!(
+            query.fill(&values),
+            Ok((None, vec!["5Grw".to_owned(), "{ free: 0 }".to_owned()]))
+        );
+        assert_eq!(
+            query.fill(&BTreeMap::new()),
+            Err("Missing values for: address".to_owned())
+        );
+        let extra = BTreeMap::from([
+            ("address".to_owned(), "5Grw".to_owned()),
+            ("amount".to_owned(), "1".to_owned()),
+        ]);
+        assert!(query.fill(&extra).is_err());
+    }
+}
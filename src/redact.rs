@@ -0,0 +1,122 @@
+//! Masking of secret key URIs in command output.
+//!
+//! Signing commands receive a SURI (`--suri`, usually from `PRIVATE_KEY`) and pop may
+//! echo it back, e.g. in argument errors. Output is returned to the model verbatim, so
+//! the executor runs everything it reports through [`Secrets::mask`] first.
+
+/// Replacement for masked secrets.
+pub const MASK: &str = "[REDACTED]";
+
+/// Flag carrying a secret key URI.
+const SURI_FLAG: &str = "--suri";
+
+/// Secrets shorter than this are not masked on their own, to avoid mangling output
+/// that merely shares a few characters with them.
+const MIN_SECRET_LEN: usize = 6;
+
+/// Secret values to mask for one command.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Secrets(Vec<String>);
+
+impl Secrets {
+    /// Secrets passed in `args` via `--suri`, plus the `PRIVATE_KEY` env value.
+    pub fn for_args(args: &[&str]) -> Self {
+        let mut values: Vec<String> = crate::read_private_key_suri().into_iter().collect();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if *arg == SURI_FLAG {
+                values.extend(iter.next().map(|value| (*value).to_owned()));
+            } else if let Some(value) = arg.strip_prefix("--suri=") {
+                values.push(value.to_owned());
+            }
+        }
+        Self::new(values)
+    }
+
+    /// Mask the given values. A SURI's phrase is also masked without its derivation path.
+    pub fn new(values: impl IntoIterator<Item = String>) -> Self {
+        let mut secrets = Vec::new();
+        for value in values {
+            let value = value.trim().to_owned();
+            if let Some((phrase, _)) = value.split_once('/') {
+                secrets.push(phrase.trim().to_owned());
+            }
+            secrets.push(value);
+        }
+        secrets.retain(|s| s.len() >= MIN_SECRET_LEN);
+        // Longest first, so a full SURI is masked before its phrase.
+        secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        secrets.dedup();
+        Self(secrets)
+    }
+
+    /// `text` with every secret replaced by [`MASK`].
+    pub fn mask(&self, text: &str) -> String {
+        self.0
+            .iter()
+            .fold(text.to_owned(), |text, secret| text.replace(secret, MASK))
+    }
+
+    /// `args` joined for display, with the value of `--suri` masked.
+    pub fn mask_args(&self, args: &[&str]) -> String {
+        self.mask_argv(args).join(" ")
+    }
+
+    /// `args` with the value of `--suri` and any other secret masked.
+    pub fn mask_argv(&self, args: &[&str]) -> Vec<String> {
+        let mut shown = Vec::with_capacity(args.len());
+        let mut hide_next = false;
+        for arg in args {
+            if hide_next {
+                shown.push(MASK.to_owned());
+                hide_next = false;
+            } else if *arg == SURI_FLAG {
+                shown.push((*arg).to_owned());
+                hide_next = true;
+            } else if arg.starts_with("--suri=") {
+                shown.push(format!("{}={}", SURI_FLAG, MASK));
+            } else {
+                shown.push(self.mask(arg));
+            }
+        }
+        shown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC: &str = "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+
+    #[test]
+    fn masks_suri_and_its_phrase() {
+        let secrets = Secrets::new([format!("{}//Alice", MNEMONIC)]);
+        let output = format!(
+            "error: invalid suri '{}//Alice'\nphrase: {}",
+            MNEMONIC, MNEMONIC
+        );
+        assert_eq!(
+            secrets.mask(&output),
+            "error: invalid suri '[REDACTED]'\nphrase: [REDACTED]"
+        );
+    }
+
+    #[test]
+    fn for_args_collects_suri_flags() {
+        let secrets = Secrets::for_args(&["call", "chain", "--suri", "0xdeadbeef", "-y"]);
+        assert_eq!(secrets.mask("signer 0xdeadbeef"), "signer [REDACTED]");
+        let secrets = Secrets::for_args(&["up", "--suri=0xcafebabe"]);
+        assert_eq!(secrets.mask("0xcafebabe"), "[REDACTED]");
+    }
+
+    #[test]
+    fn short_values_are_left_alone() {
+        let secrets = Secrets::new(["//Bob".to_owned()]);
+        assert_eq!(secrets.mask("//Bob signed"), "//Bob signed");
+        assert_eq!(
+            secrets.mask_args(&["call", "--suri", "//Bob"]),
+            "call --suri [REDACTED]"
+        );
+    }
+}
@@ -2,15 +2,32 @@
 
 use rmcp::model::{AnnotateAble, RawResource, Resource, ResourceContents};
 
+use crate::accounts::{self, ADDRESS_BOOK_URI};
+use crate::audit::{self, AUDIT_URI};
+use crate::deployments::{Registry, DEPLOYMENTS_URI};
+use crate::devloop::{ContractAlias, CONTRACTS_URI};
+use crate::docs::{self, GUIDES};
+use crate::outputs::{output_schema, SCHEMA_TOOLS, SCHEMA_URI_PREFIX};
+use crate::spill::{self, LOG_URI_PREFIX};
+
 /// URI for the type hints resource
 pub const TYPE_HINTS_URI: &str = "pop://docs/type-hints";
 
 /// Minimal documentation for Substrate/ink! types used in call_chain
 const TYPE_HINTS_CONTENT: &str = include_str!("../docs/type-hints.txt");
 
+/// URI for the Dedot/Typink frontend guide
+pub const DEDOT_URI: &str = "pop://docs/dedot";
+
+/// Condensed Dedot/Typink reference for adapting contract frontends
+pub(crate) const DEDOT_CONTENT: &str = include_str!("../docs/dedot-typink.txt");
+
+/// Resources listed per `resources/list` page.
+pub const PAGE_SIZE: usize = 50;
+
 /// List all available resources
 pub fn list_resources() -> Vec<Resource> {
-    vec![RawResource {
+    let mut resources = vec![RawResource {
         uri: TYPE_HINTS_URI.to_owned(),
         name: "type-hints".to_owned(),
         title: Some("Substrate Type Hints".to_owned()),
@@ -22,32 +39,298 @@ pub fn list_resources() -> Vec<Resource> {
         size: Some(TYPE_HINTS_CONTENT.len() as u32),
         icons: None,
     }
-    .no_annotation()]
+    .no_annotation()];
+    resources.push(
+        RawResource {
+            uri: DEDOT_URI.to_owned(),
+            name: "dedot".to_owned(),
+            title: Some("Dedot/Typink Frontends".to_owned()),
+            description: Some(
+                "Typink hooks, bindings generation and deployments for ink! contract frontends"
+                    .to_owned(),
+            ),
+            mime_type: Some("text/markdown".to_owned()),
+            size: u32::try_from(DEDOT_CONTENT.len()).ok(),
+            icons: None,
+        }
+        .no_annotation(),
+    );
+    for guide in GUIDES {
+        let index = guide.index();
+        resources.push(
+            RawResource {
+                uri: guide.uri(),
+                name: guide.name.to_owned(),
+                title: Some(format!("{} guide", guide.title)),
+                description: Some(format!(
+                    "Chapters of the {} guide, each readable as its own resource",
+                    guide.title
+                )),
+                mime_type: Some("text/markdown".to_owned()),
+                size: u32::try_from(index.len()).ok(),
+                icons: None,
+            }
+            .no_annotation(),
+        );
+        resources.extend(guide.chapters().iter().map(|chapter| {
+            RawResource {
+                uri: chapter.uri.clone(),
+                name: chapter
+                    .uri
+                    .trim_start_matches(docs::DOCS_URI_PREFIX)
+                    .to_owned(),
+                title: Some(chapter.title.clone()),
+                description: Some(format!("{} guide: {}", guide.title, chapter.title)),
+                mime_type: Some("text/plain".to_owned()),
+                size: u32::try_from(chapter.text.len()).ok(),
+                icons: None,
+            }
+            .no_annotation()
+        }));
+    }
+    resources.push(
+        RawResource {
+            uri: ADDRESS_BOOK_URI.to_owned(),
+            name: "address-book".to_owned(),
+            title: Some("Address book".to_owned()),
+            description: Some(
+                "Accounts registered this session (e.g. by derive_test_accounts), with SURIs and addresses"
+                    .to_owned(),
+            ),
+            mime_type: Some("application/json".to_owned()),
+            size: None,
+            icons: None,
+        }
+        .no_annotation(),
+    );
+    resources.push(
+        RawResource {
+            uri: AUDIT_URI.to_owned(),
+            name: "command-history".to_owned(),
+            title: Some("Command audit trail".to_owned()),
+            description: Some(
+                "Every Pop CLI command run this session: masked arguments, duration, exit status and output tail"
+                    .to_owned(),
+            ),
+            mime_type: Some("application/json".to_owned()),
+            size: None,
+            icons: None,
+        }
+        .no_annotation(),
+    );
+    resources.push(
+        RawResource {
+            uri: CONTRACTS_URI.to_owned(),
+            name: "contracts".to_owned(),
+            title: Some("Contract address book".to_owned()),
+            description: Some(
+                "Latest address of each contract deployed by a dev loop, by alias".to_owned(),
+            ),
+            mime_type: Some("application/json".to_owned()),
+            size: None,
+            icons: None,
+        }
+        .no_annotation(),
+    );
+    resources.push(
+        RawResource {
+            uri: DEPLOYMENTS_URI.to_owned(),
+            name: "deployments".to_owned(),
+            title: Some("Deployment registry".to_owned()),
+            description: Some(
+                "Contracts deployed with deploy_contract: path, network, address, code hash, constructor and time"
+                    .to_owned(),
+            ),
+            mime_type: Some("application/json".to_owned()),
+            size: None,
+            icons: None,
+        }
+        .no_annotation(),
+    );
+    resources.extend(SCHEMA_TOOLS.iter().map(|tool| {
+        RawResource {
+            uri: format!("{}{}", SCHEMA_URI_PREFIX, tool),
+            name: format!("{}-schema", tool),
+            title: Some(format!("{} output schema", tool)),
+            description: Some(format!(
+                "JSON Schema of the structured result of the {} tool",
+                tool
+            )),
+            mime_type: Some("application/schema+json".to_owned()),
+            size: None,
+            icons: None,
+        }
+        .no_annotation()
+    }));
+    resources.extend(spill::logs().into_iter().map(|log| {
+        RawResource {
+            name: log.uri.trim_start_matches(LOG_URI_PREFIX).to_owned(),
+            title: Some("Full tool output".to_owned()),
+            description: Some(
+                "Complete output of a tool call whose result was truncated".to_owned(),
+            ),
+            mime_type: Some("text/plain".to_owned()),
+            size: u32::try_from(log.size).ok(),
+            uri: log.uri,
+            icons: None,
+        }
+        .no_annotation()
+    }));
+    resources
+}
+
+/// The page of resources starting at `cursor`, and the cursor of the next page.
+///
+/// Cursors are offsets into [`list_resources`]; resources created between pages
+/// (spilled logs) are appended, so later pages still line up.
+pub fn list_page(cursor: Option<&str>) -> Result<(Vec<Resource>, Option<String>), String> {
+    let resources = list_resources();
+    let start = match cursor {
+        None => 0,
+        Some(cursor) => cursor
+            .parse::<usize>()
+            .ok()
+            .filter(|start| *start <= resources.len())
+            .ok_or_else(|| format!("Invalid resources cursor '{}'", cursor))?,
+    };
+    let end = resources.len().min(start + PAGE_SIZE);
+    let next = (end < resources.len()).then(|| end.to_string());
+    Ok((resources[start..end].to_vec(), next))
 }
 
 /// Read a resource by URI
-pub fn read_resource(uri: &str) -> Option<ResourceContents> {
+///
+/// `contracts` is the contract address book and `deployments` the deployment registry
+/// of the session reading it.
+pub fn read_resource(
+    uri: &str,
+    contracts: &[ContractAlias],
+    deployments: &Registry,
+) -> Option<ResourceContents> {
     if uri == TYPE_HINTS_URI {
-        Some(ResourceContents::text(TYPE_HINTS_CONTENT, TYPE_HINTS_URI))
-    } else {
-        None
+        return Some(ResourceContents::text(TYPE_HINTS_CONTENT, TYPE_HINTS_URI));
+    }
+    if uri == DEDOT_URI {
+        return Some(ResourceContents::TextResourceContents {
+            uri: uri.to_owned(),
+            mime_type: Some("text/markdown".to_owned()),
+            text: DEDOT_CONTENT.to_owned(),
+            meta: None,
+        });
+    }
+    if let Some(guide) = docs::guide(uri) {
+        return Some(ResourceContents::TextResourceContents {
+            uri: uri.to_owned(),
+            mime_type: Some("text/markdown".to_owned()),
+            text: guide.index(),
+            meta: None,
+        });
     }
+    if let Some(chapter) = docs::chapter(uri) {
+        return Some(ResourceContents::text(chapter.text, uri));
+    }
+    let json = match uri {
+        ADDRESS_BOOK_URI => Some(serde_json::to_string_pretty(&accounts::address_book())),
+        AUDIT_URI => Some(serde_json::to_string_pretty(&audit::entries())),
+        CONTRACTS_URI => Some(serde_json::to_string_pretty(contracts)),
+        DEPLOYMENTS_URI => Some(serde_json::to_string_pretty(&deployments.load().ok()?)),
+        _ => None,
+    };
+    if let Some(json) = json {
+        let text = json.ok()?;
+        return Some(ResourceContents::TextResourceContents {
+            uri: uri.to_owned(),
+            mime_type: Some("application/json".to_owned()),
+            text,
+            meta: None,
+        });
+    }
+    if uri.starts_with(LOG_URI_PREFIX) {
+        return spill::read_log(uri).map(|text| ResourceContents::text(text, uri));
+    }
+    let schema = output_schema(uri.strip_prefix(SCHEMA_URI_PREFIX)?)?;
+    let text = serde_json::to_string_pretty(&schema).ok()?;
+    Some(ResourceContents::TextResourceContents {
+        uri: uri.to_owned(),
+        mime_type: Some("application/schema+json".to_owned()),
+        text,
+        meta: None,
+    })
 }
 
 #[cfg(test)]
+#[allow(clippy::panic)]
 mod tests {
     use super::*;
 
+    fn registry() -> Registry {
+        Registry::new(std::env::temp_dir().join("pop-mcp-unused-deployments.json"))
+    }
+
     #[test]
     fn list_resources_returns_type_hints() {
         let resources = list_resources();
-        assert_eq!(resources.len(), 1);
+        // Logs spilled by other tests in this process may follow.
+        let fixed = resources
+            .iter()
+            .filter(|r| !r.uri.starts_with(LOG_URI_PREFIX))
+            .count();
+        let guides: usize = GUIDES.iter().map(|guide| 1 + guide.chapters().len()).sum();
+        assert_eq!(fixed, 6 + SCHEMA_TOOLS.len() + guides);
         assert_eq!(resources[0].uri, TYPE_HINTS_URI);
     }
 
+    #[test]
+    fn resources_are_listed_in_pages() -> Result<(), String> {
+        let all = list_resources();
+        let mut listed = Vec::new();
+        let mut cursor = None;
+        loop {
+            let (page, next) = list_page(cursor.as_deref())?;
+            assert!(page.len() <= PAGE_SIZE);
+            listed.extend(page.into_iter().map(|r| r.raw.uri));
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert!(all.len() > PAGE_SIZE);
+        // Logs spilled by other tests meanwhile are appended after these.
+        let uris: Vec<&str> = all.iter().map(|r| r.uri.as_str()).collect();
+        assert_eq!(listed[..uris.len()], uris[..]);
+        assert!(list_page(Some("next please")).is_err());
+        assert!(list_page(Some("1000000")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn read_resource_returns_guide_chapters() {
+        let content = read_resource("pop://docs/xcm", &[], &registry());
+        let Some(ResourceContents::TextResourceContents { text, .. }) = content else {
+            panic!("xcm guide index missing");
+        };
+        assert!(text.contains("<pop://docs/xcm/1-what-is-xcm>"));
+        let content = read_resource("pop://docs/xcm/1-what-is-xcm", &[], &registry());
+        let Some(ResourceContents::TextResourceContents { text, .. }) = content else {
+            panic!("xcm chapter missing");
+        };
+        assert!(text.contains("XCM (Cross-Consensus Messaging) is a **messaging format"));
+        assert!(!text.contains("2. CORE CONCEPTS"));
+    }
+
+    #[test]
+    fn read_resource_returns_output_schema() {
+        let content = read_resource("schema://deploy_contract", &[], &registry());
+        let Some(ResourceContents::TextResourceContents { text, .. }) = content else {
+            panic!("deploy_contract schema missing");
+        };
+        assert!(text.contains("\"address\""));
+        assert!(read_resource("schema://call_chain", &[], &registry()).is_none());
+    }
+
     #[test]
     fn read_resource_returns_content_for_valid_uri() {
-        let content = read_resource(TYPE_HINTS_URI);
+        let content = read_resource(TYPE_HINTS_URI, &[], &registry());
         assert!(content.is_some());
         if let Some(ResourceContents::TextResourceContents { text, .. }) = content {
             assert!(text.contains("MultiAddress"));
@@ -57,7 +340,7 @@ mod tests {
 
     #[test]
     fn read_resource_returns_none_for_invalid_uri() {
-        let content = read_resource("pop://invalid");
+        let content = read_resource("pop://invalid", &[], &registry());
         assert!(content.is_none());
     }
 }
@@ -0,0 +1,139 @@
+//! MCP client roots (workspace folders) as the default project directory.
+//!
+//! Clients such as editors advertise the folders the user has open as roots. When
+//! they do, tools that scaffold projects without an explicit `cwd` create them in the
+//! first root instead of the server's working directory, which for a client-spawned
+//! server is rarely the user's workspace. Roots are fetched on first use and again
+//! after the client reports that they changed.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rmcp::model::Root;
+use rmcp::{Peer, RoleServer};
+
+/// How long to wait for the client to list its roots.
+const LIST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The project root of one MCP session.
+#[derive(Debug, Clone, Default)]
+pub struct ClientRoots {
+    /// First usable root, once fetched (`Some(None)`: the client has none).
+    root: Arc<Mutex<Option<Option<PathBuf>>>>,
+}
+
+impl ClientRoots {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget the fetched roots, e.g. after `notifications/roots/list_changed`.
+    pub fn invalidate(&self) {
+        if let Ok(mut root) = self.root.lock() {
+            *root = None;
+        }
+    }
+
+    /// Directory of the client's first `file://` root, or `None` when the client
+    /// does not support roots, lists none or does not answer.
+    pub async fn project_root(&self, peer: &Peer<RoleServer>) -> Option<PathBuf> {
+        if let Some(root) = self.root.lock().ok().and_then(|root| root.clone()) {
+            return root;
+        }
+        let supported = peer
+            .peer_info()
+            .is_some_and(|info| info.capabilities.roots.is_some());
+        if !supported {
+            return None;
+        }
+        let root = match tokio::time::timeout(LIST_TIMEOUT, peer.list_roots()).await {
+            Ok(Ok(result)) => first_root(&result.roots),
+            // Ask again next time rather than caching a failure.
+            _ => return None,
+        };
+        if let Ok(mut cached) = self.root.lock() {
+            *cached = Some(root.clone());
+        }
+        root
+    }
+}
+
+/// First root that is an existing local directory.
+pub fn first_root(roots: &[Root]) -> Option<PathBuf> {
+    roots
+        .iter()
+        .filter_map(|root| root_path(&root.uri))
+        .find(|path| path.is_dir())
+}
+
+/// Local path of a `file://` URI, percent-decoded.
+fn root_path(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    // `file://localhost/path` and `file:///path` both name `/path`.
+    let path = rest.strip_prefix("localhost").unwrap_or(rest);
+    if !path.starts_with('/') {
+        return None;
+    }
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| path.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok().map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root(uri: &str) -> Root {
+        Root {
+            uri: uri.to_owned(),
+            name: None,
+        }
+    }
+
+    #[test]
+    fn file_roots_decode_to_paths() {
+        assert_eq!(
+            root_path("file:///home/dev/my%20project"),
+            Some(PathBuf::from("/home/dev/my project"))
+        );
+        assert_eq!(
+            root_path("file://localhost/srv/app"),
+            Some(PathBuf::from("/srv/app"))
+        );
+        assert_eq!(root_path("https://example.com/repo"), None);
+        assert_eq!(root_path("file://server/share"), None);
+    }
+
+    #[test]
+    fn the_first_existing_directory_is_the_project_root() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let uri = format!("file://{}", dir.path().display());
+        let roots = [
+            root("https://example.com/repo"),
+            root("file:///nonexistent/workspace"),
+            root(&uri),
+        ];
+        assert_eq!(first_root(&roots), Some(dir.path().to_path_buf()));
+        assert_eq!(first_root(&[]), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+//! Pooled JSON-RPC client for read-only chain queries.
+//!
+//! Spawning `pop call chain` for every query reconnects and refetches metadata each time.
+//! Read-only queries go through this pool instead, which keeps one WebSocket connection
+//! per endpoint alive (with pings) and transparently reconnects dropped ones.
+//! Transaction paths keep using Pop CLI, except `resubmit_transaction`, which needs a
+//! tip and nonce Pop CLI cannot set.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::core::params::ArrayParams;
+use jsonrpsee::ws_client::{PingConfig, WsClient, WsClientBuilder};
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+use crate::error::{PopMcpError, PopMcpResult};
+
+/// Interval between WebSocket keepalive pings.
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+/// Timeout for establishing a new connection.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+/// Timeout for a single request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Pool of WebSocket JSON-RPC clients keyed by endpoint URL.
+#[derive(Debug, Default)]
+pub struct RpcPool {
+    clients: Mutex<HashMap<String, Arc<WsClient>>>,
+}
+
+impl RpcPool {
+    /// Create an empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a connected client for `url`, reusing a pooled connection when it is still alive.
+    ///
+    /// New connections are made without holding the pool's lock, so a slow or unreachable
+    /// endpoint doesn't stall queries to the others.
+    pub async fn client(&self, url: &str) -> PopMcpResult<Arc<WsClient>> {
+        validate_ws_url(url)?;
+
+        if let Some(client) = self.clients.lock().await.get(url) {
+            if client.is_connected() {
+                return Ok(Arc::clone(client));
+            }
+        }
+
+        let client = WsClientBuilder::new()
+            .enable_ws_ping(PingConfig::new().ping_interval(PING_INTERVAL))
+            .connection_timeout(CONNECTION_TIMEOUT)
+            .request_timeout(REQUEST_TIMEOUT)
+            .build(url)
+            .await
+            .map_err(|e| PopMcpError::Rpc(format!("Failed to connect to {}: {}", url, e)))?;
+        let mut clients = self.clients.lock().await;
+        // Keep the connection another caller made meanwhile, so the pool holds one per URL.
+        if let Some(pooled) = clients.get(url).filter(|pooled| pooled.is_connected()) {
+            return Ok(Arc::clone(pooled));
+        }
+        let client = Arc::new(client);
+        clients.insert(url.to_owned(), Arc::clone(&client));
+        Ok(client)
+    }
+
+    /// Issue a JSON-RPC request against `url` with positional params.
+    pub async fn request(
+        &self,
+        url: &str,
+        method: &str,
+        params: Vec<Value>,
+    ) -> PopMcpResult<Value> {
+        let client = self.client(url).await?;
+
+        let mut rpc_params = ArrayParams::new();
+        for param in params {
+            rpc_params
+                .insert(param)
+                .map_err(|e| PopMcpError::InvalidInput(format!("Invalid RPC param: {}", e)))?;
+        }
+
+        client
+            .request::<Value, _>(method, rpc_params)
+            .await
+            .map_err(|e| PopMcpError::Rpc(format!("{} failed: {}", method, e)))
+    }
+
+    /// Number of pooled connections.
+    pub async fn len(&self) -> usize {
+        self.clients.lock().await.len()
+    }
+
+    /// Whether the pool holds no connections.
+    pub async fn is_empty(&self) -> bool {
+        self.clients.lock().await.is_empty()
+    }
+}
+
+/// Ensure the URL uses a WebSocket scheme.
+fn validate_ws_url(url: &str) -> PopMcpResult<()> {
+    if url.starts_with("ws://") || url.starts_with("wss://") {
+        Ok(())
+    } else {
+        Err(PopMcpError::InvalidInput(format!(
+            "RPC URL must start with ws:// or wss:// (got '{}')",
+            url
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_ws_url_accepts_ws_schemes() {
+        assert!(validate_ws_url("ws://localhost:9944").is_ok());
+        assert!(validate_ws_url("wss://rpc.polkadot.io").is_ok());
+    }
+
+    #[test]
+    fn validate_ws_url_rejects_http() {
+        assert!(validate_ws_url("http://localhost:9944").is_err());
+        assert!(validate_ws_url("localhost:9944").is_err());
+    }
+
+    #[tokio::test]
+    async fn client_rejects_invalid_url_without_connecting() {
+        let pool = RpcPool::new();
+        assert!(pool.client("http://localhost:9944").await.is_err());
+        assert!(pool.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn slow_endpoints_do_not_block_other_urls() -> anyhow::Result<()> {
+        // Accepts connections but never answers the WebSocket handshake.
+        let silent = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let silent_url = format!("ws://{}", silent.local_addr()?);
+        let pool = Arc::new(RpcPool::new());
+        let stalled = tokio::spawn({
+            let pool = Arc::clone(&pool);
+            async move { pool.client(&silent_url).await.is_ok() }
+        });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let other = tokio::time::timeout(Duration::from_secs(2), pool.client("ws://127.0.0.1:1"));
+        assert!(matches!(other.await, Ok(Err(PopMcpError::Rpc(_)))));
+        stalled.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn client_fails_for_unreachable_endpoint() {
+        let pool = RpcPool::new();
+        let result = pool.client("ws://127.0.0.1:1").await;
+        assert!(matches!(result, Err(PopMcpError::Rpc(_))));
+        assert!(pool.is_empty().await);
+    }
+}
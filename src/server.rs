@@ -1,254 +1,1536 @@
 //! MCP Server implementation for Pop CLI
 
 use rmcp::{
+    handler::server::tool::ToolCallContext,
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
     model::*,
-    service::{RequestContext, RoleServer},
-    tool, tool_handler, tool_router, ErrorData as McpError, ServerHandler,
+    service::{NotificationContext, RequestContext, RoleServer},
+    tool, tool_router, ErrorData as McpError, Peer, ServerHandler,
 };
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 
-use crate::executor::PopExecutor;
+use crate::children::Children;
+use crate::deployments::{deployments_file, Deployment, Registry};
+use crate::devloop::{self, DevLoops};
+use crate::elicit;
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::{CommandExecutor, PopExecutor, RetryPolicy};
+use crate::ipfs::Ipfs;
+use crate::logging::CommandLog;
+use crate::nodes::{NodeRegistry, RegisteredNode};
+use crate::outputs::{DeployOutput, NodeInfo};
+use crate::parsing;
+use crate::profiles::{self, profiles_dir};
+use crate::progress;
+use crate::prompts;
+use crate::queries::queries_dir;
 use crate::resources;
-use crate::tools::{common, *};
+use crate::roots::ClientRoots;
+use crate::rpc::RpcPool;
+use crate::tokens::TokenDisplays;
+use crate::tools::common::{self, OutputStyle};
+use crate::tools::*;
+use crate::wallet;
+use crate::watch::{self, Watches};
+use crate::webhook::{is_runtime_upgrade, runtime_code_hash, Announcement, Event, Webhook};
 
 /// Pop MCP Server - provides tools for Polkadot ink! smart contract development
+///
+/// Generic over the [`CommandExecutor`] running Pop CLI, so the server can be driven
+/// by a mock in tests.
 #[derive(Clone)]
-pub struct PopMcpServer {
+pub struct PopMcpServer<E: CommandExecutor = PopExecutor> {
     tool_router: ToolRouter<Self>,
-    executor: PopExecutor,
-    node_websocket_url: Arc<Mutex<Option<String>>>,
+    executor: E,
+    rpc: Arc<RpcPool>,
+    tokens: Arc<TokenDisplays>,
+    nodes: Arc<NodeRegistry>,
+    dev_loops: Arc<DevLoops>,
+    watches: Arc<Watches>,
+    children: Arc<Children<E>>,
+    webhook: Option<Webhook>,
+    ipfs: Ipfs,
+    log: CommandLog,
+    roots: ClientRoots,
+    deployments: Registry,
+    output: OutputStyle,
+    /// Whether `use_wallet` is offered, i.e. the client can reach the signing portal.
+    wallet_signing: bool,
 }
 
 impl PopMcpServer {
     /// Create a new PopMcpServer
     pub fn new() -> Self {
+        Self::with_executor(PopExecutor::new())
+    }
+
+    /// Create a server running the Pop CLI binary at `binary` instead of resolving `pop`.
+    pub fn with_pop_binary(binary: impl Into<std::path::PathBuf>) -> Self {
+        Self::with_executor(PopExecutor::new().with_binary(binary))
+    }
+}
+
+impl<E: CommandExecutor> PopMcpServer<E> {
+    /// Create a server running Pop CLI commands through `executor`.
+    pub fn with_executor(executor: E) -> Self {
+        let log = CommandLog::new();
         Self {
             tool_router: Self::tool_router(),
-            executor: PopExecutor::new(),
-            node_websocket_url: Arc::new(Mutex::new(None)),
+            children: Arc::new(Children::new(executor.clone())),
+            executor: executor.with_command_log(log.clone()),
+            rpc: Arc::new(RpcPool::new()),
+            tokens: Arc::new(TokenDisplays::new()),
+            nodes: Arc::new(NodeRegistry::new()),
+            dev_loops: Arc::new(DevLoops::new()),
+            watches: Arc::new(Watches::new()),
+            webhook: None,
+            ipfs: Ipfs::default(),
+            log,
+            roots: ClientRoots::new(),
+            deployments: Registry::new(deployments_file()),
+            output: OutputStyle::default(),
+            wallet_signing: true,
+        }
+    }
+
+    /// Emit plain ASCII results (no emoji or box-drawing characters) from every tool.
+    pub fn with_ascii_output(mut self, enabled: bool) -> Self {
+        self.output.ascii = enabled;
+        self
+    }
+
+    /// Truncate result texts longer than `bytes`, keeping the full output as a
+    /// `poplog://` resource (0 disables truncation).
+    pub fn with_output_limit(mut self, bytes: usize) -> Self {
+        self.output.limit = bytes;
+        self
+    }
+
+    /// Kill Pop CLI commands that run longer than `timeout`, unless a tool call sets its own.
+    pub fn with_command_timeout(mut self, timeout: Duration) -> Self {
+        self.executor = self.executor.with_timeout(timeout);
+        self
+    }
+
+    /// Retry network-bound commands (deploys and chain/contract calls) per `policy`
+    /// when they fail to connect.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.executor = self.executor.with_retry(policy);
+        self
+    }
+
+    /// Post an announcement to `url` after each successful deployment or runtime upgrade.
+    pub fn with_webhook(mut self, url: impl Into<String>) -> Self {
+        self.webhook = Some(Webhook::new(url));
+        self
+    }
+
+    /// Offer `use_wallet` signing (the default). Pop's signing portal listens on this
+    /// machine's localhost, so servers for HTTP and SSE clients turn it off.
+    pub fn with_wallet_signing(mut self, enabled: bool) -> Self {
+        self.wallet_signing = enabled;
+        self
+    }
+
+    /// Pin contract artifacts through `ipfs` instead of a local IPFS daemon.
+    pub fn with_ipfs(mut self, ipfs: Ipfs) -> Self {
+        self.ipfs = ipfs;
+        self
+    }
+
+    /// A server for another MCP session.
+    ///
+    /// It shares launched nodes, caches and child processes with this one, but has its
+    /// own selected node, dev loops, watches, command log and client roots, so concurrent
+    /// clients don't switch each other's default URL or workspace or see each other's
+    /// commands. The deployment registry stays shared. Its dev loops and watches stop
+    /// when the session ends.
+    pub fn session(&self) -> Self {
+        let log = CommandLog::new();
+        Self {
+            executor: self.executor.clone().with_command_log(log.clone()),
+            nodes: Arc::new(self.nodes.session()),
+            dev_loops: Arc::new(DevLoops::new()),
+            watches: Arc::new(Watches::new()),
+            log,
+            roots: ClientRoots::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Stop the nodes and networks launched without `detach` that are still running.
+    ///
+    /// Also happens when the last clone of the server is dropped.
+    pub async fn shutdown(&self) {
+        self.children.shutdown().await;
+    }
+
+    /// Track the network launched by a successful `up_network` unless it is detached.
+    fn track_network(&self, result: &CallToolResult, detach: bool) {
+        if detach || result.is_error == Some(true) {
+            return;
+        }
+        let output = common::extract_texts(result).join("\n");
+        if let Some(zombie_json) = parsing::zombie_json_path(&output) {
+            self.children.track_network(&zombie_json);
+        }
+    }
+
+    /// Stop tracking nodes a stop tool removed from the registry, and `pids` it stopped.
+    fn forget_stopped(&self, before: &[RegisteredNode], result: &CallToolResult, pids: &[u32]) {
+        let remaining = self.nodes.list();
+        for node in before {
+            if !remaining.iter().any(|n| n.name == node.name) {
+                self.children.forget_pids(&node.pids);
+            }
+        }
+        if result.is_error != Some(true) {
+            self.children.forget_pids(pids);
+        }
+    }
+
+    /// Announce `event` if a webhook is configured and `result` succeeded, naming the
+    /// signer of `suri` when it is a dev account.
+    fn announce(
+        &self,
+        result: &CallToolResult,
+        event: Event,
+        url: &str,
+        code_hash: Option<String>,
+        suri: Option<String>,
+    ) {
+        let Some(webhook) = &self.webhook else {
+            return;
+        };
+        if result.is_error == Some(true) {
+            return;
         }
+        let deployed = result
+            .structured_content
+            .clone()
+            .and_then(|value| serde_json::from_value::<DeployOutput>(value).ok());
+        let (address, code_hash) = match deployed {
+            Some(deployed) => (Some(deployed.address), deployed.code_hash.or(code_hash)),
+            None => (None, code_hash),
+        };
+        webhook.announce(Announcement::new(
+            event,
+            url,
+            address,
+            code_hash,
+            suri.as_deref(),
+        ));
     }
 
-    /// Get the stored node websocket URL
+    /// Get the websocket URL of the selected node
     fn get_stored_url(&self) -> Option<String> {
-        self.node_websocket_url
-            .lock()
-            .ok()
-            .and_then(|guard| guard.clone())
+        self.nodes.selected_url()
+    }
+
+    /// `cwd` if given, else the client's first root, as the directory to create a
+    /// project in. `None` leaves it to the server's working directory.
+    async fn cwd_or_root(&self, cwd: Option<String>, peer: &Peer<RoleServer>) -> Option<String> {
+        match cwd {
+            Some(cwd) => Some(cwd),
+            None => self
+                .roots
+                .project_root(peer)
+                .await
+                .map(|root| root.display().to_string()),
+        }
+    }
+
+    /// Render balances in `result` using the token of the network at `url`.
+    async fn humanize(&self, url: Option<&str>, result: CallToolResult) -> CallToolResult {
+        self.tokens.apply(&self.rpc, url, result).await
+    }
+
+    /// Executor whose commands are killed when the tool call is cancelled.
+    fn cancellable(&self, ct: CancellationToken) -> E {
+        self.executor.clone().with_cancellation(ct)
+    }
+
+    /// Run `call` with an executor that streams output lines as progress notifications
+    /// when the client asked for progress, waiting for them to flush before returning.
+    async fn with_progress<F, Fut>(
+        &self,
+        meta: &Meta,
+        peer: Peer<RoleServer>,
+        ct: CancellationToken,
+        call: F,
+    ) -> PopMcpResult<CallToolResult>
+    where
+        F: FnOnce(E) -> Fut,
+        Fut: std::future::Future<Output = PopMcpResult<CallToolResult>>,
+    {
+        let executor = self.cancellable(ct);
+        let Some(token) = meta.get_progress_token() else {
+            return call(executor).await;
+        };
+        let (sink, forwarder) = progress::forward(peer, token, self.output);
+        // `call` owns the only sink, so the forwarder drains once it returns.
+        let result = call(executor.with_line_sink(sink)).await;
+        let _ = forwarder.await;
+        result
+    }
+
+    /// Run `call` with an executor whose output is watched for pop's wallet signing
+    /// portal when `use_wallet` is set, so its URL reaches the client while pop waits
+    /// (see [`wallet::relay`]). The result ends with the URL too.
+    async fn with_wallet<F, Fut>(
+        &self,
+        use_wallet: bool,
+        meta: &Meta,
+        peer: Peer<RoleServer>,
+        ct: CancellationToken,
+        call: F,
+    ) -> PopMcpResult<CallToolResult>
+    where
+        F: FnOnce(E) -> Fut,
+        Fut: std::future::Future<Output = PopMcpResult<CallToolResult>>,
+    {
+        let executor = self.cancellable(ct);
+        if !use_wallet {
+            return call(executor).await;
+        }
+        if !self.wallet_signing {
+            return Err(PopMcpError::InvalidInput(wallet::REMOTE_ERROR.to_owned()));
+        }
+        let (progress, forwarder) = match meta.get_progress_token() {
+            Some(token) => {
+                let (sink, forwarder) = progress::forward(peer, token, self.output);
+                (Some(sink), Some(forwarder))
+            }
+            None => (None, None),
+        };
+        let (sink, relay) = wallet::relay(self.log.clone(), progress);
+        // `call` owns the only sink, so the relay (and then the forwarder) ends once
+        // it returns.
+        let result = call(executor.with_line_sink(sink)).await;
+        let portal = relay.await.ok().flatten();
+        if let Some(forwarder) = forwarder {
+            let _ = forwarder.await;
+        }
+        let note = portal.map(|url| format!("Wallet signing portal: {}", url));
+        result.map(|result| common::with_note(result, note))
+    }
+}
+
+/// Record a successful deployment in the persistent registry, returning a note on
+/// where it went (or why it was not recorded).
+fn record_deployment(
+    registry: &Registry,
+    result: &CallToolResult,
+    path: &str,
+    url: &str,
+    constructor: Option<String>,
+) -> Option<String> {
+    if result.is_error == Some(true) {
+        return None;
+    }
+    let deployed = result
+        .structured_content
+        .clone()
+        .and_then(|value| serde_json::from_value::<DeployOutput>(value).ok())?;
+    // The registry outlives the working directory the path is relative to.
+    let path = std::fs::canonicalize(path).map_or_else(
+        |_| path.to_owned(),
+        |absolute| absolute.display().to_string(),
+    );
+    let deployment = Deployment {
+        path,
+        url: url.to_owned(),
+        address: deployed.address,
+        code_hash: deployed.code_hash,
+        constructor: constructor.unwrap_or_else(|| "new".to_owned()),
+        deployed_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs()),
+    };
+    Some(match registry.record(deployment) {
+        Ok(()) => "Recorded in the deployment registry (list_deployments)".to_owned(),
+        Err(e) => format!("Not recorded in the deployment registry: {}", e),
+    })
+}
+
+impl Default for PopMcpServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[tool_router]
+impl<E: CommandExecutor> PopMcpServer<E> {
+    #[tool(
+        description = "Check if Pop CLI is installed and get version information",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn check_pop_installation(
+        &self,
+        Parameters(_): Parameters<CheckPopInstallationParams>,
+    ) -> Result<CallToolResult, McpError> {
+        check_pop_installation(&self.executor, CheckPopInstallationParams {})
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Get detailed instructions for installing Pop CLI on different platforms",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn install_pop_instructions(
+        &self,
+        Parameters(params): Parameters<InstallPopInstructionsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        install_pop_instructions(params).map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "List all available ink! contract templates",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn list_contract_templates(
+        &self,
+        Parameters(params): Parameters<ListContractTemplatesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        list_contract_templates(params).map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "DEPRECATED: use list_contract_templates. List all available ink! contract templates",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn list_templates(
+        &self,
+        Parameters(params): Parameters<ListTemplatesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        list_contract_templates(params.into())
+            .map(|result| deprecation::deprecated(result, "list_templates"))
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Create a new ink! smart contract from a template using Pop CLI",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn create_contract(
+        &self,
+        ct: CancellationToken,
+        peer: Peer<RoleServer>,
+        Parameters(mut params): Parameters<CreateContractParams>,
+    ) -> Result<CallToolResult, McpError> {
+        params.cwd = self.cwd_or_root(params.cwd, &peer).await;
+        create_contract(&self.cancellable(ct), params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Create a new Polkadot Chain project from a template scaffold. Providers: pop, openzeppelin, parity. Templates: r0gue-io/base-parachain, r0gue-io/assets-parachain, r0gue-io/contracts-parachain (pop), openzeppelin/generic-template, openzeppelin/evm-template (openzeppelin), paritytech/polkadot-sdk-parachain-template (parity)",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn create_chain(
+        &self,
+        ct: CancellationToken,
+        peer: Peer<RoleServer>,
+        Parameters(mut params): Parameters<CreateChainParams>,
+    ) -> Result<CallToolResult, McpError> {
+        params.cwd = self.cwd_or_root(params.cwd, &peer).await;
+        create_chain(&self.cancellable(ct), params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Create a new ink! smart contract with a Dedot/Typink frontend, then build it and have the client's model (MCP sampling) adapt frontend/src to the contract's messages using the Dedot docs resource. Requires a client that supports sampling",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn create_contract_with_frontend(
+        &self,
+        ct: CancellationToken,
+        peer: Peer<RoleServer>,
+        Parameters(mut params): Parameters<CreateContractWithFrontendParams>,
+    ) -> Result<CallToolResult, McpError> {
+        params.cwd = self.cwd_or_root(params.cwd, &peer).await;
+        create_contract_with_frontend(&self.cancellable(ct), &peer, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Build an ink! smart contract using Pop CLI",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn build_contract(
+        &self,
+        meta: Meta,
+        peer: Peer<RoleServer>,
+        ct: CancellationToken,
+        Parameters(params): Parameters<BuildContractParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.with_progress(&meta, peer, ct, |executor| async move {
+            build_contract(&executor, params).await
+        })
+        .await
+        .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Build a chain project using Pop CLI",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn build_chain(
+        &self,
+        meta: Meta,
+        peer: Peer<RoleServer>,
+        ct: CancellationToken,
+        Parameters(params): Parameters<BuildChainParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.with_progress(&meta, peer, ct, |executor| async move {
+            build_chain(&executor, params).await
+        })
+        .await
+        .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Report the code size of a built contract by section, estimate its upload storage deposit on a chain and flag code over the chain's maximum code size",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn contract_size_report(
+        &self,
+        Parameters(params): Parameters<ContractSizeReportParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let url = params.url.clone().or_else(|| self.get_stored_url());
+        let result = contract_size_report(&self.rpc, params, url.clone())
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        Ok(self.humanize(url.as_deref(), result).await)
+    }
+
+    #[tool(
+        description = "Run tests for an ink! smart contract",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn test_contract(
+        &self,
+        ct: CancellationToken,
+        Parameters(params): Parameters<TestContractParams>,
+    ) -> Result<CallToolResult, McpError> {
+        test_contract(&self.cancellable(ct), params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Run tests for a chain project",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn test_chain(
+        &self,
+        ct: CancellationToken,
+        Parameters(params): Parameters<TestChainParams>,
+    ) -> Result<CallToolResult, McpError> {
+        test_chain(&self.cancellable(ct), params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Deploy and instantiate an ink! smart contract to a network, or with upload_only only upload its code and return the code hash",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn deploy_contract(
+        &self,
+        meta: Meta,
+        ct: CancellationToken,
+        peer: Peer<RoleServer>,
+        Parameters(mut params): Parameters<DeployContractParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let ci_mode = params.ci_mode.unwrap_or(false);
+        // Uploads take no constructor to ask for.
+        if !ci_mode && !params.upload_only.unwrap_or(false) {
+            if let Err(result) = elicit::complete_deploy(&peer, &mut params).await {
+                return Ok(result);
+            }
+        }
+        let stored_url = self.get_stored_url();
+        let url = params.url.clone().or_else(|| stored_url.clone());
+        let executed = params.execute.unwrap_or(false) && !params.dry_run.unwrap_or(false);
+        let path = params.path.clone();
+        let constructor = params.constructor.clone();
+        let use_wallet = params.use_wallet.unwrap_or(false);
+        let result = self
+            .with_wallet(use_wallet, &meta, peer, ct, |executor| async move {
+                deploy_contract(&executor, params, stored_url.as_deref()).await
+            })
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        let mut note = None;
+        if let (true, Some(url)) = (executed, url.as_deref()) {
+            // Wallet signers are not known here.
+            let suri = (!use_wallet).then(crate::read_private_key_suri).flatten();
+            self.announce(&result, Event::ContractDeployed, url, None, suri);
+            note = record_deployment(&self.deployments, &result, &path, url, constructor);
+        }
+        // CI reports are JSON only.
+        if ci_mode {
+            return Ok(result);
+        }
+        let result = common::with_note(result, note);
+        Ok(self.humanize(url.as_deref(), result).await)
+    }
+
+    #[tool(
+        description = "Instantiate a contract from code already on chain (e.g. uploaded with deploy_contract upload_only) by code hash, with a constructor and args from the built contract's metadata, without uploading the code again. Requires PRIVATE_KEY to execute.",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn instantiate_from_code_hash(
+        &self,
+        ct: CancellationToken,
+        Parameters(params): Parameters<InstantiateFromCodeHashParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let stored_url = self.get_stored_url();
+        let url = params.url.clone().or_else(|| stored_url.clone());
+        let executed = params.execute.unwrap_or(false);
+        let path = params.path.clone();
+        let constructor = params.constructor.clone();
+        let result = instantiate_from_code_hash(
+            &self.cancellable(ct),
+            &self.rpc,
+            params,
+            stored_url.as_deref(),
+        )
+        .await
+        .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        let mut note = None;
+        if let (true, Some(url)) = (executed, url.as_deref()) {
+            let suri = crate::read_private_key_suri();
+            self.announce(&result, Event::ContractDeployed, url, None, suri);
+            note = record_deployment(&self.deployments, &result, &path, url, constructor);
+        }
+        let result = common::with_note(result, note);
+        Ok(self.humanize(url.as_deref(), result).await)
+    }
+
+    #[tool(
+        description = "Deploy a contract to the PassetHub testnet in one step: check that the PRIVATE_KEY signer has PAS (with a faucet link if not), build the contract when it has no artifacts, deploy it and return the address with its block explorer link",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn deploy_to_testnet(
+        &self,
+        ct: CancellationToken,
+        Parameters(params): Parameters<DeployToTestnetParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let url = params
+            .url
+            .clone()
+            .unwrap_or_else(|| up::testnet::PASSET_HUB_URL.to_owned());
+        let path = params.path.clone();
+        let constructor = params.constructor.clone();
+        let result = deploy_to_testnet(&self.cancellable(ct), &self.rpc, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        let suri = crate::read_private_key_suri();
+        self.announce(&result, Event::ContractDeployed, &url, None, suri);
+        let note = record_deployment(&self.deployments, &result, &path, &url, constructor);
+        let result = common::with_note(result, note);
+        Ok(self.humanize(Some(&url), result).await)
+    }
+
+    #[tool(
+        description = "List contracts deployed with deploy_contract by any client session or earlier server run: address, network, contract path, constructor, code hash and time. Filter by url or path.",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn list_deployments(
+        &self,
+        Parameters(params): Parameters<ListDeploymentsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        list_deployments(&self.deployments, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Remove a contract address from the deployment registry",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn forget_deployment(
+        &self,
+        Parameters(params): Parameters<ForgetDeploymentParams>,
+    ) -> Result<CallToolResult, McpError> {
+        forget_deployment(&self.deployments, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Call a contract method on a deployed contract",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn call_contract(
+        &self,
+        meta: Meta,
+        ct: CancellationToken,
+        peer: Peer<RoleServer>,
+        Parameters(mut params): Parameters<CallContractParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let ci_mode = params.ci_mode.unwrap_or(false);
+        if !ci_mode {
+            if let Err(result) = elicit::complete_call(&peer, &mut params).await {
+                return Ok(result);
+            }
+        }
+        let url = params.url.clone();
+        let use_wallet = params.use_wallet.unwrap_or(false);
+        let result = self
+            .with_wallet(use_wallet, &meta, peer, ct, |executor| async move {
+                call_contract(&executor, params).await
+            })
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        if ci_mode {
+            return Ok(result);
+        }
+        Ok(self.humanize(url.as_deref(), result).await)
+    }
+
+    #[tool(
+        description = "Run several messages against one contract in a single invocation, in order: each entry has message, args, value and execute. Stops at the first failure unless stop_on_failure is false, and returns every call's outcome (e.g. for setup scripts that mint and approve tokens).",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn call_contract_batch(
+        &self,
+        ct: CancellationToken,
+        Parameters(params): Parameters<CallContractBatchParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let url = params.url.clone();
+        let result = call_contract_batch(&self.cancellable(ct), params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        Ok(self.humanize(url.as_deref(), result).await)
+    }
+
+    #[tool(
+        description = "Estimate the gas (ref_time, proof_size) and storage deposit of a contract message by dry-running it with pop call contract, without submitting. Returns the figures as structured numbers.",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn estimate_gas(
+        &self,
+        ct: CancellationToken,
+        Parameters(params): Parameters<EstimateGasParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let url = params.url.clone();
+        let result = estimate_gas(&self.cancellable(ct), params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        Ok(self.humanize(url.as_deref(), result).await)
+    }
+
+    #[tool(
+        description = "Read a deployed contract's storage without calling a getter: the whole root storage, or one field (Lazy fields, and Mapping entries given their key). Uses the storage layout in the built metadata and the chain's ReviveApi/ContractsApi, and returns decoded values.",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn query_contract_storage(
+        &self,
+        Parameters(params): Parameters<QueryContractStorageParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let stored_url = self.get_stored_url();
+        query_contract_storage(&self.rpc, params, stored_url.as_deref())
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Load-test a deployed contract on a local node: submit N transactions of one message with concurrent dev signers and report throughput, latency and failure reasons. Useful for spotting weight issues before testnet.",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn stress_call(
+        &self,
+        ct: CancellationToken,
+        Parameters(params): Parameters<StressCallParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let stored_url = self.get_stored_url();
+        stress_call(&self.cancellable(ct), params, stored_url.as_deref())
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Save a call_chain query (pallet, function, args, optional url) under a name so a standard check such as a validator set or treasury balance runs in one call with run_saved_query. Args may contain {name} placeholders filled in at run time.",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn save_query(
+        &self,
+        Parameters(params): Parameters<SaveQueryParams>,
+    ) -> Result<CallToolResult, McpError> {
+        save_query(&queries_dir(), params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Run a query saved with save_query against the given url, the saved url or the selected node, filling its placeholders from values. Never submits transactions.",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn run_saved_query(
+        &self,
+        ct: CancellationToken,
+        Parameters(params): Parameters<RunSavedQueryParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let stored_url = self.get_stored_url();
+        let call = match saved_query_call(&queries_dir(), params, stored_url.as_deref())
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?
+        {
+            Ok(call) => call,
+            Err(result) => return Ok(result),
+        };
+        let url = call.url.clone();
+        let result = call_chain(&self.cancellable(ct), &self.rpc, call)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        Ok(self.humanize(Some(&url), result).await)
+    }
+
+    #[tool(
+        description = "Execute a chain call or contract message on a local node and report what it changed: the given storage entries (and, for contract calls, the contract's storage) are read before and after. Requires PRIVATE_KEY.",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn state_diff_call(
+        &self,
+        ct: CancellationToken,
+        Parameters(params): Parameters<StateDiffCallParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let stored_url = self.get_stored_url();
+        state_diff_call(
+            &self.cancellable(ct),
+            &self.rpc,
+            params,
+            stored_url.as_deref(),
+        )
+        .await
+        .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Interact with a chain runtime: execute transactions, query storage, or read constants. Use metadata=true to discover pallets/extrinsics/storage/constants.",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn call_chain(
+        &self,
+        meta: Meta,
+        ct: CancellationToken,
+        peer: Peer<RoleServer>,
+        Parameters(params): Parameters<CallChainParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let url = params.url.clone();
+        let upgrade = match (&params.pallet, &params.function) {
+            (Some(pallet), Some(function))
+                if params.execute.unwrap_or(false)
+                    && !params.dry_run.unwrap_or(false)
+                    && is_runtime_upgrade(pallet, function) =>
+            {
+                Some(
+                    params
+                        .args
+                        .as_ref()
+                        .and_then(|args| args.first())
+                        .and_then(|code| runtime_code_hash(code)),
+                )
+            }
+            _ => None,
+        };
+        let use_wallet = params.use_wallet.unwrap_or(false);
+        let rpc = &self.rpc;
+        let result = self
+            .with_wallet(use_wallet, &meta, peer, ct, |executor| async move {
+                call_chain(&executor, rpc, params).await
+            })
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        if let Some(code_hash) = upgrade {
+            let suri = (!use_wallet).then(crate::read_private_key_suri).flatten();
+            self.announce(&result, Event::RuntimeUpgraded, &url, code_hash, suri);
+        }
+        Ok(self.humanize(Some(&url), result).await)
+    }
+
+    #[tool(
+        description = "Submit several pallet calls at once via utility.batch_all (or batch). Each call is encoded against the chain's metadata; without execute=true only the encoded call data is returned. Requires PRIVATE_KEY when executing.",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn batch_chain_calls(
+        &self,
+        Parameters(params): Parameters<BatchChainCallsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        batch_chain_calls(&self.executor, &self.rpc, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Schedule a call for a future block via the scheduler pallet (local/dev networks). Give the call as pallet/function/args or call_data, and at_block or after_blocks. Without execute=true only the encoded call data is returned. Requires PRIVATE_KEY when executing.",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn schedule_call(
+        &self,
+        Parameters(params): Parameters<ScheduleCallParams>,
+    ) -> Result<CallToolResult, McpError> {
+        schedule_call(&self.executor, &self.rpc, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Note a call's preimage via the preimage pallet (local/test networks). Returns the preimage hash and length for submit_referendum. Without execute=true nothing is submitted. Requires PRIVATE_KEY when executing.",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn submit_preimage(
+        &self,
+        Parameters(params): Parameters<SubmitPreimageParams>,
+    ) -> Result<CallToolResult, McpError> {
+        submit_preimage(&self.executor, &self.rpc, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Submit an OpenGov referendum for a noted preimage (or small inline call) and place its decision deposit (local/test networks). Without execute=true only the encoded call data is returned. Requires PRIVATE_KEY when executing.",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn submit_referendum(
+        &self,
+        Parameters(params): Parameters<SubmitReferendumParams>,
+    ) -> Result<CallToolResult, McpError> {
+        submit_referendum(&self.executor, &self.rpc, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Set an on-chain identity (display, legal, web, email, twitter) via the identity pallet. Fails clearly if the chain lacks the pallet. Without execute=true only the encoded call data is returned. Requires PRIVATE_KEY when executing.",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn set_identity(
+        &self,
+        Parameters(params): Parameters<SetIdentityParams>,
+    ) -> Result<CallToolResult, McpError> {
+        set_identity(&self.executor, &self.rpc, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Register a proxy (delegate) for the signing account via the proxy pallet. Fails clearly if the chain lacks the pallet. Without execute=true only the encoded call data is returned. Requires PRIVATE_KEY when executing.",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn add_proxy(
+        &self,
+        Parameters(params): Parameters<AddProxyParams>,
+    ) -> Result<CallToolResult, McpError> {
+        add_proxy(&self.executor, &self.rpc, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Map the signing account to its Ethereum address on a revive (Ethereum-compatible) chain via Revive.map_account, which such chains require before the account deploys or calls contracts. Already mapped accounts succeed without change. Uses PRIVATE_KEY or a signer alias.",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn map_account(
+        &self,
+        ct: CancellationToken,
+        Parameters(params): Parameters<MapAccountParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let stored_url = self.get_stored_url();
+        map_account(&self.cancellable(ct), params, stored_url.as_deref())
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Register a collator on a parachain template: rotate session keys on the collator's local node (or use given keys), set them via session.set_keys and register as a collator-selection candidate. Without execute=true only the keys and encoded calls are returned. Requires PRIVATE_KEY when executing.",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn register_collator(
+        &self,
+        Parameters(params): Parameters<RegisterCollatorParams>,
+    ) -> Result<CallToolResult, McpError> {
+        register_collator(&self.executor, &self.rpc, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Show collator-selection state: invulnerables, candidates, desired candidates, candidacy bond and current session validators.",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn collator_selection_state(
+        &self,
+        Parameters(params): Parameters<CollatorSelectionStateParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let url = params.url.clone();
+        let result = collator_selection_state(&self.rpc, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        Ok(self.humanize(Some(&url), result).await)
+    }
+
+    #[tool(
+        description = "Query a chain over a pooled JSON-RPC connection using read-only methods (system_health, chain_getHeader, state_getStorage, state_call, ...). Faster than call_chain for repeated queries.",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn chain_rpc(
+        &self,
+        Parameters(params): Parameters<ChainRpcParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let stored_url = self.get_stored_url();
+        chain_rpc(&self.rpc, params, stored_url.as_deref())
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Write a README.md for a contract or chain project describing how to build, test, run a node and deploy it with the exact pop commands, listing its recorded deployments (addresses and code hashes). Refuses to replace an existing README unless overwrite is true.",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn generate_readme(
+        &self,
+        Parameters(params): Parameters<GenerateReadmeParams>,
+    ) -> Result<CallToolResult, McpError> {
+        generate_readme(&self.deployments, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Set license (SPDX), repository URL and/or authors in the Cargo.toml files of an existing contract or chain project. Workspace roots get them in [workspace.package]; members inheriting a field with `field.workspace = true` are left alone.",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn stamp_metadata(
+        &self,
+        Parameters(params): Parameters<StampMetadataParams>,
+    ) -> Result<CallToolResult, McpError> {
+        stamp_metadata(params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Upload and pin a built contract's metadata (.json) and bundle (.contract) to IPFS, returning their CIDs and gateway links to embed in frontends or registries. Run build_contract first.",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn pin_to_ipfs(
+        &self,
+        Parameters(params): Parameters<PinToIpfsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        pin_to_ipfs(&self.ipfs, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Derive N reproducible dev accounts (<base_path>//0, //1, ...) from the well-known dev phrase, returning their SURIs and addresses and registering them in the pop://accounts address book. Use them as signers for multi-actor or load tests.",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn derive_test_accounts(
+        &self,
+        Parameters(params): Parameters<DeriveTestAccountsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        derive_test_accounts(params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
-}
 
-impl Default for PopMcpServer {
-    fn default() -> Self {
-        Self::new()
+    #[tool(
+        description = "List recent Pop CLI commands run by this server (secrets masked) with duration, exit status and, for failures, the end of their output. The full trail is the audit://commands resource.",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn list_command_history(
+        &self,
+        Parameters(params): Parameters<ListCommandHistoryParams>,
+    ) -> Result<CallToolResult, McpError> {
+        list_command_history(params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
-}
 
-#[tool_router]
-impl PopMcpServer {
-    #[tool(description = "Check if Pop CLI is installed and get version information")]
-    async fn check_pop_installation(
+    #[tool(
+        description = "Report which features work in this environment before planning a workflow: Pop CLI subcommands (from pop --help), cached node binaries, frontend tooling on PATH and reachability of GitHub and public testnets. Returns a support matrix naming the tools each feature backs.",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn capabilities(
         &self,
-        Parameters(_): Parameters<CheckPopInstallationParams>,
+        Parameters(params): Parameters<CapabilitiesParams>,
     ) -> Result<CallToolResult, McpError> {
-        check_pop_installation(&self.executor, CheckPopInstallationParams {})
+        capabilities(&self.executor, params)
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
-    #[tool(description = "Get detailed instructions for installing Pop CLI on different platforms")]
-    async fn install_pop_instructions(
+    #[tool(
+        description = "Export a debug bundle for pop-cli or ink! bug reports: a zip with a manifest, versions (pop-mcp, Pop CLI, node), failed command history, logs of launched nodes and, for a node, its chain spec and recent blocks with events.",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn export_debug_bundle(
         &self,
-        Parameters(params): Parameters<InstallPopInstructionsParams>,
+        Parameters(params): Parameters<ExportDebugBundleParams>,
     ) -> Result<CallToolResult, McpError> {
-        install_pop_instructions(params).map_err(|e| McpError::internal_error(e.to_string(), None))
+        let stored_url = self.get_stored_url();
+        export_debug_bundle(
+            &self.executor,
+            &self.rpc,
+            &self.nodes.list(),
+            params,
+            stored_url.as_deref(),
+        )
+        .await
+        .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
-    #[tool(description = "List all available ink! contract templates")]
-    async fn list_templates(
+    #[tool(
+        description = "Check a node's health: peer count, sync status and connected peers (system_health, system_syncState, system_peers). Use it to tell a node that is up but not synced from genuine call or deploy failures.",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn node_health(
         &self,
-        Parameters(_): Parameters<ListTemplatesParams>,
+        Parameters(params): Parameters<NodeHealthParams>,
     ) -> Result<CallToolResult, McpError> {
-        list_templates(ListTemplatesParams {})
+        let stored_url = self.get_stored_url();
+        node_health(&self.rpc, params, stored_url.as_deref())
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
-    #[tool(description = "Create a new ink! smart contract from a template using Pop CLI")]
-    async fn create_contract(
+    #[tool(
+        description = "Check that a node is ready before deploying: TCP reachability, system_health and chain_getHeader (best block, peers, sync) and whether the eth-rpc sidecar answers. Defaults to the node launched with up_ink_node.",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn node_status(
         &self,
-        Parameters(params): Parameters<CreateContractParams>,
+        Parameters(params): Parameters<NodeStatusParams>,
     ) -> Result<CallToolResult, McpError> {
-        create_contract(&self.executor, params)
+        let stored_url = self.get_stored_url();
+        node_status(&self.rpc, params, stored_url.as_deref())
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
     #[tool(
-        description = "Create a new Polkadot Chain project from a template scaffold. Providers: pop, openzeppelin, parity. Templates: r0gue-io/base-parachain, r0gue-io/assets-parachain, r0gue-io/contracts-parachain (pop), openzeppelin/generic-template, openzeppelin/evm-template (openzeppelin), paritytech/polkadot-sdk-parachain-template (parity)"
+        description = "List the transactions waiting in a node's pool (author_pendingExtrinsics), decoded via runtime metadata: hash, signer, nonce and other signed extensions, and the call. Use it when a submitted transaction never gets included.",
+        annotations(read_only_hint = true, destructive_hint = false)
     )]
-    async fn create_chain(
+    async fn pending_extrinsics(
         &self,
-        Parameters(params): Parameters<CreateChainParams>,
+        Parameters(params): Parameters<PendingExtrinsicsParams>,
     ) -> Result<CallToolResult, McpError> {
-        create_chain(&self.executor, params)
+        let stored_url = self.get_stored_url();
+        pending_extrinsics(&self.rpc, params, stored_url.as_deref())
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
-    // Frontend-assisted contract creation temporarily disabled.
-    /*
     #[tool(
-        description = "Create a new ink! smart contract with Dedot/Typink frontend template, automatically adapted to the contract type using Dedot documentation"
+        description = "Replace a transaction stuck in a node's pool: re-sign it with PRIVATE_KEY with a higher tip (and optionally another nonce) and submit it. Find the hash with pending_extrinsics. Without execute=true the re-signed transaction is only described.",
+        annotations(read_only_hint = false, destructive_hint = true)
     )]
-    async fn create_contract_with_frontend(
+    async fn resubmit_transaction(
         &self,
-        Parameters(params): Parameters<CreateContractWithFrontendParams>,
+        Parameters(params): Parameters<ResubmitTransactionParams>,
     ) -> Result<CallToolResult, McpError> {
-        tools::create_contract_with_frontend(&self.executor, params)
+        let stored_url = self.get_stored_url();
+        resubmit_transaction(&self.rpc, params, stored_url.as_deref())
             .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
-    */
 
-    #[tool(description = "Build an ink! smart contract using Pop CLI")]
-    async fn build_contract(
+    #[tool(
+        description = "Call a runtime API (e.g., TransactionPaymentApi.query_info, AccountNonceApi.account_nonce, contract dry-runs) via state_call. Arguments are SCALE-encoded from runtime metadata and the result is decoded.",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn runtime_api_call(
         &self,
-        Parameters(params): Parameters<BuildContractParams>,
+        Parameters(params): Parameters<RuntimeApiCallParams>,
     ) -> Result<CallToolResult, McpError> {
-        build_contract(&self.executor, params)
+        let stored_url = self.get_stored_url();
+        runtime_api_call(&self.rpc, params, stored_url.as_deref())
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
-    #[tool(description = "Build a chain project using Pop CLI")]
-    async fn build_chain(
+    #[tool(
+        description = "Set how balances are displayed for a network: the chain's token symbol/decimals are used by default; override them or show raw planck values.",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn set_token_display(
         &self,
-        Parameters(params): Parameters<BuildChainParams>,
+        Parameters(params): Parameters<SetTokenDisplayParams>,
     ) -> Result<CallToolResult, McpError> {
-        build_chain(&self.executor, params)
+        set_token_display(&self.rpc, &self.tokens, params)
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
-    #[tool(description = "Run tests for an ink! smart contract")]
-    async fn test_contract(
+    #[tool(
+        description = "Launch a local ink! node for contract development and testing (runs in background). Set ink_node_port and eth_rpc_port to run several nodes side by side; the ports used are returned.",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn up_ink_node(
         &self,
-        Parameters(params): Parameters<TestContractParams>,
+        ct: CancellationToken,
+        Parameters(params): Parameters<UpInkNodeParams>,
     ) -> Result<CallToolResult, McpError> {
-        test_contract(&self.executor, params)
+        let name = params.name.clone();
+        let detach = params.detach.unwrap_or(false);
+        let result = up_ink_node(&self.cancellable(ct), params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        // Register the node and select it for tools called without a url
+        let node = result
+            .structured_content
+            .clone()
+            .and_then(|value| serde_json::from_value::<NodeInfo>(value).ok());
+        let note = match node {
+            Some(node) if result.is_error != Some(true) => {
+                let name = self.nodes.register(&node, name.as_deref());
+                if !detach {
+                    self.children.track_pids(&node.pids);
+                }
+                Some(format!("Registered as '{}' and selected", name))
+            }
+            _ => None,
+        };
+        Ok(common::with_note(result, note))
+    }
+
+    #[tool(
+        description = "Launch a local network using a zombienet spec",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn up_network(
+        &self,
+        ct: CancellationToken,
+        Parameters(params): Parameters<UpNetworkParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let detach = params.detach.unwrap_or(false);
+        let result = up_network(&self.cancellable(ct), params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        self.track_network(&result, detach);
+        Ok(result)
+    }
+
+    #[tool(
+        description = "Start a dev loop that watches an ink! contract directory and rebuilds and redeploys it after every source change. The latest address is recorded under the alias in pop://contracts and each outcome is sent as a log notification. Requires PRIVATE_KEY.",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn start_dev_loop(
+        &self,
+        peer: Peer<RoleServer>,
+        Parameters(params): Parameters<StartDevLoopParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let stored_url = self.get_stored_url();
+        start_dev_loop(
+            &self.executor,
+            &self.dev_loops,
+            params,
+            stored_url.as_deref(),
+            devloop::notifier(peer),
+        )
+        .await
+        .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Stop a dev loop started with start_dev_loop",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn stop_dev_loop(
+        &self,
+        Parameters(params): Parameters<StopDevLoopParams>,
+    ) -> Result<CallToolResult, McpError> {
+        stop_dev_loop(&self.dev_loops, params)
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
-    #[tool(description = "Run tests for a chain project")]
-    async fn test_chain(
+    #[tool(
+        description = "List running dev loops with their contract, node, rebuild count and latest outcome",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn list_dev_loops(
         &self,
-        Parameters(params): Parameters<TestChainParams>,
+        Parameters(params): Parameters<ListDevLoopsParams>,
     ) -> Result<CallToolResult, McpError> {
-        test_chain(&self.executor, params)
+        list_dev_loops(&self.dev_loops, params)
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
-    #[tool(description = "Deploy and instantiate an ink! smart contract to a network")]
-    async fn deploy_contract(
+    #[tool(
+        description = "Watch a chain value and send a log notification when a condition on it starts or stops holding, e.g. an account's free balance dropping below a threshold. Reads an account balance (address), a storage entry (pallet, item, keys, field) or a read-only contract message (path, contract, message) every interval_secs.",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn watch_condition(
         &self,
-        Parameters(params): Parameters<DeployContractParams>,
+        peer: Peer<RoleServer>,
+        Parameters(params): Parameters<WatchConditionParams>,
     ) -> Result<CallToolResult, McpError> {
         let stored_url = self.get_stored_url();
-        deploy_contract(&self.executor, params, stored_url.as_deref())
+        watch_condition(
+            &self.executor,
+            Arc::clone(&self.rpc),
+            &self.watches,
+            params,
+            stored_url.as_deref(),
+            watch::notifier(peer),
+        )
+        .await
+        .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Stop a watch started with watch_condition",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn stop_watch(
+        &self,
+        Parameters(params): Parameters<StopWatchParams>,
+    ) -> Result<CallToolResult, McpError> {
+        stop_watch(&self.watches, params)
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
-    #[tool(description = "Call a contract method on a deployed contract")]
-    async fn call_contract(
+    #[tool(
+        description = "List running watches with their condition, node, check count and latest value",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn list_watches(
         &self,
-        Parameters(params): Parameters<CallContractParams>,
+        Parameters(params): Parameters<ListWatchesParams>,
     ) -> Result<CallToolResult, McpError> {
-        call_contract(&self.executor, params)
+        list_watches(&self.watches, params)
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
     #[tool(
-        description = "Interact with a chain runtime: execute transactions, query storage, or read constants. Use metadata=true to discover pallets/extrinsics/storage/constants."
+        description = "Save up_network parameters (config path or relay chain, parachains, overrides) as a named profile after checking the config, so a topology used again and again launches with up_network_profile",
+        annotations(read_only_hint = false, destructive_hint = false)
     )]
-    async fn call_chain(
+    async fn save_network_profile(
         &self,
-        Parameters(params): Parameters<CallChainParams>,
+        Parameters(params): Parameters<SaveNetworkProfileParams>,
     ) -> Result<CallToolResult, McpError> {
-        call_chain(&self.executor, params)
+        save_network_profile(&profiles_dir(), params)
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
     #[tool(
-        description = "Launch a local ink! node for contract development and testing (runs in background)"
+        description = "Launch a network from a profile saved with save_network_profile",
+        annotations(read_only_hint = false, destructive_hint = false)
     )]
-    async fn up_ink_node(
+    async fn up_network_profile(
         &self,
-        Parameters(params): Parameters<UpInkNodeParams>,
+        ct: CancellationToken,
+        Parameters(params): Parameters<UpNetworkProfileParams>,
     ) -> Result<CallToolResult, McpError> {
-        let result = up_ink_node(&self.executor, params)
+        let dir = profiles_dir();
+        let detach = profiles::load(&dir, params.name.trim())
+            .await
+            .ok()
+            .flatten()
+            .and_then(|network| network.detach)
+            .unwrap_or(false);
+        let result = up_network_profile(&self.cancellable(ct), &dir, params)
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        self.track_network(&result, detach);
+        Ok(result)
+    }
 
-        // Store the WebSocket URL for later use (result contains the URL on success)
-        if result.is_error != Some(true) {
-            if let Some(url) = common::extract_text(&result) {
-                if let Ok(mut stored_url) = self.node_websocket_url.lock() {
-                    *stored_url = Some(url);
-                }
-            }
-        }
-
+    #[tool(
+        description = "Stop local ink! nodes by PID, port or launched-node name; checks that their ports closed and reports what was terminated",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn clean_nodes(
+        &self,
+        Parameters(params): Parameters<CleanNodesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let before = self.nodes.list();
+        let pids = params.pids.clone();
+        let result = clean_nodes(&self.executor, &self.nodes, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        self.forget_stopped(&before, &result, &pids);
         Ok(result)
     }
 
-    #[tool(description = "Launch a local network using a zombienet spec")]
-    async fn up_network(
+    #[tool(
+        description = "List the ink! nodes launched with up_ink_node: name, URL, pids and launch time. The selected node is used by tools called without a url.",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn list_nodes(
         &self,
-        Parameters(params): Parameters<UpNetworkParams>,
+        Parameters(params): Parameters<ListNodesParams>,
     ) -> Result<CallToolResult, McpError> {
-        up_network(&self.executor, params)
+        list_nodes(&self.nodes, params)
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
-    #[tool(description = "Stop running local ink! nodes by PID")]
-    async fn clean_nodes(
+    #[tool(
+        description = "Select a launched node by name, so tools called without a url use it",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn select_node(
         &self,
-        Parameters(params): Parameters<CleanNodesParams>,
+        Parameters(params): Parameters<SelectNodeParams>,
+    ) -> Result<CallToolResult, McpError> {
+        select_node(&self.nodes, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Stop a launched node by name and forget it",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn stop_node(
+        &self,
+        Parameters(params): Parameters<StopNodeParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let before = self.nodes.list();
+        let result = stop_node(&self.executor, &self.nodes, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        self.forget_stopped(&before, &result, &[]);
+        Ok(result)
+    }
+
+    #[tool(
+        description = "Read the log of a node launched with up_ink_node (default: the selected node), or of its Ethereum RPC node with eth_rpc=true. Use tail and grep to find errors after a failed deployment or call.",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
+    async fn get_node_logs(
+        &self,
+        Parameters(params): Parameters<GetNodeLogsParams>,
     ) -> Result<CallToolResult, McpError> {
-        clean_nodes(&self.executor, params)
+        get_node_logs(&self.nodes, params)
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
-    #[tool(description = "Stop a running network by zombie.json path or base dir")]
+    #[tool(
+        description = "Stop a running network by zombie.json path or base dir",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
     async fn clean_network(
         &self,
         Parameters(params): Parameters<CleanNetworkParams>,
     ) -> Result<CallToolResult, McpError> {
-        clean_network(&self.executor, params)
-            .map_err(|e| McpError::internal_error(e.to_string(), None))
+        let path = (!params.all.unwrap_or(false)).then(|| params.path.clone().unwrap_or_default());
+        let result = clean_network(&self.executor, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        if result.is_error != Some(true) {
+            self.children.forget_network(path.as_deref());
+        }
+        Ok(result)
+    }
+
+    #[tool(
+        description = "Delete a contract's build output (its target directory, like cargo clean). With dry_run, list what would be deleted and the space it would free",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn clean_contract(
+        &self,
+        Parameters(params): Parameters<CleanContractParams>,
+    ) -> Result<CallToolResult, McpError> {
+        clean_contract(params).map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Delete a chain project's build output (its target directory, or one profile's output). With dry_run, list what would be deleted and the space it would free",
+        annotations(read_only_hint = false, destructive_hint = true)
+    )]
+    async fn clean_chain(
+        &self,
+        Parameters(params): Parameters<CleanChainParams>,
+    ) -> Result<CallToolResult, McpError> {
+        clean_chain(params).map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
-    #[tool(description = "Get help for any Pop CLI command")]
+    #[tool(
+        description = "Get help for any Pop CLI command",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
     async fn pop_help(
         &self,
         Parameters(params): Parameters<PopHelpParams>,
     ) -> Result<CallToolResult, McpError> {
-        pop_help(&self.executor, params).map_err(|e| McpError::internal_error(e.to_string(), None))
+        pop_help(&self.executor, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 
-    #[tool(description = "Convert between Ethereum and Substrate (Polkadot) addresses")]
+    #[tool(
+        description = "Convert between Ethereum and Substrate (Polkadot) addresses",
+        annotations(read_only_hint = true, destructive_hint = false)
+    )]
     async fn convert_address(
         &self,
         Parameters(params): Parameters<ConvertAddressParams>,
     ) -> Result<CallToolResult, McpError> {
         convert_address(&self.executor, params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Get the path to a shared prebuilt contract or chain fixture, creating and building it once per Pop CLI version",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn get_fixture(
+        &self,
+        ct: CancellationToken,
+        Parameters(params): Parameters<GetFixtureParams>,
+    ) -> Result<CallToolResult, McpError> {
+        get_fixture(&self.cancellable(ct), params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    #[tool(
+        description = "Check the environment end to end: scaffold a flipper contract, build it, launch a temporary ink! node, deploy, call get/flip and clean up, reporting each stage",
+        annotations(read_only_hint = false, destructive_hint = false)
+    )]
+    async fn self_test(
+        &self,
+        ct: CancellationToken,
+        Parameters(params): Parameters<SelfTestParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self_test(&self.cancellable(ct), params)
+            .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))
     }
 }
 
-#[tool_handler]
-impl ServerHandler for PopMcpServer {
+impl<E: CommandExecutor> ServerHandler for PopMcpServer<E> {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             protocol_version: ProtocolVersion::V_2024_11_05,
             capabilities: ServerCapabilities::builder()
                 .enable_tools()
                 .enable_resources()
+                .enable_prompts()
+                .enable_logging()
                 .build(),
             server_info: Implementation::from_build_env(),
             instructions: Some(
@@ -258,37 +1540,114 @@ impl ServerHandler for PopMcpServer {
         }
     }
 
-    fn list_resources(
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let result = self
+            .tool_router
+            .call(ToolCallContext::new(self, request, context))
+            .await?;
+        Ok(self.output.apply(result))
+    }
+
+    async fn list_tools(
         &self,
         _request: Option<PaginatedRequestParam>,
         _context: RequestContext<RoleServer>,
-    ) -> impl std::future::Future<Output = Result<ListResourcesResult, McpError>> + Send + '_ {
-        std::future::ready(Ok(ListResourcesResult {
-            resources: resources::list_resources(),
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(self.tool_router.list_all()))
+    }
+
+    fn on_initialized(
+        &self,
+        context: NotificationContext<RoleServer>,
+    ) -> impl std::future::Future<Output = ()> + Send + '_ {
+        self.log.connect(context.peer);
+        std::future::ready(())
+    }
+
+    fn on_roots_list_changed(
+        &self,
+        _context: NotificationContext<RoleServer>,
+    ) -> impl std::future::Future<Output = ()> + Send + '_ {
+        self.roots.invalidate();
+        std::future::ready(())
+    }
+
+    fn set_level(
+        &self,
+        request: SetLevelRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> impl std::future::Future<Output = Result<(), McpError>> + Send + '_ {
+        self.log.set_level(request.level);
+        std::future::ready(Ok(()))
+    }
+
+    fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> impl std::future::Future<Output = Result<ListPromptsResult, McpError>> + Send + '_ {
+        std::future::ready(Ok(ListPromptsResult {
+            prompts: prompts::list_prompts(),
             next_cursor: None,
         }))
     }
 
+    fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> impl std::future::Future<Output = Result<GetPromptResult, McpError>> + Send + '_ {
+        std::future::ready(
+            prompts::get_prompt(&request.name, request.arguments.as_ref())
+                .map_err(|e| McpError::invalid_params(e.to_string(), None)),
+        )
+    }
+
+    fn list_resources(
+        &self,
+        request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> impl std::future::Future<Output = Result<ListResourcesResult, McpError>> + Send + '_ {
+        let cursor = request.and_then(|request| request.cursor);
+        std::future::ready(
+            resources::list_page(cursor.as_deref())
+                .map(|(resources, next_cursor)| ListResourcesResult {
+                    resources,
+                    next_cursor,
+                })
+                .map_err(|e| McpError::invalid_params(e, None)),
+        )
+    }
+
     fn read_resource(
         &self,
         request: ReadResourceRequestParam,
         _context: RequestContext<RoleServer>,
     ) -> impl std::future::Future<Output = Result<ReadResourceResult, McpError>> + Send + '_ {
-        std::future::ready(match resources::read_resource(&request.uri) {
-            Some(contents) => Ok(ReadResourceResult {
-                contents: vec![contents],
-            }),
-            None => Err(McpError::resource_not_found(
-                format!("Resource not found: {}", request.uri),
-                None,
-            )),
-        })
+        let contracts = self.dev_loops.contract_book();
+        std::future::ready(
+            match resources::read_resource(&request.uri, &contracts, &self.deployments) {
+                Some(contents) => Ok(ReadResourceResult {
+                    contents: vec![contents],
+                }),
+                None => Err(McpError::resource_not_found(
+                    format!("Resource not found: {}", request.uri),
+                    None,
+                )),
+            },
+        )
     }
 }
 
 #[cfg(test)]
 #[allow(clippy::panic)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use super::*;
 
     #[test]
@@ -302,6 +1661,7 @@ mod tests {
         // Verify capabilities
         assert!(info.capabilities.tools.is_some());
         assert!(info.capabilities.resources.is_some());
+        assert!(info.capabilities.prompts.is_some());
 
         // Verify instructions
         assert!(info
@@ -319,6 +1679,29 @@ mod tests {
         let _ = &server.executor;
     }
 
+    #[tokio::test]
+    async fn tools_run_through_the_injected_executor() {
+        let executor = crate::test_utils::MockExecutor::new().on(&["--version"], "pop 0.9.0");
+        let server = PopMcpServer::with_executor(executor.clone());
+        let result = server
+            .check_pop_installation(Parameters(CheckPopInstallationParams {}))
+            .await;
+        assert!(result.is_ok_and(|r| common::content_text(&r).contains("pop 0.9.0")));
+        assert_eq!(executor.calls().len(), 1);
+    }
+
+    #[test]
+    fn output_settings_belong_to_their_server() {
+        let plain = PopMcpServer::new()
+            .with_ascii_output(true)
+            .with_output_limit(0);
+        let other = PopMcpServer::new();
+        assert_eq!(other.output, OutputStyle::default());
+        assert_eq!(plain.session().output, plain.output);
+        assert!(plain.output.ascii);
+        assert_eq!(plain.output.limit, 0);
+    }
+
     #[test]
     fn url_storage_round_trips() {
         let server = PopMcpServer::new();
@@ -326,16 +1709,72 @@ mod tests {
         // Initially empty
         assert!(server.get_stored_url().is_none());
 
-        // Store a URL
-        if let Ok(mut guard) = server.node_websocket_url.lock() {
-            *guard = Some("ws://localhost:9944".to_owned());
-        }
+        // Register a node
+        server.nodes.register(
+            &NodeInfo {
+                url: "ws://localhost:9944".to_owned(),
+                pids: vec![4242],
+                logs: Vec::new(),
+                port: Some(9944),
+                eth_rpc_port: None,
+            },
+            None,
+        );
 
         // Verify retrieval
         let url = server.get_stored_url();
         assert_eq!(url, Some("ws://localhost:9944".to_owned()));
     }
 
+    #[test]
+    fn deprecated_tools_and_replacements_are_registered() {
+        let server = PopMcpServer::new();
+        let names: Vec<String> = server
+            .tool_router
+            .list_all()
+            .into_iter()
+            .map(|tool| tool.name.to_string())
+            .collect();
+        for entry in deprecation::DEPRECATIONS {
+            assert!(names.iter().any(|name| name == entry.tool));
+            assert!(names.iter().any(|name| name == entry.replacement));
+        }
+    }
+
+    #[test]
+    fn every_tool_says_whether_it_reads_or_destroys() {
+        let server = PopMcpServer::new();
+        let hints: BTreeMap<String, (Option<bool>, Option<bool>)> = server
+            .tool_router
+            .list_all()
+            .into_iter()
+            .map(|tool| {
+                let annotations = tool.annotations.unwrap_or_default();
+                (
+                    tool.name.to_string(),
+                    (annotations.read_only_hint, annotations.destructive_hint),
+                )
+            })
+            .collect();
+        assert!(hints
+            .values()
+            .all(|hints| matches!(hints, (Some(_), Some(_)))));
+        for name in ["pop_help", "convert_address", "list_nodes", "chain_rpc"] {
+            assert_eq!((name, hints[name]), (name, (Some(true), Some(false))));
+        }
+        for name in [
+            "deploy_contract",
+            "call_contract",
+            "call_chain",
+            "clean_nodes",
+            "clean_network",
+            "clean_contract",
+            "clean_chain",
+        ] {
+            assert_eq!((name, hints[name]), (name, (Some(false), Some(true))));
+        }
+    }
+
     #[test]
     fn all_tool_schemas_are_claude_code_compatible() {
         // Claude Code's MCP client has specific schema requirements:
@@ -0,0 +1,140 @@
+//! Spilling of oversized tool output to log resources.
+//!
+//! A failing chain build can print megabytes of cargo output, too much for one MCP
+//! message. Results longer than the server's output limit keep their head and tail; the full
+//! text is written to a temp file served as a `poplog://<id>` resource.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// URI scheme of spilled log resources.
+pub const LOG_URI_PREFIX: &str = "poplog://";
+
+/// Default maximum size of a result text, in bytes.
+pub const DEFAULT_OUTPUT_LIMIT: usize = 100 * 1024;
+
+/// Id of the next spilled log.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// Spilled logs, oldest first.
+static LOGS: Mutex<Vec<SpilledLog>> = Mutex::new(Vec::new());
+
+/// A full tool output saved to disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpilledLog {
+    /// Resource URI (`poplog://<id>`).
+    pub uri: String,
+    /// File holding the output.
+    pub path: PathBuf,
+    /// Size of the output in bytes.
+    pub size: usize,
+}
+
+/// Logs spilled so far.
+pub fn logs() -> Vec<SpilledLog> {
+    LOGS.lock().map(|logs| logs.clone()).unwrap_or_default()
+}
+
+/// Full output of the spilled log at `uri`.
+pub fn read_log(uri: &str) -> Option<String> {
+    let path = logs().into_iter().find(|log| log.uri == uri)?.path;
+    std::fs::read_to_string(path).ok()
+}
+
+/// Largest char boundary in `text` at or below `index`.
+fn floor_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// Smallest char boundary in `text` at or above `index`.
+fn ceil_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+/// `text` cut to `limit` bytes (0 keeps it whole), keeping the first quarter and the
+/// last three quarters, where compiler errors and summaries usually are. The full text
+/// is spilled to a log resource.
+pub fn truncate(text: String, limit: usize) -> String {
+    if limit == 0 || text.len() <= limit {
+        return text;
+    }
+    let head_end = floor_boundary(&text, limit / 4);
+    let tail_start = ceil_boundary(&text, text.len() - (limit - limit / 4));
+    let omitted = tail_start - head_end;
+    let location = match spill(&text) {
+        Ok(uri) => format!("full output: {}", uri),
+        Err(e) => format!("full output could not be saved: {}", e),
+    };
+    format!(
+        "{}\n\n[... {} bytes omitted; {} ...]\n\n{}",
+        &text[..head_end],
+        omitted,
+        location,
+        &text[tail_start..]
+    )
+}
+
+/// Write `text` to a new log file and register it, returning its URI.
+fn spill(text: &str) -> std::io::Result<String> {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let dir = std::env::temp_dir().join("pop-mcp-logs");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}-{}.log", std::process::id(), id));
+    std::fs::write(&path, text)?;
+    let uri = format!("{}{}", LOG_URI_PREFIX, id);
+    if let Ok(mut logs) = LOGS.lock() {
+        logs.push(SpilledLog {
+            uri: uri.clone(),
+            path,
+            size: text.len(),
+        });
+    }
+    Ok(uri)
+}
+
+#[cfg(test)]
+#[allow(clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_text_is_untouched() {
+        assert_eq!(
+            truncate("Build successful!".to_owned(), 64),
+            "Build successful!"
+        );
+        assert_eq!(truncate("x".repeat(100), 0), "x".repeat(100));
+    }
+
+    #[test]
+    fn long_text_keeps_head_and_tail_and_spills() {
+        let text = format!("{}{}", "a".repeat(1000), "error[E0425]: cannot find value");
+        let truncated = truncate(text.clone(), 200);
+        assert!(truncated.starts_with(&"a".repeat(50)));
+        assert!(truncated.ends_with("error[E0425]: cannot find value"));
+        let Some(uri) = truncated
+            .split("full output: ")
+            .nth(1)
+            .and_then(|rest| rest.split_whitespace().next())
+        else {
+            panic!("no log uri in {truncated}");
+        };
+        assert!(uri.starts_with(LOG_URI_PREFIX));
+        assert_eq!(read_log(uri), Some(text));
+    }
+
+    #[test]
+    fn truncation_respects_char_boundaries() {
+        let text = "é".repeat(300);
+        let truncated = truncate(text, 101);
+        assert!(truncated.starts_with(&"é".repeat(12)));
+        assert!(truncated.ends_with(&"é".repeat(37)));
+    }
+}
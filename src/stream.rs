@@ -0,0 +1,173 @@
+//! Bounded queues between producers of output and the MCP client.
+//!
+//! Command output lines become progress notifications, and commands, watches and dev
+//! loops become log messages. A producer can outpace a slow client by far (a chatty
+//! collator prints thousands of lines a minute), so each stream holds at most a fixed
+//! number of items: when it is full the oldest one is dropped, and the receiver learns
+//! how many were dropped before the next item it gets. Producers never wait.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::Notify;
+
+/// Items a stream holds by default.
+pub const DEFAULT_CAPACITY: usize = 1000;
+
+#[derive(Debug)]
+struct State<T> {
+    items: VecDeque<T>,
+    capacity: usize,
+    /// Items dropped since the receiver last took one.
+    dropped: u64,
+    senders: usize,
+    receiver_gone: bool,
+}
+
+#[derive(Debug)]
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    ready: Notify,
+}
+
+/// Sending half of a bounded stream. Clones send into the same stream.
+#[derive(Debug)]
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// Receiving half of a bounded stream.
+#[derive(Debug)]
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// A stream holding at most `capacity` (at least 1) items, dropping the oldest.
+pub fn bounded<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            items: VecDeque::new(),
+            capacity: capacity.max(1),
+            dropped: 0,
+            senders: 1,
+            receiver_gone: false,
+        }),
+        ready: Notify::new(),
+    });
+    (
+        Sender {
+            shared: Arc::clone(&shared),
+        },
+        Receiver { shared },
+    )
+}
+
+impl<T> Sender<T> {
+    /// Queue `item`, dropping the oldest queued item when the stream is full.
+    ///
+    /// Returns `false` once the receiver is gone.
+    pub fn send(&self, item: T) -> bool {
+        let Ok(mut state) = self.shared.state.lock() else {
+            return false;
+        };
+        if state.receiver_gone {
+            return false;
+        }
+        if state.items.len() == state.capacity {
+            state.items.pop_front();
+            state.dropped += 1;
+        }
+        state.items.push_back(item);
+        drop(state);
+        self.shared.ready.notify_one();
+        true
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        if let Ok(mut state) = self.shared.state.lock() {
+            state.senders += 1;
+        }
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.shared.state.lock() {
+            state.senders = state.senders.saturating_sub(1);
+        }
+        self.shared.ready.notify_one();
+    }
+}
+
+impl<T> Receiver<T> {
+    /// The next item with the number of items dropped before it, or `None` once
+    /// every sender is gone and the stream is empty.
+    pub async fn recv(&mut self) -> Option<(T, u64)> {
+        loop {
+            {
+                let mut state = self.shared.state.lock().ok()?;
+                if let Some(item) = state.items.pop_front() {
+                    return Some((item, std::mem::take(&mut state.dropped)));
+                }
+                if state.senders == 0 {
+                    return None;
+                }
+            }
+            self.shared.ready.notified().await;
+        }
+    }
+
+    /// The next item with the number of items dropped before it, if one is queued.
+    pub fn try_recv(&mut self) -> Option<(T, u64)> {
+        let mut state = self.shared.state.lock().ok()?;
+        let item = state.items.pop_front()?;
+        Some((item, std::mem::take(&mut state.dropped)))
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.shared.state.lock() {
+            state.receiver_gone = true;
+            state.items.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn full_streams_drop_the_oldest_items() {
+        let (sender, mut receiver) = bounded(2);
+        for line in ["a", "b", "c", "d"] {
+            assert!(sender.send(line));
+        }
+        assert_eq!(receiver.recv().await, Some(("c", 2)));
+        assert!(sender.send("e"));
+        assert_eq!(receiver.recv().await, Some(("d", 0)));
+        assert_eq!(receiver.recv().await, Some(("e", 0)));
+        drop(sender);
+        assert_eq!(receiver.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn streams_end_when_every_sender_is_gone() {
+        let (sender, mut receiver) = bounded::<u32>(DEFAULT_CAPACITY);
+        let clone = sender.clone();
+        drop(sender);
+        let waiting = tokio::spawn(async move { receiver.recv().await });
+        assert!(clone.send(7));
+        assert_eq!(waiting.await.ok().flatten(), Some((7, 0)));
+
+        let (sender, receiver) = bounded::<u32>(1);
+        drop(receiver);
+        assert!(!sender.send(1));
+    }
+}
@@ -0,0 +1,232 @@
+//! In-memory [`CommandExecutor`] for unit tests.
+//!
+//! [`MockExecutor`] answers Pop CLI commands with canned output and records every call,
+//! so tools and [`crate::PopMcpServer`] can be tested without a `pop` binary.
+//!
+//! ```
+//! # async fn example() -> pop_mcp_server::PopMcpResult<()> {
+//! use pop_mcp_server::test_utils::MockExecutor;
+//! use pop_mcp_server::tools::{check_pop_installation, CheckPopInstallationParams};
+//!
+//! let executor = MockExecutor::new().on(&["--version"], "pop 0.9.0");
+//! let result = check_pop_installation(&executor, CheckPopInstallationParams {}).await?;
+//! assert_eq!(result.is_error, Some(false));
+//! assert_eq!(executor.calls()[0].args, ["--version"]);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::{CommandExecutor, RetryPolicy};
+
+/// Serializes unit tests that change environment variables, which every test thread
+/// shares.
+#[cfg(test)]
+pub(crate) static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Set the environment variable `name` to `value` under [`ENV_LOCK`].
+#[cfg(test)]
+pub(crate) fn set_env(name: &str, value: &str) {
+    let _env = ENV_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    std::env::set_var(name, value);
+}
+
+/// A command run through a [`MockExecutor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockCall {
+    /// Arguments passed to `pop`.
+    pub args: Vec<String>,
+    /// Working directory the command ran in, if overridden.
+    pub dir: Option<PathBuf>,
+    /// Timeout the command ran with, if any.
+    pub timeout: Option<Duration>,
+    /// Extra environment variables the command ran with.
+    pub env: BTreeMap<String, String>,
+}
+
+/// Canned reply for commands starting with `prefix`.
+#[derive(Debug)]
+struct Response {
+    prefix: Vec<String>,
+    output: Result<String, String>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    responses: Vec<Response>,
+    calls: Vec<MockCall>,
+}
+
+/// [`CommandExecutor`] returning canned output instead of running Pop CLI.
+///
+/// Responses match on argument prefixes; the most recently added match wins, so a
+/// broad default can be narrowed later. Commands without a match fail. Clones (and
+/// copies made by `in_dir`, `with_timeout`, ...) share responses and the call log.
+#[derive(Debug, Clone, Default)]
+pub struct MockExecutor {
+    state: Arc<Mutex<State>>,
+    dir: Option<PathBuf>,
+    timeout: Option<Duration>,
+    env: BTreeMap<String, String>,
+    retry: RetryPolicy,
+}
+
+impl MockExecutor {
+    /// Create an executor with no responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Succeed with `output` for commands starting with `prefix`.
+    pub fn on(self, prefix: &[&str], output: impl Into<String>) -> Self {
+        self.respond(prefix, Ok(output.into()))
+    }
+
+    /// Fail with `output` for commands starting with `prefix`, like a non-zero exit.
+    pub fn on_failure(self, prefix: &[&str], output: impl Into<String>) -> Self {
+        self.respond(prefix, Err(output.into()))
+    }
+
+    /// Every command run so far, oldest first.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.state
+            .lock()
+            .map(|state| state.calls.clone())
+            .unwrap_or_default()
+    }
+
+    fn respond(self, prefix: &[&str], output: Result<String, String>) -> Self {
+        if let Ok(mut state) = self.state.lock() {
+            state.responses.push(Response {
+                prefix: prefix.iter().map(|arg| (*arg).to_owned()).collect(),
+                output,
+            });
+        }
+        self
+    }
+
+    fn reply(&self, args: &[&str]) -> PopMcpResult<String> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| PopMcpError::Internal("mock executor state poisoned".to_owned()))?;
+        state.calls.push(MockCall {
+            args: args.iter().map(|arg| (*arg).to_owned()).collect(),
+            dir: self.dir.clone(),
+            timeout: self.timeout,
+            env: self.env.clone(),
+        });
+        let response = state.responses.iter().rev().find(|response| {
+            response.prefix.len() <= args.len()
+                && response
+                    .prefix
+                    .iter()
+                    .zip(args)
+                    .all(|(want, got)| want == got)
+        });
+        match response {
+            Some(Response {
+                output: Ok(out), ..
+            }) => Ok(out.clone()),
+            Some(Response {
+                output: Err(out), ..
+            }) => Err(PopMcpError::CommandExecution(out.clone())),
+            None => Err(PopMcpError::CommandExecution(format!(
+                "no mock response for `pop {}`",
+                args.join(" ")
+            ))),
+        }
+    }
+}
+
+impl CommandExecutor for MockExecutor {
+    async fn execute(&self, args: &[&str]) -> PopMcpResult<String> {
+        self.reply(args)
+    }
+
+    fn in_dir(&self, dir: &Path) -> Self {
+        Self {
+            dir: Some(dir.to_path_buf()),
+            ..self.clone()
+        }
+    }
+
+    fn working_dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    fn with_env(mut self, env: BTreeMap<String, String>) -> Self {
+        self.env.extend(env);
+        self
+    }
+
+    fn with_retry(self, retry: RetryPolicy) -> Self {
+        Self { retry, ..self }
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        self.retry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn latest_matching_response_wins() {
+        let executor = MockExecutor::new()
+            .on(&["build"], "Build completed")
+            .on_failure(&["build", "--path", "broken"], "error: could not compile");
+        assert_eq!(
+            executor.execute(&["build", "--path", "ok"]).await.ok(),
+            Some("Build completed".to_owned())
+        );
+        assert!(executor
+            .execute(&["build", "--path", "broken"])
+            .await
+            .is_err());
+        assert!(executor.execute(&["up"]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn calls_record_dir_timeout_and_env() {
+        let executor = MockExecutor::new().on(&["new"], "created");
+        let env = BTreeMap::from([("RUST_LOG".to_owned(), "debug".to_owned())]);
+        let scoped = executor
+            .in_dir(Path::new("/tmp/work"))
+            .with_timeout(Duration::from_secs(5))
+            .with_env(env.clone());
+        assert!(scoped
+            .execute(&["new", "contract", "flipper"])
+            .await
+            .is_ok());
+        assert_eq!(
+            executor.calls(),
+            [MockCall {
+                args: vec![
+                    "new".to_owned(),
+                    "contract".to_owned(),
+                    "flipper".to_owned()
+                ],
+                dir: Some(PathBuf::from("/tmp/work")),
+                timeout: Some(Duration::from_secs(5)),
+                env,
+            }]
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Test support for downstream e2e suites.
+//!
+//! Launching an ink! node per test is slow and races on ports. [`SharedNode`] starts
+//! one node on first use, hands every caller a [`SharedNodeGuard`], and stops the node
+//! once the last guard drops, so a whole suite (here or in a crate embedding
+//! pop-mcp-server) shares a single node.
+//!
+//! ```no_run
+//! # async fn example() -> pop_mcp_server::PopMcpResult<()> {
+//! use pop_mcp_server::testing::SharedNode;
+//! use pop_mcp_server::PopExecutor;
+//!
+//! let (url, _guard) = SharedNode::ensure(
+//!     &PopExecutor::new(),
+//!     SharedNode::INK_NODE_PORT,
+//!     SharedNode::ETH_RPC_PORT,
+//! )
+//! .await?;
+//! // ... deploy and call against `url`; the node stops when the last guard drops.
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::{CommandExecutor, PopExecutor};
+use crate::tools::common::extract_texts;
+use crate::tools::{stop_pids, up_ink_node, UpInkNodeParams};
+
+/// Serializes [`SharedNode::ensure`], so only one caller launches the node.
+static STARTING: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// The shared ink! node, if one is running. Only held briefly and never across an
+/// `.await`, so guards can always take it when they drop.
+static NODE: Mutex<Option<NodeState>> = Mutex::new(None);
+
+struct NodeState {
+    url: String,
+    port: u16,
+    users: usize,
+    pids: Vec<u32>,
+}
+
+/// Reference-counted ink! node shared between callers.
+///
+/// The first [`SharedNode::ensure`] launches the node; later calls reuse it while it is
+/// still listening. The node is stopped when the last [`SharedNodeGuard`] drops.
+#[derive(Debug)]
+pub struct SharedNode;
+
+impl SharedNode {
+    /// Conventional ink! node port for shared test nodes, off the default 9944.
+    pub const INK_NODE_PORT: u16 = 9945;
+    /// Conventional Ethereum RPC port for shared test nodes, off the default 8545.
+    pub const ETH_RPC_PORT: u16 = 8546;
+
+    /// Start the shared node on the given ports if needed and return its URL.
+    pub async fn ensure<E: CommandExecutor>(
+        executor: &E,
+        ink_node_port: u16,
+        eth_rpc_port: u16,
+    ) -> PopMcpResult<(String, SharedNodeGuard<E>)> {
+        let _starting = STARTING.lock().await;
+
+        let running = node_state()
+            .as_ref()
+            .map(|state| (state.url.clone(), state.port));
+        if let Some((url, port)) = running {
+            if is_port_open(port).await {
+                if let Some(state) = node_state().as_mut() {
+                    state.users += 1;
+                    return Ok((url, SharedNodeGuard::new(executor)));
+                }
+            }
+            // Node died; start a fresh one below.
+        }
+
+        let result = up_ink_node(
+            executor,
+            UpInkNodeParams {
+                ink_node_port: Some(ink_node_port),
+                eth_rpc_port: Some(eth_rpc_port),
+                name: None,
+                detach: None,
+            },
+        )
+        .await?;
+        let texts = extract_texts(&result);
+        if result.is_error == Some(true) {
+            return Err(PopMcpError::CommandExecution(texts.join("\n")));
+        }
+        let url = texts
+            .first()
+            .cloned()
+            .ok_or_else(|| PopMcpError::CommandExecution("ink-node returned no URL".to_owned()))?;
+        let pids = texts
+            .iter()
+            .find_map(|t| t.strip_prefix("pids:"))
+            .map(|rest| {
+                rest.split_whitespace()
+                    .filter_map(|p| p.parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if !wait_for_port(ink_node_port, Duration::from_secs(30)).await {
+            return Err(PopMcpError::CommandExecution(format!(
+                "ink-node not listening on port {}",
+                ink_node_port
+            )));
+        }
+
+        *node_state() = Some(NodeState {
+            url: url.clone(),
+            port: ink_node_port,
+            users: 1,
+            pids,
+        });
+        Ok((url, SharedNodeGuard::new(executor)))
+    }
+}
+
+/// The shared node state, even if a panicking test poisoned the lock.
+fn node_state() -> std::sync::MutexGuard<'static, Option<NodeState>> {
+    NODE.lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Keeps the shared node alive; the last guard to drop stops it.
+#[derive(Debug)]
+pub struct SharedNodeGuard<E: CommandExecutor = PopExecutor> {
+    executor: E,
+}
+
+impl<E: CommandExecutor> SharedNodeGuard<E> {
+    fn new(executor: &E) -> Self {
+        Self {
+            executor: executor.clone(),
+        }
+    }
+}
+
+impl<E: CommandExecutor> Drop for SharedNodeGuard<E> {
+    fn drop(&mut self) {
+        let stopped = {
+            let mut node = node_state();
+            let last = match node.as_mut() {
+                Some(state) => {
+                    state.users = state.users.saturating_sub(1);
+                    state.users == 0
+                }
+                None => false,
+            };
+            if last {
+                node.take()
+            } else {
+                None
+            }
+        };
+        if let Some(state) = stopped.filter(|state| !state.pids.is_empty()) {
+            stop_node(&self.executor, state.pids);
+        }
+    }
+}
+
+/// Stop the node from a synchronous context (`Drop`), on a dedicated thread so it
+/// works whether or not a runtime is running on this one.
+fn stop_node<E: CommandExecutor>(executor: &E, pids: Vec<u32>) {
+    let executor = executor.clone();
+    let stopper = std::thread::spawn(move || {
+        let Ok(runtime) = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        else {
+            return;
+        };
+        let _ = runtime.block_on(stop_pids(&executor, &pids));
+    });
+    let _ = stopper.join();
+}
+
+async fn is_port_open(port: u16) -> bool {
+    let connect = tokio::net::TcpStream::connect(("127.0.0.1", port));
+    matches!(
+        tokio::time::timeout(Duration::from_millis(200), connect).await,
+        Ok(Ok(_))
+    )
+}
+
+async fn wait_for_port(port: u16, timeout: Duration) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        if is_port_open(port).await {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutor;
+
+    #[tokio::test]
+    async fn last_guard_stops_the_node() -> anyhow::Result<()> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let port = listener.local_addr()?.port();
+        let executor = MockExecutor::new()
+            .on(
+                &["up", "ink-node"],
+                format!(
+                    "⚙  Local node started successfully:\n│  url: ws://localhost:{}/\n└  Run `kill -9 424242` to terminate it.",
+                    port
+                ),
+            )
+            .on(&["clean", "node"], "Stopped");
+        let (url, first) = SharedNode::ensure(&executor, port, port + 1).await?;
+        let (again, second) = SharedNode::ensure(&executor, port, port + 1).await?;
+        assert_eq!(url, again);
+        let launches = |executor: &MockExecutor, command: &str| {
+            executor
+                .calls()
+                .iter()
+                .filter(|call| call.args.first().map(String::as_str) == Some(command))
+                .count()
+        };
+        assert_eq!(launches(&executor, "up"), 1);
+
+        // A guard dropped while another caller is inside `ensure` still counts down.
+        let starting = STARTING.lock().await;
+        drop(first);
+        drop(starting);
+        assert_eq!(launches(&executor, "clean"), 0);
+        drop(second);
+        assert_eq!(launches(&executor, "clean"), 1);
+        assert!(node_state().is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,207 @@
+//! Human-readable balances using the connected chain's token symbol and decimals.
+//!
+//! Tool outputs report balances in planck, which is easy to misread by orders of
+//! magnitude. [`TokenFormat`] renders them as exact token amounts (e.g. `1.5 UNIT`),
+//! using `system_properties` of the chain unless a per-network preference overrides it.
+
+use std::collections::HashMap;
+
+use rmcp::model::{CallToolResult, Content, RawContent};
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+use crate::error::PopMcpResult;
+use crate::rpc::RpcPool;
+
+/// Keys whose numeric values are balances, compared case-insensitively without `_`.
+const BALANCE_KEYS: &[&str] = &["free", "reserved", "frozen", "amount", "tip"];
+/// Key suffixes that denote balances (e.g. `CandidacyBond`, `partial_fee`).
+const BALANCE_SUFFIXES: &[&str] = &["balance", "bond", "deposit", "fee", "frozen", "issuance"];
+
+/// Token symbol and decimals of a chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenFormat {
+    /// Token symbol (e.g. `UNIT`).
+    pub symbol: String,
+    /// Number of decimals one token has in planck.
+    pub decimals: u8,
+}
+
+impl TokenFormat {
+    /// Read the native token from a `system_properties` response.
+    ///
+    /// Multi-token chains report arrays; the first entry is the native token.
+    pub fn from_properties(properties: &Value) -> Option<Self> {
+        fn first(value: Option<&Value>) -> Option<&Value> {
+            match value? {
+                Value::Array(items) => items.first(),
+                other => Some(other),
+            }
+        }
+        let symbol = first(properties.get("tokenSymbol"))?.as_str()?.to_owned();
+        let decimals = u8::try_from(first(properties.get("tokenDecimals"))?.as_u64()?).ok()?;
+        Some(Self { symbol, decimals })
+    }
+
+    /// Fetch the native token of the chain at `url`.
+    pub async fn fetch(pool: &RpcPool, url: &str) -> PopMcpResult<Option<Self>> {
+        let properties = pool.request(url, "system_properties", Vec::new()).await?;
+        Ok(Self::from_properties(&properties))
+    }
+
+    /// Render `planck` as an exact token amount, e.g. `1.5 UNIT`.
+    pub fn format(&self, planck: u128) -> String {
+        let digits = planck.to_string();
+        let decimals = usize::from(self.decimals);
+        if decimals == 0 {
+            return format!("{} {}", digits, self.symbol);
+        }
+        let padded = format!("{:0>width$}", digits, width = decimals + 1);
+        let (whole, fraction) = padded.split_at(padded.len() - decimals);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            format!("{} {}", whole, self.symbol)
+        } else {
+            format!("{}.{} {}", whole, fraction, self.symbol)
+        }
+    }
+
+    /// Rewrite balance fields (`free: 1000000000000`) in `text` as token amounts.
+    pub fn humanize(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(colon) = rest.find(':') {
+            let (before, after) = rest.split_at(colon);
+            let after = &after[1..];
+            out.push_str(before);
+            out.push(':');
+
+            let key = before
+                .rsplit(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .next()
+                .unwrap_or_default();
+            let value = after.trim_start_matches(' ');
+            let len = value
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(value.len());
+            let whole_number = !value[len..].starts_with(|c: char| c.is_alphanumeric() || c == '.');
+            if len > 0 && whole_number && is_balance_key(key) {
+                if let Ok(planck) = value[..len].parse::<u128>() {
+                    out.push_str(&after[..after.len() - value.len()]);
+                    out.push_str(&self.format(planck));
+                    rest = &value[len..];
+                    continue;
+                }
+            }
+            rest = after;
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+fn is_balance_key(key: &str) -> bool {
+    let key = key.replace('_', "").to_ascii_lowercase();
+    !key.is_empty()
+        && (BALANCE_KEYS.contains(&key.as_str())
+            || BALANCE_SUFFIXES.iter().any(|suffix| key.ends_with(suffix)))
+}
+
+/// Per-network display preferences, falling back to the chain's own token.
+#[derive(Debug, Default)]
+pub struct TokenDisplays {
+    /// `None` means balances on that network are shown raw.
+    formats: Mutex<HashMap<String, Option<TokenFormat>>>,
+}
+
+impl TokenDisplays {
+    /// Create an empty set of preferences.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Token format for `url`, fetched once from the chain when no preference is set.
+    /// Chains that can't be reached are retried on the next call.
+    pub async fn get(&self, pool: &RpcPool, url: &str) -> Option<TokenFormat> {
+        if let Some(format) = self.formats.lock().await.get(url) {
+            return format.clone();
+        }
+        let format = TokenFormat::fetch(pool, url).await.ok()?;
+        self.formats
+            .lock()
+            .await
+            .insert(url.to_owned(), format.clone());
+        format
+    }
+
+    /// Set the preference for `url`; `None` shows raw planck values.
+    pub async fn set(&self, url: &str, format: Option<TokenFormat>) {
+        self.formats.lock().await.insert(url.to_owned(), format);
+    }
+
+    /// Humanize the text content of `result` for the network at `url`.
+    pub async fn apply(
+        &self,
+        pool: &RpcPool,
+        url: Option<&str>,
+        result: CallToolResult,
+    ) -> CallToolResult {
+        let Some(url) = url else {
+            return result;
+        };
+        let Some(format) = self.get(pool, url).await else {
+            return result;
+        };
+        let content = result
+            .content
+            .into_iter()
+            .map(|content| match &content.raw {
+                RawContent::Text(text) => Content::text(format.humanize(&text.text)),
+                _ => content,
+            })
+            .collect();
+        CallToolResult { content, ..result }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit() -> TokenFormat {
+        TokenFormat {
+            symbol: "UNIT".to_owned(),
+            decimals: 12,
+        }
+    }
+
+    #[test]
+    fn format_is_exact() {
+        assert_eq!(unit().format(1_500_000_000_000), "1.5 UNIT");
+        assert_eq!(unit().format(2_000_000_000_000), "2 UNIT");
+        assert_eq!(unit().format(1), "0.000000000001 UNIT");
+        assert_eq!(unit().format(0), "0 UNIT");
+    }
+
+    #[test]
+    fn from_properties_reads_native_token() {
+        let single = serde_json::json!({"tokenSymbol": "UNIT", "tokenDecimals": 12});
+        assert_eq!(TokenFormat::from_properties(&single), Some(unit()));
+        let multi = serde_json::json!({"tokenSymbol": ["UNIT", "USDT"], "tokenDecimals": [12, 6]});
+        assert_eq!(TokenFormat::from_properties(&multi), Some(unit()));
+        assert_eq!(TokenFormat::from_properties(&serde_json::json!({})), None);
+    }
+
+    #[test]
+    fn humanize_rewrites_only_balance_fields() {
+        let text = "AccountInfo { nonce: 3, data: { free: 1500000000000, reserved: 0, \
+                    frozen: 12.5 } }\nCollatorSelection.CandidacyBond: 16000000000000\n\
+                    url: ws://localhost:9944";
+        assert_eq!(
+            unit().humanize(text),
+            "AccountInfo { nonce: 3, data: { free: 1.5 UNIT, reserved: 0 UNIT, \
+             frozen: 12.5 } }\nCollatorSelection.CandidacyBond: 16 UNIT\n\
+             url: ws://localhost:9944"
+        );
+    }
+}
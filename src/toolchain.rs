@@ -0,0 +1,184 @@
+//! Rust toolchain selection for build and test commands.
+//!
+//! Pop CLI runs `cargo` from the server's working directory, so rustup never sees the
+//! `rust-toolchain.toml` of the project being built, and a `RUSTUP_TOOLCHAIN` inherited
+//! by the server (e.g. from `cargo run`) would override it anyway. The toolchain is
+//! therefore resolved here, from the `toolchain` tool param or the project's toolchain
+//! file, and passed to the command as `RUSTUP_TOOLCHAIN`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::error::{PopMcpError, PopMcpResult};
+
+/// Env var rustup reads the toolchain from.
+pub const TOOLCHAIN_ENV: &str = "RUSTUP_TOOLCHAIN";
+
+/// Toolchain files, in the order rustup reads them.
+const TOOLCHAIN_FILES: &[&str] = &["rust-toolchain", "rust-toolchain.toml"];
+
+/// Check that `name` is a plausible toolchain name (`stable`, `1.81`,
+/// `nightly-2024-09-05`, `stable-x86_64-unknown-linux-gnu`, ...).
+fn validate(name: &str) -> PopMcpResult<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with('-')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_'));
+    if valid {
+        Ok(())
+    } else {
+        Err(PopMcpError::InvalidInput(format!(
+            "Invalid toolchain name: '{}'",
+            name
+        )))
+    }
+}
+
+/// Channel named by the contents of a toolchain file.
+///
+/// Handles the TOML format (`[toolchain] channel = "..."`) and the legacy one-line
+/// format of `rust-toolchain`. Files pinning only components or a custom `path` name
+/// no channel.
+fn channel(contents: &str) -> Option<String> {
+    if let Ok(file) = toml::from_str::<toml::Table>(contents) {
+        return file
+            .get("toolchain")?
+            .get("channel")?
+            .as_str()
+            .map(str::to_owned);
+    }
+    let line = contents.trim();
+    (!line.is_empty() && !line.contains(char::is_whitespace)).then(|| line.to_owned())
+}
+
+/// Toolchain pinned by the nearest toolchain file in `path` or one of its parents.
+pub fn project_toolchain(path: &Path) -> PopMcpResult<Option<String>> {
+    let start = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    for dir in start.ancestors() {
+        for name in TOOLCHAIN_FILES {
+            let file = dir.join(name);
+            let contents = match std::fs::read_to_string(&file) {
+                Ok(contents) => contents,
+                Err(_) => continue,
+            };
+            let Some(channel) = channel(&contents) else {
+                return Ok(None);
+            };
+            validate(&channel).map_err(|_| {
+                PopMcpError::InvalidInput(format!(
+                    "{} names an invalid toolchain: '{}'",
+                    file.display(),
+                    channel
+                ))
+            })?;
+            return Ok(Some(channel));
+        }
+    }
+    Ok(None)
+}
+
+/// The per-call `env` of a build or test of the project at `path`, with
+/// `RUSTUP_TOOLCHAIN` set to the toolchain to use.
+///
+/// An explicit `toolchain` wins over the project's toolchain file; a
+/// `RUSTUP_TOOLCHAIN` given in `env` is kept as is, but must agree with `toolchain`.
+pub fn call_env(
+    path: &str,
+    toolchain: Option<&str>,
+    env: Option<&BTreeMap<String, String>>,
+) -> PopMcpResult<Option<BTreeMap<String, String>>> {
+    let from_env = env.and_then(|env| env.get(TOOLCHAIN_ENV));
+    let toolchain = match (toolchain.map(str::trim), from_env) {
+        (Some(toolchain), Some(from_env)) if toolchain != from_env => {
+            return Err(PopMcpError::InvalidInput(format!(
+                "toolchain '{}' conflicts with {}='{}' in env",
+                toolchain, TOOLCHAIN_ENV, from_env
+            )))
+        }
+        (_, Some(_)) => return Ok(env.cloned()),
+        (Some(toolchain), None) => {
+            validate(toolchain)?;
+            Some(toolchain.to_owned())
+        }
+        (None, None) => project_toolchain(Path::new(path))?,
+    };
+    let Some(toolchain) = toolchain else {
+        return Ok(env.cloned());
+    };
+    let mut env = env.cloned().unwrap_or_default();
+    env.insert(TOOLCHAIN_ENV.to_owned(), toolchain);
+    Ok(Some(env))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toolchain_of(env: Option<BTreeMap<String, String>>) -> Option<String> {
+        env?.get(TOOLCHAIN_ENV).cloned()
+    }
+
+    #[test]
+    fn toolchain_files_are_read_from_the_project_or_its_parents() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let contract = dir.path().join("contracts/flipper");
+        std::fs::create_dir_all(&contract)?;
+        assert_eq!(project_toolchain(&contract)?, None);
+
+        std::fs::write(
+            dir.path().join("rust-toolchain.toml"),
+            "[toolchain]\nchannel = \"nightly-2024-09-05\"\ncomponents = [\"rust-src\"]\n",
+        )?;
+        assert_eq!(
+            project_toolchain(&contract)?.as_deref(),
+            Some("nightly-2024-09-05")
+        );
+
+        std::fs::write(contract.join("rust-toolchain"), "1.81\n")?;
+        assert_eq!(project_toolchain(&contract)?.as_deref(), Some("1.81"));
+
+        std::fs::write(
+            contract.join("rust-toolchain"),
+            "[toolchain]\ncomponents = [\"clippy\"]\n",
+        )?;
+        assert_eq!(project_toolchain(&contract)?, None);
+
+        std::fs::write(
+            contract.join("rust-toolchain"),
+            "[toolchain]\nchannel = \"$(id)\"\n",
+        )?;
+        assert!(project_toolchain(&contract).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn explicit_toolchain_wins_over_the_project_file() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("rust-toolchain"), "nightly\n")?;
+        let path = dir.path().to_string_lossy();
+
+        assert_eq!(
+            toolchain_of(call_env(&path, None, None)?).as_deref(),
+            Some("nightly")
+        );
+        let env = BTreeMap::from([("RUST_LOG".to_owned(), "debug".to_owned())]);
+        let merged = call_env(&path, Some("1.81"), Some(&env))?;
+        assert_eq!(toolchain_of(merged.clone()).as_deref(), Some("1.81"));
+        assert_eq!(
+            merged
+                .and_then(|env| env.get("RUST_LOG").cloned())
+                .as_deref(),
+            Some("debug")
+        );
+
+        let pinned = BTreeMap::from([(TOOLCHAIN_ENV.to_owned(), "stable".to_owned())]);
+        assert_eq!(
+            toolchain_of(call_env(&path, None, Some(&pinned))?).as_deref(),
+            Some("stable")
+        );
+        assert!(call_env(&path, Some("nightly"), Some(&pinned)).is_err());
+        assert!(call_env(&path, Some("nightly; rm -rf /"), None).is_err());
+        Ok(())
+    }
+}
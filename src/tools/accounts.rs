@@ -0,0 +1,157 @@
+//! Deterministic test accounts derived from the dev phrase
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::accounts::{self, Account, DEFAULT_SS58_PREFIX};
+use crate::encoder::to_hex;
+use crate::error::{PopMcpError, PopMcpResult};
+
+use super::common::{error_result, success_result};
+
+/// Most accounts derived in one call.
+const MAX_ACCOUNTS: u32 = 1000;
+
+/// Highest valid SS58 prefix.
+const MAX_SS58_PREFIX: u16 = 16383;
+
+/// Default base path of derived accounts.
+const DEFAULT_BASE_PATH: &str = "//Test";
+
+/// Parameters for the derive_test_accounts tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct DeriveTestAccountsParams {
+    /// Number of accounts to derive.
+    #[schemars(description = "Number of accounts to derive (at most 1000)")]
+    pub count: u32,
+    /// Base derivation path; account `i` is `<base_path>//<i>`.
+    #[schemars(
+        description = "Base derivation path from the dev phrase; account i is <base_path>//<i> (default: //Test)"
+    )]
+    pub base_path: Option<String>,
+    /// Index of the first account.
+    #[schemars(description = "Index of the first account (default: 0)")]
+    pub start: Option<u32>,
+    /// SS58 prefix of the returned addresses.
+    #[schemars(description = "SS58 address prefix (default: 42, generic Substrate)")]
+    pub ss58_prefix: Option<u16>,
+}
+
+impl DeriveTestAccountsParams {
+    /// Validate the parameters
+    fn validate(&self) -> Result<(), String> {
+        if self.count == 0 || self.count > MAX_ACCOUNTS {
+            return Err(format!("'count' must be between 1 and {}", MAX_ACCOUNTS));
+        }
+        if self
+            .start
+            .unwrap_or(0)
+            .checked_add(self.count - 1)
+            .is_none()
+        {
+            return Err("'start' + 'count' exceeds the index range".to_owned());
+        }
+        if self.ss58_prefix.is_some_and(|p| p > MAX_SS58_PREFIX) {
+            return Err(format!("'ss58_prefix' must be at most {}", MAX_SS58_PREFIX));
+        }
+        Ok(())
+    }
+}
+
+/// Derive the accounts described by `params`.
+fn derive(params: &DeriveTestAccountsParams) -> Result<Vec<Account>, String> {
+    let base = params.base_path.as_deref().unwrap_or(DEFAULT_BASE_PATH);
+    let prefix = params.ss58_prefix.unwrap_or(DEFAULT_SS58_PREFIX);
+    let start = params.start.unwrap_or(0);
+    (start..start + params.count)
+        .map(|index| {
+            let suri = format!("{}//{}", base.trim_end_matches('/'), index);
+            let public_key = accounts::derive_dev_public_key(&suri)?;
+            Ok(Account {
+                name: suri.trim_start_matches('/').to_owned(),
+                address: accounts::ss58_address(&public_key, prefix),
+                public_key: to_hex(&public_key),
+                suri,
+            })
+        })
+        .collect()
+}
+
+/// Execute derive_test_accounts tool
+pub async fn derive_test_accounts(
+    params: DeriveTestAccountsParams,
+) -> PopMcpResult<CallToolResult> {
+    params.validate().map_err(PopMcpError::InvalidInput)?;
+
+    let derived = match derive(&params) {
+        Ok(derived) => derived,
+        Err(e) => return Ok(error_result(format!("Failed to derive accounts: {}", e))),
+    };
+    accounts::register(&derived);
+
+    let lines: Vec<String> = derived
+        .iter()
+        .map(|account| format!("{}  {}", account.suri, account.address))
+        .collect();
+    Ok(success_result(format!(
+        "Derived {} dev accounts (sign with --suri <SURI>; fund them from //Alice on local networks):\n\n{}\n\nRegistered in the address book ({}).",
+        derived.len(),
+        lines.join("\n"),
+        accounts::ADDRESS_BOOK_URI
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(count: u32) -> DeriveTestAccountsParams {
+        DeriveTestAccountsParams {
+            count,
+            base_path: None,
+            start: None,
+            ss58_prefix: None,
+        }
+    }
+
+    #[test]
+    fn validate_rejects_out_of_range_counts() {
+        assert!(params(0).validate().is_err());
+        assert!(params(MAX_ACCOUNTS + 1).validate().is_err());
+        let overflow = DeriveTestAccountsParams {
+            start: Some(u32::MAX),
+            ..params(2)
+        };
+        assert!(overflow.validate().is_err());
+    }
+
+    #[test]
+    fn derive_is_reproducible_and_sequential() -> Result<(), String> {
+        let first = derive(&params(3))?;
+        let suris: Vec<&str> = first.iter().map(|a| a.suri.as_str()).collect();
+        assert_eq!(suris, ["//Test//0", "//Test//1", "//Test//2"]);
+        assert_eq!(first[0].name, "Test//0");
+        assert_eq!(derive(&params(3))?, first);
+
+        let shifted = derive(&DeriveTestAccountsParams {
+            start: Some(1),
+            ..params(1)
+        })?;
+        assert_eq!(shifted[0].address, first[1].address);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn derive_test_accounts_registers_accounts() {
+        let result = derive_test_accounts(DeriveTestAccountsParams {
+            base_path: Some("//Register".to_owned()),
+            ..params(2)
+        })
+        .await;
+        assert!(result.is_ok_and(|r| r.is_error != Some(true)));
+        let book = accounts::address_book();
+        assert!(book.iter().any(|a| a.suri == "//Register//1"));
+    }
+}
@@ -0,0 +1,160 @@
+//! Build cache for build_contract
+//!
+//! A successful build records a hash of the contract's sources and build settings in
+//! `target/.pop-mcp-build`. When the next build has the same hash and the artifacts
+//! in `target/ink` are newer than every source file, pop is not run again.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::encoder::{blake2_256, to_hex};
+use crate::outputs::BuildOutput;
+
+/// File recording the source hash of the last successful build, under `target`.
+const CACHE_FILE: &str = "target/.pop-mcp-build";
+
+/// Files whose contents decide the build output: Rust sources, manifests, lock files
+/// and toolchain pins, skipping build output and hidden directories.
+fn sources(dir: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let path = entry.path();
+            if path.is_dir() {
+                if !name.starts_with('.') && name != "target" && name != "node_modules" {
+                    pending.push(path);
+                }
+            } else if name.ends_with(".rs")
+                || matches!(
+                    name.as_ref(),
+                    "Cargo.toml" | "Cargo.lock" | "rust-toolchain.toml" | "rust-toolchain"
+                )
+            {
+                found.push(path);
+            }
+        }
+    }
+    found.sort();
+    found
+}
+
+/// Hash of the sources of the project at `path` and the build `settings` (profile,
+/// toolchain, environment), or `None` when a source can't be read.
+pub(crate) fn source_hash(path: &str, settings: &str) -> Option<String> {
+    let root = Path::new(path);
+    let mut data = settings.as_bytes().to_vec();
+    for file in sources(root) {
+        let relative = file.strip_prefix(root).unwrap_or(&file);
+        let contents = std::fs::read(&file).ok()?;
+        data.push(0);
+        data.extend_from_slice(relative.to_string_lossy().as_bytes());
+        data.push(0);
+        data.extend_from_slice(&(contents.len() as u64).to_le_bytes());
+        data.extend_from_slice(&contents);
+    }
+    Some(to_hex(&blake2_256(&data)))
+}
+
+/// Modification time of the newest source of the project at `path`.
+fn newest_source(path: &str) -> Option<SystemTime> {
+    sources(Path::new(path))
+        .iter()
+        .filter_map(|file| std::fs::metadata(file).and_then(|m| m.modified()).ok())
+        .max()
+}
+
+/// The artifacts of the last build of the project at `path` if it had `hash` and its
+/// artifacts are newer than every source.
+pub(crate) fn hit(path: &str, hash: &str, release: bool) -> Option<BuildOutput> {
+    let recorded = std::fs::read_to_string(Path::new(path).join(CACHE_FILE)).ok()?;
+    if recorded.trim() != hash {
+        return None;
+    }
+    let build = BuildOutput::collect(path, release);
+    if build.blob.is_none() && build.bundle.is_none() {
+        return None;
+    }
+    let oldest = [&build.bundle, &build.metadata, &build.blob]
+        .into_iter()
+        .flatten()
+        .map(|file| {
+            std::fs::metadata(&file.path)
+                .and_then(|m| m.modified())
+                .ok()
+        })
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .min()?;
+    (oldest >= newest_source(path)?).then_some(build)
+}
+
+/// Record `hash` as the source hash of the project at `path` after a successful build.
+pub(crate) fn record(path: &str, hash: &str) {
+    let file = Path::new(path).join(CACHE_FILE);
+    if let Some(parent) = file.parent() {
+        if std::fs::create_dir_all(parent).is_ok() {
+            let _ = std::fs::write(file, hash);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_covers_sources_and_settings_only() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().display().to_string();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"flipper\"\n",
+        )?;
+        std::fs::write(dir.path().join("lib.rs"), "mod flipper {}")?;
+        let hash = source_hash(&path, "release");
+        assert!(hash.is_some());
+        assert_ne!(hash, source_hash(&path, "debug"));
+
+        std::fs::create_dir_all(dir.path().join("target/ink"))?;
+        std::fs::write(dir.path().join("target/ink/flipper.polkavm"), "abc")?;
+        std::fs::write(dir.path().join("README.md"), "docs")?;
+        assert_eq!(hash, source_hash(&path, "release"));
+
+        std::fs::write(dir.path().join("Cargo.lock"), "version = 4")?;
+        assert_ne!(hash, source_hash(&path, "release"));
+        Ok(())
+    }
+
+    #[test]
+    fn hits_need_the_recorded_hash_and_fresh_artifacts() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().display().to_string();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"flipper\"\n",
+        )?;
+        assert!(hit(&path, "0x01", false).is_none());
+
+        std::fs::create_dir_all(dir.path().join("target/ink"))?;
+        std::fs::write(dir.path().join("target/ink/flipper.polkavm"), "abc")?;
+        record(&path, "0x01");
+        assert!(hit(&path, "0x02", false).is_none());
+        let build = hit(&path, "0x01", true);
+        assert!(build.is_some_and(|build| build.release && build.blob.is_some()));
+
+        // A source touched after the build is newer than the artifacts.
+        let later = SystemTime::now() + std::time::Duration::from_secs(60);
+        std::fs::File::options()
+            .write(true)
+            .open(dir.path().join("Cargo.toml"))?
+            .set_modified(later)?;
+        assert!(hit(&path, "0x01", false).is_none());
+        Ok(())
+    }
+}
@@ -1,12 +1,26 @@
 //! Chain build (pop build)
 
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
 use rmcp::model::CallToolResult;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::error::PopMcpResult;
-use crate::executor::PopExecutor;
+use crate::diagnostics::with_compile_errors;
+use crate::encoder::{blake2_256, to_hex};
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::toolchain;
 use crate::tools::common::{error_result, success_result};
+use crate::tools::metadata::manifests;
+
+/// What build_chain can build: the whole project, only the runtime, or only the
+/// runtime with `runtime-benchmarks` enabled.
+pub const BUILD_MODES: &[&str] = &["node", "runtime", "benchmark"];
+
+/// Feature enabling the benchmarks of a runtime.
+const BENCHMARKS_FEATURE: &str = "runtime-benchmarks";
 
 /// Parameters for the build_chain tool.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
@@ -18,6 +32,36 @@ pub struct BuildChainParams {
     /// Whether to build in release mode (default: false).
     #[schemars(description = "Build in release mode with optimizations (default: false)")]
     pub release: Option<bool>,
+    /// Kill the command after this many seconds (default: no timeout).
+    #[schemars(
+        description = "Kill the command after this many seconds and return its partial output (default: no timeout)"
+    )]
+    pub timeout_secs: Option<u64>,
+    /// Environment variables set for this command only.
+    #[schemars(
+        description = "Environment variables to set for this command only, e.g. {\"RUST_LOG\": \"debug\", \"CARGO_TARGET_DIR\": \"/tmp/target\"}"
+    )]
+    pub env: Option<BTreeMap<String, String>>,
+    /// Rust toolchain to build with (default: the project's rust-toolchain.toml).
+    #[schemars(
+        description = "Rust toolchain to use, e.g. 'nightly-2024-09-05' or '1.81' (default: the one pinned by the project's rust-toolchain.toml, if any)"
+    )]
+    pub toolchain: Option<String>,
+    /// Cargo features to enable.
+    #[schemars(
+        description = "Cargo features to enable, e.g. ['runtime-benchmarks'] or ['try-runtime'] (pop build --features)"
+    )]
+    pub features: Option<Vec<String>>,
+    /// Cargo profile to build with.
+    #[schemars(
+        description = "Cargo profile to build with, e.g. 'production' (pop build --profile; default: debug, or release with release: true)"
+    )]
+    pub profile: Option<String>,
+    /// What to build (default: node).
+    #[schemars(
+        description = "What to build: 'node' (the whole project, default), 'runtime' (only the runtime package and its wasm) or 'benchmark' (only the runtime, with runtime-benchmarks, for weight generation)"
+    )]
+    pub mode: Option<String>,
 }
 
 impl BuildChainParams {
@@ -26,36 +70,175 @@ impl BuildChainParams {
         if self.path.is_empty() {
             return Err("Path cannot be empty".to_owned());
         }
+        for feature in self.features.iter().flatten() {
+            if feature.is_empty()
+                || feature.starts_with('-')
+                || feature.contains(|c: char| c == ',' || c.is_whitespace())
+            {
+                return Err(format!(
+                    "Invalid feature '{}': pass one feature name per entry",
+                    feature
+                ));
+            }
+        }
+        if !BUILD_MODES.contains(&self.mode()) {
+            return Err(format!(
+                "Invalid mode '{}'. Supported: {}",
+                self.mode(),
+                BUILD_MODES.join(", ")
+            ));
+        }
+        if let Some(profile) = &self.profile {
+            if profile.is_empty()
+                || !profile
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                return Err(format!("Invalid profile '{}'", profile));
+            }
+            if self.release == Some(true) && profile != "release" {
+                return Err(format!(
+                    "release builds use the release profile; drop release to build with profile '{}'",
+                    profile
+                ));
+            }
+        }
         Ok(())
     }
+
+    /// The build mode, `node` unless given.
+    fn mode(&self) -> &str {
+        self.mode.as_deref().unwrap_or("node")
+    }
+
+    /// Directory under the target directory the build profile writes to.
+    fn profile_dir(&self) -> &str {
+        match self.profile.as_deref() {
+            Some("dev") => "debug",
+            Some(profile) => profile,
+            None if self.release.unwrap_or(false) => "release",
+            None => "debug",
+        }
+    }
 }
 
-/// Build command arguments for build_chain
-fn build_build_chain_args(params: &BuildChainParams) -> Vec<&str> {
-    let mut args = vec!["build", "--path", params.path.as_str()];
+/// Name of the runtime package of the chain project at `path`: the first package,
+/// in path order, whose name ends in `runtime`.
+fn runtime_package(path: &str) -> Option<String> {
+    manifests(Path::new(path)).iter().find_map(|manifest| {
+        let doc: toml_edit::DocumentMut = std::fs::read_to_string(manifest).ok()?.parse().ok()?;
+        let name = doc.get("package")?.get("name")?.as_str()?;
+        name.ends_with("runtime").then(|| name.to_owned())
+    })
+}
+
+/// The compressed wasm of `runtime` built with `params`, under `CARGO_TARGET_DIR`
+/// when set in `params.env`, else the project's `target`.
+fn runtime_wasm(params: &BuildChainParams, runtime: &str) -> Option<PathBuf> {
+    let target = params
+        .env
+        .as_ref()
+        .and_then(|env| env.get("CARGO_TARGET_DIR"))
+        .map_or_else(|| Path::new(&params.path).join("target"), PathBuf::from);
+    let dir = target
+        .join(params.profile_dir())
+        .join("wbuild")
+        .join(runtime);
+    let stem = runtime.replace('-', "_");
+    ["compact.compressed.wasm", "compact.wasm", "wasm"]
+        .iter()
+        .map(|extension| dir.join(format!("{}.{}", stem, extension)))
+        .find(|file| file.is_file())
+}
+
+/// Path, size and code hash (blake2-256) of the runtime wasm at `wasm`.
+fn wasm_report(wasm: &Path) -> Option<String> {
+    let code = std::fs::read(wasm).ok()?;
+    Some(format!(
+        "Runtime wasm: {} ({} bytes)\nCode hash: {}",
+        wasm.display(),
+        code.len(),
+        to_hex(&blake2_256(&code))
+    ))
+}
+
+/// Build command arguments for build_chain; `runtime` limits the build to that
+/// package.
+fn build_build_chain_args(params: &BuildChainParams, runtime: Option<&str>) -> Vec<String> {
+    let mut args = vec!["build".to_owned(), "--path".to_owned(), params.path.clone()];
+    if let Some(runtime) = runtime {
+        args.push("--package".to_owned());
+        args.push(runtime.to_owned());
+    }
 
     // Default to debug mode for chains
-    if params.release.unwrap_or(false) {
-        args.push("--release");
+    if let Some(profile) = &params.profile {
+        args.push("--profile".to_owned());
+        args.push(profile.clone());
+    } else if params.release.unwrap_or(false) {
+        args.push("--release".to_owned());
+    }
+    let mut features = params.features.clone().unwrap_or_default();
+    if params.mode() == "benchmark" && !features.iter().any(|f| f == BENCHMARKS_FEATURE) {
+        features.push(BENCHMARKS_FEATURE.to_owned());
+    }
+    if !features.is_empty() {
+        args.push("--features".to_owned());
+        args.push(features.join(","));
     }
 
     args
 }
 
 /// Execute build_chain tool
-pub fn build_chain(
-    executor: &PopExecutor,
+pub async fn build_chain<E: CommandExecutor>(
+    executor: &E,
     params: BuildChainParams,
 ) -> PopMcpResult<CallToolResult> {
-    params
-        .validate()
-        .map_err(crate::error::PopMcpError::InvalidInput)?;
+    params.validate().map_err(PopMcpError::InvalidInput)?;
 
-    let args = build_build_chain_args(&params);
+    let runtime = runtime_package(&params.path);
+    let only_runtime = params.mode() != "node";
+    if only_runtime && runtime.is_none() {
+        return Err(PopMcpError::InvalidInput(format!(
+            "No runtime package (a package named *runtime) found in {}",
+            params.path
+        )));
+    }
+    let args = build_build_chain_args(&params, runtime.as_deref().filter(|_| only_runtime));
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
 
-    match executor.execute(&args) {
-        Ok(_output) => Ok(success_result("Chain build successful!")),
-        Err(e) => Ok(error_result(format!("Chain build failed: {}", e))),
+    let env = toolchain::call_env(
+        &params.path,
+        params.toolchain.as_deref(),
+        params.env.as_ref(),
+    )?;
+    match executor
+        .with_timeout_secs(params.timeout_secs)?
+        .with_env_vars(env.as_ref())?
+        .execute(&args)
+        .await
+    {
+        Ok(_output) => {
+            let wasm = runtime
+                .as_deref()
+                .and_then(|runtime| runtime_wasm(&params, runtime))
+                .and_then(|wasm| wasm_report(&wasm));
+            let heading = match params.mode() {
+                "runtime" => "Runtime build successful!",
+                "benchmark" => "Runtime build with runtime-benchmarks successful!",
+                _ => "Chain build successful!",
+            };
+            Ok(success_result(match wasm {
+                Some(wasm) => format!("{}\n{}", heading, wasm),
+                None => heading.to_owned(),
+            }))
+        }
+        Err(e) => {
+            let output = e.to_string();
+            let result = error_result(format!("Chain build failed: {}", output));
+            Ok(with_compile_errors(result, "Chain build failed", &output))
+        }
     }
 }
 
@@ -68,6 +251,12 @@ mod tests {
         let params = BuildChainParams {
             path: String::new(),
             release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            features: None,
+            profile: None,
+            mode: None,
         };
         assert!(params.validate().is_err());
     }
@@ -77,6 +266,12 @@ mod tests {
         let params = BuildChainParams {
             path: "./my_chain".to_owned(),
             release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            features: None,
+            profile: None,
+            mode: None,
         };
         assert!(params.validate().is_ok());
     }
@@ -86,8 +281,14 @@ mod tests {
         let params = BuildChainParams {
             path: "./my_chain".to_owned(),
             release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            features: None,
+            profile: None,
+            mode: None,
         };
-        let args = build_build_chain_args(&params);
+        let args = build_build_chain_args(&params, None);
         assert_eq!(args, vec!["build", "--path", "./my_chain"]);
     }
 
@@ -96,8 +297,14 @@ mod tests {
         let params = BuildChainParams {
             path: "./my_chain".to_owned(),
             release: Some(true),
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            features: None,
+            profile: None,
+            mode: None,
         };
-        let args = build_build_chain_args(&params);
+        let args = build_build_chain_args(&params, None);
         assert_eq!(args, vec!["build", "--path", "./my_chain", "--release"]);
     }
 
@@ -106,8 +313,106 @@ mod tests {
         let params = BuildChainParams {
             path: "./my_chain".to_owned(),
             release: Some(false),
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            features: None,
+            profile: None,
+            mode: None,
         };
-        let args = build_build_chain_args(&params);
+        let args = build_build_chain_args(&params, None);
         assert_eq!(args, vec!["build", "--path", "./my_chain"]);
     }
+
+    #[test]
+    fn build_args_forward_features_and_profile() {
+        let params = BuildChainParams {
+            path: "./my_chain".to_owned(),
+            release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            features: Some(vec![
+                "runtime-benchmarks".to_owned(),
+                "try-runtime".to_owned(),
+            ]),
+            profile: Some("production".to_owned()),
+            mode: None,
+        };
+        assert!(params.validate().is_ok());
+        let args = build_build_chain_args(&params, None);
+        assert_eq!(
+            args,
+            vec![
+                "build",
+                "--path",
+                "./my_chain",
+                "--profile",
+                "production",
+                "--features",
+                "runtime-benchmarks,try-runtime"
+            ]
+        );
+
+        let conflicting = BuildChainParams {
+            release: Some(true),
+            ..params.clone()
+        };
+        assert!(conflicting.validate().is_err());
+        let joined = BuildChainParams {
+            features: Some(vec!["a,b".to_owned()]),
+            profile: None,
+            mode: None,
+            ..params
+        };
+        assert_eq!(
+            joined.validate(),
+            Err("Invalid feature 'a,b': pass one feature name per entry".to_owned())
+        );
+    }
+
+    #[test]
+    fn runtime_modes_need_a_runtime_package() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let params = BuildChainParams {
+            path: dir.path().display().to_string(),
+            release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            features: Some(vec![BENCHMARKS_FEATURE.to_owned()]),
+            profile: None,
+            mode: Some("benchmark".to_owned()),
+        };
+        assert_eq!(runtime_package(&params.path), None);
+        std::fs::create_dir_all(dir.path().join("node"))?;
+        std::fs::write(
+            dir.path().join("node/Cargo.toml"),
+            "[package]\nname = \"node\"\n",
+        )?;
+        std::fs::create_dir_all(dir.path().join("runtime"))?;
+        std::fs::write(
+            dir.path().join("runtime/Cargo.toml"),
+            "[package]\nname = \"my-runtime\"\n",
+        )?;
+        let runtime = runtime_package(&params.path);
+        assert_eq!(runtime.as_deref(), Some("my-runtime"));
+        // The benchmarks feature is not repeated.
+        assert_eq!(
+            build_build_chain_args(&params, runtime.as_deref())[3..],
+            [
+                "--package",
+                "my-runtime",
+                "--features",
+                "runtime-benchmarks"
+            ]
+        );
+        assert!(BuildChainParams {
+            mode: Some("wasm".to_owned()),
+            ..params
+        }
+        .validate()
+        .is_err_and(|e| e == "Invalid mode 'wasm'. Supported: node, runtime, benchmark"));
+        Ok(())
+    }
 }
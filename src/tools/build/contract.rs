@@ -1,12 +1,18 @@
 //! Contract build (pop build)
 
+use std::collections::BTreeMap;
+
 use rmcp::model::CallToolResult;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::diagnostics::{compile_errors, summary};
 use crate::error::PopMcpResult;
-use crate::executor::PopExecutor;
-use crate::tools::common::{error_result, success_result};
+use crate::executor::CommandExecutor;
+use crate::outputs::{ArtifactFile, BuildOutput};
+use crate::toolchain;
+use crate::tools::build::cache;
+use crate::tools::common::{error_result, success_result, with_structured, with_summary};
 
 /// Parameters for the build_contract tool.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
@@ -18,6 +24,31 @@ pub struct BuildContractParams {
     /// Whether to build in release mode.
     #[schemars(description = "Build in release mode with optimizations")]
     pub release: Option<bool>,
+    /// Kill the command after this many seconds (default: no timeout).
+    #[schemars(
+        description = "Kill the command after this many seconds and return its partial output (default: no timeout)"
+    )]
+    pub timeout_secs: Option<u64>,
+    /// Environment variables set for this command only.
+    #[schemars(
+        description = "Environment variables to set for this command only, e.g. {\"RUST_LOG\": \"debug\", \"CARGO_TARGET_DIR\": \"/tmp/target\"}"
+    )]
+    pub env: Option<BTreeMap<String, String>>,
+    /// Rust toolchain to build with (default: the project's rust-toolchain.toml).
+    #[schemars(
+        description = "Rust toolchain to use, e.g. 'nightly-2024-09-05' or '1.81' (default: the one pinned by the project's rust-toolchain.toml, if any)"
+    )]
+    pub toolchain: Option<String>,
+    /// Whether to run a verifiable (deterministic, Docker-based) build.
+    #[schemars(
+        description = "Run pop's verifiable build: a deterministic release build inside a pinned Docker image, so the resulting code hash can later be checked against the source. Requires Docker"
+    )]
+    pub verifiable: Option<bool>,
+    /// Build even if the sources are unchanged since the last build.
+    #[schemars(
+        description = "Run pop even when the sources (.rs files, Cargo.toml, Cargo.lock) and build settings are unchanged since the last successful build and its artifacts are up to date (default: false, reuse them)"
+    )]
+    pub force: Option<bool>,
 }
 
 impl BuildContractParams {
@@ -26,47 +57,156 @@ impl BuildContractParams {
         if self.path.is_empty() {
             return Err("Path cannot be empty".to_owned());
         }
+        if self.verifiable == Some(true) && self.toolchain.is_some() {
+            return Err(
+                "toolchain cannot be set for a verifiable build: the Docker image pins it"
+                    .to_owned(),
+            );
+        }
         Ok(())
     }
+
+    /// Build settings that change the output, hashed with the sources for the cache.
+    fn settings(&self) -> String {
+        format!(
+            "release={} verifiable={} toolchain={:?} env={:?}",
+            self.release.unwrap_or(false),
+            self.verifiable == Some(true),
+            self.toolchain,
+            self.env
+        )
+    }
 }
 
 /// Build command arguments for build_contract
 fn build_build_contract_args(params: &BuildContractParams) -> Vec<&str> {
     let mut args = vec!["build", "--path", params.path.as_str()];
 
-    if params.release.unwrap_or(false) {
+    if params.verifiable == Some(true) {
+        args.push("--verifiable");
+    } else if params.release.unwrap_or(false) {
         args.push("--release");
     }
 
     args
 }
 
+/// `heading` followed by the paths and sizes of the artifacts of `build` and its code
+/// hash.
+fn report(heading: &str, build: &BuildOutput) -> String {
+    let mut lines = vec![heading.to_owned()];
+    for (label, file) in [
+        ("Bundle", &build.bundle),
+        ("Metadata", &build.metadata),
+        ("Code", &build.blob),
+    ] {
+        if let Some(ArtifactFile { path, size }) = file {
+            lines.push(format!("{}: {} ({} bytes)", label, path, size));
+        }
+    }
+    match &build.code_hash {
+        Some(hash) => lines.push(format!("Code hash: {}", hash)),
+        None if build.verifiable => {
+            lines.push("No code hash found in the contract metadata.".to_owned());
+        }
+        None => {}
+    }
+    lines.join("\n")
+}
+
 /// Execute build_contract tool
-pub fn build_contract(
-    executor: &PopExecutor,
+pub async fn build_contract<E: CommandExecutor>(
+    executor: &E,
     params: BuildContractParams,
 ) -> PopMcpResult<CallToolResult> {
     params
         .validate()
         .map_err(crate::error::PopMcpError::InvalidInput)?;
 
+    let release = params.release.unwrap_or(false) || params.verifiable == Some(true);
+    let hash = cache::source_hash(&params.path, &params.settings());
+    if let (Some(hash), false) = (&hash, params.force.unwrap_or(false)) {
+        if let Some(mut build) = cache::hit(&params.path, hash, release) {
+            build.verifiable = params.verifiable == Some(true);
+            build.cached = true;
+            let heading = "Build skipped: sources unchanged since the last build (cache hit; pass force to rebuild)";
+            return Ok(with_structured(
+                success_result(report(heading, &build)),
+                &build,
+            ));
+        }
+    }
+
     let args = build_build_contract_args(&params);
 
-    match executor.execute(&args) {
-        Ok(_output) => Ok(success_result("Build successful!")),
-        Err(e) => Ok(error_result(format!("Build failed: {}", e))),
+    let env = toolchain::call_env(
+        &params.path,
+        params.toolchain.as_deref(),
+        params.env.as_ref(),
+    )?;
+    match executor
+        .with_timeout_secs(params.timeout_secs)?
+        .with_env_vars(env.as_ref())?
+        .execute(&args)
+        .await
+    {
+        Ok(_output) => {
+            if let Some(hash) = &hash {
+                cache::record(&params.path, hash);
+            }
+            let mut build = BuildOutput::collect(&params.path, release);
+            build.verifiable = params.verifiable == Some(true);
+            let heading = if build.verifiable {
+                "Verifiable build successful!"
+            } else {
+                "Build successful!"
+            };
+            Ok(with_structured(
+                success_result(report(heading, &build)),
+                &build,
+            ))
+        }
+        Err(e) => {
+            let output = e.to_string();
+            let result = error_result(format!("Build failed: {}", output));
+            let errors = compile_errors(&output);
+            if errors.is_empty() {
+                return Ok(result);
+            }
+            let build = BuildOutput {
+                path: params.path.clone(),
+                release: params.release.unwrap_or(false),
+                verifiable: params.verifiable == Some(true),
+                cached: false,
+                artifacts: Vec::new(),
+                bundle: None,
+                metadata: None,
+                blob: None,
+                code_hash: None,
+                errors,
+            };
+            let result = with_summary(result, summary("Build failed", &build.errors));
+            Ok(with_structured(result, &build))
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::MockExecutor;
+    use crate::tools::common::extract_texts;
 
     #[test]
     fn validate_rejects_empty_path() {
         let params = BuildContractParams {
             path: String::new(),
             release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
         };
         assert!(params.validate().is_err());
     }
@@ -76,8 +216,161 @@ mod tests {
         let params = BuildContractParams {
             path: "./my_contract".to_owned(),
             release: Some(true),
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
         };
         let args = build_build_contract_args(&params);
         assert_eq!(args, vec!["build", "--path", "./my_contract", "--release"]);
     }
+
+    #[tokio::test]
+    async fn verifiable_builds_report_the_code_hash() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::create_dir_all(dir.path().join("target/ink"))?;
+        std::fs::write(
+            dir.path().join("target/ink/flipper.json"),
+            r#"{"source": {"hash": "0xc0de"}}"#,
+        )?;
+        let executor = MockExecutor::new().on(&["build"], "Build completed");
+        let params = BuildContractParams {
+            path: dir.path().to_string_lossy().into_owned(),
+            release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: Some(true),
+            force: None,
+        };
+        let result = build_contract(&executor, params.clone()).await?;
+        assert!(extract_texts(&result)[0].starts_with("Verifiable build successful!\nMetadata: "));
+        assert!(extract_texts(&result)[0].ends_with("flipper.json (30 bytes)\nCode hash: 0xc0de"));
+        let build: BuildOutput =
+            serde_json::from_value(result.structured_content.unwrap_or_default())?;
+        assert!(build.verifiable && build.release);
+        assert_eq!(build.code_hash.as_deref(), Some("0xc0de"));
+        assert_eq!(executor.calls()[0].args[3], "--verifiable");
+
+        let pinned = BuildContractParams {
+            toolchain: Some("1.81".to_owned()),
+            ..params
+        };
+        assert!(pinned.validate().is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unchanged_sources_skip_the_build_unless_forced() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"flipper\"\n",
+        )?;
+        std::fs::write(dir.path().join("lib.rs"), "mod flipper {}")?;
+        std::fs::create_dir_all(dir.path().join("target/ink"))?;
+        std::fs::write(dir.path().join("target/ink/flipper.polkavm"), "abc")?;
+        let executor = MockExecutor::new().on(&["build"], "Build completed");
+        let params = BuildContractParams {
+            path: dir.path().to_string_lossy().into_owned(),
+            release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
+        };
+        build_contract(&executor, params.clone()).await?;
+        let result = build_contract(&executor, params.clone()).await?;
+        assert!(extract_texts(&result)[0].starts_with("Build skipped: sources unchanged"));
+        let build: BuildOutput =
+            serde_json::from_value(result.structured_content.unwrap_or_default())?;
+        assert!(build.cached && build.blob.is_some());
+        assert_eq!(executor.calls().len(), 1);
+
+        // Other settings or force run pop again.
+        let release = BuildContractParams {
+            release: Some(true),
+            ..params.clone()
+        };
+        build_contract(&executor, release).await?;
+        let forced = BuildContractParams {
+            force: Some(true),
+            ..params
+        };
+        let result = build_contract(&executor, forced).await?;
+        assert!(extract_texts(&result)[0].starts_with("Build successful!"));
+        assert_eq!(executor.calls().len(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn failed_builds_summarize_compiler_errors() -> anyhow::Result<()> {
+        let executor = MockExecutor::new().on_failure(
+            &["build"],
+            "error[E0425]: cannot find value `x` in this scope\n  --> lib.rs:10:9\n   |\n10 |         x\n   |         ^ not found in this scope\n\nerror: could not compile `flipper`",
+        );
+        let params = BuildContractParams {
+            path: "./flipper".to_owned(),
+            release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
+        };
+        let result = build_contract(&executor, params).await?;
+        assert_eq!(result.is_error, Some(true));
+        let texts = extract_texts(&result);
+        assert!(texts[0].starts_with("Build failed with 1 error(s):"));
+        assert!(texts[0].contains("  --> lib.rs:10:9"));
+        assert!(texts[1].contains("could not compile"));
+        let build: BuildOutput =
+            serde_json::from_value(result.structured_content.unwrap_or_default())?;
+        assert_eq!(build.errors[0].code.as_deref(), Some("E0425"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn builds_run_with_the_project_toolchain() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(
+            dir.path().join("rust-toolchain.toml"),
+            "[toolchain]\nchannel = \"nightly-2024-09-05\"\n",
+        )?;
+        let executor = MockExecutor::new().on(&["build"], "Build completed");
+        let params = BuildContractParams {
+            path: dir.path().to_string_lossy().into_owned(),
+            release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
+        };
+        build_contract(&executor, params.clone()).await?;
+        build_contract(
+            &executor,
+            BuildContractParams {
+                toolchain: Some("1.81".to_owned()),
+                verifiable: None,
+                ..params
+            },
+        )
+        .await?;
+        let toolchains: Vec<Option<String>> = executor
+            .calls()
+            .into_iter()
+            .map(|call| call.env.get("RUSTUP_TOOLCHAIN").cloned())
+            .collect();
+        assert_eq!(
+            toolchains,
+            vec![
+                Some("nightly-2024-09-05".to_owned()),
+                Some("1.81".to_owned())
+            ]
+        );
+        Ok(())
+    }
 }
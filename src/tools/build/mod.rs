@@ -3,9 +3,14 @@
 //! Submodules:
 //! - `contract` - Contract building (pop build)
 //! - `chain` - Chain building (pop build)
+//! - `cache` - Skipping contract builds whose sources are unchanged
+//! - `size` - Code size and upload deposit of a built contract
 
+pub(crate) mod cache;
 pub mod chain;
 pub mod contract;
+pub mod size;
 
 pub use chain::*;
 pub use contract::*;
+pub use size::*;
@@ -0,0 +1,489 @@
+//! Contract size and storage-deposit report
+//!
+//! Reads the code blob `build_contract` left in `target/ink`, splits it into its
+//! top-level sections (PolkaVM or Wasm) and, given a chain, estimates the deposit
+//! reserved for uploading it from the contracts pallet's `DepositPerByte` and
+//! `DepositPerItem` constants.
+
+use std::path::Path;
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::encoder::ChainMetadata;
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::outputs::{ArtifactFile, BuildOutput};
+use crate::rpc::RpcPool;
+use crate::tools::common::{error_result, success_result, with_structured};
+
+/// Code size limit of pallet-revive (`limits::code::BLOB_BYTES`), which has no
+/// `MaxCodeLen` constant.
+const REVIVE_MAX_CODE_LEN: u64 = 1024 * 1024;
+/// `MaxCodeLen` of pallet-contracts in the substrate contracts node.
+const CONTRACTS_MAX_CODE_LEN: u64 = 123 * 1024;
+/// Storage items an upload creates: the code and its code info record.
+const UPLOAD_ITEMS: u128 = 2;
+
+/// Parameters for the contract_size_report tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct ContractSizeReportParams {
+    /// Path to the built contract directory.
+    #[schemars(description = "Path to the contract directory, built with build_contract")]
+    pub path: String,
+    /// WebSocket URL of the chain to estimate the deposit on.
+    #[schemars(
+        description = "WebSocket URL of the chain to estimate the upload deposit and code size limit on (default: the node launched with up_ink_node; without one, only the pallet's default limit is checked)"
+    )]
+    pub url: Option<String>,
+}
+
+/// A top-level section of a code blob.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct BlobSection {
+    /// Section name (e.g. `code_and_jump_table`, or `custom:name` for Wasm custom sections).
+    pub name: String,
+    /// Size of the section's contents in bytes.
+    pub size: u64,
+}
+
+/// Upload costs and limits of a chain's contracts pallet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct UploadLimits {
+    /// Contracts pallet (`Revive` or `Contracts`).
+    pub pallet: String,
+    /// Deposit per byte of stored code, in planck.
+    pub deposit_per_byte: u128,
+    /// Deposit per storage item, in planck.
+    pub deposit_per_item: u128,
+    /// Maximum code size in bytes.
+    pub max_code_len: u64,
+}
+
+/// Result of `contract_size_report`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SizeReport {
+    /// The code blob.
+    pub blob: ArtifactFile,
+    /// Blob format: `polkavm` or `wasm`.
+    pub format: String,
+    /// Top-level sections, in blob order; empty when the format isn't recognised.
+    pub sections: Vec<BlobSection>,
+    /// Chain the deposit was estimated on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Limits read from the chain, when a chain was given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<UploadLimits>,
+    /// Estimated upload storage deposit, in planck.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_deposit: Option<u128>,
+    /// Maximum code size the blob was checked against.
+    pub max_code_len: u64,
+    /// Whether the blob exceeds `max_code_len`.
+    pub exceeds_max: bool,
+}
+
+/// PolkaVM section name for `id`.
+fn polkavm_section(id: u8) -> String {
+    match id {
+        1 => "memory_config".to_owned(),
+        2 => "ro_data".to_owned(),
+        3 => "rw_data".to_owned(),
+        4 => "imports".to_owned(),
+        5 => "exports".to_owned(),
+        6 => "code_and_jump_table".to_owned(),
+        128 => "debug_strings".to_owned(),
+        129 => "debug_line_programs".to_owned(),
+        130 => "debug_line_program_ranges".to_owned(),
+        other => format!("section_{}", other),
+    }
+}
+
+/// Read a PolkaVM varint: the leading ones of the first byte count the little-endian
+/// bytes that follow, the remaining bits are the value's high bits.
+fn polkavm_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let (&first, rest) = bytes.split_first()?;
+    let length = first.leading_ones() as usize;
+    if length > 4 {
+        return None;
+    }
+    let tail = rest.get(..length)?;
+    let upper = u64::from(first & (0xff_u8.checked_shr(length as u32).unwrap_or(0)));
+    let lower = tail
+        .iter()
+        .rev()
+        .fold(0_u64, |value, &byte| (value << 8) | u64::from(byte));
+    *bytes = &rest[length..];
+    Some((upper << (length * 8)) | lower)
+}
+
+/// Sections of a PolkaVM blob: magic, version byte, an optional blob length, then
+/// `id, varint length, contents` until the end-of-file section.
+fn polkavm_sections(blob: &[u8]) -> Option<Vec<BlobSection>> {
+    let mut rest = blob.strip_prefix(b"PVM\0")?.get(1..)?;
+    if let Some(len) = rest.get(..8) {
+        let mut le = [0_u8; 8];
+        le.copy_from_slice(len);
+        if u64::from_le_bytes(le) == blob.len() as u64 {
+            rest = &rest[8..];
+        }
+    }
+    let mut sections = Vec::new();
+    while let Some((&id, tail)) = rest.split_first() {
+        if id == 0 {
+            break;
+        }
+        rest = tail;
+        let size = polkavm_varint(&mut rest)?;
+        rest = rest.get(usize::try_from(size).ok()?..)?;
+        sections.push(BlobSection {
+            name: polkavm_section(id),
+            size,
+        });
+    }
+    Some(sections)
+}
+
+/// Read an unsigned LEB128 value.
+fn leb128(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value = 0_u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = bytes.split_first()?;
+        *bytes = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Sections of a Wasm module, custom sections named after their contents.
+fn wasm_sections(blob: &[u8]) -> Option<Vec<BlobSection>> {
+    let mut rest = blob.strip_prefix(b"\0asm")?.get(4..)?;
+    let mut sections = Vec::new();
+    while let Some((&id, tail)) = rest.split_first() {
+        rest = tail;
+        let size = leb128(&mut rest)?;
+        let contents = rest.get(..usize::try_from(size).ok()?)?;
+        rest = &rest[contents.len()..];
+        let name = match id {
+            0 => {
+                let mut contents = contents;
+                let len = usize::try_from(leb128(&mut contents)?).ok()?;
+                let name = contents.get(..len).map(String::from_utf8_lossy)?;
+                format!("custom:{}", name)
+            }
+            1 => "type".to_owned(),
+            2 => "import".to_owned(),
+            3 => "function".to_owned(),
+            4 => "table".to_owned(),
+            5 => "memory".to_owned(),
+            6 => "global".to_owned(),
+            7 => "export".to_owned(),
+            8 => "start".to_owned(),
+            9 => "element".to_owned(),
+            10 => "code".to_owned(),
+            11 => "data".to_owned(),
+            12 => "data_count".to_owned(),
+            other => format!("section_{}", other),
+        };
+        sections.push(BlobSection { name, size });
+    }
+    Some(sections)
+}
+
+impl UploadLimits {
+    /// Read the limits of the contracts pallet in `metadata`, preferring pallet-revive.
+    pub fn from_metadata(metadata: &ChainMetadata) -> Result<Self, String> {
+        let pallet = ["Revive", "Contracts"]
+            .into_iter()
+            .find(|pallet| metadata.has_constant(pallet, "DepositPerByte"))
+            .ok_or("the chain has no contracts pallet (Revive or Contracts)")?;
+        let number = |name: &str| -> Result<u128, String> {
+            let value = metadata.decode_constant(pallet, name)?;
+            value
+                .parse()
+                .map_err(|_| format!("{}.{} is not a number: {}", pallet, name, value))
+        };
+        let max_code_len = if metadata.has_constant(pallet, "MaxCodeLen") {
+            u64::try_from(number("MaxCodeLen")?).map_err(|e| e.to_string())?
+        } else {
+            REVIVE_MAX_CODE_LEN
+        };
+        Ok(Self {
+            pallet: pallet.to_owned(),
+            deposit_per_byte: number("DepositPerByte")?,
+            deposit_per_item: number("DepositPerItem")?,
+            max_code_len,
+        })
+    }
+
+    /// Estimated deposit for uploading `size` bytes of code: the bytes stored plus
+    /// the code and code info items.
+    pub fn upload_deposit(&self, size: u64) -> u128 {
+        self.deposit_per_byte
+            .saturating_mul(u128::from(size))
+            .saturating_add(self.deposit_per_item.saturating_mul(UPLOAD_ITEMS))
+    }
+}
+
+impl SizeReport {
+    /// Report on `blob`, checked against `limits` when given and otherwise the
+    /// default limit of the pallet its format targets.
+    pub fn new(
+        blob: ArtifactFile,
+        bytes: &[u8],
+        url: Option<String>,
+        limits: Option<UploadLimits>,
+    ) -> Self {
+        let polkavm = blob.path.ends_with(".polkavm");
+        let sections = if polkavm {
+            polkavm_sections(bytes)
+        } else {
+            wasm_sections(bytes)
+        };
+        let max_code_len = match &limits {
+            Some(limits) => limits.max_code_len,
+            None if polkavm => REVIVE_MAX_CODE_LEN,
+            None => CONTRACTS_MAX_CODE_LEN,
+        };
+        Self {
+            format: if polkavm { "polkavm" } else { "wasm" }.to_owned(),
+            sections: sections.unwrap_or_default(),
+            url,
+            upload_deposit: limits.as_ref().map(|l| l.upload_deposit(blob.size)),
+            limits,
+            exceeds_max: blob.size > max_code_len,
+            max_code_len,
+            blob,
+        }
+    }
+
+    /// Human-readable report.
+    pub fn describe(&self) -> String {
+        let mut lines = vec![
+            format!(
+                "Code: {} ({} bytes, {})",
+                self.blob.path, self.blob.size, self.format
+            ),
+            format!(
+                "Max code size: {} bytes{}",
+                self.max_code_len,
+                match &self.limits {
+                    Some(limits) => format!(" ({} on the chain)", limits.pallet),
+                    None => " (pallet default)".to_owned(),
+                }
+            ),
+        ];
+        if self.exceeds_max {
+            lines.push(format!(
+                "WARNING: the code is {} bytes over the maximum and will be rejected on upload",
+                self.blob.size - self.max_code_len
+            ));
+        }
+        if let (Some(deposit), Some(limits)) = (self.upload_deposit, &self.limits) {
+            lines.push(format!("Upload deposit: {}", deposit));
+            lines.push(format!(
+                "  estimated as {} bytes x per_byte_deposit: {} + {} items x per_item_deposit: {}",
+                self.blob.size, limits.deposit_per_byte, UPLOAD_ITEMS, limits.deposit_per_item
+            ));
+        } else {
+            lines.push("Upload deposit: pass url to estimate it on a chain".to_owned());
+        }
+        if self.sections.is_empty() {
+            lines.push(format!("Sections: not a recognised {} blob", self.format));
+        } else {
+            lines.push("Sections:".to_owned());
+            let width = self
+                .sections
+                .iter()
+                .map(|s| s.name.len())
+                .max()
+                .unwrap_or(0);
+            for section in &self.sections {
+                let share = section.size as f64 * 100.0 / self.blob.size.max(1) as f64;
+                lines.push(format!(
+                    "  {:width$}  {:>8} bytes  {:>5.1}%",
+                    section.name,
+                    section.size,
+                    share,
+                    width = width
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Report the size of the contract built at `params.path`, estimating the upload
+/// deposit on `url` when given.
+pub async fn contract_size_report(
+    pool: &RpcPool,
+    params: ContractSizeReportParams,
+    url: Option<String>,
+) -> PopMcpResult<CallToolResult> {
+    if !Path::new(&params.path).join("Cargo.toml").is_file() {
+        return Err(PopMcpError::InvalidInput(format!(
+            "{} is not a contract project (no Cargo.toml)",
+            params.path
+        )));
+    }
+    let Some(blob) = BuildOutput::collect(&params.path, false).blob else {
+        return Ok(error_result(format!(
+            "No code blob in {}/target/ink; build the contract first with build_contract",
+            params.path
+        )));
+    };
+    let bytes = match std::fs::read(&blob.path) {
+        Ok(bytes) => bytes,
+        Err(e) => return Ok(error_result(format!("Failed to read {}: {}", blob.path, e))),
+    };
+    let limits = match &url {
+        Some(url) => {
+            let metadata = match ChainMetadata::fetch(pool, url).await {
+                Ok(metadata) => metadata,
+                Err(e) => return Ok(error_result(format!("Failed to read {}: {}", url, e))),
+            };
+            match UploadLimits::from_metadata(&metadata) {
+                Ok(limits) => Some(limits),
+                Err(e) => return Ok(error_result(format!("{}: {}", url, e))),
+            }
+        }
+        None => None,
+    };
+    let report = SizeReport::new(blob, &bytes, url, limits);
+    Ok(with_structured(success_result(report.describe()), &report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polkavm_blob() -> Vec<u8> {
+        let mut blob = b"PVM\0\x00".to_vec();
+        blob.extend([0; 8]);
+        blob.extend([1, 2, 0xaa, 0xbb]);
+        // 300 bytes of code: varint 0x81 0x2c (one extra byte, high bits 1).
+        blob.extend([6, 0x81, 0x2c]);
+        blob.extend([0; 300]);
+        blob.extend([128, 3, b'a', b'b', b'c']);
+        blob.push(0);
+        let len = blob.len() as u64;
+        blob[5..13].copy_from_slice(&len.to_le_bytes());
+        blob
+    }
+
+    fn artifact(path: &str, size: usize) -> ArtifactFile {
+        ArtifactFile {
+            path: path.to_owned(),
+            size: size as u64,
+        }
+    }
+
+    #[test]
+    fn polkavm_sections_are_split() {
+        assert_eq!(
+            polkavm_sections(&polkavm_blob()),
+            Some(vec![
+                BlobSection {
+                    name: "memory_config".to_owned(),
+                    size: 2
+                },
+                BlobSection {
+                    name: "code_and_jump_table".to_owned(),
+                    size: 300
+                },
+                BlobSection {
+                    name: "debug_strings".to_owned(),
+                    size: 3
+                },
+            ])
+        );
+        assert_eq!(polkavm_sections(b"\0asm"), None);
+        assert_eq!(polkavm_sections(b"PVM\0\x00\x06\x05ab"), None);
+    }
+
+    #[test]
+    fn wasm_sections_name_custom_sections() {
+        let mut blob = b"\0asm\x01\x00\x00\x00".to_vec();
+        blob.extend([0, 5, 4, b'n', b'a', b'm', b'e']);
+        blob.extend([10, 0x80, 0x01]);
+        blob.extend([0; 128]);
+        let sections = wasm_sections(&blob).unwrap_or_default();
+        let names: Vec<(&str, u64)> = sections.iter().map(|s| (s.name.as_str(), s.size)).collect();
+        assert_eq!(names, [("custom:name", 5), ("code", 128)]);
+    }
+
+    #[test]
+    fn deposit_and_limit_follow_the_chain() {
+        let blob = polkavm_blob();
+        let limits = UploadLimits {
+            pallet: "Revive".to_owned(),
+            deposit_per_byte: 10,
+            deposit_per_item: 1_000,
+            max_code_len: 100,
+        };
+        let report = SizeReport::new(
+            artifact("/c/target/ink/flipper.polkavm", blob.len()),
+            &blob,
+            Some("ws://localhost:9944".to_owned()),
+            Some(limits),
+        );
+        assert_eq!(report.upload_deposit, Some(blob.len() as u128 * 10 + 2_000));
+        assert!(report.exceeds_max);
+        let text = report.describe();
+        assert!(text.contains("WARNING: the code is"), "{}", text);
+        assert!(text.contains("Max code size: 100 bytes (Revive on the chain)"));
+        assert!(text.contains("code_and_jump_table"));
+    }
+
+    #[test]
+    fn offline_reports_use_the_pallet_default() {
+        let report = SizeReport::new(
+            artifact("/c/target/ink/flipper.wasm", 3),
+            b"abc",
+            None,
+            None,
+        );
+        assert_eq!(report.max_code_len, CONTRACTS_MAX_CODE_LEN);
+        assert!(!report.exceeds_max);
+        assert_eq!(report.upload_deposit, None);
+        let text = report.describe();
+        assert!(text.contains("Upload deposit: pass url"), "{}", text);
+        assert!(text.contains("Sections: not a recognised wasm blob"));
+    }
+
+    #[tokio::test]
+    async fn missing_blob_asks_for_a_build() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().display().to_string();
+        let params = |path: &str| ContractSizeReportParams {
+            path: path.to_owned(),
+            url: None,
+        };
+        assert!(contract_size_report(&RpcPool::new(), params(&path), None)
+            .await
+            .is_err());
+
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"flipper\"\n",
+        )?;
+        let result = contract_size_report(&RpcPool::new(), params(&path), None).await?;
+        assert_eq!(result.is_error, Some(true));
+
+        let ink = dir.path().join("target/ink");
+        std::fs::create_dir_all(&ink)?;
+        std::fs::write(ink.join("flipper.polkavm"), polkavm_blob())?;
+        let result = contract_size_report(&RpcPool::new(), params(&path), None).await?;
+        assert_eq!(result.is_error, Some(false));
+        let report: SizeReport =
+            serde_json::from_value(result.structured_content.unwrap_or_default())?;
+        assert_eq!(report.format, "polkavm");
+        assert_eq!(report.sections.len(), 3);
+        Ok(())
+    }
+}
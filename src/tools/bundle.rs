@@ -0,0 +1,349 @@
+//! Debug bundles for pop-cli and ink! bug reports
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::audit::{self, CommandStatus};
+use crate::bundle::{Bundle, MANIFEST};
+use crate::encoder::{fetch_storage_at, ChainMetadata};
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::nodes::RegisteredNode;
+use crate::redact::Secrets;
+use crate::rpc::RpcPool;
+use crate::tools::common::success_result;
+use crate::tools::rpc::hex_number;
+
+/// Recent blocks included by default.
+const DEFAULT_BLOCKS: u64 = 5;
+
+/// Most recent blocks that can be included.
+const MAX_BLOCKS: u64 = 50;
+
+/// Log bytes kept per file (the end, where the failure is).
+const MAX_LOG_BYTES: usize = 1024 * 1024;
+
+/// Parameters for the export_debug_bundle tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct ExportDebugBundleParams {
+    /// WebSocket URL of the node to snapshot.
+    #[schemars(
+        description = "WebSocket URL of the node whose chain spec, versions and recent blocks are included (default: the selected node; omit both to bundle only logs and commands)"
+    )]
+    pub url: Option<String>,
+    /// Number of recent blocks to include.
+    #[schemars(
+        description = "Number of most recent blocks to include with their events (default 5, max 50)"
+    )]
+    pub blocks: Option<u64>,
+    /// Where to write the zip.
+    #[schemars(
+        description = "Path of the zip to write (default: pop-mcp-debug-<timestamp>.zip in the temp directory)"
+    )]
+    pub output: Option<String>,
+}
+
+/// JSON of `value`, pretty-printed.
+fn pretty(value: &impl Serialize) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+/// Add the Pop CLI, server and node versions.
+async fn add_versions<E: CommandExecutor>(
+    bundle: &mut Bundle,
+    executor: &E,
+    pool: &RpcPool,
+    url: Option<&str>,
+) {
+    let mut versions = vec![format!("pop-mcp {}", env!("CARGO_PKG_VERSION"))];
+    match executor.execute(&["--version"]).await {
+        Ok(output) => versions.push(output.trim().to_owned()),
+        Err(e) => bundle.fail("pop --version", e),
+    }
+    if let Some(url) = url {
+        for method in ["system_name", "system_version"] {
+            match pool.request(url, method, Vec::new()).await {
+                Ok(value) => versions.push(format!(
+                    "{}: {}",
+                    method,
+                    value.as_str().unwrap_or_default()
+                )),
+                Err(e) => bundle.fail(method, e),
+            }
+        }
+    }
+    bundle.add(
+        "versions.txt",
+        "Versions of pop-mcp, Pop CLI and the node",
+        versions.join("\n") + "\n",
+    );
+}
+
+/// Add the failed and interrupted commands of the audit trail.
+fn add_commands(bundle: &mut Bundle) {
+    let failed: Vec<_> = audit::entries()
+        .into_iter()
+        .filter(|entry| entry.status != CommandStatus::Succeeded)
+        .collect();
+    bundle.add(
+        "commands.json",
+        "Pop CLI commands that failed or were interrupted (secrets masked)",
+        pretty(&failed),
+    );
+}
+
+/// Add the end of each log file of the nodes launched by the server.
+fn add_logs(bundle: &mut Bundle, nodes: &[RegisteredNode], secrets: &Secrets) {
+    for node in nodes {
+        for log in &node.logs {
+            let file_name = Path::new(log)
+                .file_name()
+                .map_or_else(|| "node.log".into(), |name| name.to_string_lossy());
+            let name = format!("logs/{}/{}", node.name, file_name);
+            match std::fs::read(log) {
+                Ok(bytes) => {
+                    let start = bytes.len().saturating_sub(MAX_LOG_BYTES);
+                    let text = String::from_utf8_lossy(&bytes[start..]);
+                    let description = format!("Log of node '{}' ({}), last MiB", node.name, log);
+                    bundle.add(&name, &description, secrets.mask(&text));
+                }
+                Err(e) => bundle.fail(&name, format!("Failed to read {}: {}", log, e)),
+            }
+        }
+    }
+}
+
+/// Add the chain's identity, runtime version and chain spec.
+async fn add_chain(bundle: &mut Bundle, pool: &RpcPool, url: &str) {
+    let mut chain = Map::new();
+    let genesis = vec![json!(0)];
+    for (method, params) in [
+        ("system_chain", Vec::new()),
+        ("system_properties", Vec::new()),
+        ("system_health", Vec::new()),
+        ("chain_getBlockHash", genesis),
+        ("state_getRuntimeVersion", Vec::new()),
+    ] {
+        match pool.request(url, method, params).await {
+            Ok(value) => {
+                chain.insert(method.to_owned(), value);
+            }
+            Err(e) => bundle.fail(method, e),
+        }
+    }
+    bundle.add(
+        "chain.json",
+        "Chain name, properties, health, genesis hash and runtime version",
+        pretty(&chain),
+    );
+    match pool
+        .request(url, "sync_state_genSyncSpec", vec![json!(true)])
+        .await
+    {
+        Ok(spec) => bundle.add(
+            "chain-spec.json",
+            "Raw chain spec of the node",
+            pretty(&spec),
+        ),
+        Err(e) => bundle.fail("chain-spec.json", e),
+    }
+}
+
+/// Add the `count` most recent blocks with their events.
+async fn add_blocks(bundle: &mut Bundle, pool: &RpcPool, url: &str, count: u64) {
+    let best = match pool.request(url, "chain_getHeader", Vec::new()).await {
+        Ok(header) => header.get("number").and_then(hex_number),
+        Err(e) => {
+            bundle.fail("blocks.json", e);
+            return;
+        }
+    };
+    let Some(best) = best else {
+        bundle.fail("blocks.json", "best header has no block number");
+        return;
+    };
+    let metadata = match ChainMetadata::fetch(pool, url).await {
+        Ok(metadata) => Some(metadata),
+        Err(e) => {
+            bundle.fail("events", e);
+            None
+        }
+    };
+    let events_key = metadata
+        .as_ref()
+        .and_then(|metadata| metadata.storage_key("System", "Events", &[]).ok());
+    let mut blocks = Vec::new();
+    for number in (best.saturating_sub(count - 1)..=best).rev() {
+        let hash = match pool
+            .request(url, "chain_getBlockHash", vec![json!(number)])
+            .await
+        {
+            Ok(Value::String(hash)) => hash,
+            Ok(_) => continue,
+            Err(e) => {
+                bundle.fail(&format!("block {}", number), e);
+                continue;
+            }
+        };
+        let mut block = json!({ "number": number, "hash": hash });
+        match pool.request(url, "chain_getBlock", vec![json!(hash)]).await {
+            Ok(value) => block["block"] = value["block"].clone(),
+            Err(e) => bundle.fail(&format!("block {}", number), e),
+        }
+        if let (Some(metadata), Some(key)) = (&metadata, &events_key) {
+            let events = match fetch_storage_at(pool, url, key, Some(&hash)).await {
+                Ok(bytes) => metadata.decode_storage("System", "Events", bytes.as_deref()),
+                Err(e) => Err(e.to_string()),
+            };
+            match events {
+                Ok(events) => block["events"] = json!(events),
+                Err(e) => bundle.fail(&format!("events of block {}", number), e),
+            }
+        }
+        blocks.push(block);
+    }
+    bundle.add(
+        "blocks.json",
+        &format!(
+            "Last {} block(s), newest first, with decoded events",
+            blocks.len()
+        ),
+        pretty(&blocks),
+    );
+}
+
+/// Execute export_debug_bundle tool
+pub async fn export_debug_bundle<E: CommandExecutor>(
+    executor: &E,
+    pool: &RpcPool,
+    nodes: &[RegisteredNode],
+    params: ExportDebugBundleParams,
+    stored_url: Option<&str>,
+) -> PopMcpResult<CallToolResult> {
+    let blocks = params.blocks.unwrap_or(DEFAULT_BLOCKS);
+    if !(1..=MAX_BLOCKS).contains(&blocks) {
+        return Err(PopMcpError::InvalidInput(format!(
+            "'blocks' must be between 1 and {}",
+            MAX_BLOCKS
+        )));
+    }
+    let url = params.url.as_deref().or(stored_url);
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let output = params.output.map_or_else(
+        || std::env::temp_dir().join(format!("pop-mcp-debug-{}.zip", created_at)),
+        PathBuf::from,
+    );
+
+    let mut bundle = Bundle::new();
+    add_versions(&mut bundle, executor, pool, url).await;
+    add_commands(&mut bundle);
+    add_logs(&mut bundle, nodes, &Secrets::for_args(&[]));
+    if let Some(url) = url {
+        add_chain(&mut bundle, pool, url).await;
+        add_blocks(&mut bundle, pool, url, blocks).await;
+    }
+
+    let zip = bundle
+        .to_zip(created_at, url)
+        .map_err(PopMcpError::Internal)?;
+    tokio::fs::write(&output, &zip).await.map_err(|e| {
+        PopMcpError::Internal(format!("Failed to write {}: {}", output.display(), e))
+    })?;
+
+    let files: Vec<String> = bundle
+        .files()
+        .iter()
+        .map(|file| {
+            format!(
+                "- {} ({} bytes): {}",
+                file.name, file.bytes, file.description
+            )
+        })
+        .collect();
+    let mut message = format!(
+        "Debug bundle written to {} ({} bytes). Attach it to the pop-cli or ink! issue.\n\n\
+         Files (listed in {}):\n{}",
+        output.display(),
+        zip.len(),
+        MANIFEST,
+        files.join("\n")
+    );
+    if url.is_none() {
+        message.push_str("\n\nNo node given or selected: chain state was not included.");
+    }
+    if !bundle.errors().is_empty() {
+        let errors: Vec<String> = bundle
+            .errors()
+            .iter()
+            .map(|error| format!("- {}: {}", error.section, error.error))
+            .collect();
+        message.push_str(&format!("\n\nNot included:\n{}", errors.join("\n")));
+    }
+    Ok(success_result(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutor;
+    use crate::tools::common::content_text;
+
+    #[tokio::test]
+    async fn bundles_hold_logs_commands_and_versions() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let log = dir.path().join("ink-node.log");
+        std::fs::write(&log, "2024-01-01 Idle (0 peers)\n")?;
+        let node = RegisteredNode {
+            name: "dev".to_owned(),
+            url: "ws://localhost:9944".to_owned(),
+            pids: vec![42],
+            launched_at: 0,
+            logs: vec![log.display().to_string()],
+        };
+        let output = dir.path().join("bundle.zip");
+        let executor = MockExecutor::new().on(&["--version"], "pop 0.9.0");
+        let result = export_debug_bundle(
+            &executor,
+            &RpcPool::new(),
+            &[node],
+            ExportDebugBundleParams {
+                url: None,
+                blocks: None,
+                output: Some(output.display().to_string()),
+            },
+            None,
+        )
+        .await?;
+
+        let text = content_text(&result);
+        assert!(text.contains("- versions.txt"));
+        assert!(text.contains("- commands.json"));
+        assert!(text.contains("- logs/dev/ink-node.log (26 bytes)"));
+        assert!(text.contains("chain state was not included"));
+        let zip = std::fs::read(&output)?;
+        assert!(zip
+            .windows(b"pop 0.9.0".len())
+            .any(|window| window == b"pop 0.9.0"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn block_counts_are_bounded() {
+        let params = ExportDebugBundleParams {
+            url: None,
+            blocks: Some(0),
+            output: None,
+        };
+        let result =
+            export_debug_bundle(&MockExecutor::new(), &RpcPool::new(), &[], params, None).await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+    }
+}
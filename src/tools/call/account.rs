@@ -0,0 +1,409 @@
+//! Account-management helpers (identity.set_identity, proxy.add_proxy,
+//! revive.map_account)
+//!
+//! The identity and proxy tools check the runtime metadata first, so chains without
+//! the pallet get a clear error instead of a failed extrinsic.
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::encoder::{to_hex, ChainMetadata};
+use crate::endpoints::execute_with_failover;
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::parsing;
+use crate::rpc::RpcPool;
+use crate::tools::call::chain::submit_call_data;
+use crate::tools::ci;
+use crate::tools::common::{error_result, success_result, with_note};
+
+/// Longest value an identity `Data::Raw` field holds.
+const MAX_IDENTITY_FIELD_LEN: usize = 32;
+
+/// Parameters for the set_identity tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct SetIdentityParams {
+    /// WebSocket URL of the chain node.
+    #[schemars(description = "WebSocket URL of the chain node (e.g., ws://localhost:9944)")]
+    pub url: String,
+    /// Display name.
+    #[schemars(description = "Display name (at most 32 bytes)")]
+    pub display: String,
+    /// Legal name.
+    #[schemars(description = "Legal name (optional)")]
+    pub legal: Option<String>,
+    /// Website.
+    #[schemars(description = "Website (optional)")]
+    pub web: Option<String>,
+    /// Email address.
+    #[schemars(description = "Email address (optional)")]
+    pub email: Option<String>,
+    /// Twitter/X handle.
+    #[schemars(description = "Twitter/X handle (optional)")]
+    pub twitter: Option<String>,
+    /// Submit the extrinsic; otherwise only the encoded call data is returned.
+    #[schemars(description = "Submit on-chain; when false only the encoded call data is returned")]
+    pub execute: Option<bool>,
+}
+
+impl SetIdentityParams {
+    /// Provided fields as (name, value) pairs.
+    fn fields(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("display", Some(self.display.as_str())),
+            ("legal", self.legal.as_deref()),
+            ("web", self.web.as_deref()),
+            ("email", self.email.as_deref()),
+            ("twitter", self.twitter.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|v| (name, v)))
+        .collect()
+    }
+}
+
+/// Parameters for the add_proxy tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct AddProxyParams {
+    /// WebSocket URL of the chain node.
+    #[schemars(description = "WebSocket URL of the chain node (e.g., ws://localhost:9944)")]
+    pub url: String,
+    /// Account allowed to act on behalf of the signer.
+    #[schemars(description = "SS58 address of the proxy (delegate) account")]
+    pub delegate: String,
+    /// Proxy type.
+    #[schemars(description = "Proxy type as defined by the runtime (default: Any)")]
+    pub proxy_type: Option<String>,
+    /// Announcement delay in blocks.
+    #[schemars(description = "Announcement delay in blocks (default: 0)")]
+    pub delay: Option<u32>,
+    /// Submit the extrinsic; otherwise only the encoded call data is returned.
+    #[schemars(description = "Submit on-chain; when false only the encoded call data is returned")]
+    pub execute: Option<bool>,
+}
+
+/// Parameters for the map_account tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct MapAccountParams {
+    /// WebSocket URL of the chain node.
+    #[schemars(description = "WebSocket URL of the chain node (default: the selected node)")]
+    pub url: Option<String>,
+    /// Keystore alias of the signer.
+    #[schemars(
+        description = "Keystore alias of the account to map: its SURI is read from the POP_MCP_KEY_<ALIAS> environment variable instead of PRIVATE_KEY"
+    )]
+    pub signer: Option<String>,
+}
+
+/// Outcome of mapping an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Mapping {
+    /// The account is mapped now.
+    Mapped(String),
+    /// The account was mapped before.
+    AlreadyMapped(String),
+}
+
+/// Map the account of `suri` to its Ethereum address on the revive chain at `url`
+/// (`pop call chain --pallet Revive --function map_account`), returning the note on
+/// the endpoint used, if another one answered.
+pub(crate) async fn run_map_account<E: CommandExecutor>(
+    executor: &E,
+    url: &str,
+    suri: &str,
+) -> (Result<Mapping, String>, Option<String>) {
+    let args: Vec<String> = [
+        "call",
+        "chain",
+        "--pallet",
+        "Revive",
+        "--function",
+        "map_account",
+        "--url",
+        url,
+        "-y",
+        "--suri",
+        suri,
+    ]
+    .iter()
+    .map(|arg| (*arg).to_owned())
+    .collect();
+    let (outcome, note) = execute_with_failover(executor, &args, false).await;
+    let output = match outcome {
+        Ok(output) => output,
+        Err(e) => e.to_string(),
+    };
+    let mapping = if output.contains("AccountAlreadyMapped") {
+        Ok(Mapping::AlreadyMapped(output))
+    } else if output.contains("not found in pallet") || output.contains("Failed to find the pallet")
+    {
+        Err(format!(
+            "the chain has no Revive pallet; only revive (Ethereum-compatible) chains map accounts\n\n{}",
+            output
+        ))
+    } else if parsing::has_error(&output, &[]) {
+        Err(output)
+    } else {
+        Ok(Mapping::Mapped(output))
+    };
+    (mapping, note)
+}
+
+/// Build an `IdentityInfo` value for a runtime whose struct has `fields`.
+///
+/// Unset fields become `None()`, `additional` an empty list.
+fn identity_info(fields: &[String], values: &[(&str, &str)]) -> Result<String, String> {
+    for (name, value) in values {
+        if !fields.iter().any(|f| f == name) {
+            return Err(format!(
+                "Identity field '{}' is not supported by this runtime",
+                name
+            ));
+        }
+        if value.len() > MAX_IDENTITY_FIELD_LEN {
+            return Err(format!(
+                "Identity field '{}' exceeds {} bytes",
+                name, MAX_IDENTITY_FIELD_LEN
+            ));
+        }
+    }
+    let entries: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            let value = match values.iter().find(|(name, _)| name == field) {
+                Some((_, v)) if !v.is_empty() => {
+                    format!("Raw{}({})", v.len(), to_hex(v.as_bytes()))
+                }
+                _ if field == "additional" => "()".to_owned(),
+                _ => "None()".to_owned(),
+            };
+            format!("{}: {}", field, value)
+        })
+        .collect();
+    Ok(format!("{{ {} }}", entries.join(", ")))
+}
+
+/// Return the encoded call, or submit it with the signer from `PRIVATE_KEY`.
+async fn finish<E: CommandExecutor>(
+    executor: &E,
+    url: &str,
+    call_data: &[u8],
+    execute: Option<bool>,
+    label: &str,
+) -> PopMcpResult<CallToolResult> {
+    if !execute.unwrap_or(false) {
+        return Ok(success_result(format!(
+            "Encoded {}:\n{}",
+            label,
+            to_hex(call_data)
+        )));
+    }
+    submit_call_data(executor, url, &to_hex(call_data), false).await
+}
+
+/// Execute set_identity tool
+pub async fn set_identity<E: CommandExecutor>(
+    executor: &E,
+    pool: &RpcPool,
+    params: SetIdentityParams,
+) -> PopMcpResult<CallToolResult> {
+    if params.display.trim().is_empty() {
+        return Err(PopMcpError::InvalidInput(
+            "'display' cannot be empty".to_owned(),
+        ));
+    }
+
+    let metadata = match ChainMetadata::fetch(pool, &params.url).await {
+        Ok(metadata) => metadata,
+        Err(e) => return Ok(error_result(format!("Set identity failed: {}", e))),
+    };
+    if !metadata.has_call("Identity", "set_identity") {
+        return Ok(error_result(
+            "Set identity failed: the chain does not include the Identity pallet",
+        ));
+    }
+
+    let encoded = metadata
+        .arg_fields("Identity", "set_identity", 0)
+        .and_then(|fields| identity_info(&fields, &params.fields()))
+        .and_then(|info| metadata.encode_call("Identity", "set_identity", &[info]));
+    match encoded {
+        Ok(call) => {
+            finish(
+                executor,
+                &params.url,
+                &call,
+                params.execute,
+                "identity.set_identity",
+            )
+            .await
+        }
+        Err(e) => Ok(error_result(format!("Set identity failed: {}", e))),
+    }
+}
+
+/// Execute add_proxy tool
+pub async fn add_proxy<E: CommandExecutor>(
+    executor: &E,
+    pool: &RpcPool,
+    params: AddProxyParams,
+) -> PopMcpResult<CallToolResult> {
+    if params.delegate.trim().is_empty() {
+        return Err(PopMcpError::InvalidInput(
+            "'delegate' cannot be empty".to_owned(),
+        ));
+    }
+
+    let metadata = match ChainMetadata::fetch(pool, &params.url).await {
+        Ok(metadata) => metadata,
+        Err(e) => return Ok(error_result(format!("Add proxy failed: {}", e))),
+    };
+    if !metadata.has_call("Proxy", "add_proxy") {
+        return Ok(error_result(
+            "Add proxy failed: the chain does not include the Proxy pallet",
+        ));
+    }
+
+    let proxy_type = params.proxy_type.as_deref().unwrap_or("Any").to_owned();
+    let delay = params.delay.unwrap_or(0).to_string();
+    let delegate = params.delegate.trim();
+    // Newer runtimes take a `MultiAddress`, older ones a plain `AccountId`.
+    let encoded = metadata
+        .encode_call(
+            "Proxy",
+            "add_proxy",
+            &[
+                format!("Id({})", delegate),
+                proxy_type.clone(),
+                delay.clone(),
+            ],
+        )
+        .or_else(|_| {
+            metadata.encode_call(
+                "Proxy",
+                "add_proxy",
+                &[delegate.to_owned(), proxy_type, delay],
+            )
+        });
+    match encoded {
+        Ok(call) => {
+            finish(
+                executor,
+                &params.url,
+                &call,
+                params.execute,
+                "proxy.add_proxy",
+            )
+            .await
+        }
+        Err(e) => Ok(error_result(format!("Add proxy failed: {}", e))),
+    }
+}
+
+/// Execute map_account tool
+pub async fn map_account<E: CommandExecutor>(
+    executor: &E,
+    params: MapAccountParams,
+    stored_url: Option<&str>,
+) -> PopMcpResult<CallToolResult> {
+    let Some(url) = params.url.as_deref().or(stored_url) else {
+        return Err(PopMcpError::InvalidInput(
+            "'url' is required when no node has been launched".to_owned(),
+        ));
+    };
+    let suri = match params.signer.as_deref() {
+        Some(alias) => ci::signer_suri(Some(alias)).map_err(PopMcpError::InvalidInput)?,
+        None => crate::read_private_key_suri().ok_or_else(|| {
+            PopMcpError::InvalidInput(
+                "PRIVATE_KEY environment variable is required to map the account".to_owned(),
+            )
+        })?,
+    };
+    let (mapping, note) = run_map_account(executor, url, &suri).await;
+    let result = match mapping {
+        Ok(Mapping::Mapped(output)) => success_result(format!(
+            "Account mapped: contracts can now be deployed and called with it.\n\n{}",
+            output
+        )),
+        Ok(Mapping::AlreadyMapped(output)) => success_result(format!(
+            "Account already mapped; nothing to do.\n\n{}",
+            output
+        )),
+        Err(e) => error_result(format!("Account mapping failed: {}", e)),
+    };
+    Ok(with_note(result, note))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutor;
+
+    #[tokio::test]
+    async fn map_account_treats_an_existing_mapping_as_done() {
+        let executor = MockExecutor::new()
+            .on(&["call", "chain"], "└  Extrinsic submitted with hash: 0x9a")
+            .on_failure(
+                &[
+                    "call",
+                    "chain",
+                    "--pallet",
+                    "Revive",
+                    "--function",
+                    "map_account",
+                    "--url",
+                    "ws://mapped",
+                ],
+                "Error: Module error: Revive::AccountAlreadyMapped",
+            );
+        let (mapping, _) = run_map_account(&executor, "ws://localhost:9944", "//Alice").await;
+        assert!(matches!(mapping, Ok(Mapping::Mapped(_))));
+        let (mapping, _) = run_map_account(&executor, "ws://mapped", "//Alice").await;
+        assert!(matches!(mapping, Ok(Mapping::AlreadyMapped(_))));
+        assert_eq!(
+            executor.calls()[0].args.join(" "),
+            "call chain --pallet Revive --function map_account --url ws://localhost:9944 -y --suri //Alice"
+        );
+    }
+
+    #[tokio::test]
+    async fn map_account_explains_chains_without_revive() {
+        let executor = MockExecutor::new().on(
+            &["call", "chain"],
+            "Error: Call with name map_account not found in pallet Revive",
+        );
+        let (mapping, _) = run_map_account(&executor, "ws://localhost:9944", "//Alice").await;
+        assert!(mapping.is_err_and(|e| e.contains("no Revive pallet")));
+    }
+
+    fn legacy_fields() -> Vec<String> {
+        ["additional", "display", "legal", "web", "pgp_fingerprint"]
+            .iter()
+            .map(|f| (*f).to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn identity_info_fills_unset_fields() {
+        let info = identity_info(&legacy_fields(), &[("display", "Alice"), ("web", "")]);
+        assert_eq!(
+            info,
+            Ok(
+                "{ additional: (), display: Raw5(0x416c696365), legal: None(), web: None(), \
+                pgp_fingerprint: None() }"
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn identity_info_rejects_unknown_or_long_fields() {
+        assert!(identity_info(&legacy_fields(), &[("twitter", "@alice")]).is_err());
+        let long = "a".repeat(MAX_IDENTITY_FIELD_LEN + 1);
+        assert!(identity_info(&legacy_fields(), &[("display", long.as_str())]).is_err());
+    }
+}
@@ -0,0 +1,177 @@
+//! Batched chain calls (utility.batch / utility.batch_all)
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::encoder::{to_hex, ChainMetadata};
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::rpc::RpcPool;
+use crate::tools::call::chain::submit_call_data;
+use crate::tools::common::{error_result, success_result};
+
+/// A single call inside a batch.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct BatchCall {
+    /// Pallet name.
+    #[schemars(description = "Pallet name (e.g., 'Assets', 'Balances')")]
+    pub pallet: String,
+    /// Extrinsic name.
+    #[schemars(description = "Extrinsic name (e.g., 'create', 'mint')")]
+    pub function: String,
+    /// Arguments for the call.
+    #[schemars(description = "Arguments for the call, one value per parameter")]
+    pub args: Option<Vec<String>>,
+}
+
+/// Parameters for the batch_chain_calls tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct BatchChainCallsParams {
+    /// WebSocket URL of the chain node.
+    #[schemars(description = "WebSocket URL of the chain node (e.g., ws://localhost:9944)")]
+    pub url: String,
+    /// Calls to batch, in order.
+    #[schemars(description = "Calls to batch, in order")]
+    pub calls: Vec<BatchCall>,
+    /// Revert every call if one fails (default: true).
+    #[schemars(
+        description = "Use batch_all so all calls revert if one fails (default: true); false uses batch"
+    )]
+    pub atomic: Option<bool>,
+    /// Dispatch the batch with root origin via sudo.
+    #[schemars(description = "Dispatch the batch with root origin via sudo pallet")]
+    pub sudo: Option<bool>,
+    /// Submit the batch; otherwise only the encoded call data is returned.
+    #[schemars(
+        description = "Submit the batch on-chain; when false only the encoded call data is returned"
+    )]
+    pub execute: Option<bool>,
+}
+
+impl BatchChainCallsParams {
+    fn validate(&self) -> Result<(), String> {
+        if self.calls.is_empty() {
+            return Err("At least one call is required".to_owned());
+        }
+        if self.sudo.unwrap_or(false) && !self.execute.unwrap_or(false) {
+            return Err("'execute' must be true when sudo=true".to_owned());
+        }
+        Ok(())
+    }
+
+    fn batch_function(&self) -> &'static str {
+        if self.atomic.unwrap_or(true) {
+            "batch_all"
+        } else {
+            "batch"
+        }
+    }
+}
+
+/// Encode every call and the wrapping utility batch.
+fn encode_batch(
+    metadata: &ChainMetadata,
+    params: &BatchChainCallsParams,
+) -> Result<Vec<u8>, String> {
+    let calls = params
+        .calls
+        .iter()
+        .enumerate()
+        .map(|(i, call)| {
+            metadata
+                .encode_call(
+                    &call.pallet,
+                    &call.function,
+                    call.args.as_deref().unwrap_or_default(),
+                )
+                .map_err(|e| format!("Call {}: {}", i + 1, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    metadata.encode_call_with_calls("Utility", params.batch_function(), &calls)
+}
+
+/// Execute batch_chain_calls tool
+pub async fn batch_chain_calls<E: CommandExecutor>(
+    executor: &E,
+    pool: &RpcPool,
+    params: BatchChainCallsParams,
+) -> PopMcpResult<CallToolResult> {
+    params.validate().map_err(PopMcpError::InvalidInput)?;
+
+    let metadata = match ChainMetadata::fetch(pool, &params.url).await {
+        Ok(metadata) => metadata,
+        Err(e) => return Ok(error_result(format!("Batch failed: {}", e))),
+    };
+    if !metadata.has_pallet("Utility") {
+        return Ok(error_result(
+            "Batch failed: the chain does not include the Utility pallet",
+        ));
+    }
+    let call_data = match encode_batch(&metadata, &params) {
+        Ok(encoded) => to_hex(&encoded),
+        Err(e) => return Ok(error_result(format!("Batch failed: {}", e))),
+    };
+
+    if !params.execute.unwrap_or(false) {
+        return Ok(success_result(format!(
+            "Encoded utility.{} with {} call(s):\n{}",
+            params.batch_function(),
+            params.calls.len(),
+            call_data
+        )));
+    }
+
+    submit_call_data(
+        executor,
+        &params.url,
+        &call_data,
+        params.sudo.unwrap_or(false),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params() -> BatchChainCallsParams {
+        BatchChainCallsParams {
+            url: "ws://localhost:9944".to_owned(),
+            calls: vec![BatchCall {
+                pallet: "System".to_owned(),
+                function: "remark".to_owned(),
+                args: Some(vec!["0x00".to_owned()]),
+            }],
+            atomic: None,
+            sudo: None,
+            execute: None,
+        }
+    }
+
+    #[test]
+    fn validate_requires_calls() {
+        let mut params = params();
+        params.calls.clear();
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn validate_requires_execute_with_sudo() {
+        let mut params = params();
+        params.sudo = Some(true);
+        assert!(params.validate().is_err());
+        params.execute = Some(true);
+        assert!(params.validate().is_ok());
+    }
+
+    #[test]
+    fn batch_function_defaults_to_batch_all() {
+        let mut params = params();
+        assert_eq!(params.batch_function(), "batch_all");
+        params.atomic = Some(false);
+        assert_eq!(params.batch_function(), "batch");
+    }
+}
@@ -4,9 +4,14 @@ use rmcp::model::CallToolResult;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::encoder::{fetch_storage_at, ChainMetadata};
+use crate::endpoints::execute_with_failover;
 use crate::error::{PopMcpError, PopMcpResult};
-use crate::executor::PopExecutor;
-use crate::tools::common::{error_result, success_result};
+use crate::executor::CommandExecutor;
+use crate::parsing;
+use crate::rpc::RpcPool;
+use crate::tools::common::{dry_run_result, error_result, success_result, with_note};
+use crate::wallet;
 
 /// Type hints for formatting arguments in chain calls (single source of truth).
 const TYPE_HINTS: &str = include_str!("../../../docs/type-hints.txt");
@@ -46,11 +51,29 @@ pub struct CallChainParams {
     #[schemars(description = "Submit an extrinsic for on-chain execution")]
     pub execute: Option<bool>,
 
+    /// Sign with a browser wallet instead of a SURI.
+    #[schemars(
+        description = "Sign with a browser wallet extension instead of PRIVATE_KEY (pop --use-wallet): pop serves a local signing portal and waits; its URL is sent as a progress notification and a 'wallet' log message and ends the result. Only over stdio. Requires execute=true"
+    )]
+    pub use_wallet: Option<bool>,
+
     /// Display chain metadata instead of executing a call.
     #[schemars(
         description = "Display chain metadata. Use alone to list all pallets, or with pallet to show pallet details (extrinsics, storage, constants). Cannot be used with function, args, sudo, or execute."
     )]
     pub metadata: Option<bool>,
+
+    /// Return the resolved command without running it.
+    #[schemars(
+        description = "Return the fully-resolved pop command (URL fallback and signer applied, secrets masked) without running it, so it can be reviewed first"
+    )]
+    pub dry_run: Option<bool>,
+
+    /// Block to query storage or constants at.
+    #[schemars(
+        description = "Query a storage item or constant as of this block (number or 0x hash) instead of the latest state. Read-only; older blocks need an archive node."
+    )]
+    pub at_block: Option<String>,
 }
 
 impl CallChainParams {
@@ -72,6 +95,9 @@ impl CallChainParams {
             if self.execute.unwrap_or(false) {
                 return Err("Cannot use 'execute' with metadata=true".to_owned());
             }
+            if self.use_wallet.unwrap_or(false) {
+                return Err("Cannot use 'use_wallet' with metadata=true".to_owned());
+            }
         } else {
             // In call mode, pallet and function are required
             if self.pallet.is_none() {
@@ -84,6 +110,15 @@ impl CallChainParams {
                 return Err("'execute' must be true when sudo=true".to_owned());
             }
         }
+        wallet::check(self.use_wallet, self.execute.unwrap_or(false), None)?;
+        if self.at_block.is_some()
+            && (metadata_mode || self.execute.unwrap_or(false) || self.dry_run.unwrap_or(false))
+        {
+            return Err(
+                "'at_block' only applies to storage and constant queries, not metadata, execute or dry_run"
+                    .to_owned(),
+            );
+        }
 
         Ok(())
     }
@@ -136,42 +171,200 @@ fn build_call_chain_args(params: &CallChainParams) -> Vec<String> {
 
 /// Check if output contains error indicators from pop CLI
 fn is_error_output(output: &str) -> bool {
-    let error_indicators = [
-        "Error:",
-        "error:",
-        "Failed to",
-        "failed to",
-        "Unable to",
-        "not found in pallet", // "Call with name X not found in pallet Y"
+    // "Call with name X not found in pallet Y"
+    parsing::has_error(output, &["not found in pallet"])
+}
+
+/// Submit pre-encoded call data (`pop call chain --call 0x...`), signed with PRIVATE_KEY.
+///
+/// Shared by tools that compose calls through the encoder (batches, scheduler, governance).
+pub(crate) async fn submit_call_data<E: CommandExecutor>(
+    executor: &E,
+    url: &str,
+    call_data: &str,
+    sudo: bool,
+) -> PopMcpResult<CallToolResult> {
+    let suri = crate::read_private_key_suri().ok_or_else(|| {
+        PopMcpError::InvalidInput(
+            "PRIVATE_KEY environment variable is required when execute=true".to_owned(),
+        )
+    })?;
+
+    let mut args = vec![
+        "call".to_owned(),
+        "chain".to_owned(),
+        "--url".to_owned(),
+        url.to_owned(),
+        "--call".to_owned(),
+        call_data.to_owned(),
     ];
-    error_indicators
-        .iter()
-        .any(|indicator| output.contains(indicator))
+    if sudo {
+        args.push("--sudo".to_owned());
+    }
+    args.push("-y".to_owned());
+    args.push("--suri".to_owned());
+    args.push(suri);
+
+    let (outcome, note) = execute_with_failover(executor, &args, false).await;
+    let result = match outcome {
+        Ok(output) if is_error_output(&output) => {
+            error_result(format!("Chain call failed:\n\n{}", output))
+        }
+        Ok(output) => success_result(format!("Chain call successful!\n\n{}", output)),
+        Err(e) => error_result(format!("Chain call failed: {}", e)),
+    };
+    Ok(with_note(result, note))
+}
+
+/// Hash of `block`, given as a number or a `0x` hash.
+async fn block_hash(pool: &RpcPool, url: &str, block: &str) -> Result<String, String> {
+    let block = block.trim();
+    if block.starts_with("0x") {
+        return Ok(block.to_owned());
+    }
+    let number = block
+        .trim_start_matches('#')
+        .parse::<u64>()
+        .map_err(|_| format!("'{}' is not a block number or 0x hash", block))?;
+    match pool
+        .request(url, "chain_getBlockHash", vec![number.into()])
+        .await
+    {
+        Ok(serde_json::Value::String(hash)) => Ok(hash),
+        Ok(_) => Err(format!("Block #{} does not exist yet", number)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Read `pallet.function` over RPC, as of block hash `at` (default: best), returning
+/// `None` when it is not a storage item or constant.
+async fn read_item(
+    pool: &RpcPool,
+    params: &CallChainParams,
+    at: Option<&str>,
+) -> Result<Option<String>, String> {
+    let (Some(pallet), Some(item)) = (params.pallet.as_deref(), params.function.as_deref()) else {
+        return Err("'pallet' and 'function' are required".to_owned());
+    };
+    let url = params.url.as_str();
+    let metadata = ChainMetadata::fetch_at(pool, url, at)
+        .await
+        .map_err(|e| e.to_string())?;
+    let args = params.args.as_deref().unwrap_or_default();
+    if metadata.has_storage(pallet, item) {
+        let key = metadata.storage_key(pallet, item, args)?;
+        let bytes = fetch_storage_at(pool, url, &key, at)
+            .await
+            .map_err(|e| e.to_string())?;
+        metadata
+            .decode_storage(pallet, item, bytes.as_deref())
+            .map(Some)
+    } else if metadata.has_constant(pallet, item) {
+        metadata.decode_constant(pallet, item).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Read `pallet.function` (a storage item or constant) at `block` over RPC.
+///
+/// Pop CLI only queries the latest state, so historical queries bypass it.
+async fn query_at_block(
+    pool: &RpcPool,
+    params: &CallChainParams,
+    block: &str,
+) -> Result<String, String> {
+    let hash = block_hash(pool, &params.url, block).await?;
+    let Some(value) = read_item(pool, params, Some(&hash)).await? else {
+        return Err(format!(
+            "{}.{} is not a storage item or constant at block {}",
+            params.pallet.as_deref().unwrap_or_default(),
+            params.function.as_deref().unwrap_or_default(),
+            block
+        ));
+    };
+    Ok(format!(
+        "{}.{} at block {} ({}):\n{}",
+        params.pallet.as_deref().unwrap_or_default(),
+        params.function.as_deref().unwrap_or_default(),
+        block,
+        hash,
+        value
+    ))
+}
+
+/// Read the latest value of a storage item or constant through the pooled connection,
+/// instead of spawning Pop CLI (which reconnects and refetches metadata every time).
+///
+/// `None` for extrinsics and whenever the RPC read fails (unreachable node, arguments
+/// the encoder can't parse...): Pop CLI then handles the call and reports its errors.
+async fn query_latest(pool: &RpcPool, params: &CallChainParams) -> Option<String> {
+    let value = read_item(pool, params, None).await.ok()??;
+    Some(format!(
+        "{}.{}:\n{}",
+        params.pallet.as_deref().unwrap_or_default(),
+        params.function.as_deref().unwrap_or_default(),
+        value
+    ))
 }
 
 /// Execute call_chain tool
-pub fn call_chain(executor: &PopExecutor, params: CallChainParams) -> PopMcpResult<CallToolResult> {
+pub async fn call_chain<E: CommandExecutor>(
+    executor: &E,
+    pool: &RpcPool,
+    params: CallChainParams,
+) -> PopMcpResult<CallToolResult> {
     params.validate().map_err(PopMcpError::InvalidInput)?;
 
+    if let Some(block) = params.at_block.as_deref() {
+        return Ok(match query_at_block(pool, &params, block).await {
+            Ok(value) => success_result(value),
+            Err(e) if e.contains("State already discarded") || e.contains("UnknownBlock") => {
+                error_result(format!(
+                    "Chain query failed: {}\n\nThe node no longer keeps state for that block; query an archive node (--pruning archive).",
+                    e
+                ))
+            }
+            Err(e) => error_result(format!("Chain query failed: {}", e)),
+        });
+    }
+
     let metadata_mode = params.metadata.unwrap_or(false);
+    let read_only =
+        !metadata_mode && !params.execute.unwrap_or(false) && !params.dry_run.unwrap_or(false);
+    if read_only {
+        if let Some(value) = query_latest(pool, &params).await {
+            return Ok(success_result(format!(
+                "Chain call successful!\n\n{}",
+                value
+            )));
+        }
+    }
+    let use_wallet = !metadata_mode && params.use_wallet.unwrap_or(false);
     // Read suri from PRIVATE_KEY environment variable
     let suri = crate::read_private_key_suri();
-    if params.execute.unwrap_or(false) && suri.is_none() {
+    if params.execute.unwrap_or(false) && !use_wallet && suri.is_none() {
         return Err(PopMcpError::InvalidInput(
             "PRIVATE_KEY environment variable is required when execute=true".to_owned(),
         ));
     }
 
     let mut args = build_call_chain_args(&params);
-    if !metadata_mode && params.execute.unwrap_or(false) {
+    if use_wallet {
+        args.push(wallet::FLAG.to_owned());
+    } else if !metadata_mode && params.execute.unwrap_or(false) {
         if let Some(suri) = suri {
             args.push("--suri".to_owned());
             args.push(suri);
         }
     }
-    let args_refs: Vec<&str> = args.iter().map(String::as_str).collect();
 
-    match executor.execute(&args_refs) {
+    if params.dry_run.unwrap_or(false) {
+        return Ok(dry_run_result(&args));
+    }
+
+    let (outcome, note) = execute_with_failover(executor, &args, false).await;
+    let result = match outcome {
         Ok(output) => {
             // In metadata mode, check for specific pallet not found error
             // In call mode, check for general error indicators
@@ -182,21 +375,16 @@ pub fn call_chain(executor: &PopExecutor, params: CallChainParams) -> PopMcpResu
             };
 
             if is_error {
-                Ok(error_result(format!("Chain call failed:\n\n{}", output)))
+                error_result(format!("Chain call failed:\n\n{}", output))
             } else if metadata_mode {
-                Ok(success_result(format!(
-                    "Chain metadata\n\n{}{}",
-                    output, TYPE_HINTS
-                )))
+                success_result(format!("Chain metadata\n\n{}{}", output, TYPE_HINTS))
             } else {
-                Ok(success_result(format!(
-                    "Chain call successful!\n\n{}",
-                    output
-                )))
+                success_result(format!("Chain call successful!\n\n{}", output))
             }
         }
-        Err(e) => Ok(error_result(format!("Chain call failed: {}", e))),
-    }
+        Err(e) => error_result(format!("Chain call failed: {}", e)),
+    };
+    Ok(with_note(result, note))
 }
 
 #[cfg(test)]
@@ -212,11 +400,76 @@ mod tests {
             args: None,
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: Some(true),
+            dry_run: None,
+            at_block: None,
+        };
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_at_block_with_execute() {
+        let mut params = CallChainParams {
+            url: "ws://localhost:9944".to_owned(),
+            pallet: Some("System".to_owned()),
+            function: Some("Number".to_owned()),
+            args: None,
+            sudo: None,
+            execute: None,
+            use_wallet: None,
+            metadata: None,
+            dry_run: None,
+            at_block: Some("100".to_owned()),
         };
+        assert!(params.validate().is_ok());
+        params.execute = Some(true);
         assert!(params.validate().is_err());
     }
 
+    #[tokio::test]
+    async fn at_block_must_be_a_number_or_hash() {
+        let params = CallChainParams {
+            url: "ws://localhost:9944".to_owned(),
+            pallet: Some("System".to_owned()),
+            function: Some("Number".to_owned()),
+            args: None,
+            sudo: None,
+            execute: None,
+            use_wallet: None,
+            metadata: None,
+            dry_run: None,
+            at_block: Some("yesterday".to_owned()),
+        };
+        let executor = crate::test_utils::MockExecutor::new();
+        let result = call_chain(&executor, &RpcPool::new(), params).await;
+        assert!(result.is_ok_and(|r| r.is_error == Some(true)
+            && crate::tools::common::content_text(&r).contains("not a block number")));
+        assert!(executor.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn queries_fall_back_to_pop_cli_without_rpc() -> anyhow::Result<()> {
+        let params = CallChainParams {
+            url: "ws://127.0.0.1:1".to_owned(),
+            pallet: Some("System".to_owned()),
+            function: Some("Number".to_owned()),
+            args: None,
+            sudo: None,
+            execute: None,
+            use_wallet: None,
+            metadata: None,
+            dry_run: None,
+            at_block: None,
+        };
+        let executor =
+            crate::test_utils::MockExecutor::new().on(&["call", "chain"], "Storage value: 7");
+        let result = call_chain(&executor, &RpcPool::new(), params).await?;
+        assert_eq!(result.is_error, Some(false));
+        assert_eq!(executor.calls().len(), 1);
+        Ok(())
+    }
+
     #[test]
     fn validate_rejects_args_with_metadata() {
         let params = CallChainParams {
@@ -226,7 +479,10 @@ mod tests {
             args: Some(vec!["arg1".to_owned()]),
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: Some(true),
+            dry_run: None,
+            at_block: None,
         };
         assert!(params.validate().is_err());
     }
@@ -240,11 +496,37 @@ mod tests {
             args: None,
             sudo: Some(true),
             execute: None,
+            use_wallet: None,
             metadata: Some(true),
+            dry_run: None,
+            at_block: None,
         };
         assert!(params.validate().is_err());
     }
 
+    #[test]
+    fn validate_rejects_use_wallet_without_execute() {
+        let params = CallChainParams {
+            url: "ws://localhost:9944".to_owned(),
+            pallet: Some("System".to_owned()),
+            function: Some("remark".to_owned()),
+            args: None,
+            sudo: None,
+            execute: None,
+            use_wallet: Some(true),
+            metadata: None,
+            dry_run: None,
+            at_block: None,
+        };
+        assert!(params.validate().is_err());
+        assert!(CallChainParams {
+            execute: Some(true),
+            ..params
+        }
+        .validate()
+        .is_ok());
+    }
+
     #[test]
     fn validate_rejects_execute_with_metadata() {
         let params = CallChainParams {
@@ -254,7 +536,10 @@ mod tests {
             args: None,
             sudo: None,
             execute: Some(true),
+            use_wallet: None,
             metadata: Some(true),
+            dry_run: None,
+            at_block: None,
         };
         assert!(params.validate().is_err());
     }
@@ -268,7 +553,10 @@ mod tests {
             args: None,
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: None,
+            dry_run: None,
+            at_block: None,
         };
         assert!(params.validate().is_err());
     }
@@ -282,7 +570,10 @@ mod tests {
             args: None,
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: None,
+            dry_run: None,
+            at_block: None,
         };
         assert!(params.validate().is_err());
     }
@@ -296,7 +587,10 @@ mod tests {
             args: None,
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: Some(true),
+            dry_run: None,
+            at_block: None,
         };
         assert!(params.validate().is_ok());
     }
@@ -310,7 +604,10 @@ mod tests {
             args: None,
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: Some(true),
+            dry_run: None,
+            at_block: None,
         };
         assert!(params.validate().is_ok());
     }
@@ -324,7 +621,10 @@ mod tests {
             args: Some(vec!["0x1234".to_owned()]),
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: None,
+            dry_run: None,
+            at_block: None,
         };
         assert!(params.validate().is_ok());
     }
@@ -338,7 +638,10 @@ mod tests {
             args: None,
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: Some(true),
+            dry_run: None,
+            at_block: None,
         };
         let args = build_call_chain_args(&params);
         assert_eq!(
@@ -362,7 +665,10 @@ mod tests {
             args: None,
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: Some(true),
+            dry_run: None,
+            at_block: None,
         };
         let args = build_call_chain_args(&params);
         assert_eq!(
@@ -390,7 +696,10 @@ mod tests {
             ]),
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: None,
+            dry_run: None,
+            at_block: None,
         };
         let args = build_call_chain_args(&params);
         assert_eq!(
@@ -420,7 +729,10 @@ mod tests {
             args: Some(vec!["0x1234".to_owned()]),
             sudo: Some(true),
             execute: Some(true),
+            use_wallet: None,
             metadata: None,
+            dry_run: None,
+            at_block: None,
         };
         let args = build_call_chain_args(&params);
         assert_eq!(
@@ -451,7 +763,10 @@ mod tests {
             args: None,
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: None,
+            dry_run: None,
+            at_block: None,
         };
         let args = build_call_chain_args(&params);
         assert_eq!(
@@ -0,0 +1,249 @@
+//! Collator helpers for parachain templates (session.set_keys, collator_selection)
+//!
+//! Registering a collator rotates session keys on the collator's own node, sets them
+//! on-chain and, when the runtime includes collator selection, registers as a candidate.
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::encoder::{
+    decode_hex, fetch_storage, storage_value_key, to_hex, CallArg, ChainMetadata,
+};
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::rpc::RpcPool;
+use crate::tools::call::chain::submit_call_data;
+use crate::tools::common::{error_result, success_result};
+
+/// Hosts `author_rotateKeys` may be sent to. The method is unsafe, so it is only
+/// issued against nodes on this machine.
+const KEY_ROTATION_HOSTS: &[&str] = &["localhost", "127.0.0.1", "[::1]"];
+
+/// Plain storage values reported by collator_selection_state, in display order.
+const COLLATOR_STORAGE: &[(&str, &str)] = &[
+    ("CollatorSelection", "Invulnerables"),
+    ("CollatorSelection", "CandidateList"),
+    ("CollatorSelection", "Candidates"),
+    ("CollatorSelection", "DesiredCandidates"),
+    ("CollatorSelection", "CandidacyBond"),
+    ("Session", "CurrentIndex"),
+    ("Session", "Validators"),
+];
+
+/// Parameters for the register_collator tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct RegisterCollatorParams {
+    /// WebSocket URL used to submit the extrinsics.
+    #[schemars(description = "WebSocket URL of the chain node (e.g., ws://localhost:9944)")]
+    pub url: String,
+    /// WebSocket URL of the collator node whose keys are rotated.
+    #[schemars(
+        description = "WebSocket URL of the new collator's node, used for author_rotateKeys (default: url; must be local)"
+    )]
+    pub collator_url: Option<String>,
+    /// Pre-generated session keys, instead of rotating.
+    #[schemars(description = "Session keys (0x...) to set instead of rotating new ones")]
+    pub keys: Option<String>,
+    /// Also call collator_selection.register_as_candidate.
+    #[schemars(
+        description = "Register as a collator candidate after setting keys (default: true when the runtime includes collator selection)"
+    )]
+    pub register_candidate: Option<bool>,
+    /// Submit the extrinsics; otherwise only the encoded call data is returned.
+    #[schemars(description = "Submit on-chain; when false only the encoded call data is returned")]
+    pub execute: Option<bool>,
+}
+
+/// Parameters for the collator_selection_state tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct CollatorSelectionStateParams {
+    /// WebSocket URL of the chain node.
+    #[schemars(description = "WebSocket URL of the chain node (e.g., ws://localhost:9944)")]
+    pub url: String,
+}
+
+/// Whether `url` points at a node on this machine.
+fn is_local_url(url: &str) -> bool {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split('/').next().unwrap_or_default();
+    let host = match authority.rsplit_once(':') {
+        Some((host, port)) if !port.contains(']') => host,
+        _ => authority,
+    };
+    KEY_ROTATION_HOSTS.contains(&host)
+}
+
+/// Execute register_collator tool
+pub async fn register_collator<E: CommandExecutor>(
+    executor: &E,
+    pool: &RpcPool,
+    params: RegisterCollatorParams,
+) -> PopMcpResult<CallToolResult> {
+    let collator_url = params.collator_url.as_deref().unwrap_or(&params.url);
+    if params.keys.is_none() && !is_local_url(collator_url) {
+        return Err(PopMcpError::InvalidInput(format!(
+            "author_rotateKeys is only sent to local nodes ({}); pass 'keys' for remote collators",
+            KEY_ROTATION_HOSTS.join(", ")
+        )));
+    }
+    let provided_keys = params
+        .keys
+        .as_deref()
+        .map(decode_hex)
+        .transpose()
+        .map_err(PopMcpError::InvalidInput)?;
+
+    let metadata = match ChainMetadata::fetch(pool, &params.url).await {
+        Ok(metadata) => metadata,
+        Err(e) => return Ok(error_result(format!("Register collator failed: {}", e))),
+    };
+    if !metadata.has_call("Session", "set_keys") {
+        return Ok(error_result(
+            "Register collator failed: the chain does not include the Session pallet",
+        ));
+    }
+    let has_selection = metadata.has_call("CollatorSelection", "register_as_candidate");
+    let register = params.register_candidate.unwrap_or(has_selection);
+    if register && !has_selection {
+        return Ok(error_result(
+            "Register collator failed: the chain does not include the CollatorSelection pallet",
+        ));
+    }
+
+    let keys = match provided_keys {
+        Some(keys) => keys,
+        None => {
+            let rotated = pool
+                .request(collator_url, "author_rotateKeys", Vec::new())
+                .await
+                .and_then(|value| {
+                    value
+                        .as_str()
+                        .map(decode_hex)
+                        .transpose()
+                        .map_err(PopMcpError::Rpc)
+                });
+            match rotated {
+                Ok(Some(keys)) => keys,
+                Ok(None) => {
+                    return Ok(error_result(
+                        "Register collator failed: author_rotateKeys returned no keys",
+                    ))
+                }
+                Err(e) => return Ok(error_result(format!("Register collator failed: {}", e))),
+            }
+        }
+    };
+
+    // `proof` is an empty `Vec<u8>` (compact length 0).
+    let mut calls = Vec::new();
+    let set_keys = metadata.encode_call_with(
+        "Session",
+        "set_keys",
+        &[CallArg::Raw(&keys), CallArg::Raw(&[0])],
+    );
+    calls.push(("session.set_keys", set_keys));
+    if register {
+        calls.push((
+            "collator_selection.register_as_candidate",
+            metadata.encode_call("CollatorSelection", "register_as_candidate", &[]),
+        ));
+    }
+
+    let mut encoded = Vec::new();
+    for (label, call) in calls {
+        match call {
+            Ok(call) => encoded.push((label, to_hex(&call))),
+            Err(e) => return Ok(error_result(format!("Register collator failed: {}", e))),
+        }
+    }
+
+    let summary = format!("Session keys: {}", to_hex(&keys));
+    if !params.execute.unwrap_or(false) {
+        let lines: Vec<String> = encoded
+            .iter()
+            .map(|(label, data)| format!("{}: {}", label, data))
+            .collect();
+        return Ok(success_result(format!(
+            "{}\nEncoded calls (submit in order):\n{}",
+            summary,
+            lines.join("\n")
+        )));
+    }
+
+    for (_, data) in &encoded {
+        let result = submit_call_data(executor, &params.url, data, false).await?;
+        if result.is_error == Some(true) {
+            return Ok(result);
+        }
+    }
+    let registered = if register {
+        "Keys set and candidate registered."
+    } else {
+        "Keys set."
+    };
+    Ok(success_result(format!("{}\n{}", registered, summary)))
+}
+
+/// Execute collator_selection_state tool
+pub async fn collator_selection_state(
+    pool: &RpcPool,
+    params: CollatorSelectionStateParams,
+) -> PopMcpResult<CallToolResult> {
+    let metadata = match ChainMetadata::fetch(pool, &params.url).await {
+        Ok(metadata) => metadata,
+        Err(e) => return Ok(error_result(format!("Collator query failed: {}", e))),
+    };
+    if !metadata.has_pallet("CollatorSelection") {
+        return Ok(error_result(
+            "Collator query failed: the chain does not include the CollatorSelection pallet",
+        ));
+    }
+
+    let mut lines = Vec::new();
+    for (pallet, item) in COLLATOR_STORAGE {
+        if !metadata.has_storage(pallet, item) {
+            continue;
+        }
+        let rendered =
+            match fetch_storage(pool, &params.url, &storage_value_key(pallet, item)).await {
+                Ok(bytes) => metadata
+                    .decode_storage(pallet, item, bytes.as_deref())
+                    .unwrap_or_else(|e| format!("<{}>", e)),
+                Err(e) => return Ok(error_result(format!("Collator query failed: {}", e))),
+            };
+        lines.push(format!("{}.{}: {}", pallet, item, rendered));
+    }
+    Ok(success_result(lines.join("\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::PopExecutor;
+
+    #[test]
+    fn is_local_url_accepts_only_loopback_hosts() {
+        assert!(is_local_url("ws://localhost:9944"));
+        assert!(is_local_url("ws://127.0.0.1:9944/"));
+        assert!(is_local_url("ws://[::1]:9944"));
+        assert!(!is_local_url("wss://rpc.polkadot.io"));
+        assert!(!is_local_url("ws://localhost.example.com:9944"));
+    }
+
+    #[tokio::test]
+    async fn register_collator_rejects_remote_key_rotation() {
+        let params = RegisterCollatorParams {
+            url: "wss://rpc.example.com".to_owned(),
+            collator_url: None,
+            keys: None,
+            register_candidate: None,
+            execute: None,
+        };
+        let result = register_collator(&PopExecutor::new(), &RpcPool::new(), params).await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+    }
+}
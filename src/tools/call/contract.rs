@@ -3,10 +3,20 @@
 use rmcp::model::CallToolResult;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
+use crate::encoder::typed_json;
+use crate::endpoints::execute_with_failover;
 use crate::error::{PopMcpError, PopMcpResult};
-use crate::executor::PopExecutor;
-use crate::tools::common::{error_result, success_result};
+use crate::executor::CommandExecutor;
+use crate::outputs::{BuildOutput, CallOutput};
+use crate::parsing;
+use crate::tools::ci::{self, CiReport, CiStage};
+use crate::tools::common::{
+    dry_run_result, error_result, load_args_file, success_result, summary_block, with_note,
+    with_structured, with_summary,
+};
+use crate::wallet;
 
 /// Parameters for the call_contract tool.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
@@ -28,15 +38,66 @@ pub struct CallContractParams {
         deserialize_with = "crate::tools::common::deserialize_stringy_bool"
     )]
     pub args: Option<String>,
+    /// JSON/TOML file with method arguments, as an alternative to `args`.
+    #[schemars(
+        description = "Method arguments from a JSON array or TOML `args = [...]` file inside the project (relative to path); use instead of args for large values"
+    )]
+    pub args_file: Option<String>,
     /// Value to transfer with the call (in tokens).
     #[schemars(description = "Value to transfer with the call (in tokens)")]
     pub value: Option<String>,
+    /// Gas (`ref_time`) limit of the call.
+    #[schemars(
+        description = "Maximum gas (ref_time) for the call, passed as --gas; set together with proof_size to skip the dry-run estimation (e.g. when a call needs more than estimated)"
+    )]
+    pub gas_limit: Option<u64>,
+    /// Proof size limit of the call.
+    #[schemars(
+        description = "Maximum proof size for the call, passed as --proof-size; set together with gas_limit"
+    )]
+    pub proof_size: Option<u64>,
     /// Whether to submit an extrinsic for on-chain execution.
     #[schemars(description = "Submit an extrinsic for on-chain execution")]
     pub execute: Option<bool>,
     /// WebSocket URL of the node.
     #[schemars(description = "WebSocket URL of the node")]
     pub url: Option<String>,
+    /// Put a compact summary block before the full output.
+    #[schemars(
+        description = "Return a compact 'key: value' summary block as the first content item and the full output as the second"
+    )]
+    pub summary: Option<bool>,
+    /// Return the resolved command without running it.
+    #[schemars(
+        description = "Return the fully-resolved pop command (URL fallback and signer applied, secrets masked) without running it, so it can be reviewed first"
+    )]
+    pub dry_run: Option<bool>,
+    /// Run as a CI step.
+    #[schemars(
+        description = "CI mode: never prompt or fall back to other endpoints; requires url and signer, and returns a single JSON report (failures included)"
+    )]
+    pub ci_mode: Option<bool>,
+    /// Keystore alias of the signer.
+    #[schemars(
+        description = "Keystore alias of the signer: its SURI is read from the POP_MCP_KEY_<ALIAS> environment variable instead of PRIVATE_KEY (required in ci_mode)"
+    )]
+    pub signer: Option<String>,
+    /// Sign with a browser wallet instead of a SURI.
+    #[schemars(
+        description = "Sign with a browser wallet extension instead of PRIVATE_KEY (pop --use-wallet): pop serves a local signing portal and waits; its URL is sent as a progress notification and a 'wallet' log message and ends the result. Only over stdio. Requires execute=true; not allowed with signer or ci_mode"
+    )]
+    pub use_wallet: Option<bool>,
+}
+
+/// Pop only skips the dry-run estimation when both weight limits are given.
+fn check_weight_limits(params: &CallContractParams) -> Result<(), String> {
+    if params.gas_limit.is_some() != params.proof_size.is_some() {
+        return Err(
+            "Set gas_limit and proof_size together; pop estimates both when either is missing"
+                .to_owned(),
+        );
+    }
+    Ok(())
 }
 
 /// Build command arguments for call_contract
@@ -66,6 +127,16 @@ fn build_call_contract_args(params: &CallContractParams) -> Vec<String> {
         args.push(value.clone());
     }
 
+    if let Some(gas) = params.gas_limit {
+        args.push("--gas".to_owned());
+        args.push(gas.to_string());
+    }
+
+    if let Some(proof_size) = params.proof_size {
+        args.push("--proof-size".to_owned());
+        args.push(proof_size.to_string());
+    }
+
     if let Some(ref url) = params.url {
         args.push("--url".to_owned());
         args.push(url.clone());
@@ -78,63 +149,221 @@ fn build_call_contract_args(params: &CallContractParams) -> Vec<String> {
     args
 }
 
-/// Check if output contains error indicators from pop CLI
-fn is_error_output(output: &str) -> bool {
-    let error_indicators = [
-        "Unable to",
-        "Error:",
-        "error:",
-        "Failed to",
-        "failed to",
-        "not connected",
-        "Contract not found",
-    ];
-    error_indicators
+/// `result` of `message` typed by its return type in the ink! metadata `metadata`.
+fn typed_return(metadata: &Value, message: &str, result: &str) -> Option<Value> {
+    let ty = metadata
+        .pointer("/spec/messages")?
+        .as_array()?
         .iter()
-        .any(|indicator| output.contains(indicator))
+        .find(|m| m.get("label").and_then(Value::as_str) == Some(message))?
+        .pointer("/returnType/type")?
+        .as_u64()?;
+    let types: scale_info::PortableRegistry = serde_json::from_value(metadata.clone()).ok()?;
+    typed_json(result, u32::try_from(ty).ok()?, &types).ok()
+}
+
+/// Parse the call `output`, typing the return value with the metadata built in
+/// `params.path`, when there is one.
+fn call_output(params: &CallContractParams, output: &str) -> CallOutput {
+    let mut call = CallOutput::parse(output, params.execute.unwrap_or(false));
+    call.value = call.result.as_deref().and_then(|result| {
+        let file = BuildOutput::collect(&params.path, false).metadata?;
+        let metadata = serde_json::from_str(&std::fs::read_to_string(file.path).ok()?).ok()?;
+        typed_return(&metadata, &params.message, result)
+    });
+    call
+}
+
+/// Check if output contains error indicators from pop CLI
+pub(crate) fn is_error_output(output: &str) -> bool {
+    parsing::has_error(output, &["not connected", "Contract not found"])
 }
 
 /// Execute call_contract tool
-pub fn call_contract(
-    executor: &PopExecutor,
+pub async fn call_contract<E: CommandExecutor>(
+    executor: &E,
     params: CallContractParams,
 ) -> PopMcpResult<CallToolResult> {
-    // Read suri from PRIVATE_KEY environment variable
-    let suri = crate::read_private_key_suri();
-    if params.execute.unwrap_or(false) && suri.is_none() {
+    if params.ci_mode.unwrap_or(false) {
+        return Ok(call_contract_ci(executor, params).await);
+    }
+    let use_wallet = params.use_wallet.unwrap_or(false);
+    wallet::check(
+        params.use_wallet,
+        params.execute.unwrap_or(false),
+        params.signer.as_deref(),
+    )
+    .map_err(PopMcpError::InvalidInput)?;
+    check_weight_limits(&params).map_err(PopMcpError::InvalidInput)?;
+    // Read suri from keystore alias or PRIVATE_KEY environment variable
+    let suri = match params.signer.as_deref() {
+        Some(alias) => Some(ci::signer_suri(Some(alias)).map_err(PopMcpError::InvalidInput)?),
+        None if use_wallet => None,
+        None => crate::read_private_key_suri(),
+    };
+    if params.execute.unwrap_or(false) && !use_wallet && suri.is_none() {
         return Err(PopMcpError::InvalidInput(
             "PRIVATE_KEY environment variable is required when execute=true".to_owned(),
         ));
     }
+    let file_args = match params.args_file.as_deref() {
+        Some(_) if params.args.is_some() => {
+            return Err(PopMcpError::InvalidInput(
+                "Provide either 'args' or 'args_file', not both".to_owned(),
+            ));
+        }
+        Some(file) => load_args_file(executor.working_dir(), &params.path, file)
+            .map_err(PopMcpError::InvalidInput)?,
+        None => Vec::new(),
+    };
     let mut args = build_call_contract_args(&params);
-    if params.execute.unwrap_or(false) {
+    if !file_args.is_empty() {
+        args.push("--args".to_owned());
+        args.extend(file_args);
+    }
+    if use_wallet {
+        args.push(wallet::FLAG.to_owned());
+    } else if params.execute.unwrap_or(false) {
         if let Some(suri) = suri {
             args.push("--suri".to_owned());
             args.push(suri);
         }
     }
-    let args_refs: Vec<&str> = args.iter().map(String::as_str).collect();
-
-    match executor.execute(&args_refs) {
+    if params.dry_run.unwrap_or(false) {
+        return Ok(dry_run_result(&args));
+    }
+    let (outcome, note) = execute_with_failover(executor, &args, true).await;
+    let summary = params
+        .summary
+        .unwrap_or(false)
+        .then(|| call_summary(&params, &outcome));
+    let result = match outcome {
         Ok(output) => {
             // Check if the output contains error indicators even if exit code was 0
             if is_error_output(&output) {
-                Ok(error_result(format!("Contract call failed:\n\n{}", output)))
+                error_result(format!("Contract call failed:\n\n{}", output))
             } else {
-                Ok(success_result(format!(
-                    "Contract call successful!\n\n{}",
-                    output
-                )))
+                let call = call_output(&params, &output);
+                with_structured(
+                    success_result(format!("Contract call successful!\n\n{}", output)),
+                    &call,
+                )
             }
         }
-        Err(e) => Ok(error_result(format!("Contract call failed: {}", e))),
+        Err(e) => error_result(format!("Contract call failed: {}", e)),
+    };
+    let result = with_note(result, note);
+    Ok(match summary {
+        Some(summary) => with_summary(result, summary),
+        None => result,
+    })
+}
+
+/// Call as a CI step: validate, call, report.
+async fn call_contract_ci<E: CommandExecutor>(
+    executor: &E,
+    params: CallContractParams,
+) -> CallToolResult {
+    let mut report = CiReport::new("call_contract", params.signer.as_deref());
+    let suri = match ci::signer_suri(params.signer.as_deref()) {
+        Ok(suri) => suri,
+        Err(e) => return report.fail(CiStage::Validate, e),
+    };
+    if params.use_wallet.unwrap_or(false) {
+        return report.fail(CiStage::Validate, wallet::CI_ERROR);
+    }
+    if params.url.is_none() {
+        return report.fail(CiStage::Validate, "ci_mode requires url");
+    }
+    if let Err(e) = check_weight_limits(&params) {
+        return report.fail(CiStage::Validate, e);
+    }
+    if params.dry_run.unwrap_or(false) {
+        return report.fail(CiStage::Validate, "dry_run is not available in ci_mode");
+    }
+    let file_args = match params.args_file.as_deref() {
+        Some(_) if params.args.is_some() => {
+            return report.fail(
+                CiStage::Validate,
+                "Provide either 'args' or 'args_file', not both",
+            );
+        }
+        Some(file) => match load_args_file(executor.working_dir(), &params.path, file) {
+            Ok(args) => args,
+            Err(e) => return report.fail(CiStage::Validate, e),
+        },
+        None => Vec::new(),
+    };
+    let mut args = build_call_contract_args(&params);
+    if !file_args.is_empty() {
+        args.push("--args".to_owned());
+        args.extend(file_args);
+    }
+    args.push("--suri".to_owned());
+    args.push(suri);
+    let output = match ci::execute(executor, &mut report, &args).await {
+        Ok(output) => output,
+        Err(failed) => return failed,
+    };
+    if is_error_output(&output) {
+        return report.fail(CiStage::Execute, output);
     }
+    report.succeed(&call_output(&params, &output))
+}
+
+/// Summary block for a contract call outcome.
+fn call_summary(params: &CallContractParams, outcome: &PopMcpResult<String>) -> String {
+    let (status, result, error) = match outcome {
+        Ok(output) if is_error_output(output) => ("failed", None, Some(output.clone())),
+        Ok(output) => ("ok", parsing::call_result(output), None),
+        Err(e) => ("failed", None, Some(e.to_string())),
+    };
+    let executed = if params.execute.unwrap_or(false) {
+        "yes"
+    } else {
+        "no (dry run)"
+    };
+    summary_block(&[
+        ("status", Some(status.to_owned())),
+        ("contract", Some(params.contract.clone())),
+        ("message", Some(params.message.clone())),
+        ("executed", Some(executed.to_owned())),
+        ("result", result),
+        ("url", params.url.clone()),
+        ("error", error),
+    ])
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn typed_return_uses_the_message_return_type() {
+        let metadata = serde_json::json!({
+            "spec": {
+                "constructors": [],
+                "messages": [{ "label": "get", "args": [], "returnType": { "type": 1 } }]
+            },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "bool" } } },
+                { "id": 1, "type": { "path": ["Result"], "def": { "variant": { "variants": [
+                    { "name": "Ok", "index": 0, "fields": [{ "type": 0 }] },
+                    { "name": "Err", "index": 1, "fields": [{ "type": 2 }] }
+                ] } } } },
+                { "id": 2, "type": { "def": { "variant": { "variants": [
+                    { "name": "CouldNotReadInput", "index": 1 }
+                ] } } } }
+            ]
+        });
+        assert_eq!(
+            typed_return(&metadata, "get", "Ok(false)"),
+            Some(serde_json::json!({ "Ok": false }))
+        );
+        assert_eq!(typed_return(&metadata, "flip", "Ok(())"), None);
+        assert_eq!(typed_return(&metadata, "get", "false"), None);
+    }
+
     #[test]
     fn build_args_minimal() {
         let params = CallContractParams {
@@ -142,9 +371,17 @@ mod tests {
             contract: "0x1234".to_owned(),
             message: "get".to_owned(),
             args: None,
+            args_file: None,
             value: None,
+            gas_limit: None,
+            proof_size: None,
             execute: None,
             url: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
         };
         let args = build_call_contract_args(&params);
         assert_eq!(
@@ -170,9 +407,17 @@ mod tests {
             contract: "0xabc".to_owned(),
             message: "transfer".to_owned(),
             args: Some("0x5678 100".to_owned()),
+            args_file: None,
             value: Some("10".to_owned()),
+            gas_limit: None,
+            proof_size: None,
             execute: Some(true),
             url: Some("ws://localhost:9944".to_owned()),
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
         };
         let args = build_call_contract_args(&params);
         assert_eq!(
@@ -198,4 +443,115 @@ mod tests {
             ]
         );
     }
+
+    #[tokio::test]
+    async fn weight_limits_are_forwarded_together() -> anyhow::Result<()> {
+        let params = CallContractParams {
+            path: "./p".to_owned(),
+            contract: "0xabc".to_owned(),
+            message: "flip".to_owned(),
+            args: None,
+            args_file: None,
+            value: None,
+            gas_limit: Some(50_000_000_000),
+            proof_size: Some(1_048_576),
+            execute: None,
+            url: None,
+            summary: None,
+            dry_run: Some(true),
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+        };
+        let args = build_call_contract_args(&params);
+        assert_eq!(
+            args[9..],
+            ["--gas", "50000000000", "--proof-size", "1048576"]
+        );
+        let executor = crate::test_utils::MockExecutor::new();
+        let result = call_contract(&executor, params.clone()).await?;
+        assert_eq!(result.is_error, Some(false));
+
+        let proof_only = CallContractParams {
+            gas_limit: None,
+            ..params
+        };
+        let result = call_contract(&executor, proof_only).await;
+        assert!(
+            matches!(result, Err(PopMcpError::InvalidInput(e)) if e.starts_with("Set gas_limit and proof_size together"))
+        );
+        assert!(executor.calls().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn call_summary_reports_result() {
+        let params = CallContractParams {
+            path: "./flipper".to_owned(),
+            contract: "0x58".to_owned(),
+            message: "get".to_owned(),
+            args: None,
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: None,
+            url: None,
+            summary: Some(true),
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+        };
+        let output = Ok("⚙  Result: Ok(false)\n└  Call completed successfully!".to_owned());
+        assert_eq!(
+            call_summary(&params, &output),
+            "status: ok\ncontract: 0x58\nmessage: get\nexecuted: no (dry run)\nresult: Ok(false)"
+        );
+        let failed = Ok("Error: Contract not found".to_owned());
+        let summary = call_summary(&params, &failed);
+        assert!(summary.starts_with("status: failed\n"));
+        assert!(summary.ends_with("error: Error: Contract not found"));
+    }
+
+    #[tokio::test]
+    async fn ci_call_failures_are_json_reports() -> anyhow::Result<()> {
+        let executor = crate::test_utils::MockExecutor::new()
+            .on_failure(&["call", "contract"], "Error: Not connected");
+        crate::test_utils::set_env("POP_MCP_KEY_CALL_CONTRACT_TEST", "//Bob");
+        let mut params = CallContractParams {
+            path: "./flipper".to_owned(),
+            contract: "0x58".to_owned(),
+            message: "flip".to_owned(),
+            args: None,
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: Some(true),
+            url: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: Some(true),
+            signer: Some("call-contract-test".to_owned()),
+            use_wallet: None,
+        };
+        let result = call_contract(&executor, params.clone()).await?;
+        let report: Value = serde_json::from_str(&crate::tools::common::content_text(&result))?;
+        assert_eq!(
+            (report["ok"].clone(), report["stage"].clone()),
+            (false.into(), "validate".into())
+        );
+        assert!(executor.calls().is_empty());
+
+        params.url = Some("ws://localhost:9944".to_owned());
+        let result = call_contract(&executor, params).await?;
+        assert_eq!(result.is_error, Some(true));
+        let report = result.structured_content.unwrap_or_default();
+        assert_eq!(report["stage"], "execute");
+        assert!(report["error"]
+            .as_str()
+            .is_some_and(|e| e.contains("Not connected")));
+        Ok(())
+    }
 }
@@ -0,0 +1,306 @@
+//! Sequences of contract calls
+//!
+//! Setting up a contract for a test or demo usually takes several messages in a row
+//! (mint, approve, transfer, ...). This tool runs them against one contract with
+//! `pop call contract`, one after another, and reports every call's outcome, so the
+//! setup is a single tool invocation.
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::outputs::{BatchCallResult, CallOutput, ContractBatchOutput};
+use crate::tools::call::contract::{call_contract, CallContractParams};
+use crate::tools::ci;
+use crate::tools::common::{error_result, extract_texts, success_result, with_structured};
+
+/// One message of a contract call batch.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct ContractBatchCall {
+    /// Message/method to call on the contract.
+    #[schemars(description = "Message/method to call")]
+    pub message: String,
+    /// Method arguments as space-separated values.
+    #[schemars(description = "Method arguments as space-separated values")]
+    pub args: Option<String>,
+    /// Value to transfer with the call (in tokens).
+    #[schemars(description = "Value to transfer with the call (in tokens)")]
+    pub value: Option<String>,
+    /// Whether to submit an extrinsic for on-chain execution.
+    #[schemars(
+        description = "Submit an extrinsic for on-chain execution; otherwise the message is only dry-run"
+    )]
+    pub execute: Option<bool>,
+}
+
+/// Parameters for the call_contract_batch tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct CallContractBatchParams {
+    /// Path to the contract directory (needed for metadata).
+    #[schemars(description = "Path to the contract directory (needed for contract metadata)")]
+    pub path: String,
+    /// Contract address to call.
+    #[schemars(description = "Contract address")]
+    pub contract: String,
+    /// Calls to make, in order.
+    #[schemars(description = "Calls to make, in order")]
+    pub calls: Vec<ContractBatchCall>,
+    /// WebSocket URL of the node.
+    #[schemars(description = "WebSocket URL of the node")]
+    pub url: Option<String>,
+    /// Keystore alias of the signer.
+    #[schemars(
+        description = "Keystore alias of the signer: its SURI is read from the POP_MCP_KEY_<ALIAS> environment variable instead of PRIVATE_KEY"
+    )]
+    pub signer: Option<String>,
+    /// Stop at the first failed call (default: true).
+    #[schemars(
+        description = "Stop at the first failed call and skip the rest (default: true); false runs every call"
+    )]
+    pub stop_on_failure: Option<bool>,
+}
+
+impl CallContractBatchParams {
+    fn validate(&self) -> Result<(), String> {
+        if self.calls.is_empty() {
+            return Err("At least one call is required".to_owned());
+        }
+        let executes = self.calls.iter().any(|call| call.execute.unwrap_or(false));
+        match self.signer.as_deref() {
+            Some(alias) => ci::signer_suri(Some(alias)).map(drop),
+            None if executes && crate::read_private_key_suri().is_none() => Err(
+                "PRIVATE_KEY environment variable is required when a call has execute=true"
+                    .to_owned(),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// `call_contract` parameters of `call`.
+    fn call_params(&self, call: &ContractBatchCall) -> CallContractParams {
+        CallContractParams {
+            path: self.path.clone(),
+            contract: self.contract.clone(),
+            message: call.message.clone(),
+            args: call.args.clone(),
+            args_file: None,
+            value: call.value.clone(),
+            gas_limit: None,
+            proof_size: None,
+            execute: call.execute,
+            url: self.url.clone(),
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: self.signer.clone(),
+            use_wallet: None,
+        }
+    }
+}
+
+/// Outcome of one call from the `call_contract` `result`.
+fn call_result(message: &str, result: &CallToolResult, output: &str) -> BatchCallResult {
+    let call = result
+        .structured_content
+        .clone()
+        .and_then(|value| serde_json::from_value::<CallOutput>(value).ok());
+    let ok = result.is_error != Some(true);
+    BatchCallResult {
+        message: message.to_owned(),
+        ok,
+        executed: call.as_ref().is_some_and(|call| call.executed),
+        result: call.as_ref().and_then(|call| call.result.clone()),
+        value: call.and_then(|call| call.value),
+        error: (!ok).then(|| output.to_owned()),
+    }
+}
+
+/// One line per call of `batch`, for the text result.
+fn describe(batch: &ContractBatchOutput) -> String {
+    let mut lines: Vec<String> = batch
+        .calls
+        .iter()
+        .enumerate()
+        .map(|(i, call)| {
+            let outcome = match (call.ok, call.result.as_deref()) {
+                (false, _) => "failed".to_owned(),
+                (true, Some(result)) => format!("ok, {}", result),
+                (true, None) => "ok".to_owned(),
+            };
+            let mode = if call.executed { "executed" } else { "dry run" };
+            format!("{}. {} ({}): {}", i + 1, call.message, mode, outcome)
+        })
+        .collect();
+    if batch.skipped > 0 {
+        lines.push(format!(
+            "Skipped {} call(s) after the failure.",
+            batch.skipped
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Execute call_contract_batch tool
+pub async fn call_contract_batch<E: CommandExecutor>(
+    executor: &E,
+    params: CallContractBatchParams,
+) -> PopMcpResult<CallToolResult> {
+    params.validate().map_err(PopMcpError::InvalidInput)?;
+    let stop_on_failure = params.stop_on_failure.unwrap_or(true);
+    let mut batch = ContractBatchOutput {
+        calls: Vec::new(),
+        skipped: 0,
+    };
+    let mut outputs = Vec::new();
+    for (i, call) in params.calls.iter().enumerate() {
+        let (result, output) = match call_contract(executor, params.call_params(call)).await {
+            Ok(result) => {
+                let output = extract_texts(&result).join("\n");
+                (call_result(&call.message, &result, &output), output)
+            }
+            Err(e) => {
+                let output = e.to_string();
+                (
+                    BatchCallResult {
+                        message: call.message.clone(),
+                        ok: false,
+                        executed: false,
+                        result: None,
+                        value: None,
+                        error: Some(output.clone()),
+                    },
+                    output,
+                )
+            }
+        };
+        let failed = !result.ok;
+        batch.calls.push(result);
+        outputs.push(format!("--- {}. {} ---\n{}", i + 1, call.message, output));
+        if failed && stop_on_failure {
+            batch.skipped = params.calls.len() - i - 1;
+            break;
+        }
+    }
+    let failures = batch.calls.iter().filter(|call| !call.ok).count();
+    let text = format!("{}\n\n{}", describe(&batch), outputs.join("\n\n"));
+    let result = if failures == 0 {
+        success_result(format!(
+            "All {} contract calls succeeded:\n{}",
+            batch.calls.len(),
+            text
+        ))
+    } else {
+        error_result(format!(
+            "{} of {} contract calls failed:\n{}",
+            failures,
+            params.calls.len(),
+            text
+        ))
+    };
+    Ok(with_structured(result, &batch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutor;
+
+    fn call(message: &str, execute: bool) -> ContractBatchCall {
+        ContractBatchCall {
+            message: message.to_owned(),
+            args: None,
+            value: None,
+            execute: Some(execute),
+        }
+    }
+
+    fn params(calls: Vec<ContractBatchCall>) -> CallContractBatchParams {
+        CallContractBatchParams {
+            path: "./token".to_owned(),
+            contract: "0x58".to_owned(),
+            calls,
+            url: None,
+            signer: None,
+            stop_on_failure: None,
+        }
+    }
+
+    fn output(result: &CallToolResult) -> anyhow::Result<ContractBatchOutput> {
+        Ok(serde_json::from_value(
+            result.structured_content.clone().unwrap_or_default(),
+        )?)
+    }
+
+    #[tokio::test]
+    async fn calls_run_in_order() -> anyhow::Result<()> {
+        let executor = MockExecutor::new().on(&["call", "contract"], "⚙  Result: Ok(())");
+        let mut params = params(vec![call("mint", false), call("total_supply", false)]);
+        params.calls[0].args = Some("100".to_owned());
+        let result = call_contract_batch(&executor, params).await?;
+        assert_eq!(result.is_error, Some(false));
+        let batch = output(&result)?;
+        assert_eq!(batch.calls.len(), 2);
+        assert!(batch.calls.iter().all(|call| call.ok));
+        assert_eq!(batch.calls[0].result.as_deref(), Some("Ok(())"));
+        let calls = executor.calls();
+        assert_eq!(calls.len(), 2);
+        assert!(calls[0].args.contains(&"mint".to_owned()));
+        assert!(calls[0].args.contains(&"100".to_owned()));
+        assert!(calls[1].args.contains(&"total_supply".to_owned()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn failures_stop_the_batch_unless_asked_not_to() -> anyhow::Result<()> {
+        let executor = MockExecutor::new()
+            .on(&["call", "contract"], "⚙  Result: Ok(())")
+            .on_failure(
+                &[
+                    "call",
+                    "contract",
+                    "--path",
+                    "./token",
+                    "--contract",
+                    "0x58",
+                    "--message",
+                    "approve",
+                ],
+                "Error: Contract reverted",
+            );
+        let calls = vec![
+            call("mint", false),
+            call("approve", false),
+            call("get", false),
+        ];
+        let result = call_contract_batch(&executor, params(calls.clone())).await?;
+        assert_eq!(result.is_error, Some(true));
+        let batch = output(&result)?;
+        assert_eq!(batch.calls.len(), 2);
+        assert!(!batch.calls[1].ok);
+        assert_eq!(batch.skipped, 1);
+
+        let mut params = params(calls);
+        params.stop_on_failure = Some(false);
+        let batch = output(&call_contract_batch(&executor, params).await?)?;
+        assert_eq!(batch.calls.len(), 3);
+        assert!(batch.calls[2].ok);
+        assert_eq!(batch.skipped, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validation_runs_before_any_call() {
+        let executor = MockExecutor::new();
+        let result = call_contract_batch(&executor, params(Vec::new())).await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+        let mut params = params(vec![call("mint", true)]);
+        params.signer = Some("missing-batch-signer".to_owned());
+        let result = call_contract_batch(&executor, params).await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+        assert!(executor.calls().is_empty());
+    }
+}
@@ -0,0 +1,475 @@
+//! Storage diffs around a call on a local node
+//!
+//! The selected storage entries (and, for contract calls, the contract's child trie)
+//! are read over RPC before and after the call is executed through Pop CLI.
+
+use std::collections::BTreeMap;
+
+use codec::Decode;
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::encoder::{decode_hex, fetch_storage_at, to_hex, ChainMetadata};
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::rpc::RpcPool;
+use crate::tools::call::chain::{call_chain, CallChainParams};
+use crate::tools::call::contract::{call_contract, CallContractParams};
+use crate::tools::call::stress::is_local_url;
+use crate::tools::common::{error_result, extract_texts, success_result};
+
+/// Prefix of a contract's child trie key; the contract's trie id follows.
+const CHILD_STORAGE_PREFIX: &[u8] = b":child_storage:default:";
+
+/// Most contract storage keys compared.
+const MAX_CONTRACT_KEYS: u32 = 1000;
+
+/// A storage entry to snapshot.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct StorageSelector {
+    /// Pallet name.
+    #[schemars(description = "Pallet name (e.g., 'System')")]
+    pub pallet: String,
+    /// Storage item name.
+    #[schemars(description = "Storage item name (e.g., 'Account')")]
+    pub item: String,
+    /// Map keys, one per hasher.
+    #[schemars(
+        description = "Map keys, one per key of the item (e.g., ['5GrwvaEF...'] for System.Account); omit for plain values"
+    )]
+    pub keys: Option<Vec<String>>,
+}
+
+impl StorageSelector {
+    /// `Pallet.Item(key, ...)`.
+    fn label(&self) -> String {
+        match self.keys.as_deref() {
+            Some(keys) if !keys.is_empty() => {
+                format!("{}.{}({})", self.pallet, self.item, keys.join(", "))
+            }
+            _ => format!("{}.{}", self.pallet, self.item),
+        }
+    }
+}
+
+/// Parameters for the state_diff_call tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct StateDiffCallParams {
+    /// WebSocket URL of a local node.
+    #[schemars(
+        description = "WebSocket URL of a local node (default: the node started by up_ink_node). Shared networks are refused."
+    )]
+    pub url: Option<String>,
+    /// Pallet of a chain call.
+    #[schemars(description = "Pallet of the chain call to execute (e.g., 'Balances')")]
+    pub pallet: Option<String>,
+    /// Function of a chain call.
+    #[schemars(
+        description = "Function of the chain call to execute (e.g., 'transfer_keep_alive')"
+    )]
+    pub function: Option<String>,
+    /// Call arguments.
+    #[schemars(description = "Arguments of the chain call or contract message")]
+    pub args: Option<Vec<String>>,
+    /// Path to the contract directory (needed for metadata).
+    #[schemars(description = "Path to the contract directory, for a contract call")]
+    pub path: Option<String>,
+    /// Contract address to call.
+    #[schemars(
+        description = "Contract address, for a contract call; its storage is compared as well"
+    )]
+    pub contract: Option<String>,
+    /// Message to call on the contract.
+    #[schemars(description = "Message to call, for a contract call")]
+    pub message: Option<String>,
+    /// Value to transfer with a contract call (in tokens).
+    #[schemars(description = "Value to transfer with the contract call (in tokens)")]
+    pub value: Option<String>,
+    /// Storage entries to compare.
+    #[schemars(
+        description = "Storage entries to snapshot before and after the call (required for chain calls)"
+    )]
+    pub storage: Option<Vec<StorageSelector>>,
+}
+
+/// The call a diff is taken around.
+enum DiffedCall<'a> {
+    Chain {
+        pallet: &'a str,
+        function: &'a str,
+    },
+    Contract {
+        path: &'a str,
+        contract: &'a str,
+        message: &'a str,
+    },
+}
+
+impl StateDiffCallParams {
+    /// Validate the parameters and pick the call to execute.
+    fn call(&self) -> Result<DiffedCall<'_>, String> {
+        let chain = (self.pallet.as_deref(), self.function.as_deref());
+        let contract = (
+            self.path.as_deref(),
+            self.contract.as_deref(),
+            self.message.as_deref(),
+        );
+        match (chain, contract) {
+            ((Some(pallet), Some(function)), (None, None, None)) => {
+                if self.value.is_some() {
+                    return Err("'value' only applies to contract calls".to_owned());
+                }
+                if self.storage.as_ref().is_none_or(Vec::is_empty) {
+                    return Err("'storage' must list the entries to compare".to_owned());
+                }
+                Ok(DiffedCall::Chain { pallet, function })
+            }
+            ((None, None), (Some(path), Some(contract), Some(message))) => {
+                Ok(DiffedCall::Contract {
+                    path,
+                    contract,
+                    message,
+                })
+            }
+            _ => Err(
+                "Give either 'pallet' and 'function' (chain call) or 'path', 'contract' and 'message' (contract call)"
+                    .to_owned(),
+            ),
+        }
+    }
+}
+
+/// Rendered values by label.
+type Snapshot = BTreeMap<String, String>;
+
+/// Read the selected entries and, when given, the storage of `contract`.
+async fn snapshot(
+    pool: &RpcPool,
+    url: &str,
+    storage: &[StorageSelector],
+    contract: Option<&str>,
+) -> Result<Snapshot, String> {
+    let metadata = ChainMetadata::fetch(pool, url)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut values = Snapshot::new();
+    for selector in storage {
+        let keys = selector.keys.as_deref().unwrap_or_default();
+        let key = metadata.storage_key(&selector.pallet, &selector.item, keys)?;
+        let bytes = fetch_storage_at(pool, url, &key, None)
+            .await
+            .map_err(|e| e.to_string())?;
+        let value = metadata.decode_storage(&selector.pallet, &selector.item, bytes.as_deref())?;
+        values.insert(selector.label(), value);
+    }
+    if let Some(contract) = contract {
+        values.extend(contract_storage(pool, url, &metadata, contract).await?);
+    }
+    Ok(values)
+}
+
+/// Raw key/value pairs of the child trie of `contract`.
+async fn contract_storage(
+    pool: &RpcPool,
+    url: &str,
+    metadata: &ChainMetadata,
+    contract: &str,
+) -> Result<Snapshot, String> {
+    let pallet = ["Revive", "Contracts"]
+        .into_iter()
+        .find(|pallet| metadata.has_storage(pallet, "ContractInfoOf"))
+        .ok_or_else(|| "The chain has no contracts pallet".to_owned())?;
+    let key = metadata.storage_key(pallet, "ContractInfoOf", &[contract.to_owned()])?;
+    let info = fetch_storage_at(pool, url, &key, None)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("No contract at {}", contract))?;
+    // The trie id is the first field of ContractInfo.
+    let trie_id = Vec::<u8>::decode(&mut info.as_slice())
+        .map_err(|e| format!("Failed to decode the contract info of {}: {}", contract, e))?;
+    let child = Value::String(to_hex(&[CHILD_STORAGE_PREFIX, &trie_id].concat()));
+
+    let keys = pool
+        .request(
+            url,
+            "childstate_getKeysPaged",
+            vec![
+                child.clone(),
+                Value::String("0x".to_owned()),
+                Value::from(MAX_CONTRACT_KEYS),
+                Value::Null,
+            ],
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut values = Snapshot::new();
+    for key in keys
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+    {
+        let value = pool
+            .request(
+                url,
+                "childstate_getStorage",
+                vec![child.clone(), Value::String(key.to_owned())],
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        if let Some(value) = value.as_str() {
+            values.insert(
+                format!("{} storage {}", contract, key),
+                contract_value(value),
+            );
+        }
+    }
+    Ok(values)
+}
+
+/// Contract storage values are shown as hex, with short ones also as a number.
+fn contract_value(hex: &str) -> String {
+    match decode_hex(hex) {
+        Ok(bytes) if !bytes.is_empty() && bytes.len() <= 16 => {
+            let mut padded = [0u8; 16];
+            padded[..bytes.len()].copy_from_slice(&bytes);
+            format!("{} ({})", hex, u128::from_le_bytes(padded))
+        }
+        _ => hex.to_owned(),
+    }
+}
+
+/// Describe what differs between `before` and `after`.
+fn describe_diff(before: &Snapshot, after: &Snapshot) -> String {
+    let mut changed = Vec::new();
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let mut unchanged = 0;
+    for (label, old) in before {
+        match after.get(label) {
+            Some(new) if new == old => unchanged += 1,
+            Some(new) => changed.push(format!("  {}:\n    - {}\n    + {}", label, old, new)),
+            None => removed.push(format!("  {}: {}", label, old)),
+        }
+    }
+    for (label, new) in after {
+        if !before.contains_key(label) {
+            added.push(format!("  {}: {}", label, new));
+        }
+    }
+    if changed.is_empty() && added.is_empty() && removed.is_empty() {
+        return format!("No changes in the {} compared entries.", unchanged);
+    }
+    let mut sections = Vec::new();
+    for (title, lines) in [("Changed", changed), ("Added", added), ("Removed", removed)] {
+        if !lines.is_empty() {
+            sections.push(format!(
+                "{} ({}):\n{}",
+                title,
+                lines.len(),
+                lines.join("\n")
+            ));
+        }
+    }
+    sections.push(format!("Unchanged: {}", unchanged));
+    sections.join("\n\n")
+}
+
+/// Execute state_diff_call tool
+pub async fn state_diff_call<E: CommandExecutor>(
+    executor: &E,
+    pool: &RpcPool,
+    params: StateDiffCallParams,
+    stored_url: Option<&str>,
+) -> PopMcpResult<CallToolResult> {
+    let call = params.call().map_err(PopMcpError::InvalidInput)?;
+    let Some(url) = params.url.as_deref().or(stored_url) else {
+        return Err(PopMcpError::InvalidInput(
+            "'url' is required when no node has been launched".to_owned(),
+        ));
+    };
+    if !is_local_url(url) {
+        return Err(PopMcpError::InvalidInput(format!(
+            "state_diff_call only targets local nodes (localhost, 127.0.0.1); refusing {}",
+            url
+        )));
+    }
+    let storage = params.storage.as_deref().unwrap_or_default();
+    let contract = match call {
+        DiffedCall::Contract { contract, .. } => Some(contract),
+        DiffedCall::Chain { .. } => None,
+    };
+
+    let before = match snapshot(pool, url, storage, contract).await {
+        Ok(before) => before,
+        Err(e) => {
+            return Ok(error_result(format!(
+                "Snapshot before the call failed: {}",
+                e
+            )))
+        }
+    };
+    let (name, result) = match call {
+        DiffedCall::Chain { pallet, function } => (
+            format!("{}.{}", pallet, function),
+            call_chain(
+                executor,
+                pool,
+                CallChainParams {
+                    url: url.to_owned(),
+                    pallet: Some(pallet.to_owned()),
+                    function: Some(function.to_owned()),
+                    args: params.args.clone(),
+                    sudo: None,
+                    execute: Some(true),
+                    use_wallet: None,
+                    metadata: None,
+                    dry_run: None,
+                    at_block: None,
+                },
+            )
+            .await?,
+        ),
+        DiffedCall::Contract {
+            path,
+            contract,
+            message,
+        } => (
+            format!("{}::{}", contract, message),
+            call_contract(
+                executor,
+                CallContractParams {
+                    path: path.to_owned(),
+                    contract: contract.to_owned(),
+                    message: message.to_owned(),
+                    args: params.args.as_ref().map(|args| args.join(" ")),
+                    args_file: None,
+                    value: params.value.clone(),
+                    gas_limit: None,
+                    proof_size: None,
+                    execute: Some(true),
+                    url: Some(url.to_owned()),
+                    summary: None,
+                    dry_run: None,
+                    ci_mode: None,
+                    signer: None,
+                    use_wallet: None,
+                },
+            )
+            .await?,
+        ),
+    };
+    if result.is_error == Some(true) {
+        return Ok(error_result(format!(
+            "{} failed, so state was not compared:\n\n{}",
+            name,
+            extract_texts(&result).join("\n\n")
+        )));
+    }
+    let after = match snapshot(pool, url, storage, contract).await {
+        Ok(after) => after,
+        Err(e) => {
+            return Ok(error_result(format!(
+                "{} was executed, but the snapshot after it failed: {}",
+                name, e
+            )))
+        }
+    };
+
+    Ok(success_result(format!(
+        "State diff around {}:\n\n{}\n\nCall output:\n{}",
+        name,
+        describe_diff(&before, &after),
+        extract_texts(&result).join("\n\n")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutor;
+
+    fn params() -> StateDiffCallParams {
+        StateDiffCallParams {
+            url: None,
+            pallet: Some("Balances".to_owned()),
+            function: Some("transfer_keep_alive".to_owned()),
+            args: None,
+            path: None,
+            contract: None,
+            message: None,
+            value: None,
+            storage: Some(vec![StorageSelector {
+                pallet: "System".to_owned(),
+                item: "Number".to_owned(),
+                keys: None,
+            }]),
+        }
+    }
+
+    #[test]
+    fn call_requires_exactly_one_kind_of_call() {
+        assert!(params().call().is_ok());
+        let mut mixed = params();
+        mixed.message = Some("flip".to_owned());
+        assert!(mixed.call().is_err());
+        let mut no_storage = params();
+        no_storage.storage = None;
+        assert!(no_storage.call().is_err());
+        let contract = StateDiffCallParams {
+            pallet: None,
+            function: None,
+            path: Some("./flipper".to_owned()),
+            contract: Some("0x00".to_owned()),
+            message: Some("flip".to_owned()),
+            storage: None,
+            ..params()
+        };
+        assert!(matches!(contract.call(), Ok(DiffedCall::Contract { .. })));
+    }
+
+    #[tokio::test]
+    async fn state_diff_call_refuses_remote_nodes() {
+        let result = state_diff_call(
+            &MockExecutor::new(),
+            &RpcPool::new(),
+            params(),
+            Some("wss://rpc.polkadot.io"),
+        )
+        .await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn describe_diff_lists_changes_additions_and_removals() {
+        let before = Snapshot::from([
+            ("a".to_owned(), "1".to_owned()),
+            ("b".to_owned(), "2".to_owned()),
+            ("c".to_owned(), "3".to_owned()),
+        ]);
+        let after = Snapshot::from([
+            ("a".to_owned(), "1".to_owned()),
+            ("b".to_owned(), "5".to_owned()),
+            ("d".to_owned(), "4".to_owned()),
+        ]);
+        let diff = describe_diff(&before, &after);
+        assert!(diff.contains("Changed (1):\n  b:\n    - 2\n    + 5"));
+        assert!(diff.contains("Added (1):\n  d: 4"));
+        assert!(diff.contains("Removed (1):\n  c: 3"));
+        assert!(diff.ends_with("Unchanged: 1"));
+        assert_eq!(
+            describe_diff(&before, &before),
+            "No changes in the 3 compared entries."
+        );
+    }
+
+    #[test]
+    fn short_contract_values_are_also_shown_as_numbers() {
+        assert_eq!(contract_value("0x01"), "0x01 (1)");
+        assert_eq!(contract_value("0x"), "0x");
+    }
+}
@@ -0,0 +1,157 @@
+//! Gas estimates of contract calls
+//!
+//! `pop call contract` without `--execute` dry-runs the message and reports the
+//! weight and storage deposit it would need. This tool runs that dry run and returns
+//! the figures as numbers, so an agent can compare them with limits or balances
+//! before submitting anything.
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::PopMcpResult;
+use crate::executor::CommandExecutor;
+use crate::outputs::GasEstimate;
+use crate::tools::call::contract::{call_contract, CallContractParams};
+use crate::tools::common::{error_result, extract_texts, success_result, with_structured};
+
+/// Parameters for the estimate_gas tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct EstimateGasParams {
+    /// Path to the contract directory (needed for metadata).
+    #[schemars(description = "Path to the contract directory (needed for contract metadata)")]
+    pub path: String,
+    /// Contract address to call.
+    #[schemars(description = "Contract address")]
+    pub contract: String,
+    /// Message to estimate.
+    #[schemars(description = "Message/method to estimate")]
+    pub message: String,
+    /// Method arguments as space-separated values.
+    #[schemars(description = "Method arguments as space-separated values")]
+    pub args: Option<String>,
+    /// JSON/TOML file with method arguments, as an alternative to `args`.
+    #[schemars(
+        description = "Method arguments from a JSON array or TOML `args = [...]` file inside the project (relative to path); use instead of args for large values"
+    )]
+    pub args_file: Option<String>,
+    /// Value to transfer with the call (in tokens).
+    #[schemars(description = "Value to transfer with the call (in tokens)")]
+    pub value: Option<String>,
+    /// WebSocket URL of the node.
+    #[schemars(description = "WebSocket URL of the node")]
+    pub url: Option<String>,
+}
+
+/// Lines describing `estimate`, for the text result.
+fn describe(estimate: &GasEstimate) -> String {
+    [
+        ("ref_time", estimate.ref_time.map(u128::from)),
+        ("proof_size", estimate.proof_size.map(u128::from)),
+        ("storage_deposit", estimate.storage_deposit),
+        ("storage_refund", estimate.storage_refund),
+    ]
+    .iter()
+    .filter_map(|(key, value)| value.map(|value| format!("{}: {}", key, value)))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// Execute estimate_gas tool
+pub async fn estimate_gas<E: CommandExecutor>(
+    executor: &E,
+    params: EstimateGasParams,
+) -> PopMcpResult<CallToolResult> {
+    let message = params.message.clone();
+    let result = call_contract(
+        executor,
+        CallContractParams {
+            path: params.path,
+            contract: params.contract,
+            message: params.message,
+            args: params.args,
+            args_file: params.args_file,
+            value: params.value,
+            gas_limit: None,
+            proof_size: None,
+            execute: None,
+            url: params.url,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+        },
+    )
+    .await?;
+    if result.is_error == Some(true) {
+        return Ok(result);
+    }
+    let output = extract_texts(&result).join("\n");
+    let estimate = GasEstimate::parse(&output);
+    if estimate.is_empty() {
+        return Ok(error_result(format!(
+            "Pop CLI reported no gas or storage deposit figures for '{}'.\n\n{}",
+            message, output
+        )));
+    }
+    Ok(with_structured(
+        success_result(format!(
+            "Gas estimate for '{}' (dry run, nothing submitted):\n{}\n\n{}",
+            message,
+            describe(&estimate),
+            output
+        )),
+        &estimate,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutor;
+
+    fn params() -> EstimateGasParams {
+        EstimateGasParams {
+            path: "./flipper".to_owned(),
+            contract: "0x58".to_owned(),
+            message: "flip".to_owned(),
+            args: None,
+            args_file: None,
+            value: None,
+            url: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn estimates_come_from_a_dry_run() -> anyhow::Result<()> {
+        let executor = MockExecutor::new().on(
+            &["call", "contract"],
+            "◇  Gas limit: Weight { ref_time: 1112520, proof_size: 16689 }\n│  Storage deposit: Charge(4000)\n⚙  Result: Ok(())",
+        );
+        let result = estimate_gas(&executor, params()).await?;
+        let estimate: GasEstimate =
+            serde_json::from_value(result.structured_content.unwrap_or_default())?;
+        assert_eq!(
+            estimate,
+            GasEstimate {
+                ref_time: Some(1112520),
+                proof_size: Some(16689),
+                storage_deposit: Some(4000),
+                storage_refund: None,
+                result: Some("Ok(())".to_owned()),
+            }
+        );
+        assert!(!executor.calls()[0].args.contains(&"--execute".to_owned()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn missing_figures_are_an_error() -> anyhow::Result<()> {
+        let executor = MockExecutor::new().on(&["call", "contract"], "⚙  Result: Ok(false)");
+        let result = estimate_gas(&executor, params()).await?;
+        assert_eq!(result.is_error, Some(true));
+        Ok(())
+    }
+}
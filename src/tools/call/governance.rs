@@ -0,0 +1,307 @@
+//! OpenGov helpers (preimage.note_preimage, referenda.submit)
+//!
+//! Aimed at local/test networks: note a call's preimage, then submit it as a referendum
+//! and place the decision deposit in the same transaction.
+
+use codec::Encode;
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::encoder::{
+    blake2_256, decode_hex, fetch_storage, storage_value_key, to_hex, CallArg, ChainMetadata,
+};
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::rpc::RpcPool;
+use crate::tools::call::chain::submit_call_data;
+use crate::tools::common::{error_result, success_result};
+
+/// Largest call the referenda pallet accepts inline (`Bounded::Inline`).
+const MAX_INLINE_LEN: usize = 128;
+/// Default origin for referenda proposals.
+const DEFAULT_ORIGIN: &str = "system(Root)";
+
+/// Parameters for the submit_preimage tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct SubmitPreimageParams {
+    /// WebSocket URL of the chain node.
+    #[schemars(description = "WebSocket URL of the chain node (e.g., ws://localhost:9944)")]
+    pub url: String,
+    /// Pallet of the proposed call.
+    #[schemars(description = "Pallet of the proposed call (use with function)")]
+    pub pallet: Option<String>,
+    /// Extrinsic of the proposed call.
+    #[schemars(description = "Extrinsic of the proposed call (use with pallet)")]
+    pub function: Option<String>,
+    /// Arguments for the proposed call.
+    #[schemars(description = "Arguments for the proposed call, one value per parameter")]
+    pub args: Option<Vec<String>>,
+    /// Pre-encoded call data, instead of pallet/function/args.
+    #[schemars(description = "Pre-encoded call data (0x...) instead of pallet/function/args")]
+    pub call_data: Option<String>,
+    /// Submit the preimage; otherwise only the hash and call data are returned.
+    #[schemars(
+        description = "Submit on-chain; when false only the preimage hash and call data are returned"
+    )]
+    pub execute: Option<bool>,
+}
+
+/// Parameters for the submit_referendum tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct SubmitReferendumParams {
+    /// WebSocket URL of the chain node.
+    #[schemars(description = "WebSocket URL of the chain node (e.g., ws://localhost:9944)")]
+    pub url: String,
+    /// Hash of a noted preimage.
+    #[schemars(
+        description = "Hash of a preimage noted with submit_preimage (use with preimage_len)"
+    )]
+    pub preimage_hash: Option<String>,
+    /// Length in bytes of the noted preimage.
+    #[schemars(description = "Length in bytes of the noted preimage")]
+    pub preimage_len: Option<u32>,
+    /// Inline call data (at most 128 bytes), instead of a preimage.
+    #[schemars(description = "Inline call data (0x..., at most 128 bytes) instead of a preimage")]
+    pub call_data: Option<String>,
+    /// Proposal origin.
+    #[schemars(description = "Proposal origin (default: system(Root)); e.g. Origins(Treasurer)")]
+    pub origin: Option<String>,
+    /// Blocks after approval before enactment.
+    #[schemars(description = "Enact this many blocks after approval (default: 1)")]
+    pub enactment_after: Option<u32>,
+    /// Also place the decision deposit.
+    #[schemars(description = "Place the decision deposit in the same transaction (default: true)")]
+    pub place_deposit: Option<bool>,
+    /// Submit the referendum; otherwise only the encoded call data is returned.
+    #[schemars(description = "Submit on-chain; when false only the encoded call data is returned")]
+    pub execute: Option<bool>,
+}
+
+impl SubmitReferendumParams {
+    fn validate(&self) -> Result<(), String> {
+        match (&self.preimage_hash, self.preimage_len, &self.call_data) {
+            (Some(_), Some(_), None) | (None, None, Some(_)) => Ok(()),
+            _ => Err(
+                "Provide either 'preimage_hash' with 'preimage_len', or inline 'call_data'"
+                    .to_owned(),
+            ),
+        }
+    }
+
+    /// `Bounded<RuntimeCall>` proposal in value syntax.
+    fn proposal(&self) -> Result<String, String> {
+        if let Some(data) = &self.call_data {
+            let bytes = decode_hex(data)?;
+            if bytes.len() > MAX_INLINE_LEN {
+                return Err(format!(
+                    "Inline call data is {} bytes; note a preimage for calls over {} bytes",
+                    bytes.len(),
+                    MAX_INLINE_LEN
+                ));
+            }
+            return Ok(format!("Inline({})", to_hex(&bytes)));
+        }
+        let hash = decode_hex(self.preimage_hash.as_deref().unwrap_or_default())?;
+        if hash.len() != 32 {
+            return Err("'preimage_hash' must be 32 bytes".to_owned());
+        }
+        Ok(format!(
+            "Lookup {{ hash: {}, len: {} }}",
+            to_hex(&hash),
+            self.preimage_len.unwrap_or_default()
+        ))
+    }
+}
+
+/// Execute submit_preimage tool
+pub async fn submit_preimage<E: CommandExecutor>(
+    executor: &E,
+    pool: &RpcPool,
+    params: SubmitPreimageParams,
+) -> PopMcpResult<CallToolResult> {
+    let metadata = match ChainMetadata::fetch(pool, &params.url).await {
+        Ok(metadata) => metadata,
+        Err(e) => return Ok(error_result(format!("Preimage failed: {}", e))),
+    };
+    if !metadata.has_pallet("Preimage") {
+        return Ok(error_result(
+            "Preimage failed: the chain does not include the Preimage pallet",
+        ));
+    }
+
+    let encoded = metadata
+        .resolve_call(
+            params.call_data.as_deref(),
+            params.pallet.as_deref(),
+            params.function.as_deref(),
+            params.args.as_deref().unwrap_or_default(),
+        )
+        .and_then(|proposal| {
+            let note = metadata.encode_call_with(
+                "Preimage",
+                "note_preimage",
+                &[CallArg::Raw(&proposal.encode())],
+            )?;
+            Ok((proposal, note))
+        });
+    let (proposal, note) = match encoded {
+        Ok(encoded) => encoded,
+        Err(e) => return Ok(error_result(format!("Preimage failed: {}", e))),
+    };
+    let summary = format!(
+        "preimage_hash: {}\npreimage_len: {}",
+        to_hex(&blake2_256(&proposal)),
+        proposal.len()
+    );
+
+    if !params.execute.unwrap_or(false) {
+        return Ok(success_result(format!(
+            "{}\nproposal call data: {}\nnote_preimage call data: {}",
+            summary,
+            to_hex(&proposal),
+            to_hex(&note)
+        )));
+    }
+
+    let result = submit_call_data(executor, &params.url, &to_hex(&note), false).await?;
+    if result.is_error == Some(true) {
+        return Ok(result);
+    }
+    Ok(success_result(format!("Preimage noted.\n{}", summary)))
+}
+
+/// Execute submit_referendum tool
+pub async fn submit_referendum<E: CommandExecutor>(
+    executor: &E,
+    pool: &RpcPool,
+    params: SubmitReferendumParams,
+) -> PopMcpResult<CallToolResult> {
+    params.validate().map_err(PopMcpError::InvalidInput)?;
+    let proposal = params.proposal().map_err(PopMcpError::InvalidInput)?;
+
+    let metadata = match ChainMetadata::fetch(pool, &params.url).await {
+        Ok(metadata) => metadata,
+        Err(e) => return Ok(error_result(format!("Referendum failed: {}", e))),
+    };
+    if !metadata.has_pallet("Referenda") {
+        return Ok(error_result(
+            "Referendum failed: the chain does not include the Referenda pallet",
+        ));
+    }
+
+    // The new referendum takes the next index, so the deposit can be batched with it.
+    let index = match fetch_storage(
+        pool,
+        &params.url,
+        &storage_value_key("Referenda", "ReferendumCount"),
+    )
+    .await
+    {
+        Ok(Some(bytes)) => bytes
+            .get(..4)
+            .and_then(|b| b.try_into().ok())
+            .map(u32::from_le_bytes)
+            .unwrap_or_default(),
+        Ok(None) => 0,
+        Err(e) => return Ok(error_result(format!("Referendum failed: {}", e))),
+    };
+
+    let origin = params.origin.as_deref().unwrap_or(DEFAULT_ORIGIN);
+    let enactment = format!("After({})", params.enactment_after.unwrap_or(1));
+    let place_deposit = params.place_deposit.unwrap_or(true);
+    let encoded = metadata
+        .encode_call_with(
+            "Referenda",
+            "submit",
+            &[
+                CallArg::Text(origin),
+                CallArg::Text(&proposal),
+                CallArg::Text(&enactment),
+            ],
+        )
+        .and_then(|submit| {
+            if !place_deposit {
+                return Ok(submit);
+            }
+            let deposit = metadata.encode_call(
+                "Referenda",
+                "place_decision_deposit",
+                &[index.to_string()],
+            )?;
+            metadata.encode_call_with_calls("Utility", "batch_all", &[submit, deposit])
+        });
+    let call_data = match encoded {
+        Ok(encoded) => to_hex(&encoded),
+        Err(e) => return Ok(error_result(format!("Referendum failed: {}", e))),
+    };
+
+    if !params.execute.unwrap_or(false) {
+        return Ok(success_result(format!(
+            "Encoded referendum #{} submission:\n{}",
+            index, call_data
+        )));
+    }
+
+    let result = submit_call_data(executor, &params.url, &call_data, false).await?;
+    if result.is_error == Some(true) {
+        return Ok(result);
+    }
+    Ok(success_result(format!("Referendum #{} submitted.", index)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn referendum() -> SubmitReferendumParams {
+        SubmitReferendumParams {
+            url: "ws://localhost:9944".to_owned(),
+            preimage_hash: None,
+            preimage_len: None,
+            call_data: Some("0x0000".to_owned()),
+            origin: None,
+            enactment_after: None,
+            place_deposit: None,
+            execute: None,
+        }
+    }
+
+    #[test]
+    fn validate_requires_one_proposal_source() {
+        let mut params = referendum();
+        assert!(params.validate().is_ok());
+        params.preimage_hash = Some(format!("0x{}", "11".repeat(32)));
+        assert!(params.validate().is_err());
+        params.call_data = None;
+        assert!(params.validate().is_err());
+        params.preimage_len = Some(10);
+        assert!(params.validate().is_ok());
+    }
+
+    #[test]
+    fn proposal_uses_inline_or_lookup() {
+        let mut params = referendum();
+        assert_eq!(params.proposal(), Ok("Inline(0x0000)".to_owned()));
+        params.call_data = None;
+        params.preimage_hash = Some(format!("0x{}", "11".repeat(32)));
+        params.preimage_len = Some(10);
+        assert_eq!(
+            params.proposal(),
+            Ok(format!("Lookup {{ hash: 0x{}, len: 10 }}", "11".repeat(32)))
+        );
+    }
+
+    #[test]
+    fn proposal_rejects_oversized_inline_call() {
+        let mut params = referendum();
+        params.call_data = Some(format!("0x{}", "00".repeat(MAX_INLINE_LEN + 1)));
+        assert!(params.proposal().is_err());
+        params.call_data = None;
+        params.preimage_hash = Some("0x1234".to_owned());
+        params.preimage_len = Some(2);
+        assert!(params.proposal().is_err());
+    }
+}
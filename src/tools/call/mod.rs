@@ -1,11 +1,47 @@
 //! Call tools (pop call)
 //!
 //! Submodules:
+//! - `account` - Identity, proxy and account-mapping helpers
+//! - `batch` - Batched chain calls (utility.batch_all)
 //! - `chain` - Chain calls (pop call chain)
+//! - `collator` - Session key and collator-selection helpers
 //! - `contract` - Contract calls (pop call contract)
+//! - `contract_batch` - Sequences of contract calls
+//! - `diff` - Storage diffs around a call on a local node
+//! - `estimate` - Gas and storage deposit estimates of contract calls
+//! - `governance` - Preimage and referendum helpers
+//! - `query` - Saved call_chain parameter sets
+//! - `resubmit` - Re-signing stuck transactions with a higher tip
+//! - `schedule` - Delayed calls via the scheduler pallet
+//! - `storage` - Contract storage queries
+//! - `stress` - Contract load testing against a local node
 
+pub mod account;
+pub mod batch;
 pub mod chain;
+pub mod collator;
 pub mod contract;
+pub mod contract_batch;
+pub mod diff;
+pub mod estimate;
+pub mod governance;
+pub mod query;
+pub mod resubmit;
+pub mod schedule;
+pub mod storage;
+pub mod stress;
 
+pub use account::*;
+pub use batch::*;
 pub use chain::*;
+pub use collator::*;
 pub use contract::*;
+pub use contract_batch::*;
+pub use diff::*;
+pub use estimate::*;
+pub use governance::*;
+pub use query::*;
+pub use resubmit::*;
+pub use schedule::*;
+pub use storage::*;
+pub use stress::*;
@@ -0,0 +1,223 @@
+//! Saved chain queries (named call_chain parameter sets)
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::queries::{self, SavedQuery};
+use crate::tools::call::chain::CallChainParams;
+use crate::tools::common::{error_result, success_result};
+
+/// Parameters for the save_query tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct SaveQueryParams {
+    /// Name to save the query under.
+    #[schemars(
+        description = "Query name: letters, digits, '-' and '_' (e.g., 'treasury-balance')"
+    )]
+    pub name: String,
+    /// What the query checks.
+    #[schemars(
+        description = "What the query checks, stored with it for whoever reads the query file"
+    )]
+    pub description: Option<String>,
+    /// Pallet name.
+    #[schemars(description = "Pallet name (e.g., 'System', 'Session')")]
+    pub pallet: String,
+    /// Storage item, constant or extrinsic name.
+    #[schemars(description = "Storage item or constant to query (e.g., 'Account', 'Validators')")]
+    pub function: String,
+    /// Call arguments, possibly with placeholders.
+    #[schemars(
+        description = "Arguments as for call_chain. Use {name} placeholders (e.g., '{address}', '{amount}') for values given when the query is run."
+    )]
+    pub args: Option<Vec<String>>,
+    /// Node to query by default.
+    #[schemars(
+        description = "WebSocket URL to query by default (may contain placeholders; default: the url given at run time, else the selected node)"
+    )]
+    pub url: Option<String>,
+}
+
+/// Parameters for the run_saved_query tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct RunSavedQueryParams {
+    /// Name of the query to run.
+    #[schemars(description = "Name of a query saved with save_query")]
+    pub name: String,
+    /// Node to query instead of the saved one.
+    #[schemars(
+        description = "WebSocket URL to query (default: the URL saved with the query, else the selected node)"
+    )]
+    pub url: Option<String>,
+    /// Placeholder values.
+    #[schemars(
+        description = "Values for the query's placeholders, e.g. {\"address\": \"5Grw...\"}"
+    )]
+    pub values: Option<BTreeMap<String, String>>,
+}
+
+/// Execute save_query tool
+pub async fn save_query(dir: &Path, params: SaveQueryParams) -> PopMcpResult<CallToolResult> {
+    let name = params.name.trim();
+    queries::validate_name(name)?;
+    let pallet = params.pallet.trim();
+    let function = params.function.trim();
+    if pallet.is_empty() || function.is_empty() {
+        return Err(PopMcpError::InvalidInput(
+            "pallet and function cannot be empty".to_owned(),
+        ));
+    }
+    let query = SavedQuery {
+        description: params.description,
+        url: params.url,
+        pallet: pallet.to_owned(),
+        function: function.to_owned(),
+        args: params.args.unwrap_or_default(),
+    };
+    let file = queries::save(dir, name, &query).await?;
+    let placeholders: Vec<String> = query.placeholders().into_iter().collect();
+    let usage = if placeholders.is_empty() {
+        String::new()
+    } else {
+        format!(" with values for {}", placeholders.join(", "))
+    };
+    Ok(success_result(format!(
+        "Saved query '{}' ({}::{}) to {}. Run it with run_saved_query{}.",
+        name,
+        query.pallet,
+        query.function,
+        file.display(),
+        usage
+    )))
+}
+
+/// Resolve saved query `params.name` into call_chain parameters, or into an error
+/// result when the query or its placeholder values are missing.
+///
+/// The node is `params.url`, else the URL saved with the query, else `stored_url`.
+pub async fn saved_query_call(
+    dir: &Path,
+    params: RunSavedQueryParams,
+    stored_url: Option<&str>,
+) -> PopMcpResult<Result<CallChainParams, CallToolResult>> {
+    let name = params.name.trim();
+    let Some(query) = queries::load(dir, name).await? else {
+        let saved = queries::list(dir);
+        return Ok(Err(error_result(if saved.is_empty() {
+            format!(
+                "No saved query named '{}': none saved yet (use save_query)",
+                name
+            )
+        } else {
+            format!(
+                "No saved query named '{}'. Saved queries: {}",
+                name,
+                saved.join(", ")
+            )
+        })));
+    };
+    let (saved_url, args) = match query.fill(&params.values.unwrap_or_default()) {
+        Ok(filled) => filled,
+        Err(e) => return Ok(Err(error_result(format!("Query '{}': {}", name, e)))),
+    };
+    let Some(url) = params.url.or(saved_url).or(stored_url.map(str::to_owned)) else {
+        return Err(PopMcpError::InvalidInput(format!(
+            "Query '{}' has no saved url: pass url or select a node",
+            name
+        )));
+    };
+    Ok(Ok(CallChainParams {
+        url,
+        pallet: Some(query.pallet),
+        function: Some(query.function),
+        args: (!args.is_empty()).then_some(args),
+        sudo: None,
+        execute: None,
+        use_wallet: None,
+        metadata: None,
+        dry_run: None,
+        at_block: None,
+    }))
+}
+
+#[cfg(test)]
+#[allow(clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::rpc::RpcPool;
+    use crate::test_utils::MockExecutor;
+    use crate::tools::call::chain::call_chain;
+    use crate::tools::common::content_text;
+
+    fn run_params(values: &[(&str, &str)]) -> RunSavedQueryParams {
+        RunSavedQueryParams {
+            name: "balance".to_owned(),
+            url: None,
+            values: Some(
+                values
+                    .iter()
+                    .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+                    .collect(),
+            ),
+        }
+    }
+
+    #[tokio::test]
+    async fn saved_queries_run_as_chain_calls() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let saved = save_query(
+            dir.path(),
+            SaveQueryParams {
+                name: "balance".to_owned(),
+                description: None,
+                pallet: "System".to_owned(),
+                function: "Account".to_owned(),
+                args: Some(vec!["{address}".to_owned()]),
+                url: None,
+            },
+        )
+        .await?;
+        assert!(content_text(&saved).ends_with("with values for address."));
+
+        let missing = saved_query_call(dir.path(), run_params(&[]), Some("ws://localhost:9944"));
+        let Err(result) = missing.await? else {
+            panic!("expected a missing value error");
+        };
+        assert!(content_text(&result).contains("Missing values for: address"));
+
+        let Ok(call) = saved_query_call(
+            dir.path(),
+            run_params(&[("address", "5GrwvaEF")]),
+            Some("ws://localhost:9944"),
+        )
+        .await?
+        else {
+            panic!("expected the query to resolve");
+        };
+        let executor = MockExecutor::new().on(&["call", "chain"], "free: 100");
+        let result = call_chain(&executor, &RpcPool::new(), call).await?;
+        assert_eq!(result.is_error, Some(false));
+        let args = &executor.calls()[0].args;
+        assert!(args
+            .windows(2)
+            .any(|w| w == ["--url", "ws://localhost:9944"]));
+        assert!(args.windows(2).any(|w| w == ["--args", "5GrwvaEF"]));
+
+        let unknown = RunSavedQueryParams {
+            name: "validators".to_owned(),
+            ..run_params(&[])
+        };
+        let Err(result) = saved_query_call(dir.path(), unknown, None).await? else {
+            panic!("expected an unknown query error");
+        };
+        assert!(content_text(&result).contains("Saved queries: balance"));
+        Ok(())
+    }
+}
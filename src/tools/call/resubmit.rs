@@ -0,0 +1,211 @@
+//! Re-signing stuck transactions with a higher tip
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::accounts::keypair_from_suri;
+use crate::encoder::{blake2_256, decode_hex, to_hex, ChainMetadata, Resign};
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::rpc::RpcPool;
+use crate::tools::common::{error_result, success_result};
+
+/// Parameters for the resubmit_transaction tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct ResubmitTransactionParams {
+    /// WebSocket URL of the chain node.
+    #[schemars(
+        description = "WebSocket URL of the chain node (defaults to the node launched with up_ink_node)"
+    )]
+    pub url: Option<String>,
+    /// Hash of the stuck transaction.
+    #[schemars(description = "Hash of the stuck transaction, as listed by pending_extrinsics")]
+    pub hash: String,
+    /// New tip, in the chain's smallest unit.
+    #[schemars(
+        description = "New tip in the chain's smallest unit (plancks); must beat the stuck transaction's priority"
+    )]
+    pub tip: String,
+    /// Nonce to use instead of the stuck transaction's.
+    #[schemars(
+        description = "Nonce to sign with instead of the stuck transaction's (e.g. the account's next nonce when the stuck one left a gap)"
+    )]
+    pub nonce: Option<u64>,
+    /// Submit the replacement; otherwise it is only built and described.
+    #[schemars(
+        description = "Submit the re-signed transaction; when false it is only built and described"
+    )]
+    pub execute: Option<bool>,
+}
+
+/// Find the transaction with `hash` in the pool of `url` and re-sign it with `suri`.
+async fn resign(
+    pool: &RpcPool,
+    url: &str,
+    params: &ResubmitTransactionParams,
+    tip: u128,
+    suri: &str,
+) -> Result<(ChainMetadata, Vec<u8>), String> {
+    let wanted = decode_hex(&params.hash)?;
+    let pending = pool
+        .request(url, "author_pendingExtrinsics", Vec::new())
+        .await
+        .map_err(|e| e.to_string())?;
+    let stuck = pending
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter_map(|hex| decode_hex(hex).ok())
+        .find(|bytes| blake2_256(bytes).as_slice() == wanted.as_slice())
+        .ok_or_else(|| {
+            format!(
+                "{} is not in the transaction pool of {}: it was included or dropped",
+                params.hash, url
+            )
+        })?;
+
+    let metadata = ChainMetadata::fetch(pool, url)
+        .await
+        .map_err(|e| e.to_string())?;
+    let version = pool
+        .request(url, "state_getRuntimeVersion", Vec::new())
+        .await
+        .map_err(|e| e.to_string())?;
+    let version_field = |name: &str| {
+        version
+            .get(name)
+            .and_then(Value::as_u64)
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| format!("state_getRuntimeVersion returned no {}", name))
+    };
+    let genesis = pool
+        .request(url, "chain_getBlockHash", vec![Value::from(0)])
+        .await
+        .map_err(|e| e.to_string())?;
+    let genesis_hash = genesis
+        .as_str()
+        .ok_or_else(|| "chain_getBlockHash returned no genesis hash".to_owned())
+        .and_then(decode_hex)?
+        .try_into()
+        .map_err(|_| "Genesis hash is not 32 bytes".to_owned())?;
+    let resign = Resign {
+        tip,
+        nonce: params.nonce,
+        spec_version: version_field("specVersion")?,
+        transaction_version: version_field("transactionVersion")?,
+        genesis_hash,
+    };
+
+    let keypair =
+        keypair_from_suri(suri).map_err(|e| format!("PRIVATE_KEY is not a valid SURI: {}", e))?;
+    let resigned =
+        metadata.resign_extrinsic(&stuck, &keypair.public.to_bytes(), &resign, |payload| {
+            keypair.sign_simple(b"substrate", payload).to_bytes()
+        })?;
+    Ok((metadata, resigned))
+}
+
+/// Execute resubmit_transaction tool
+pub async fn resubmit_transaction(
+    pool: &RpcPool,
+    params: ResubmitTransactionParams,
+    stored_url: Option<&str>,
+) -> PopMcpResult<CallToolResult> {
+    let Some(url) = params.url.as_deref().or(stored_url) else {
+        return Err(PopMcpError::InvalidInput(
+            "'url' is required when no node has been launched".to_owned(),
+        ));
+    };
+    let tip = params.tip.trim().parse::<u128>().map_err(|_| {
+        PopMcpError::InvalidInput(format!(
+            "'tip' must be a whole number of plancks, got '{}'",
+            params.tip
+        ))
+    })?;
+    let suri = crate::read_private_key_suri().ok_or_else(|| {
+        PopMcpError::InvalidInput(
+            "PRIVATE_KEY environment variable is required to re-sign the transaction".to_owned(),
+        )
+    })?;
+
+    let (metadata, resigned) = match resign(pool, url, &params, tip, &suri).await {
+        Ok(resigned) => resigned,
+        Err(e) => return Ok(error_result(format!("Resubmission failed: {}", e))),
+    };
+    let hex = to_hex(&resigned);
+    let described = match metadata.decode_extrinsic(&resigned) {
+        Ok(decoded) => decoded
+            .extensions
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value))
+            .chain([format!("call: {}", decoded.call)])
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Err(e) => format!("(could not be decoded: {})", e),
+    };
+
+    if !params.execute.unwrap_or(false) {
+        return Ok(success_result(format!(
+            "Re-signed {} with tip {} (not submitted):\n{}\n\nhash: {}\nextrinsic: {}",
+            params.hash,
+            tip,
+            described,
+            to_hex(&blake2_256(&resigned)),
+            hex
+        )));
+    }
+    match pool
+        .request(url, "author_submitExtrinsic", vec![Value::String(hex)])
+        .await
+    {
+        Ok(hash) => Ok(success_result(format!(
+            "Resubmitted {} as {} with tip {}:\n{}",
+            params.hash,
+            hash.as_str().unwrap_or_default(),
+            tip,
+            described
+        ))),
+        Err(e) => {
+            let e = e.to_string();
+            let hint = if e.contains("Priority is too low") {
+                "\n\nThe pool keeps the transaction with the higher priority: raise the tip."
+            } else {
+                ""
+            };
+            Ok(error_result(format!("Resubmission failed: {}{}", e, hint)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params() -> ResubmitTransactionParams {
+        ResubmitTransactionParams {
+            url: None,
+            hash: "0x00".to_owned(),
+            tip: "1000".to_owned(),
+            nonce: None,
+            execute: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn resubmit_requires_url_without_stored_node() {
+        let result = resubmit_transaction(&RpcPool::new(), params(), None).await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn resubmit_rejects_non_numeric_tip() {
+        let mut params = params();
+        params.tip = "1.5 UNIT".to_owned();
+        let result =
+            resubmit_transaction(&RpcPool::new(), params, Some("ws://localhost:9944")).await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+    }
+}
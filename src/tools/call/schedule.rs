@@ -0,0 +1,216 @@
+//! Delayed calls through the scheduler pallet (schedule / schedule_after)
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::encoder::{to_hex, CallArg, ChainMetadata};
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::rpc::RpcPool;
+use crate::tools::call::chain::submit_call_data;
+use crate::tools::common::{error_result, success_result};
+
+/// Default scheduler priority (0 is highest, 255 lowest).
+const DEFAULT_PRIORITY: u8 = 127;
+
+/// Parameters for the schedule_call tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct ScheduleCallParams {
+    /// WebSocket URL of the chain node.
+    #[schemars(description = "WebSocket URL of the chain node (e.g., ws://localhost:9944)")]
+    pub url: String,
+    /// Pallet of the call to schedule.
+    #[schemars(description = "Pallet of the call to schedule (use with function)")]
+    pub pallet: Option<String>,
+    /// Extrinsic to schedule.
+    #[schemars(description = "Extrinsic to schedule (use with pallet)")]
+    pub function: Option<String>,
+    /// Arguments for the scheduled call.
+    #[schemars(description = "Arguments for the scheduled call, one value per parameter")]
+    pub args: Option<Vec<String>>,
+    /// Pre-encoded call data, instead of pallet/function/args.
+    #[schemars(description = "Pre-encoded call data (0x...) instead of pallet/function/args")]
+    pub call_data: Option<String>,
+    /// Absolute block number to dispatch at.
+    #[schemars(description = "Absolute block number to dispatch at (use this or after_blocks)")]
+    pub at_block: Option<u32>,
+    /// Number of blocks from now to dispatch after.
+    #[schemars(description = "Dispatch after this many blocks from now (use this or at_block)")]
+    pub after_blocks: Option<u32>,
+    /// Blocks between repetitions.
+    #[schemars(description = "Repeat every N blocks (requires repeat_count)")]
+    pub repeat_every: Option<u32>,
+    /// Total number of dispatches when repeating.
+    #[schemars(description = "Total number of dispatches when repeating (requires repeat_every)")]
+    pub repeat_count: Option<u32>,
+    /// Scheduler priority.
+    #[schemars(description = "Scheduler priority, 0 highest to 255 lowest (default: 127)")]
+    pub priority: Option<u8>,
+    /// Dispatch the scheduling call via sudo.
+    #[schemars(
+        description = "Dispatch via sudo; the scheduler usually requires root origin (default: true)"
+    )]
+    pub sudo: Option<bool>,
+    /// Submit the scheduling extrinsic; otherwise only the encoded call data is returned.
+    #[schemars(description = "Submit on-chain; when false only the encoded call data is returned")]
+    pub execute: Option<bool>,
+}
+
+impl ScheduleCallParams {
+    fn validate(&self) -> Result<(), String> {
+        match (self.at_block, self.after_blocks) {
+            (Some(_), Some(_)) => {
+                return Err("Provide either 'at_block' or 'after_blocks', not both".to_owned())
+            }
+            (None, None) => return Err("Provide 'at_block' or 'after_blocks'".to_owned()),
+            _ => {}
+        }
+        if self.repeat_every.is_some() != self.repeat_count.is_some() {
+            return Err("'repeat_every' and 'repeat_count' must be provided together".to_owned());
+        }
+        if self.repeat_count == Some(0) || self.repeat_every == Some(0) {
+            return Err("'repeat_every' and 'repeat_count' must be greater than 0".to_owned());
+        }
+        Ok(())
+    }
+
+    /// Scheduler extrinsic and its `when` argument.
+    fn schedule_function(&self) -> (&'static str, String) {
+        match (self.at_block, self.after_blocks) {
+            (Some(block), _) => ("schedule", block.to_string()),
+            (None, after) => ("schedule_after", after.unwrap_or_default().to_string()),
+        }
+    }
+
+    fn maybe_periodic(&self) -> String {
+        match (self.repeat_every, self.repeat_count) {
+            (Some(every), Some(count)) => format!("Some(({}, {}))", every, count),
+            _ => "None".to_owned(),
+        }
+    }
+}
+
+/// Encode the scheduler call wrapping the inner call.
+fn encode_schedule(
+    metadata: &ChainMetadata,
+    params: &ScheduleCallParams,
+) -> Result<Vec<u8>, String> {
+    let inner = metadata.resolve_call(
+        params.call_data.as_deref(),
+        params.pallet.as_deref(),
+        params.function.as_deref(),
+        params.args.as_deref().unwrap_or_default(),
+    )?;
+    let (function, when) = params.schedule_function();
+    let periodic = params.maybe_periodic();
+    let priority = params.priority.unwrap_or(DEFAULT_PRIORITY).to_string();
+    metadata.encode_call_with(
+        "Scheduler",
+        function,
+        &[
+            CallArg::Text(&when),
+            CallArg::Text(&periodic),
+            CallArg::Text(&priority),
+            CallArg::Raw(&inner),
+        ],
+    )
+}
+
+/// Execute schedule_call tool
+pub async fn schedule_call<E: CommandExecutor>(
+    executor: &E,
+    pool: &RpcPool,
+    params: ScheduleCallParams,
+) -> PopMcpResult<CallToolResult> {
+    params.validate().map_err(PopMcpError::InvalidInput)?;
+
+    let metadata = match ChainMetadata::fetch(pool, &params.url).await {
+        Ok(metadata) => metadata,
+        Err(e) => return Ok(error_result(format!("Scheduling failed: {}", e))),
+    };
+    if !metadata.has_pallet("Scheduler") {
+        return Ok(error_result(
+            "Scheduling failed: the chain does not include the Scheduler pallet",
+        ));
+    }
+    let call_data = match encode_schedule(&metadata, &params) {
+        Ok(encoded) => to_hex(&encoded),
+        Err(e) => return Ok(error_result(format!("Scheduling failed: {}", e))),
+    };
+
+    if !params.execute.unwrap_or(false) {
+        let (function, when) = params.schedule_function();
+        return Ok(success_result(format!(
+            "Encoded scheduler.{}({}):\n{}",
+            function, when, call_data
+        )));
+    }
+
+    submit_call_data(
+        executor,
+        &params.url,
+        &call_data,
+        params.sudo.unwrap_or(true),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params() -> ScheduleCallParams {
+        ScheduleCallParams {
+            url: "ws://localhost:9944".to_owned(),
+            pallet: Some("System".to_owned()),
+            function: Some("remark".to_owned()),
+            args: Some(vec!["0x00".to_owned()]),
+            call_data: None,
+            at_block: None,
+            after_blocks: Some(10),
+            repeat_every: None,
+            repeat_count: None,
+            priority: None,
+            sudo: None,
+            execute: None,
+        }
+    }
+
+    #[test]
+    fn validate_requires_exactly_one_dispatch_time() {
+        let mut params = params();
+        assert!(params.validate().is_ok());
+        params.at_block = Some(100);
+        assert!(params.validate().is_err());
+        params.after_blocks = None;
+        assert!(params.validate().is_ok());
+        params.at_block = None;
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn validate_requires_complete_repetition() {
+        let mut params = params();
+        params.repeat_every = Some(5);
+        assert!(params.validate().is_err());
+        params.repeat_count = Some(0);
+        assert!(params.validate().is_err());
+        params.repeat_count = Some(3);
+        assert!(params.validate().is_ok());
+        assert_eq!(params.maybe_periodic(), "Some((5, 3))");
+    }
+
+    #[test]
+    fn schedule_function_follows_dispatch_time() {
+        let mut params = params();
+        assert_eq!(
+            params.schedule_function(),
+            ("schedule_after", "10".to_owned())
+        );
+        params.after_blocks = None;
+        params.at_block = Some(42);
+        assert_eq!(params.schedule_function(), ("schedule", "42".to_owned()));
+    }
+}
@@ -0,0 +1,534 @@
+//! Contract storage queries
+//!
+//! Contract state is otherwise only visible through getter messages. The ink!
+//! metadata describes the storage layout: the root struct is stored under the root
+//! key, and `Lazy`/`Mapping` fields under their own root keys (a `Mapping` entry's
+//! key is followed by its encoded map key). This tool reads those cells through the
+//! contracts pallet's runtime API (`ReviveApi`, or `ContractsApi` on ink! v5 chains)
+//! and decodes them with the metadata's types.
+
+use codec::Decode;
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::encoder::{decode_hex, decode_json, encode_text, to_hex, ChainMetadata};
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::outputs::{BuildOutput, ContractStorageOutput};
+use crate::rpc::RpcPool;
+use crate::tools::common::{error_result, success_result, with_structured};
+
+/// Runtime APIs reading a contract storage cell, in order of preference.
+const STORAGE_APIS: &[(&str, &str)] = &[
+    ("ReviveApi", "get_storage_var_key"),
+    ("ReviveApi", "get_storage"),
+    ("ContractsApi", "get_storage"),
+];
+
+/// Parameters for the query_contract_storage tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct QueryContractStorageParams {
+    /// Path to the contract directory (needed for metadata).
+    #[schemars(
+        description = "Path to the contract directory; its built metadata (target/ink/*.json) describes the storage layout"
+    )]
+    pub path: String,
+    /// Contract address to read.
+    #[schemars(description = "Contract address")]
+    pub contract: String,
+    /// Storage field to read instead of the whole root storage.
+    #[schemars(
+        description = "Storage field to read, dot-separated for nested structs (e.g. 'total_supply' or 'balances'); Lazy and Mapping fields are read from their own storage cell (default: the whole root storage)"
+    )]
+    pub field: Option<String>,
+    /// Key of a Mapping field.
+    #[schemars(
+        description = "Key of the Mapping field to read, in call_contract argument syntax (e.g. an address or a number); required for Mapping fields"
+    )]
+    pub key: Option<String>,
+    /// WebSocket URL of the node.
+    #[schemars(
+        description = "WebSocket URL of the node (defaults to the node launched with up_ink_node)"
+    )]
+    pub url: Option<String>,
+}
+
+/// A storage cell of the contract and how to decode it.
+#[derive(Debug, Clone)]
+struct Cell<'a> {
+    /// Storage key: the cell's root key, followed by the encoded map key for a
+    /// `Mapping` entry.
+    key: Vec<u8>,
+    /// Layout of the value stored in the cell.
+    layout: &'a Value,
+    /// Path of the requested field below the cell's value, if it is part of it.
+    within: Vec<String>,
+}
+
+/// Root key of a `root` layout, as the bytes ink! stores it under.
+fn root_key(root: &Value) -> Result<Vec<u8>, String> {
+    root.get("root_key")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "Storage layout has a root without a root_key".to_owned())
+        .and_then(decode_hex)
+}
+
+/// Last segment of the path of type `ty` (e.g. `Mapping`).
+fn type_name(types: &scale_info::PortableRegistry, ty: Option<u64>) -> Option<&str> {
+    let ty = types.resolve(u32::try_from(ty?).ok()?)?;
+    ty.path.segments.last().map(String::as_str)
+}
+
+/// Type parameter `name` of type `ty` (e.g. the `K` of a `Mapping<K, V>`).
+fn type_param(types: &scale_info::PortableRegistry, ty: Option<u64>, name: &str) -> Option<u32> {
+    let ty = types.resolve(u32::try_from(ty?).ok()?)?;
+    ty.type_params
+        .iter()
+        .find(|param| param.name == name)
+        .and_then(|param| param.ty.map(|ty| ty.id))
+}
+
+/// Storage cell holding `field` (the root storage when `None`) of the contract
+/// described by `metadata`, with `key` encoded after a `Mapping`'s root key.
+fn cell<'a>(
+    metadata: &'a Value,
+    types: &scale_info::PortableRegistry,
+    field: Option<&str>,
+    key: Option<&str>,
+) -> Result<Cell<'a>, String> {
+    let root = metadata
+        .pointer("/storage/root")
+        .ok_or("The contract metadata has no storage layout")?;
+    let mut cell_key = root_key(root)?;
+    let mut layout = root.get("layout").ok_or("The storage root has no layout")?;
+    let mut cell_layout = layout;
+    let mut within = Vec::new();
+    let mut map_key = None;
+    let segments: Vec<&str> = field
+        .map(|field| field.split('.').filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+    for (i, segment) in segments.iter().enumerate() {
+        let fields = layout
+            .pointer("/struct/fields")
+            .and_then(Value::as_array)
+            .ok_or_else(|| format!("'{}' is not a struct with fields", segments[..i].join(".")))?;
+        let names = || {
+            fields
+                .iter()
+                .filter_map(|f| f.get("name").and_then(Value::as_str))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        layout = fields
+            .iter()
+            .find(|f| f.get("name").and_then(Value::as_str) == Some(segment))
+            .and_then(|f| f.get("layout"))
+            .ok_or_else(|| format!("Storage has no field '{}'. Fields: {}", segment, names()))?;
+        match layout.get("root") {
+            Some(root) => {
+                let ty = root.get("ty").and_then(Value::as_u64);
+                cell_key = root_key(root)?;
+                within.clear();
+                map_key = (type_name(types, ty) == Some("Mapping"))
+                    .then(|| type_param(types, ty, "K"))
+                    .map(|k| k.ok_or_else(|| format!("Mapping '{}' has no key type", segment)))
+                    .transpose()?;
+                layout = root
+                    .get("layout")
+                    .ok_or_else(|| format!("'{}' has no layout", segment))?;
+                cell_layout = layout;
+                if map_key.is_some() && i + 1 < segments.len() {
+                    return Err(format!(
+                        "'{}' is a Mapping: read it whole with key, not its fields",
+                        segment
+                    ));
+                }
+            }
+            None => within.push((*segment).to_owned()),
+        }
+    }
+    match (map_key, key) {
+        (Some(ty), Some(key)) => cell_key.extend(
+            encode_text(key, ty, types)
+                .map_err(|e| format!("Key '{}' could not be encoded: {}", key, e))?,
+        ),
+        (Some(_), None) => {
+            return Err(format!(
+                "'{}' is a Mapping: pass the entry's key",
+                field.unwrap_or_default()
+            ))
+        }
+        (None, Some(_)) => return Err("key is only used with a Mapping field".to_owned()),
+        (None, None) => {}
+    }
+    Ok(Cell {
+        key: cell_key,
+        layout: cell_layout,
+        within,
+    })
+}
+
+/// Decode the value of `layout` from `bytes`. `Lazy` and `Mapping` fields live in
+/// their own cells and show as `{"root_key": ...}`.
+fn decode_layout(
+    layout: &Value,
+    bytes: &mut &[u8],
+    types: &scale_info::PortableRegistry,
+) -> Result<Value, String> {
+    if let Some(leaf) = layout.get("leaf") {
+        let ty = leaf
+            .get("ty")
+            .and_then(Value::as_u64)
+            .and_then(|ty| u32::try_from(ty).ok())
+            .ok_or("Storage leaf has no type")?;
+        return decode_json(bytes, ty, types);
+    }
+    if let Some(root) = layout.get("root") {
+        let key = root.get("root_key").cloned().unwrap_or(Value::Null);
+        return Ok(serde_json::json!({ "root_key": key }));
+    }
+    if let Some(fields) = layout.pointer("/struct/fields").and_then(Value::as_array) {
+        let fields = decode_fields(fields, bytes, types)?;
+        return Ok(Value::Object(fields.into_iter().collect()));
+    }
+    if let Some(variants) = layout.pointer("/enum/variants").and_then(Value::as_object) {
+        let index = u8::decode(bytes).map_err(|e| e.to_string())?;
+        let variant = variants
+            .get(&index.to_string())
+            .ok_or_else(|| format!("Unknown enum variant {}", index))?;
+        let name = variant.get("name").and_then(Value::as_str).unwrap_or("?");
+        let fields = variant
+            .get("fields")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let mut fields = decode_fields(fields, bytes, types)?;
+        // Tuple variants (`Some(x)`) have fields named by position.
+        let positional = fields
+            .iter()
+            .all(|(name, _)| name.chars().all(|c| c.is_ascii_digit()));
+        let value = match fields.len() {
+            0 => return Ok(Value::String(name.to_owned())),
+            1 if positional => fields.remove(0).1,
+            _ if positional => fields.into_iter().map(|(_, value)| value).collect(),
+            _ => Value::Object(fields.into_iter().collect()),
+        };
+        return Ok(serde_json::json!({ name: value }));
+    }
+    if let Some(array) = layout.get("array") {
+        let len = array.get("len").and_then(Value::as_u64).unwrap_or_default();
+        let inner = array.get("layout").ok_or("Storage array has no layout")?;
+        return (0..len)
+            .map(|_| decode_layout(inner, bytes, types))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array);
+    }
+    Err(format!("Unsupported storage layout: {}", layout))
+}
+
+/// Decode the `fields` of a struct or enum variant layout from `bytes`, in order.
+fn decode_fields(
+    fields: &[Value],
+    bytes: &mut &[u8],
+    types: &scale_info::PortableRegistry,
+) -> Result<Vec<(String, Value)>, String> {
+    fields
+        .iter()
+        .map(|field| {
+            let name = field.get("name").and_then(Value::as_str).unwrap_or("?");
+            let layout = field
+                .get("layout")
+                .ok_or_else(|| format!("Field '{}' has no layout", name))?;
+            Ok((name.to_owned(), decode_layout(layout, bytes, types)?))
+        })
+        .collect()
+}
+
+/// Raw value of the contract's storage cell `key` at `url`, if set.
+async fn read_cell(
+    pool: &RpcPool,
+    url: &str,
+    contract: &str,
+    key: &[u8],
+) -> Result<Option<Vec<u8>>, String> {
+    let metadata = ChainMetadata::fetch(pool, url)
+        .await
+        .map_err(|e| e.to_string())?;
+    let apis: Vec<_> = STORAGE_APIS
+        .iter()
+        .filter(|(api, method)| metadata.has_runtime_api(api, method))
+        // Fixed keys are 32 bytes: ink! keys shorter than that are zero-padded.
+        .filter(|(_, method)| *method != "get_storage" || key.len() <= 32)
+        .collect();
+    if apis.is_empty() {
+        return Err(
+            "The chain exposes neither ReviveApi nor ContractsApi storage reads".to_owned(),
+        );
+    }
+    for (api, method) in apis {
+        let key = if (*api, *method) == ("ReviveApi", "get_storage") {
+            let mut fixed = key.to_vec();
+            fixed.resize(32, 0);
+            fixed
+        } else {
+            key.to_vec()
+        };
+        let (name, encoded) =
+            metadata.encode_runtime_api_call(api, method, &[contract.to_owned(), to_hex(&key)])?;
+        let params = vec![Value::String(name.clone()), Value::String(to_hex(&encoded))];
+        let output = pool
+            .request(url, "state_call", params)
+            .await
+            .map_err(|e| e.to_string())?;
+        let bytes = decode_hex(output.as_str().unwrap_or_default())?;
+        // ContractAccessError only has unit variants.
+        match Result::<Option<Vec<u8>>, u8>::decode(&mut &bytes[..]) {
+            Ok(Ok(Some(value))) => return Ok(Some(value)),
+            Ok(Ok(None)) => continue,
+            Ok(Err(0)) => return Err(format!("No contract at {}", contract)),
+            Ok(Err(error)) => return Err(format!("{} failed with error {}", name, error)),
+            Err(e) => return Err(format!("{} returned undecodable data: {}", name, e)),
+        }
+    }
+    Ok(None)
+}
+
+/// Execute query_contract_storage tool
+pub async fn query_contract_storage(
+    pool: &RpcPool,
+    params: QueryContractStorageParams,
+    stored_url: Option<&str>,
+) -> PopMcpResult<CallToolResult> {
+    let Some(url) = params.url.as_deref().or(stored_url) else {
+        return Err(PopMcpError::InvalidInput(
+            "'url' is required when no node has been launched".to_owned(),
+        ));
+    };
+    let Some(file) = BuildOutput::collect(&params.path, false).metadata else {
+        return Ok(error_result(format!(
+            "No contract metadata in {}/target/ink; build the contract first with build_contract",
+            params.path
+        )));
+    };
+    let metadata: Value = match std::fs::read_to_string(&file.path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+    {
+        Ok(metadata) => metadata,
+        Err(e) => return Ok(error_result(format!("Failed to read {}: {}", file.path, e))),
+    };
+    let types: scale_info::PortableRegistry = match serde_json::from_value(metadata.clone()) {
+        Ok(types) => types,
+        Err(e) => {
+            return Ok(error_result(format!(
+                "The contract metadata has no readable type registry: {}",
+                e
+            )))
+        }
+    };
+    let cell = match cell(
+        &metadata,
+        &types,
+        params.field.as_deref(),
+        params.key.as_deref(),
+    ) {
+        Ok(cell) => cell,
+        Err(e) => return Err(PopMcpError::InvalidInput(e)),
+    };
+    let raw = match read_cell(pool, url, &params.contract, &cell.key).await {
+        Ok(raw) => raw,
+        Err(e) => return Ok(error_result(format!("Storage query failed: {}", e))),
+    };
+    let value = match raw.as_deref() {
+        Some(mut bytes) => match decode_layout(cell.layout, &mut bytes, &types) {
+            Ok(value) => Some(
+                cell.within
+                    .iter()
+                    .try_fold(value, |value, name| value.get(name).cloned())
+                    .unwrap_or(Value::Null),
+            ),
+            Err(e) => {
+                return Ok(error_result(format!(
+                    "Storage cell {} could not be decoded: {}",
+                    to_hex(&cell.key),
+                    e
+                )))
+            }
+        },
+        None => None,
+    };
+    let field = params.field.as_deref().unwrap_or("root storage");
+    let text = match &value {
+        Some(value) => format!(
+            "{} of {}:\n{}",
+            field,
+            params.contract,
+            serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+        ),
+        None => format!(
+            "{} of {} is not set (storage key {})",
+            field,
+            params.contract,
+            to_hex(&cell.key)
+        ),
+    };
+    Ok(with_structured(
+        success_result(text),
+        &ContractStorageOutput {
+            field: params.field,
+            key: to_hex(&cell.key),
+            value,
+            raw: raw.map(|raw| to_hex(&raw)),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ink! metadata of a token with `total_supply: u128`, `owner: Option<[u8; 20]>`,
+    /// `balances: Mapping<[u8; 20], u128>` and `paused: Lazy<bool>`.
+    fn token() -> Value {
+        serde_json::json!({
+            "storage": { "root": {
+                "root_key": "0x00000000",
+                "ty": 6,
+                "layout": { "struct": { "name": "Token", "fields": [
+                    { "name": "total_supply", "layout": { "leaf": { "key": "0x00000000", "ty": 0 } } },
+                    { "name": "owner", "layout": { "enum": {
+                        "name": "Option",
+                        "dispatchKey": "0x00000000",
+                        "variants": {
+                            "0": { "name": "None", "fields": [] },
+                            "1": { "name": "Some", "fields": [
+                                { "name": "0", "layout": { "leaf": { "key": "0x00000000", "ty": 2 } } }
+                            ] }
+                        }
+                    } } },
+                    { "name": "balances", "layout": { "root": {
+                        "root_key": "0x2e3a5b1c",
+                        "ty": 4,
+                        "layout": { "leaf": { "key": "0x2e3a5b1c", "ty": 0 } }
+                    } } },
+                    { "name": "paused", "layout": { "root": {
+                        "root_key": "0x0a0b0c0d",
+                        "ty": 5,
+                        "layout": { "leaf": { "key": "0x0a0b0c0d", "ty": 3 } }
+                    } } }
+                ] } }
+            } },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "u128" } } },
+                { "id": 1, "type": { "def": { "primitive": "u8" } } },
+                { "id": 2, "type": { "def": { "array": { "len": 20, "type": 1 } } } },
+                { "id": 3, "type": { "def": { "primitive": "bool" } } },
+                { "id": 4, "type": {
+                    "path": ["ink_storage", "lazy", "mapping", "Mapping"],
+                    "params": [{ "name": "K", "type": 2 }, { "name": "V", "type": 0 }],
+                    "def": { "composite": {} }
+                } },
+                { "id": 5, "type": {
+                    "path": ["ink_storage", "lazy", "Lazy"],
+                    "params": [{ "name": "V", "type": 3 }],
+                    "def": { "composite": {} }
+                } },
+                { "id": 6, "type": { "path": ["token", "Token"], "def": { "composite": {} } } }
+            ]
+        })
+    }
+
+    fn types(metadata: &Value) -> scale_info::PortableRegistry {
+        serde_json::from_value(metadata.clone())
+            .unwrap_or_else(|_| scale_info::Registry::new().into())
+    }
+
+    #[test]
+    fn root_storage_decodes_every_field() -> Result<(), String> {
+        let metadata = token();
+        let types = types(&metadata);
+        let cell = cell(&metadata, &types, None, None)?;
+        assert_eq!(cell.key, vec![0, 0, 0, 0]);
+        let mut bytes = 1000u128.to_le_bytes().to_vec();
+        bytes.push(1);
+        bytes.extend([0xab; 20]);
+        let value = decode_layout(cell.layout, &mut &bytes[..], &types)?;
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "total_supply": 1000,
+                "owner": { "Some": format!("0x{}", "ab".repeat(20)) },
+                "balances": { "root_key": "0x2e3a5b1c" },
+                "paused": { "root_key": "0x0a0b0c0d" }
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fields_select_their_cell() -> Result<(), String> {
+        let metadata = token();
+        let types = types(&metadata);
+        let supply = cell(&metadata, &types, Some("total_supply"), None)?;
+        assert_eq!(supply.key, vec![0, 0, 0, 0]);
+        assert_eq!(supply.within, vec!["total_supply".to_owned()]);
+
+        let paused = cell(&metadata, &types, Some("paused"), None)?;
+        assert_eq!(paused.key, vec![0x0a, 0x0b, 0x0c, 0x0d]);
+        assert!(paused.within.is_empty());
+
+        let address = format!("0x{}", "01".repeat(20));
+        let balance = cell(&metadata, &types, Some("balances"), Some(&address))?;
+        let mut key = vec![0x2e, 0x3a, 0x5b, 0x1c];
+        key.extend([1; 20]);
+        assert_eq!(balance.key, key);
+        assert_eq!(
+            decode_layout(balance.layout, &mut &7u128.to_le_bytes()[..], &types),
+            Ok(serde_json::json!(7))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn storage_needs_a_url_and_built_metadata() -> anyhow::Result<()> {
+        let dir = tempfile::TempDir::new()?;
+        let params = QueryContractStorageParams {
+            path: dir.path().display().to_string(),
+            contract: format!("0x{}", "58".repeat(20)),
+            field: None,
+            key: None,
+            url: None,
+        };
+        let pool = RpcPool::new();
+        let result = query_contract_storage(&pool, params.clone(), None).await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+        let result = query_contract_storage(&pool, params, Some("ws://localhost:9944")).await?;
+        assert_eq!(result.is_error, Some(true));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_selections_are_explained() {
+        let metadata = token();
+        let types = types(&metadata);
+        let error = |field, key| cell(&metadata, &types, field, key).err();
+        assert_eq!(
+            error(Some("balances"), None),
+            Some("'balances' is a Mapping: pass the entry's key".to_owned())
+        );
+        assert_eq!(
+            error(Some("paused"), Some("1")),
+            Some("key is only used with a Mapping field".to_owned())
+        );
+        assert_eq!(
+            error(Some("supply"), None),
+            Some(
+                "Storage has no field 'supply'. Fields: total_supply, owner, balances, paused"
+                    .to_owned()
+            )
+        );
+        assert!(error(Some("balances"), Some("not an address")).is_some());
+    }
+}
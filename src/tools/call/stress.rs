@@ -0,0 +1,403 @@
+//! Contract load testing (repeated pop call contract --execute)
+//!
+//! Each worker signs with its own account and submits sequentially, so concurrent
+//! transactions never race for the same nonce.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::tools::call::contract::is_error_output;
+use crate::tools::common::{error_result, success_result};
+
+/// Most transactions submitted by one call.
+const MAX_TRANSACTIONS: u32 = 1000;
+
+/// Signers used when none are given.
+const DEFAULT_SIGNERS: &[&str] = &[
+    "//Alice",
+    "//Bob",
+    "//Charlie",
+    "//Dave",
+    "//Eve",
+    "//Ferdie",
+];
+
+/// Distinct failure reasons listed in the report.
+const MAX_REASONS: usize = 5;
+
+/// Longest failure reason shown, in characters.
+const MAX_REASON_LEN: usize = 160;
+
+/// Parameters for the stress_call tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct StressCallParams {
+    /// Path to the contract directory (needed for metadata).
+    #[schemars(description = "Path to the contract directory (needed for contract metadata)")]
+    pub path: String,
+    /// Contract address to call.
+    #[schemars(description = "Contract address")]
+    pub contract: String,
+    /// Message to call.
+    #[schemars(description = "Mutating message to call in every transaction")]
+    pub message: String,
+    /// Message arguments as space-separated values.
+    #[schemars(description = "Message arguments as space-separated values")]
+    pub args: Option<String>,
+    /// Number of transactions to submit.
+    #[schemars(description = "Number of transactions to submit (at most 1000)")]
+    pub transactions: u32,
+    /// Transactions in flight at once.
+    #[schemars(
+        description = "Transactions in flight at once (default and maximum: number of signers; each signer submits sequentially)"
+    )]
+    pub concurrency: Option<u32>,
+    /// Signing accounts.
+    #[schemars(
+        description = "Dev SURIs to sign with, one per concurrent worker (default: //Alice, //Bob, //Charlie, //Dave, //Eve, //Ferdie). Accounts from derive_test_accounts work once funded."
+    )]
+    pub signers: Option<Vec<String>>,
+    /// WebSocket URL of a local node.
+    #[schemars(
+        description = "WebSocket URL of a local node (default: the node started by up_ink_node). Shared networks are refused."
+    )]
+    pub url: Option<String>,
+}
+
+impl StressCallParams {
+    /// Validate the parameters
+    fn validate(&self) -> Result<(), String> {
+        if self.transactions == 0 || self.transactions > MAX_TRANSACTIONS {
+            return Err(format!(
+                "'transactions' must be between 1 and {}",
+                MAX_TRANSACTIONS
+            ));
+        }
+        if self.concurrency == Some(0) {
+            return Err("'concurrency' must be greater than 0".to_owned());
+        }
+        if self
+            .signers
+            .as_ref()
+            .is_some_and(|signers| signers.is_empty() || signers.iter().any(String::is_empty))
+        {
+            return Err("'signers' must list at least one non-empty SURI".to_owned());
+        }
+        Ok(())
+    }
+
+    /// Signing accounts, given or default.
+    fn signers(&self) -> Vec<String> {
+        self.signers.clone().unwrap_or_else(|| {
+            DEFAULT_SIGNERS
+                .iter()
+                .map(|signer| (*signer).to_owned())
+                .collect()
+        })
+    }
+}
+
+/// Whether `url` points at a node on this machine.
+pub(crate) fn is_local_url(url: &str) -> bool {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let host = if let Some(bracketed) = rest.strip_prefix('[') {
+        bracketed.split(']').next().unwrap_or_default()
+    } else {
+        rest.split([':', '/']).next().unwrap_or_default()
+    };
+    matches!(host, "localhost" | "127.0.0.1" | "0.0.0.0" | "::1")
+}
+
+/// Build the command arguments shared by every transaction (signer excluded).
+fn build_stress_call_args(params: &StressCallParams, url: &str) -> Vec<String> {
+    let mut args = vec![
+        "call".to_owned(),
+        "contract".to_owned(),
+        "--path".to_owned(),
+        params.path.clone(),
+        "--contract".to_owned(),
+        params.contract.clone(),
+        "--message".to_owned(),
+        params.message.clone(),
+        "-y".to_owned(),
+    ];
+    if let Some(ref contract_args) = params.args {
+        args.push("--args".to_owned());
+        args.extend(contract_args.split_whitespace().map(str::to_owned));
+    }
+    args.push("--url".to_owned());
+    args.push(url.to_owned());
+    args.push("--execute".to_owned());
+    args
+}
+
+/// Outcome of one transaction.
+struct Sample {
+    latency: Duration,
+    failure: Option<String>,
+}
+
+/// Short reason for a failed transaction: its first error line, else its first line.
+fn failure_reason(output: &str) -> String {
+    let lines = || {
+        output
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+    };
+    let line = lines()
+        .find(|line| line.to_lowercase().contains("error"))
+        .or_else(|| lines().next())
+        .unwrap_or("(no output)");
+    line.chars().take(MAX_REASON_LEN).collect()
+}
+
+/// Submit transactions with `signer` until `next` runs past `total`.
+async fn worker<E: CommandExecutor>(
+    executor: E,
+    base: Arc<Vec<String>>,
+    signer: String,
+    next: Arc<AtomicU32>,
+    total: u32,
+) -> Vec<Sample> {
+    let mut args = base.as_ref().clone();
+    args.push("--suri".to_owned());
+    args.push(signer);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let mut samples = Vec::new();
+    while next.fetch_add(1, Ordering::Relaxed) < total {
+        if executor.is_cancelled() {
+            break;
+        }
+        let started = Instant::now();
+        let failure = match executor.execute(&args).await {
+            Ok(output) if is_error_output(&output) => Some(failure_reason(&output)),
+            Ok(_) => None,
+            Err(PopMcpError::CommandExecution(output)) => Some(failure_reason(&output)),
+            Err(e) => Some(e.to_string()),
+        };
+        samples.push(Sample {
+            latency: started.elapsed(),
+            failure,
+        });
+    }
+    samples
+}
+
+/// Throughput, latency and failure report for `samples` collected over `elapsed`.
+fn report(samples: &[Sample], elapsed: Duration, workers: usize, url: &str) -> String {
+    let failed: Vec<&str> = samples
+        .iter()
+        .filter_map(|sample| sample.failure.as_deref())
+        .collect();
+    let succeeded = samples.len() - failed.len();
+    let mut latencies: Vec<Duration> = samples.iter().map(|sample| sample.latency).collect();
+    latencies.sort();
+    let secs = elapsed.as_secs_f64();
+    let per_sec = |count: usize| {
+        if secs > 0.0 {
+            count as f64 / secs
+        } else {
+            0.0
+        }
+    };
+    let percentile = |p: usize| {
+        latencies
+            .get((latencies.len() * p / 100).min(latencies.len().saturating_sub(1)))
+            .map_or(0, Duration::as_millis)
+    };
+    let mut lines = vec![
+        format!(
+            "Submitted {} transactions to {} with {} concurrent signer(s) in {:.2}s",
+            samples.len(),
+            url,
+            workers,
+            secs
+        ),
+        format!("Succeeded: {} ({:.2} tx/s)", succeeded, per_sec(succeeded)),
+        format!(
+            "Failed: {} ({:.1}%)",
+            failed.len(),
+            if samples.is_empty() {
+                0.0
+            } else {
+                failed.len() as f64 * 100.0 / samples.len() as f64
+            }
+        ),
+        format!(
+            "Latency: p50 {} ms, p95 {} ms, max {} ms",
+            percentile(50),
+            percentile(95),
+            latencies.last().map_or(0, Duration::as_millis)
+        ),
+    ];
+    let mut reasons: BTreeMap<&str, usize> = BTreeMap::new();
+    for reason in failed {
+        *reasons.entry(reason).or_default() += 1;
+    }
+    let mut reasons: Vec<(&str, usize)> = reasons.into_iter().collect();
+    reasons.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    if !reasons.is_empty() {
+        lines.push(String::new());
+        lines.push("Failure reasons:".to_owned());
+        lines.extend(
+            reasons
+                .iter()
+                .take(MAX_REASONS)
+                .map(|(reason, count)| format!("  {} x {}", count, reason)),
+        );
+    }
+    lines.join("\n")
+}
+
+/// Execute stress_call tool
+pub async fn stress_call<E: CommandExecutor>(
+    executor: &E,
+    params: StressCallParams,
+    stored_url: Option<&str>,
+) -> PopMcpResult<CallToolResult> {
+    params.validate().map_err(PopMcpError::InvalidInput)?;
+
+    let Some(url) = params.url.as_deref().or(stored_url) else {
+        return Err(PopMcpError::InvalidInput(
+            "'url' is required when no local node was started with up_ink_node".to_owned(),
+        ));
+    };
+    if !is_local_url(url) {
+        return Err(PopMcpError::InvalidInput(format!(
+            "stress_call only targets local nodes (localhost, 127.0.0.1); refusing {}",
+            url
+        )));
+    }
+
+    let signers = params.signers();
+    let workers = params
+        .concurrency
+        .map_or(signers.len(), |c| c as usize)
+        .min(signers.len())
+        .min(params.transactions as usize);
+    let base = Arc::new(build_stress_call_args(&params, url));
+    let next = Arc::new(AtomicU32::new(0));
+
+    let started = Instant::now();
+    let mut tasks = tokio::task::JoinSet::new();
+    for signer in signers.into_iter().take(workers) {
+        tasks.spawn(worker(
+            executor.clone(),
+            Arc::clone(&base),
+            signer,
+            Arc::clone(&next),
+            params.transactions,
+        ));
+    }
+    let mut samples = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        samples.extend(joined.map_err(|e| PopMcpError::Internal(e.to_string()))?);
+    }
+    let text = report(&samples, started.elapsed(), workers, url);
+
+    if samples.iter().all(|sample| sample.failure.is_some()) {
+        return Ok(error_result(format!("Load test failed:\n\n{}", text)));
+    }
+    Ok(success_result(format!("Load test complete\n\n{}", text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutor;
+
+    fn params(transactions: u32) -> StressCallParams {
+        StressCallParams {
+            path: "./flipper".to_owned(),
+            contract: "0x5801b439a678d9d3a68b8019da6a4abfa507de11".to_owned(),
+            message: "flip".to_owned(),
+            args: None,
+            transactions,
+            concurrency: None,
+            signers: None,
+            url: None,
+        }
+    }
+
+    #[test]
+    fn local_urls_are_recognised() {
+        assert!(is_local_url("ws://localhost:9944"));
+        assert!(is_local_url("ws://127.0.0.1:9944/"));
+        assert!(is_local_url("ws://[::1]:9944"));
+        assert!(!is_local_url("wss://rpc1.paseo.popnetwork.xyz"));
+        assert!(!is_local_url("ws://localhost.example.com:9944"));
+    }
+
+    #[test]
+    fn failure_reason_prefers_error_lines() {
+        assert_eq!(
+            failure_reason("Submitting...\nError: Module error: ContractTrapped\n"),
+            "Error: Module error: ContractTrapped"
+        );
+        assert_eq!(failure_reason(""), "(no output)");
+    }
+
+    #[tokio::test]
+    #[allow(clippy::panic)]
+    async fn stress_call_spreads_transactions_over_signers() {
+        let executor =
+            MockExecutor::new().on(&["call", "contract"], "Call completed successfully!");
+        let result = stress_call(
+            &executor,
+            StressCallParams {
+                concurrency: Some(2),
+                ..params(5)
+            },
+            Some("ws://localhost:9944"),
+        )
+        .await;
+        let Ok(result) = result else {
+            panic!("stress_call failed: {result:?}");
+        };
+        assert_eq!(result.is_error, Some(false));
+        let text = crate::tools::common::content_text(&result);
+        assert!(text.contains("Succeeded: 5"));
+        let calls = executor.calls();
+        assert_eq!(calls.len(), 5);
+        let signers: std::collections::BTreeSet<&str> = calls
+            .iter()
+            .filter_map(|call| call.args.last().map(String::as_str))
+            .collect();
+        assert!(signers.len() <= 2);
+        assert!(calls
+            .iter()
+            .all(|call| call.args.contains(&"--execute".to_owned())));
+    }
+
+    #[tokio::test]
+    async fn stress_call_reports_failures() {
+        let executor =
+            MockExecutor::new().on_failure(&["call", "contract"], "Error: Module error: OutOfGas");
+        let result = stress_call(&executor, params(3), Some("ws://127.0.0.1:9944")).await;
+        let text = result.map(|r| crate::tools::common::content_text(&r));
+        assert!(text.is_ok_and(|text| text.contains("3 x Error: Module error: OutOfGas")));
+    }
+
+    #[tokio::test]
+    async fn stress_call_refuses_shared_networks() {
+        let result = stress_call(
+            &MockExecutor::new(),
+            StressCallParams {
+                url: Some("wss://rpc1.paseo.popnetwork.xyz".to_owned()),
+                ..params(1)
+            },
+            None,
+        )
+        .await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,509 @@
+//! Capability discovery (what works in this environment)
+//!
+//! Agents plan multi-step workflows (scaffold, build, launch, deploy) and otherwise only
+//! learn that a step is unavailable when it fails halfway through. The capabilities
+//! tool checks up front which Pop CLI subcommands exist, which node binaries are
+//! already cached, whether frontend tooling is installed and which networks answer.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpStream;
+use tokio::task::JoinSet;
+
+use crate::endpoints::NETWORKS;
+use crate::error::PopMcpResult;
+use crate::executor::CommandExecutor;
+use crate::tools::common::{success_result, with_structured};
+
+/// How long each reachability check may take.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Host serving the project templates.
+const TEMPLATES_HOST: (&str, &str) = ("GitHub (templates, binary downloads)", "github.com");
+
+/// Node binaries pop downloads on first use.
+const NODE_BINARIES: &[&str] = &[
+    "ink-node",
+    "eth-rpc",
+    "polkadot",
+    "polkadot-parachain",
+    "substrate-contracts-node",
+];
+
+/// Programs used by frontend templates.
+const FRONTEND_TOOLS: &[&str] = &["node", "npm", "npx", "bun", "pnpm", "yarn"];
+
+/// Features backed by a Pop CLI subcommand: (feature, subcommand, tools).
+const POP_FEATURES: &[(&str, &str, &[&str])] = &[
+    (
+        "Scaffold projects",
+        "new",
+        &["create_contract", "create_chain"],
+    ),
+    ("Build", "build", &["build_contract", "build_chain"]),
+    ("Test", "test", &["test_contract", "test_chain"]),
+    (
+        "Launch nodes and deploy",
+        "up",
+        &["up_ink_node", "up_network", "deploy_contract"],
+    ),
+    (
+        "Call contracts and chains",
+        "call",
+        &["call_contract", "call_chain"],
+    ),
+    ("Clean up", "clean", &["clean_nodes", "clean_network"]),
+];
+
+/// Parameters for the capabilities tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct CapabilitiesParams {
+    /// Whether to check network reachability.
+    #[schemars(
+        description = "Check that GitHub and the public testnet RPC endpoints are reachable (default: true; takes up to 3s)"
+    )]
+    pub check_network: Option<bool>,
+}
+
+/// One row of the support matrix.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Feature {
+    /// What the feature does.
+    pub name: String,
+    /// Whether it is expected to work.
+    pub available: bool,
+    /// Why, or what is missing.
+    pub detail: String,
+    /// Tools relying on it.
+    pub tools: Vec<String>,
+}
+
+/// A program found on `PATH`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Program {
+    /// Program name.
+    pub name: String,
+    /// Where it was found.
+    pub path: PathBuf,
+}
+
+/// Result of a reachability check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Reachability {
+    /// Service or network name.
+    pub name: String,
+    /// Host checked.
+    pub host: String,
+    /// Whether a TCP connection to port 443 succeeded.
+    pub reachable: bool,
+}
+
+/// Everything the capabilities tool found.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Capabilities {
+    /// `pop --version` output, when Pop CLI runs.
+    pub pop_version: Option<String>,
+    /// Subcommands listed by `pop --help`.
+    pub subcommands: Vec<String>,
+    /// Node binaries already in pop's cache.
+    pub cached_binaries: Vec<String>,
+    /// Frontend programs on `PATH`.
+    pub frontend_tools: Vec<Program>,
+    /// Reachability of GitHub and public networks (empty when not checked).
+    pub network: Vec<Reachability>,
+    /// The support matrix.
+    pub features: Vec<Feature>,
+}
+
+/// Subcommands in the `Commands:` section of `pop --help` output.
+fn subcommands(help: &str) -> Vec<String> {
+    help.lines()
+        .skip_while(|line| line.trim() != "Commands:")
+        .skip(1)
+        .take_while(|line| line.starts_with(' '))
+        .filter_map(|line| line.split_whitespace().next())
+        .filter(|name| *name != "help")
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Directory where pop caches downloaded binaries.
+fn pop_cache_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var("XDG_CACHE_HOME") {
+        return Some(PathBuf::from(dir).join("pop"));
+    }
+    let home = PathBuf::from(std::env::var("HOME").ok()?);
+    Some(if cfg!(target_os = "macos") {
+        home.join("Library/Caches/pop")
+    } else {
+        home.join(".cache/pop")
+    })
+}
+
+/// Node binaries in `dir`, including versioned ones (e.g. `polkadot-stable2503`).
+fn cached_binaries(dir: &Path) -> Vec<String> {
+    let mut found: Vec<String> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| {
+            NODE_BINARIES
+                .iter()
+                .any(|binary| name == binary || name.starts_with(&format!("{}-", binary)))
+        })
+        .collect();
+    found.sort();
+    found
+}
+
+/// `name` in the directories of `path` (a `PATH` value), if present.
+fn find_program(name: &str, path: &str) -> Option<PathBuf> {
+    std::env::split_paths(path)
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Host of a `wss://host[:port]/path` URL.
+fn host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split('/').next().unwrap_or(rest);
+    authority.split(':').next().unwrap_or(authority)
+}
+
+/// Check that GitHub and the first endpoint of each public network accept connections.
+async fn check_network() -> Vec<Reachability> {
+    let targets = std::iter::once(TEMPLATES_HOST).chain(
+        NETWORKS
+            .iter()
+            .filter_map(|network| Some((network.name, host(network.endpoints.first()?)))),
+    );
+    let mut checks = JoinSet::new();
+    for (order, (name, host)) in targets.enumerate() {
+        checks.spawn(async move {
+            let connect = TcpStream::connect((host, 443));
+            let reachable = matches!(
+                tokio::time::timeout(CONNECT_TIMEOUT, connect).await,
+                Ok(Ok(_))
+            );
+            (
+                order,
+                Reachability {
+                    name: name.to_owned(),
+                    host: host.to_owned(),
+                    reachable,
+                },
+            )
+        });
+    }
+    let mut results: Vec<(usize, Reachability)> = checks.join_all().await;
+    results.sort_by_key(|(order, _)| *order);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// The support matrix for what was found.
+fn features(
+    pop_version: Option<&str>,
+    subcommands: &[String],
+    cached: &[String],
+    frontend: &[Program],
+    network: Option<&[Reachability]>,
+) -> Vec<Feature> {
+    let tools = |names: &[&str]| names.iter().map(|name| (*name).to_owned()).collect();
+    let mut features: Vec<Feature> = POP_FEATURES
+        .iter()
+        .map(|(name, subcommand, names)| {
+            let available = subcommands.iter().any(|s| s == subcommand);
+            let detail = match (pop_version, available) {
+                (None, _) => "Pop CLI is not installed (see install_pop_instructions)".to_owned(),
+                (Some(_), true) => format!("pop {}", subcommand),
+                (Some(_), false) => format!("this Pop CLI has no '{}' subcommand", subcommand),
+            };
+            Feature {
+                name: (*name).to_owned(),
+                available,
+                detail,
+                tools: tools(names),
+            }
+        })
+        .collect();
+
+    let has_ink_node = cached.iter().any(|name| name == "ink-node");
+    features.push(Feature {
+        name: "Local ink! node without a download".to_owned(),
+        available: has_ink_node,
+        detail: if has_ink_node {
+            "ink-node is cached".to_owned()
+        } else {
+            "ink-node is not cached: the first up_ink_node downloads it".to_owned()
+        },
+        tools: tools(&["up_ink_node", "self_test"]),
+    });
+
+    let has = |name: &str| frontend.iter().any(|program| program.name == name);
+    let runner = ["npm", "bun", "pnpm", "yarn"]
+        .into_iter()
+        .find(|name| has(name));
+    features.push(Feature {
+        name: "Contract frontends".to_owned(),
+        available: has("node") && runner.is_some(),
+        detail: match (has("node"), runner) {
+            (true, Some(runner)) => format!("node and {}", runner),
+            (false, _) => "node is not on PATH".to_owned(),
+            (true, None) => "no package manager (npm, bun, pnpm or yarn) on PATH".to_owned(),
+        },
+        tools: tools(&["create_contract"]),
+    });
+
+    if let Some(network) = network {
+        let github = network
+            .iter()
+            .any(|r| r.name == TEMPLATES_HOST.0 && r.reachable);
+        features.push(Feature {
+            name: "Templates and binary downloads".to_owned(),
+            available: github,
+            detail: if github {
+                "github.com is reachable".to_owned()
+            } else {
+                "github.com is unreachable: scaffolding and first node launches fail".to_owned()
+            },
+            tools: tools(&["create_contract", "create_chain", "up_ink_node"]),
+        });
+        let testnets: Vec<&str> = network
+            .iter()
+            .filter(|r| r.name != TEMPLATES_HOST.0 && r.reachable)
+            .map(|r| r.name.as_str())
+            .collect();
+        features.push(Feature {
+            name: "Public testnets".to_owned(),
+            available: !testnets.is_empty(),
+            detail: if testnets.is_empty() {
+                "no public testnet endpoint is reachable".to_owned()
+            } else {
+                format!("reachable: {}", testnets.join(", "))
+            },
+            tools: tools(&[
+                "deploy_contract",
+                "call_contract",
+                "call_chain",
+                "chain_rpc",
+            ]),
+        });
+    }
+    features
+}
+
+/// The report shown to the agent.
+fn report(capabilities: &Capabilities) -> String {
+    let mut lines = vec![format!(
+        "Pop CLI: {}",
+        capabilities
+            .pop_version
+            .as_deref()
+            .unwrap_or("not installed")
+    )];
+    lines.push(String::new());
+    lines.push("Features:".to_owned());
+    for feature in &capabilities.features {
+        lines.push(format!(
+            "[{}] {}: {} ({})",
+            if feature.available { "x" } else { " " },
+            feature.name,
+            feature.detail,
+            feature.tools.join(", ")
+        ));
+    }
+    lines.push(String::new());
+    lines.push(format!(
+        "Cached node binaries: {}",
+        if capabilities.cached_binaries.is_empty() {
+            "none".to_owned()
+        } else {
+            capabilities.cached_binaries.join(", ")
+        }
+    ));
+    let frontend: Vec<&str> = capabilities
+        .frontend_tools
+        .iter()
+        .map(|program| program.name.as_str())
+        .collect();
+    lines.push(format!(
+        "Frontend tooling on PATH: {}",
+        if frontend.is_empty() {
+            "none".to_owned()
+        } else {
+            frontend.join(", ")
+        }
+    ));
+    if capabilities.network.is_empty() {
+        lines.push("Network: not checked".to_owned());
+    } else {
+        let hosts: Vec<String> = capabilities
+            .network
+            .iter()
+            .map(|r| {
+                let state = if r.reachable { "ok" } else { "unreachable" };
+                format!("{} ({}) {}", r.name, r.host, state)
+            })
+            .collect();
+        lines.push(format!("Network: {}", hosts.join("; ")));
+    }
+    lines.join("\n")
+}
+
+/// Execute capabilities tool
+pub async fn capabilities<E: CommandExecutor>(
+    executor: &E,
+    params: CapabilitiesParams,
+) -> PopMcpResult<CallToolResult> {
+    let pop_version = executor
+        .execute(&["--version"])
+        .await
+        .ok()
+        .map(|output| output.trim().to_owned());
+    let subcommands = match pop_version {
+        Some(_) => executor
+            .execute(&["--help"])
+            .await
+            .map(|help| subcommands(&help))
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+    let cached_binaries = pop_cache_dir()
+        .map(|dir| cached_binaries(&dir))
+        .unwrap_or_default();
+    let path = std::env::var("PATH").unwrap_or_default();
+    let frontend_tools = FRONTEND_TOOLS
+        .iter()
+        .filter_map(|name| {
+            find_program(name, &path).map(|path| Program {
+                name: (*name).to_owned(),
+                path,
+            })
+        })
+        .collect::<Vec<_>>();
+    let network = if params.check_network.unwrap_or(true) {
+        Some(check_network().await)
+    } else {
+        None
+    };
+    let features = features(
+        pop_version.as_deref(),
+        &subcommands,
+        &cached_binaries,
+        &frontend_tools,
+        network.as_deref(),
+    );
+    let capabilities = Capabilities {
+        pop_version,
+        subcommands,
+        cached_binaries,
+        frontend_tools,
+        network: network.unwrap_or_default(),
+        features,
+    };
+    Ok(with_structured(
+        success_result(report(&capabilities)),
+        &capabilities,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutor;
+    use crate::tools::common::content_text;
+
+    const HELP: &str = "\
+Usage: pop <COMMAND>
+
+Commands:
+  new      Generate a new chain or smart contract
+  build    Build a chain or smart contract
+  up       Launch a local network or deploy a smart contract
+  help     Print this message or the help of the given subcommand(s)
+
+Options:
+  -h, --help  Print help";
+
+    #[test]
+    fn subcommands_come_from_the_help_commands_section() {
+        assert_eq!(subcommands(HELP), vec!["new", "build", "up"]);
+        assert!(subcommands("error: unknown flag").is_empty());
+    }
+
+    #[test]
+    fn cached_node_binaries_include_versioned_ones() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        for name in [
+            "ink-node",
+            "polkadot-stable2503",
+            "polkadot-parachain",
+            "notes.txt",
+        ] {
+            std::fs::write(dir.path().join(name), "")?;
+        }
+        std::fs::create_dir(dir.path().join("eth-rpc"))?;
+        assert_eq!(
+            cached_binaries(dir.path()),
+            vec!["ink-node", "polkadot-parachain", "polkadot-stable2503"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn programs_are_found_on_path() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("node"), "")?;
+        let path = std::env::join_paths(["/nonexistent", &dir.path().display().to_string()])?;
+        let path = path.to_string_lossy();
+        assert_eq!(find_program("node", &path), Some(dir.path().join("node")));
+        assert_eq!(find_program("bun", &path), None);
+        assert_eq!(host("wss://rpc.ibp.network:443/paseo"), "rpc.ibp.network");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn missing_subcommands_are_reported_unavailable() -> anyhow::Result<()> {
+        let executor = MockExecutor::new()
+            .on(&["--version"], "pop 0.9.0")
+            .on(&["--help"], HELP);
+        let result = capabilities(
+            &executor,
+            CapabilitiesParams {
+                check_network: Some(false),
+            },
+        )
+        .await?;
+        let text = content_text(&result);
+        assert!(text.starts_with("Pop CLI: pop 0.9.0"));
+        assert!(text.contains("[x] Build: pop build (build_contract, build_chain)"));
+        assert!(
+            text.contains("[ ] Call contracts and chains: this Pop CLI has no 'call' subcommand")
+        );
+        assert!(text.contains("Network: not checked"));
+        let Some(structured) = result.structured_content else {
+            anyhow::bail!("expected structured content");
+        };
+        assert_eq!(
+            structured["subcommands"],
+            serde_json::json!(["new", "build", "up"])
+        );
+
+        let missing = capabilities(
+            &MockExecutor::new(),
+            CapabilitiesParams {
+                check_network: Some(false),
+            },
+        )
+        .await?;
+        assert!(content_text(&missing).contains("Pop CLI is not installed"));
+        Ok(())
+    }
+}
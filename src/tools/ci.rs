@@ -0,0 +1,219 @@
+//! CI mode for deploy_contract and call_contract
+//!
+//! With `ci_mode`, the tools behave as a deployment step of a pipeline rather than a
+//! chat helper: nothing is asked or guessed (no elicitation, no selected-node URL, no
+//! failover to other endpoints), the signer comes from an explicit keystore alias, a
+//! deployment needs a fixed salt and the code hash the artifact is expected to have,
+//! and every outcome — including validation errors and non-zero exits — is a single
+//! JSON report.
+
+use std::path::Path;
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::executor::CommandExecutor;
+use crate::redact::Secrets;
+use crate::tools::common::{error_result, success_result, with_structured};
+
+/// Prefix of the environment variables holding keystore SURIs (`POP_MCP_KEY_<ALIAS>`).
+pub const SIGNER_ENV_PREFIX: &str = "POP_MCP_KEY_";
+
+/// Step of a CI run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CiStage {
+    /// Checking the parameters and resolving the signer.
+    Validate,
+    /// Checking the artifact's code hash.
+    Verify,
+    /// Running the pop command.
+    Execute,
+}
+
+/// Outcome of a CI run, returned as the only content (JSON) and as structured content.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CiReport {
+    /// Whether the run succeeded.
+    pub ok: bool,
+    /// Tool that ran.
+    pub tool: String,
+    /// Step reached: the failing one, or `execute` on success.
+    pub stage: CiStage,
+    /// Keystore alias of the signer.
+    pub signer: Option<String>,
+    /// The pop command, with the SURI masked, once it was resolved.
+    pub command: Option<String>,
+    /// Why the run failed.
+    pub error: Option<String>,
+    /// Tool-specific result fields (e.g. `address` and `code_hash`).
+    #[serde(flatten)]
+    pub result: Option<Value>,
+}
+
+impl CiReport {
+    /// Report for `tool` signed by `signer`, not run yet.
+    pub(crate) fn new(tool: &str, signer: Option<&str>) -> Self {
+        Self {
+            ok: false,
+            tool: tool.to_owned(),
+            stage: CiStage::Validate,
+            signer: signer.map(str::to_owned),
+            command: None,
+            error: None,
+            result: None,
+        }
+    }
+
+    /// This report as a failure at `stage`.
+    pub(crate) fn fail(mut self, stage: CiStage, error: impl Into<String>) -> CallToolResult {
+        self.stage = stage;
+        self.error = Some(error.into());
+        self.into_result()
+    }
+
+    /// This report as a success with `result` fields.
+    pub(crate) fn succeed(mut self, result: &impl Serialize) -> CallToolResult {
+        self.ok = true;
+        self.stage = CiStage::Execute;
+        self.result = serde_json::to_value(result).ok();
+        self.into_result()
+    }
+
+    fn into_result(self) -> CallToolResult {
+        let json = serde_json::to_string_pretty(&self).unwrap_or_default();
+        let result = if self.ok {
+            success_result(json)
+        } else {
+            error_result(json)
+        };
+        with_structured(result, &self)
+    }
+}
+
+/// Environment variable holding the SURI of keystore alias `alias`.
+pub(crate) fn signer_env(alias: &str) -> String {
+    let name: String = alias
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}{}", SIGNER_ENV_PREFIX, name)
+}
+
+/// SURI of keystore alias `alias`, from its environment variable.
+pub(crate) fn signer_suri(alias: Option<&str>) -> Result<String, String> {
+    let alias = alias
+        .map(str::trim)
+        .filter(|alias| !alias.is_empty())
+        .ok_or("ci_mode requires 'signer', the keystore alias to sign with")?;
+    let env = signer_env(alias);
+    std::env::var(&env)
+        .ok()
+        .filter(|suri| !suri.trim().is_empty())
+        .ok_or_else(|| format!("No key for signer '{}': set {}", alias, env))
+}
+
+/// Code hash recorded in the ink! metadata under `<path>/target/ink`.
+pub(crate) fn artifact_code_hash(path: &str) -> Result<String, String> {
+    let dir = Path::new(path).join("target/ink");
+    let mut files: Vec<_> = std::fs::read_dir(&dir)
+        .map_err(|e| format!("No build artifacts in {}: {}", dir.display(), e))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+        .collect();
+    files.sort();
+    files
+        .iter()
+        .find_map(|file| {
+            let metadata: Value =
+                serde_json::from_str(&std::fs::read_to_string(file).ok()?).ok()?;
+            metadata
+                .pointer("/source/hash")
+                .and_then(Value::as_str)
+                .map(str::to_owned)
+        })
+        .ok_or_else(|| format!("No code hash in the contract metadata in {}", dir.display()))
+}
+
+/// Whether two 0x-prefixed hashes are equal, ignoring case.
+pub(crate) fn same_hash(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+/// Run `args` once against the given URL (retrying transient failures, never failing
+/// over), recording the masked command in `report`. Returns the output or the
+/// failure report.
+pub(crate) async fn execute<E: CommandExecutor>(
+    executor: &E,
+    report: &mut CiReport,
+    args: &[String],
+) -> Result<String, CallToolResult> {
+    let refs: Vec<&str> = args.iter().map(String::as_str).collect();
+    report.command = Some(format!("pop {}", Secrets::for_args(&refs).mask_args(&refs)));
+    executor
+        .execute_json_with_retry(&refs)
+        .await
+        .map_err(|e| report.clone().fail(CiStage::Execute, e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::common::content_text;
+
+    #[test]
+    fn signer_aliases_name_environment_variables() {
+        assert_eq!(signer_env("ci-deployer"), "POP_MCP_KEY_CI_DEPLOYER");
+        assert!(signer_suri(None).is_err());
+        let missing = signer_suri(Some("pop-mcp-test-missing-alias"));
+        assert_eq!(
+            missing.err().as_deref(),
+            Some(
+                "No key for signer 'pop-mcp-test-missing-alias': set POP_MCP_KEY_POP_MCP_TEST_MISSING_ALIAS"
+            )
+        );
+    }
+
+    #[test]
+    fn artifact_code_hashes_come_from_metadata() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().display().to_string();
+        assert!(artifact_code_hash(&path).is_err());
+        std::fs::create_dir_all(dir.path().join("target/ink"))?;
+        std::fs::write(
+            dir.path().join("target/ink/flipper.json"),
+            r#"{"source": {"hash": "0xABCD"}}"#,
+        )?;
+        let hash = artifact_code_hash(&path).map_err(anyhow::Error::msg)?;
+        assert!(same_hash(&hash, "0xabcd"));
+        Ok(())
+    }
+
+    #[test]
+    fn reports_are_json_with_flattened_results() -> anyhow::Result<()> {
+        let result = CiReport::new("deploy_contract", Some("ci")).succeed(&serde_json::json!({
+            "address": "0x58"
+        }));
+        let report: Value = serde_json::from_str(&content_text(&result))?;
+        assert_eq!(report["ok"], true);
+        assert_eq!(report["address"], "0x58");
+        assert_eq!(result.structured_content, Some(report));
+
+        let failed = CiReport::new("call_contract", None).fail(CiStage::Validate, "no signer");
+        assert_eq!(failed.is_error, Some(true));
+        let report: Value = serde_json::from_str(&content_text(&failed))?;
+        assert_eq!(report["stage"], "validate");
+        assert_eq!(report["error"], "no signer");
+        Ok(())
+    }
+}
@@ -1,19 +1,39 @@
 //! Clean tools (pop clean)
+//!
+//! Nodes and networks are stopped through `pop clean`. Build output is removed like
+//! `cargo clean` does, by deleting the project's `target` directory, measured first
+//! so the result (or a dry run) states the space freed.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use rmcp::model::CallToolResult;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::error::PopMcpResult;
-use crate::executor::PopExecutor;
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::nodes::{NodeRegistry, RegisteredNode};
+use crate::parsing;
 use crate::tools::common::{error_result, success_result};
 
+/// How long a stopped node's port may take to close.
+const PORT_CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Parameters for the clean_nodes tool.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 #[schemars(extend("properties" = {}))]
 pub struct CleanNodesParams {
     /// Process IDs of nodes to stop.
+    #[schemars(description = "Process ids of the nodes to stop")]
+    #[serde(default)]
     pub pids: Vec<u32>,
+    /// Port of a launched node to stop.
+    #[schemars(description = "Stop the launched node listening on this port (e.g., 9944)")]
+    pub port: Option<u16>,
+    /// Registry name of a launched node to stop.
+    #[schemars(description = "Stop the launched node with this name, as listed by list_nodes")]
+    pub name: Option<String>,
 }
 
 /// Parameters for the clean_network tool.
@@ -31,6 +51,39 @@ pub struct CleanNetworkParams {
     pub keep_state: Option<bool>,
 }
 
+/// Parameters for the clean_contract tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct CleanContractParams {
+    /// Path to the contract directory.
+    #[schemars(description = "Path to the contract directory")]
+    pub path: String,
+    /// List what would be deleted without deleting it (default: false).
+    #[schemars(
+        description = "List what would be deleted and the space it takes, without deleting anything (default: false)"
+    )]
+    pub dry_run: Option<bool>,
+}
+
+/// Parameters for the clean_chain tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct CleanChainParams {
+    /// Path to the chain project directory.
+    #[schemars(description = "Path to the chain project directory")]
+    pub path: String,
+    /// Build profile whose output to delete (default: all of target).
+    #[schemars(
+        description = "Only delete the output of this build profile, e.g. 'debug' to keep release binaries (default: the whole target directory)"
+    )]
+    pub profile: Option<String>,
+    /// List what would be deleted without deleting it (default: false).
+    #[schemars(
+        description = "List what would be deleted and the space it takes, without deleting anything (default: false)"
+    )]
+    pub dry_run: Option<bool>,
+}
+
 impl CleanNetworkParams {
     fn validate(&self) -> Result<(), String> {
         let all = self.all.unwrap_or(false);
@@ -65,37 +118,193 @@ fn build_clean_network_args(params: &CleanNetworkParams) -> Vec<String> {
     args
 }
 
-/// Stop running local nodes using pop clean node --pid <pid...>
-pub fn clean_nodes(
-    executor: &PopExecutor,
-    params: CleanNodesParams,
+/// Stop processes `pids` using pop clean node --pid <pid...>
+pub async fn stop_pids<E: CommandExecutor>(
+    executor: &E,
+    pids: &[u32],
 ) -> PopMcpResult<CallToolResult> {
-    if params.pids.is_empty() {
+    if pids.is_empty() {
         return Ok(error_result("At least one pid is required"));
     }
 
     let mut args = vec!["clean".to_owned(), "node".to_owned(), "--pid".to_owned()];
-    args.extend(params.pids.iter().map(ToString::to_string));
+    args.extend(pids.iter().map(ToString::to_string));
     let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
 
-    match executor.execute(&arg_refs) {
+    match executor.execute(&arg_refs).await {
         Ok(output) => Ok(success_result(format!(
             "Nodes cleaned for pids: {}\n\n{}",
-            params
-                .pids
-                .iter()
-                .map(ToString::to_string)
-                .collect::<Vec<_>>()
-                .join(" "),
+            join_pids(pids),
             output
         ))),
         Err(e) => Ok(error_result(format!("Failed to clean nodes: {}", e))),
     }
 }
 
+fn join_pids(pids: &[u32]) -> String {
+    pids.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether nothing accepts connections on local `port` within `timeout`.
+async fn wait_for_port_closed(port: u16, timeout: Duration) -> bool {
+    let start = Instant::now();
+    loop {
+        if tokio::net::TcpStream::connect(("127.0.0.1", port))
+            .await
+            .is_err()
+        {
+            return true;
+        }
+        if start.elapsed() >= timeout {
+            return false;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+}
+
+/// Launched nodes targeted by `params`: the named node, the node on `port`, and the
+/// nodes owning any of `pids`.
+fn targeted_nodes(
+    registry: &NodeRegistry,
+    params: &CleanNodesParams,
+) -> PopMcpResult<Vec<RegisteredNode>> {
+    let nodes = registry.list();
+    let names = || {
+        let names: Vec<String> = nodes.iter().map(|node| node.name.clone()).collect();
+        if names.is_empty() {
+            "no nodes have been launched".to_owned()
+        } else {
+            format!("launched nodes: {}", names.join(", "))
+        }
+    };
+    let mut targeted = Vec::new();
+    if let Some(name) = params.name.as_deref().map(str::trim) {
+        let node = nodes.iter().find(|node| node.name == name).ok_or_else(|| {
+            PopMcpError::InvalidInput(format!("No node named '{}' ({})", name, names()))
+        })?;
+        targeted.push(node.clone());
+    }
+    if let Some(port) = params.port {
+        let node = nodes
+            .iter()
+            .find(|node| parsing::url_port(&node.url) == Some(port))
+            .ok_or_else(|| {
+                PopMcpError::InvalidInput(format!(
+                    "No launched node listens on port {} ({}); pass its pids instead",
+                    port,
+                    names()
+                ))
+            })?;
+        targeted.push(node.clone());
+    }
+    targeted.extend(
+        nodes
+            .iter()
+            .filter(|node| node.pids.iter().any(|pid| params.pids.contains(pid)))
+            .cloned(),
+    );
+    targeted.sort_by(|a, b| a.name.cmp(&b.name));
+    targeted.dedup_by(|a, b| a.name == b.name);
+    Ok(targeted)
+}
+
+/// Stop the nodes selected by pid, port or registry name, check that their ports
+/// closed, and forget them.
+pub async fn clean_nodes<E: CommandExecutor>(
+    executor: &E,
+    registry: &NodeRegistry,
+    params: CleanNodesParams,
+) -> PopMcpResult<CallToolResult> {
+    if params.pids.is_empty() && params.port.is_none() && params.name.is_none() {
+        return Err(PopMcpError::InvalidInput(
+            "Give the 'pids', 'port' or 'name' of the nodes to stop".to_owned(),
+        ));
+    }
+    let nodes = targeted_nodes(registry, &params)?;
+    let mut pids = params.pids.clone();
+    for pid in nodes.iter().flat_map(|node| &node.pids) {
+        if !pids.contains(pid) {
+            pids.push(*pid);
+        }
+    }
+    if pids.is_empty() {
+        return Ok(error_result(format!(
+            "Pids of {} are unknown; pass them explicitly",
+            nodes
+                .iter()
+                .map(|node| node.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+
+    let result = stop_pids(executor, &pids).await?;
+    if result.is_error == Some(true) {
+        return Ok(result);
+    }
+    let output = crate::tools::common::extract_text(&result).unwrap_or_default();
+
+    let mut lines = Vec::new();
+    for node in &nodes {
+        registry.remove(&node.name);
+        lines.push(format!(
+            "Stopped {} ({}, pids {})",
+            node.name,
+            node.url,
+            join_pids(&node.pids)
+        ));
+    }
+    let others: Vec<u32> = pids
+        .iter()
+        .copied()
+        .filter(|pid| !nodes.iter().any(|node| node.pids.contains(pid)))
+        .collect();
+    if !others.is_empty() {
+        lines.push(format!("Stopped pids {}", join_pids(&others)));
+    }
+
+    let mut ports: Vec<u16> = nodes
+        .iter()
+        .filter_map(|node| parsing::url_port(&node.url))
+        .chain(params.port)
+        .collect();
+    ports.sort_unstable();
+    ports.dedup();
+    let mut open = Vec::new();
+    for port in ports {
+        if wait_for_port_closed(port, PORT_CLOSE_TIMEOUT).await {
+            lines.push(format!("Port {} is closed", port));
+        } else {
+            open.push(port);
+        }
+    }
+    if !open.is_empty() {
+        let open = open
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Ok(error_result(format!(
+            "{}\nPort(s) {} still accept connections after {}s: another process may be listening.\n\n{}",
+            lines.join("\n"),
+            open,
+            PORT_CLOSE_TIMEOUT.as_secs(),
+            output
+        )));
+    }
+    Ok(success_result(format!(
+        "{}\n\n{}",
+        lines.join("\n"),
+        output
+    )))
+}
+
 /// Stop running networks using pop clean network.
-pub fn clean_network(
-    executor: &PopExecutor,
+pub async fn clean_network<E: CommandExecutor>(
+    executor: &E,
     params: CleanNetworkParams,
 ) -> PopMcpResult<CallToolResult> {
     if let Err(message) = params.validate() {
@@ -105,15 +314,137 @@ pub fn clean_network(
     let args = build_clean_network_args(&params);
     let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
 
-    match executor.execute(&arg_refs) {
+    match executor.execute(&arg_refs).await {
         Ok(output) => Ok(success_result(output)),
         Err(e) => Ok(error_result(format!("Failed to clean network: {}", e))),
     }
 }
 
+/// Number of files and bytes under a directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Usage {
+    files: u64,
+    bytes: u64,
+}
+
+/// Files and bytes under `dir`, without following symlinks.
+fn usage(dir: &Path) -> Usage {
+    let mut total = Usage::default();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let Ok(metadata) = entry.path().symlink_metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(entry.path());
+            } else {
+                total.files += 1;
+                total.bytes += metadata.len();
+            }
+        }
+    }
+    total
+}
+
+/// `bytes` in the largest binary unit that keeps the value at least 1.
+fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// `target` under the Rust project at `path`, refusing paths that are not one.
+fn target_dir(path: &str) -> PopMcpResult<PathBuf> {
+    let project = Path::new(path);
+    if path.trim().is_empty() || !project.join("Cargo.toml").is_file() {
+        return Err(PopMcpError::InvalidInput(format!(
+            "{} is not a Rust project (no Cargo.toml)",
+            path
+        )));
+    }
+    Ok(project.join("target"))
+}
+
+/// Delete `dir` (or, with `dry_run`, report what deleting it would free).
+fn clean_dir(dir: &Path, dry_run: bool) -> CallToolResult {
+    if !dir.is_dir() {
+        return success_result(format!(
+            "Nothing to clean: {} does not exist",
+            dir.display()
+        ));
+    }
+    let usage = usage(dir);
+    let listing = format!(
+        "{} ({} files, {})",
+        dir.display(),
+        usage.files,
+        human_bytes(usage.bytes)
+    );
+    if dry_run {
+        return success_result(format!(
+            "Dry run: nothing was deleted. Would delete {}, freeing {}.",
+            listing,
+            human_bytes(usage.bytes)
+        ));
+    }
+    match std::fs::remove_dir_all(dir) {
+        Ok(()) => success_result(format!(
+            "Deleted {}, freeing {}.",
+            listing,
+            human_bytes(usage.bytes)
+        )),
+        Err(e) => error_result(format!("Failed to delete {}: {}", dir.display(), e)),
+    }
+}
+
+/// Delete the build output of the contract at `params.path`.
+pub fn clean_contract(params: CleanContractParams) -> PopMcpResult<CallToolResult> {
+    let target = target_dir(&params.path)?;
+    Ok(clean_dir(&target, params.dry_run.unwrap_or(false)))
+}
+
+/// Delete the build output of the chain project at `params.path`, or of one profile.
+pub fn clean_chain(params: CleanChainParams) -> PopMcpResult<CallToolResult> {
+    let target = target_dir(&params.path)?;
+    let dir = match params.profile.as_deref().map(str::trim) {
+        None => target,
+        Some("dev") => target.join("debug"),
+        Some(profile)
+            if !profile.is_empty()
+                && profile
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+        {
+            target.join(profile)
+        }
+        Some(profile) => {
+            return Err(PopMcpError::InvalidInput(format!(
+                "Invalid profile '{}'",
+                profile
+            )))
+        }
+    };
+    Ok(clean_dir(&dir, params.dry_run.unwrap_or(false)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::outputs::NodeInfo;
+    use crate::test_utils::MockExecutor;
+    use crate::tools::common::content_text;
 
     #[test]
     fn clean_network_rejects_missing_path_and_all() {
@@ -177,4 +508,149 @@ mod tests {
             vec!["clean".to_owned(), "network".to_owned(), "--all".to_owned()]
         );
     }
+
+    fn registry() -> NodeRegistry {
+        let registry = NodeRegistry::new();
+        for (port, pids) in [(19944, vec![11, 12]), (19955, vec![21])] {
+            registry.register(
+                &NodeInfo {
+                    url: format!("ws://localhost:{}", port),
+                    pids,
+                    logs: Vec::new(),
+                    port: Some(port),
+                    eth_rpc_port: None,
+                },
+                None,
+            );
+        }
+        registry
+    }
+
+    fn params() -> CleanNodesParams {
+        CleanNodesParams {
+            pids: Vec::new(),
+            port: None,
+            name: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn clean_nodes_stops_only_the_node_on_a_port() -> PopMcpResult<()> {
+        let registry = registry();
+        let executor = MockExecutor::new().on(&["clean", "node"], "Nodes terminated.");
+        let result = clean_nodes(
+            &executor,
+            &registry,
+            CleanNodesParams {
+                port: Some(19955),
+                ..params()
+            },
+        )
+        .await?;
+        assert_eq!(
+            executor.calls()[0].args,
+            vec!["clean", "node", "--pid", "21"]
+        );
+        let text = content_text(&result);
+        assert!(text.starts_with("Stopped node-19955 (ws://localhost:19955, pids 21)"));
+        assert!(text.contains("Port 19955 is closed"));
+        let names: Vec<String> = registry.list().into_iter().map(|node| node.name).collect();
+        assert_eq!(names, vec!["node-19944"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn clean_nodes_resolves_names_and_reports_unknown_pids() -> PopMcpResult<()> {
+        let registry = registry();
+        let executor = MockExecutor::new().on(&["clean", "node"], "Nodes terminated.");
+        let result = clean_nodes(
+            &executor,
+            &registry,
+            CleanNodesParams {
+                pids: vec![99],
+                name: Some("node-19944".to_owned()),
+                ..params()
+            },
+        )
+        .await?;
+        assert_eq!(
+            executor.calls()[0].args,
+            vec!["clean", "node", "--pid", "99", "11", "12"]
+        );
+        assert!(content_text(&result).contains("Stopped pids 99"));
+
+        let unknown = clean_nodes(
+            &executor,
+            &registry,
+            CleanNodesParams {
+                name: Some("missing".to_owned()),
+                ..params()
+            },
+        )
+        .await;
+        assert!(matches!(unknown, Err(PopMcpError::InvalidInput(_))));
+        let nothing = clean_nodes(&executor, &registry, params()).await;
+        assert!(matches!(nothing, Err(PopMcpError::InvalidInput(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn clean_contract_reports_the_space_freed() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().display().to_string();
+        let params = |dry_run| CleanContractParams {
+            path: path.clone(),
+            dry_run: Some(dry_run),
+        };
+        assert!(matches!(
+            clean_contract(params(false)),
+            Err(PopMcpError::InvalidInput(_))
+        ));
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"flipper\"\n",
+        )?;
+        let nothing = content_text(&clean_contract(params(false))?);
+        assert!(nothing.starts_with("Nothing to clean"));
+
+        std::fs::create_dir_all(dir.path().join("target/ink"))?;
+        std::fs::write(dir.path().join("target/ink/flipper.polkavm"), vec![0; 2048])?;
+        std::fs::write(dir.path().join("target/CACHEDIR.TAG"), "tag")?;
+        let dry = content_text(&clean_contract(params(true))?);
+        assert!(
+            dry.ends_with("(2 files, 2.0 KiB), freeing 2.0 KiB."),
+            "{}",
+            dry
+        );
+        assert!(dir.path().join("target").exists());
+        let done = content_text(&clean_contract(params(false))?);
+        assert!(done.starts_with("Deleted "));
+        assert!(!dir.path().join("target").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn clean_chain_can_keep_other_profiles() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("Cargo.toml"), "[workspace]\n")?;
+        for profile in ["debug", "release"] {
+            std::fs::create_dir_all(dir.path().join("target").join(profile))?;
+            std::fs::write(dir.path().join("target").join(profile).join("node"), "bin")?;
+        }
+        let params = CleanChainParams {
+            path: dir.path().display().to_string(),
+            profile: Some("dev".to_owned()),
+            dry_run: None,
+        };
+        clean_chain(params.clone())?;
+        assert!(!dir.path().join("target/debug").exists());
+        assert!(dir.path().join("target/release/node").exists());
+        let invalid = clean_chain(CleanChainParams {
+            profile: Some("../..".to_owned()),
+            ..params
+        });
+        assert!(matches!(invalid, Err(PopMcpError::InvalidInput(_))));
+        assert_eq!(human_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+        Ok(())
+    }
 }
@@ -1,6 +1,101 @@
 //! Common helper functions for tool implementations
 
 use rmcp::model::{CallToolResult, Content, RawContent};
+use serde::Serialize;
+
+use crate::redact::{Secrets, MASK};
+
+/// ASCII replacements for status symbols Pop CLI prints.
+const STATUS_SYMBOLS: &[(char, &str)] = &[
+    ('✅', "[OK]"),
+    ('✔', "[OK]"),
+    ('✓', "[OK]"),
+    ('❌', "[ERROR]"),
+    ('✖', "[ERROR]"),
+    ('✗', "[ERROR]"),
+    ('⚠', "[WARN]"),
+    ('ℹ', "[INFO]"),
+];
+
+/// Replace status symbols with ASCII prefixes and drop emoji and box-drawing
+/// characters. Other non-ASCII text (names, localized messages) is kept.
+pub fn to_ascii(text: &str) -> String {
+    text.split('\n')
+        .map(|line| {
+            let mut out = String::with_capacity(line.len());
+            let mut decorated = false;
+            for c in line.chars() {
+                if let Some((_, ascii)) = STATUS_SYMBOLS.iter().find(|(symbol, _)| *symbol == c) {
+                    out.push_str(ascii);
+                } else if is_decoration(c) {
+                    decorated = true;
+                } else {
+                    out.push(c);
+                }
+            }
+            // Drop the gap left behind by gutters such as `│  url: ...`.
+            if decorated {
+                out.trim().to_owned()
+            } else {
+                out
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Emoji, pictographs, box-drawing and geometric shapes used as decoration.
+fn is_decoration(c: char) -> bool {
+    matches!(
+        c,
+        '\u{2500}'..='\u{25FF}'
+            | '\u{2600}'..='\u{27BF}'
+            | '\u{2B00}'..='\u{2BFF}'
+            | '\u{1F000}'..='\u{1FAFF}'
+            | '\u{FE0F}'
+            | '\u{200D}'
+    )
+}
+
+/// How a server emits result texts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputStyle {
+    /// Strip emoji and box-drawing characters (see [`to_ascii`]).
+    pub ascii: bool,
+    /// Maximum size of a result text in bytes; 0 disables truncation.
+    pub limit: usize,
+}
+
+impl Default for OutputStyle {
+    fn default() -> Self {
+        Self {
+            ascii: false,
+            limit: crate::spill::DEFAULT_OUTPUT_LIMIT,
+        }
+    }
+}
+
+impl OutputStyle {
+    /// `text` as emitted, cut to [`Self::limit`] and made ASCII when asked to.
+    pub fn render(&self, text: String) -> String {
+        let text = crate::spill::truncate(text, self.limit);
+        if self.ascii {
+            to_ascii(&text)
+        } else {
+            text
+        }
+    }
+
+    /// `result` with every text content rendered.
+    pub fn apply(&self, mut result: CallToolResult) -> CallToolResult {
+        for content in &mut result.content {
+            if let RawContent::Text(text) = &mut content.raw {
+                text.text = self.render(std::mem::take(&mut text.text));
+            }
+        }
+        result
+    }
+}
 
 /// Deserialize an `Option<String>` that also accepts JSON booleans.
 ///
@@ -23,16 +118,176 @@ where
     }
 }
 
+/// Validate an optional `cwd` tool parameter, which must name an existing directory.
+pub(crate) fn resolve_cwd(cwd: Option<&str>) -> Result<Option<std::path::PathBuf>, String> {
+    let Some(cwd) = cwd else {
+        return Ok(None);
+    };
+    let path = std::path::PathBuf::from(cwd);
+    if !path.is_dir() {
+        return Err(format!("'cwd' is not an existing directory: {}", cwd));
+    }
+    Ok(Some(path))
+}
+
+/// Load contract arguments from a JSON or TOML file.
+///
+/// A relative `project` resolves against `cwd` (the directory commands run in, when
+/// not the server's) and relative files against `project`. The file must lie inside
+/// the project: absolute paths elsewhere and `..` escapes (symlinks included) are
+/// refused. JSON files hold either an array or an object with an `args` array; TOML
+/// files need a top-level `args` array. Strings are passed through as-is, everything
+/// else as compact JSON (e.g. `[1,2,3]`).
+pub(crate) fn load_args_file(
+    cwd: Option<&std::path::Path>,
+    project: &str,
+    file: &str,
+) -> Result<Vec<String>, String> {
+    let project = match cwd {
+        Some(cwd) => cwd.join(project),
+        None => std::path::PathBuf::from(project),
+    };
+    let project = std::fs::canonicalize(&project).map_err(|e| {
+        format!(
+            "Failed to resolve project directory {}: {}",
+            project.display(),
+            e
+        )
+    })?;
+    let path = std::fs::canonicalize(project.join(file))
+        .map_err(|e| format!("Failed to read args file {}: {}", file, e))?;
+    if !path.starts_with(&project) {
+        return Err(format!(
+            "Args file {} is outside the project directory {}",
+            file,
+            project.display()
+        ));
+    }
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read args file {}: {}", path.display(), e))?;
+
+    let is_toml = path.extension().is_some_and(|ext| ext == "toml");
+    let value: serde_json::Value = if is_toml {
+        toml::from_str(&contents)
+            .map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e))?
+    } else {
+        serde_json::from_str(&contents)
+            .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?
+    };
+
+    let items = match value {
+        serde_json::Value::Array(items) => items,
+        serde_json::Value::Object(mut map) => match map.remove("args") {
+            Some(serde_json::Value::Array(items)) => items,
+            _ => {
+                return Err(format!(
+                    "Args file {} must contain an 'args' array",
+                    path.display()
+                ))
+            }
+        },
+        _ => {
+            return Err(format!(
+                "Args file {} must contain an array of arguments",
+                path.display()
+            ))
+        }
+    };
+
+    Ok(items
+        .into_iter()
+        .map(|item| match item {
+            serde_json::Value::String(s) => s,
+            other => other.to_string(),
+        })
+        .collect())
+}
+
 /// Create a success result with the given text
 pub(crate) fn success_result(text: impl Into<String>) -> CallToolResult {
     CallToolResult::success(vec![Content::text(text.into())])
 }
 
+/// Create a success result with one content item per text
+pub(crate) fn success_texts(texts: Vec<String>) -> CallToolResult {
+    CallToolResult::success(texts.into_iter().map(Content::text).collect())
+}
+
+/// Result of a `dry_run` call: the `pop` command that would run, with secrets masked.
+pub(crate) fn dry_run_result(args: &[String]) -> CallToolResult {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let argv = Secrets::for_args(&args).mask_argv(&args);
+    // The mask is a placeholder for the signer, not a value to paste, so it stays bare.
+    let command: Vec<String> = argv
+        .iter()
+        .map(|arg| match arg.as_str() {
+            MASK => MASK.to_owned(),
+            arg => shell_quote(arg),
+        })
+        .collect();
+    success_result(format!(
+        "Dry run: nothing was executed. The command would be:\n\npop {}\n\nCall the tool again without dry_run to run it. Unreachable public endpoints may still be swapped for another provider.",
+        command.join(" ")
+    ))
+}
+
+/// `arg` quoted for a POSIX shell when it contains anything but safe characters.
+fn shell_quote(arg: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./:=,@%+".contains(c);
+    if !arg.is_empty() && arg.chars().all(safe) {
+        arg.to_owned()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
 /// Create an error result with the given text
 pub(crate) fn error_result(text: impl Into<String>) -> CallToolResult {
     CallToolResult::error(vec![Content::text(text.into())])
 }
 
+/// Compact `key: value` block for clients that render results as cards.
+///
+/// Fields without a value are skipped and multi-line values keep their first
+/// non-empty line, so the block always has one line per field.
+pub(crate) fn summary_block(fields: &[(&str, Option<String>)]) -> String {
+    fields
+        .iter()
+        .filter_map(|(key, value)| {
+            let value = value
+                .as_deref()?
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())?;
+            Some(format!("{}: {}", key, value))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Put `summary` before the existing content of `result`, keeping the full output second.
+pub(crate) fn with_summary(mut result: CallToolResult, summary: String) -> CallToolResult {
+    result.content.insert(0, Content::text(summary));
+    result
+}
+
+/// Attach `value` as the machine-readable `structured_content` of `result`.
+pub(crate) fn with_structured(
+    mut result: CallToolResult,
+    value: &impl Serialize,
+) -> CallToolResult {
+    result.structured_content = serde_json::to_value(value).ok();
+    result
+}
+
+/// Append `note` (e.g. which RPC endpoint was used) after the content of `result`.
+pub(crate) fn with_note(mut result: CallToolResult, note: Option<String>) -> CallToolResult {
+    if let Some(note) = note {
+        result.content.push(Content::text(note));
+    }
+    result
+}
+
 /// Extract text content from a CallToolResult
 pub fn extract_text(result: &CallToolResult) -> Option<String> {
     result.content.first().and_then(|c| match &c.raw {
@@ -41,8 +296,190 @@ pub fn extract_text(result: &CallToolResult) -> Option<String> {
     })
 }
 
+/// Extract every text content item from a CallToolResult
+pub fn extract_texts(result: &CallToolResult) -> Vec<String> {
+    result
+        .content
+        .iter()
+        .filter_map(|c| match &c.raw {
+            RawContent::Text(t) => Some(t.text.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
 /// Extract text content from a CallToolResult, returning empty string if missing.
 #[cfg(test)]
 pub(crate) fn content_text(result: &CallToolResult) -> String {
     extract_text(result).unwrap_or_default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn output_style_renders_every_text() {
+        let style = OutputStyle {
+            ascii: true,
+            limit: 64,
+        };
+        let result = style.apply(success_texts(vec!["Built ✅".to_owned(), "x".repeat(100)]));
+        let texts = extract_texts(&result);
+        assert_eq!(texts[0], "Built [OK]");
+        assert!(texts[1].contains("bytes omitted"));
+        assert_eq!(
+            OutputStyle::default().render("Built ✅".to_owned()),
+            "Built ✅"
+        );
+    }
+
+    #[test]
+    fn to_ascii_strips_decoration_and_keeps_text() {
+        let output = "┌   Pop CLI : Deploy\n│\n◇  Contract deployed:\n│  address: \"0x58\"\n\
+                      └  🚀 Deployment complete ✅\n  {\n    \"name\": \"Zoë\"\n  }";
+        assert_eq!(
+            to_ascii(output),
+            "Pop CLI : Deploy\n\nContract deployed:\naddress: \"0x58\"\n\
+             Deployment complete [OK]\n  {\n    \"name\": \"Zoë\"\n  }"
+        );
+        assert_eq!(to_ascii("⚠️ low balance"), "[WARN] low balance");
+    }
+
+    #[test]
+    fn summary_block_skips_missing_fields_and_keeps_first_line() {
+        let block = summary_block(&[
+            ("status", Some("failed".to_owned())),
+            ("address", None),
+            (
+                "error",
+                Some("\nInsufficient balance\nat block 7".to_owned()),
+            ),
+        ]);
+        assert_eq!(block, "status: failed\nerror: Insufficient balance");
+    }
+
+    #[test]
+    fn with_summary_puts_block_first() {
+        let result = with_summary(success_result("full output"), "status: ok".to_owned());
+        assert_eq!(extract_texts(&result), vec!["status: ok", "full output"]);
+    }
+
+    #[test]
+    fn dry_run_result_masks_suri_and_quotes_args() {
+        let args: Vec<String> = [
+            "call",
+            "chain",
+            "--args",
+            "Hello world",
+            "--suri",
+            "//Alice",
+        ]
+        .map(str::to_owned)
+        .to_vec();
+        let text = content_text(&dry_run_result(&args));
+        assert!(text.contains("pop call chain --args 'Hello world' --suri [REDACTED]"));
+        assert!(!text.contains("//Alice"));
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+
+    #[test]
+    #[allow(clippy::panic)]
+    fn load_args_file_reads_json_array_relative_to_project() {
+        let dir = match tempdir() {
+            Ok(dir) => dir,
+            Err(err) => panic!("tempdir failed: {err}"),
+        };
+        if let Err(err) = std::fs::write(
+            dir.path().join("args.json"),
+            r#"["0x1234", 42, true, [1, 2]]"#,
+        ) {
+            panic!("write failed: {err}");
+        }
+        let args = load_args_file(None, &dir.path().display().to_string(), "args.json");
+        assert_eq!(
+            args,
+            Ok(vec![
+                "0x1234".to_owned(),
+                "42".to_owned(),
+                "true".to_owned(),
+                "[1,2]".to_owned()
+            ])
+        );
+    }
+
+    #[test]
+    #[allow(clippy::panic)]
+    fn load_args_file_resolves_the_project_against_the_working_dir() {
+        let dir = match tempdir() {
+            Ok(dir) => dir,
+            Err(err) => panic!("tempdir failed: {err}"),
+        };
+        if let Err(err) = std::fs::create_dir(dir.path().join("flipper")) {
+            panic!("create_dir failed: {err}");
+        }
+        if let Err(err) = std::fs::write(dir.path().join("flipper/args.json"), r#"["true"]"#) {
+            panic!("write failed: {err}");
+        }
+        let args = load_args_file(Some(dir.path()), "./flipper", "args.json");
+        assert_eq!(args, Ok(vec!["true".to_owned()]));
+    }
+
+    #[test]
+    #[allow(clippy::panic)]
+    fn load_args_file_rejects_paths_outside_the_project() {
+        let dir = match tempdir() {
+            Ok(dir) => dir,
+            Err(err) => panic!("tempdir failed: {err}"),
+        };
+        let project = dir.path().join("flipper");
+        if let Err(err) = std::fs::create_dir(&project) {
+            panic!("create_dir failed: {err}");
+        }
+        let outside = dir.path().join("args.json");
+        if let Err(err) = std::fs::write(&outside, r#"["secret"]"#) {
+            panic!("write failed: {err}");
+        }
+        let project = project.display().to_string();
+        for file in [outside.display().to_string(), "../args.json".to_owned()] {
+            let Err(err) = load_args_file(None, &project, &file) else {
+                panic!("{file} was read from outside the project");
+            };
+            assert!(err.contains("outside the project directory"));
+        }
+    }
+
+    #[test]
+    #[allow(clippy::panic)]
+    fn load_args_file_reads_toml_args_table() {
+        let dir = match tempdir() {
+            Ok(dir) => dir,
+            Err(err) => panic!("tempdir failed: {err}"),
+        };
+        let file = dir.path().join("args.toml");
+        if let Err(err) = std::fs::write(&file, "args = [\"alice\", 1000]\n") {
+            panic!("write failed: {err}");
+        }
+        let args = load_args_file(
+            None,
+            &dir.path().display().to_string(),
+            &file.display().to_string(),
+        );
+        assert_eq!(args, Ok(vec!["alice".to_owned(), "1000".to_owned()]));
+    }
+
+    #[test]
+    #[allow(clippy::panic)]
+    fn load_args_file_rejects_non_array() {
+        let dir = match tempdir() {
+            Ok(dir) => dir,
+            Err(err) => panic!("tempdir failed: {err}"),
+        };
+        if let Err(err) = std::fs::write(dir.path().join("args.json"), r#"{"value": 1}"#) {
+            panic!("write failed: {err}");
+        }
+        assert!(load_args_file(None, &dir.path().display().to_string(), "args.json").is_err());
+        assert!(load_args_file(None, &dir.path().display().to_string(), "missing.json").is_err());
+    }
+}
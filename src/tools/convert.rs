@@ -5,7 +5,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::error::PopMcpResult;
-use crate::executor::PopExecutor;
+use crate::executor::CommandExecutor;
 
 use super::common::{error_result, success_result};
 
@@ -36,8 +36,8 @@ fn build_convert_address_args(params: &ConvertAddressParams) -> [&str; 3] {
 }
 
 /// Execute convert_address tool
-pub fn convert_address(
-    executor: &PopExecutor,
+pub async fn convert_address<E: CommandExecutor>(
+    executor: &E,
     params: ConvertAddressParams,
 ) -> PopMcpResult<CallToolResult> {
     // Validate parameters
@@ -47,7 +47,7 @@ pub fn convert_address(
 
     let args = build_convert_address_args(&params);
 
-    match executor.execute(&args) {
+    match executor.execute(&args).await {
         Ok(output) => Ok(success_result(output)),
         Err(e) => Ok(error_result(format!("Address conversion failed:\n\n{}", e))),
     }
@@ -0,0 +1,169 @@
+//! Deployment registry tools (list and forget recorded deployments)
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::deployments::{same_url, Deployment, Registry};
+use crate::error::PopMcpResult;
+use crate::tools::common::{error_result, success_result};
+
+/// Parameters for the list_deployments tool.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct ListDeploymentsParams {
+    /// Only list deployments on this network.
+    #[schemars(description = "Only list deployments on this network (WebSocket URL)")]
+    pub url: Option<String>,
+    /// Only list deployments built from paths containing this text.
+    #[schemars(
+        description = "Only list deployments whose contract path contains this text (e.g., 'flipper')"
+    )]
+    pub path: Option<String>,
+}
+
+/// Parameters for the forget_deployment tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct ForgetDeploymentParams {
+    /// Address of the deployment to forget.
+    #[schemars(description = "Contract address to remove from the registry")]
+    pub address: String,
+    /// Only forget the deployment on this network.
+    #[schemars(
+        description = "Only forget the deployment on this network (default: on every network)"
+    )]
+    pub url: Option<String>,
+}
+
+/// One line describing `deployment`.
+fn describe(deployment: &Deployment) -> String {
+    let code_hash = deployment
+        .code_hash
+        .as_ref()
+        .map(|hash| format!(", code hash {}", hash))
+        .unwrap_or_default();
+    format!(
+        "{} on {}: {} (constructor {}{}, deployed at {})",
+        deployment.address,
+        deployment.url,
+        deployment.path,
+        deployment.constructor,
+        code_hash,
+        deployment.deployed_at
+    )
+}
+
+/// Execute list_deployments tool
+pub async fn list_deployments(
+    registry: &Registry,
+    params: ListDeploymentsParams,
+) -> PopMcpResult<CallToolResult> {
+    let deployments: Vec<Deployment> = registry
+        .load()?
+        .into_iter()
+        .filter(|d| {
+            params
+                .url
+                .as_deref()
+                .is_none_or(|url| same_url(&d.url, url))
+        })
+        .filter(|d| {
+            params
+                .path
+                .as_deref()
+                .is_none_or(|path| d.path.contains(path))
+        })
+        .collect();
+    if deployments.is_empty() {
+        return Ok(success_result(
+            "No deployments recorded. deploy_contract with execute=true records each one.",
+        ));
+    }
+    let lines: Vec<String> = deployments.iter().map(describe).collect();
+    Ok(success_result(format!(
+        "Deployments, oldest first (times in Unix seconds):\n{}",
+        lines.join("\n")
+    )))
+}
+
+/// Execute forget_deployment tool
+pub async fn forget_deployment(
+    registry: &Registry,
+    params: ForgetDeploymentParams,
+) -> PopMcpResult<CallToolResult> {
+    let address = params.address.trim();
+    let forgotten = registry.forget(address, params.url.as_deref())?;
+    if forgotten.is_empty() {
+        return Ok(error_result(format!(
+            "No deployment recorded at {}",
+            address
+        )));
+    }
+    let lines: Vec<String> = forgotten.iter().map(describe).collect();
+    Ok(success_result(format!(
+        "Forgot {} deployment(s):\n{}",
+        forgotten.len(),
+        lines.join("\n")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::common::content_text;
+
+    #[tokio::test]
+    async fn deployments_are_listed_filtered_and_forgotten() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let registry = Registry::new(dir.path().join("deployments.json"));
+        for (path, url) in [
+            ("./flipper", "ws://localhost:9944"),
+            ("./erc20", "ws://localhost:9955"),
+        ] {
+            registry.record(Deployment {
+                path: path.to_owned(),
+                url: url.to_owned(),
+                address: format!("0x{}", path.trim_start_matches("./")),
+                code_hash: Some("0x4f1d".to_owned()),
+                constructor: "new".to_owned(),
+                deployed_at: 1_700_000_000,
+            })?;
+        }
+
+        let listed = list_deployments(
+            &registry,
+            ListDeploymentsParams {
+                url: Some("ws://localhost:9944/".to_owned()),
+                path: None,
+            },
+        )
+        .await?;
+        assert!(content_text(&listed).ends_with(
+            "0xflipper on ws://localhost:9944: ./flipper (constructor new, code hash 0x4f1d, deployed at 1700000000)"
+        ));
+        assert!(!content_text(&listed).contains("erc20"));
+
+        let forgotten = forget_deployment(
+            &registry,
+            ForgetDeploymentParams {
+                address: "0xFLIPPER".to_owned(),
+                url: None,
+            },
+        )
+        .await?;
+        assert!(content_text(&forgotten).starts_with("Forgot 1 deployment(s):"));
+        let listed = list_deployments(&registry, ListDeploymentsParams::default()).await?;
+        assert!(!content_text(&listed).contains("flipper"));
+        let missing = forget_deployment(
+            &registry,
+            ForgetDeploymentParams {
+                address: "0xflipper".to_owned(),
+                url: None,
+            },
+        )
+        .await?;
+        assert_eq!(missing.is_error, Some(true));
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+//! Deprecated tool names kept as thin wrappers around their replacements.
+//!
+//! When a tool is renamed or its parameters change, the old name stays
+//! registered for at least one release. The wrapper maps its params onto the
+//! new tool, calls it, and passes the result through [`deprecated`], which adds
+//! a warning line to the text content and a `deprecation` object to `_meta` so
+//! agents and configs built against the old surface keep working while the
+//! rename is visible.
+
+use rmcp::model::{CallToolResult, Content, Meta};
+use serde_json::json;
+
+/// A tool name that is still served but scheduled for removal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deprecation {
+    /// Old tool name.
+    pub tool: &'static str,
+    /// Tool that replaces it.
+    pub replacement: &'static str,
+    /// Release that deprecated the old name.
+    pub since: &'static str,
+    /// Earliest release that may remove the old name.
+    pub remove_in: &'static str,
+}
+
+impl Deprecation {
+    /// Human-readable warning shown alongside the tool output.
+    pub fn message(&self) -> String {
+        format!(
+            "Deprecated: '{}' is deprecated since {} and will be removed in {}; use '{}' instead.",
+            self.tool, self.since, self.remove_in, self.replacement
+        )
+    }
+}
+
+/// Every deprecated tool the server still registers.
+pub const DEPRECATIONS: &[Deprecation] = &[Deprecation {
+    tool: "list_templates",
+    replacement: "list_contract_templates",
+    since: "0.2.0",
+    remove_in: "0.3.0",
+}];
+
+/// Look up the deprecation entry for a tool name.
+pub fn deprecation(tool: &str) -> Option<&'static Deprecation> {
+    DEPRECATIONS.iter().find(|entry| entry.tool == tool)
+}
+
+/// Mark a result produced on behalf of a deprecated tool.
+///
+/// Unknown tool names pass the result through unchanged.
+pub fn deprecated(mut result: CallToolResult, tool: &str) -> CallToolResult {
+    let Some(entry) = deprecation(tool) else {
+        return result;
+    };
+    let message = entry.message();
+    result.content.push(Content::text(format!("⚠️ {message}")));
+    result.meta.get_or_insert_with(Meta::new).insert(
+        "deprecation".to_owned(),
+        json!({
+            "tool": entry.tool,
+            "replacement": entry.replacement,
+            "since": entry.since,
+            "remove_in": entry.remove_in,
+            "message": message,
+        }),
+    );
+    result
+}
+
+#[cfg(test)]
+#[allow(clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::tools::common::success_result;
+
+    #[test]
+    fn deprecated_appends_warning_and_meta() {
+        let result = deprecated(success_result("templates"), "list_templates");
+        assert_eq!(result.content.len(), 2);
+        let Some(warning) = result.content[1].as_text() else {
+            panic!("Expected text content");
+        };
+        assert!(warning
+            .text
+            .contains("use 'list_contract_templates' instead"));
+        let Some(meta) = result.meta else {
+            panic!("Expected _meta");
+        };
+        assert_eq!(
+            meta.get("deprecation")
+                .and_then(|d| d.get("replacement"))
+                .and_then(|r| r.as_str()),
+            Some("list_contract_templates")
+        );
+    }
+
+    #[test]
+    fn unknown_tools_pass_through() {
+        let result = deprecated(success_result("ok"), "build_contract");
+        assert_eq!(result.content.len(), 1);
+        assert!(result.meta.is_none());
+    }
+
+    #[test]
+    fn registry_has_no_self_replacements() {
+        for entry in DEPRECATIONS {
+            assert_ne!(entry.tool, entry.replacement);
+            assert!(deprecation(entry.replacement).is_none());
+        }
+    }
+}
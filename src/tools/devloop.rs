@@ -0,0 +1,252 @@
+//! Contract dev loops (rebuild and redeploy on change)
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::devloop::{DevLoopConfig, DevLoopEvent, DevLoops};
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::tools::common::{error_result, success_result};
+
+/// Seconds between checks for changes by default.
+const DEFAULT_INTERVAL_SECS: u64 = 2;
+
+/// Parameters for the start_dev_loop tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct StartDevLoopParams {
+    /// Path to the contract directory.
+    #[schemars(description = "Path to the contract directory to watch")]
+    pub path: String,
+    /// Alias the latest address is recorded under.
+    #[schemars(
+        description = "Alias the latest address is recorded under in pop://contracts (default: the directory name)"
+    )]
+    pub alias: Option<String>,
+    /// WebSocket URL of the node to deploy to.
+    #[schemars(
+        description = "WebSocket URL of the node to deploy to (default: the node launched with up_ink_node)"
+    )]
+    pub url: Option<String>,
+    /// Constructor to instantiate with.
+    #[schemars(description = "Constructor to instantiate with (default: new)")]
+    pub constructor: Option<String>,
+    /// Constructor arguments, space-separated.
+    #[schemars(description = "Constructor arguments as space-separated values")]
+    pub args: Option<String>,
+    /// Seconds between checks for changes.
+    #[schemars(description = "Seconds between checks for source changes (default 2)")]
+    pub interval_secs: Option<u64>,
+}
+
+/// Parameters for the stop_dev_loop tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct StopDevLoopParams {
+    /// Alias of the loop.
+    #[schemars(description = "Alias of the dev loop, as listed by list_dev_loops")]
+    pub alias: String,
+}
+
+/// Parameters for the list_dev_loops tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+#[allow(clippy::empty_structs_with_brackets)]
+pub struct ListDevLoopsParams {}
+
+/// One line describing `event`.
+fn describe_event(event: &DevLoopEvent) -> String {
+    match event {
+        DevLoopEvent::Deployed { address, .. } => format!("deployed at {}", address),
+        DevLoopEvent::Failed { stage, error, .. } => {
+            let last = error.lines().rev().find(|line| !line.trim().is_empty());
+            format!("{} failed: {}", stage, last.unwrap_or_default().trim())
+        }
+    }
+}
+
+/// Execute start_dev_loop tool
+///
+/// `notify` receives the outcome of every rebuild, including the first one, which
+/// starts right away.
+pub async fn start_dev_loop<E, N>(
+    executor: &E,
+    loops: &DevLoops,
+    params: StartDevLoopParams,
+    stored_url: Option<&str>,
+    notify: N,
+) -> PopMcpResult<CallToolResult>
+where
+    E: CommandExecutor,
+    N: Fn(&DevLoopEvent) + Send + Sync + 'static,
+{
+    let Some(url) = params.url.as_deref().or(stored_url) else {
+        return Err(PopMcpError::InvalidInput(
+            "'url' is required when no node has been launched".to_owned(),
+        ));
+    };
+    if crate::read_private_key_suri().is_none() {
+        return Err(PopMcpError::InvalidInput(
+            "PRIVATE_KEY environment variable is required to deploy".to_owned(),
+        ));
+    }
+    if params.interval_secs == Some(0) {
+        return Err(PopMcpError::InvalidInput(
+            "'interval_secs' must be greater than 0".to_owned(),
+        ));
+    }
+    let path = PathBuf::from(&params.path);
+    if !path.is_dir() {
+        return Ok(error_result(format!(
+            "Contract directory {} does not exist",
+            params.path
+        )));
+    }
+    let alias = params
+        .alias
+        .as_deref()
+        .map(str::trim)
+        .filter(|alias| !alias.is_empty())
+        .map(str::to_owned)
+        .or_else(|| {
+            path.canonicalize()
+                .ok()
+                .as_deref()
+                .and_then(Path::file_name)
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "contract".to_owned());
+
+    let config = DevLoopConfig {
+        alias: alias.clone(),
+        path,
+        url: url.to_owned(),
+        constructor: params.constructor,
+        args: params.args,
+        interval: Duration::from_secs(params.interval_secs.unwrap_or(DEFAULT_INTERVAL_SECS)),
+    };
+    loops.start(executor.clone(), config, notify);
+    Ok(success_result(format!(
+        "Dev loop '{}' started: {} is rebuilt and redeployed to {} after every change. \
+         The latest address is recorded as '{}' in pop://contracts and each outcome is sent \
+         as a log notification. Stop it with stop_dev_loop.",
+        alias, params.path, url, alias
+    )))
+}
+
+/// Execute stop_dev_loop tool
+pub async fn stop_dev_loop(
+    loops: &DevLoops,
+    params: StopDevLoopParams,
+) -> PopMcpResult<CallToolResult> {
+    let alias = params.alias.trim();
+    Ok(match loops.stop(alias) {
+        Some(config) => success_result(format!(
+            "Stopped dev loop '{}' ({})",
+            alias,
+            config.path.display()
+        )),
+        None => error_result(format!("No dev loop named '{}'", alias)),
+    })
+}
+
+/// Execute list_dev_loops tool
+pub async fn list_dev_loops(
+    loops: &DevLoops,
+    _params: ListDevLoopsParams,
+) -> PopMcpResult<CallToolResult> {
+    let running = loops.list();
+    if running.is_empty() {
+        return Ok(success_result(
+            "No dev loops running. Start one with start_dev_loop.",
+        ));
+    }
+    let lines: Vec<String> = running
+        .iter()
+        .map(|(config, status)| {
+            let last = status
+                .last
+                .as_ref()
+                .map_or_else(|| "building".to_owned(), describe_event);
+            format!(
+                "{}: {} -> {} ({} rebuild(s); last: {})",
+                config.alias,
+                config.path.display(),
+                config.url,
+                status.rebuilds,
+                last
+            )
+        })
+        .collect();
+    Ok(success_result(format!("Dev loops:\n{}", lines.join("\n"))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutor;
+    use crate::tools::common::content_text;
+
+    #[test]
+    fn describe_event_keeps_the_last_error_line() {
+        let event = DevLoopEvent::Failed {
+            alias: "flipper".to_owned(),
+            stage: "build".to_owned(),
+            error: "Compiling\nerror[E0425]: cannot find value `x`\n\n".to_owned(),
+        };
+        assert_eq!(
+            describe_event(&event),
+            "build failed: error[E0425]: cannot find value `x`"
+        );
+    }
+
+    #[tokio::test]
+    async fn dev_loops_report_failed_rebuilds_until_stopped() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("lib.rs"), "broken")?;
+        let loops = DevLoops::new();
+        let (events, mut received) = tokio::sync::mpsc::unbounded_channel();
+        let executor = MockExecutor::new().on_failure(&["build"], "error: expected item");
+        loops.start(
+            executor.clone(),
+            DevLoopConfig {
+                alias: "flipper".to_owned(),
+                path: dir.path().to_path_buf(),
+                url: "ws://localhost:9944".to_owned(),
+                constructor: None,
+                args: None,
+                interval: Duration::from_millis(10),
+            },
+            move |event: &DevLoopEvent| {
+                let _ = events.send(event.clone());
+            },
+        );
+
+        let Some(DevLoopEvent::Failed { stage, .. }) = received.recv().await else {
+            anyhow::bail!("expected a failed rebuild");
+        };
+        assert_eq!(stage, "build");
+        let listed = list_dev_loops(&loops, ListDevLoopsParams {}).await?;
+        assert!(content_text(&listed).contains("flipper: "));
+        assert!(content_text(&listed).contains("1 rebuild(s); last: build failed"));
+
+        // Unchanged sources are not rebuilt.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(executor.calls().len(), 1);
+
+        let stopped = stop_dev_loop(
+            &loops,
+            StopDevLoopParams {
+                alias: "flipper".to_owned(),
+            },
+        )
+        .await?;
+        assert!(content_text(&stopped).starts_with("Stopped dev loop 'flipper'"));
+        assert!(loops.list().is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+//! Per-network balance display preferences
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::rpc::RpcPool;
+use crate::tokens::{TokenDisplays, TokenFormat};
+
+use super::common::{error_result, success_result};
+
+/// Parameters for the set_token_display tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct SetTokenDisplayParams {
+    /// WebSocket URL of the network the preference applies to.
+    #[schemars(description = "WebSocket URL of the network (e.g., ws://localhost:9944)")]
+    pub url: String,
+    /// Token symbol to display.
+    #[schemars(description = "Token symbol to display (default: the chain's own symbol)")]
+    pub symbol: Option<String>,
+    /// Decimals of one token.
+    #[schemars(description = "Decimals of one token (default: the chain's own decimals)")]
+    pub decimals: Option<u8>,
+    /// Show raw planck values instead.
+    #[schemars(description = "Show raw planck values for this network instead of token amounts")]
+    pub raw: Option<bool>,
+}
+
+impl SetTokenDisplayParams {
+    /// Validate parameters
+    fn validate(&self) -> Result<(), String> {
+        if self.raw == Some(true) && (self.symbol.is_some() || self.decimals.is_some()) {
+            return Err("'raw' cannot be combined with 'symbol' or 'decimals'".to_owned());
+        }
+        if self.symbol.as_deref().is_some_and(|s| s.trim().is_empty()) {
+            return Err("'symbol' cannot be empty".to_owned());
+        }
+        Ok(())
+    }
+}
+
+/// Execute set_token_display tool
+pub async fn set_token_display(
+    pool: &RpcPool,
+    displays: &TokenDisplays,
+    params: SetTokenDisplayParams,
+) -> PopMcpResult<CallToolResult> {
+    params.validate().map_err(PopMcpError::InvalidInput)?;
+
+    if params.raw == Some(true) {
+        displays.set(&params.url, None).await;
+        return Ok(success_result(format!(
+            "Balances on {} are shown in planck.",
+            params.url
+        )));
+    }
+
+    let chain = match (&params.symbol, params.decimals) {
+        (Some(_), Some(_)) => None,
+        _ => TokenFormat::fetch(pool, &params.url).await.ok().flatten(),
+    };
+    let symbol = params
+        .symbol
+        .clone()
+        .or_else(|| chain.as_ref().map(|c| c.symbol.clone()));
+    let decimals = params.decimals.or(chain.as_ref().map(|c| c.decimals));
+    let (Some(symbol), Some(decimals)) = (symbol, decimals) else {
+        return Ok(error_result(format!(
+            "Could not read the token of {}; provide both 'symbol' and 'decimals'",
+            params.url
+        )));
+    };
+
+    let message = format!(
+        "Balances on {} are shown in {} ({} decimals).",
+        params.url, symbol, decimals
+    );
+    displays
+        .set(&params.url, Some(TokenFormat { symbol, decimals }))
+        .await;
+    Ok(success_result(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::common::extract_text;
+
+    fn params() -> SetTokenDisplayParams {
+        SetTokenDisplayParams {
+            url: "ws://localhost:9944".to_owned(),
+            symbol: Some("DOT".to_owned()),
+            decimals: Some(10),
+            raw: None,
+        }
+    }
+
+    #[test]
+    fn validate_rejects_raw_with_overrides() {
+        assert!(params().validate().is_ok());
+        let mut invalid = params();
+        invalid.raw = Some(true);
+        assert!(invalid.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn set_token_display_stores_override() {
+        let pool = RpcPool::new();
+        let displays = TokenDisplays::new();
+        let result = set_token_display(&pool, &displays, params()).await;
+        assert!(result.is_ok_and(|r| extract_text(&r).is_some_and(|t| t.contains("DOT"))));
+        assert_eq!(
+            displays.get(&pool, "ws://localhost:9944").await,
+            Some(TokenFormat {
+                symbol: "DOT".to_owned(),
+                decimals: 10
+            })
+        );
+    }
+}
@@ -0,0 +1,59 @@
+//! Prebuilt fixture access (get_fixture)
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::PopMcpResult;
+use crate::executor::CommandExecutor;
+use crate::fixtures::{ensure_fixture, FixtureKind};
+use crate::tools::common::{error_result, success_result};
+
+/// Parameters for the get_fixture tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct GetFixtureParams {
+    /// Fixture kind: "contract" or "chain".
+    #[schemars(description = "Fixture kind: 'contract' (flipper) or 'chain' (base parachain)")]
+    pub kind: String,
+    /// Build the fixture if it has no artifacts yet (default: true).
+    #[schemars(description = "Build the fixture if it has no artifacts yet (default: true)")]
+    pub build: Option<bool>,
+}
+
+/// Execute get_fixture tool
+pub async fn get_fixture<E: CommandExecutor>(
+    executor: &E,
+    params: GetFixtureParams,
+) -> PopMcpResult<CallToolResult> {
+    let kind = FixtureKind::parse(&params.kind)?;
+
+    match ensure_fixture(executor, kind, params.build.unwrap_or(true), None).await {
+        Ok(fixture) => Ok(success_result(format!(
+            "Fixture path: {}\nBuilt: {}",
+            fixture.path.display(),
+            fixture.built
+        ))),
+        Err(e) => Ok(error_result(format!("Failed to prepare fixture: {}", e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::PopMcpError;
+    use crate::executor::PopExecutor;
+
+    #[tokio::test]
+    async fn get_fixture_rejects_unknown_kind() {
+        let result = get_fixture(
+            &PopExecutor::new(),
+            GetFixtureParams {
+                kind: "parachain".to_owned(),
+                build: None,
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,94 @@
+//! Command history from the audit trail
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::audit::{self, AuditEntry, CommandStatus, AUDIT_URI};
+use crate::error::PopMcpResult;
+
+use super::common::success_result;
+
+/// Commands listed when no limit is given.
+const DEFAULT_LIMIT: usize = 20;
+
+/// Parameters for the list_command_history tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct ListCommandHistoryParams {
+    /// Number of most recent commands to list.
+    #[schemars(description = "Number of most recent commands to list (default: 20)")]
+    pub limit: Option<usize>,
+    /// Only list commands that failed or were interrupted.
+    #[schemars(description = "Only list commands that failed, timed out or were cancelled")]
+    pub failed_only: Option<bool>,
+}
+
+/// One history line for `entry`, followed by its output when it did not succeed.
+fn describe(entry: &AuditEntry) -> String {
+    let status = match entry.status {
+        CommandStatus::Succeeded => "ok",
+        CommandStatus::Failed => "failed",
+        CommandStatus::Interrupted => "interrupted",
+    };
+    let line = format!(
+        "#{} [{}] {:.1}s  {}",
+        entry.id,
+        status,
+        entry.duration_ms as f64 / 1000.0,
+        entry.command
+    );
+    if entry.status == CommandStatus::Succeeded || entry.output.is_empty() {
+        return line;
+    }
+    let output: Vec<String> = entry.output.lines().map(|l| format!("    {}", l)).collect();
+    format!("{}\n{}", line, output.join("\n"))
+}
+
+/// Execute list_command_history tool
+pub async fn list_command_history(
+    params: ListCommandHistoryParams,
+) -> PopMcpResult<CallToolResult> {
+    let failed_only = params.failed_only.unwrap_or(false);
+    let entries: Vec<AuditEntry> = audit::entries()
+        .into_iter()
+        .filter(|entry| !failed_only || entry.status != CommandStatus::Succeeded)
+        .collect();
+    if entries.is_empty() {
+        return Ok(success_result("No Pop CLI commands recorded yet."));
+    }
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT).max(1);
+    let shown = &entries[entries.len().saturating_sub(limit)..];
+    let lines: Vec<String> = shown.iter().map(describe).collect();
+    Ok(success_result(format!(
+        "Last {} of {} recorded command(s), oldest first (full trail: {}):\n\n{}",
+        shown.len(),
+        entries.len(),
+        AUDIT_URI,
+        lines.join("\n")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_shows_output_of_failures_only() {
+        let mut entry = AuditEntry {
+            id: 7,
+            started_at: 0,
+            command: "pop build --path ./flipper".to_owned(),
+            duration_ms: 2500,
+            status: CommandStatus::Succeeded,
+            output: "Build completed".to_owned(),
+        };
+        assert_eq!(describe(&entry), "#7 [ok] 2.5s  pop build --path ./flipper");
+        entry.status = CommandStatus::Failed;
+        entry.output = "error[E0425]: cannot find value".to_owned();
+        assert_eq!(
+            describe(&entry),
+            "#7 [failed] 2.5s  pop build --path ./flipper\n    error[E0425]: cannot find value"
+        );
+    }
+}
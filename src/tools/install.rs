@@ -5,7 +5,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::error::PopMcpResult;
-use crate::executor::PopExecutor;
+use crate::executor::CommandExecutor;
 
 use super::common::{error_result, success_result};
 
@@ -25,11 +25,11 @@ pub struct InstallPopInstructionsParams {
 }
 
 /// Check if Pop CLI is installed and return version information.
-pub fn check_pop_installation(
-    executor: &PopExecutor,
+pub async fn check_pop_installation<E: CommandExecutor>(
+    executor: &E,
     _params: CheckPopInstallationParams,
 ) -> PopMcpResult<CallToolResult> {
-    match executor.execute(&["--version"]) {
+    match executor.execute(&["--version"]).await {
         Ok(output) => Ok(success_result(format!("Pop CLI is installed!\n\n{}", output))),
         Err(e) => Ok(error_result(format!(
             "Pop CLI is not installed.\n\nError: {}\n\nTo install Pop CLI, use the install_pop_instructions tool.",
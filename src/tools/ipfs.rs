@@ -0,0 +1,156 @@
+//! Pinning contract artifacts to IPFS
+
+use std::path::{Path, PathBuf};
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::PopMcpResult;
+use crate::ipfs::Ipfs;
+use crate::outputs::{PinOutput, PinnedFile};
+use crate::tools::common::{error_result, success_result, with_structured};
+
+/// Artifact extensions that are published: metadata and the contract bundle.
+const PINNED_EXTENSIONS: &[&str] = &["json", "contract"];
+
+/// Parameters for the pin_to_ipfs tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct PinToIpfsParams {
+    /// Path to the contract directory.
+    #[schemars(description = "Path to the built contract directory")]
+    pub path: String,
+    /// Contract name, when target/ink holds artifacts of several contracts.
+    #[schemars(
+        description = "Contract name (artifact file stem) to pin, when target/ink holds several contracts"
+    )]
+    pub contract: Option<String>,
+}
+
+impl PinToIpfsParams {
+    /// Validate the parameters
+    fn validate(&self) -> Result<(), String> {
+        if self.path.is_empty() {
+            return Err("Path cannot be empty".to_owned());
+        }
+        Ok(())
+    }
+}
+
+/// Metadata and bundle files of the contract built at `path`, sorted by name.
+fn artifacts(path: &str, contract: Option<&str>) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(Path::new(path).join("target/ink"))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|e| e.path())
+                .filter(|p| p.is_file())
+                .filter(|p| {
+                    p.extension()
+                        .and_then(|e| e.to_str())
+                        .is_some_and(|e| PINNED_EXTENSIONS.contains(&e))
+                })
+                .filter(|p| {
+                    contract.is_none_or(|name| p.file_stem().and_then(|s| s.to_str()) == Some(name))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Execute pin_to_ipfs tool
+pub async fn pin_to_ipfs(ipfs: &Ipfs, params: PinToIpfsParams) -> PopMcpResult<CallToolResult> {
+    params
+        .validate()
+        .map_err(crate::error::PopMcpError::InvalidInput)?;
+
+    let files = artifacts(&params.path, params.contract.as_deref());
+    if files.is_empty() {
+        return Ok(error_result(format!(
+            "No contract metadata or bundle found in {}/target/ink. Build the contract first with build_contract.",
+            params.path
+        )));
+    }
+
+    let mut pinned = Vec::new();
+    for file in files {
+        let name = file
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let content = match std::fs::read(&file) {
+            Ok(content) => content,
+            Err(e) => return Ok(error_result(format!("Failed to read {}: {}", name, e))),
+        };
+        match ipfs.add(&name, &content).await {
+            Ok(cid) => pinned.push(PinnedFile {
+                url: ipfs.gateway_url(&cid),
+                name,
+                cid,
+            }),
+            Err(e) => {
+                return Ok(error_result(format!("Failed to pin {}: {}", name, e)));
+            }
+        }
+    }
+
+    let lines: Vec<String> = pinned
+        .iter()
+        .map(|file| format!("{}: {}\n  {}", file.name, file.cid, file.url))
+        .collect();
+    let output = PinOutput { files: pinned };
+    Ok(with_structured(
+        success_result(format!("Pinned to IPFS:\n\n{}", lines.join("\n"))),
+        &output,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn artifacts_selects_metadata_and_bundles() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let ink = dir.path().join("target/ink");
+        std::fs::create_dir_all(&ink)?;
+        for name in [
+            "flipper.json",
+            "flipper.contract",
+            "flipper.polkavm",
+            "erc20.json",
+        ] {
+            std::fs::write(ink.join(name), "{}")?;
+        }
+        let path = dir.path().to_string_lossy();
+        let names = |files: Vec<PathBuf>| -> Vec<String> {
+            files
+                .iter()
+                .filter_map(|f| f.file_name())
+                .map(|n| n.to_string_lossy().into_owned())
+                .collect()
+        };
+        assert_eq!(
+            names(artifacts(&path, None)),
+            ["erc20.json", "flipper.contract", "flipper.json"]
+        );
+        assert_eq!(
+            names(artifacts(&path, Some("flipper"))),
+            ["flipper.contract", "flipper.json"]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn pin_without_artifacts_is_an_error_result() {
+        let params = PinToIpfsParams {
+            path: "/nonexistent/contract".to_owned(),
+            contract: None,
+        };
+        let result = pin_to_ipfs(&Ipfs::default(), params).await;
+        assert!(result.is_ok_and(|r| r.is_error == Some(true)));
+    }
+}
@@ -0,0 +1,326 @@
+//! Package metadata stamping (license, repository and authors in Cargo.toml)
+
+use std::path::{Path, PathBuf};
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use toml_edit::{value, Array, DocumentMut, Item, Table};
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::tools::common::{error_result, success_result};
+
+/// Parameters for the stamp_metadata tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct StampMetadataParams {
+    /// Path to the contract or chain project directory.
+    #[schemars(description = "Path to the contract or chain project directory")]
+    pub path: String,
+    /// SPDX license expression.
+    #[schemars(description = "SPDX license expression, e.g. 'Apache-2.0' or 'MIT OR Apache-2.0'")]
+    pub license: Option<String>,
+    /// Source repository URL.
+    #[schemars(description = "Source repository URL, e.g. 'https://github.com/org/project'")]
+    pub repository: Option<String>,
+    /// Package authors.
+    #[schemars(description = "Package authors, e.g. ['Jane Doe <jane@example.com>']")]
+    pub authors: Option<Vec<String>>,
+}
+
+/// Package metadata to set in a project's manifests.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageMetadata {
+    /// SPDX license expression.
+    pub license: Option<String>,
+    /// Source repository URL.
+    pub repository: Option<String>,
+    /// Package authors.
+    pub authors: Option<Vec<String>>,
+}
+
+impl PackageMetadata {
+    /// Metadata from the optional tool params, trimmed.
+    pub fn new(
+        license: Option<&str>,
+        repository: Option<&str>,
+        authors: Option<&[String]>,
+    ) -> Self {
+        Self {
+            license: license.map(str::trim).map(str::to_owned),
+            repository: repository.map(str::trim).map(str::to_owned),
+            authors: authors.map(|authors| authors.iter().map(|a| a.trim().to_owned()).collect()),
+        }
+    }
+
+    /// Whether nothing is set.
+    pub fn is_empty(&self) -> bool {
+        self.license.is_none() && self.repository.is_none() && self.authors.is_none()
+    }
+
+    /// Check the values before writing them to a manifest.
+    pub fn validate(&self) -> PopMcpResult<()> {
+        if let Some(license) = &self.license {
+            let valid = !license.is_empty()
+                && license
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || " -.+()".contains(c));
+            if !valid {
+                return Err(PopMcpError::InvalidInput(format!(
+                    "Invalid license '{}': use an SPDX expression such as 'Apache-2.0' or 'MIT OR Apache-2.0'",
+                    license
+                )));
+            }
+        }
+        if let Some(repository) = &self.repository {
+            let valid = (repository.starts_with("https://") || repository.starts_with("http://"))
+                && !repository.contains(char::is_whitespace);
+            if !valid {
+                return Err(PopMcpError::InvalidInput(format!(
+                    "Invalid repository '{}': use an http(s) URL",
+                    repository
+                )));
+            }
+        }
+        if let Some(authors) = &self.authors {
+            if authors.is_empty() || authors.iter().any(|a| a.is_empty() || a.contains('\n')) {
+                return Err(PopMcpError::InvalidInput(
+                    "authors must be a non-empty list of single-line names".to_owned(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the fields in `table`, skipping those inherited from the workspace when
+    /// `inherit` is set. Returns whether anything changed.
+    fn apply(&self, table: &mut Table, inherit: bool) -> bool {
+        let inherited = |table: &Table, key: &str| {
+            inherit
+                && table
+                    .get(key)
+                    .and_then(|item| item.get("workspace"))
+                    .and_then(Item::as_bool)
+                    == Some(true)
+        };
+        let set = |table: &mut Table, key: &str, item: Item| {
+            let current = table.get(key).map(ToString::to_string);
+            if inherited(table, key)
+                || current.as_deref().map(str::trim) == Some(item.to_string().trim())
+            {
+                return false;
+            }
+            table.insert(key, item);
+            true
+        };
+        let mut changed = false;
+        if let Some(license) = &self.license {
+            changed |= set(table, "license", value(license.as_str()));
+            // Cargo rejects a package with both.
+            if !inherited(table, "license") && table.remove("license-file").is_some() {
+                changed = true;
+            }
+        }
+        if let Some(repository) = &self.repository {
+            changed |= set(table, "repository", value(repository.as_str()));
+        }
+        if let Some(authors) = &self.authors {
+            let authors: Array = authors.iter().map(String::as_str).collect();
+            changed |= set(table, "authors", value(authors));
+        }
+        changed
+    }
+}
+
+/// `Cargo.toml` files under `dir`, skipping build output and hidden directories.
+pub(crate) fn manifests(dir: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let path = entry.path();
+            if path.is_dir() {
+                if !name.starts_with('.') && name != "target" && name != "node_modules" {
+                    pending.push(path);
+                }
+            } else if name == "Cargo.toml" {
+                found.push(path);
+            }
+        }
+    }
+    found.sort();
+    found
+}
+
+/// Set `metadata` in every manifest of the project at `dir`: in `[workspace.package]`
+/// of workspace roots, and in `[package]` unless the field is inherited from the
+/// workspace. Returns the manifests that changed.
+pub fn stamp(dir: &Path, metadata: &PackageMetadata) -> PopMcpResult<Vec<PathBuf>> {
+    metadata.validate()?;
+    let mut stamped = Vec::new();
+    for manifest in manifests(dir) {
+        let text = std::fs::read_to_string(&manifest).map_err(|e| {
+            PopMcpError::Internal(format!("Failed to read {}: {}", manifest.display(), e))
+        })?;
+        let mut doc: DocumentMut = text.parse().map_err(|e| {
+            PopMcpError::InvalidInput(format!("{} is invalid: {}", manifest.display(), e))
+        })?;
+        let mut changed = false;
+        if let Some(workspace) = doc.get_mut("workspace").and_then(Item::as_table_mut) {
+            let package = workspace
+                .entry("package")
+                .or_insert_with(|| Item::Table(Table::new()));
+            if let Some(package) = package.as_table_mut() {
+                changed |= metadata.apply(package, false);
+            }
+        }
+        if let Some(package) = doc.get_mut("package").and_then(Item::as_table_mut) {
+            changed |= metadata.apply(package, true);
+        }
+        if changed {
+            std::fs::write(&manifest, doc.to_string()).map_err(|e| {
+                PopMcpError::Internal(format!("Failed to write {}: {}", manifest.display(), e))
+            })?;
+            stamped.push(manifest);
+        }
+    }
+    Ok(stamped)
+}
+
+/// One line listing the manifests `stamp` changed, relative to `dir`.
+pub fn describe(dir: &Path, stamped: &[PathBuf]) -> String {
+    if stamped.is_empty() {
+        return "Package metadata already up to date".to_owned();
+    }
+    let files: Vec<String> = stamped
+        .iter()
+        .map(|path| path.strip_prefix(dir).unwrap_or(path).display().to_string())
+        .collect();
+    format!("Package metadata set in {}", files.join(", "))
+}
+
+/// Stamp a freshly scaffolded project, returning a note for the tool result (`None`
+/// when no metadata was requested).
+pub fn stamp_scaffold(dir: &Path, metadata: &PackageMetadata) -> Option<String> {
+    if metadata.is_empty() {
+        return None;
+    }
+    if !dir.join("Cargo.toml").is_file() {
+        return Some(format!(
+            "Package metadata not set: no Cargo.toml in {}",
+            dir.display()
+        ));
+    }
+    Some(match stamp(dir, metadata) {
+        Ok(stamped) => describe(dir, &stamped),
+        Err(e) => format!("Package metadata not set: {}", e),
+    })
+}
+
+/// Execute stamp_metadata tool
+pub async fn stamp_metadata(params: StampMetadataParams) -> PopMcpResult<CallToolResult> {
+    let metadata = PackageMetadata::new(
+        params.license.as_deref(),
+        params.repository.as_deref(),
+        params.authors.as_deref(),
+    );
+    if metadata.is_empty() {
+        return Err(PopMcpError::InvalidInput(
+            "Set at least one of license, repository or authors".to_owned(),
+        ));
+    }
+    let dir = Path::new(&params.path);
+    if !dir.join("Cargo.toml").is_file() {
+        return Ok(error_result(format!(
+            "No Cargo.toml in {}. Point path at a contract or chain project.",
+            params.path
+        )));
+    }
+    let stamped = stamp(dir, &metadata)?;
+    Ok(success_result(describe(dir, &stamped)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::common::content_text;
+
+    const WORKSPACE: &str = "\
+[workspace]
+members = [\"node\", \"runtime\"]
+
+[workspace.package]
+authors = [\"Anonymous\"]
+license = \"Unlicense\"
+# Keep in sync with the node.
+edition = \"2021\"
+";
+
+    const RUNTIME: &str = "\
+[package]
+name = \"parachain-runtime\"
+authors.workspace = true
+license-file = \"LICENSE\"
+repository = \"https://example.com/old\"
+";
+
+    #[tokio::test]
+    async fn workspaces_are_stamped_where_fields_are_defined() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("Cargo.toml"), WORKSPACE)?;
+        std::fs::create_dir_all(dir.path().join("runtime"))?;
+        std::fs::write(dir.path().join("runtime/Cargo.toml"), RUNTIME)?;
+        std::fs::create_dir_all(dir.path().join("target/debug"))?;
+        std::fs::write(dir.path().join("target/debug/Cargo.toml"), "[package]\n")?;
+
+        let result = stamp_metadata(StampMetadataParams {
+            path: dir.path().to_string_lossy().into_owned(),
+            license: Some("Apache-2.0".to_owned()),
+            repository: Some("https://github.com/org/chain".to_owned()),
+            authors: Some(vec!["Org <dev@org.io>".to_owned()]),
+        })
+        .await?;
+        assert_eq!(
+            content_text(&result),
+            "Package metadata set in Cargo.toml, runtime/Cargo.toml"
+        );
+
+        let root = std::fs::read_to_string(dir.path().join("Cargo.toml"))?;
+        assert!(root.contains("authors = [\"Org <dev@org.io>\"]"));
+        assert!(root.contains("license = \"Apache-2.0\""));
+        assert!(root.contains("# Keep in sync with the node."));
+        let runtime = std::fs::read_to_string(dir.path().join("runtime/Cargo.toml"))?;
+        assert!(runtime.contains("authors.workspace = true"));
+        assert!(runtime.contains("license = \"Apache-2.0\""));
+        assert!(!runtime.contains("license-file"));
+        assert!(runtime.contains("repository = \"https://github.com/org/chain\""));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("target/debug/Cargo.toml"))?,
+            "[package]\n"
+        );
+
+        let again = stamp(
+            dir.path(),
+            &PackageMetadata::new(Some("Apache-2.0"), None, None),
+        )?;
+        assert!(again.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_metadata_is_rejected() {
+        let invalid = [
+            PackageMetadata::new(Some("MIT; rm -rf"), None, None),
+            PackageMetadata::new(None, Some("git@github.com:org/x"), None),
+            PackageMetadata::new(None, None, Some(&[])),
+        ];
+        for metadata in invalid {
+            assert!(metadata.validate().is_err());
+        }
+    }
+}
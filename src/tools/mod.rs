@@ -5,37 +5,114 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::error::PopMcpResult;
-use crate::executor::PopExecutor;
+use crate::executor::CommandExecutor;
 
+pub mod accounts;
 pub mod build;
+pub mod bundle;
 pub mod call;
+pub mod capabilities;
+pub mod ci;
 pub mod clean;
 pub mod common;
 pub mod convert;
+pub mod deployments;
+pub mod deprecation;
+pub mod devloop;
+pub mod display;
+pub mod fixture;
+pub mod history;
 pub mod install;
+pub mod ipfs;
+pub mod metadata;
 pub mod new;
+pub mod nodes;
+pub mod readme;
+pub mod rpc;
+pub mod self_test;
 pub mod test;
 pub mod up;
+pub mod watch;
 
+pub use accounts::{derive_test_accounts, DeriveTestAccountsParams};
 pub use build::chain::{build_chain, BuildChainParams};
 pub use build::contract::{build_contract, BuildContractParams};
+pub use build::size::{contract_size_report, ContractSizeReportParams};
+pub use bundle::{export_debug_bundle, ExportDebugBundleParams};
+pub use call::account::{
+    add_proxy, map_account, set_identity, AddProxyParams, MapAccountParams, SetIdentityParams,
+};
+pub use call::batch::{batch_chain_calls, BatchCall, BatchChainCallsParams};
 pub use call::chain::{call_chain, CallChainParams};
+pub use call::collator::{
+    collator_selection_state, register_collator, CollatorSelectionStateParams,
+    RegisterCollatorParams,
+};
 pub use call::contract::{call_contract, CallContractParams};
-pub use clean::{clean_network, clean_nodes, CleanNetworkParams, CleanNodesParams};
+pub use call::contract_batch::{call_contract_batch, CallContractBatchParams, ContractBatchCall};
+pub use call::diff::{state_diff_call, StateDiffCallParams, StorageSelector};
+pub use call::estimate::{estimate_gas, EstimateGasParams};
+pub use call::governance::{
+    submit_preimage, submit_referendum, SubmitPreimageParams, SubmitReferendumParams,
+};
+pub use call::query::{save_query, saved_query_call, RunSavedQueryParams, SaveQueryParams};
+pub use call::resubmit::{resubmit_transaction, ResubmitTransactionParams};
+pub use call::schedule::{schedule_call, ScheduleCallParams};
+pub use call::storage::{query_contract_storage, QueryContractStorageParams};
+pub use call::stress::{stress_call, StressCallParams};
+pub use capabilities::{capabilities, CapabilitiesParams};
+pub use clean::{
+    clean_chain, clean_contract, clean_network, clean_nodes, stop_pids, CleanChainParams,
+    CleanContractParams, CleanNetworkParams, CleanNodesParams,
+};
 pub use convert::{convert_address, ConvertAddressParams};
+pub use deployments::{
+    forget_deployment, list_deployments, ForgetDeploymentParams, ListDeploymentsParams,
+};
+pub use devloop::{
+    list_dev_loops, start_dev_loop, stop_dev_loop, ListDevLoopsParams, StartDevLoopParams,
+    StopDevLoopParams,
+};
+pub use display::{set_token_display, SetTokenDisplayParams};
+pub use fixture::{get_fixture, GetFixtureParams};
+pub use history::{list_command_history, ListCommandHistoryParams};
 pub use install::{
     check_pop_installation, install_pop_instructions, CheckPopInstallationParams,
     InstallPopInstructionsParams,
 };
+pub use ipfs::{pin_to_ipfs, PinToIpfsParams};
+pub use metadata::{stamp_metadata, StampMetadataParams};
 pub use new::chain::{create_chain, CreateChainParams};
 pub use new::contract::{create_contract, CreateContractParams};
+pub use new::frontend::{create_contract_with_frontend, CreateContractWithFrontendParams};
+pub use nodes::{
+    get_node_logs, list_nodes, select_node, stop_node, GetNodeLogsParams, ListNodesParams,
+    SelectNodeParams, StopNodeParams,
+};
+pub use readme::{generate_readme, GenerateReadmeParams};
+pub use rpc::{
+    chain_rpc, node_health, node_status, pending_extrinsics, runtime_api_call, ChainRpcParams,
+    NodeHealthParams, NodeStatusParams, PendingExtrinsicsParams, RuntimeApiCallParams,
+};
+pub use self_test::{self_test, SelfTestParams};
 pub use test::chain::{test_chain, TestChainParams};
 pub use test::contract::{test_contract, TestContractParams};
 pub use up::chain::{up_ink_node, UpInkNodeParams};
 pub use up::contract::{deploy_contract, DeployContractParams};
-pub use up::network::{up_network, UpNetworkParams};
+pub use up::instantiate::{instantiate_from_code_hash, InstantiateFromCodeHashParams};
+pub use up::network::{up_network, NetworkOverrides, UpNetworkParams};
+pub use up::profile::{
+    save_network_profile, up_network_profile, SaveNetworkProfileParams, UpNetworkProfileParams,
+};
+pub use up::testnet::{deploy_to_testnet, DeployToTestnetParams};
+pub use watch::{
+    list_watches, stop_watch, watch_condition, ListWatchesParams, StopWatchParams,
+    WatchConditionParams,
+};
 
-pub(crate) use new::contract::{list_templates, ListTemplatesParams};
+pub(crate) use new::contract::{
+    list_contract_templates, ListContractTemplatesParams, ListTemplatesParams,
+};
 
 /// Parameters for the pop_help tool.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
@@ -47,8 +124,8 @@ pub struct PopHelpParams {
 }
 
 /// Get help for Pop CLI commands.
-pub(crate) fn pop_help(
-    executor: &PopExecutor,
+pub(crate) async fn pop_help<E: CommandExecutor>(
+    executor: &E,
     params: PopHelpParams,
 ) -> PopMcpResult<CallToolResult> {
     let args = if let Some(ref command) = params.command {
@@ -59,7 +136,7 @@ pub(crate) fn pop_help(
         vec!["--help"]
     };
 
-    match executor.execute(&args) {
+    match executor.execute(&args).await {
         Ok(output) => Ok(common::success_result(format!(
             "Pop CLI Help:\n\n{}",
             output
@@ -5,8 +5,10 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::error::PopMcpResult;
-use crate::executor::PopExecutor;
-use crate::tools::common::{error_result, success_result};
+use crate::executor::CommandExecutor;
+use crate::tools::common::{error_result, resolve_cwd, success_result};
+use crate::tools::metadata::{self, PackageMetadata};
+use crate::tools::new::project_path;
 
 /// Parameters for the create_chain tool.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
@@ -37,9 +39,36 @@ pub struct CreateChainParams {
     /// Token decimals for the chain (Pop templates only).
     #[schemars(description = "Token decimals (default: 12) - only applies to Pop templates")]
     pub decimals: Option<u8>,
+    /// Directory to create the project in.
+    #[schemars(
+        description = "Directory to create the project in (default: the client's first workspace root, else the server's working directory)"
+    )]
+    pub cwd: Option<String>,
+    /// SPDX license to set in Cargo.toml.
+    #[schemars(
+        description = "SPDX license expression to set in the generated Cargo.toml, e.g. 'Apache-2.0'"
+    )]
+    pub license: Option<String>,
+    /// Repository URL to set in Cargo.toml.
+    #[schemars(description = "Source repository URL to set in the generated Cargo.toml")]
+    pub repository: Option<String>,
+    /// Authors to set in Cargo.toml.
+    #[schemars(
+        description = "Authors to set in the generated Cargo.toml, e.g. ['Jane Doe <jane@example.com>']"
+    )]
+    pub authors: Option<Vec<String>>,
 }
 
 impl CreateChainParams {
+    /// Package metadata to stamp into the generated manifests.
+    fn metadata(&self) -> PackageMetadata {
+        PackageMetadata::new(
+            self.license.as_deref(),
+            self.repository.as_deref(),
+            self.authors.as_deref(),
+        )
+    }
+
     /// Validate the chain name and parameters.
     fn validate(&self) -> Result<(), String> {
         if self.name.is_empty() {
@@ -109,19 +138,28 @@ fn build_create_chain_args(params: &CreateChainParams) -> Vec<String> {
 }
 
 /// Execute create_chain tool.
-pub fn create_chain(
-    executor: &PopExecutor,
+pub async fn create_chain<E: CommandExecutor>(
+    executor: &E,
     params: CreateChainParams,
 ) -> PopMcpResult<CallToolResult> {
     // Validate parameters
     params
         .validate()
         .map_err(crate::error::PopMcpError::InvalidInput)?;
+    let package_metadata = params.metadata();
+    package_metadata.validate()?;
 
+    let dir =
+        resolve_cwd(params.cwd.as_deref()).map_err(crate::error::PopMcpError::InvalidInput)?;
+    let project = project_path(executor, dir.as_deref(), &params.name)?;
     let args = build_create_chain_args(&params);
     let args_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = match &dir {
+        Some(dir) => executor.execute_in_dir(dir, &args_refs).await,
+        None => executor.execute(&args_refs).await,
+    };
 
-    match executor.execute(&args_refs) {
+    match output {
         Ok(output) => {
             // Check for common error patterns in output
             if output.contains("directory already exists")
@@ -130,12 +168,19 @@ pub fn create_chain(
             {
                 Ok(error_result(format!("Failed to create chain: {}", output)))
             } else {
+                let stamped = metadata::stamp_scaffold(&project, &package_metadata)
+                    .map(|note| format!("{}\n", note))
+                    .unwrap_or_default();
                 Ok(success_result(format!(
-                    "Successfully created chain project: {}\n\nNext steps:\n\
+                    "Successfully created chain project: {} at {}\n{}\nNext steps:\n\
                     1. cd {}\n\
                     2. pop build --release\n\
                     3. pop up network -f ./network.toml\n\n{}",
-                    params.name, params.name, output
+                    params.name,
+                    project.display(),
+                    stamped,
+                    project.display(),
+                    output
                 )))
             }
         }
@@ -157,6 +202,10 @@ mod tests {
                 template: "r0gue-io/base-parachain".to_owned(),
                 symbol: None,
                 decimals: None,
+                cwd: None,
+                license: None,
+                repository: None,
+                authors: None,
             };
             assert!(params.validate().is_ok());
         }
@@ -171,6 +220,10 @@ mod tests {
                 template: "r0gue-io/base-parachain".to_owned(),
                 symbol: None,
                 decimals: None,
+                cwd: None,
+                license: None,
+                repository: None,
+                authors: None,
             };
             assert!(
                 params.validate().is_err(),
@@ -188,6 +241,10 @@ mod tests {
             template: "r0gue-io/base-parachain".to_owned(),
             symbol: None,
             decimals: None,
+            cwd: None,
+            license: None,
+            repository: None,
+            authors: None,
         };
         let result = params.validate();
         assert!(result.is_err());
@@ -206,6 +263,10 @@ mod tests {
             template: "openzeppelin/generic-template".to_owned(),
             symbol: None,
             decimals: None,
+            cwd: None,
+            license: None,
+            repository: None,
+            authors: None,
         };
         let result = params.validate();
         assert!(result.is_err());
@@ -235,6 +296,10 @@ mod tests {
                 template: template.to_owned(),
                 symbol: None,
                 decimals: None,
+                cwd: None,
+                license: None,
+                repository: None,
+                authors: None,
             };
             assert!(
                 params.validate().is_ok(),
@@ -253,6 +318,10 @@ mod tests {
             template: "r0gue-io/base-parachain".to_owned(),
             symbol: None,
             decimals: None,
+            cwd: None,
+            license: None,
+            repository: None,
+            authors: None,
         };
         let args = build_create_chain_args(&params);
         assert_eq!(
@@ -276,6 +345,10 @@ mod tests {
             template: "r0gue-io/base-parachain".to_owned(),
             symbol: Some("TOKEN".to_owned()),
             decimals: Some(18),
+            cwd: None,
+            license: None,
+            repository: None,
+            authors: None,
         };
         let args = build_create_chain_args(&params);
         assert_eq!(
@@ -294,4 +367,29 @@ mod tests {
             ]
         );
     }
+
+    #[tokio::test]
+    async fn create_rejects_existing_project_directories() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::create_dir(dir.path().join("my_chain"))?;
+        let executor = crate::test_utils::MockExecutor::new().in_dir(dir.path());
+        let params = CreateChainParams {
+            name: "my_chain".to_owned(),
+            provider: "pop".to_owned(),
+            template: "r0gue-io/base-parachain".to_owned(),
+            symbol: None,
+            decimals: None,
+            cwd: None,
+            license: None,
+            repository: None,
+            authors: None,
+        };
+        let result = create_chain(&executor, params).await;
+        let Err(crate::error::PopMcpError::InvalidInput(message)) = result else {
+            panic!("existing project directory accepted");
+        };
+        assert!(message.contains(&dir.path().join("my_chain").display().to_string()));
+        assert!(executor.calls().is_empty());
+        Ok(())
+    }
 }
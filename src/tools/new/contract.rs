@@ -3,20 +3,39 @@
 use rmcp::model::CallToolResult;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use std::process::Command;
 
+use toml_edit::{value, DocumentMut, Item};
+
 use crate::error::PopMcpResult;
-use crate::executor::PopExecutor;
-use crate::tools::common::{error_result, success_result};
+use crate::executor::CommandExecutor;
+use crate::tools::common::{error_result, resolve_cwd, success_result};
+use crate::tools::metadata::{self, PackageMetadata};
+use crate::tools::new::{e2e, ink, project_path};
+
+/// Parameters for the list_contract_templates tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+#[allow(clippy::empty_structs_with_brackets)]
+pub(crate) struct ListContractTemplatesParams {}
 
-/// Parameters for the list_templates tool.
+/// Parameters for the deprecated list_templates tool.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 #[schemars(extend("properties" = {}))]
 #[allow(clippy::empty_structs_with_brackets)]
 pub(crate) struct ListTemplatesParams {}
 
+impl From<ListTemplatesParams> for ListContractTemplatesParams {
+    fn from(_: ListTemplatesParams) -> Self {
+        Self {}
+    }
+}
+
 /// List available ink! contract templates.
-pub(crate) fn list_templates(_params: ListTemplatesParams) -> PopMcpResult<CallToolResult> {
+pub(crate) fn list_contract_templates(
+    _params: ListContractTemplatesParams,
+) -> PopMcpResult<CallToolResult> {
     let templates = "\
 Available ink! Contract Templates:\n\n\
 1. **standard** - Basic flipper contract (boolean toggle)\n\
@@ -41,15 +60,58 @@ pub struct CreateContractParams {
     pub name: String,
     /// Template to use for the contract.
     #[schemars(
-        description = "Template to use (standard, erc20, erc721, erc1155, dns, cross-contract-calls, multisig)"
+        description = "Template to use (standard, erc20, erc721, erc1155, dns, cross-contract-calls, multisig); omit when using template_url"
     )]
+    #[serde(default)]
     pub template: String,
+    /// Git repository to scaffold from instead of a built-in template.
+    #[schemars(
+        description = "https:// or SSH (ssh://, git@host:org/repo.git) URL of a git repository holding a contract template (e.g. a team's audited template), cloned instead of using a built-in template"
+    )]
+    pub template_url: Option<String>,
+    /// ink! release to pin the project to.
+    #[schemars(
+        description = "ink! release the project targets: 'v6' (pallet-revive chains, the templates' default) or 'v5' (pallet-contracts chains); the ink dependencies of the new project are pinned to it"
+    )]
+    pub ink_version: Option<String>,
+    /// Whether to scaffold ink_e2e tests.
+    #[schemars(
+        description = "Add an ink_e2e test module exercising the template's messages (standard and erc20 templates), with the ink_e2e dev-dependency and e2e-tests feature, ready for test_contract with e2e"
+    )]
+    pub with_e2e_tests: Option<bool>,
     /// Whether to scaffold a frontend using the typink template.
     #[schemars(description = "Scaffold a typink frontend alongside the contract")]
     pub with_frontend: Option<bool>,
+    /// Directory to create the project in.
+    #[schemars(
+        description = "Directory to create the project in (default: the client's first workspace root, else the server's working directory)"
+    )]
+    pub cwd: Option<String>,
+    /// SPDX license to set in Cargo.toml.
+    #[schemars(
+        description = "SPDX license expression to set in the generated Cargo.toml, e.g. 'Apache-2.0'"
+    )]
+    pub license: Option<String>,
+    /// Repository URL to set in Cargo.toml.
+    #[schemars(description = "Source repository URL to set in the generated Cargo.toml")]
+    pub repository: Option<String>,
+    /// Authors to set in Cargo.toml.
+    #[schemars(
+        description = "Authors to set in the generated Cargo.toml, e.g. ['Jane Doe <jane@example.com>']"
+    )]
+    pub authors: Option<Vec<String>>,
 }
 
 impl CreateContractParams {
+    /// Package metadata to stamp into the generated manifests.
+    fn metadata(&self) -> PackageMetadata {
+        PackageMetadata::new(
+            self.license.as_deref(),
+            self.repository.as_deref(),
+            self.authors.as_deref(),
+        )
+    }
+
     /// Validate the contract name
     fn validate(&self) -> Result<(), String> {
         if self.name.is_empty() {
@@ -61,10 +123,126 @@ impl CreateContractParams {
                     .to_owned(),
             );
         }
+        match &self.template_url {
+            Some(url) => {
+                validate_template_url(url)?;
+                if self.with_frontend == Some(true) {
+                    return Err("with_frontend is not supported with template_url".to_owned());
+                }
+            }
+            None if self.template.trim().is_empty() => {
+                return Err("Provide a template or a template_url".to_owned());
+            }
+            None => {}
+        }
+        if let Some(version) = &self.ink_version {
+            ink::release(version)?;
+        }
+        if self.with_e2e_tests == Some(true)
+            && (self.template_url.is_some()
+                || !e2e::E2E_TEMPLATES.contains(&self.template.as_str()))
+        {
+            return Err(format!(
+                "with_e2e_tests supports the {} templates",
+                e2e::E2E_TEMPLATES.join(" and ")
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Check that `url` is an https or SSH git URL.
+fn validate_template_url(url: &str) -> Result<(), String> {
+    let invalid = || {
+        format!(
+            "Invalid template_url '{}': use an https:// or SSH (ssh:// or git@host:path) git URL",
+            url
+        )
+    };
+    if url.is_empty() || url.starts_with('-') || url.chars().any(char::is_whitespace) {
+        return Err(invalid());
+    }
+    let valid = match url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("ssh://"))
+    {
+        Some(rest) => rest
+            .split_once('/')
+            .is_some_and(|(host, path)| !host.is_empty() && !path.is_empty()),
+        // scp-like syntax: user@host:path
+        None => url.split_once(':').is_some_and(|(user_host, path)| {
+            user_host
+                .split_once('@')
+                .is_some_and(|(user, host)| !user.is_empty() && !host.is_empty())
+                && !user_host.contains('/')
+                && !path.is_empty()
+        }),
+    };
+    if valid {
         Ok(())
+    } else {
+        Err(invalid())
     }
 }
 
+/// Clone the template at `url` into `project`, returning the commit it was taken from.
+///
+/// The clone's history is dropped so the project starts fresh, and the package is
+/// renamed to `name`. A failed or unusable checkout is removed.
+async fn checkout_template(url: &str, project: &Path, name: &str) -> Result<String, String> {
+    let output = tokio::process::Command::new("git")
+        .args(["clone", "--depth", "1", "--quiet", "--", url])
+        .arg(project)
+        // Fail instead of waiting for credentials nobody can type.
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        let _ = std::fs::remove_dir_all(project);
+        return Err(format!(
+            "git clone of {} failed:\n{}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let commit = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(project)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .await
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+        .unwrap_or_default();
+    let prepared = std::fs::remove_dir_all(project.join(".git"))
+        .map_err(|e| format!("Failed to remove the template's git history: {}", e))
+        .and_then(|()| rename_package(&project.join("Cargo.toml"), name));
+    if let Err(e) = prepared {
+        let _ = std::fs::remove_dir_all(project);
+        return Err(format!("{} is not a usable contract template: {}", url, e));
+    }
+    Ok(commit)
+}
+
+/// Set the package name in the manifest at `manifest`.
+fn rename_package(manifest: &Path, name: &str) -> Result<(), String> {
+    let text = std::fs::read_to_string(manifest)
+        .map_err(|e| format!("Failed to read {}: {}", manifest.display(), e))?;
+    let mut doc: DocumentMut = text
+        .parse()
+        .map_err(|e| format!("Invalid {}: {}", manifest.display(), e))?;
+    let package = doc
+        .get_mut("package")
+        .and_then(Item::as_table_like_mut)
+        .ok_or_else(|| format!("{} has no [package]", manifest.display()))?;
+    package.insert("name", value(name));
+    std::fs::write(manifest, doc.to_string())
+        .map_err(|e| format!("Failed to write {}: {}", manifest.display(), e))
+}
+
 /// Build command arguments for create_contract
 fn build_create_contract_args(params: &CreateContractParams) -> Vec<&str> {
     let mut args = vec![
@@ -83,14 +261,16 @@ fn build_create_contract_args(params: &CreateContractParams) -> Vec<&str> {
 }
 
 /// Execute create_contract tool
-pub fn create_contract(
-    executor: &PopExecutor,
+pub async fn create_contract<E: CommandExecutor>(
+    executor: &E,
     params: CreateContractParams,
 ) -> PopMcpResult<CallToolResult> {
     // Validate parameters
     params
         .validate()
         .map_err(crate::error::PopMcpError::InvalidInput)?;
+    let package_metadata = params.metadata();
+    package_metadata.validate()?;
 
     if params.with_frontend == Some(true) {
         if let Err(message) = validate_frontend_requirements() {
@@ -98,24 +278,90 @@ pub fn create_contract(
         }
     }
 
+    let dir =
+        resolve_cwd(params.cwd.as_deref()).map_err(crate::error::PopMcpError::InvalidInput)?;
+    let project = project_path(executor, dir.as_deref(), &params.name)?;
+    if let Some(url) = params.template_url.as_deref() {
+        let message = match checkout_template(url, &project, &params.name).await {
+            Ok(commit) => format!(
+                "Successfully created contract: {} at {}\nTemplate: {} (commit {})",
+                params.name,
+                project.display(),
+                url,
+                if commit.is_empty() {
+                    "unknown"
+                } else {
+                    &commit
+                }
+            ),
+            Err(e) => return Ok(error_result(format!("Failed to create contract: {}", e))),
+        };
+        return Ok(success_result(finish(
+            message,
+            &project,
+            &params,
+            &package_metadata,
+        )));
+    }
     let args = build_create_contract_args(&params);
+    let output = match &dir {
+        Some(dir) => executor.execute_in_dir(dir, &args).await,
+        None => executor.execute(&args).await,
+    };
 
-    match executor.execute(&args) {
+    match output {
         Ok(_) => {
             let message = if params.with_frontend == Some(true) {
                 format!(
-                    "Successfully created contract with typink frontend: {}",
-                    params.name
+                    "Successfully created contract with typink frontend: {} at {}",
+                    params.name,
+                    project.display()
                 )
             } else {
-                format!("Successfully created contract: {}", params.name)
+                format!(
+                    "Successfully created contract: {} at {}",
+                    params.name,
+                    project.display()
+                )
             };
-            Ok(success_result(message))
+            Ok(success_result(finish(
+                message,
+                &project,
+                &params,
+                &package_metadata,
+            )))
         }
         Err(e) => Ok(error_result(format!("Failed to create contract: {}", e))),
     }
 }
 
+/// `message` followed by notes on the ink! release pinned, the e2e tests scaffolded and
+/// the package metadata stamped into `project`.
+fn finish(
+    message: String,
+    project: &Path,
+    params: &CreateContractParams,
+    metadata: &PackageMetadata,
+) -> String {
+    let ink = params
+        .ink_version
+        .as_deref()
+        .and_then(|version| ink::release(version).ok())
+        .map(|release| ink::pin_scaffold(project, release));
+    let e2e = (params.with_e2e_tests == Some(true))
+        .then(|| e2e::scaffold_note(project, &params.template));
+    [
+        Some(message),
+        ink,
+        e2e,
+        metadata::stamp_scaffold(project, metadata),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
 fn validate_frontend_requirements() -> Result<(), String> {
     let node_major = node_major_version()?;
     if node_major < 20 {
@@ -167,174 +413,6 @@ fn has_npm() -> bool {
         .unwrap_or(false)
 }
 
-// Frontend creation temporarily disabled.
-/*
-#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
-pub struct CreateContractWithFrontendParams {
-    #[schemars(
-        description = "Name of the contract project (alphanumeric characters and underscores only)"
-    )]
-    pub name: String,
-    #[schemars(
-        description = "Template to use (standard, erc20, erc721, erc1155, dns, cross-contract-calls, multisig)"
-    )]
-    pub template: String,
-}
-
-impl CreateContractWithFrontendParams {
-    /// Validate the contract name
-    pub fn validate(&self) -> Result<(), String> {
-        if self.name.is_empty() {
-            return Err("Contract name cannot be empty".to_string());
-        }
-        if !self.name.chars().all(|c| c.is_alphanumeric() || c == '_') {
-            return Err(
-                "Contract names can only contain alphanumeric characters and underscores"
-                    .to_string(),
-            );
-        }
-        Ok(())
-    }
-}
-
-
-/// Build command arguments for create_contract_with_frontend
-pub fn build_create_contract_with_frontend_args(
-    params: &CreateContractWithFrontendParams,
-) -> Vec<String> {
-    vec![
-        "new".to_string(),
-        "contract".to_string(),
-        params.name.clone(),
-        "--template".to_string(),
-        params.template.clone(),
-        "--with-frontend=typink".to_string(),
-    ]
-}
-
-/// Execute create_contract_with_frontend tool
-pub async fn create_contract_with_frontend<E: CommandExecutor>(
-    executor: &E,
-    params: CreateContractWithFrontendParams,
-) -> PopMcpResult<CallToolResult> {
-    // Validate parameters
-    params
-        .validate()
-        .map_err(|e| crate::error::PopMcpError::InvalidInput(e))?;
-
-    let args = build_create_contract_with_frontend_args(&params);
-    let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-
-    let create_result = match executor.execute(&args_refs) {
-        Ok(output) => output,
-        Err(e) => {
-            return Ok(error_result(format!(
-                "Failed to create contract with frontend: {}",
-                e
-            )))
-        }
-    };
-
-    // Adapt frontend to contract template
-    let contract_path = &params.name;
-    let frontend_path = format!("{}/frontend", contract_path);
-
-    let adaptation_result =
-        adapt_frontend_to_contract(executor, &params.template, &frontend_path, &params.name).await;
-
-    match adaptation_result {
-        Ok(adaptation_msg) => Ok(success_result(format!(
-            "Successfully created contract with Dedot frontend: {}\n\n{}\n\n{}",
-            params.name, create_result, adaptation_msg
-        ))),
-        Err(e) => Ok(success_result(format!(
-            "Contract created: {}\n\n{}\n\nNote: Frontend adaptation encountered an issue: {}\n\
-            Please refer to the Dedot documentation resource (dedot://docs/full-guide) for manual adaptation instructions.",
-            params.name, create_result, e
-        ))),
-    }
-}
-
-/// Adapt frontend to contract template
-async fn adapt_frontend_to_contract<E: CommandExecutor>(
-    executor: &E,
-    template: &str,
-    frontend_path: &str,
-    contract_name: &str,
-) -> Result<String, String> {
-    // Fetch Dedot documentation
-    let _dedot_docs = match crate::resources::read_resource("dedot://docs/full-guide").await {
-        Ok(result) => {
-            if result.contents.first().is_some() {
-                "Available"
-            } else {
-                return Err("Failed to read Dedot documentation".to_string());
-            }
-        }
-        Err(e) => return Err(format!("Failed to fetch Dedot documentation: {}", e)),
-    };
-
-    // Build the contract first to generate metadata
-    let contract_base_path = frontend_path.replace("/frontend", "");
-    let metadata_path = format!("{}/target/ink/{}.json", contract_base_path, contract_name);
-
-    let build_args = vec!["build", "--path", &contract_base_path];
-    let _ = executor.execute(&build_args);
-
-    // Read the generated metadata
-    let metadata_content = match std::fs::read_to_string(&metadata_path) {
-        Ok(content) => content,
-        Err(_) => {
-            return Ok(format!(
-                "Frontend created with default flipper template.\n\
-                To adapt it to your {} contract:\n\
-                1. Build your contract first: `pop build --path {}`\n\
-                2. The contract metadata will be at: {}\n\
-                3. Use Dedot's typink to generate types: `npx dedot typink -m {} -o frontend/src/contracts`\n\
-                4. Update frontend/src/app/page.tsx to use your contract's methods\n\n\
-                Refer to Dedot documentation for detailed instructions on contract integration.",
-                template,
-                frontend_path.replace("/frontend", ""),
-                metadata_path,
-                metadata_path
-            ))
-        }
-    };
-
-    // Parse metadata to extract contract methods
-    let metadata: serde_json::Value = match serde_json::from_str(&metadata_content) {
-        Ok(v) => v,
-        Err(e) => return Err(format!("Failed to parse contract metadata: {}", e)),
-    };
-
-    let spec = metadata.get("spec").ok_or("No spec in metadata")?;
-    let messages = spec
-        .get("messages")
-        .and_then(|m| m.as_array())
-        .ok_or("No messages in spec")?;
-
-    let mut methods_list = String::new();
-    for msg in messages {
-        if let Some(label) = msg.get("label").and_then(|l| l.as_str()) {
-            methods_list.push_str(&format!("  - {}\n", label));
-        }
-    }
-
-    Ok(format!(
-        "Frontend adapted for {} template (contract: {})\n\n\
-        Contract methods detected:\n{}\n\
-        Generated TypeScript types using Dedot's typink\n\
-        Next steps:\n\
-        1. Navigate to frontend: `cd {}`\n\
-        2. Install dependencies: `npm install`\n\
-        3. Update src/app/page.tsx to use the contract methods above\n\
-        4. The contract types are available in src/contracts/\n\n\
-        Dedot documentation reference: https://docs.dedot.dev/smart-contracts",
-        template, contract_name, methods_list, frontend_path
-    ))
-}
-*/
-
 #[cfg(test)]
 #[allow(clippy::panic)]
 mod tests {
@@ -347,12 +425,41 @@ mod tests {
             let params = CreateContractParams {
                 name: (*name).to_owned(),
                 template: "standard".to_owned(),
+                template_url: None,
+                ink_version: None,
+                with_e2e_tests: None,
                 with_frontend: None,
+                cwd: None,
+                license: None,
+                repository: None,
+                authors: None,
             };
             assert!(params.validate().is_ok());
         }
     }
 
+    #[test]
+    fn validate_limits_e2e_tests_to_known_templates() {
+        let mut params = CreateContractParams {
+            name: "token".to_owned(),
+            template: "erc20".to_owned(),
+            template_url: None,
+            ink_version: None,
+            with_e2e_tests: Some(true),
+            with_frontend: None,
+            cwd: None,
+            license: None,
+            repository: None,
+            authors: None,
+        };
+        assert!(params.validate().is_ok());
+        params.template = "erc721".to_owned();
+        assert_eq!(
+            params.validate(),
+            Err("with_e2e_tests supports the standard and erc20 templates".to_owned())
+        );
+    }
+
     #[test]
     fn validate_rejects_invalid_names() {
         for name in [
@@ -361,7 +468,14 @@ mod tests {
             let params = CreateContractParams {
                 name: (*name).to_owned(),
                 template: "standard".to_owned(),
+                template_url: None,
+                ink_version: None,
+                with_e2e_tests: None,
                 with_frontend: None,
+                cwd: None,
+                license: None,
+                repository: None,
+                authors: None,
             };
             assert!(params.validate().is_err());
         }
@@ -372,7 +486,14 @@ mod tests {
         let params = CreateContractParams {
             name: "my_contract".to_owned(),
             template: "erc20".to_owned(),
+            template_url: None,
+            ink_version: None,
+            with_e2e_tests: None,
             with_frontend: None,
+            cwd: None,
+            license: None,
+            repository: None,
+            authors: None,
         };
         let args = build_create_contract_args(&params);
         assert_eq!(
@@ -386,7 +507,14 @@ mod tests {
         let params = CreateContractParams {
             name: "my_contract".to_owned(),
             template: "standard".to_owned(),
+            template_url: None,
+            ink_version: None,
+            with_e2e_tests: None,
             with_frontend: Some(true),
+            cwd: None,
+            license: None,
+            repository: None,
+            authors: None,
         };
         let args = build_create_contract_args(&params);
         assert_eq!(
@@ -409,7 +537,14 @@ mod tests {
         let params = CreateContractParams {
             name: "my_contract".to_owned(),
             template: "standard".to_owned(),
+            template_url: None,
+            ink_version: None,
+            with_e2e_tests: None,
             with_frontend: Some(false),
+            cwd: None,
+            license: None,
+            repository: None,
+            authors: None,
         };
         let args = build_create_contract_args(&params);
         assert_eq!(
@@ -419,8 +554,8 @@ mod tests {
     }
 
     #[test]
-    fn list_templates_includes_known_entries() {
-        let Ok(result) = list_templates(ListTemplatesParams {}) else {
+    fn list_contract_templates_includes_known_entries() {
+        let Ok(result) = list_contract_templates(ListTemplatesParams {}.into()) else {
             panic!("Expected Ok result");
         };
         assert!(!result.is_error.unwrap_or(true));
@@ -437,4 +572,69 @@ mod tests {
             assert!(text.contains(expected));
         }
     }
+
+    #[test]
+    fn template_urls_must_be_https_or_ssh() {
+        for url in [
+            "https://github.com/org/audited-template",
+            "ssh://git@github.com/org/audited-template.git",
+            "git@github.com:org/audited-template.git",
+        ] {
+            assert!(validate_template_url(url).is_ok());
+        }
+        for url in [
+            "http://github.com/org/template",
+            "file:///tmp/template",
+            "/tmp/template",
+            "--upload-pack=touch /tmp/x",
+            "https://github.com",
+            "github.com:org/template",
+        ] {
+            assert!(validate_template_url(url).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn checkouts_drop_history_and_rename_the_package() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let template = dir.path().join("template");
+        std::fs::create_dir(&template)?;
+        std::fs::write(
+            template.join("Cargo.toml"),
+            "[package]\nname = \"audited\"\nversion = \"0.1.0\"\n",
+        )?;
+        std::fs::write(template.join("lib.rs"), "#![no_std]\n")?;
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .arg("-C")
+                .arg(&template)
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .status()
+        };
+        git(&["init", "--quiet"])?;
+        git(&["add", "."])?;
+        git(&["commit", "--quiet", "-m", "template"])?;
+
+        let project = dir.path().join("my_token");
+        let url = template.display().to_string();
+        let commit = checkout_template(&url, &project, "my_token")
+            .await
+            .map_err(anyhow::Error::msg)?;
+        assert_eq!(commit.len(), 40);
+        assert!(!project.join(".git").exists());
+        let manifest = std::fs::read_to_string(project.join("Cargo.toml"))?;
+        assert!(manifest.contains("name = \"my_token\""));
+
+        let missing = dir.path().join("missing");
+        let failed = checkout_template(
+            &dir.path().join("nothing").display().to_string(),
+            &missing,
+            "missing",
+        )
+        .await;
+        assert!(failed.is_err_and(|e| e.contains("git clone")));
+        assert!(!missing.exists());
+        Ok(())
+    }
 }
@@ -0,0 +1,286 @@
+//! End-to-end test scaffolding for new contracts
+//!
+//! With `with_e2e_tests`, a freshly created flipper or ERC20 project gets an
+//! `e2e_tests` module exercising the template's messages, the `ink_e2e`
+//! dev-dependency and the `e2e-tests` feature gating the module, so test_contract
+//! with `e2e` runs them right away.
+
+use std::path::Path;
+
+use toml_edit::{table, value, Array, DocumentMut, InlineTable, Item, Value};
+
+use crate::tools::new::ink::major;
+
+/// Templates e2e tests can be scaffolded for.
+pub const E2E_TEMPLATES: &[&str] = &["standard", "erc20"];
+
+/// Tests of the flipper messages, placed inside the contract module.
+const FLIPPER_TESTS: &str = r#"
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink_e2e::ContractsBackend;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        #[ink_e2e::test]
+        async fn flip_toggles_the_value<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let mut constructor = {contract}Ref::new(false);
+            let contract = client
+                .instantiate("{package}", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<{contract}>();
+
+            let get = call_builder.get();
+            let value = client.call(&ink_e2e::bob(), &get).dry_run().await?;
+            assert!(!value.return_value());
+
+            let flip = call_builder.flip();
+            client
+                .call(&ink_e2e::bob(), &flip)
+                .submit()
+                .await
+                .expect("flip failed");
+
+            let get = call_builder.get();
+            let value = client.call(&ink_e2e::bob(), &get).dry_run().await?;
+            assert!(value.return_value());
+            Ok(())
+        }
+    }
+"#;
+
+/// Tests of the ERC20 messages, placed inside the contract module.
+const ERC20_TESTS: &str = r#"
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink_e2e::ContractsBackend;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        #[ink_e2e::test]
+        async fn transfer_moves_tokens<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let total_supply = {balance}::from(1_000_000u128);
+            let mut constructor = {contract}Ref::new(total_supply);
+            let contract = client
+                .instantiate("{package}", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<{contract}>();
+
+            let supply = call_builder.total_supply();
+            let supply = client.call(&ink_e2e::alice(), &supply).dry_run().await?;
+            assert_eq!(supply.return_value(), total_supply);
+
+            let bob = {bob};
+            let amount = {balance}::from(1_000u128);
+            let transfer = call_builder.transfer(bob, amount);
+            client
+                .call(&ink_e2e::alice(), &transfer)
+                .submit()
+                .await
+                .expect("transfer failed");
+
+            let balance = call_builder.balance_of(bob);
+            let balance = client.call(&ink_e2e::alice(), &balance).dry_run().await?;
+            assert_eq!(balance.return_value(), amount);
+            Ok(())
+        }
+    }
+"#;
+
+/// Name of the `#[ink(storage)]` struct in `lib`.
+fn storage_struct(lib: &str) -> Option<&str> {
+    let (_, rest) = lib.split_once("#[ink(storage)]")?;
+    let (_, rest) = rest.split_once("struct ")?;
+    let end = rest.find(|c: char| !c.is_alphanumeric() && c != '_')?;
+    Some(&rest[..end]).filter(|name| !name.is_empty())
+}
+
+/// `lib` with e2e tests for `template` added at the end of its contract module.
+fn add_tests(lib: &str, template: &str, package: &str, ink_major: &str) -> Result<String, String> {
+    let contract = storage_struct(lib).ok_or("no #[ink(storage)] struct in lib.rs")?;
+    let tests = match template {
+        "standard" => FLIPPER_TESTS,
+        "erc20" => ERC20_TESTS,
+        other => return Err(format!("no e2e tests for the {} template", other)),
+    };
+    let (balance, bob) = if ink_major == "5" {
+        (
+            "Balance",
+            "ink_e2e::account_id(ink_e2e::AccountKeyring::Bob)",
+        )
+    } else {
+        (
+            "U256",
+            "ink_e2e::address::<ink::env::DefaultEnvironment>(ink_e2e::Sr25519Keyring::Bob)",
+        )
+    };
+    let tests = tests
+        .replace("{contract}", contract)
+        .replace("{package}", package)
+        .replace("{balance}", balance)
+        .replace("{bob}", bob);
+    let end = lib
+        .trim_end()
+        .strip_suffix('}')
+        .ok_or("lib.rs does not end with the contract module")?;
+    Ok(format!(
+        "{}\n{}}}\n",
+        end.trim_end_matches([' ', '\n']),
+        tests.trim_end_matches(' ')
+    ))
+}
+
+/// Add the `ink_e2e` dev-dependency, following the `ink` dependency, and the
+/// `e2e-tests` feature to `doc`. Returns the package name and the ink! major version.
+fn wire_manifest(doc: &mut DocumentMut) -> Result<(String, String), String> {
+    let package = doc
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(Item::as_str)
+        .ok_or("Cargo.toml has no package name")?
+        .to_owned();
+    let ink = doc
+        .get("dependencies")
+        .and_then(|deps| deps.get("ink"))
+        .ok_or("Cargo.toml has no ink dependency")?;
+    let mut e2e = InlineTable::new();
+    let version = match ink.as_str() {
+        Some(version) => {
+            e2e.insert("version", version.into());
+            version.to_owned()
+        }
+        None => {
+            let dep = ink
+                .as_table_like()
+                .ok_or("the ink dependency is not a table")?;
+            for key in ["version", "git", "branch", "tag", "rev"] {
+                if let Some(v) = dep.get(key).and_then(Item::as_value) {
+                    e2e.insert(key, v.clone());
+                }
+            }
+            dep.get("version")
+                .and_then(Item::as_str)
+                .unwrap_or_default()
+                .to_owned()
+        }
+    };
+    if doc.get("dev-dependencies").is_none() {
+        doc.insert("dev-dependencies", table());
+    }
+    if let Some(deps) = doc
+        .get_mut("dev-dependencies")
+        .and_then(Item::as_table_like_mut)
+    {
+        if !deps.contains_key("ink_e2e") {
+            e2e.fmt();
+            deps.insert("ink_e2e", Item::Value(Value::InlineTable(e2e)));
+        }
+    }
+    if doc.get("features").is_none() {
+        doc.insert("features", table());
+    }
+    if let Some(features) = doc.get_mut("features").and_then(Item::as_table_like_mut) {
+        if !features.contains_key("e2e-tests") {
+            features.insert("e2e-tests", value(Array::new()));
+        }
+    }
+    Ok((package, major(&version).to_owned()))
+}
+
+/// Scaffold e2e tests for `template` in the project at `dir`, returning whether the
+/// tests were written (rather than already present).
+pub(crate) fn scaffold(dir: &Path, template: &str) -> Result<bool, String> {
+    let manifest = dir.join("Cargo.toml");
+    let lib_path = dir.join("lib.rs");
+    let text = std::fs::read_to_string(&manifest)
+        .map_err(|e| format!("Failed to read {}: {}", manifest.display(), e))?;
+    let mut doc: DocumentMut = text
+        .parse()
+        .map_err(|e| format!("{} is invalid: {}", manifest.display(), e))?;
+    let lib = std::fs::read_to_string(&lib_path)
+        .map_err(|e| format!("Failed to read {}: {}", lib_path.display(), e))?;
+    let (package, ink_major) = wire_manifest(&mut doc)?;
+    let written = !lib.contains("mod e2e_tests");
+    if written {
+        let lib = add_tests(&lib, template, &package, &ink_major)?;
+        std::fs::write(&lib_path, lib)
+            .map_err(|e| format!("Failed to write {}: {}", lib_path.display(), e))?;
+    }
+    std::fs::write(&manifest, doc.to_string())
+        .map_err(|e| format!("Failed to write {}: {}", manifest.display(), e))?;
+    Ok(written)
+}
+
+/// Scaffold e2e tests in a freshly created project at `dir`, returning a note for the
+/// tool result.
+pub(crate) fn scaffold_note(dir: &Path, template: &str) -> String {
+    let run = "run them with test_contract (e2e true)";
+    match scaffold(dir, template) {
+        Ok(true) => format!(
+            "E2E tests added to lib.rs (mod e2e_tests) with the ink_e2e dev-dependency and the e2e-tests feature; {}",
+            run
+        ),
+        Ok(false) => format!(
+            "E2E tests: the template already has an e2e_tests module; the ink_e2e dev-dependency and the e2e-tests feature are set; {}",
+            run
+        ),
+        Err(e) => format!("E2E tests not added: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "[package]\nname = \"token\"\nversion = \"0.1.0\"\n\n[dependencies]\n\
+                            ink = { version = \"5.1.1\", default-features = false }\n";
+    const LIB: &str = "#![cfg_attr(not(feature = \"std\"), no_std, no_main)]\n\n\
+                       #[ink::contract]\nmod token {\n    #[ink(storage)]\n    pub struct Token {\n        \
+                       total_supply: Balance,\n    }\n}\n";
+
+    #[test]
+    fn erc20_tests_are_added_inside_the_contract_module() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("Cargo.toml"), MANIFEST)?;
+        std::fs::write(dir.path().join("lib.rs"), LIB)?;
+        let note = scaffold_note(dir.path(), "erc20");
+        assert!(note.starts_with("E2E tests added"));
+
+        let lib = std::fs::read_to_string(dir.path().join("lib.rs"))?;
+        assert!(lib.contains("let mut constructor = TokenRef::new(total_supply);"));
+        assert!(lib.contains(".instantiate(\"token\", &ink_e2e::alice(), &mut constructor)"));
+        assert!(lib.contains("ink_e2e::account_id(ink_e2e::AccountKeyring::Bob)"));
+        assert!(lib.ends_with("        }\n    }\n}\n"));
+        let manifest = std::fs::read_to_string(dir.path().join("Cargo.toml"))?;
+        assert!(manifest.contains("[dev-dependencies]\nink_e2e = { version = \"5.1.1\" }"));
+        assert!(manifest.contains("[features]\ne2e-tests = []"));
+
+        // Scaffolding again changes nothing.
+        assert_eq!(scaffold(dir.path(), "erc20"), Ok(false));
+        assert_eq!(std::fs::read_to_string(dir.path().join("lib.rs"))?, lib);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("Cargo.toml"))?,
+            manifest
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn flipper_tests_follow_the_ink_release() {
+        let lib = LIB.replace("Token", "Flipper");
+        let tests = add_tests(&lib, "standard", "flipper", "6");
+        assert!(tests
+            .is_ok_and(|lib| lib.contains("call_builder::<Flipper>()")
+                && lib.contains("call_builder.flip()")));
+        assert_eq!(
+            add_tests("mod empty {}\n", "standard", "flipper", "6"),
+            Err("no #[ink(storage)] struct in lib.rs".to_owned())
+        );
+    }
+}
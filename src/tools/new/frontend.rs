@@ -0,0 +1,361 @@
+//! Frontend-assisted contract creation (MCP sampling)
+//!
+//! `create_contract_with_frontend` scaffolds a contract with the typink frontend,
+//! builds it, and asks the client's model (MCP sampling) to adapt `frontend/src` to
+//! the contract's constructors and messages, following the Dedot guide. The files the
+//! model returns are written to disk. Clients without sampling support are pointed at
+//! `create_contract` with `with_frontend` and the guide instead.
+
+use std::path::{Component, Path};
+use std::time::Duration;
+
+use rmcp::model::{
+    CallToolResult, Content, CreateMessageRequestParam, RawContent, Role, SamplingMessage,
+};
+use rmcp::{Peer, RoleServer};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::resources::{DEDOT_CONTENT, DEDOT_URI};
+use crate::tools::common::{error_result, resolve_cwd, with_note};
+use crate::tools::new::contract::{create_contract, CreateContractParams};
+use crate::tools::new::project_path;
+
+/// How long the client's model has to answer.
+const SAMPLING_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Most tokens the model may generate.
+const MAX_TOKENS: u32 = 16_000;
+
+/// Most bytes of frontend sources sent to the model.
+const MAX_SOURCE_BYTES: usize = 96 * 1024;
+
+/// Extensions of the frontend sources sent to and accepted from the model.
+const SOURCE_EXTENSIONS: &[&str] = &["ts", "tsx", "css"];
+
+/// Parameters for the create_contract_with_frontend tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct CreateContractWithFrontendParams {
+    /// Name of the contract project.
+    #[schemars(
+        description = "Name of the contract project (alphanumeric characters and underscores only)"
+    )]
+    pub name: String,
+    /// Template to use for the contract.
+    #[schemars(
+        description = "Template to use (standard, erc20, erc721, erc1155, dns, cross-contract-calls, multisig)"
+    )]
+    pub template: String,
+    /// Directory to create the project in.
+    #[schemars(
+        description = "Directory to create the project in (default: the client's first workspace root, else the server's working directory)"
+    )]
+    pub cwd: Option<String>,
+}
+
+/// A frontend file written by the model.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct FrontendFile {
+    /// Path relative to the frontend directory, under `src/`.
+    pub path: String,
+    /// Full new contents.
+    pub content: String,
+}
+
+/// Reply the model is asked for.
+#[derive(Debug, Deserialize)]
+struct Reply {
+    files: Vec<FrontendFile>,
+}
+
+/// Whether `path` (relative to the frontend) is a source file the model may write.
+fn writable(path: &str) -> bool {
+    let path = Path::new(path);
+    let mut components = path.components();
+    components.next() == Some(Component::Normal("src".as_ref()))
+        && components.all(|c| matches!(c, Component::Normal(_)))
+        && !path.starts_with("src/contracts/types")
+        && path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| SOURCE_EXTENSIONS.contains(&e))
+}
+
+/// Sources under `frontend/src` the model may adapt, as `(relative path, contents)`,
+/// in path order, up to [`MAX_SOURCE_BYTES`]. Generated bindings are left out.
+pub(crate) fn sources(frontend: &Path) -> Vec<(String, String)> {
+    let mut files = Vec::new();
+    let mut dirs = vec![frontend.join("src")];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            if path.is_dir() {
+                dirs.push(path);
+                continue;
+            }
+            let Some(relative) = path
+                .strip_prefix(frontend)
+                .ok()
+                .and_then(Path::to_str)
+                .map(|p| p.replace('\\', "/"))
+            else {
+                continue;
+            };
+            if writable(&relative) {
+                files.push(relative);
+            }
+        }
+    }
+    files.sort();
+    let mut total = 0;
+    files
+        .into_iter()
+        .filter_map(|relative| {
+            let text = std::fs::read_to_string(frontend.join(&relative)).ok()?;
+            total += text.len();
+            (total <= MAX_SOURCE_BYTES).then_some((relative, text))
+        })
+        .collect()
+}
+
+/// Sampling request asking the model to adapt `sources` to contract `name`, whose
+/// metadata `spec` lists its constructors and messages.
+pub(crate) fn sampling_request(
+    name: &str,
+    spec: &Value,
+    sources: &[(String, String)],
+) -> CreateMessageRequestParam {
+    let mut prompt = format!(
+        "Adapt the typink frontend of the ink! contract `{}` so its UI uses this contract's \
+         constructors and messages instead of the template's defaults.\n\n\
+         Contract metadata spec (from target/ink/{}.json):\n```json\n{}\n```\n\n\
+         Current frontend sources:\n",
+        name,
+        name,
+        serde_json::to_string(spec).unwrap_or_default()
+    );
+    for (path, text) in sources {
+        prompt.push_str(&format!("\n--- {} ---\n{}\n", path, text));
+    }
+    prompt.push_str(&format!(
+        "\nDedot/Typink reference ({}):\n\n{}",
+        DEDOT_URI, DEDOT_CONTENT
+    ));
+    CreateMessageRequestParam {
+        messages: vec![SamplingMessage {
+            role: Role::User,
+            content: Content::text(prompt),
+        }],
+        model_preferences: None,
+        system_prompt: Some(
+            "You adapt Dedot/Typink React frontends to ink! contracts. Reply with only a JSON \
+             object {\"files\": [{\"path\": \"src/...\", \"content\": \"...\"}]} holding the \
+             full new contents of each file to create or change under src/ (TypeScript, TSX or \
+             CSS). Do not touch src/contracts/types: the bindings are generated with \
+             `npx dedot typink`. No prose, no markdown fences."
+                .to_owned(),
+        ),
+        include_context: None,
+        temperature: Some(0.2),
+        max_tokens: MAX_TOKENS,
+        stop_sequences: None,
+        metadata: None,
+    }
+}
+
+/// Files in the model's reply, which may be wrapped in prose or a code fence.
+pub(crate) fn parse_files(reply: &str) -> Result<Vec<FrontendFile>, String> {
+    let start = reply.find('{').ok_or("the reply holds no JSON object")?;
+    let end = reply.rfind('}').ok_or("the reply holds no JSON object")?;
+    let reply: Reply = serde_json::from_str(reply.get(start..=end).unwrap_or_default())
+        .map_err(|e| format!("the reply is not a files object: {}", e))?;
+    if let Some(file) = reply.files.iter().find(|file| !writable(&file.path)) {
+        return Err(format!(
+            "refusing to write {}: only {} files under src/ (outside src/contracts/types) may be changed",
+            file.path,
+            SOURCE_EXTENSIONS.join("/")
+        ));
+    }
+    if reply.files.is_empty() {
+        return Err("the reply changes no files".to_owned());
+    }
+    Ok(reply.files)
+}
+
+/// Write `files` under `frontend`, returning their paths.
+pub(crate) fn write_files(frontend: &Path, files: &[FrontendFile]) -> Result<Vec<String>, String> {
+    let mut written = Vec::new();
+    for file in files {
+        let path = frontend.join(&file.path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::write(&path, &file.content)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        written.push(file.path.clone());
+    }
+    Ok(written)
+}
+
+/// Build the contract at `project`, ask the client's model to adapt its frontend and
+/// write the result, returning a summary of the changes.
+async fn adapt<E: CommandExecutor>(
+    executor: &E,
+    peer: &Peer<RoleServer>,
+    project: &Path,
+    name: &str,
+) -> Result<String, String> {
+    let path = project.display().to_string();
+    executor
+        .execute(&["build", "--path", &path])
+        .await
+        .map_err(|e| format!("the contract did not build: {}", e))?;
+    let metadata = project.join("target/ink").join(format!("{}.json", name));
+    let metadata: Value = std::fs::read_to_string(&metadata)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .ok_or_else(|| format!("no contract metadata at {}", metadata.display()))?;
+    let spec = metadata.get("spec").ok_or("the metadata has no spec")?;
+    let frontend = project.join("frontend");
+    let request = sampling_request(name, spec, &sources(&frontend));
+    let result = tokio::time::timeout(SAMPLING_TIMEOUT, peer.create_message(request))
+        .await
+        .map_err(|_| "the client's model did not answer in time".to_owned())?
+        .map_err(|e| format!("sampling failed: {}", e))?;
+    let RawContent::Text(reply) = &result.message.content.raw else {
+        return Err("the client's model did not reply with text".to_owned());
+    };
+    let written = write_files(&frontend, &parse_files(&reply.text)?)?;
+    Ok(format!(
+        "Frontend adapted by {}: {}\nNext: cd {} && npm install && npx dedot typink -m ../target/ink/{}.json -o ./src/contracts/types",
+        result.model,
+        written.join(", "),
+        frontend.display(),
+        name
+    ))
+}
+
+/// Execute create_contract_with_frontend tool
+pub async fn create_contract_with_frontend<E: CommandExecutor>(
+    executor: &E,
+    peer: &Peer<RoleServer>,
+    params: CreateContractWithFrontendParams,
+) -> PopMcpResult<CallToolResult> {
+    let sampling = peer
+        .peer_info()
+        .is_some_and(|info| info.capabilities.sampling.is_some());
+    if !sampling {
+        return Ok(error_result(format!(
+            "create_contract_with_frontend needs a client that supports MCP sampling. \
+             Use create_contract with with_frontend instead and adapt frontend/src following {}.",
+            DEDOT_URI
+        )));
+    }
+    let dir = resolve_cwd(params.cwd.as_deref()).map_err(PopMcpError::InvalidInput)?;
+    let project = project_path(executor, dir.as_deref(), &params.name)?;
+    let created = create_contract(
+        executor,
+        CreateContractParams {
+            name: params.name.clone(),
+            template: params.template,
+            template_url: None,
+            ink_version: None,
+            with_e2e_tests: None,
+            with_frontend: Some(true),
+            cwd: params.cwd,
+            license: None,
+            repository: None,
+            authors: None,
+        },
+    )
+    .await?;
+    if created.is_error == Some(true) {
+        return Ok(created);
+    }
+    let note = match adapt(executor, peer, &project, &params.name).await {
+        Ok(summary) => summary,
+        Err(e) => format!(
+            "Frontend not adapted: {}. Adapt frontend/src by hand following {}.",
+            e, DEDOT_URI
+        ),
+    };
+    Ok(with_note(created, Some(note)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources_skip_generated_bindings() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let frontend = dir.path();
+        std::fs::create_dir_all(frontend.join("src/components"))?;
+        std::fs::create_dir_all(frontend.join("src/contracts/types/flipper"))?;
+        std::fs::write(frontend.join("src/components/Flipper.tsx"), "flip")?;
+        std::fs::write(frontend.join("src/contracts/deployments.ts"), "deploy")?;
+        std::fs::write(frontend.join("src/contracts/types/flipper/index.ts"), "gen")?;
+        std::fs::write(frontend.join("src/logo.svg"), "<svg/>")?;
+        let found = sources(frontend);
+        let paths: Vec<&str> = found.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["src/components/Flipper.tsx", "src/contracts/deployments.ts"]
+        );
+
+        let request = sampling_request("flipper", &serde_json::json!({"messages": []}), &found);
+        let RawContent::Text(prompt) = &request.messages[0].content.raw else {
+            anyhow::bail!("prompt is not text");
+        };
+        assert!(prompt
+            .text
+            .contains("--- src/components/Flipper.tsx ---\nflip"));
+        assert!(prompt.text.contains("useContractQuery"));
+        Ok(())
+    }
+
+    #[test]
+    fn replies_may_only_change_sources() -> anyhow::Result<()> {
+        let reply =
+            "```json\n{\"files\": [{\"path\": \"src/app/page.tsx\", \"content\": \"page\"}]}\n```";
+        let files = parse_files(reply).map_err(anyhow::Error::msg)?;
+        let dir = tempfile::tempdir()?;
+        assert_eq!(
+            write_files(dir.path(), &files),
+            Ok(vec!["src/app/page.tsx".to_owned()])
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("src/app/page.tsx"))?,
+            "page"
+        );
+
+        for path in [
+            "../lib.rs",
+            "src/../../lib.rs",
+            "/etc/passwd",
+            "package.json",
+            "src/contracts/types/flipper/index.ts",
+        ] {
+            let reply = format!(
+                "{{\"files\": [{{\"path\": \"{}\", \"content\": \"x\"}}]}}",
+                path
+            );
+            assert!(
+                parse_files(&reply).is_err_and(|e| e.starts_with("refusing to write")),
+                "{}",
+                path
+            );
+        }
+        assert!(parse_files("no changes needed").is_err());
+        assert!(parse_files("{\"files\": []}").is_err());
+        Ok(())
+    }
+}
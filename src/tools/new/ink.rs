@@ -0,0 +1,193 @@
+//! ink! version selection for new contracts
+//!
+//! Pop CLI templates track the latest ink! release. Projects for chains that still run
+//! an older contracts pallet pin another release by rewriting the `ink` and `ink_*`
+//! dependencies of the scaffolded manifests.
+
+use std::path::{Path, PathBuf};
+
+use toml_edit::{value, DocumentMut, Item, TableLike};
+
+use crate::tools::metadata::manifests;
+
+/// An ink! release contracts can be created for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InkRelease {
+    /// Name of the release in the `ink_version` parameter.
+    pub name: &'static str,
+    /// Crate version the dependencies are pinned to.
+    pub version: &'static str,
+    /// Chains the release targets.
+    pub targets: &'static str,
+}
+
+/// Releases `ink_version` accepts.
+pub const INK_RELEASES: &[InkRelease] = &[
+    InkRelease {
+        name: "v5",
+        version: "5.1.1",
+        targets: "pallet-contracts (Wasm) chains; build with cargo-contract 5",
+    },
+    InkRelease {
+        name: "v6",
+        version: "6.0.0-beta",
+        targets: "pallet-revive (PolkaVM) chains",
+    },
+];
+
+/// The release named `name` (`v5`, `5`, `V6`, ...).
+pub(crate) fn release(name: &str) -> Result<&'static InkRelease, String> {
+    let lower = name.trim().to_ascii_lowercase();
+    let number = lower.strip_prefix('v').unwrap_or(&lower);
+    INK_RELEASES
+        .iter()
+        .find(|release| release.name.trim_start_matches('v') == number)
+        .ok_or_else(|| {
+            let names: Vec<&str> = INK_RELEASES.iter().map(|release| release.name).collect();
+            format!(
+                "Unsupported ink_version '{}'. Supported: {}",
+                name.trim(),
+                names.join(", ")
+            )
+        })
+}
+
+/// Major version of a version requirement such as `6.0.0-beta` or `^5.1`.
+pub(crate) fn major(version: &str) -> &str {
+    version
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .split('.')
+        .next()
+        .unwrap_or_default()
+}
+
+/// Pin the ink! dependencies in `deps` to `release`, returning whether any changed.
+///
+/// Dependencies already on the release's major version keep their exact version, git
+/// dependencies become registry ones, and path or workspace-inherited ones are left
+/// alone.
+fn pin_table(deps: &mut dyn TableLike, release: &InkRelease) -> bool {
+    let wanted = major(release.version);
+    let mut changed = false;
+    for (name, item) in deps.iter_mut() {
+        if name.get() != "ink" && !name.get().starts_with("ink_") {
+            continue;
+        }
+        if let Some(version) = item.as_str() {
+            if major(version) != wanted {
+                *item = value(release.version);
+                changed = true;
+            }
+            continue;
+        }
+        let Some(dep) = item.as_table_like_mut() else {
+            continue;
+        };
+        if dep.contains_key("path") || dep.contains_key("workspace") {
+            continue;
+        }
+        let current = dep.get("version").and_then(Item::as_str).map(major);
+        if current == Some(wanted) && !dep.contains_key("git") {
+            continue;
+        }
+        for key in ["git", "branch", "tag", "rev"] {
+            dep.remove(key);
+        }
+        dep.insert("version", value(release.version));
+        changed = true;
+    }
+    changed
+}
+
+/// Pin the ink! dependencies of every manifest under `dir` to `release`, returning the
+/// manifests that changed.
+pub(crate) fn pin(dir: &Path, release: &InkRelease) -> Result<Vec<PathBuf>, String> {
+    let mut pinned = Vec::new();
+    for manifest in manifests(dir) {
+        let text = std::fs::read_to_string(&manifest)
+            .map_err(|e| format!("Failed to read {}: {}", manifest.display(), e))?;
+        let mut doc: DocumentMut = text
+            .parse()
+            .map_err(|e| format!("{} is invalid: {}", manifest.display(), e))?;
+        let mut changed = false;
+        for key in ["dependencies", "dev-dependencies"] {
+            if let Some(deps) = doc.get_mut(key).and_then(Item::as_table_like_mut) {
+                changed |= pin_table(deps, release);
+            }
+        }
+        if let Some(deps) = doc
+            .get_mut("workspace")
+            .and_then(Item::as_table_mut)
+            .and_then(|workspace| workspace.get_mut("dependencies"))
+            .and_then(Item::as_table_like_mut)
+        {
+            changed |= pin_table(deps, release);
+        }
+        if changed {
+            std::fs::write(&manifest, doc.to_string())
+                .map_err(|e| format!("Failed to write {}: {}", manifest.display(), e))?;
+            pinned.push(manifest);
+        }
+    }
+    Ok(pinned)
+}
+
+/// Pin a freshly scaffolded project at `dir`, returning a note for the tool result.
+pub(crate) fn pin_scaffold(dir: &Path, release: &InkRelease) -> String {
+    match pin(dir, release) {
+        Ok(pinned) if pinned.is_empty() => format!(
+            "ink! {} ({}): the template already uses it",
+            release.name, release.version
+        ),
+        Ok(pinned) => {
+            let files: Vec<String> = pinned
+                .iter()
+                .map(|path| path.strip_prefix(dir).unwrap_or(path).display().to_string())
+                .collect();
+            format!(
+                "ink! pinned to {} ({}, for {}) in {}. Template code written for another release may need migrating.",
+                release.version,
+                release.name,
+                release.targets,
+                files.join(", ")
+            )
+        }
+        Err(e) => format!("ink! version not set: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn releases_accept_common_spellings() {
+        assert_eq!(release("v5").map(|r| r.version), Ok("5.1.1"));
+        assert_eq!(release(" V6 ").map(|r| r.name), Ok("v6"));
+        assert_eq!(release("5").map(|r| r.name), Ok("v5"));
+        assert!(release("v4").is_err_and(|e| e.contains("Supported: v5, v6")));
+    }
+
+    #[test]
+    fn pinning_rewrites_ink_dependencies_only() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"flipper\"\n\n[dependencies]\n\
+             ink = { version = \"6.0.0-beta\", default-features = false }\n\
+             scale = { package = \"parity-scale-codec\", version = \"3\" }\n\n\
+             [dev-dependencies]\n\
+             ink_e2e = { git = \"https://github.com/use-ink/ink\", branch = \"master\" }\n",
+        )?;
+        let v5 = release("v5").map_err(anyhow::Error::msg)?;
+        let note = pin_scaffold(dir.path(), v5);
+        assert!(note.starts_with("ink! pinned to 5.1.1 (v5"));
+        let manifest = std::fs::read_to_string(dir.path().join("Cargo.toml"))?;
+        assert!(manifest.contains("ink = { version = \"5.1.1\", default-features = false }"));
+        assert!(manifest.contains("ink_e2e = { version = \"5.1.1\" }"));
+        assert!(manifest.contains("version = \"3\""));
+
+        assert_eq!(pin(dir.path(), v5).map(|pinned| pinned.len()), Ok(0));
+        Ok(())
+    }
+}
@@ -1,7 +1,37 @@
 //! Creation tools (pop new)
 
+use std::path::{Path, PathBuf};
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+
 pub mod chain;
 pub mod contract;
+pub mod e2e;
+pub mod frontend;
+pub mod ink;
 
 pub use chain::*;
 pub use contract::*;
+pub use frontend::*;
+
+/// Absolute path of project `name` created in `dir`, else in the executor's (or the
+/// server's) working directory, refusing paths that already exist.
+pub(crate) fn project_path<E: CommandExecutor>(
+    executor: &E,
+    dir: Option<&Path>,
+    name: &str,
+) -> PopMcpResult<PathBuf> {
+    let base = dir.or_else(|| executor.working_dir());
+    let relative = base.map_or_else(|| PathBuf::from(name), |base| base.join(name));
+    let project = std::path::absolute(&relative).map_err(|e| {
+        PopMcpError::Internal(format!("Failed to resolve {}: {}", relative.display(), e))
+    })?;
+    if project.exists() {
+        return Err(PopMcpError::InvalidInput(format!(
+            "{} already exists: choose another name or cwd",
+            project.display()
+        )));
+    }
+    Ok(project)
+}
@@ -0,0 +1,343 @@
+//! Listing, selecting, stopping and reading the logs of nodes launched with up_ink_node
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::nodes::{NodeRegistry, RegisteredNode};
+use crate::tools::clean::stop_pids;
+
+use super::common::{error_result, success_result};
+
+/// Parameters for the list_nodes tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+#[allow(clippy::empty_structs_with_brackets)]
+pub struct ListNodesParams {}
+
+/// Parameters for the select_node tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct SelectNodeParams {
+    /// Name of the node.
+    #[schemars(description = "Name of the node, as listed by list_nodes (e.g., 'node-9944')")]
+    pub name: String,
+}
+
+/// Parameters for the stop_node tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct StopNodeParams {
+    /// Name of the node.
+    #[schemars(description = "Name of the node, as listed by list_nodes (e.g., 'node-9944')")]
+    pub name: String,
+}
+
+/// Lines returned by get_node_logs by default.
+const DEFAULT_LOG_LINES: usize = 100;
+
+/// Most lines returned by get_node_logs.
+const MAX_LOG_LINES: usize = 2000;
+
+/// Parameters for the get_node_logs tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct GetNodeLogsParams {
+    /// Name of the node (default: the selected node).
+    #[schemars(
+        description = "Name of the node, as listed by list_nodes (default: the selected node)"
+    )]
+    pub name: Option<String>,
+    /// Read the Ethereum RPC node's log instead of the chain node's.
+    #[schemars(description = "Read the Ethereum RPC (eth-rpc) log instead of the node's")]
+    pub eth_rpc: Option<bool>,
+    /// Number of lines to return, from the end.
+    #[schemars(
+        description = "Number of lines to return from the end of the log (default 100, at most 2000)"
+    )]
+    pub tail: Option<usize>,
+    /// Only keep lines containing this text.
+    #[schemars(
+        description = "Only keep lines containing this text, case-insensitively (e.g., 'error', 'panicked')"
+    )]
+    pub grep: Option<String>,
+}
+
+/// One line describing `node`.
+fn describe(node: &RegisteredNode, selected: bool) -> String {
+    let pids = node
+        .pids
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "{}{}: {} (pids: {}, launched at {})",
+        if selected { "* " } else { "  " },
+        node.name,
+        node.url,
+        if pids.is_empty() { "unknown" } else { &pids },
+        node.launched_at
+    )
+}
+
+/// Error for a name that is not registered.
+fn unknown_node(registry: &NodeRegistry, name: &str) -> CallToolResult {
+    let names: Vec<String> = registry.list().into_iter().map(|node| node.name).collect();
+    if names.is_empty() {
+        return error_result(format!(
+            "No node named '{}': no nodes have been launched",
+            name
+        ));
+    }
+    error_result(format!(
+        "No node named '{}'. Launched nodes: {}",
+        name,
+        names.join(", ")
+    ))
+}
+
+/// Execute list_nodes tool
+pub async fn list_nodes(
+    registry: &NodeRegistry,
+    _params: ListNodesParams,
+) -> PopMcpResult<CallToolResult> {
+    let nodes = registry.list();
+    if nodes.is_empty() {
+        return Ok(success_result(
+            "No nodes launched yet. Use up_ink_node to start one.",
+        ));
+    }
+    let selected = registry.selected().map(|node| node.name);
+    let lines: Vec<String> = nodes
+        .iter()
+        .map(|node| describe(node, selected.as_deref() == Some(node.name.as_str())))
+        .collect();
+    Ok(success_result(format!(
+        "Launched nodes (* = selected, used when a tool's url is omitted; launch times in Unix seconds):\n{}",
+        lines.join("\n")
+    )))
+}
+
+/// Execute select_node tool
+pub async fn select_node(
+    registry: &NodeRegistry,
+    params: SelectNodeParams,
+) -> PopMcpResult<CallToolResult> {
+    Ok(match registry.select(params.name.trim()) {
+        Some(node) => success_result(format!(
+            "Selected {} ({}); tools without a url now use it.",
+            node.name, node.url
+        )),
+        None => unknown_node(registry, &params.name),
+    })
+}
+
+/// Execute stop_node tool
+pub async fn stop_node<E: CommandExecutor>(
+    executor: &E,
+    registry: &NodeRegistry,
+    params: StopNodeParams,
+) -> PopMcpResult<CallToolResult> {
+    let name = params.name.trim();
+    let Some(node) = registry.list().into_iter().find(|node| node.name == name) else {
+        return Ok(unknown_node(registry, name));
+    };
+    if node.pids.is_empty() {
+        return Ok(error_result(format!(
+            "Pids of {} are unknown; stop it with clean_nodes",
+            node.name
+        )));
+    }
+    let result = stop_pids(executor, &node.pids).await?;
+    if result.is_error == Some(true) {
+        return Ok(result);
+    }
+    registry.remove(&node.name);
+    let selected = match registry.selected() {
+        Some(next) => format!("{} ({}) is now selected.", next.name, next.url),
+        None => "No nodes remain.".to_owned(),
+    };
+    Ok(success_result(format!(
+        "Stopped {} ({}). {}",
+        node.name, node.url, selected
+    )))
+}
+
+/// The last `tail` lines of `log` containing `grep` (case-insensitive).
+fn filter_log<'a>(log: &'a str, grep: Option<&str>, tail: usize) -> Vec<&'a str> {
+    let grep = grep.map(str::to_lowercase).filter(|grep| !grep.is_empty());
+    let lines: Vec<&str> = log
+        .lines()
+        .filter(|line| {
+            grep.as_ref()
+                .is_none_or(|grep| line.to_lowercase().contains(grep))
+        })
+        .collect();
+    lines[lines.len().saturating_sub(tail)..].to_vec()
+}
+
+/// Execute get_node_logs tool
+pub async fn get_node_logs(
+    registry: &NodeRegistry,
+    params: GetNodeLogsParams,
+) -> PopMcpResult<CallToolResult> {
+    let tail = params.tail.unwrap_or(DEFAULT_LOG_LINES);
+    if tail == 0 || tail > MAX_LOG_LINES {
+        return Err(PopMcpError::InvalidInput(format!(
+            "'tail' must be between 1 and {}",
+            MAX_LOG_LINES
+        )));
+    }
+    let node = match params.name.as_deref().map(str::trim) {
+        Some(name) => match registry.list().into_iter().find(|node| node.name == name) {
+            Some(node) => node,
+            None => return Ok(unknown_node(registry, name)),
+        },
+        None => match registry.selected() {
+            Some(node) => node,
+            None => {
+                return Ok(error_result(
+                    "No nodes launched yet. Use up_ink_node to start one.",
+                ))
+            }
+        },
+    };
+    let eth_rpc = params.eth_rpc.unwrap_or(false);
+    let Some(path) = node.logs.get(usize::from(eth_rpc)) else {
+        return Ok(error_result(format!(
+            "Pop CLI reported no {} log for {}",
+            if eth_rpc { "eth-rpc" } else { "node" },
+            node.name
+        )));
+    };
+    let log = match tokio::fs::read(path).await {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(e) => {
+            return Ok(error_result(format!(
+                "Failed to read the log of {} ({}): {}",
+                node.name, path, e
+            )))
+        }
+    };
+
+    let lines = filter_log(&log, params.grep.as_deref(), tail);
+    let matching = params
+        .grep
+        .as_deref()
+        .map(|grep| format!(" matching '{}'", grep))
+        .unwrap_or_default();
+    if lines.is_empty() {
+        return Ok(success_result(format!(
+            "No lines{} in the log of {} ({})",
+            matching, node.name, path
+        )));
+    }
+    Ok(success_result(format!(
+        "Last {} line(s){} of the log of {} ({}):\n{}",
+        lines.len(),
+        matching,
+        node.name,
+        path,
+        lines.join("\n")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outputs::NodeInfo;
+    use crate::test_utils::MockExecutor;
+    use crate::tools::common::content_text;
+
+    #[tokio::test]
+    async fn stop_node_cleans_its_pids_and_forgets_it() -> PopMcpResult<()> {
+        let registry = NodeRegistry::new();
+        registry.register(
+            &NodeInfo {
+                url: "ws://localhost:9944".to_owned(),
+                pids: vec![11, 12],
+                logs: Vec::new(),
+                port: Some(9944),
+                eth_rpc_port: None,
+            },
+            None,
+        );
+        let executor = MockExecutor::new().on(&["clean", "node"], "Stopped");
+        let result = stop_node(
+            &executor,
+            &registry,
+            StopNodeParams {
+                name: "node-9944".to_owned(),
+            },
+        )
+        .await?;
+        assert!(content_text(&result).starts_with("Stopped node-9944"));
+        assert_eq!(
+            executor.calls()[0].args,
+            vec!["clean", "node", "--pid", "11", "12"]
+        );
+        assert!(registry.list().is_empty());
+
+        let unknown = stop_node(
+            &executor,
+            &registry,
+            StopNodeParams {
+                name: "node-9944".to_owned(),
+            },
+        )
+        .await?;
+        assert_eq!(unknown.is_error, Some(true));
+        Ok(())
+    }
+
+    #[test]
+    fn filter_log_keeps_the_last_matching_lines() {
+        let log = "start\nERROR one\nok\nerror two\nError three";
+        assert_eq!(
+            filter_log(log, Some("error"), 2),
+            vec!["error two", "Error three"]
+        );
+        assert_eq!(filter_log(log, None, 10).len(), 5);
+        assert_eq!(filter_log(log, Some(""), 1), vec!["Error three"]);
+    }
+
+    #[tokio::test]
+    async fn get_node_logs_reads_the_selected_node_log() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("node.log");
+        std::fs::write(&path, "Idle (0 peers)\nImported #1\nImported #2\n")?;
+        let registry = NodeRegistry::new();
+        registry.register(
+            &NodeInfo {
+                url: "ws://localhost:9944".to_owned(),
+                pids: vec![11],
+                logs: vec![path.display().to_string()],
+                port: Some(9944),
+                eth_rpc_port: None,
+            },
+            None,
+        );
+        let params = GetNodeLogsParams {
+            name: None,
+            eth_rpc: None,
+            tail: Some(1),
+            grep: Some("imported".to_owned()),
+        };
+        let result = get_node_logs(&registry, params.clone()).await?;
+        assert!(content_text(&result).ends_with(":\nImported #2"));
+
+        let eth_rpc = get_node_logs(
+            &registry,
+            GetNodeLogsParams {
+                eth_rpc: Some(true),
+                ..params
+            },
+        )
+        .await?;
+        assert_eq!(eth_rpc.is_error, Some(true));
+        Ok(())
+    }
+}
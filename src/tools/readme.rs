@@ -0,0 +1,331 @@
+//! Project README generation (build, test, node and deployment guide)
+
+use std::path::{Path, PathBuf};
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::deployments::{same_url, Deployment, Registry};
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::toolchain::project_toolchain;
+use crate::tools::common::{error_result, success_result};
+
+/// Node URL used in the deploy command when nothing was deployed yet.
+const LOCAL_URL: &str = "ws://localhost:9944";
+
+/// Parameters for the generate_readme tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct GenerateReadmeParams {
+    /// Path to the contract or chain project directory.
+    #[schemars(description = "Path to the contract or chain project directory")]
+    pub path: String,
+    /// Only list deployments on this network.
+    #[schemars(
+        description = "Only list deployments on this network (WebSocket URL; default: every network)"
+    )]
+    pub url: Option<String>,
+    /// Replace an existing README.md.
+    #[schemars(description = "Replace an existing README.md (default: false)")]
+    pub overwrite: Option<bool>,
+}
+
+/// What a project builds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Contract,
+    Chain,
+}
+
+/// What the README describes.
+#[derive(Debug)]
+struct Project {
+    name: String,
+    kind: Kind,
+    toolchain: Option<String>,
+    /// Network configs (`[relaychain]` TOML files) in the project directory.
+    networks: Vec<String>,
+    deployments: Vec<Deployment>,
+}
+
+/// Read the project at `dir` from its `Cargo.toml`.
+fn inspect(dir: &Path, manifest: &str) -> PopMcpResult<(String, Kind)> {
+    let manifest: toml::Table = toml::from_str(manifest)
+        .map_err(|e| PopMcpError::InvalidInput(format!("Cargo.toml is invalid: {}", e)))?;
+    let name = manifest
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(toml::Value::as_str)
+        .map(str::to_owned)
+        .or_else(|| Some(dir.file_name()?.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "project".to_owned());
+    let is_contract = manifest
+        .get("dependencies")
+        .and_then(|deps| deps.get("ink"))
+        .is_some();
+    let kind = if is_contract {
+        Kind::Contract
+    } else {
+        Kind::Chain
+    };
+    Ok((name, kind))
+}
+
+/// Network configs in `dir`, by file name.
+fn network_configs(dir: &Path) -> Vec<String> {
+    let mut configs: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+                .filter(|path| {
+                    std::fs::read_to_string(path)
+                        .ok()
+                        .and_then(|text| toml::from_str::<toml::Table>(&text).ok())
+                        .is_some_and(|config| config.contains_key("relaychain"))
+                })
+                .filter_map(|path| Some(path.file_name()?.to_string_lossy().into_owned()))
+                .collect()
+        })
+        .unwrap_or_default();
+    configs.sort();
+    configs
+}
+
+/// Markdown README for `project`.
+fn render(project: &Project) -> String {
+    let mut readme = format!("# {}\n\n", project.name);
+    readme.push_str(match project.kind {
+        Kind::Contract => "An ink! smart contract built with [Pop CLI](https://github.com/r0gue-io/pop-cli). Run the commands below from this directory.\n\n",
+        Kind::Chain => "A Polkadot SDK chain built with [Pop CLI](https://github.com/r0gue-io/pop-cli). Run the commands below from this directory.\n\n",
+    });
+
+    readme.push_str("## Prerequisites\n\n- [Pop CLI](https://learn.onpop.io/v/cli/installing-pop-cli): `pop --version`\n");
+    if let Some(toolchain) = &project.toolchain {
+        readme.push_str(&format!(
+            "- Rust toolchain `{}`, pinned in `rust-toolchain.toml`: `rustup toolchain install {}`\n",
+            toolchain, toolchain
+        ));
+    }
+
+    readme.push_str("\n## Build\n\n```bash\npop build --path .\n");
+    readme.push_str(match project.kind {
+        Kind::Contract => "# Optimized build for deployment\npop build --path . --release\n```\n\nArtifacts (`.contract`, `.json`, `.polkavm`) are written to `target/ink/`.\n",
+        Kind::Chain => "# Release build for running a network\npop build --path . --release\n```\n",
+    });
+
+    readme.push_str("\n## Test\n\n```bash\npop test --path .\n");
+    if project.kind == Kind::Contract {
+        readme
+            .push_str("# End-to-end tests (they start their own node)\npop test --path . --e2e\n");
+    }
+    readme.push_str("```\n");
+
+    match project.kind {
+        Kind::Contract => {
+            readme.push_str(&format!(
+                "\n## Run a local node\n\n```bash\npop up ink-node -y --detach\n```\n\nThe node listens on `{}`.\n",
+                LOCAL_URL
+            ));
+            let latest = project.deployments.last();
+            let constructor = latest.map_or("new", |d| d.constructor.as_str());
+            let url = latest.map_or(LOCAL_URL, |d| d.url.as_str());
+            readme.push_str(&format!(
+                "\n## Deploy\n\n```bash\npop up . -y --constructor {} --args <ARGS> --url {} --suri \"$PRIVATE_KEY\" --execute\n```\n\n\
+                 Omit `--args` for constructors without arguments. Use dev keys such as `//Alice` on local networks only.\n",
+                constructor, url
+            ));
+        }
+        Kind::Chain => {
+            readme.push_str("\n## Run a network\n\n```bash\n");
+            if project.networks.is_empty() {
+                readme.push_str("pop up network ./network.toml -y --detach\n```\n\nAdd a network configuration (relay chain and parachains) as `network.toml` first.\n");
+            } else {
+                for config in &project.networks {
+                    readme.push_str(&format!("pop up network ./{} -y --detach\n", config));
+                }
+                readme.push_str("```\n");
+            }
+        }
+    }
+
+    let Some(latest) = project.deployments.last() else {
+        return readme;
+    };
+    readme.push_str(
+        "\n## Deployments\n\n| Network | Address | Code hash | Constructor | Deployed (Unix time) |\n|---|---|---|---|---|\n",
+    );
+    for d in project.deployments.iter().rev() {
+        readme.push_str(&format!(
+            "| `{}` | `{}` | {} | `{}` | {} |\n",
+            d.url,
+            d.address,
+            d.code_hash
+                .as_ref()
+                .map_or_else(|| "-".to_owned(), |hash| format!("`{}`", hash)),
+            d.constructor,
+            d.deployed_at
+        ));
+    }
+    readme.push_str(&format!(
+        "\nCall the latest instance:\n\n```bash\npop call contract --path . --contract {} --message <MESSAGE> -y --url {}\n```\n",
+        latest.address, latest.url
+    ));
+    readme
+}
+
+/// Execute generate_readme tool
+pub async fn generate_readme(
+    registry: &Registry,
+    params: GenerateReadmeParams,
+) -> PopMcpResult<CallToolResult> {
+    if params.path.trim().is_empty() {
+        return Err(PopMcpError::InvalidInput("Path cannot be empty".to_owned()));
+    }
+    let dir = PathBuf::from(&params.path);
+    let Ok(manifest) = std::fs::read_to_string(dir.join("Cargo.toml")) else {
+        return Ok(error_result(format!(
+            "No Cargo.toml in {}. Point path at a contract or chain project.",
+            params.path
+        )));
+    };
+    let target = dir.join("README.md");
+    if target.exists() && !params.overwrite.unwrap_or(false) {
+        return Ok(error_result(format!(
+            "{} already exists. Pass overwrite: true to replace it.",
+            target.display()
+        )));
+    }
+
+    let (name, kind) = inspect(&dir, &manifest)?;
+    // Deployments are recorded under the canonical contract path.
+    let canonical = std::fs::canonicalize(&dir).unwrap_or_else(|_| dir.clone());
+    let deployments = registry
+        .load()?
+        .into_iter()
+        .filter(|d| Path::new(&d.path) == canonical)
+        .filter(|d| {
+            params
+                .url
+                .as_deref()
+                .is_none_or(|url| same_url(&d.url, url))
+        })
+        .collect();
+    let project = Project {
+        name,
+        kind,
+        toolchain: project_toolchain(&dir)?,
+        networks: network_configs(&dir),
+        deployments,
+    };
+    let readme = render(&project);
+    std::fs::write(&target, &readme).map_err(|e| {
+        PopMcpError::Internal(format!("Failed to write {}: {}", target.display(), e))
+    })?;
+    Ok(success_result(format!(
+        "Wrote {} ({} deployment(s) listed):\n\n{}",
+        target.display(),
+        project.deployments.len(),
+        readme
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::common::content_text;
+
+    fn params(path: &Path) -> GenerateReadmeParams {
+        GenerateReadmeParams {
+            path: path.to_string_lossy().into_owned(),
+            url: None,
+            overwrite: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn contract_readmes_list_the_project_deployments() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let contract = dir.path().join("flipper");
+        std::fs::create_dir_all(&contract)?;
+        std::fs::write(
+            contract.join("Cargo.toml"),
+            "[package]\nname = \"flipper\"\n\n[dependencies]\nink = { version = \"6.0.0-alpha\", default-features = false }\n",
+        )?;
+        let registry = Registry::new(dir.path().join("deployments.json"));
+        for (path, address) in [
+            (std::fs::canonicalize(&contract)?, "0xf1"),
+            (dir.path().join("erc20"), "0xe2"),
+        ] {
+            registry.record(Deployment {
+                path: path.display().to_string(),
+                url: "ws://localhost:9955".to_owned(),
+                address: address.to_owned(),
+                code_hash: Some("0x4f1d".to_owned()),
+                constructor: "default".to_owned(),
+                deployed_at: 1_700_000_000,
+            })?;
+        }
+
+        let result = generate_readme(&registry, params(&contract)).await?;
+        assert_eq!(result.is_error, Some(false));
+        let readme = std::fs::read_to_string(contract.join("README.md"))?;
+        assert!(readme.starts_with("# flipper\n"));
+        assert!(readme.contains("pop test --path . --e2e"));
+        assert!(readme
+            .contains("pop up . -y --constructor default --args <ARGS> --url ws://localhost:9955"));
+        assert!(readme
+            .contains("| `ws://localhost:9955` | `0xf1` | `0x4f1d` | `default` | 1700000000 |"));
+        assert!(!readme.contains("0xe2"));
+        assert!(content_text(&result).contains("1 deployment(s) listed"));
+
+        // An existing README is kept unless overwriting is asked for.
+        let again = generate_readme(&registry, params(&contract)).await?;
+        assert_eq!(again.is_error, Some(true));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn chain_readmes_launch_the_project_network_configs() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"node\", \"runtime\"]\n",
+        )?;
+        std::fs::write(
+            dir.path().join("pop-paseo.toml"),
+            "[relaychain]\nchain = \"paseo-local\"\n",
+        )?;
+        std::fs::write(dir.path().join("rustfmt.toml"), "edition = \"2021\"\n")?;
+        std::fs::write(
+            dir.path().join("rust-toolchain.toml"),
+            "[toolchain]\nchannel = \"1.81\"\n",
+        )?;
+        std::fs::write(dir.path().join("README.md"), "old")?;
+
+        let result = generate_readme(
+            &Registry::new(dir.path().join("deployments.json")),
+            GenerateReadmeParams {
+                overwrite: Some(true),
+                ..params(dir.path())
+            },
+        )
+        .await?;
+        assert_eq!(result.is_error, Some(false));
+        let readme = std::fs::read_to_string(dir.path().join("README.md"))?;
+        assert!(readme.contains("pop up network ./pop-paseo.toml -y --detach"));
+        assert!(!readme.contains("rustfmt.toml"));
+        assert!(readme.contains("Rust toolchain `1.81`"));
+        assert!(!readme.contains("## Deploy"));
+
+        let missing = generate_readme(
+            &Registry::new(dir.path().join("deployments.json")),
+            params(&dir.path().join("missing")),
+        )
+        .await?;
+        assert_eq!(missing.is_error, Some(true));
+        Ok(())
+    }
+}
@@ -0,0 +1,684 @@
+//! Read-only chain queries over pooled JSON-RPC connections
+
+use std::time::Duration;
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::encoder::{blake2_256, decode_hex, to_hex, ChainMetadata};
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::rpc::RpcPool;
+use crate::tools::common::{error_result, success_result};
+
+/// JSON-RPC methods that only read chain state and may be issued through chain_rpc.
+const READ_ONLY_METHODS: &[&str] = &[
+    "rpc_methods",
+    "system_chain",
+    "system_health",
+    "system_name",
+    "system_peers",
+    "system_properties",
+    "system_syncState",
+    "system_version",
+    "system_accountNextIndex",
+    "chain_getBlock",
+    "chain_getBlockHash",
+    "chain_getFinalizedHead",
+    "chain_getHeader",
+    "state_call",
+    "state_getKeysPaged",
+    "state_getMetadata",
+    "state_getRuntimeVersion",
+    "state_getStorage",
+    "state_queryStorageAt",
+    "author_pendingExtrinsics",
+];
+
+/// Parameters for the chain_rpc tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct ChainRpcParams {
+    /// WebSocket URL of the chain node.
+    #[schemars(
+        description = "WebSocket URL of the chain node (defaults to the node launched with up_ink_node)"
+    )]
+    pub url: Option<String>,
+    /// JSON-RPC method to call.
+    #[schemars(
+        description = "Read-only JSON-RPC method (e.g., 'system_health', 'chain_getHeader', 'state_getStorage')"
+    )]
+    pub method: String,
+    /// Positional params for the method.
+    #[schemars(description = "Positional JSON params for the method (e.g., [\"0x26aa...\"])")]
+    pub params: Option<Vec<Value>>,
+}
+
+impl ChainRpcParams {
+    /// Validate parameters
+    fn validate(&self) -> Result<(), String> {
+        if !READ_ONLY_METHODS.contains(&self.method.as_str()) {
+            return Err(format!(
+                "Method '{}' is not allowed. Allowed read-only methods: {}",
+                self.method,
+                READ_ONLY_METHODS.join(", ")
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Execute chain_rpc tool
+pub async fn chain_rpc(
+    pool: &RpcPool,
+    params: ChainRpcParams,
+    stored_url: Option<&str>,
+) -> PopMcpResult<CallToolResult> {
+    params.validate().map_err(PopMcpError::InvalidInput)?;
+
+    let Some(url) = params.url.as_deref().or(stored_url) else {
+        return Err(PopMcpError::InvalidInput(
+            "'url' is required when no node has been launched".to_owned(),
+        ));
+    };
+
+    match pool
+        .request(url, &params.method, params.params.unwrap_or_default())
+        .await
+    {
+        Ok(value) => Ok(success_result(
+            serde_json::to_string_pretty(&value).unwrap_or_else(|_| value.to_string()),
+        )),
+        Err(e) => Ok(error_result(format!("Chain RPC failed: {}", e))),
+    }
+}
+
+/// Parameters for the runtime_api_call tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct RuntimeApiCallParams {
+    /// WebSocket URL of the chain node.
+    #[schemars(
+        description = "WebSocket URL of the chain node (defaults to the node launched with up_ink_node)"
+    )]
+    pub url: Option<String>,
+    /// Runtime API trait name.
+    #[schemars(
+        description = "Runtime API name (e.g., 'TransactionPaymentApi', 'AccountNonceApi', 'ReviveApi')"
+    )]
+    pub api: String,
+    /// Method of the runtime API.
+    #[schemars(description = "Runtime API method (e.g., 'query_info', 'account_nonce')")]
+    pub method: String,
+    /// Arguments for the method.
+    #[schemars(
+        description = "Arguments, one value per parameter, in call_chain syntax (numbers, 0x hex, SS58 addresses, Variant(..), { field: value })"
+    )]
+    pub args: Option<Vec<String>>,
+    /// Block hash to query at.
+    #[schemars(description = "Block hash to run the call at (defaults to the best block)")]
+    pub at: Option<String>,
+}
+
+/// Execute runtime_api_call tool
+pub async fn runtime_api_call(
+    pool: &RpcPool,
+    params: RuntimeApiCallParams,
+    stored_url: Option<&str>,
+) -> PopMcpResult<CallToolResult> {
+    let Some(url) = params.url.as_deref().or(stored_url) else {
+        return Err(PopMcpError::InvalidInput(
+            "'url' is required when no node has been launched".to_owned(),
+        ));
+    };
+
+    let metadata = match ChainMetadata::fetch(pool, url).await {
+        Ok(metadata) => metadata,
+        Err(e) => return Ok(error_result(format!("Runtime API call failed: {}", e))),
+    };
+    let (method, encoded) = match metadata.encode_runtime_api_call(
+        &params.api,
+        &params.method,
+        params.args.as_deref().unwrap_or_default(),
+    ) {
+        Ok(call) => call,
+        Err(e) => return Ok(error_result(format!("Runtime API call failed: {}", e))),
+    };
+
+    let mut rpc_params = vec![
+        Value::String(method.clone()),
+        Value::String(to_hex(&encoded)),
+    ];
+    if let Some(at) = params.at {
+        rpc_params.push(Value::String(at));
+    }
+    let output = match pool.request(url, "state_call", rpc_params).await {
+        Ok(Value::String(hex)) => hex,
+        Ok(other) => {
+            return Ok(error_result(format!(
+                "Runtime API call failed: unexpected state_call response {}",
+                other
+            )))
+        }
+        Err(e) => return Ok(error_result(format!("Runtime API call failed: {}", e))),
+    };
+    let decoded = decode_hex(&output)
+        .and_then(|bytes| metadata.decode_runtime_api_output(&params.api, &params.method, &bytes));
+    match decoded {
+        Ok(rendered) => Ok(success_result(format!("{}:\n{}", method, rendered))),
+        Err(e) => Ok(error_result(format!(
+            "{} returned {} but it could not be decoded: {}",
+            method, output, e
+        ))),
+    }
+}
+
+/// Parameters for the node_health tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct NodeHealthParams {
+    /// WebSocket URL of the chain node.
+    #[schemars(
+        description = "WebSocket URL of the chain node (defaults to the node launched with up_ink_node)"
+    )]
+    pub url: Option<String>,
+}
+
+/// Block number from a `system_syncState` field.
+fn block_number(sync: &Value, field: &str) -> Option<u64> {
+    sync.get(field).and_then(Value::as_u64)
+}
+
+/// Render a health report from `system_health`, `system_syncState` and `system_peers`
+/// results. Sync state and peers are optional: public nodes often refuse `system_peers`.
+fn health_report(
+    url: &str,
+    health: &Value,
+    sync: Result<&Value, String>,
+    peers: Result<&Value, String>,
+) -> String {
+    let peer_count = health.get("peers").and_then(Value::as_u64).unwrap_or(0);
+    let syncing = health
+        .get("isSyncing")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let should_have_peers = health
+        .get("shouldHavePeers")
+        .and_then(Value::as_bool)
+        .unwrap_or(true);
+
+    let status = if syncing {
+        "up but still syncing; calls may fail or see stale state until it catches up"
+    } else if peer_count == 0 && should_have_peers {
+        "up but has no peers; blocks may not be produced or imported"
+    } else {
+        "healthy"
+    };
+    let mut lines = vec![
+        format!("Node at {}: {}", url, status),
+        String::new(),
+        format!("peers: {}", peer_count),
+        format!("syncing: {}", if syncing { "yes" } else { "no" }),
+    ];
+    match sync {
+        Ok(sync) => {
+            if let Some(current) = block_number(sync, "currentBlock") {
+                lines.push(format!("current block: {}", current));
+            }
+            if let Some(highest) = block_number(sync, "highestBlock") {
+                lines.push(format!("highest block: {}", highest));
+            }
+        }
+        Err(e) => lines.push(format!("sync state: unavailable ({})", e)),
+    }
+    match peers.map(Value::as_array) {
+        Ok(Some(peers)) if !peers.is_empty() => {
+            lines.push(String::new());
+            lines.push("Peers:".to_owned());
+            for peer in peers {
+                let field = |name: &str| {
+                    peer.get(name)
+                        .map(|v| v.as_str().map_or_else(|| v.to_string(), str::to_owned))
+                        .unwrap_or_else(|| "?".to_owned())
+                };
+                lines.push(format!(
+                    "- {} ({}) best #{}",
+                    field("peerId"),
+                    field("roles"),
+                    field("bestNumber")
+                ));
+            }
+        }
+        Ok(_) => {}
+        Err(e) => lines.push(format!("peer details: unavailable ({})", e)),
+    }
+    lines.join("\n")
+}
+
+/// Execute node_health tool
+pub async fn node_health(
+    pool: &RpcPool,
+    params: NodeHealthParams,
+    stored_url: Option<&str>,
+) -> PopMcpResult<CallToolResult> {
+    let Some(url) = params.url.as_deref().or(stored_url) else {
+        return Err(PopMcpError::InvalidInput(
+            "'url' is required when no node has been launched".to_owned(),
+        ));
+    };
+
+    let health = match pool.request(url, "system_health", Vec::new()).await {
+        Ok(health) => health,
+        Err(e) => {
+            return Ok(error_result(format!(
+                "Node at {} is unreachable: {}",
+                url, e
+            )))
+        }
+    };
+    let (sync, peers) = tokio::join!(
+        pool.request(url, "system_syncState", Vec::new()),
+        pool.request(url, "system_peers", Vec::new())
+    );
+    Ok(success_result(health_report(
+        url,
+        &health,
+        sync.as_ref().map_err(ToString::to_string),
+        peers.as_ref().map_err(ToString::to_string),
+    )))
+}
+
+/// Timeout of the TCP and eth-rpc probes of node_status.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Port of the eth-rpc sidecar started by `pop up ink-node`.
+const DEFAULT_ETH_RPC_PORT: u16 = 8545;
+
+/// Parameters for the node_status tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct NodeStatusParams {
+    /// WebSocket URL of the node.
+    #[schemars(
+        description = "WebSocket URL of the node (defaults to the node launched with up_ink_node)"
+    )]
+    pub url: Option<String>,
+    /// HTTP URL of the eth-rpc sidecar.
+    #[schemars(
+        description = "HTTP URL of the eth-rpc sidecar (default: port 8545 on the node's host)"
+    )]
+    pub eth_rpc_url: Option<String>,
+}
+
+/// What node_status found.
+#[derive(Debug)]
+struct NodeStatus {
+    address: String,
+    best_block: Result<u64, String>,
+    peers: u64,
+    syncing: bool,
+    eth_rpc_url: String,
+    eth_rpc: Result<u64, String>,
+}
+
+/// Block number from a hex quantity such as `"0x1a"`.
+pub(crate) fn hex_number(value: &Value) -> Option<u64> {
+    u64::from_str_radix(value.as_str()?.trim_start_matches("0x"), 16).ok()
+}
+
+/// Render a node_status report.
+fn status_report(url: &str, status: &NodeStatus) -> String {
+    let verdict = match (&status.best_block, status.syncing, &status.eth_rpc) {
+        (Err(_), _, _) => "reachable but not serving chain data",
+        (_, true, _) => "up but still syncing",
+        (_, _, Err(_)) => "ready for Substrate calls; the eth-rpc sidecar is down",
+        _ => "ready",
+    };
+    let block = match &status.best_block {
+        Ok(number) => format!("#{}", number),
+        Err(e) => format!("unavailable ({})", e),
+    };
+    let eth = match &status.eth_rpc {
+        Ok(number) => format!("up, block #{}", number),
+        Err(e) => format!("down ({})", e),
+    };
+    [
+        format!("Node at {}: {}", url, verdict),
+        String::new(),
+        format!("tcp: reachable ({})", status.address),
+        format!("best block: {}", block),
+        format!("peers: {}", status.peers),
+        format!("syncing: {}", if status.syncing { "yes" } else { "no" }),
+        format!("eth-rpc ({}): {}", status.eth_rpc_url, eth),
+    ]
+    .join("\n")
+}
+
+/// Latest block reported by the eth-rpc sidecar at `url`.
+async fn eth_block_number(url: &str) -> Result<u64, String> {
+    let client = reqwest::Client::builder()
+        .timeout(PROBE_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response: Value = client
+        .post(url)
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_blockNumber",
+            "params": [],
+        }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+    response
+        .get("result")
+        .and_then(hex_number)
+        .ok_or_else(|| format!("unexpected response {}", response))
+}
+
+/// Execute node_status tool
+pub async fn node_status(
+    pool: &RpcPool,
+    params: NodeStatusParams,
+    stored_url: Option<&str>,
+) -> PopMcpResult<CallToolResult> {
+    let Some(url) = params.url.as_deref().or(stored_url) else {
+        return Err(PopMcpError::InvalidInput(
+            "'url' is required when no node has been launched".to_owned(),
+        ));
+    };
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| PopMcpError::InvalidInput(format!("Invalid node URL '{}': {}", url, e)))?;
+    let (Some(host), Some(port)) = (parsed.host_str(), parsed.port_or_known_default()) else {
+        return Err(PopMcpError::InvalidInput(format!(
+            "Node URL '{}' has no host or port",
+            url
+        )));
+    };
+    let address = format!("{}:{}", host, port);
+
+    let connect =
+        tokio::time::timeout(PROBE_TIMEOUT, tokio::net::TcpStream::connect(&address)).await;
+    let unreachable = match connect {
+        Ok(Ok(_)) => None,
+        Ok(Err(e)) => Some(e.to_string()),
+        Err(_) => Some("timed out".to_owned()),
+    };
+    if let Some(e) = unreachable {
+        return Ok(error_result(format!(
+            "Nothing is accepting connections at {} ({}). Is the node running? Launch one with up_ink_node.",
+            address, e
+        )));
+    }
+
+    let health = match pool.request(url, "system_health", Vec::new()).await {
+        Ok(health) => health,
+        Err(e) => {
+            return Ok(error_result(format!(
+                "{} accepts connections but does not answer JSON-RPC: {}",
+                address, e
+            )))
+        }
+    };
+    let eth_rpc_url = params
+        .eth_rpc_url
+        .clone()
+        .unwrap_or_else(|| format!("http://{}:{}", host, DEFAULT_ETH_RPC_PORT));
+    let (header, eth_rpc) = tokio::join!(
+        pool.request(url, "chain_getHeader", Vec::new()),
+        eth_block_number(&eth_rpc_url)
+    );
+    let status = NodeStatus {
+        address,
+        best_block: header.map_err(|e| e.to_string()).and_then(|header| {
+            header
+                .get("number")
+                .and_then(hex_number)
+                .ok_or_else(|| "header has no block number".to_owned())
+        }),
+        peers: health.get("peers").and_then(Value::as_u64).unwrap_or(0),
+        syncing: health
+            .get("isSyncing")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        eth_rpc_url,
+        eth_rpc,
+    };
+    Ok(success_result(status_report(url, &status)))
+}
+
+/// Parameters for the pending_extrinsics tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct PendingExtrinsicsParams {
+    /// WebSocket URL of the chain node.
+    #[schemars(
+        description = "WebSocket URL of the chain node (defaults to the node launched with up_ink_node)"
+    )]
+    pub url: Option<String>,
+}
+
+/// Render one pooled transaction, numbered from 1.
+fn describe_pending(metadata: &ChainMetadata, number: usize, hex: &str) -> String {
+    let bytes = match decode_hex(hex) {
+        Ok(bytes) => bytes,
+        Err(e) => return format!("{}. {} (not hex: {})", number, hex, e),
+    };
+    let mut lines = vec![format!("{}. {}", number, to_hex(&blake2_256(&bytes)))];
+    match metadata.decode_extrinsic(&bytes) {
+        Ok(decoded) => {
+            if let Some(signer) = decoded.signer {
+                lines.push(format!("   signer: {}", signer));
+            }
+            for (name, value) in decoded.extensions {
+                lines.push(format!("   {}: {}", name, value));
+            }
+            lines.push(format!("   call: {}", decoded.call));
+        }
+        Err(e) => lines.push(format!(
+            "   could not be decoded ({}): {} bytes {}",
+            e,
+            bytes.len(),
+            hex
+        )),
+    }
+    lines.join("\n")
+}
+
+/// Execute pending_extrinsics tool
+pub async fn pending_extrinsics(
+    pool: &RpcPool,
+    params: PendingExtrinsicsParams,
+    stored_url: Option<&str>,
+) -> PopMcpResult<CallToolResult> {
+    let Some(url) = params.url.as_deref().or(stored_url) else {
+        return Err(PopMcpError::InvalidInput(
+            "'url' is required when no node has been launched".to_owned(),
+        ));
+    };
+
+    let pending = match pool
+        .request(url, "author_pendingExtrinsics", Vec::new())
+        .await
+    {
+        Ok(pending) => pending,
+        Err(e) => {
+            return Ok(error_result(format!(
+                "Reading the transaction pool failed: {}",
+                e
+            )))
+        }
+    };
+    let pending: Vec<&str> = pending
+        .as_array()
+        .map(|items| items.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    if pending.is_empty() {
+        return Ok(success_result(format!(
+            "The transaction pool of {} is empty: every submitted transaction was included or dropped.",
+            url
+        )));
+    }
+    let metadata = match ChainMetadata::fetch(pool, url).await {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            return Ok(error_result(format!(
+                "Reading the transaction pool failed: {}",
+                e
+            )))
+        }
+    };
+    let described: Vec<String> = pending
+        .iter()
+        .enumerate()
+        .map(|(i, hex)| describe_pending(&metadata, i + 1, hex))
+        .collect();
+    Ok(success_result(format!(
+        "{} pending extrinsic(s) at {}. A transaction stays here while its nonce is ahead of the account's next nonce, its fee or tip is outbid, or blocks are not being produced.\n\n{}",
+        described.len(),
+        url,
+        described.join("\n\n")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_accepts_read_only_method() {
+        let params = ChainRpcParams {
+            url: None,
+            method: "system_health".to_owned(),
+            params: None,
+        };
+        assert!(params.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_state_changing_method() {
+        let params = ChainRpcParams {
+            url: None,
+            method: "author_submitExtrinsic".to_owned(),
+            params: Some(vec![Value::String("0x00".to_owned())]),
+        };
+        assert!(params.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn chain_rpc_requires_url_without_stored_node() {
+        let params = ChainRpcParams {
+            url: None,
+            method: "system_health".to_owned(),
+            params: None,
+        };
+        let result = chain_rpc(&RpcPool::new(), params, None).await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn runtime_api_call_requires_url_without_stored_node() {
+        let params = RuntimeApiCallParams {
+            url: None,
+            api: "AccountNonceApi".to_owned(),
+            method: "account_nonce".to_owned(),
+            args: None,
+            at: None,
+        };
+        let result = runtime_api_call(&RpcPool::new(), params, None).await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn pending_extrinsics_requires_url_without_stored_node() {
+        let params = PendingExtrinsicsParams { url: None };
+        let result = pending_extrinsics(&RpcPool::new(), params, None).await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn node_status_reports_closed_port() -> Result<(), std::io::Error> {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        let port = listener.local_addr()?.port();
+        drop(listener);
+        let params = NodeStatusParams {
+            url: Some(format!("ws://127.0.0.1:{}", port)),
+            eth_rpc_url: None,
+        };
+        let result = node_status(&RpcPool::new(), params, None).await;
+        assert!(result.is_ok_and(|r| r.is_error == Some(true)
+            && crate::tools::common::content_text(&r)
+                .contains("Nothing is accepting connections")));
+        Ok(())
+    }
+
+    #[test]
+    fn status_report_flags_missing_eth_rpc() {
+        let mut status = NodeStatus {
+            address: "127.0.0.1:9944".to_owned(),
+            best_block: Ok(26),
+            peers: 0,
+            syncing: false,
+            eth_rpc_url: "http://127.0.0.1:8545".to_owned(),
+            eth_rpc: Ok(26),
+        };
+        assert_eq!(hex_number(&Value::String("0x1a".to_owned())), Some(26));
+        let report = status_report("ws://127.0.0.1:9944", &status);
+        assert!(report.starts_with("Node at ws://127.0.0.1:9944: ready\n"));
+        assert!(report.contains("best block: #26\n"));
+        assert!(report.ends_with("eth-rpc (http://127.0.0.1:8545): up, block #26"));
+        status.eth_rpc = Err("connection refused".to_owned());
+        let report = status_report("ws://127.0.0.1:9944", &status);
+        assert!(report.contains("the eth-rpc sidecar is down"));
+        assert!(report.ends_with("down (connection refused)"));
+    }
+
+    #[test]
+    fn health_report_flags_syncing_node() {
+        let health = serde_json::json!({"peers": 3, "isSyncing": true, "shouldHavePeers": true});
+        let sync =
+            serde_json::json!({"startingBlock": 0, "currentBlock": 120, "highestBlock": 5000});
+        let report = health_report(
+            "wss://testnet",
+            &health,
+            Ok(&sync),
+            Err("system_peers failed: RPC call is unsafe".to_owned()),
+        );
+        assert!(report.starts_with("Node at wss://testnet: up but still syncing"));
+        assert!(report.contains("current block: 120\nhighest block: 5000"));
+        assert!(
+            report.ends_with("peer details: unavailable (system_peers failed: RPC call is unsafe)")
+        );
+    }
+
+    #[test]
+    fn health_report_lists_peers_of_healthy_node() {
+        let health = serde_json::json!({"peers": 1, "isSyncing": false, "shouldHavePeers": true});
+        let peers = serde_json::json!([
+            {"peerId": "12D3KooW", "roles": "AUTHORITY", "bestNumber": 42}
+        ]);
+        let report = health_report("ws://localhost:9944", &health, Ok(&Value::Null), Ok(&peers));
+        assert!(report.starts_with("Node at ws://localhost:9944: healthy"));
+        assert!(report.ends_with("Peers:\n- 12D3KooW (AUTHORITY) best #42"));
+    }
+
+    #[test]
+    fn health_report_allows_dev_node_without_peers() {
+        let health = serde_json::json!({"peers": 0, "isSyncing": false, "shouldHavePeers": false});
+        let report = health_report(
+            "ws://localhost:9944",
+            &health,
+            Ok(&Value::Null),
+            Ok(&serde_json::json!([])),
+        );
+        assert!(report.starts_with("Node at ws://localhost:9944: healthy"));
+        let lonely = serde_json::json!({"peers": 0, "isSyncing": false, "shouldHavePeers": true});
+        let report = health_report("wss://testnet", &lonely, Ok(&Value::Null), Ok(&Value::Null));
+        assert!(report.contains("has no peers"));
+    }
+}
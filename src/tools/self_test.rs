@@ -0,0 +1,436 @@
+//! Environment self-test (self_test)
+//!
+//! Runs a miniature end-to-end against a throwaway flipper contract and a private
+//! ink! node, so users get one answer to "is my setup actually working?".
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::outputs::{DeployOutput, NodeInfo};
+use crate::parsing;
+use crate::tools::call::contract::is_error_output;
+use crate::tools::common::{error_result, extract_text, success_result};
+use crate::tools::{
+    build_contract, create_contract, stop_pids, BuildContractParams, CreateContractParams,
+};
+
+/// Name of the scaffolded contract.
+const CONTRACT_NAME: &str = "pop_mcp_self_test";
+/// Dev account that signs on the self-test node, which nothing else uses.
+const DEV_SURI: &str = "//Alice";
+/// Default ports, off the usual 9944/8545 so a running node is left alone.
+const DEFAULT_INK_NODE_PORT: u16 = 9966;
+const DEFAULT_ETH_RPC_PORT: u16 = 8566;
+/// Upper bound for cleanup, which runs even when the budget is spent.
+const CLEANUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Stages in the order they run.
+const STAGES: &[&str] = &[
+    "scaffold",
+    "build",
+    "launch node",
+    "deploy",
+    "call get",
+    "call flip",
+];
+
+/// Parameters for the self_test tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct SelfTestParams {
+    /// Overall time budget in seconds (default: unlimited).
+    #[schemars(
+        description = "Overall time budget in seconds; stages that don't fit are skipped (default: unlimited)"
+    )]
+    pub budget_secs: Option<u64>,
+    /// Port for the temporary ink! node (default: 9966).
+    #[schemars(description = "Port for the temporary ink! node (default: 9966)")]
+    pub ink_node_port: Option<u16>,
+    /// Port for the temporary Ethereum RPC node (default: 8566).
+    #[schemars(description = "Port for the temporary Ethereum RPC node (default: 8566)")]
+    pub eth_rpc_port: Option<u16>,
+}
+
+/// How a stage ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    Passed(String),
+    Failed(String),
+    Skipped,
+}
+
+/// A finished stage.
+#[derive(Debug, Clone)]
+struct StageReport {
+    name: &'static str,
+    outcome: Outcome,
+    elapsed: Duration,
+}
+
+/// Progress of one self-test run.
+#[derive(Debug)]
+struct Run {
+    deadline: Option<Instant>,
+    current: Option<(&'static str, Instant)>,
+    stages: Vec<StageReport>,
+}
+
+impl Run {
+    fn new(budget: Option<Duration>) -> Self {
+        Self {
+            deadline: budget.map(|budget| Instant::now() + budget),
+            current: None,
+            stages: Vec::new(),
+        }
+    }
+
+    /// Start `name`, returning an executor limited to the remaining budget.
+    fn begin<E: CommandExecutor>(&mut self, name: &'static str, executor: &E) -> PopMcpResult<E> {
+        self.current = Some((name, Instant::now()));
+        match self.deadline {
+            None => Ok(executor.clone()),
+            Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                Some(remaining) if !remaining.is_zero() => {
+                    Ok(executor.clone().with_timeout(remaining))
+                }
+                _ => Err(PopMcpError::CommandExecution(
+                    "time budget exhausted".to_owned(),
+                )),
+            },
+        }
+    }
+
+    fn finish(&mut self, outcome: Outcome) {
+        if let Some((name, started)) = self.current.take() {
+            self.stages.push(StageReport {
+                name,
+                outcome,
+                elapsed: started.elapsed(),
+            });
+        }
+    }
+
+    fn pass(&mut self, detail: impl Into<String>) {
+        self.finish(Outcome::Passed(detail.into()));
+    }
+
+    fn fail(&mut self, error: &PopMcpError) {
+        self.finish(Outcome::Failed(error.to_string()));
+    }
+
+    /// Record every stage that never started as skipped.
+    fn skip_rest(&mut self) {
+        for name in STAGES {
+            if !self.stages.iter().any(|stage| stage.name == *name) {
+                self.stages.push(StageReport {
+                    name,
+                    outcome: Outcome::Skipped,
+                    elapsed: Duration::ZERO,
+                });
+            }
+        }
+    }
+
+    fn passed(&self) -> bool {
+        self.stages
+            .iter()
+            .all(|stage| matches!(stage.outcome, Outcome::Passed(_)))
+    }
+
+    fn report(&self, cleanup: &[String]) -> String {
+        let mut lines = Vec::new();
+        for stage in &self.stages {
+            let line = match &stage.outcome {
+                Outcome::Passed(detail) if detail.is_empty() => {
+                    format!("✅ {} ({:.1}s)", stage.name, stage.elapsed.as_secs_f64())
+                }
+                Outcome::Passed(detail) => format!(
+                    "✅ {} ({:.1}s): {}",
+                    stage.name,
+                    stage.elapsed.as_secs_f64(),
+                    detail
+                ),
+                Outcome::Failed(error) => format!(
+                    "❌ {} ({:.1}s): {}",
+                    stage.name,
+                    stage.elapsed.as_secs_f64(),
+                    error
+                ),
+                Outcome::Skipped => format!("- {}: skipped", stage.name),
+            };
+            lines.push(line);
+        }
+        for note in cleanup {
+            lines.push(format!("- cleanup: {}", note));
+        }
+        let verdict = match self
+            .stages
+            .iter()
+            .find(|stage| matches!(stage.outcome, Outcome::Failed(_)))
+        {
+            None => "Self-test passed: Pop CLI can scaffold, build, deploy and call contracts."
+                .to_owned(),
+            Some(stage) => format!("Self-test failed at '{}'.", stage.name),
+        };
+        format!("{}\n\n{}", verdict, lines.join("\n"))
+    }
+}
+
+/// Fail a stage whose tool call returned an error result.
+fn check(result: &CallToolResult, what: &str) -> PopMcpResult<()> {
+    if result.is_error == Some(true) {
+        return Err(PopMcpError::CommandExecution(
+            extract_text(result).unwrap_or_else(|| format!("{} failed", what)),
+        ));
+    }
+    Ok(())
+}
+
+/// Call `message` on the deployed contract, failing on Pop CLI error text.
+async fn call<E: CommandExecutor>(
+    executor: &E,
+    path: &str,
+    url: &str,
+    address: &str,
+    message: &str,
+    execute: bool,
+) -> PopMcpResult<String> {
+    let mut args = vec![
+        "call",
+        "contract",
+        "--path",
+        path,
+        "--contract",
+        address,
+        "--message",
+        message,
+        "--url",
+        url,
+        "-y",
+    ];
+    if execute {
+        args.extend(["--execute", "--suri", DEV_SURI]);
+    }
+    let output = executor.execute(&args).await?;
+    if is_error_output(&output) {
+        return Err(PopMcpError::CommandExecution(output));
+    }
+    Ok(parsing::call_result(&output).unwrap_or_default())
+}
+
+/// Run the stages, recording each in `run` and collecting node pids for cleanup.
+async fn run_stages<E: CommandExecutor>(
+    executor: &E,
+    params: &SelfTestParams,
+    workdir: &Path,
+    run: &mut Run,
+    pids: &mut Vec<u32>,
+) -> PopMcpResult<()> {
+    let stage = run.begin("scaffold", executor)?;
+    let result = create_contract(
+        &stage.in_dir(workdir),
+        CreateContractParams {
+            name: CONTRACT_NAME.to_owned(),
+            template: "standard".to_owned(),
+            template_url: None,
+            ink_version: None,
+            with_e2e_tests: None,
+            with_frontend: None,
+            cwd: None,
+            license: None,
+            repository: None,
+            authors: None,
+        },
+    )
+    .await?;
+    check(&result, "Scaffold")?;
+    let path = workdir.join(CONTRACT_NAME).display().to_string();
+    run.pass(String::new());
+
+    let stage = run.begin("build", executor)?;
+    let result = build_contract(
+        &stage,
+        BuildContractParams {
+            path: path.clone(),
+            release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
+        },
+    )
+    .await?;
+    check(&result, "Build")?;
+    run.pass(String::new());
+
+    let stage = run.begin("launch node", executor)?;
+    let ink_port = params
+        .ink_node_port
+        .unwrap_or(DEFAULT_INK_NODE_PORT)
+        .to_string();
+    let eth_port = params
+        .eth_rpc_port
+        .unwrap_or(DEFAULT_ETH_RPC_PORT)
+        .to_string();
+    let output = stage
+        .execute(&[
+            "up", "ink-node", "-y", "--detach", "-i", &ink_port, "-e", &eth_port,
+        ])
+        .await?;
+    let node = NodeInfo::parse(&output).ok_or_else(|| {
+        PopMcpError::CommandExecution("Failed to parse websocket URL from output".to_owned())
+    })?;
+    pids.extend(&node.pids);
+    run.pass(node.url.clone());
+
+    let stage = run.begin("deploy", executor)?;
+    let output = stage
+        .execute(&[
+            "up",
+            &path,
+            "--constructor",
+            "new",
+            "--args",
+            "false",
+            "--execute",
+            "--suri",
+            DEV_SURI,
+            "--url",
+            &node.url,
+            "-y",
+        ])
+        .await?;
+    let deployed = DeployOutput::parse(&output).ok_or_else(|| {
+        PopMcpError::CommandExecution(format!("No contract address in output:\n\n{}", output))
+    })?;
+    run.pass(deployed.address.clone());
+
+    for (message, execute) in [("get", false), ("flip", true)] {
+        let name = if execute { "call flip" } else { "call get" };
+        let stage = run.begin(name, executor)?;
+        let detail = call(
+            &stage,
+            &path,
+            &node.url,
+            &deployed.address,
+            message,
+            execute,
+        )
+        .await?;
+        run.pass(detail);
+    }
+    Ok(())
+}
+
+/// Stop the node and remove the scratch directory, returning notes for the report.
+async fn clean_up<E: CommandExecutor>(executor: &E, workdir: &Path, pids: Vec<u32>) -> Vec<String> {
+    let mut notes = Vec::new();
+    if !pids.is_empty() {
+        // Cleanup still runs when the self-test itself was cancelled.
+        let executor = executor.detached().with_timeout(CLEANUP_TIMEOUT);
+        match stop_pids(&executor, &pids).await {
+            Ok(result) if result.is_error != Some(true) => {
+                notes.push(format!("stopped node (pids {:?})", pids));
+            }
+            Ok(result) => notes.push(format!(
+                "failed to stop node pids {:?}: {}",
+                pids,
+                extract_text(&result).unwrap_or_default()
+            )),
+            Err(e) => notes.push(format!("failed to stop node pids {:?}: {}", pids, e)),
+        }
+    }
+    match std::fs::remove_dir_all(workdir) {
+        Ok(()) => notes.push("removed scratch directory".to_owned()),
+        Err(e) => notes.push(format!("failed to remove {}: {}", workdir.display(), e)),
+    }
+    notes
+}
+
+/// Fresh scratch directory for one run.
+fn scratch_dir() -> PopMcpResult<PathBuf> {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    let dir = std::env::temp_dir().join(format!(
+        "pop-mcp-self-test-{}-{}",
+        std::process::id(),
+        nanos
+    ));
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| PopMcpError::Internal(format!("Failed to create scratch dir: {}", e)))?;
+    Ok(dir)
+}
+
+/// Execute self_test tool
+pub async fn self_test<E: CommandExecutor>(
+    executor: &E,
+    params: SelfTestParams,
+) -> PopMcpResult<CallToolResult> {
+    if params.budget_secs == Some(0) {
+        return Err(PopMcpError::InvalidInput(
+            "budget_secs must be greater than 0".to_owned(),
+        ));
+    }
+
+    let workdir = scratch_dir()?;
+    let mut run = Run::new(params.budget_secs.map(Duration::from_secs));
+    let mut pids = Vec::new();
+    if let Err(e) = run_stages(executor, &params, &workdir, &mut run, &mut pids).await {
+        run.fail(&e);
+    }
+    run.skip_rest();
+    let cleanup = clean_up(executor, &workdir, pids).await;
+
+    let report = run.report(&cleanup);
+    if run.passed() {
+        Ok(success_result(report))
+    } else {
+        Ok(error_result(report))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::executor::PopExecutor;
+
+    #[test]
+    fn exhausted_budget_fails_the_stage_and_skips_the_rest() {
+        let mut run = Run::new(Some(Duration::ZERO));
+        let Err(e) = run.begin("scaffold", &PopExecutor::new()) else {
+            panic!("Expected budget error");
+        };
+        run.fail(&e);
+        run.skip_rest();
+        assert_eq!(run.stages.len(), STAGES.len());
+        assert!(!run.passed());
+        let report = run.report(&[]);
+        assert!(report.starts_with("Self-test failed at 'scaffold'."));
+        assert!(report.contains("time budget exhausted"));
+        assert!(report.contains("- call flip: skipped"));
+    }
+
+    #[tokio::test]
+    async fn zero_budget_is_rejected() {
+        let result = self_test(
+            &PopExecutor::new(),
+            SelfTestParams {
+                budget_secs: Some(0),
+                ink_node_port: None,
+                eth_rpc_port: None,
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+    }
+}
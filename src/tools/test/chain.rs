@@ -1,11 +1,15 @@
 //! Chain testing (pop test)
 
+use std::collections::BTreeMap;
+
 use rmcp::model::CallToolResult;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::diagnostics::with_compile_errors;
 use crate::error::PopMcpResult;
-use crate::executor::PopExecutor;
+use crate::executor::CommandExecutor;
+use crate::toolchain;
 use crate::tools::common::{error_result, success_result};
 
 /// Parameters for the test_chain tool.
@@ -15,6 +19,21 @@ pub struct TestChainParams {
     /// Path to the chain project directory.
     #[schemars(description = "Path to the chain project directory")]
     pub path: String,
+    /// Kill the command after this many seconds (default: no timeout).
+    #[schemars(
+        description = "Kill the command after this many seconds and return its partial output (default: no timeout)"
+    )]
+    pub timeout_secs: Option<u64>,
+    /// Environment variables set for this command only.
+    #[schemars(
+        description = "Environment variables to set for this command only, e.g. {\"RUST_LOG\": \"debug\", \"CARGO_TARGET_DIR\": \"/tmp/target\"}"
+    )]
+    pub env: Option<BTreeMap<String, String>>,
+    /// Rust toolchain to test with (default: the project's rust-toolchain.toml).
+    #[schemars(
+        description = "Rust toolchain to use, e.g. 'nightly-2024-09-05' or '1.81' (default: the one pinned by the project's rust-toolchain.toml, if any)"
+    )]
+    pub toolchain: Option<String>,
 }
 
 impl TestChainParams {
@@ -33,16 +52,33 @@ fn build_test_chain_args(params: &TestChainParams) -> Vec<&str> {
 }
 
 /// Execute test_chain tool
-pub fn test_chain(executor: &PopExecutor, params: TestChainParams) -> PopMcpResult<CallToolResult> {
+pub async fn test_chain<E: CommandExecutor>(
+    executor: &E,
+    params: TestChainParams,
+) -> PopMcpResult<CallToolResult> {
     params
         .validate()
         .map_err(crate::error::PopMcpError::InvalidInput)?;
 
     let args = build_test_chain_args(&params);
 
-    match executor.execute(&args) {
+    let env = toolchain::call_env(
+        &params.path,
+        params.toolchain.as_deref(),
+        params.env.as_ref(),
+    )?;
+    match executor
+        .with_timeout_secs(params.timeout_secs)?
+        .with_env_vars(env.as_ref())?
+        .execute(&args)
+        .await
+    {
         Ok(output) => Ok(success_result(format!("Tests completed!\n\n{}", output))),
-        Err(e) => Ok(error_result(format!("Tests failed: {}", e))),
+        Err(e) => {
+            let output = e.to_string();
+            let result = error_result(format!("Tests failed: {}", output));
+            Ok(with_compile_errors(result, "Tests failed", &output))
+        }
     }
 }
 
@@ -54,6 +90,9 @@ mod tests {
     fn validate_rejects_empty_path() {
         let params = TestChainParams {
             path: String::new(),
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
         };
         assert!(params.validate().is_err());
     }
@@ -62,6 +101,9 @@ mod tests {
     fn validate_accepts_valid_path() {
         let params = TestChainParams {
             path: "./my_chain".to_owned(),
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
         };
         assert!(params.validate().is_ok());
     }
@@ -70,6 +112,9 @@ mod tests {
     fn build_args_basic() {
         let params = TestChainParams {
             path: "./my_chain".to_owned(),
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
         };
         let args = build_test_chain_args(&params);
         assert_eq!(args, vec!["test", "--path", "./my_chain"]);
@@ -1,11 +1,15 @@
 //! Contract testing (pop test)
 
+use std::collections::BTreeMap;
+
 use rmcp::model::CallToolResult;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::diagnostics::with_compile_errors;
 use crate::error::PopMcpResult;
-use crate::executor::PopExecutor;
+use crate::executor::CommandExecutor;
+use crate::toolchain;
 use crate::tools::common::{error_result, success_result};
 
 /// Parameters for the test_contract tool.
@@ -18,6 +22,21 @@ pub struct TestContractParams {
     /// Whether to run end-to-end tests.
     #[schemars(description = "Run end-to-end tests")]
     pub e2e: bool,
+    /// Kill the command after this many seconds (default: no timeout).
+    #[schemars(
+        description = "Kill the command after this many seconds and return its partial output (default: no timeout)"
+    )]
+    pub timeout_secs: Option<u64>,
+    /// Environment variables set for this command only.
+    #[schemars(
+        description = "Environment variables to set for this command only, e.g. {\"RUST_LOG\": \"debug\", \"CARGO_TARGET_DIR\": \"/tmp/target\"}"
+    )]
+    pub env: Option<BTreeMap<String, String>>,
+    /// Rust toolchain to test with (default: the project's rust-toolchain.toml).
+    #[schemars(
+        description = "Rust toolchain to use, e.g. 'nightly-2024-09-05' or '1.81' (default: the one pinned by the project's rust-toolchain.toml, if any)"
+    )]
+    pub toolchain: Option<String>,
 }
 
 impl TestContractParams {
@@ -42,8 +61,8 @@ fn build_test_contract_args(params: &TestContractParams) -> Vec<&str> {
 }
 
 /// Execute test_contract tool
-pub fn test_contract(
-    executor: &PopExecutor,
+pub async fn test_contract<E: CommandExecutor>(
+    executor: &E,
     params: TestContractParams,
 ) -> PopMcpResult<CallToolResult> {
     params
@@ -52,9 +71,23 @@ pub fn test_contract(
 
     let args = build_test_contract_args(&params);
 
-    match executor.execute(&args) {
+    let env = toolchain::call_env(
+        &params.path,
+        params.toolchain.as_deref(),
+        params.env.as_ref(),
+    )?;
+    match executor
+        .with_timeout_secs(params.timeout_secs)?
+        .with_env_vars(env.as_ref())?
+        .execute(&args)
+        .await
+    {
         Ok(output) => Ok(success_result(format!("Tests completed!\n\n{}", output))),
-        Err(e) => Ok(error_result(format!("Tests failed: {}", e))),
+        Err(e) => {
+            let output = e.to_string();
+            let result = error_result(format!("Tests failed: {}", output));
+            Ok(with_compile_errors(result, "Tests failed", &output))
+        }
     }
 }
 
@@ -67,6 +100,9 @@ mod tests {
         let params = TestContractParams {
             path: String::new(),
             e2e: false,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
         };
         assert!(params.validate().is_err());
     }
@@ -76,6 +112,9 @@ mod tests {
         let params = TestContractParams {
             path: "./my_contract".to_owned(),
             e2e: false,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
         };
         let args = build_test_contract_args(&params);
         assert_eq!(args, vec!["test", "--path", "./my_contract"]);
@@ -86,6 +125,9 @@ mod tests {
         let params = TestContractParams {
             path: "./my_contract".to_owned(),
             e2e: true,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
         };
         let args = build_test_contract_args(&params);
         assert_eq!(args, vec!["test", "--path", "./my_contract", "--e2e"]);
@@ -1,93 +1,83 @@
 //! Chain/node management (pop up ink-node)
 
-use rmcp::model::{CallToolResult, Content};
+use rmcp::model::CallToolResult;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::error::PopMcpResult;
-use crate::executor::PopExecutor;
-use crate::tools::common::error_result;
+use crate::executor::CommandExecutor;
+use crate::outputs::NodeInfo;
+use crate::parsing;
+use crate::tools::clean::stop_pids;
+use crate::tools::common::{error_result, success_texts, with_structured};
 
 /// Parameters for the up_ink_node tool.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 #[schemars(extend("properties" = {}))]
 pub struct UpInkNodeParams {
     /// The port to be used for the ink! node (default: 9944).
+    #[schemars(
+        description = "WebSocket port of the ink! node (default: 9944); pick another when several nodes run side by side"
+    )]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ink_node_port: Option<u16>,
     /// The port to be used for the Ethereum RPC node (default: 8545).
+    #[schemars(
+        description = "Port of the Ethereum RPC (eth-rpc) node launched alongside it (default: 8545)"
+    )]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub eth_rpc_port: Option<u16>,
+    /// Name to register the node under.
+    #[schemars(
+        description = "Name to register the node under for list_nodes, select_node and stop_node (default: 'node-<port>')"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Keep the node running after the server exits (default: false).
+    #[schemars(
+        description = "Keep the node running after the server exits (default: false, the node is stopped with the server)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detach: Option<bool>,
 }
 
-/// Parse the output to extract WebSocket URL.
+/// Stop whatever node a cancelled launch got far enough to start.
 ///
-/// Looks for lines like `url: ws://localhost:9944/` in the output.
-fn parse_ws_url(output: &str) -> Option<String> {
-    for line in output.lines() {
-        let cleaned = strip_ansi(line);
-        // Strip common prefixes (pipe chars from formatted output)
-        let trimmed = cleaned.trim().trim_start_matches('│').trim();
-        // Look for "url: ws://..." pattern
-        if trimmed.starts_with("url:") && trimmed.contains("ws://") {
-            if let Some(start) = trimmed.find("ws://") {
-                return Some(trimmed[start..].trim_end_matches('/').to_owned());
-            }
-        }
-    }
-    None
-}
-
-fn strip_ansi(input: &str) -> String {
-    let mut out = String::with_capacity(input.len());
-    let mut chars = input.chars().peekable();
-
-    while let Some(ch) = chars.next() {
-        if ch == '\u{1b}' {
-            if matches!(chars.peek(), Some('[')) {
-                chars.next();
-                for next in chars.by_ref() {
-                    if ('@'..='~').contains(&next) {
-                        break;
-                    }
-                }
-            }
-            continue;
-        }
-        out.push(ch);
+/// The launch may have finished just before the cancellation, or printed its pids
+/// before being killed; either way nobody is left to stop the node.
+async fn stop_cancelled_node<E: CommandExecutor>(
+    executor: &E,
+    outcome: &PopMcpResult<String>,
+) -> CallToolResult {
+    let output = match outcome {
+        Ok(output) => output.clone(),
+        Err(e) => e.to_string(),
+    };
+    let Some(pids) = parsing::pids(&output) else {
+        return error_result("ink-node launch cancelled");
+    };
+    let pid_text = pids
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+    match stop_pids(&executor.detached(), &pids).await {
+        Ok(result) if result.is_error != Some(true) => error_result(format!(
+            "ink-node launch cancelled; stopped pids {}",
+            pid_text
+        )),
+        _ => error_result(format!(
+            "ink-node launch cancelled; failed to stop pids {}",
+            pid_text
+        )),
     }
-    out
-}
-
-/// Parse the output to extract PIDs from the `kill -9` hint.
-fn parse_pids(output: &str) -> Option<Vec<u32>> {
-    for line in output.lines() {
-        let trimmed = line.trim().trim_start_matches('│').trim();
-        if let Some(start) = trimmed.find("kill -9") {
-            let after = &trimmed[start + "kill -9".len()..];
-            let mut pids = Vec::new();
-            for token in after.split_whitespace() {
-                let token = token.trim_matches(|c: char| !c.is_ascii_digit());
-                if token.is_empty() {
-                    continue;
-                }
-                if let Ok(pid) = token.parse::<u32>() {
-                    pids.push(pid);
-                }
-            }
-            if !pids.is_empty() {
-                return Some(pids);
-            }
-        }
-    }
-    None
 }
 
 /// Execute up_ink_node tool (pop up ink-node)
 ///
 /// Returns the websocket URL on success (e.g., "ws://localhost:9944")
-pub fn up_ink_node(
-    executor: &PopExecutor,
+pub async fn up_ink_node<E: CommandExecutor>(
+    executor: &E,
     params: UpInkNodeParams,
 ) -> PopMcpResult<CallToolResult> {
     let mut args = vec!["up", "ink-node", "-y", "--detach"];
@@ -106,61 +96,41 @@ pub fn up_ink_node(
         args.push(&eth_port_str);
     }
 
-    match executor.execute(&args) {
-        Ok(output) => match parse_ws_url(&output) {
-            Some(url) => {
-                let mut content = vec![Content::text(url)];
-                if let Some(pids) = parse_pids(&output) {
-                    let pid_text = pids
+    let outcome = executor.execute_json(&args).await;
+    if executor.is_cancelled() {
+        return Ok(stop_cancelled_node(executor, &outcome).await);
+    }
+
+    match outcome {
+        Ok(output) => match NodeInfo::parse(&output) {
+            Some(node) => {
+                let mut content = vec![node.url.clone()];
+                if !node.pids.is_empty() {
+                    let pid_text = node
+                        .pids
                         .iter()
                         .map(ToString::to_string)
                         .collect::<Vec<_>>()
                         .join(" ");
-                    content.push(Content::text(format!("pids: {}", pid_text)));
+                    content.push(format!("pids: {}", pid_text));
+                }
+                let ports = [("ink-node", node.port), ("eth-rpc", node.eth_rpc_port)]
+                    .into_iter()
+                    .filter_map(|(name, port)| port.map(|port| format!("{} {}", name, port)))
+                    .collect::<Vec<_>>();
+                if !ports.is_empty() {
+                    content.push(format!("ports: {}", ports.join(", ")));
                 }
-                Ok(CallToolResult::success(content))
+                Ok(with_structured(success_texts(content), &node))
             }
             None => Ok(error_result("Failed to parse websocket URL from output")),
         },
+        Err(e) if e.to_string().to_lowercase().contains("address already in use") => {
+            Ok(error_result(format!(
+                "{}\n\nThe port is taken, possibly by another node: pass a free ink_node_port (and eth_rpc_port).",
+                e
+            )))
+        }
         Err(e) => Ok(error_result(e.to_string())),
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn parse_ws_url_extracts_localhost_url() {
-        let output = r#"
-┌   Pop CLI : Launch a local Ink! node
-│
-⚙  Local node started successfully:
-│  portal: https://polkadot.js.org/apps/?rpc=ws://localhost:9944/#/explorer
-│  url: ws://localhost:9944/
-│  logs: tail -f /var/folders/32/t119h4g16mq5jrlm7f4_shhm0000gp/T/.tmpDGAoYa
-│
-⚙  Ethereum RPC node started successfully:
-│  url: ws://localhost:8545
-│  logs: tail -f /var/folders/32/t119h4g16mq5jrlm7f4_shhm0000gp/T/.tmptLAPcC
-│
-└  ✅ Ink! node bootstrapped successfully. Run `kill -9 11040 11253` to terminate it.
-"#;
-        let url = parse_ws_url(output);
-        assert_eq!(url, Some("ws://localhost:9944".to_owned()));
-    }
-
-    #[test]
-    fn parse_ws_url_returns_none_when_missing() {
-        let output = "Some error occurred";
-        let url = parse_ws_url(output);
-        assert_eq!(url, None);
-    }
-
-    #[test]
-    fn parse_ws_url_strips_ansi() {
-        let output = "\u{1b}[2m│  url: ws://localhost:9944/\u{1b}[0m";
-        let url = parse_ws_url(output);
-        assert_eq!(url, Some("ws://localhost:9944".to_owned()));
-    }
-}
@@ -4,9 +4,18 @@ use rmcp::model::CallToolResult;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::endpoints::execute_with_failover;
 use crate::error::{PopMcpError, PopMcpResult};
-use crate::executor::PopExecutor;
-use crate::tools::common::{error_result, success_result};
+use crate::executor::CommandExecutor;
+use crate::outputs::DeployOutput;
+use crate::parsing;
+use crate::tools::call::account::run_map_account;
+use crate::tools::ci::{self, CiReport, CiStage};
+use crate::tools::common::{
+    dry_run_result, error_result, load_args_file, success_result, summary_block, with_note,
+    with_structured, with_summary,
+};
+use crate::wallet;
 
 /// Parameters for the deploy_contract tool.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
@@ -27,15 +36,91 @@ pub struct DeployContractParams {
         deserialize_with = "crate::tools::common::deserialize_stringy_bool"
     )]
     pub args: Option<String>,
+    /// JSON/TOML file with constructor arguments, as an alternative to `args`.
+    #[schemars(
+        description = "Constructor arguments from a JSON array or TOML `args = [...]` file inside the project (relative to path); use instead of args for large values"
+    )]
+    pub args_file: Option<String>,
     /// Initial balance to transfer to the contract (in tokens).
     #[schemars(description = "Initial balance to transfer to the contract (in tokens)")]
     pub value: Option<String>,
+    /// Gas (`ref_time`) limit of the instantiation.
+    #[schemars(
+        description = "Maximum gas (ref_time) for the instantiation, passed as --gas; set together with proof_size to skip the dry-run estimation (e.g. when it fails on a congested testnet)"
+    )]
+    pub gas_limit: Option<u64>,
+    /// Proof size limit of the instantiation.
+    #[schemars(
+        description = "Maximum proof size for the instantiation, passed as --proof-size; set together with gas_limit"
+    )]
+    pub proof_size: Option<u64>,
     /// Whether to submit an extrinsic for on-chain execution.
     #[schemars(description = "Submit an extrinsic for on-chain execution")]
     pub execute: Option<bool>,
+    /// Upload the code without instantiating a contract.
+    #[schemars(
+        description = "Only upload the contract code and return its code hash, without instantiating (pop up --upload-only), so an instance can be created later or from another account. Not allowed with constructor, args, args_file, value, gas_limit, proof_size, salt or ci_mode"
+    )]
+    pub upload_only: Option<bool>,
     /// WebSocket URL of the node.
     #[schemars(description = "WebSocket URL of the node")]
     pub url: Option<String>,
+    /// Kill the command after this many seconds (default: no timeout).
+    #[schemars(
+        description = "Kill the command after this many seconds and return its partial output (default: no timeout)"
+    )]
+    pub timeout_secs: Option<u64>,
+    /// Put a compact summary block before the full output.
+    #[schemars(
+        description = "Return a compact 'key: value' summary block as the first content item and the full output as the second"
+    )]
+    pub summary: Option<bool>,
+    /// Return the resolved command without running it.
+    #[schemars(
+        description = "Return the fully-resolved pop command (URL fallback and signer applied, secrets masked) without running it, so it can be reviewed first"
+    )]
+    pub dry_run: Option<bool>,
+    /// Run as a CI deployment step.
+    #[schemars(
+        description = "CI mode: never prompt or fall back to the selected node or other endpoints; requires url, signer, salt and expected_code_hash, verifies the artifact's code hash before and after deploying, and returns a single JSON report (failures included)"
+    )]
+    pub ci_mode: Option<bool>,
+    /// Keystore alias of the signer.
+    #[schemars(
+        description = "Keystore alias of the signer: its SURI is read from the POP_MCP_KEY_<ALIAS> environment variable instead of PRIVATE_KEY (required in ci_mode)"
+    )]
+    pub signer: Option<String>,
+    /// Sign with a browser wallet instead of a SURI.
+    #[schemars(
+        description = "Sign with a browser wallet extension instead of PRIVATE_KEY (pop --use-wallet): pop serves a local signing portal and waits; its URL is sent as a progress notification and a 'wallet' log message and ends the result. Only over stdio. Requires execute=true; not allowed with signer or ci_mode"
+    )]
+    pub use_wallet: Option<bool>,
+    /// Map the signer's account and retry when a revive chain reports it unmapped.
+    #[schemars(
+        description = "When a revive chain rejects the deployment because the signer's account is not mapped (AccountUnmapped), map it with Revive.map_account and retry once. Without it, the error suggests the map_account tool"
+    )]
+    pub map_account: Option<bool>,
+    /// Salt for the contract address.
+    #[schemars(
+        description = "Salt used in address derivation, making the address deterministic (required in ci_mode)"
+    )]
+    pub salt: Option<String>,
+    /// Code hash the built artifact must have.
+    #[schemars(
+        description = "Code hash (0x-prefixed) the built contract must have; the deployment is refused otherwise (required in ci_mode)"
+    )]
+    pub expected_code_hash: Option<String>,
+}
+
+/// Pop only skips the dry-run estimation when both weight limits are given.
+fn check_weight_limits(params: &DeployContractParams) -> Result<(), String> {
+    if params.gas_limit.is_some() != params.proof_size.is_some() {
+        return Err(
+            "Set gas_limit and proof_size together; pop estimates both when either is missing"
+                .to_owned(),
+        );
+    }
+    Ok(())
 }
 
 /// Build command arguments for deploy_contract
@@ -62,6 +147,25 @@ fn build_deploy_contract_args(
         args.push(value.clone());
     }
 
+    if let Some(gas) = params.gas_limit {
+        args.push("--gas".to_owned());
+        args.push(gas.to_string());
+    }
+
+    if let Some(proof_size) = params.proof_size {
+        args.push("--proof-size".to_owned());
+        args.push(proof_size.to_string());
+    }
+
+    if let Some(ref salt) = params.salt {
+        args.push("--salt".to_owned());
+        args.push(salt.clone());
+    }
+
+    if params.upload_only.unwrap_or(false) {
+        args.push("--upload-only".to_owned());
+    }
+
     if params.execute.unwrap_or(false) {
         args.push("--execute".to_owned());
     }
@@ -79,36 +183,280 @@ fn build_deploy_contract_args(
 }
 
 /// Execute deploy_contract tool
-pub fn deploy_contract(
-    executor: &PopExecutor,
+pub async fn deploy_contract<E: CommandExecutor>(
+    executor: &E,
     params: DeployContractParams,
     stored_url: Option<&str>,
 ) -> PopMcpResult<CallToolResult> {
-    // Read suri from PRIVATE_KEY environment variable
-    let suri = crate::read_private_key_suri();
-    if params.execute.unwrap_or(false) && suri.is_none() {
+    if params.ci_mode.unwrap_or(false) {
+        return Ok(deploy_contract_ci(executor, params).await);
+    }
+    let use_wallet = params.use_wallet.unwrap_or(false);
+    wallet::check(
+        params.use_wallet,
+        params.execute.unwrap_or(false),
+        params.signer.as_deref(),
+    )
+    .map_err(PopMcpError::InvalidInput)?;
+    // Read suri from keystore alias or PRIVATE_KEY environment variable
+    let suri = match params.signer.as_deref() {
+        Some(alias) => Some(ci::signer_suri(Some(alias)).map_err(PopMcpError::InvalidInput)?),
+        None if use_wallet => None,
+        None => crate::read_private_key_suri(),
+    };
+    if params.execute.unwrap_or(false) && !use_wallet && suri.is_none() {
         return Err(PopMcpError::InvalidInput(
             "PRIVATE_KEY environment variable is required when execute=true".to_owned(),
         ));
     }
+    if params.upload_only.unwrap_or(false) {
+        let instantiation: Vec<&str> = [
+            ("constructor", params.constructor.is_some()),
+            ("args", params.args.is_some()),
+            ("args_file", params.args_file.is_some()),
+            ("value", params.value.is_some()),
+            ("salt", params.salt.is_some()),
+            ("gas_limit", params.gas_limit.is_some()),
+            ("proof_size", params.proof_size.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(name, set)| set.then_some(name))
+        .collect();
+        if !instantiation.is_empty() {
+            return Err(PopMcpError::InvalidInput(format!(
+                "upload_only does not instantiate the contract: remove {}",
+                instantiation.join(", ")
+            )));
+        }
+    }
+    check_weight_limits(&params).map_err(PopMcpError::InvalidInput)?;
+    let file_args = match params.args_file.as_deref() {
+        Some(_) if params.args.is_some() => {
+            return Err(PopMcpError::InvalidInput(
+                "Provide either 'args' or 'args_file', not both".to_owned(),
+            ));
+        }
+        Some(file) => load_args_file(executor.working_dir(), &params.path, file)
+            .map_err(PopMcpError::InvalidInput)?,
+        None => Vec::new(),
+    };
     let mut args = build_deploy_contract_args(&params, stored_url);
-    if params.execute.unwrap_or(false) {
-        if let Some(suri) = suri {
+    if !file_args.is_empty() {
+        args.push("--args".to_owned());
+        args.extend(file_args);
+    }
+    if use_wallet {
+        args.push(wallet::FLAG.to_owned());
+    } else if params.execute.unwrap_or(false) {
+        if let Some(suri) = &suri {
             args.push("--suri".to_owned());
-            args.push(suri);
+            args.push(suri.clone());
+        }
+    }
+    if params.dry_run.unwrap_or(false) {
+        return Ok(dry_run_result(&args));
+    }
+    let executor = executor.with_timeout_secs(params.timeout_secs)?;
+    let (mut outcome, mut note) = execute_with_failover(&executor, &args, true).await;
+    let mut mapping_note = None;
+    if unmapped(&outcome) {
+        let url = params.url.as_deref().or(stored_url);
+        mapping_note = Some(match (params.map_account.unwrap_or(false), url, &suri) {
+            (true, Some(url), Some(suri)) => {
+                match run_map_account(&executor, url, suri).await.0 {
+                    Ok(_) => {
+                        (outcome, note) = execute_with_failover(&executor, &args, true).await;
+                        "The signer's account was not mapped: mapped it (Revive.map_account) and retried the deployment.".to_owned()
+                    }
+                    Err(e) => format!("The signer's account is not mapped and mapping it failed: {}", e),
+                }
+            }
+            _ => "The signer's account is not mapped to an Ethereum address, which this chain requires. Run map_account with the same signer, or pass map_account=true to map it and retry.".to_owned(),
+        });
+    }
+    let summary = params
+        .summary
+        .unwrap_or(false)
+        .then(|| deploy_summary(&params, stored_url, &outcome));
+    let result = match outcome {
+        Ok(output) if params.upload_only.unwrap_or(false) => match parsing::code_hash(&output) {
+            Some(hash) => success_result(format!(
+                "Code uploaded without instantiating.\nCode hash: {}\n\n{}",
+                hash, output
+            )),
+            None => success_result(output),
+        },
+        Ok(output) => match DeployOutput::parse(&output) {
+            Some(deployed) => with_structured(
+                success_result(format!(
+                    "Contract deployed!\n{}\n\n{}",
+                    deployed.describe(),
+                    output
+                )),
+                &deployed,
+            ),
+            None => success_result(output),
+        },
+        Err(e) => error_result(format!("Deployment failed:\n\n{}", e)),
+    };
+    let result = with_note(with_note(result, note), mapping_note);
+    Ok(match summary {
+        Some(summary) => with_summary(result, summary),
+        None => result,
+    })
+}
+
+/// Deploy as a CI step: validate, verify the artifact, deploy, verify the result.
+async fn deploy_contract_ci<E: CommandExecutor>(
+    executor: &E,
+    params: DeployContractParams,
+) -> CallToolResult {
+    let mut report = CiReport::new("deploy_contract", params.signer.as_deref());
+    let suri = match ci::signer_suri(params.signer.as_deref()) {
+        Ok(suri) => suri,
+        Err(e) => return report.fail(CiStage::Validate, e),
+    };
+    if params.use_wallet.unwrap_or(false) {
+        return report.fail(CiStage::Validate, wallet::CI_ERROR);
+    }
+    let missing: Vec<&str> = [
+        ("url", params.url.is_none()),
+        ("salt", params.salt.is_none()),
+        ("expected_code_hash", params.expected_code_hash.is_none()),
+    ]
+    .into_iter()
+    .filter_map(|(name, missing)| missing.then_some(name))
+    .collect();
+    if !missing.is_empty() {
+        return report.fail(
+            CiStage::Validate,
+            format!("ci_mode requires {}", missing.join(", ")),
+        );
+    }
+    if params.dry_run.unwrap_or(false) {
+        return report.fail(CiStage::Validate, "dry_run is not available in ci_mode");
+    }
+    if let Err(e) = check_weight_limits(&params) {
+        return report.fail(CiStage::Validate, e);
+    }
+    if params.upload_only.unwrap_or(false) {
+        return report.fail(
+            CiStage::Validate,
+            "upload_only is not available in ci_mode, which verifies an instantiated contract",
+        );
+    }
+    let file_args = match params.args_file.as_deref() {
+        Some(_) if params.args.is_some() => {
+            return report.fail(
+                CiStage::Validate,
+                "Provide either 'args' or 'args_file', not both",
+            );
+        }
+        Some(file) => match load_args_file(executor.working_dir(), &params.path, file) {
+            Ok(args) => args,
+            Err(e) => return report.fail(CiStage::Validate, e),
+        },
+        None => Vec::new(),
+    };
+    let expected = params.expected_code_hash.clone().unwrap_or_default();
+    match ci::artifact_code_hash(&params.path) {
+        Ok(hash) if ci::same_hash(&hash, &expected) => {}
+        Ok(hash) => {
+            return report.fail(
+                CiStage::Verify,
+                format!(
+                    "The built contract has code hash {}, expected {}",
+                    hash, expected
+                ),
+            );
+        }
+        Err(e) => return report.fail(CiStage::Verify, e),
+    }
+
+    let mut args = build_deploy_contract_args(&params, None);
+    if !file_args.is_empty() {
+        args.push("--args".to_owned());
+        args.extend(file_args);
+    }
+    args.push("--suri".to_owned());
+    args.push(suri);
+    let executor = match executor.with_timeout_secs(params.timeout_secs) {
+        Ok(executor) => executor,
+        Err(e) => return report.fail(CiStage::Validate, e.to_string()),
+    };
+    let output = match ci::execute(&executor, &mut report, &args).await {
+        Ok(output) => output,
+        Err(failed) => return failed,
+    };
+    if !params.execute.unwrap_or(false) {
+        return report.succeed(&serde_json::json!({ "executed": false }));
+    }
+    let Some(deployed) = DeployOutput::parse(&output) else {
+        return report.fail(
+            CiStage::Execute,
+            format!("No contract address in the output:\n{}", output),
+        );
+    };
+    if let Some(hash) = deployed.code_hash.as_deref() {
+        if !ci::same_hash(hash, &expected) {
+            return report.fail(
+                CiStage::Verify,
+                format!(
+                    "The chain reports code hash {}, expected {}",
+                    hash, expected
+                ),
+            );
         }
     }
-    let args_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+    report.succeed(&deployed)
+}
 
-    match executor.execute(&args_refs) {
-        Ok(output) => Ok(success_result(output)),
-        Err(e) => Ok(error_result(format!("Deployment failed:\n\n{}", e))),
+/// Whether a deployment failed because the signer's account is not mapped.
+fn unmapped(outcome: &PopMcpResult<String>) -> bool {
+    match outcome {
+        Ok(output) => parsing::account_unmapped(output),
+        Err(e) => parsing::account_unmapped(&e.to_string()),
     }
 }
 
+/// Summary block for a deployment outcome.
+fn deploy_summary(
+    params: &DeployContractParams,
+    stored_url: Option<&str>,
+    outcome: &PopMcpResult<String>,
+) -> String {
+    let url = params.url.as_deref().or(stored_url).map(str::to_owned);
+    let (status, address, code_hash, error) = match outcome {
+        Ok(output) if params.upload_only.unwrap_or(false) => match parsing::code_hash(output) {
+            Some(hash) => ("uploaded", None, Some(hash), None),
+            None => ("dry-run", None, None, None),
+        },
+        Ok(output) => match DeployOutput::parse(output) {
+            Some(deployed) => ("deployed", Some(deployed.address), deployed.code_hash, None),
+            // Dry runs (no --execute) only estimate gas.
+            None => ("dry-run", None, None, None),
+        },
+        Err(e) => ("failed", None, None, Some(e.to_string())),
+    };
+    let tx_hash = outcome
+        .as_ref()
+        .ok()
+        .and_then(|output| parsing::extrinsic_hash(output));
+    summary_block(&[
+        ("status", Some(status.to_owned())),
+        ("contract", Some(params.path.clone())),
+        ("address", address),
+        ("code_hash", code_hash),
+        ("tx_hash", tx_hash),
+        ("url", url),
+        ("error", error),
+    ])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::executor::PopExecutor;
 
     #[test]
     fn build_args_minimal() {
@@ -116,9 +464,22 @@ mod tests {
             path: "./my_contract".to_owned(),
             constructor: None,
             args: None,
+            args_file: None,
             value: None,
+            gas_limit: None,
+            proof_size: None,
             execute: None,
+            upload_only: None,
             url: None,
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
         };
         let args = build_deploy_contract_args(&params, None);
         assert_eq!(
@@ -133,9 +494,22 @@ mod tests {
             path: "./my_contract".to_owned(),
             constructor: Some("new".to_owned()),
             args: Some("100 true".to_owned()),
+            args_file: None,
             value: Some("1000".to_owned()),
+            gas_limit: None,
+            proof_size: None,
             execute: Some(true),
+            upload_only: None,
             url: Some("ws://localhost:9944".to_owned()),
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
         };
         let args = build_deploy_contract_args(&params, None);
         assert_eq!(
@@ -158,6 +532,56 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn weight_limits_are_forwarded_together() -> anyhow::Result<()> {
+        let params = DeployContractParams {
+            path: "./my_contract".to_owned(),
+            constructor: None,
+            args: None,
+            args_file: None,
+            value: None,
+            gas_limit: Some(50_000_000_000),
+            proof_size: Some(1_048_576),
+            execute: None,
+            upload_only: None,
+            url: None,
+            timeout_secs: None,
+            summary: None,
+            dry_run: Some(true),
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
+        };
+        let args = build_deploy_contract_args(&params, None);
+        assert_eq!(
+            args[3..],
+            ["--gas", "50000000000", "--proof-size", "1048576"]
+        );
+        let result = deploy_contract(&PopExecutor::new(), params.clone(), None).await?;
+        assert_eq!(result.is_error, Some(false));
+
+        let gas_only = DeployContractParams {
+            proof_size: None,
+            ..params.clone()
+        };
+        let result = deploy_contract(&PopExecutor::new(), gas_only, None).await;
+        assert!(
+            matches!(result, Err(PopMcpError::InvalidInput(e)) if e.starts_with("Set gas_limit and proof_size together"))
+        );
+        let upload = DeployContractParams {
+            upload_only: Some(true),
+            ..params
+        };
+        let result = deploy_contract(&PopExecutor::new(), upload, None).await;
+        assert!(
+            matches!(result, Err(PopMcpError::InvalidInput(e)) if e.ends_with("remove gas_limit, proof_size"))
+        );
+        Ok(())
+    }
+
     #[test]
     #[allow(clippy::panic)]
     fn deserialize_args_from_json_bool() {
@@ -186,9 +610,22 @@ mod tests {
             path: "./my_contract".to_owned(),
             constructor: None,
             args: None,
+            args_file: None,
             value: None,
+            gas_limit: None,
+            proof_size: None,
             execute: None,
+            upload_only: None,
             url: None,
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
         };
         let args = build_deploy_contract_args(&params, Some("ws://stored:9944"));
         assert_eq!(
@@ -202,4 +639,305 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn deploy_summary_reports_address_and_url() {
+        let params = DeployContractParams {
+            path: "./flipper".to_owned(),
+            constructor: None,
+            args: None,
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: Some(true),
+            upload_only: None,
+            url: None,
+            timeout_secs: None,
+            summary: Some(true),
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
+        };
+        let output =
+            Ok("The contract address is \"0x58\"\nThe contract code hash is \"0x4f\"\nExtrinsic hash: 0x9a".to_owned());
+        assert_eq!(
+            deploy_summary(&params, Some("ws://localhost:9944"), &output),
+            "status: deployed\ncontract: ./flipper\naddress: 0x58\ncode_hash: 0x4f\ntx_hash: 0x9a\nurl: ws://localhost:9944"
+        );
+        let failed = Err(PopMcpError::CommandExecution(
+            "Insufficient balance".to_owned(),
+        ));
+        assert_eq!(
+            deploy_summary(&params, None, &failed),
+            "status: failed\ncontract: ./flipper\nerror: Command execution error: Insufficient balance"
+        );
+    }
+
+    #[tokio::test]
+    async fn unmapped_accounts_are_mapped_on_request() -> anyhow::Result<()> {
+        let executor = crate::test_utils::MockExecutor::new()
+            .on_failure(&["up"], "Error: Module error: Revive::AccountUnmapped")
+            .on(&["call", "chain"], "└  Extrinsic submitted");
+        let params = DeployContractParams {
+            path: "./flipper".to_owned(),
+            constructor: Some("new".to_owned()),
+            args: None,
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: Some(true),
+            upload_only: None,
+            url: Some("ws://localhost:9944".to_owned()),
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: Some("map_account_test".to_owned()),
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
+        };
+        crate::test_utils::set_env("POP_MCP_KEY_MAP_ACCOUNT_TEST", "//Alice");
+        let result = deploy_contract(&executor, params.clone(), None).await?;
+        let texts = crate::tools::common::extract_texts(&result);
+        assert!(texts.iter().any(|text| text.contains("Run map_account")));
+        assert_eq!(executor.calls().len(), 1);
+
+        let mapping = DeployContractParams {
+            map_account: Some(true),
+            ..params
+        };
+        let result = deploy_contract(&executor, mapping, None).await?;
+        let texts = crate::tools::common::extract_texts(&result);
+        assert!(texts.iter().any(|text| text.contains("mapped it")));
+        let commands: Vec<String> = executor
+            .calls()
+            .iter()
+            .map(|c| c.args[..2].join(" "))
+            .collect();
+        assert_eq!(
+            commands,
+            ["up ./flipper", "up ./flipper", "call chain", "up ./flipper"]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn upload_only_returns_the_code_hash() -> anyhow::Result<()> {
+        let executor = crate::test_utils::MockExecutor::new().on(
+            &["up"],
+            "Contract code uploaded. The contract code hash is \"0x4f\"",
+        );
+        let params = DeployContractParams {
+            path: "./flipper".to_owned(),
+            constructor: None,
+            args: None,
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: Some(true),
+            upload_only: Some(true),
+            url: Some("ws://localhost:9944".to_owned()),
+            timeout_secs: None,
+            summary: Some(true),
+            dry_run: None,
+            ci_mode: None,
+            signer: Some("upload_only_test".to_owned()),
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
+        };
+        crate::test_utils::set_env("POP_MCP_KEY_UPLOAD_ONLY_TEST", "//Alice");
+        let result = deploy_contract(&executor, params.clone(), None).await?;
+        let texts = crate::tools::common::extract_texts(&result);
+        assert_eq!(
+            texts[0],
+            "status: uploaded\ncontract: ./flipper\ncode_hash: 0x4f\nurl: ws://localhost:9944"
+        );
+        assert!(texts[1].starts_with("Code uploaded without instantiating.\nCode hash: 0x4f"));
+        assert_eq!(result.structured_content, None);
+        assert!(executor.calls()[0]
+            .args
+            .contains(&"--upload-only".to_owned()));
+
+        let instantiating = DeployContractParams {
+            constructor: Some("new".to_owned()),
+            salt: Some("0x01".to_owned()),
+            ..params
+        };
+        let result = deploy_contract(&executor, instantiating, None).await;
+        assert!(
+            matches!(&result, Err(PopMcpError::InvalidInput(e)) if e.ends_with("remove constructor, salt"))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn deploy_rejects_args_with_args_file() {
+        let params = DeployContractParams {
+            path: "./my_contract".to_owned(),
+            constructor: None,
+            args: Some("true".to_owned()),
+            args_file: Some("args.json".to_owned()),
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: None,
+            upload_only: None,
+            url: None,
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
+        };
+        let result = deploy_contract(&PopExecutor::new(), params, None).await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn deploy_dry_run_returns_command_without_running_it() {
+        let executor = crate::test_utils::MockExecutor::new();
+        let params = DeployContractParams {
+            path: "./flipper".to_owned(),
+            constructor: Some("new".to_owned()),
+            args: Some("false".to_owned()),
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: None,
+            upload_only: None,
+            url: None,
+            timeout_secs: None,
+            summary: None,
+            dry_run: Some(true),
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
+        };
+        let result = deploy_contract(&executor, params, Some("ws://localhost:9944")).await;
+        let text = result.map(|r| crate::tools::common::content_text(&r));
+        assert!(text.is_ok_and(|text| text.contains(
+            "pop up ./flipper -y --constructor new --args false --url ws://localhost:9944"
+        )));
+        assert!(executor.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn use_wallet_signs_instead_of_a_suri() {
+        let executor = crate::test_utils::MockExecutor::new();
+        let mut params = DeployContractParams {
+            path: "./flipper".to_owned(),
+            constructor: Some("new".to_owned()),
+            args: None,
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: Some(true),
+            upload_only: None,
+            url: None,
+            timeout_secs: None,
+            summary: None,
+            dry_run: Some(true),
+            ci_mode: None,
+            signer: None,
+            use_wallet: Some(true),
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
+        };
+        let result = deploy_contract(&executor, params.clone(), Some("ws://localhost:9944")).await;
+        let text = result.map(|r| crate::tools::common::content_text(&r));
+        assert!(text.is_ok_and(|text| text
+            .contains("--execute --url ws://localhost:9944 --use-wallet")
+            && !text.contains("--suri")));
+
+        params.signer = Some("ci-deployer".to_owned());
+        let result = deploy_contract(&executor, params, None).await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+    }
+
+    fn ci_params(path: &str, expected_code_hash: &str) -> DeployContractParams {
+        DeployContractParams {
+            path: path.to_owned(),
+            constructor: Some("new".to_owned()),
+            args: Some("false".to_owned()),
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: Some(true),
+            upload_only: None,
+            url: Some("ws://localhost:9944".to_owned()),
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: Some(true),
+            signer: Some("up-contract-test".to_owned()),
+            use_wallet: None,
+            map_account: None,
+            salt: Some("0x01".to_owned()),
+            expected_code_hash: Some(expected_code_hash.to_owned()),
+        }
+    }
+
+    #[tokio::test]
+    async fn ci_deploy_verifies_the_artifact_and_reports_json() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::create_dir_all(dir.path().join("target/ink"))?;
+        std::fs::write(
+            dir.path().join("target/ink/flipper.json"),
+            r#"{"source": {"hash": "0x4f"}}"#,
+        )?;
+        let path = dir.path().display().to_string();
+        // Unique to this test, so no other test observes it.
+        crate::test_utils::set_env("POP_MCP_KEY_UP_CONTRACT_TEST", "//Bob");
+        let executor = crate::test_utils::MockExecutor::new().on(
+            &["up"],
+            "The contract address is \"0x58\"\nThe contract code hash is \"0x4f\"",
+        );
+
+        let result = deploy_contract(&executor, ci_params(&path, "0x4F"), None).await?;
+        let report: serde_json::Value =
+            serde_json::from_str(&crate::tools::common::content_text(&result))?;
+        assert_eq!(report["ok"], true);
+        assert_eq!(report["address"], "0x58");
+        assert_eq!(report["signer"], "up-contract-test");
+        let command = report["command"].as_str().unwrap_or_default();
+        assert!(command.contains("--salt 0x01 --execute --url ws://localhost:9944 --suri"));
+        assert!(!command.contains("//Bob"));
+
+        let result = deploy_contract(&executor, ci_params(&path, "0x99"), None).await?;
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(
+            result.structured_content.map(|r| r["stage"].clone()),
+            Some("verify".into())
+        );
+        assert_eq!(executor.calls().len(), 1);
+
+        let mut params = ci_params(&path, "0x4f");
+        params.salt = None;
+        params.url = None;
+        let result = deploy_contract(&executor, params, Some("ws://stored:9944")).await?;
+        assert!(crate::tools::common::content_text(&result).contains("ci_mode requires url, salt"));
+        Ok(())
+    }
 }
@@ -0,0 +1,436 @@
+//! Contract instantiation from uploaded code
+//!
+//! Pop CLI uploads code with every deployment (or only uploads it, with
+//! `upload_only`) but cannot instantiate code that is already on chain. This tool
+//! encodes the constructor call from the contract's metadata and submits the
+//! contracts pallet's `instantiate` extrinsic (`Revive`, or `Contracts` on ink! v5
+//! chains) for an existing code hash through `pop call chain --call`.
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::elicit::DEFAULT_CONSTRUCTOR;
+use crate::encoder::{decode_hex, encode_text, fetch_storage, to_hex, ChainMetadata};
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::outputs::{BuildOutput, DeployOutput};
+use crate::parsing;
+use crate::rpc::RpcPool;
+use crate::tools::call::chain::submit_call_data;
+use crate::tools::common::{
+    error_result, extract_texts, success_result, with_note, with_structured,
+};
+
+/// Default `ref_time` of the gas limit (0.1s of execution).
+const DEFAULT_REF_TIME: u64 = 100_000_000_000;
+/// Default `proof_size` of the gas limit (1 MiB).
+const DEFAULT_PROOF_SIZE: u64 = 1024 * 1024;
+
+/// Parameters for the instantiate_from_code_hash tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct InstantiateFromCodeHashParams {
+    /// Path to the built contract directory, for its metadata.
+    #[schemars(
+        description = "Path to the contract directory; its built metadata (target/ink/*.json) describes the constructors"
+    )]
+    pub path: String,
+    /// Code hash of the uploaded code.
+    #[schemars(
+        description = "Code hash (0x-prefixed) of code already on chain, e.g. from deploy_contract with upload_only"
+    )]
+    pub code_hash: String,
+    /// Constructor to call (default: new).
+    #[schemars(description = "Constructor to call (default: 'new')")]
+    pub constructor: Option<String>,
+    /// Constructor arguments, one value per parameter.
+    #[schemars(
+        description = "Constructor arguments, one value per parameter (numbers, 0x hex, true/false, strings, Some(..)/None)"
+    )]
+    pub args: Option<Vec<String>>,
+    /// Balance to transfer to the new contract, in planck.
+    #[schemars(description = "Balance to transfer to the new contract, in planck (default: 0)")]
+    pub value: Option<String>,
+    /// Salt for the contract address.
+    #[schemars(
+        description = "Salt (0x-prefixed, 32 bytes on Revive chains) making the address deterministic, needed for a second instance from the same account"
+    )]
+    pub salt: Option<String>,
+    /// WebSocket URL of the chain.
+    #[schemars(
+        description = "WebSocket URL of the chain (default: the node launched with up_ink_node)"
+    )]
+    pub url: Option<String>,
+    /// `ref_time` of the gas limit.
+    #[schemars(description = "ref_time of the gas limit (default: 100000000000)")]
+    pub ref_time_limit: Option<u64>,
+    /// `proof_size` of the gas limit.
+    #[schemars(description = "proof_size of the gas limit (default: 1048576)")]
+    pub proof_size_limit: Option<u64>,
+    /// Storage deposit limit, in planck.
+    #[schemars(
+        description = "Maximum storage deposit to reserve, in planck (default: no limit beyond the signer's balance)"
+    )]
+    pub storage_deposit_limit: Option<String>,
+    /// Submit the extrinsic; otherwise only the encoded call data is returned.
+    #[schemars(
+        description = "Submit the extrinsic, signed with PRIVATE_KEY; when false only the encoded call data is returned"
+    )]
+    pub execute: Option<bool>,
+}
+
+impl InstantiateFromCodeHashParams {
+    fn validate(&self) -> Result<(), String> {
+        match decode_hex(&self.code_hash) {
+            Ok(bytes) if bytes.len() == 32 => {}
+            _ => {
+                return Err(format!(
+                    "Invalid code_hash '{}': expected 32 bytes of 0x-prefixed hex",
+                    self.code_hash
+                ))
+            }
+        }
+        if let Some(salt) = &self.salt {
+            decode_hex(salt).map_err(|e| format!("Invalid salt '{}': {}", salt, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Selector followed by the SCALE-encoded `args` of `constructor`, per the ink!
+/// metadata `metadata`.
+fn constructor_data(
+    metadata: &Value,
+    constructor: &str,
+    args: &[String],
+) -> Result<Vec<u8>, String> {
+    let types: scale_info::PortableRegistry = serde_json::from_value(metadata.clone())
+        .map_err(|e| format!("The contract metadata has no readable type registry: {}", e))?;
+    let constructors = metadata
+        .pointer("/spec/constructors")
+        .and_then(Value::as_array)
+        .ok_or("The contract metadata lists no constructors")?;
+    let label = |c: &Value| c.get("label").and_then(Value::as_str).map(str::to_owned);
+    let spec = constructors
+        .iter()
+        .find(|c| label(c).as_deref() == Some(constructor))
+        .ok_or_else(|| {
+            let labels: Vec<String> = constructors.iter().filter_map(label).collect();
+            format!(
+                "The contract has no '{}' constructor. Constructors: {}",
+                constructor,
+                labels.join(", ")
+            )
+        })?;
+    let selector = spec
+        .get("selector")
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("Constructor '{}' has no selector", constructor))?;
+    let mut data = decode_hex(selector)?;
+    let params = spec
+        .get("args")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    if params.len() != args.len() {
+        return Err(format!(
+            "Constructor '{}' takes {} argument(s), got {}",
+            constructor,
+            params.len(),
+            args.len()
+        ));
+    }
+    for (param, arg) in params.iter().zip(args) {
+        let name = param.get("label").and_then(Value::as_str).unwrap_or("?");
+        let ty = param
+            .pointer("/type/type")
+            .and_then(Value::as_u64)
+            .and_then(|ty| u32::try_from(ty).ok())
+            .ok_or_else(|| format!("Argument '{}' has no type in the metadata", name))?;
+        let bytes = encode_text(arg, ty, &types).map_err(|e| {
+            format!(
+                "Argument '{}' ('{}') of '{}' could not be encoded: {}",
+                name, arg, constructor, e
+            )
+        })?;
+        data.extend(bytes);
+    }
+    Ok(data)
+}
+
+/// Contracts pallet of the chain: `Revive`, or `Contracts` on ink! v5 chains.
+fn contracts_pallet(metadata: &ChainMetadata) -> Option<&'static str> {
+    ["Revive", "Contracts"]
+        .into_iter()
+        .find(|pallet| metadata.has_call(pallet, "instantiate"))
+}
+
+/// Arguments of `pallet.instantiate` for `data`.
+fn instantiate_args(
+    pallet: &str,
+    params: &InstantiateFromCodeHashParams,
+    data: &[u8],
+) -> Vec<String> {
+    let revive = pallet == "Revive";
+    let weight = format!(
+        "{{ ref_time: {}, proof_size: {} }}",
+        params.ref_time_limit.unwrap_or(DEFAULT_REF_TIME),
+        params.proof_size_limit.unwrap_or(DEFAULT_PROOF_SIZE)
+    );
+    let deposit_limit = match (&params.storage_deposit_limit, revive) {
+        (Some(limit), true) => limit.clone(),
+        (Some(limit), false) => format!("Some({})", limit),
+        (None, true) => u128::MAX.to_string(),
+        (None, false) => "None".to_owned(),
+    };
+    let salt = match (&params.salt, revive) {
+        (Some(salt), true) => format!("Some({})", salt),
+        (Some(salt), false) => salt.clone(),
+        (None, true) => "None".to_owned(),
+        (None, false) => "0x".to_owned(),
+    };
+    vec![
+        params.value.clone().unwrap_or_else(|| "0".to_owned()),
+        weight,
+        deposit_limit,
+        params.code_hash.clone(),
+        to_hex(data),
+        salt,
+    ]
+}
+
+/// Whether code with `code_hash` is stored on the chain, when the pallet says.
+async fn code_exists(
+    pool: &RpcPool,
+    url: &str,
+    metadata: &ChainMetadata,
+    pallet: &str,
+    code_hash: &str,
+) -> Option<bool> {
+    let key = metadata
+        .storage_key(pallet, "CodeInfoOf", &[code_hash.to_owned()])
+        .ok()?;
+    fetch_storage(pool, url, &key)
+        .await
+        .ok()
+        .map(|value| value.is_some())
+}
+
+/// Execute instantiate_from_code_hash tool
+pub async fn instantiate_from_code_hash<E: CommandExecutor>(
+    executor: &E,
+    pool: &RpcPool,
+    params: InstantiateFromCodeHashParams,
+    stored_url: Option<&str>,
+) -> PopMcpResult<CallToolResult> {
+    params.validate().map_err(PopMcpError::InvalidInput)?;
+    let url = params
+        .url
+        .clone()
+        .or_else(|| stored_url.map(str::to_owned))
+        .ok_or_else(|| {
+            PopMcpError::InvalidInput(
+                "url is required when no node was launched with up_ink_node".to_owned(),
+            )
+        })?;
+    let Some(file) = BuildOutput::collect(&params.path, false).metadata else {
+        return Ok(error_result(format!(
+            "No contract metadata in {}/target/ink; build the contract first with build_contract",
+            params.path
+        )));
+    };
+    let contract: Value = match std::fs::read_to_string(&file.path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+    {
+        Ok(contract) => contract,
+        Err(e) => return Ok(error_result(format!("Failed to read {}: {}", file.path, e))),
+    };
+    let constructor = params.constructor.as_deref().unwrap_or(DEFAULT_CONSTRUCTOR);
+    let data = match constructor_data(
+        &contract,
+        constructor,
+        params.args.as_deref().unwrap_or_default(),
+    ) {
+        Ok(data) => data,
+        Err(e) => return Ok(error_result(format!("Instantiation failed: {}", e))),
+    };
+
+    let metadata = match ChainMetadata::fetch(pool, &url).await {
+        Ok(metadata) => metadata,
+        Err(e) => return Ok(error_result(format!("Instantiation failed: {}", e))),
+    };
+    let Some(pallet) = contracts_pallet(&metadata) else {
+        return Ok(error_result(
+            "Instantiation failed: the chain has no contracts pallet (Revive or Contracts)",
+        ));
+    };
+    if code_exists(pool, &url, &metadata, pallet, &params.code_hash).await == Some(false) {
+        return Ok(error_result(format!(
+            "Instantiation failed: no code with hash {} on the chain; upload it first with deploy_contract (upload_only)",
+            params.code_hash
+        )));
+    }
+    let call_data = match metadata.encode_call(
+        pallet,
+        "instantiate",
+        &instantiate_args(pallet, &params, &data),
+    ) {
+        Ok(encoded) => to_hex(&encoded),
+        Err(e) => return Ok(error_result(format!("Instantiation failed: {}", e))),
+    };
+    let metadata_hash = contract.pointer("/source/hash").and_then(Value::as_str);
+    let note = metadata_hash
+        .filter(|hash| !hash.eq_ignore_ascii_case(&params.code_hash))
+        .map(|hash| {
+            format!(
+                "Note: the metadata in {} is for code hash {}; check that its constructors match the uploaded code.",
+                file.path, hash
+            )
+        });
+
+    if !params.execute.unwrap_or(false) {
+        let text = format!(
+            "Encoded {}.instantiate of {} with {}:\n{}",
+            pallet, params.code_hash, constructor, call_data
+        );
+        return Ok(with_note(success_result(text), note));
+    }
+
+    let result = submit_call_data(executor, &url, &call_data, false).await?;
+    if result.is_error == Some(true) {
+        return Ok(result);
+    }
+    let output = extract_texts(&result).join("\n");
+    let result = match parsing::instantiated_address(&output) {
+        Some(address) => with_structured(
+            success_result(format!(
+                "Contract instantiated from code hash {}.\nAddress: {}\n\n{}",
+                params.code_hash, address, output
+            )),
+            &DeployOutput {
+                address,
+                code_hash: Some(params.code_hash),
+                tx_hash: parsing::extrinsic_hash(&output),
+                explorer_url: None,
+            },
+        ),
+        None => success_result(format!(
+            "Instantiation submitted; no Instantiated event in the output.\n\n{}",
+            output
+        )),
+    };
+    Ok(with_note(result, note))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ink! metadata of a flipper-like contract with `new(init_value: bool)`.
+    fn contract() -> Value {
+        serde_json::json!({
+            "source": { "hash": "0xab" },
+            "spec": {
+                "constructors": [
+                    {
+                        "label": "new",
+                        "selector": "0x9bae9d5e",
+                        "args": [{ "label": "init_value", "type": { "displayName": ["bool"], "type": 0 } }]
+                    },
+                    { "label": "default", "selector": "0xed4b9d1b", "args": [] }
+                ],
+                "messages": []
+            },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "bool" } } }
+            ]
+        })
+    }
+
+    fn params() -> InstantiateFromCodeHashParams {
+        InstantiateFromCodeHashParams {
+            path: "./flipper".to_owned(),
+            code_hash: format!("0x{}", "4f".repeat(32)),
+            constructor: None,
+            args: None,
+            value: None,
+            salt: None,
+            url: None,
+            ref_time_limit: None,
+            proof_size_limit: None,
+            storage_deposit_limit: None,
+            execute: None,
+        }
+    }
+
+    #[test]
+    fn constructor_data_is_selector_and_args() {
+        assert_eq!(
+            constructor_data(&contract(), "new", &["true".to_owned()]),
+            Ok(vec![0x9b, 0xae, 0x9d, 0x5e, 1])
+        );
+        assert_eq!(
+            constructor_data(&contract(), "default", &[]),
+            Ok(vec![0xed, 0x4b, 0x9d, 0x1b])
+        );
+        assert_eq!(
+            constructor_data(&contract(), "new", &[]),
+            Err("Constructor 'new' takes 1 argument(s), got 0".to_owned())
+        );
+        assert_eq!(
+            constructor_data(&contract(), "other", &[]),
+            Err("The contract has no 'other' constructor. Constructors: new, default".to_owned())
+        );
+    }
+
+    #[test]
+    fn instantiate_args_follow_the_pallet() {
+        let mut params = params();
+        let revive = instantiate_args("Revive", &params, &[0x9b, 1]);
+        assert_eq!(revive[1], "{ ref_time: 100000000000, proof_size: 1048576 }");
+        assert_eq!(revive[2], u128::MAX.to_string());
+        assert_eq!(revive[4], "0x9b01");
+        assert_eq!(revive[5], "None");
+
+        params.salt = Some("0x01".to_owned());
+        params.storage_deposit_limit = Some("1000".to_owned());
+        let contracts = instantiate_args("Contracts", &params, &[]);
+        assert_eq!(contracts[2], "Some(1000)");
+        assert_eq!(contracts[5], "0x01");
+        assert_eq!(instantiate_args("Revive", &params, &[])[5], "Some(0x01)");
+    }
+
+    #[test]
+    fn validate_checks_the_code_hash_and_salt() {
+        let mut params = params();
+        assert!(params.validate().is_ok());
+        params.code_hash = "0x4f".to_owned();
+        assert!(params.validate().is_err());
+        params = self::params();
+        params.salt = Some("salt".to_owned());
+        assert!(params.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn unbuilt_contracts_are_reported() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let params = InstantiateFromCodeHashParams {
+            path: dir.path().display().to_string(),
+            url: Some("ws://localhost:9944".to_owned()),
+            ..params()
+        };
+        let executor = crate::test_utils::MockExecutor::new();
+        let result = instantiate_from_code_hash(&executor, &RpcPool::new(), params, None).await?;
+        assert_eq!(result.is_error, Some(true));
+        assert!(extract_texts(&result)[0].contains("build the contract first"));
+        assert!(
+            instantiate_from_code_hash(&executor, &RpcPool::new(), self::params(), None)
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
+}
@@ -3,8 +3,14 @@
 //! Submodules:
 //! - `contract` - Contract deployment (`pop up <contract>`)
 //! - `chain` - Chain/node management (pop up ink-node)
+//! - `instantiate` - Contract instantiation from code already on chain
 //! - `network` - Network management (pop up network)
+//! - `profile` - Named network profiles (saved up_network parameters)
+//! - `testnet` - One-shot contract deployment to PassetHub
 
 pub mod chain;
 pub mod contract;
+pub mod instantiate;
 pub mod network;
+pub mod profile;
+pub mod testnet;
@@ -1,7 +1,10 @@
 //! Network management (pop up network)
 
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
 use crate::error::{PopMcpError, PopMcpResult};
-use crate::executor::PopExecutor;
+use crate::executor::CommandExecutor;
 use crate::tools::common::{error_result, success_result};
 use rmcp::model::CallToolResult;
 use schemars::JsonSchema;
@@ -34,6 +37,66 @@ pub struct UpNetworkParams {
     )]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parachain: Option<Vec<String>>,
+    /// Changes merged into a temporary copy of the config at `path`.
+    #[schemars(
+        description = "Changes merged into a temporary copy of the TOML config at 'path' before launch, so small variations need no file edits"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overrides: Option<NetworkOverrides>,
+    /// Keep the network running after the server exits (default: false).
+    #[schemars(
+        description = "Keep the network running after the server exits (default: false, the network is stopped with the server)"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detach: Option<bool>,
+}
+
+/// Changes to a Zombienet TOML config.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct NetworkOverrides {
+    /// Relay chain to use instead of the config's.
+    #[schemars(description = "Relay chain spec name to use (e.g., 'paseo-local')")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relay_chain: Option<String>,
+    /// Arguments appended to every parachain collator's `args`.
+    #[schemars(
+        description = "Arguments appended to every parachain collator (e.g., [\"-lruntime=debug\"])"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collator_args: Option<Vec<String>>,
+    /// Runtime (wasm) overrides, by `relay` or parachain id.
+    #[schemars(
+        description = "Runtime wasm to run instead of the chain spec's, by 'relay' or parachain id (e.g., {\"1000\": \"./runtime.wasm\"}); ids are those of the original config"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runtime_overrides: Option<BTreeMap<String, String>>,
+    /// New ids for parachains, by their id in the config.
+    #[schemars(
+        description = "New parachain ids by current id (e.g., {\"1000\": 2000}); HRMP channels follow"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub para_ids: Option<BTreeMap<String, u32>>,
+}
+
+impl NetworkOverrides {
+    /// Validate the override keys.
+    fn validate(&self) -> Result<(), String> {
+        for target in self.runtime_overrides.iter().flat_map(BTreeMap::keys) {
+            if target != "relay" && target.parse::<u32>().is_err() {
+                return Err(format!(
+                    "Runtime override target '{}' must be 'relay' or a parachain id",
+                    target
+                ));
+            }
+        }
+        for id in self.para_ids.iter().flat_map(BTreeMap::keys) {
+            if id.parse::<u32>().is_err() {
+                return Err(format!("'{}' in para_ids is not a parachain id", id));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl UpNetworkParams {
@@ -41,7 +104,14 @@ impl UpNetworkParams {
     ///
     /// - Exactly one of `path` or `chain` must be provided.
     /// - If `chain` is provided, it must be one of the known chains (case-insensitive).
-    fn validate(&self) -> Result<(), String> {
+    /// - `overrides` need a `path`.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if let Some(overrides) = &self.overrides {
+            if self.path.is_none() {
+                return Err("'overrides' apply to a config file: use them with 'path'".to_owned());
+            }
+            overrides.validate()?;
+        }
         if let Some(parachains) = &self.parachain {
             if parachains.is_empty() {
                 return Err("Parachain list cannot be empty".to_owned());
@@ -132,23 +202,181 @@ fn build_up_network_args<'a>(
     args
 }
 
+/// Parachain tables of `config`.
+fn parachains(config: &mut toml::Table) -> impl Iterator<Item = &mut toml::Table> {
+    config
+        .get_mut("parachains")
+        .and_then(toml::Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_table_mut)
+}
+
+/// The parachain with `id` in `config`.
+fn parachain(config: &mut toml::Table, id: u32) -> Result<&mut toml::Table, String> {
+    parachains(config)
+        .find(|para| para.get("id").and_then(toml::Value::as_integer) == Some(i64::from(id)))
+        .ok_or_else(|| format!("The config has no parachain with id {}", id))
+}
+
+/// The relay chain table of `config`, created if missing.
+fn relaychain(config: &mut toml::Table) -> Result<&mut toml::Table, String> {
+    config
+        .entry("relaychain")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        .as_table_mut()
+        .ok_or_else(|| "'relaychain' is not a table".to_owned())
+}
+
+/// Merge `overrides` into `config`.
+fn apply_overrides(config: &mut toml::Table, overrides: &NetworkOverrides) -> Result<(), String> {
+    if let Some(chain) = &overrides.relay_chain {
+        relaychain(config)?.insert("chain".to_owned(), chain.clone().into());
+    }
+    for (target, wasm) in overrides.runtime_overrides.iter().flatten() {
+        let table = match target.parse::<u32>() {
+            Ok(id) => parachain(config, id)?,
+            Err(_) => relaychain(config)?,
+        };
+        table.insert("wasm_override".to_owned(), wasm.clone().into());
+    }
+    if let Some(extra) = &overrides.collator_args {
+        for para in parachains(config) {
+            // Collators are a `collators` array or a single `collator` table.
+            let collators = para
+                .iter_mut()
+                .filter(|(key, _)| *key == "collators" || *key == "collator")
+                .flat_map(|(_, value)| match value {
+                    toml::Value::Array(collators) => collators
+                        .iter_mut()
+                        .filter_map(toml::Value::as_table_mut)
+                        .collect(),
+                    toml::Value::Table(collator) => vec![collator],
+                    _ => Vec::new(),
+                });
+            for collator in collators {
+                let args = collator
+                    .entry("args")
+                    .or_insert_with(|| toml::Value::Array(Vec::new()));
+                if let Some(args) = args.as_array_mut() {
+                    args.extend(extra.iter().map(|arg| toml::Value::from(arg.clone())));
+                }
+            }
+        }
+    }
+    if let Some(remap) = &overrides.para_ids {
+        let remap: Vec<(i64, i64)> = remap
+            .iter()
+            .filter_map(|(old, new)| Some((old.parse::<i64>().ok()?, i64::from(*new))))
+            .collect();
+        // Look every parachain up first, so a swap of two ids is applied at once.
+        let mut found = Vec::new();
+        for (old, new) in &remap {
+            let index = config
+                .get("parachains")
+                .and_then(toml::Value::as_array)
+                .and_then(|paras| {
+                    paras.iter().position(|para| {
+                        para.get("id").and_then(toml::Value::as_integer) == Some(*old)
+                    })
+                })
+                .ok_or_else(|| format!("The config has no parachain with id {}", old))?;
+            found.push((index, *new));
+        }
+        for (index, para) in parachains(config).enumerate() {
+            if let Some((_, new)) = found.iter().find(|(i, _)| *i == index) {
+                para.insert("id".to_owned(), (*new).into());
+            }
+        }
+        let channels = config
+            .get_mut("hrmp_channels")
+            .and_then(toml::Value::as_array_mut)
+            .into_iter()
+            .flatten()
+            .filter_map(toml::Value::as_table_mut);
+        for channel in channels {
+            for end in ["sender", "recipient"] {
+                let id = channel.get(end).and_then(toml::Value::as_integer);
+                if let Some((_, new)) = remap.iter().find(|(old, _)| Some(*old) == id) {
+                    channel.insert(end.to_owned(), (*new).into());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The config at `path` with `overrides` (if any) applied, rendered as TOML.
+pub(crate) async fn overridden_config(
+    path: &str,
+    overrides: Option<&NetworkOverrides>,
+) -> Result<String, String> {
+    let contents = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let mut config: toml::Table =
+        toml::from_str(&contents).map_err(|e| format!("{} is not valid TOML: {}", path, e))?;
+    if let Some(overrides) = overrides {
+        apply_overrides(&mut config, overrides)?;
+    }
+    toml::to_string(&config).map_err(|e| e.to_string())
+}
+
+/// Write `path` with `overrides` applied to a hidden sibling file, so paths relative
+/// to the config keep working. Returns the copy's path.
+async fn write_overridden_config(
+    path: &str,
+    overrides: &NetworkOverrides,
+) -> Result<PathBuf, String> {
+    let rendered = overridden_config(path, Some(overrides)).await?;
+    let original = Path::new(path);
+    let stem = original
+        .file_stem()
+        .map_or_else(|| "network".into(), |stem| stem.to_string_lossy());
+    let copy = original.with_file_name(format!(".{}.pop-mcp-{}.toml", stem, std::process::id()));
+    tokio::fs::write(&copy, rendered)
+        .await
+        .map_err(|e| format!("Failed to write {}: {}", copy.display(), e))?;
+    Ok(copy)
+}
+
 /// Execute up_network tool (pop up network / `pop up <chain>`).
 ///
 /// Returns the Pop CLI output directly, which includes the zombie.json path
 /// and network status.
-pub fn up_network(executor: &PopExecutor, params: UpNetworkParams) -> PopMcpResult<CallToolResult> {
+pub async fn up_network<E: CommandExecutor>(
+    executor: &E,
+    mut params: UpNetworkParams,
+) -> PopMcpResult<CallToolResult> {
     params.validate().map_err(PopMcpError::InvalidInput)?;
 
+    let copy = match (&params.path, &params.overrides) {
+        (Some(path), Some(overrides)) => match write_overridden_config(path, overrides).await {
+            Ok(copy) => Some(copy),
+            Err(e) => return Ok(error_result(format!("Failed to apply overrides: {}", e))),
+        },
+        _ => None,
+    };
+    if let Some(copy) = &copy {
+        params.path = Some(copy.display().to_string());
+    }
+
     let chain_normalized = params.normalized_chain();
     let args = build_up_network_args(&params, &chain_normalized);
     let args_ref: Vec<&str> = args.iter().map(String::as_str).collect();
-    match executor.execute(&args_ref) {
+    let outcome = executor.execute(&args_ref).await;
+    // The network is detached by now and no longer reads its config.
+    if let Some(copy) = &copy {
+        let _ = tokio::fs::remove_file(copy).await;
+    }
+    match outcome {
         Ok(output) => Ok(success_result(output)),
         Err(e) => Ok(error_result(e.to_string())),
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::panic)]
 mod tests {
     use super::*;
 
@@ -161,6 +389,8 @@ mod tests {
             chain: None,
             verbose: None,
             parachain: None,
+            overrides: None,
+            detach: None,
         };
         assert!(params.validate().is_err());
     }
@@ -172,6 +402,8 @@ mod tests {
             chain: None,
             verbose: None,
             parachain: None,
+            overrides: None,
+            detach: None,
         };
         assert!(params.validate().is_ok());
     }
@@ -184,6 +416,8 @@ mod tests {
                 chain: Some((*chain).to_owned()),
                 verbose: None,
                 parachain: None,
+                overrides: None,
+                detach: None,
             };
             assert!(params.validate().is_ok(), "should accept chain '{}'", chain);
         }
@@ -196,6 +430,8 @@ mod tests {
             chain: Some("PASEO".to_owned()),
             verbose: None,
             parachain: None,
+            overrides: None,
+            detach: None,
         };
         assert!(params.validate().is_ok());
 
@@ -204,6 +440,8 @@ mod tests {
             chain: Some("Kusama".to_owned()),
             verbose: None,
             parachain: None,
+            overrides: None,
+            detach: None,
         };
         assert!(params.validate().is_ok());
     }
@@ -215,6 +453,8 @@ mod tests {
             chain: Some("unknown".to_owned()),
             verbose: None,
             parachain: None,
+            overrides: None,
+            detach: None,
         };
         assert!(params.validate().is_err());
     }
@@ -226,6 +466,8 @@ mod tests {
             chain: Some("paseo".to_owned()),
             verbose: None,
             parachain: None,
+            overrides: None,
+            detach: None,
         };
         assert!(params.validate().is_err());
     }
@@ -237,6 +479,8 @@ mod tests {
             chain: None,
             verbose: None,
             parachain: None,
+            overrides: None,
+            detach: None,
         };
         assert!(params.validate().is_err());
     }
@@ -248,6 +492,8 @@ mod tests {
             chain: Some("paseo".to_owned()),
             verbose: None,
             parachain: Some(vec![]),
+            overrides: None,
+            detach: None,
         };
         assert!(params.validate().is_err());
     }
@@ -259,6 +505,8 @@ mod tests {
             chain: Some("paseo".to_owned()),
             verbose: None,
             parachain: Some(vec![" ".to_owned()]),
+            overrides: None,
+            detach: None,
         };
         assert!(params.validate().is_err());
     }
@@ -273,6 +521,8 @@ mod tests {
                 "asset-hub".to_owned(),
                 "asset-hub#1000:9944".to_owned(),
             ]),
+            overrides: None,
+            detach: None,
         };
         assert!(params.validate().is_ok());
     }
@@ -286,6 +536,8 @@ mod tests {
             chain: None,
             verbose: None,
             parachain: None,
+            overrides: None,
+            detach: None,
         };
         let chain_normalized = params.normalized_chain();
         let args = build_up_network_args(&params, &chain_normalized);
@@ -308,6 +560,8 @@ mod tests {
             chain: Some("PASEO".to_owned()),
             verbose: None,
             parachain: None,
+            overrides: None,
+            detach: None,
         };
         let chain_normalized = params.normalized_chain();
         let args = build_up_network_args(&params, &chain_normalized);
@@ -329,6 +583,8 @@ mod tests {
             chain: None,
             verbose: Some(true),
             parachain: None,
+            overrides: None,
+            detach: None,
         };
         let chain_normalized = params.normalized_chain();
         let args = build_up_network_args(&params, &chain_normalized);
@@ -352,6 +608,8 @@ mod tests {
             chain: Some("kusama".to_owned()),
             verbose: Some(true),
             parachain: None,
+            overrides: None,
+            detach: None,
         };
         let chain_normalized = params.normalized_chain();
         let args = build_up_network_args(&params, &chain_normalized);
@@ -374,6 +632,8 @@ mod tests {
             chain: Some("paseo".to_owned()),
             verbose: None,
             parachain: Some(vec!["asset-hub".to_owned()]),
+            overrides: None,
+            detach: None,
         };
         let chain_normalized = params.normalized_chain();
         let args = build_up_network_args(&params, &chain_normalized);
@@ -397,6 +657,8 @@ mod tests {
             chain: None,
             verbose: None,
             parachain: Some(vec!["asset-hub#1000:9944".to_owned()]),
+            overrides: None,
+            detach: None,
         };
         let chain_normalized = params.normalized_chain();
         let args = build_up_network_args(&params, &chain_normalized);
@@ -413,4 +675,107 @@ mod tests {
             ]
         );
     }
+
+    // Override tests
+
+    const CONFIG: &str = r#"
+[relaychain]
+chain = "paseo-local"
+
+[[relaychain.nodes]]
+name = "alice"
+
+[[parachains]]
+id = 1000
+chain = "asset-hub-paseo-local"
+
+[[parachains.collators]]
+name = "asset-hub"
+args = ["-lxcm=trace"]
+
+[[parachains]]
+id = 2000
+
+[parachains.collator]
+name = "pop"
+
+[[hrmp_channels]]
+sender = 1000
+recipient = 2000
+"#;
+
+    #[test]
+    fn apply_overrides_edits_relay_collators_runtimes_and_ids() {
+        let Ok(mut config) = toml::from_str::<toml::Table>(CONFIG) else {
+            panic!("fixture is valid TOML");
+        };
+        let overrides = NetworkOverrides {
+            relay_chain: Some("westend-local".to_owned()),
+            collator_args: Some(vec!["-lruntime=debug".to_owned()]),
+            runtime_overrides: Some(BTreeMap::from([
+                ("relay".to_owned(), "./relay.wasm".to_owned()),
+                ("2000".to_owned(), "./pop.wasm".to_owned()),
+            ])),
+            para_ids: Some(BTreeMap::from([
+                ("1000".to_owned(), 2000),
+                ("2000".to_owned(), 1000),
+            ])),
+        };
+        assert_eq!(apply_overrides(&mut config, &overrides), Ok(()));
+        let rendered = toml::to_string(&config).unwrap_or_default();
+        assert!(rendered.contains("chain = \"westend-local\""));
+        assert!(rendered.contains("wasm_override = \"./relay.wasm\""));
+
+        let paras: Vec<&mut toml::Table> = parachains(&mut config).collect();
+        assert_eq!(paras[0].get("id"), Some(&toml::Value::Integer(2000)));
+        assert_eq!(paras[1].get("id"), Some(&toml::Value::Integer(1000)));
+        assert_eq!(
+            paras[1].get("wasm_override"),
+            Some(&toml::Value::from("./pop.wasm"))
+        );
+        assert!(rendered.contains("args = [\"-lxcm=trace\", \"-lruntime=debug\"]"));
+        assert!(rendered.contains("args = [\"-lruntime=debug\"]"));
+        assert!(rendered.contains("sender = 2000"));
+        assert!(rendered.contains("recipient = 1000"));
+    }
+
+    #[test]
+    fn apply_overrides_rejects_unknown_parachains() {
+        let Ok(mut config) = toml::from_str::<toml::Table>(CONFIG) else {
+            panic!("fixture is valid TOML");
+        };
+        let overrides = NetworkOverrides {
+            para_ids: Some(BTreeMap::from([("3000".to_owned(), 4000)])),
+            ..NetworkOverrides::default()
+        };
+        assert!(apply_overrides(&mut config, &overrides).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_overrides_without_path() {
+        let params = UpNetworkParams {
+            path: None,
+            chain: Some("paseo".to_owned()),
+            verbose: None,
+            parachain: None,
+            overrides: Some(NetworkOverrides::default()),
+            detach: None,
+        };
+        assert!(params.validate().is_err());
+        let params = UpNetworkParams {
+            path: Some("./network.toml".to_owned()),
+            chain: None,
+            verbose: None,
+            parachain: None,
+            overrides: Some(NetworkOverrides {
+                runtime_overrides: Some(BTreeMap::from([(
+                    "asset-hub".to_owned(),
+                    "./a.wasm".to_owned(),
+                )])),
+                ..NetworkOverrides::default()
+            }),
+            detach: None,
+        };
+        assert!(params.validate().is_err());
+    }
 }
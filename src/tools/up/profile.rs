@@ -0,0 +1,180 @@
+//! Named network profiles (saved up_network parameters)
+
+use std::path::Path;
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::profiles;
+use crate::tools::common::{error_result, success_result};
+use crate::tools::up::network::{overridden_config, up_network, UpNetworkParams};
+
+/// Parameters for the save_network_profile tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct SaveNetworkProfileParams {
+    /// Name to save the profile under.
+    #[schemars(
+        description = "Profile name: letters, digits, '-' and '_' (e.g., 'relay-assets-contracts')"
+    )]
+    pub name: String,
+    /// The up_network parameters to save.
+    #[serde(flatten)]
+    pub network: UpNetworkParams,
+}
+
+/// Parameters for the up_network_profile tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct UpNetworkProfileParams {
+    /// Name of the profile to launch.
+    #[schemars(description = "Name of a profile saved with save_network_profile")]
+    pub name: String,
+}
+
+/// One line describing the saved parameters.
+fn describe(params: &UpNetworkParams) -> String {
+    let mut parts = Vec::new();
+    if let Some(path) = &params.path {
+        parts.push(format!("config {}", path));
+    }
+    if let Some(chain) = &params.chain {
+        parts.push(format!("relay chain {}", chain));
+    }
+    if let Some(parachains) = &params.parachain {
+        parts.push(format!("parachains {}", parachains.join(", ")));
+    }
+    if params.overrides.is_some() {
+        parts.push("with overrides".to_owned());
+    }
+    parts.join(", ")
+}
+
+/// Execute save_network_profile tool
+pub async fn save_network_profile(
+    dir: &Path,
+    params: SaveNetworkProfileParams,
+) -> PopMcpResult<CallToolResult> {
+    let name = params.name.trim();
+    profiles::validate_name(name)?;
+    let mut network = params.network;
+    network.validate().map_err(PopMcpError::InvalidInput)?;
+
+    // Profiles outlive the working directory they were saved from.
+    if let Some(path) = &network.path {
+        let absolute = match tokio::fs::canonicalize(path).await {
+            Ok(absolute) => absolute.display().to_string(),
+            Err(e) => return Ok(error_result(format!("Failed to read {}: {}", path, e))),
+        };
+        if let Err(e) = overridden_config(&absolute, network.overrides.as_ref()).await {
+            return Ok(error_result(format!("Profile not saved: {}", e)));
+        }
+        network.path = Some(absolute);
+    }
+
+    let file = profiles::save(dir, name, &network).await?;
+    Ok(success_result(format!(
+        "Saved network profile '{}' ({}) to {}. Launch it with up_network_profile.",
+        name,
+        describe(&network),
+        file.display()
+    )))
+}
+
+/// Execute up_network_profile tool
+pub async fn up_network_profile<E: CommandExecutor>(
+    executor: &E,
+    dir: &Path,
+    params: UpNetworkProfileParams,
+) -> PopMcpResult<CallToolResult> {
+    let name = params.name.trim();
+    let Some(network) = profiles::load(dir, name).await? else {
+        let saved = profiles::list(dir);
+        return Ok(error_result(if saved.is_empty() {
+            format!(
+                "No network profile named '{}': none saved yet (use save_network_profile)",
+                name
+            )
+        } else {
+            format!(
+                "No network profile named '{}'. Saved profiles: {}",
+                name,
+                saved.join(", ")
+            )
+        }));
+    };
+    up_network(executor, network).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutor;
+    use crate::tools::common::content_text;
+    use crate::tools::up::network::NetworkOverrides;
+
+    fn save_params(path: &Path) -> SaveNetworkProfileParams {
+        SaveNetworkProfileParams {
+            name: "assets".to_owned(),
+            network: UpNetworkParams {
+                path: Some(path.display().to_string()),
+                chain: None,
+                verbose: None,
+                parachain: None,
+                overrides: Some(NetworkOverrides {
+                    relay_chain: Some("westend-local".to_owned()),
+                    ..NetworkOverrides::default()
+                }),
+                detach: None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn saved_profiles_launch_with_their_parameters() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let config = dir.path().join("network.toml");
+        std::fs::write(&config, "[relaychain]\nchain = \"paseo-local\"\n")?;
+        let profiles = dir.path().join("profiles");
+
+        let saved = save_network_profile(&profiles, save_params(&config)).await?;
+        assert!(content_text(&saved).starts_with("Saved network profile 'assets'"));
+
+        let executor = MockExecutor::new().on(&["up", "network"], "zombie.json");
+        let result = up_network_profile(
+            &executor,
+            &profiles,
+            UpNetworkProfileParams {
+                name: "assets".to_owned(),
+            },
+        )
+        .await?;
+        assert!(content_text(&result).contains("zombie.json"));
+        assert!(executor.calls()[0].args[2].contains(".network.pop-mcp-"));
+
+        let missing = up_network_profile(
+            &executor,
+            &profiles,
+            UpNetworkProfileParams {
+                name: "other".to_owned(),
+            },
+        )
+        .await?;
+        assert!(content_text(&missing).contains("Saved profiles: assets"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn save_network_profile_rejects_configs_that_do_not_parse() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let config = dir.path().join("network.toml");
+        std::fs::write(&config, "not = [toml")?;
+        let result = save_network_profile(dir.path(), save_params(&config)).await?;
+        assert_eq!(result.is_error, Some(true));
+        assert!(profiles::list(dir.path()).is_empty());
+        Ok(())
+    }
+}
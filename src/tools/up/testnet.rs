@@ -0,0 +1,264 @@
+//! One-shot deployment to the PassetHub testnet
+//!
+//! Deploying to a public testnet takes several steps: pick the endpoint, make sure
+//! the signer has testnet tokens, build the contract and deploy it. This tool runs
+//! them in order against PassetHub (Paseo's contracts testnet) and links the
+//! deployed contract on its block explorer.
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::accounts::{keypair_from_suri, ss58_address};
+use crate::deployments::same_url;
+use crate::encoder::{fetch_storage, to_hex, ChainMetadata};
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::outputs::{BuildOutput, DeployOutput};
+use crate::rpc::RpcPool;
+use crate::tools::build::contract::{build_contract, BuildContractParams};
+use crate::tools::common::{error_result, extract_texts, success_result, with_structured};
+use crate::tools::up::contract::{deploy_contract, DeployContractParams};
+
+/// Default PassetHub RPC endpoint.
+pub const PASSET_HUB_URL: &str = "wss://testnet-passet-hub.polkadot.io";
+
+/// Blockscout explorer of PassetHub.
+const PASSET_HUB_EXPLORER: &str = "https://blockscout-passet-hub.parity-testnet.parity.io";
+
+/// Faucet handing out PAS on PassetHub.
+const PASSET_HUB_FAUCET: &str = "https://faucet.polkadot.io/?parachain=1111";
+
+/// Parameters for the deploy_to_testnet tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct DeployToTestnetParams {
+    /// Path to the contract directory.
+    #[schemars(description = "Path to the contract directory")]
+    pub path: String,
+    /// Constructor function to call.
+    #[schemars(description = "Constructor function to call (default: new)")]
+    pub constructor: Option<String>,
+    /// Constructor arguments as space-separated values.
+    #[schemars(description = "Constructor arguments as space-separated values")]
+    pub args: Option<String>,
+    /// Initial balance to transfer to the contract (in tokens).
+    #[schemars(description = "Initial balance to transfer to the contract (in tokens)")]
+    pub value: Option<String>,
+    /// Salt for the contract address.
+    #[schemars(description = "Salt used in address derivation, making the address deterministic")]
+    pub salt: Option<String>,
+    /// PassetHub endpoint.
+    #[schemars(
+        description = "PassetHub WebSocket endpoint (default: wss://testnet-passet-hub.polkadot.io)"
+    )]
+    pub url: Option<String>,
+    /// Least free balance the signer must have.
+    #[schemars(
+        description = "Least free balance, in planck, the signer must have before deploying (default: the chain's existential deposit)"
+    )]
+    pub min_balance: Option<String>,
+}
+
+/// Blockscout page of the contract at `address`, when `url` is the default PassetHub
+/// endpoint. Other chains behind a custom `url` have no known explorer.
+fn explorer_url(url: &str, address: &str) -> Option<String> {
+    same_url(url, PASSET_HUB_URL).then(|| format!("{}/address/{}", PASSET_HUB_EXPLORER, address))
+}
+
+/// Free balance of the account of `public_key` at `url` and the least it must have:
+/// `min_balance` or the existential deposit.
+async fn balances(
+    pool: &RpcPool,
+    url: &str,
+    public_key: &[u8; 32],
+    min_balance: Option<u128>,
+) -> Result<(u128, u128), String> {
+    let metadata = ChainMetadata::fetch(pool, url)
+        .await
+        .map_err(|e| e.to_string())?;
+    let key = metadata.storage_key("System", "Account", &[to_hex(public_key)])?;
+    let bytes = fetch_storage(pool, url, &key)
+        .await
+        .map_err(|e| e.to_string())?;
+    let free = metadata.decode_storage_field("System", "Account", bytes.as_deref(), "data.free")?;
+    let free = free
+        .parse::<u128>()
+        .map_err(|_| format!("Unexpected free balance '{}'", free))?;
+    let required = match min_balance {
+        Some(min_balance) => min_balance,
+        None => {
+            let deposit = metadata.decode_constant("Balances", "ExistentialDeposit")?;
+            deposit
+                .parse::<u128>()
+                .map_err(|_| format!("Unexpected existential deposit '{}'", deposit))?
+        }
+    };
+    Ok((free, required))
+}
+
+/// Execute deploy_to_testnet tool
+pub async fn deploy_to_testnet<E: CommandExecutor>(
+    executor: &E,
+    pool: &RpcPool,
+    params: DeployToTestnetParams,
+) -> PopMcpResult<CallToolResult> {
+    let min_balance = params
+        .min_balance
+        .as_deref()
+        .map(|min| {
+            min.trim().parse::<u128>().map_err(|_| {
+                PopMcpError::InvalidInput(format!(
+                    "'min_balance' must be a whole number of plancks, got '{}'",
+                    min
+                ))
+            })
+        })
+        .transpose()?;
+    let suri = crate::read_private_key_suri().ok_or_else(|| {
+        PopMcpError::InvalidInput(
+            "PRIVATE_KEY environment variable is required to deploy to PassetHub".to_owned(),
+        )
+    })?;
+    let public_key = keypair_from_suri(&suri)
+        .map_err(|e| PopMcpError::InvalidInput(format!("PRIVATE_KEY is not a valid SURI: {}", e)))?
+        .public
+        .to_bytes();
+    let url = params.url.as_deref().unwrap_or(PASSET_HUB_URL);
+
+    let (free, required) = match balances(pool, url, &public_key, min_balance).await {
+        Ok(balances) => balances,
+        Err(e) => {
+            return Ok(error_result(format!(
+                "Could not read the signer's balance on PassetHub ({}): {}",
+                url, e
+            )))
+        }
+    };
+    if free <= required {
+        return Ok(error_result(format!(
+            "Signer {} has {} planck free on PassetHub; more than {} is needed to deploy.\nGet PAS from the faucet: {}",
+            ss58_address(&public_key, 42),
+            free,
+            required,
+            PASSET_HUB_FAUCET
+        )));
+    }
+
+    let built = BuildOutput::collect(&params.path, true);
+    let mut steps = Vec::new();
+    if built.blob.is_none() && built.bundle.is_none() {
+        let build = build_contract(
+            executor,
+            BuildContractParams {
+                path: params.path.clone(),
+                release: Some(true),
+                timeout_secs: None,
+                env: None,
+                toolchain: None,
+                verifiable: None,
+                force: None,
+            },
+        )
+        .await?;
+        if build.is_error == Some(true) {
+            return Ok(build);
+        }
+        steps.push("Built the contract (release).");
+    }
+
+    let deployed = deploy_contract(
+        executor,
+        DeployContractParams {
+            path: params.path,
+            constructor: params.constructor,
+            args: params.args,
+            args_file: None,
+            value: params.value,
+            gas_limit: None,
+            proof_size: None,
+            execute: Some(true),
+            upload_only: None,
+            url: Some(url.to_owned()),
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: params.salt,
+            expected_code_hash: None,
+        },
+        None,
+    )
+    .await?;
+    if deployed.is_error == Some(true) {
+        return Ok(deployed);
+    }
+    let output = extract_texts(&deployed).join("\n");
+    let Some(mut result) = deployed
+        .structured_content
+        .and_then(|value| serde_json::from_value::<DeployOutput>(value).ok())
+    else {
+        return Ok(success_result(output));
+    };
+    result.explorer_url = explorer_url(url, &result.address);
+    steps.push("Deployed to PassetHub.");
+    let explorer = result
+        .explorer_url
+        .as_ref()
+        .map(|explorer| format!("\nExplorer: {}", explorer))
+        .unwrap_or_default();
+    Ok(with_structured(
+        success_result(format!(
+            "{}{}\nSigner balance: {}\n\n{}",
+            steps.join("\n"),
+            explorer,
+            free,
+            output
+        )),
+        &result,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutor;
+
+    #[test]
+    fn explorer_links_the_contract_address() {
+        let address = "0x5801b439a678d9d3a68b8019da6a4abfa507de11";
+        assert_eq!(
+            explorer_url(PASSET_HUB_URL, address).as_deref(),
+            Some("https://blockscout-passet-hub.parity-testnet.parity.io/address/0x5801b439a678d9d3a68b8019da6a4abfa507de11")
+        );
+        assert!(explorer_url("wss://testnet-passet-hub.polkadot.io/", address).is_some());
+        assert_eq!(
+            explorer_url("wss://westend-asset-hub-rpc.polkadot.io", address),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_min_balance_is_rejected_before_any_command() {
+        let executor = MockExecutor::new();
+        let result = deploy_to_testnet(
+            &executor,
+            &RpcPool::new(),
+            DeployToTestnetParams {
+                path: "./flipper".to_owned(),
+                constructor: None,
+                args: None,
+                value: None,
+                salt: None,
+                url: None,
+                min_balance: Some("1 PAS".to_owned()),
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+        assert!(executor.calls().is_empty());
+    }
+}
@@ -0,0 +1,317 @@
+//! Condition watches (alerts on chain values)
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use rmcp::model::CallToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PopMcpError, PopMcpResult};
+use crate::executor::CommandExecutor;
+use crate::rpc::RpcPool;
+use crate::tools::common::{error_result, success_result};
+use crate::watch::{Comparison, WatchConfig, WatchEvent, WatchQuery, Watches};
+
+/// Seconds between reads by default.
+const DEFAULT_INTERVAL_SECS: u64 = 10;
+
+/// Parameters for the watch_condition tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct WatchConditionParams {
+    /// Name of the watch.
+    #[schemars(
+        description = "Name of the watch, used to stop it (e.g., 'treasury-low'); a watch with the same name is replaced"
+    )]
+    pub name: String,
+    /// WebSocket URL of the node.
+    #[schemars(
+        description = "WebSocket URL of the node to read from (default: the selected node)"
+    )]
+    pub url: Option<String>,
+    /// Account whose free balance is watched.
+    #[schemars(
+        description = "Watch the free balance (System.Account data.free, in planck) of this account"
+    )]
+    pub address: Option<String>,
+    /// Pallet of a storage entry.
+    #[schemars(description = "Pallet of the storage entry to watch (e.g., 'Balances')")]
+    pub pallet: Option<String>,
+    /// Storage item name.
+    #[schemars(description = "Storage item to watch (e.g., 'TotalIssuance')")]
+    pub item: Option<String>,
+    /// Map keys of the storage item.
+    #[schemars(description = "Map keys of the storage item, one per key; omit for plain values")]
+    pub keys: Option<Vec<String>>,
+    /// Field of the storage value to compare.
+    #[schemars(
+        description = "Dot-separated field of the storage value to compare (e.g., 'data.free'); default: the whole value"
+    )]
+    pub field: Option<String>,
+    /// Contract directory, for a contract query.
+    #[schemars(description = "Path to the contract directory, to watch a contract message")]
+    pub path: Option<String>,
+    /// Contract address, for a contract query.
+    #[schemars(description = "Contract address, to watch a contract message")]
+    pub contract: Option<String>,
+    /// Contract message to query.
+    #[schemars(description = "Read-only contract message to query (e.g., 'get')")]
+    pub message: Option<String>,
+    /// Contract message arguments.
+    #[schemars(description = "Contract message arguments as space-separated values")]
+    pub args: Option<String>,
+    /// Comparison of the value with the threshold.
+    #[schemars(description = "Comparison of the value with threshold: <, <=, >, >=, == or !=")]
+    pub comparison: String,
+    /// Value compared against.
+    #[schemars(
+        description = "Value to compare with: a number (e.g., '1000000000000') or, for == and !=, any text (e.g., 'true')"
+    )]
+    pub threshold: String,
+    /// Seconds between reads.
+    #[schemars(description = "Seconds between reads (default 10)")]
+    pub interval_secs: Option<u64>,
+}
+
+impl WatchConditionParams {
+    /// Validate the parameters and pick the value to read.
+    fn query(&self) -> Result<WatchQuery, String> {
+        let storage = (self.pallet.as_deref(), self.item.as_deref());
+        let contract = (
+            self.path.as_deref(),
+            self.contract.as_deref(),
+            self.message.as_deref(),
+        );
+        match (self.address.as_deref(), storage, contract) {
+            (Some(address), (None, None), (None, None, None)) => Ok(WatchQuery::Storage {
+                pallet: "System".to_owned(),
+                item: "Account".to_owned(),
+                keys: vec![address.trim().to_owned()],
+                field: Some("data.free".to_owned()),
+            }),
+            (None, (Some(pallet), Some(item)), (None, None, None)) => Ok(WatchQuery::Storage {
+                pallet: pallet.to_owned(),
+                item: item.to_owned(),
+                keys: self.keys.clone().unwrap_or_default(),
+                field: self.field.clone(),
+            }),
+            (None, (None, None), (Some(path), Some(contract), Some(message))) => {
+                Ok(WatchQuery::Contract {
+                    path: path.to_owned(),
+                    contract: contract.to_owned(),
+                    message: message.to_owned(),
+                    args: self.args.clone(),
+                })
+            }
+            _ => Err(
+                "Give one of 'address' (balance), 'pallet' and 'item' (storage) or 'path', 'contract' and 'message' (contract query)"
+                    .to_owned(),
+            ),
+        }
+    }
+}
+
+/// Parameters for the stop_watch tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+pub struct StopWatchParams {
+    /// Name of the watch.
+    #[schemars(description = "Name of the watch, as listed by list_watches")]
+    pub name: String,
+}
+
+/// Parameters for the list_watches tool.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(extend("properties" = {}))]
+#[allow(clippy::empty_structs_with_brackets)]
+pub struct ListWatchesParams {}
+
+/// Execute watch_condition tool
+///
+/// `notify` receives every trigger, clear and read failure of the watch.
+pub async fn watch_condition<E, N>(
+    executor: &E,
+    pool: Arc<RpcPool>,
+    watches: &Watches,
+    params: WatchConditionParams,
+    stored_url: Option<&str>,
+    notify: N,
+) -> PopMcpResult<CallToolResult>
+where
+    E: CommandExecutor,
+    N: Fn(&WatchEvent) + Send + Sync + 'static,
+{
+    let name = params.name.trim();
+    if name.is_empty() {
+        return Err(PopMcpError::InvalidInput(
+            "'name' cannot be empty".to_owned(),
+        ));
+    }
+    let query = params.query().map_err(PopMcpError::InvalidInput)?;
+    let comparison = Comparison::parse(&params.comparison).ok_or_else(|| {
+        PopMcpError::InvalidInput(format!(
+            "Invalid comparison '{}': use <, <=, >, >=, == or !=",
+            params.comparison
+        ))
+    })?;
+    if params.interval_secs == Some(0) {
+        return Err(PopMcpError::InvalidInput(
+            "'interval_secs' must be greater than 0".to_owned(),
+        ));
+    }
+    let Some(url) = params.url.as_deref().or(stored_url) else {
+        return Err(PopMcpError::InvalidInput(
+            "'url' is required when no node is selected".to_owned(),
+        ));
+    };
+    let interval = params.interval_secs.unwrap_or(DEFAULT_INTERVAL_SECS);
+    let config = WatchConfig {
+        name: name.to_owned(),
+        url: url.to_owned(),
+        query,
+        comparison,
+        threshold: params.threshold.trim().to_owned(),
+        interval: Duration::from_secs(interval),
+    };
+    let condition = config.condition();
+    watches.start(executor.clone(), pool, config, notify);
+    Ok(success_result(format!(
+        "Watch '{}' started: checking {} on {} every {}s. A log notification is sent when \
+         the condition holds, when it stops holding and when reads fail. Stop it with stop_watch.",
+        name, condition, url, interval
+    )))
+}
+
+/// Execute stop_watch tool
+pub async fn stop_watch(
+    watches: &Watches,
+    params: StopWatchParams,
+) -> PopMcpResult<CallToolResult> {
+    let name = params.name.trim();
+    Ok(match watches.stop(name) {
+        Some(config) => {
+            success_result(format!("Stopped watch '{}' ({})", name, config.condition()))
+        }
+        None => error_result(format!("No watch named '{}'", name)),
+    })
+}
+
+/// Execute list_watches tool
+pub async fn list_watches(
+    watches: &Watches,
+    _params: ListWatchesParams,
+) -> PopMcpResult<CallToolResult> {
+    let running = watches.list();
+    if running.is_empty() {
+        return Ok(success_result(
+            "No watches running. Start one with watch_condition.",
+        ));
+    }
+    let lines: Vec<String> = running
+        .iter()
+        .map(|(config, status)| {
+            let state = match (&status.error, &status.value) {
+                (Some(error), _) => {
+                    format!("failing: {}", error.lines().next().unwrap_or_default())
+                }
+                (None, Some(value)) if status.triggered => format!("TRIGGERED, value {}", value),
+                (None, Some(value)) => format!("ok, value {}", value),
+                (None, None) => "not read yet".to_owned(),
+            };
+            format!(
+                "{}: {} on {} ({} check(s); {})",
+                config.name,
+                config.condition(),
+                config.url,
+                status.checks,
+                state
+            )
+        })
+        .collect();
+    Ok(success_result(format!("Watches:\n{}", lines.join("\n"))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockExecutor;
+    use crate::tools::common::content_text;
+
+    fn contract_params() -> WatchConditionParams {
+        WatchConditionParams {
+            name: "low-supply".to_owned(),
+            url: None,
+            address: None,
+            pallet: None,
+            item: None,
+            keys: None,
+            field: None,
+            path: Some("./token".to_owned()),
+            contract: Some("0xc0ffee".to_owned()),
+            message: Some("total_supply".to_owned()),
+            args: None,
+            comparison: "<".to_owned(),
+            threshold: "1000".to_owned(),
+            interval_secs: None,
+        }
+    }
+
+    #[test]
+    fn balance_watches_read_the_free_balance() {
+        let params = WatchConditionParams {
+            address: Some("5GrwvaEF".to_owned()),
+            path: None,
+            contract: None,
+            message: None,
+            ..contract_params()
+        };
+        assert_eq!(
+            params.query().map(|query| query.label()),
+            Ok("System.Account(5GrwvaEF).data.free".to_owned())
+        );
+        let both = WatchConditionParams {
+            address: Some("5GrwvaEF".to_owned()),
+            ..contract_params()
+        };
+        assert!(both.query().is_err());
+    }
+
+    #[tokio::test]
+    async fn watches_report_when_the_condition_starts_to_hold() -> anyhow::Result<()> {
+        let watches = Watches::new();
+        let (events, mut received) = tokio::sync::mpsc::unbounded_channel();
+        let executor = MockExecutor::new().on(&["call", "contract"], "Result: Ok(900)");
+        let started = watch_condition(
+            &executor,
+            Arc::new(RpcPool::new()),
+            &watches,
+            contract_params(),
+            Some("ws://localhost:9944"),
+            move |event: &WatchEvent| {
+                let _ = events.send(event.clone());
+            },
+        )
+        .await?;
+        assert!(content_text(&started)
+            .starts_with("Watch 'low-supply' started: checking 0xc0ffee.total_supply() < 1000"));
+
+        let Some(WatchEvent::Triggered { value, .. }) = received.recv().await else {
+            anyhow::bail!("expected the watch to trigger");
+        };
+        assert_eq!(value, "Ok(900)");
+        let listed = list_watches(&watches, ListWatchesParams {}).await?;
+        assert!(content_text(&listed).contains("TRIGGERED, value Ok(900)"));
+
+        let stopped = stop_watch(
+            &watches,
+            StopWatchParams {
+                name: "low-supply".to_owned(),
+            },
+        )
+        .await?;
+        assert!(content_text(&stopped).starts_with("Stopped watch 'low-supply'"));
+        assert!(watches.list().is_empty());
+        Ok(())
+    }
+}
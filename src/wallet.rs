@@ -0,0 +1,126 @@
+//! Browser wallet signing (`--use-wallet`).
+//!
+//! With `--use-wallet`, Pop CLI serves a local signing portal and waits until the
+//! transaction is signed there with a browser wallet extension. The portal URL is
+//! only printed to the command's output, so [`relay`] watches the output lines and
+//! hands the URL to the client while pop waits: as a progress notification when the
+//! call asked for progress, and as a `wallet` log message. The tool result repeats it.
+//!
+//! The portal listens on the server's localhost, so signing is only offered to stdio
+//! clients, which run on the same machine.
+
+use rmcp::model::LoggingLevel;
+use tokio::task::JoinHandle;
+
+use crate::executor::LineSink;
+use crate::logging::CommandLog;
+use crate::parsing;
+use crate::stream;
+
+/// Pop CLI flag that signs through the browser wallet instead of a SURI.
+pub const FLAG: &str = "--use-wallet";
+
+/// Logger name of the signing messages.
+pub const LOGGER: &str = "wallet";
+
+/// Refused in CI mode, where nobody is there to sign.
+pub(crate) const CI_ERROR: &str =
+    "use_wallet waits for a browser signature and is not available in ci_mode";
+
+/// Refused over HTTP and SSE, whose clients can't reach the portal.
+pub(crate) const REMOTE_ERROR: &str =
+    "use_wallet is only available over stdio: the signing portal listens on the server's localhost, out of reach of HTTP and SSE clients";
+
+/// Check that `use_wallet` is not combined with another signer and is only asked for
+/// when something is submitted.
+pub(crate) fn check(
+    use_wallet: Option<bool>,
+    execute: bool,
+    signer: Option<&str>,
+) -> Result<(), String> {
+    if !use_wallet.unwrap_or(false) {
+        return Ok(());
+    }
+    if !execute {
+        return Err("use_wallet signs a submitted transaction: set execute=true".to_owned());
+    }
+    if signer.is_some() {
+        return Err("Use either use_wallet or signer, not both".to_owned());
+    }
+    Ok(())
+}
+
+/// Message asking the user to sign at the portal `url`.
+pub fn signing_message(url: &str) -> String {
+    format!(
+        "Open {} in a browser with a wallet extension to sign the transaction; pop waits for the signature",
+        url
+    )
+}
+
+/// Watch the lines sent to the returned sink for the signing portal URL and, once
+/// found, send it to `progress` (if given) and log it to `log`.
+///
+/// The task ends once every clone of the sink is dropped and returns the URL, if pop
+/// printed one.
+pub fn relay(
+    log: CommandLog,
+    progress: Option<LineSink>,
+) -> (LineSink, JoinHandle<Option<String>>) {
+    let (sink, mut lines) = stream::bounded::<String>(stream::DEFAULT_CAPACITY);
+    let handle = tokio::spawn(async move {
+        let mut portal = None;
+        while let Some((line, _)) = lines.recv().await {
+            if portal.is_none() {
+                if let Some(url) = parsing::signing_portal(&line) {
+                    // A client that went away just misses the URL; pop keeps waiting.
+                    if let Some(progress) = &progress {
+                        progress.send(signing_message(&url));
+                    }
+                    log.log(
+                        LOGGER,
+                        LoggingLevel::Notice,
+                        serde_json::json!({
+                            "event": "signature_requested",
+                            "url": url,
+                            "message": signing_message(&url),
+                        }),
+                    );
+                    portal = Some(url);
+                }
+            }
+        }
+        portal
+    });
+    (sink, handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_rejects_conflicting_signers() {
+        assert!(check(None, false, Some("ci")).is_ok());
+        assert!(check(Some(true), true, None).is_ok());
+        assert!(check(Some(true), false, None).is_err());
+        assert!(check(Some(true), true, Some("ci")).is_err());
+    }
+
+    #[tokio::test]
+    async fn relay_sends_the_portal_url_once() {
+        let (progress, mut received) = stream::bounded(4);
+        let (sink, handle) = relay(CommandLog::new(), Some(progress));
+        sink.send("Estimating gas...".to_owned());
+        sink.send("Wallet signing portal started at http://127.0.0.1:9090.".to_owned());
+        sink.send("Wallet signing portal started at http://127.0.0.1:9091.".to_owned());
+        drop(sink);
+        assert_eq!(
+            handle.await.ok().flatten().as_deref(),
+            Some("http://127.0.0.1:9090")
+        );
+        let message = received.try_recv().map(|(message, _)| message);
+        assert_eq!(message, Some(signing_message("http://127.0.0.1:9090")));
+        assert!(received.try_recv().is_none());
+    }
+}
@@ -0,0 +1,524 @@
+//! Watches: poll a chain value and report when a condition on it starts or stops
+//! holding.
+//!
+//! A watch reads a storage entry (or one field of it, such as an account's free
+//! balance) over RPC, or queries a contract message through Pop CLI, every interval.
+//! The value is compared with a threshold and a [`WatchEvent`] is reported when the
+//! condition becomes true, when it becomes false again, and when reads start failing,
+//! so a long-running demo network can be left alone until something needs attention.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rmcp::model::{LoggingLevel, LoggingMessageNotificationParam};
+use rmcp::{Peer, RoleServer};
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+
+use crate::encoder::{fetch_storage_at, ChainMetadata};
+use crate::executor::CommandExecutor;
+use crate::logging;
+use crate::parsing;
+use crate::rpc::RpcPool;
+use crate::tools::call::contract::{call_contract, CallContractParams};
+use crate::tools::common::extract_text;
+
+/// Logger name of watch messages.
+pub const LOGGER: &str = "watch";
+
+/// The value a watch reads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchQuery {
+    /// A storage entry, read over RPC.
+    Storage {
+        /// Pallet name.
+        pallet: String,
+        /// Storage item name.
+        item: String,
+        /// Map keys, one per hasher.
+        keys: Vec<String>,
+        /// Dot-separated path of the field to compare (e.g. `data.free`).
+        field: Option<String>,
+    },
+    /// The result of a contract message, queried without submitting it.
+    Contract {
+        /// Contract directory, for its metadata.
+        path: String,
+        /// Contract address.
+        contract: String,
+        /// Message to query.
+        message: String,
+        /// Message arguments, space-separated.
+        args: Option<String>,
+    },
+}
+
+impl WatchQuery {
+    /// `Pallet.Item(keys).field` or `contract.message(args)`.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Storage {
+                pallet,
+                item,
+                keys,
+                field,
+            } => {
+                let mut label = format!("{}.{}", pallet, item);
+                if !keys.is_empty() {
+                    label.push_str(&format!("({})", keys.join(", ")));
+                }
+                if let Some(field) = field {
+                    label.push_str(&format!(".{}", field));
+                }
+                label
+            }
+            Self::Contract {
+                contract,
+                message,
+                args,
+                ..
+            } => format!(
+                "{}.{}({})",
+                contract,
+                message,
+                args.as_deref().unwrap_or_default()
+            ),
+        }
+    }
+}
+
+/// How the value is compared with the threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+    /// `==`
+    Eq,
+    /// `!=`
+    Ne,
+}
+
+impl Comparison {
+    /// Parse `<`, `<=`, `>`, `>=`, `==`, `!=` or their names (`lt`, `le`, ...).
+    pub fn parse(text: &str) -> Option<Self> {
+        Some(match text.trim().to_ascii_lowercase().as_str() {
+            "<" | "lt" => Self::Lt,
+            "<=" | "le" => Self::Le,
+            ">" | "gt" => Self::Gt,
+            ">=" | "ge" => Self::Ge,
+            "==" | "=" | "eq" => Self::Eq,
+            "!=" | "ne" => Self::Ne,
+            _ => return None,
+        })
+    }
+
+    /// The operator.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+        }
+    }
+
+    /// Whether `value` compares to `threshold` this way. Numbers are compared as
+    /// numbers; anything else only supports `==` and `!=`.
+    pub fn holds(self, value: &str, threshold: &str) -> Result<bool, String> {
+        let (value, threshold) = (unwrap_result(value), unwrap_result(threshold));
+        let ordering = match (number(value), number(threshold)) {
+            (Some(value), Some(threshold)) => value.cmp(&threshold),
+            _ => match self {
+                Self::Eq => return Ok(value == threshold),
+                Self::Ne => return Ok(value != threshold),
+                _ => {
+                    return Err(format!(
+                        "Cannot compare '{}' {} '{}': not numbers",
+                        value,
+                        self.symbol(),
+                        threshold
+                    ))
+                }
+            },
+        };
+        Ok(match self {
+            Self::Lt => ordering.is_lt(),
+            Self::Le => ordering.is_le(),
+            Self::Gt => ordering.is_gt(),
+            Self::Ge => ordering.is_ge(),
+            Self::Eq => ordering.is_eq(),
+            Self::Ne => ordering.is_ne(),
+        })
+    }
+}
+
+/// `value` without `Ok(..)` / `Some(..)` wrappers, as contract queries return.
+fn unwrap_result(value: &str) -> &str {
+    let mut value = value.trim();
+    while let Some(inner) = ["Ok(", "Some("]
+        .iter()
+        .find_map(|prefix| value.strip_prefix(prefix)?.strip_suffix(')'))
+    {
+        value = inner.trim();
+    }
+    value
+}
+
+/// `value` as an integer, allowing `_` and `,` digit separators.
+fn number(value: &str) -> Option<i128> {
+    let digits: String = value.chars().filter(|c| !matches!(c, '_' | ',')).collect();
+    digits.parse().ok()
+}
+
+/// What a watch reads and when it triggers.
+#[derive(Debug, Clone)]
+pub struct WatchConfig {
+    /// Name of the watch.
+    pub name: String,
+    /// WebSocket URL of the node to read from.
+    pub url: String,
+    /// Value to read.
+    pub query: WatchQuery,
+    /// Comparison of the value with `threshold`.
+    pub comparison: Comparison,
+    /// Value compared against.
+    pub threshold: String,
+    /// Delay between reads.
+    pub interval: Duration,
+}
+
+impl WatchConfig {
+    /// `label < threshold`.
+    pub fn condition(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.query.label(),
+            self.comparison.symbol(),
+            self.threshold
+        )
+    }
+}
+
+/// A change in a watch's state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WatchEvent {
+    /// The condition started to hold.
+    Triggered {
+        /// Name of the watch.
+        name: String,
+        /// The condition.
+        condition: String,
+        /// Value read.
+        value: String,
+    },
+    /// The condition stopped holding.
+    Cleared {
+        /// Name of the watch.
+        name: String,
+        /// The condition.
+        condition: String,
+        /// Value read.
+        value: String,
+    },
+    /// The value could not be read or compared; reported once until a read succeeds.
+    Failed {
+        /// Name of the watch.
+        name: String,
+        /// What went wrong.
+        error: String,
+    },
+}
+
+/// State of a running watch.
+#[derive(Debug, Clone, Default)]
+pub struct WatchStatus {
+    /// Reads attempted.
+    pub checks: u32,
+    /// Latest value read.
+    pub value: Option<String>,
+    /// Whether the condition holds.
+    pub triggered: bool,
+    /// Error of the latest read, if it failed.
+    pub error: Option<String>,
+}
+
+struct Watch {
+    config: WatchConfig,
+    status: Arc<Mutex<WatchStatus>>,
+    cancel: CancellationToken,
+}
+
+/// Running watches by name.
+///
+/// Each MCP session has its own watches, which stop when the session ends.
+#[derive(Default)]
+pub struct Watches {
+    inner: Mutex<BTreeMap<String, Watch>>,
+}
+
+impl Watches {
+    /// Create an empty set of watches.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a watch for `config`, replacing any watch with the same name. `notify` is
+    /// called with every [`WatchEvent`].
+    pub fn start<E, N>(&self, executor: E, pool: Arc<RpcPool>, config: WatchConfig, notify: N)
+    where
+        E: CommandExecutor,
+        N: Fn(&WatchEvent) + Send + Sync + 'static,
+    {
+        let status = Arc::new(Mutex::new(WatchStatus::default()));
+        let cancel = CancellationToken::new();
+        tokio::spawn(run(
+            executor,
+            pool,
+            config.clone(),
+            Arc::clone(&status),
+            cancel.clone(),
+            notify,
+        ));
+        if let Ok(mut watches) = self.inner.lock() {
+            let replaced = watches.insert(
+                config.name.clone(),
+                Watch {
+                    config,
+                    status,
+                    cancel,
+                },
+            );
+            if let Some(replaced) = replaced {
+                replaced.cancel.cancel();
+            }
+        }
+    }
+
+    /// Stop the watch `name`, returning its config.
+    pub fn stop(&self, name: &str) -> Option<WatchConfig> {
+        let watch = self.inner.lock().ok()?.remove(name)?;
+        watch.cancel.cancel();
+        Some(watch.config)
+    }
+
+    /// Running watches with their state, by name.
+    pub fn list(&self) -> Vec<(WatchConfig, WatchStatus)> {
+        self.inner
+            .lock()
+            .map(|watches| {
+                watches
+                    .values()
+                    .map(|watch| {
+                        let status = watch
+                            .status
+                            .lock()
+                            .map(|status| status.clone())
+                            .unwrap_or_default();
+                        (watch.config.clone(), status)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for Watches {
+    fn drop(&mut self) {
+        if let Ok(watches) = self.inner.lock() {
+            for watch in watches.values() {
+                watch.cancel.cancel();
+            }
+        }
+    }
+}
+
+/// Send each event to `peer` as a `watch` log message: `warning` when a condition
+/// triggers, `info` when it clears and `error` when reads fail.
+pub fn notifier(peer: Peer<RoleServer>) -> impl Fn(&WatchEvent) + Send + Sync + 'static {
+    let messages = logging::forward(peer);
+    move |event: &WatchEvent| {
+        let message = LoggingMessageNotificationParam {
+            level: match event {
+                WatchEvent::Triggered { .. } => LoggingLevel::Warning,
+                WatchEvent::Cleared { .. } => LoggingLevel::Info,
+                WatchEvent::Failed { .. } => LoggingLevel::Error,
+            },
+            logger: Some(LOGGER.to_owned()),
+            data: serde_json::to_value(event).unwrap_or_default(),
+        };
+        // A client that went away just misses the update; the watch runs on.
+        messages.send(message);
+    }
+}
+
+/// Read the watched value, reusing `metadata` for storage reads.
+async fn read<E: CommandExecutor>(
+    executor: &E,
+    pool: &RpcPool,
+    url: &str,
+    query: &WatchQuery,
+    metadata: &mut Option<ChainMetadata>,
+) -> Result<String, String> {
+    match query {
+        WatchQuery::Storage {
+            pallet,
+            item,
+            keys,
+            field,
+        } => {
+            let chain = match metadata.take() {
+                Some(chain) => chain,
+                None => ChainMetadata::fetch(pool, url)
+                    .await
+                    .map_err(|e| e.to_string())?,
+            };
+            let key = chain.storage_key(pallet, item, keys)?;
+            let bytes = fetch_storage_at(pool, url, &key, None)
+                .await
+                .map_err(|e| e.to_string())?;
+            let value = match field {
+                Some(field) => chain.decode_storage_field(pallet, item, bytes.as_deref(), field),
+                None => chain.decode_storage(pallet, item, bytes.as_deref()),
+            }?;
+            // Kept only after a successful read, so a runtime upgrade refetches it.
+            *metadata = Some(chain);
+            Ok(value)
+        }
+        WatchQuery::Contract {
+            path,
+            contract,
+            message,
+            args,
+        } => {
+            let result = call_contract(
+                executor,
+                CallContractParams {
+                    path: path.clone(),
+                    contract: contract.clone(),
+                    message: message.clone(),
+                    args: args.clone(),
+                    args_file: None,
+                    value: None,
+                    gas_limit: None,
+                    proof_size: None,
+                    execute: None,
+                    url: Some(url.to_owned()),
+                    summary: None,
+                    dry_run: None,
+                    ci_mode: None,
+                    signer: None,
+                    use_wallet: None,
+                },
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            let output = extract_text(&result).unwrap_or_default();
+            if result.is_error == Some(true) {
+                return Err(output);
+            }
+            parsing::call_result(&output)
+                .ok_or_else(|| format!("No result in the output of {}", message))
+        }
+    }
+}
+
+/// Read and compare every interval until `cancel` fires.
+async fn run<E, N>(
+    executor: E,
+    pool: Arc<RpcPool>,
+    config: WatchConfig,
+    status: Arc<Mutex<WatchStatus>>,
+    cancel: CancellationToken,
+    notify: N,
+) where
+    E: CommandExecutor,
+    N: Fn(&WatchEvent),
+{
+    let condition = config.condition();
+    let mut metadata = None;
+    let mut triggered = false;
+    let mut failing = false;
+    loop {
+        let read = tokio::select! {
+            read = read(&executor, &pool, &config.url, &config.query, &mut metadata) => read,
+            () = cancel.cancelled() => break,
+        };
+        let outcome = read.and_then(|value| {
+            let holds = config.comparison.holds(&value, &config.threshold)?;
+            Ok((value, holds))
+        });
+        let event = match &outcome {
+            Ok((value, holds)) if *holds != triggered => {
+                triggered = *holds;
+                let (name, condition, value) =
+                    (config.name.clone(), condition.clone(), value.clone());
+                Some(if triggered {
+                    WatchEvent::Triggered {
+                        name,
+                        condition,
+                        value,
+                    }
+                } else {
+                    WatchEvent::Cleared {
+                        name,
+                        condition,
+                        value,
+                    }
+                })
+            }
+            Err(error) if !failing => Some(WatchEvent::Failed {
+                name: config.name.clone(),
+                error: error.clone(),
+            }),
+            _ => None,
+        };
+        failing = outcome.is_err();
+        if let Ok(mut status) = status.lock() {
+            status.checks += 1;
+            status.triggered = triggered;
+            match &outcome {
+                Ok((value, _)) => {
+                    status.value = Some(value.clone());
+                    status.error = None;
+                }
+                Err(error) => status.error = Some(error.clone()),
+            }
+        }
+        if let Some(event) = event {
+            notify(&event);
+        }
+        tokio::select! {
+            () = tokio::time::sleep(config.interval) => {}
+            () = cancel.cancelled() => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comparisons_are_numeric_when_both_sides_are_numbers() {
+        assert_eq!(Comparison::parse("<"), Some(Comparison::Lt));
+        assert_eq!(Comparison::parse("GE"), Some(Comparison::Ge));
+        assert_eq!(Comparison::parse("~"), None);
+
+        assert_eq!(Comparison::Lt.holds("900", "1_000"), Ok(true));
+        assert_eq!(Comparison::Lt.holds("Ok(1000)", "1000"), Ok(false));
+        assert_eq!(Comparison::Ge.holds(" Ok(Some(12)) ", "12"), Ok(true));
+        assert_eq!(Comparison::Eq.holds("Ok(true)", "true"), Ok(true));
+        assert_eq!(Comparison::Ne.holds("Ok(false)", "true"), Ok(true));
+        assert!(Comparison::Gt.holds("Ok(false)", "1").is_err());
+    }
+}
@@ -0,0 +1,244 @@
+//! Deployment announcements posted to a webhook.
+//!
+//! When a webhook URL is configured, the server posts a JSON [`Announcement`] after
+//! each successful contract deployment or runtime upgrade, so teams can follow what
+//! their agents shipped in Slack, Discord or any other service accepting JSON.
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::encoder::{blake2_256, decode_hex, to_hex};
+use crate::endpoints;
+use crate::error::{PopMcpError, PopMcpResult};
+
+/// Env var naming the webhook URL.
+pub const WEBHOOK_URL_ENV: &str = "POP_MCP_WEBHOOK_URL";
+
+/// Calls that replace a chain's runtime code, as (pallet, function).
+const RUNTIME_UPGRADE_CALLS: &[(&str, &str)] = &[
+    ("System", "set_code"),
+    ("System", "set_code_without_checks"),
+    ("System", "apply_authorized_upgrade"),
+];
+
+/// Give up on a webhook that doesn't answer within this time.
+const POST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What was shipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Event {
+    /// A contract was deployed and instantiated.
+    ContractDeployed,
+    /// The runtime code of a chain was replaced.
+    RuntimeUpgraded,
+}
+
+/// JSON payload posted to the webhook.
+///
+/// `text` and `content` carry the same human-readable line, so the payload renders
+/// in Slack and Discord incoming webhooks without a translation layer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Announcement {
+    /// What was shipped.
+    pub event: Event,
+    /// Known network name, or the RPC URL.
+    pub network: String,
+    /// RPC URL the change was submitted to.
+    pub url: String,
+    /// Contract address, for deployments.
+    pub address: Option<String>,
+    /// Code hash of the contract or runtime, when known.
+    pub code_hash: Option<String>,
+    /// Signing account, when it is a public dev account (e.g. `//Alice`).
+    pub signer: Option<String>,
+    /// One-line summary for chat services (Slack).
+    pub text: String,
+    /// Same summary for Discord.
+    pub content: String,
+}
+
+impl Announcement {
+    /// Announcement of `event` on the chain at `url`.
+    pub fn new(
+        event: Event,
+        url: &str,
+        address: Option<String>,
+        code_hash: Option<String>,
+        suri: Option<&str>,
+    ) -> Self {
+        let network = endpoints::network_for(url)
+            .map_or_else(|| url.to_owned(), |network| network.name.to_owned());
+        let signer = suri.and_then(public_signer);
+        let mut text = match (event, &address) {
+            (Event::ContractDeployed, Some(address)) => {
+                format!("Contract {} deployed on {}", address, network)
+            }
+            (Event::ContractDeployed, None) => format!("Contract deployed on {}", network),
+            (Event::RuntimeUpgraded, _) => format!("Runtime upgraded on {}", network),
+        };
+        if let Some(hash) = &code_hash {
+            text.push_str(&format!(" (code hash {})", hash));
+        }
+        if let Some(signer) = &signer {
+            text.push_str(&format!(" by {}", signer));
+        }
+        Self {
+            event,
+            network,
+            url: url.to_owned(),
+            address,
+            code_hash,
+            signer,
+            content: text.clone(),
+            text,
+        }
+    }
+}
+
+/// Whether `pallet.function` upgrades the runtime.
+pub fn is_runtime_upgrade(pallet: &str, function: &str) -> bool {
+    RUNTIME_UPGRADE_CALLS
+        .iter()
+        .any(|(p, f)| p.eq_ignore_ascii_case(pallet) && f.eq_ignore_ascii_case(function))
+}
+
+/// Code hash (BLAKE2-256) of runtime code given as hex.
+pub fn runtime_code_hash(code: &str) -> Option<String> {
+    decode_hex(code)
+        .ok()
+        .map(|bytes| to_hex(&blake2_256(&bytes)))
+}
+
+/// `suri` if it names a well-known dev account (`//Alice`), which is safe to share.
+/// Any other SURI is a secret and is never announced.
+fn public_signer(suri: &str) -> Option<String> {
+    let name = suri.strip_prefix("//")?;
+    (!name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric())).then(|| suri.to_owned())
+}
+
+/// Posts announcements to a configured URL.
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl Webhook {
+    /// Webhook posting to `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Post `announcement`, failing on transport errors and non-success statuses.
+    pub async fn post(&self, announcement: &Announcement) -> PopMcpResult<()> {
+        let response = self
+            .client
+            .post(&self.url)
+            .timeout(POST_TIMEOUT)
+            .json(announcement)
+            .send()
+            .await
+            .map_err(|e| PopMcpError::Internal(format!("Webhook request failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(PopMcpError::Internal(format!(
+                "Webhook returned {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Post `announcement` in the background. Delivery is best effort: a failing
+    /// webhook must not hold up or fail the tool call that triggered it.
+    pub fn announce(&self, announcement: Announcement) {
+        let webhook = self.clone();
+        tokio::spawn(async move {
+            let _ = webhook.post(&announcement).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn announcement_names_network_and_hides_secret_signers() {
+        let announcement = Announcement::new(
+            Event::ContractDeployed,
+            "wss://rpc1.paseo.popnetwork.xyz",
+            Some("0x58".to_owned()),
+            Some("0x4f1d".to_owned()),
+            Some("//Alice"),
+        );
+        assert_eq!(announcement.network, "Pop Testnet");
+        assert_eq!(
+            announcement.text,
+            "Contract 0x58 deployed on Pop Testnet (code hash 0x4f1d) by //Alice"
+        );
+        let secret = Announcement::new(
+            Event::RuntimeUpgraded,
+            "ws://localhost:9944",
+            None,
+            None,
+            Some("bottom drive obey lake curtain smoke basket hold race lonely fit walk"),
+        );
+        assert_eq!(secret.signer, None);
+        assert_eq!(secret.text, "Runtime upgraded on ws://localhost:9944");
+    }
+
+    #[test]
+    fn runtime_upgrades_are_recognised() {
+        assert!(is_runtime_upgrade("system", "set_code"));
+        assert!(!is_runtime_upgrade("System", "remark"));
+        assert_eq!(
+            runtime_code_hash("0x"),
+            Some("0x0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8".to_owned())
+        );
+        assert_eq!(runtime_code_hash("./runtime.wasm"), None);
+    }
+
+    #[tokio::test]
+    async fn post_sends_json_payload() -> std::io::Result<()> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}/hook", listener.local_addr()?);
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await?;
+            let mut request = Vec::new();
+            let mut chunk = [0u8; 4096];
+            // Read until the JSON body is complete.
+            while !request.ends_with(b"}") {
+                let read = socket.read(&mut chunk).await?;
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&chunk[..read]);
+            }
+            socket
+                .write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n")
+                .await?;
+            Ok::<_, std::io::Error>(String::from_utf8_lossy(&request).into_owned())
+        });
+
+        let announcement = Announcement::new(
+            Event::ContractDeployed,
+            "ws://localhost:9944",
+            Some("0x58".to_owned()),
+            None,
+            None,
+        );
+        let posted = Webhook::new(url).post(&announcement).await;
+        assert!(posted.is_ok());
+        let request = server.await.map_err(std::io::Error::other)??;
+        assert!(request.starts_with("POST /hook HTTP/1.1"));
+        assert!(request.contains(r#""event":"contract_deployed""#));
+        assert!(request.contains(r#""address":"0x58""#));
+        Ok(())
+    }
+}
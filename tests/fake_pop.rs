@@ -0,0 +1,1161 @@
+//! Hermetic integration tests running every tool against the `fake-pop` binary.
+#![cfg(feature = "fake-pop")]
+#![allow(missing_docs, clippy::str_to_string)]
+
+use anyhow::{anyhow, Result};
+use pop_mcp_server::deployments::Registry;
+use pop_mcp_server::fixtures::{ensure_fixture, FixtureKind, FIXTURES_DIR_ENV};
+use pop_mcp_server::logging::CommandLog;
+use pop_mcp_server::nodes::NodeRegistry;
+use pop_mcp_server::outputs::{BuildOutput, CallOutput, DeployOutput, GasEstimate, NodeInfo};
+use pop_mcp_server::resources::read_resource;
+use pop_mcp_server::rpc::RpcPool;
+use pop_mcp_server::stream;
+use pop_mcp_server::tools::common::{extract_text, extract_texts};
+use pop_mcp_server::tools::*;
+use pop_mcp_server::wallet;
+use pop_mcp_server::{CommandExecutor, PopExecutor, PopMcpError};
+use rmcp::model::{CallToolResult, ResourceContents};
+use tempfile::TempDir;
+
+fn executor() -> PopExecutor {
+    PopExecutor::new().with_binary(env!("CARGO_BIN_EXE_fake-pop"))
+}
+
+/// Executor running in `dir`, for commands that write into the cwd.
+fn executor_in(dir: &TempDir) -> PopExecutor {
+    PopExecutor::with_cwd(dir.path().to_path_buf()).with_binary(env!("CARGO_BIN_EXE_fake-pop"))
+}
+
+fn is_error(result: &CallToolResult) -> bool {
+    result.is_error == Some(true)
+}
+
+fn text(result: &CallToolResult) -> Result<String> {
+    extract_text(result).ok_or_else(|| anyhow!("CallToolResult missing text content"))
+}
+
+/// Assert `value` conforms to the published `schema://<tool>` resource.
+fn assert_matches_schema(tool: &str, value: impl serde::Serialize) -> Result<()> {
+    let Some(ResourceContents::TextResourceContents { text, .. }) = read_resource(
+        &format!("schema://{}", tool),
+        &[],
+        &Registry::new("deployments.json"),
+    ) else {
+        return Err(anyhow!("no schema published for {}", tool));
+    };
+    let schema: serde_json::Value = serde_json::from_str(&text)?;
+    let instance = serde_json::to_value(value)?;
+    let validator = jsonschema::validator_for(&schema).map_err(|e| anyhow!("{}", e))?;
+    let errors: Vec<String> = validator
+        .iter_errors(&instance)
+        .map(|e| e.to_string())
+        .collect();
+    assert!(
+        errors.is_empty(),
+        "{} output violates schema: {:?}",
+        tool,
+        errors
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn check_pop_installation_reports_version() -> Result<()> {
+    let result = check_pop_installation(&executor(), CheckPopInstallationParams {}).await?;
+    assert!(!is_error(&result));
+    assert!(text(&result)?.contains("0.0.0-fake"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_contract_succeeds() -> Result<()> {
+    let dir = TempDir::new()?;
+    let result = create_contract(
+        &executor_in(&dir),
+        CreateContractParams {
+            name: "flipper".to_string(),
+            template: "standard".to_string(),
+            template_url: None,
+            ink_version: Some("v5".to_string()),
+            with_e2e_tests: Some(true),
+            with_frontend: None,
+            cwd: None,
+            license: None,
+            repository: None,
+            authors: None,
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    let absolute = std::path::absolute(dir.path().join("flipper"))?;
+    assert!(text(&result)?.contains(&format!("flipper at {}", absolute.display())));
+    assert!(dir.path().join("flipper/lib.rs").exists());
+    assert!(text(&result)?.contains("ink! pinned to 5.1.1 (v5"));
+    let manifest = std::fs::read_to_string(dir.path().join("flipper/Cargo.toml"))?;
+    assert!(manifest.contains("ink = { version = \"5.1.1\", default-features = false }"));
+    assert!(manifest.contains("ink_e2e = { version = \"5.1.1\" }"));
+    assert!(manifest.contains("e2e-tests = []"));
+    let lib = std::fs::read_to_string(dir.path().join("flipper/lib.rs"))?;
+    assert!(lib.contains("contract.call_builder::<Flipper>()"));
+
+    let again = create_contract(
+        &executor_in(&dir),
+        CreateContractParams {
+            name: "flipper".to_string(),
+            template: "standard".to_string(),
+            template_url: None,
+            ink_version: None,
+            with_e2e_tests: None,
+            with_frontend: None,
+            cwd: None,
+            license: None,
+            repository: None,
+            authors: None,
+        },
+    )
+    .await;
+    assert!(matches!(again, Err(PopMcpError::InvalidInput(_))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_contract_in_cwd_leaves_process_dir_alone() -> Result<()> {
+    let dir = TempDir::new()?;
+    let result = create_contract(
+        &executor(),
+        CreateContractParams {
+            name: "flipper".to_string(),
+            template: "standard".to_string(),
+            template_url: None,
+            ink_version: None,
+            with_e2e_tests: None,
+            with_frontend: None,
+            cwd: Some(dir.path().display().to_string()),
+            license: None,
+            repository: None,
+            authors: None,
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    assert!(text(&result)?.contains(&dir.path().join("flipper").display().to_string()));
+    assert!(dir.path().join("flipper/lib.rs").exists());
+    assert!(!std::env::current_dir()?.join("flipper").exists());
+
+    let missing = create_chain(
+        &executor(),
+        CreateChainParams {
+            name: "my_chain".to_string(),
+            provider: "pop".to_string(),
+            template: "r0gue-io/base-parachain".to_string(),
+            symbol: None,
+            decimals: None,
+            cwd: Some(dir.path().join("missing").display().to_string()),
+            license: None,
+            repository: None,
+            authors: None,
+        },
+    )
+    .await;
+    assert!(matches!(missing, Err(PopMcpError::InvalidInput(_))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_chain_succeeds() -> Result<()> {
+    let dir = TempDir::new()?;
+    let result = create_chain(
+        &executor_in(&dir),
+        CreateChainParams {
+            name: "my_chain".to_string(),
+            provider: "pop".to_string(),
+            template: "r0gue-io/base-parachain".to_string(),
+            symbol: None,
+            decimals: None,
+            cwd: None,
+            license: None,
+            repository: None,
+            authors: None,
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    let absolute = std::path::absolute(dir.path().join("my_chain"))?;
+    assert!(text(&result)?.contains(&format!("my_chain at {}", absolute.display())));
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_chain_stamps_package_metadata() -> Result<()> {
+    let dir = TempDir::new()?;
+    let result = create_chain(
+        &executor(),
+        CreateChainParams {
+            name: "my_chain".to_string(),
+            provider: "pop".to_string(),
+            template: "r0gue-io/base-parachain".to_string(),
+            symbol: None,
+            decimals: None,
+            cwd: Some(dir.path().display().to_string()),
+            license: Some("Apache-2.0".to_string()),
+            repository: Some("https://github.com/org/my_chain".to_string()),
+            authors: None,
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    assert!(text(&result)?.contains("Package metadata set in Cargo.toml, runtime/Cargo.toml"));
+    let root = std::fs::read_to_string(dir.path().join("my_chain/Cargo.toml"))?;
+    assert!(root.contains("license = \"Apache-2.0\""));
+    assert!(root.contains("authors = [\"Anonymous\"]"));
+    let runtime = std::fs::read_to_string(dir.path().join("my_chain/runtime/Cargo.toml"))?;
+    assert!(runtime.contains("license.workspace = true"));
+    assert!(runtime.contains("repository = \"https://github.com/org/my_chain\""));
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_contract_succeeds_for_existing_path() -> Result<()> {
+    let dir = TempDir::new()?;
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"flipper\"\n",
+    )?;
+    std::fs::write(dir.path().join("lib.rs"), "")?;
+    let result = build_contract(
+        &executor(),
+        BuildContractParams {
+            path: dir.path().display().to_string(),
+            release: Some(true),
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    let ink = dir.path().join("target/ink");
+    let report = text(&result)?;
+    assert!(report.starts_with("Build successful!\nBundle: "));
+    assert!(report.contains(&format!(
+        "Code: {} (3 bytes)",
+        ink.join("flipper.polkavm").display()
+    )));
+    assert!(report.ends_with(&format!("Code hash: 0x{}", "ab".repeat(32))));
+    let build: BuildOutput = serde_json::from_value(structured(&result)?)?;
+    assert_eq!(
+        build.metadata.map(|metadata| metadata.path),
+        Some(ink.join("flipper.json").display().to_string())
+    );
+
+    let result = build_contract(
+        &executor(),
+        BuildContractParams {
+            path: dir.path().display().to_string(),
+            release: Some(true),
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
+        },
+    )
+    .await?;
+    assert!(text(&result)?.starts_with("Build skipped: sources unchanged"));
+    let cached: BuildOutput = serde_json::from_value(structured(&result)?)?;
+    assert!(cached.cached);
+    assert_eq!(cached.code_hash, build.code_hash);
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_contract_missing_path_fails() -> Result<()> {
+    let result = build_contract(
+        &executor(),
+        BuildContractParams {
+            path: "/nonexistent/contract".to_string(),
+            release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
+        },
+    )
+    .await?;
+    assert!(is_error(&result));
+    assert!(text(&result)?.contains("Build failed"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn failed_commands_are_in_the_history() -> Result<()> {
+    let path = "/nonexistent/audited-contract";
+    let result = build_contract(
+        &executor(),
+        BuildContractParams {
+            path: path.to_string(),
+            release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
+        },
+    )
+    .await?;
+    assert!(is_error(&result));
+    let history = list_command_history(ListCommandHistoryParams {
+        limit: Some(500),
+        failed_only: Some(true),
+    })
+    .await?;
+    let history = text(&history)?;
+    assert!(history.contains("[failed]"));
+    assert!(history.contains(&format!("pop build --path {}", path)));
+    Ok(())
+}
+
+/// Whether the worker a hung fake-pop command spawned in `dir` is still running.
+///
+/// Killed workers may linger as zombies until reaped, which counts as gone.
+#[cfg(target_os = "linux")]
+async fn hang_worker_running(dir: &TempDir) -> Result<bool> {
+    let pid = std::fs::read_to_string(dir.path().join(".fake-pop-hang-worker.pid"))?;
+    for _ in 0..50 {
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid.trim()));
+        let running = stat.is_ok_and(|stat| {
+            stat.rsplit_once(") ")
+                .is_some_and(|(_, rest)| !rest.starts_with('Z'))
+        });
+        if !running {
+            return Ok(false);
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    Ok(true)
+}
+
+#[tokio::test]
+async fn build_contract_timeout_kills_hung_build() -> Result<()> {
+    let dir = TempDir::new()?;
+    std::fs::write(dir.path().join(".fake-pop-hang"), "")?;
+    let start = std::time::Instant::now();
+    let result = build_contract(
+        &executor(),
+        BuildContractParams {
+            path: dir.path().display().to_string(),
+            release: None,
+            timeout_secs: Some(1),
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
+        },
+    )
+    .await?;
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+    assert!(is_error(&result));
+    let text = text(&result)?;
+    assert!(text.contains("timed out after 1s"));
+    assert!(text.contains("Compiling hang"));
+    // A cargo left compiling would hold the target-dir lock and block the next build.
+    #[cfg(target_os = "linux")]
+    assert!(!hang_worker_running(&dir).await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn cancellation_kills_hung_build() -> Result<()> {
+    let dir = TempDir::new()?;
+    std::fs::write(dir.path().join(".fake-pop-hang"), "")?;
+    let token = tokio_util::sync::CancellationToken::new();
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        canceller.cancel();
+    });
+    let start = std::time::Instant::now();
+    let result = build_contract(
+        &executor().with_cancellation(token),
+        BuildContractParams {
+            path: dir.path().display().to_string(),
+            release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
+        },
+    )
+    .await?;
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+    assert!(is_error(&result));
+    let text = text(&result)?;
+    assert!(text.contains("was cancelled"));
+    assert!(text.contains("Compiling hang"));
+    #[cfg(target_os = "linux")]
+    assert!(!hang_worker_running(&dir).await?);
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn dropping_a_running_command_kills_what_it_spawned() -> Result<()> {
+    let dir = TempDir::new()?;
+    std::fs::write(dir.path().join(".fake-pop-hang"), "")?;
+    let path = dir.path().display().to_string();
+    let executor = executor();
+    let args = ["build", "--path", &path];
+    let run = executor.execute(&args);
+    assert!(tokio::time::timeout(std::time::Duration::from_secs(1), run)
+        .await
+        .is_err());
+    assert!(!hang_worker_running(&dir).await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn line_sink_receives_output_while_running() -> Result<()> {
+    let (sink, mut lines) = stream::bounded::<String>(stream::DEFAULT_CAPACITY);
+    let output = executor()
+        .with_line_sink(sink)
+        .execute(&["up", "ink-node", "-y", "--detach"])
+        .await?;
+    let mut received = Vec::new();
+    while let Some((line, _)) = lines.recv().await {
+        received.push(line);
+    }
+    assert_eq!(
+        received.len(),
+        output.lines().filter(|l| !l.trim().is_empty()).count()
+    );
+    assert!(received
+        .iter()
+        .any(|line| line.contains("url: ws://localhost:9944/")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn execute_json_prefers_json_and_falls_back_to_text() -> Result<()> {
+    let node = executor()
+        .execute_json(&["up", "ink-node", "-y", "--detach"])
+        .await?;
+    assert!(node.starts_with('{'));
+    assert_eq!(
+        NodeInfo::parse(&node),
+        Some(NodeInfo {
+            url: "ws://localhost:9944".to_string(),
+            pids: vec![424242, 424243],
+            logs: Vec::new(),
+            port: Some(9944),
+            eth_rpc_port: Some(8545),
+        })
+    );
+
+    let dir = TempDir::new()?;
+    let path = dir.path().display().to_string();
+    let call = executor()
+        .execute_json(&["call", "contract", "--path", &path, "-y"])
+        .await?;
+    assert!(call.contains("Result: Ok(false)"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn zero_timeout_is_rejected() {
+    let result = test_chain(
+        &executor(),
+        TestChainParams {
+            path: ".".to_string(),
+            timeout_secs: Some(0),
+            env: None,
+            toolchain: None,
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+}
+
+#[tokio::test]
+async fn build_chain_succeeds_for_existing_path() -> Result<()> {
+    let dir = TempDir::new()?;
+    let result = build_chain(
+        &executor(),
+        BuildChainParams {
+            path: dir.path().display().to_string(),
+            release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            features: None,
+            profile: None,
+            mode: None,
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_chain_benchmark_mode_builds_only_the_runtime() -> Result<()> {
+    let dir = TempDir::new()?;
+    std::fs::create_dir_all(dir.path().join("runtime"))?;
+    std::fs::write(
+        dir.path().join("runtime/Cargo.toml"),
+        "[package]\nname = \"parachain-template-runtime\"\n",
+    )?;
+    let path = dir.path().display().to_string();
+    let result = build_chain(
+        &executor(),
+        BuildChainParams {
+            path: path.clone(),
+            release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            features: None,
+            profile: Some("production".to_string()),
+            mode: Some("benchmark".to_string()),
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    let wasm = dir.path().join(
+        "target/production/wbuild/parachain-template-runtime/parachain_template_runtime.compact.compressed.wasm",
+    );
+    let report = text(&result)?;
+    assert!(report.starts_with("Runtime build with runtime-benchmarks successful!"));
+    assert!(report.contains(&format!("Runtime wasm: {} (4 bytes)", wasm.display())));
+    assert!(report.contains("Code hash: 0x"));
+
+    let history = list_command_history(ListCommandHistoryParams {
+        limit: Some(500),
+        failed_only: None,
+    })
+    .await?;
+    assert!(text(&history)?.contains(&format!(
+        "pop build --path {} --package parachain-template-runtime --profile production --features runtime-benchmarks",
+        path
+    )));
+    Ok(())
+}
+
+#[tokio::test]
+async fn build_chain_env_applies_to_the_command_only() -> Result<()> {
+    let dir = TempDir::new()?;
+    let target = TempDir::new()?;
+    let env = [(
+        "CARGO_TARGET_DIR".to_string(),
+        target.path().display().to_string(),
+    )];
+    let result = build_chain(
+        &executor(),
+        BuildChainParams {
+            path: dir.path().display().to_string(),
+            release: None,
+            timeout_secs: None,
+            env: Some(env.into_iter().collect()),
+            toolchain: None,
+            features: None,
+            profile: None,
+            mode: None,
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    assert!(target.path().join("debug").is_dir());
+    assert!(!dir.path().join("target").exists());
+    assert!(std::env::var_os("CARGO_TARGET_DIR").is_none());
+
+    let invalid = build_chain(
+        &executor(),
+        BuildChainParams {
+            path: dir.path().display().to_string(),
+            release: None,
+            timeout_secs: None,
+            env: Some([("A=B".to_string(), String::new())].into_iter().collect()),
+            toolchain: None,
+            features: None,
+            profile: None,
+            mode: None,
+        },
+    )
+    .await;
+    assert!(matches!(invalid, Err(PopMcpError::InvalidInput(_))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_contract_and_chain_report_results() -> Result<()> {
+    let dir = TempDir::new()?;
+    let path = dir.path().display().to_string();
+
+    let result = test_contract(
+        &executor(),
+        TestContractParams {
+            path: path.clone(),
+            e2e: false,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+        },
+    )
+    .await?;
+    assert!(text(&result)?.contains("test result: ok"));
+
+    let result = test_chain(
+        &executor(),
+        TestChainParams {
+            path,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+        },
+    )
+    .await?;
+    assert!(text(&result)?.contains("test result: ok"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn up_ink_node_returns_url_and_pids() -> Result<()> {
+    let result = up_ink_node(
+        &executor(),
+        UpInkNodeParams {
+            ink_node_port: Some(9950),
+            eth_rpc_port: Some(8550),
+            name: None,
+            detach: None,
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    assert_eq!(text(&result)?, "ws://localhost:9950");
+    assert_eq!(result.content.len(), 3);
+    let node: NodeInfo = serde_json::from_value(structured(&result)?)?;
+    assert_eq!((node.port, node.eth_rpc_port), (Some(9950), Some(8550)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn deploy_contract_returns_address() -> Result<()> {
+    let dir = TempDir::new()?;
+    let result = deploy_contract(
+        &executor(),
+        DeployContractParams {
+            path: dir.path().display().to_string(),
+            constructor: Some("new".to_string()),
+            args: Some("false".to_string()),
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: None,
+            upload_only: None,
+            url: None,
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
+        },
+        Some("ws://localhost:9944"),
+    )
+    .await?;
+    assert!(!is_error(&result));
+    assert!(text(&result)?
+        .starts_with("Contract deployed!\nAddress: 0x5801b439a678d9d3a68b8019da6a4abfa507de11\n"));
+    let deployed: DeployOutput = serde_json::from_value(structured(&result)?)?;
+    assert_eq!(
+        deployed.address,
+        "0x5801b439a678d9d3a68b8019da6a4abfa507de11"
+    );
+    assert!(deployed
+        .code_hash
+        .is_some_and(|hash| hash.starts_with("0x4f1d")));
+    assert!(deployed
+        .tx_hash
+        .is_some_and(|hash| hash.starts_with("0x9a3f")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn deploy_contract_relays_the_wallet_portal() -> Result<()> {
+    let dir = TempDir::new()?;
+    let (progress, mut received) = stream::bounded(stream::DEFAULT_CAPACITY);
+    let (sink, relay) = wallet::relay(CommandLog::new(), Some(progress));
+    let result = deploy_contract(
+        &executor().with_line_sink(sink),
+        DeployContractParams {
+            path: dir.path().display().to_string(),
+            constructor: Some("new".to_string()),
+            args: Some("false".to_string()),
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: Some(true),
+            upload_only: None,
+            url: None,
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: Some(true),
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
+        },
+        Some("ws://localhost:9944"),
+    )
+    .await?;
+    assert!(!is_error(&result));
+    assert_eq!(relay.await?.as_deref(), Some("http://127.0.0.1:9090"));
+    let message = received.try_recv().map(|(message, _)| message);
+    assert_eq!(
+        message,
+        Some(wallet::signing_message("http://127.0.0.1:9090"))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn call_contract_returns_value() -> Result<()> {
+    let dir = TempDir::new()?;
+    let result = call_contract(
+        &executor(),
+        CallContractParams {
+            path: dir.path().display().to_string(),
+            contract: "0x5801b439a678d9d3a68b8019da6a4abfa507de11".to_string(),
+            message: "get".to_string(),
+            args: None,
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: None,
+            url: Some("ws://localhost:9944".to_string()),
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    assert!(text(&result)?.contains("Ok(false)"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn estimate_gas_returns_numbers() -> Result<()> {
+    let dir = TempDir::new()?;
+    let result = estimate_gas(
+        &executor(),
+        EstimateGasParams {
+            path: dir.path().display().to_string(),
+            contract: "0x5801b439a678d9d3a68b8019da6a4abfa507de11".to_string(),
+            message: "flip".to_string(),
+            args: None,
+            args_file: None,
+            value: None,
+            url: Some("ws://localhost:9944".to_string()),
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    let estimate: GasEstimate = serde_json::from_value(structured(&result)?)?;
+    assert_eq!(
+        (
+            estimate.ref_time,
+            estimate.proof_size,
+            estimate.storage_deposit
+        ),
+        (Some(1112520), Some(16689), Some(4000))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn call_contract_summary_comes_first() -> Result<()> {
+    let dir = TempDir::new()?;
+    let result = call_contract(
+        &executor(),
+        CallContractParams {
+            path: dir.path().display().to_string(),
+            contract: "0x5801b439a678d9d3a68b8019da6a4abfa507de11".to_string(),
+            message: "get".to_string(),
+            args: None,
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: None,
+            url: None,
+            summary: Some(true),
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+        },
+    )
+    .await?;
+    let texts = extract_texts(&result);
+    assert_eq!(texts.len(), 2);
+    assert!(texts[0].starts_with("status: ok\n"));
+    assert!(texts[0].contains("result: Ok(false)"));
+    assert!(texts[1].contains("Call completed successfully!"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn call_chain_metadata_lists_pallets() -> Result<()> {
+    let result = call_chain(
+        &executor(),
+        &RpcPool::new(),
+        CallChainParams {
+            url: "ws://localhost:9944".to_string(),
+            pallet: None,
+            function: None,
+            args: None,
+            sudo: None,
+            execute: None,
+            use_wallet: None,
+            metadata: Some(true),
+            dry_run: None,
+            at_block: None,
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    assert!(text(&result)?.contains("Balances"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn up_network_and_clean_network_succeed() -> Result<()> {
+    let result = up_network(
+        &executor(),
+        UpNetworkParams {
+            path: None,
+            chain: Some("paseo".to_string()),
+            verbose: None,
+            parachain: None,
+            overrides: None,
+            detach: None,
+        },
+    )
+    .await?;
+    assert!(text(&result)?.contains("zombie.json"));
+
+    let result = clean_network(
+        &executor(),
+        CleanNetworkParams {
+            path: None,
+            all: Some(true),
+            keep_state: None,
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    Ok(())
+}
+
+#[tokio::test]
+async fn up_network_overrides_launch_a_temporary_copy() -> Result<()> {
+    let dir = TempDir::new()?;
+    let config = dir.path().join("network.toml");
+    std::fs::write(&config, "[relaychain]\nchain = \"paseo-local\"\n")?;
+    let result = up_network(
+        &executor(),
+        UpNetworkParams {
+            path: Some(config.display().to_string()),
+            chain: None,
+            verbose: None,
+            parachain: None,
+            overrides: Some(NetworkOverrides {
+                relay_chain: Some("westend-local".to_string()),
+                ..NetworkOverrides::default()
+            }),
+            detach: None,
+        },
+    )
+    .await?;
+    assert!(text(&result)?.contains("zombie.json"));
+    // Only the original config is left.
+    assert_eq!(std::fs::read_dir(dir.path())?.count(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn clean_nodes_reports_pids() -> Result<()> {
+    let result = clean_nodes(
+        &executor(),
+        &NodeRegistry::new(),
+        CleanNodesParams {
+            pids: vec![424242],
+            port: None,
+            name: None,
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    assert!(text(&result)?.contains("424242"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn convert_address_succeeds() -> Result<()> {
+    let result = convert_address(
+        &executor(),
+        ConvertAddressParams {
+            address: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    assert!(text(&result)?.contains("0x"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_fixture_creates_once_and_reuses() -> Result<()> {
+    let cache = TempDir::new()?;
+    std::env::set_var(FIXTURES_DIR_ENV, cache.path());
+
+    let result = get_fixture(
+        &executor(),
+        GetFixtureParams {
+            kind: "contract".to_string(),
+            build: None,
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    let output = text(&result)?;
+    assert!(output.contains("Built: true"));
+    let fixture = ensure_fixture(&executor(), FixtureKind::Contract, true, None).await?;
+    assert!(output.contains(&fixture.path.display().to_string()));
+    assert!(fixture
+        .path
+        .starts_with(cache.path().join("pop-cli_0.0.0-fake")));
+    assert!(fixture.path.join("target/ink").exists());
+
+    let chain = ensure_fixture(&executor(), FixtureKind::Chain, false, None).await?;
+    assert!(!chain.built);
+    assert!(chain.path.join("runtime").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn call_contract_missing_args_file_is_rejected() -> Result<()> {
+    let dir = TempDir::new()?;
+    let result = call_contract(
+        &executor(),
+        CallContractParams {
+            path: dir.path().display().to_string(),
+            contract: "0x5801b439a678d9d3a68b8019da6a4abfa507de11".to_string(),
+            message: "flip".to_string(),
+            args: None,
+            args_file: Some("args.json".to_string()),
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: None,
+            url: Some("ws://localhost:9944".to_string()),
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(PopMcpError::InvalidInput(_))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn deploy_contract_reads_args_file() -> Result<()> {
+    let dir = TempDir::new()?;
+    std::fs::write(dir.path().join("args.json"), "[false]")?;
+    let result = deploy_contract(
+        &executor(),
+        DeployContractParams {
+            path: dir.path().display().to_string(),
+            constructor: Some("new".to_string()),
+            args: None,
+            args_file: Some("args.json".to_string()),
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: None,
+            upload_only: None,
+            url: None,
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
+        },
+        Some("ws://localhost:9944"),
+    )
+    .await?;
+    assert!(!is_error(&result));
+    Ok(())
+}
+
+/// The `structured_content` of `result`.
+fn structured(result: &CallToolResult) -> Result<serde_json::Value> {
+    result
+        .structured_content
+        .clone()
+        .ok_or_else(|| anyhow!("no structured content"))
+}
+
+#[tokio::test]
+async fn tool_outputs_match_published_schemas() -> Result<()> {
+    let dir = TempDir::new()?;
+    std::fs::write(dir.path().join("lib.rs"), "")?;
+    std::fs::write(
+        dir.path().join("Cargo.toml"),
+        "[package]\nname = \"flipper\"\n",
+    )?;
+    let path = dir.path().display().to_string();
+
+    let result = build_contract(
+        &executor(),
+        BuildContractParams {
+            path: path.clone(),
+            release: Some(true),
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    let build: BuildOutput = serde_json::from_value(structured(&result)?)?;
+    assert_eq!(build, BuildOutput::collect(&path, true));
+    assert_matches_schema("build_contract", build)?;
+
+    let result = deploy_contract(
+        &executor(),
+        DeployContractParams {
+            path: path.clone(),
+            constructor: Some("new".to_string()),
+            args: Some("false".to_string()),
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: None,
+            upload_only: None,
+            url: None,
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
+        },
+        Some("ws://localhost:9944"),
+    )
+    .await?;
+    let deployed: DeployOutput = serde_json::from_value(structured(&result)?)?;
+    assert_eq!(
+        deployed.address,
+        "0x5801b439a678d9d3a68b8019da6a4abfa507de11"
+    );
+    assert_matches_schema("deploy_contract", deployed)?;
+
+    let result = call_contract(
+        &executor(),
+        CallContractParams {
+            path,
+            contract: "0x5801b439a678d9d3a68b8019da6a4abfa507de11".to_string(),
+            message: "get".to_string(),
+            args: None,
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: None,
+            url: Some("ws://localhost:9944".to_string()),
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+        },
+    )
+    .await?;
+    let call: CallOutput = serde_json::from_value(structured(&result)?)?;
+    assert_eq!(call.result.as_deref(), Some("Ok(false)"));
+    assert_eq!(call.value, Some(serde_json::json!({ "Ok": false })));
+    assert_matches_schema("call_contract", call)?;
+
+    let result = up_ink_node(
+        &executor(),
+        UpInkNodeParams {
+            ink_node_port: None,
+            eth_rpc_port: None,
+            name: None,
+            detach: None,
+        },
+    )
+    .await?;
+    let node: NodeInfo = serde_json::from_value(structured(&result)?)?;
+    assert_eq!(node.pids, vec![424242, 424243]);
+    assert_matches_schema("up_ink_node", node)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn self_test_reports_every_stage() -> Result<()> {
+    let result = self_test(
+        &executor(),
+        SelfTestParams {
+            budget_secs: None,
+            ink_node_port: None,
+            eth_rpc_port: None,
+        },
+    )
+    .await?;
+    let text = text(&result)?;
+    assert!(!is_error(&result));
+    assert!(text.starts_with("Self-test passed"));
+    for stage in [
+        "scaffold",
+        "build",
+        "launch node",
+        "deploy",
+        "call get",
+        "call flip",
+    ] {
+        assert!(text.contains(&format!("✅ {} (", stage)));
+    }
+    assert!(text.contains("Ok(false)"));
+    assert!(text.contains("cleanup: stopped node"));
+    Ok(())
+}
@@ -0,0 +1,129 @@
+//! Resilience tests injecting failures into `fake-pop` runs.
+#![cfg(all(feature = "fake-pop", feature = "fault-injection"))]
+#![allow(missing_docs, clippy::str_to_string)]
+
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use pop_mcp_server::faults::{Fault, FaultPlan};
+use pop_mcp_server::tools::common::extract_text;
+use pop_mcp_server::tools::*;
+use pop_mcp_server::{CommandExecutor, PopExecutor, PopMcpError};
+use rmcp::model::CallToolResult;
+use tempfile::TempDir;
+
+fn executor(plan: FaultPlan) -> PopExecutor {
+    PopExecutor::new()
+        .with_binary(env!("CARGO_BIN_EXE_fake-pop"))
+        .with_faults(plan)
+}
+
+fn is_error(result: &CallToolResult) -> bool {
+    result.is_error == Some(true)
+}
+
+fn text(result: &CallToolResult) -> Result<String> {
+    extract_text(result).ok_or_else(|| anyhow!("CallToolResult missing text content"))
+}
+
+#[tokio::test]
+async fn timeout_surfaces_as_command_error() {
+    let executor =
+        executor(FaultPlan::new().inject("call", Fault::Timeout(Duration::from_millis(10))));
+    let result = executor.execute(&["call", "chain", "--metadata"]).await;
+    assert!(
+        matches!(result, Err(PopMcpError::CommandExecution(ref msg)) if msg.contains("timed out"))
+    );
+}
+
+#[tokio::test]
+async fn faults_only_hit_matching_subcommands() -> Result<()> {
+    let executor =
+        executor(FaultPlan::new().inject("build", Fault::ProcessDeath { after_bytes: 0 }));
+    let result = check_pop_installation(&executor, CheckPopInstallationParams {}).await?;
+    assert!(!is_error(&result));
+    Ok(())
+}
+
+#[tokio::test]
+async fn up_ink_node_partial_output_reports_parse_failure() -> Result<()> {
+    let executor =
+        executor(FaultPlan::new().inject("up ink-node", Fault::PartialOutput { bytes: 40 }));
+    let result = up_ink_node(
+        &executor,
+        UpInkNodeParams {
+            ink_node_port: None,
+            eth_rpc_port: None,
+            name: None,
+            detach: None,
+        },
+    )
+    .await?;
+    assert!(is_error(&result));
+    assert!(text(&result)?.contains("Failed to parse websocket URL"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn non_utf8_output_is_decoded_lossily() -> Result<()> {
+    let executor = executor(FaultPlan::new().inject("convert", Fault::NonUtf8));
+    let result = convert_address(
+        &executor,
+        ConvertAddressParams {
+            address: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+        },
+    )
+    .await?;
+    assert!(!is_error(&result));
+    assert!(text(&result)?.contains('\u{FFFD}'));
+    Ok(())
+}
+
+#[tokio::test]
+async fn deploy_process_death_is_reported_as_failure() -> Result<()> {
+    let dir = TempDir::new()?;
+    let executor = executor(FaultPlan::new().inject("up", Fault::ProcessDeath { after_bytes: 30 }));
+    let result = deploy_contract(
+        &executor,
+        DeployContractParams {
+            path: dir.path().display().to_string(),
+            constructor: None,
+            args: None,
+            args_file: None,
+            value: None,
+            gas_limit: None,
+            proof_size: None,
+            execute: None,
+            upload_only: None,
+            url: Some("ws://localhost:9944".to_string()),
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
+        },
+        None,
+    )
+    .await?;
+    assert!(is_error(&result));
+    assert!(!text(&result)?.contains("0x5801b439a678d9d3a68b8019da6a4abfa507de11"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn slow_commands_run_concurrently() {
+    let executor =
+        executor(FaultPlan::new().inject("build", Fault::Timeout(Duration::from_millis(300))));
+    let start = Instant::now();
+    let (first, second) = tokio::join!(
+        executor.execute(&["build", "--path", "."]),
+        executor.execute(&["build", "--path", "."])
+    );
+    assert!(first.is_err() && second.is_err());
+    // Sequential (blocking) execution would take at least 600ms.
+    assert!(start.elapsed() < Duration::from_millis(550));
+}
@@ -14,22 +14,16 @@ mod common {
     //! Common test utilities and fixtures for pop-mcp-server integration tests.
 
     use anyhow::{anyhow, Context, Result};
-    use pop_mcp_server::executor::PopExecutor;
-    // use pop_mcp_server::tools::build::chain::{build_chain, BuildChainParams};
-    use pop_mcp_server::tools::build::contract::{build_contract, BuildContractParams};
-    use pop_mcp_server::tools::common::extract_text;
-    // use pop_mcp_server::tools::new::chain::{create_chain, CreateChainParams};
-    use pop_mcp_server::tools::new::contract::{create_contract, CreateContractParams};
-    use pop_mcp_server::tools::up::chain::{up_ink_node, UpInkNodeParams};
+    use pop_mcp_server::executor::{CommandExecutor, PopExecutor};
+    use pop_mcp_server::fixtures::{ensure_fixture, FixtureKind};
+    use pop_mcp_server::outputs::DeployOutput;
+    use pop_mcp_server::testing::{SharedNode, SharedNodeGuard};
+    use pop_mcp_server::tools::common::{extract_text, extract_texts};
     use pop_mcp_server::tools::up::contract::{deploy_contract, DeployContractParams};
-    use rmcp::model::{CallToolResult, RawContent};
+    use rmcp::model::CallToolResult;
     use std::net::{TcpStream, ToSocketAddrs};
     use std::path::{Path, PathBuf};
-    use std::process::Command;
-    use std::sync::{
-        atomic::{AtomicUsize, Ordering},
-        Mutex, OnceLock,
-    };
+    use std::sync::{Mutex, OnceLock};
     use std::time::{Duration, Instant};
     use tempfile::TempDir;
 
@@ -93,14 +87,7 @@ mod common {
     }
 
     pub(crate) fn texts(result: &CallToolResult) -> Vec<String> {
-        result
-            .content
-            .iter()
-            .filter_map(|c| match &c.raw {
-                RawContent::Text(t) => Some(t.text.clone()),
-                _ => None,
-            })
-            .collect()
+        extract_texts(result)
     }
 
     pub(crate) struct TestEnv {
@@ -110,11 +97,12 @@ mod common {
 
     impl TestEnv {
         /// Create a temporary workdir and verify Pop CLI is available.
-        pub(crate) fn new() -> Result<Self> {
+        pub(crate) async fn new() -> Result<Self> {
             let tempdir = TempDir::new().context("Failed to create temp dir")?;
             let executor = PopExecutor::with_cwd(tempdir.path().to_path_buf());
             executor
                 .execute(&["--version"])
+                .await
                 .map_err(|e| anyhow!("Pop CLI not available: {e}"))?;
             Ok(Self { tempdir, executor })
         }
@@ -132,90 +120,20 @@ mod common {
 
     impl InkNode {
         /// Port for shared test node (different from default 9944 to avoid conflicts).
-        pub(crate) const PORT: u16 = 9945;
+        pub(crate) const PORT: u16 = SharedNode::INK_NODE_PORT;
         /// ETH RPC port for shared test node.
-        pub(crate) const ETH_PORT: u16 = 8546;
+        pub(crate) const ETH_PORT: u16 = SharedNode::ETH_RPC_PORT;
 
         /// Start the shared ink-node if needed and return its URL.
-        /// Uses a Mutex to allow restart if node was killed.
-        fn start_or_get_url() -> Result<String> {
-            let mut node = NODE.lock().unwrap();
-
-            // Check if we have a node and it's still alive
-            if let Some(ref info) = *node {
-                if is_port_open("127.0.0.1", Self::PORT) {
-                    info.users.fetch_add(1, Ordering::SeqCst);
-                    return Ok(info.url.clone());
-                }
-                // Node is dead, will restart below
-            }
-
-            // Start (or restart) the node
-            let executor = PopExecutor::new();
-            let result = up_ink_node(
-                &executor,
-                UpInkNodeParams {
-                    ink_node_port: Some(Self::PORT),
-                    eth_rpc_port: Some(Self::ETH_PORT),
-                },
-            )
-            .map_err(|e| anyhow!(e.to_string()))?;
-
-            if is_error(&result) {
-                let msg = text(&result).unwrap_or_else(|_| "Failed to launch ink-node".to_string());
-                return Err(anyhow!(msg));
-            }
-
-            let url = extract_text(&result)
-                .ok_or_else(|| anyhow!("Failed to extract URL from ink-node output"))?;
-
-            if url.is_empty() || !url.starts_with("ws://") {
-                return Err(anyhow!("Invalid URL from ink-node: '{}'", url));
-            }
-
-            let output = texts(&result).join("\n");
-            let pids = parse_pids(&output).context("Failed to parse ink-node PIDs")?;
-
-            // Wait for node to be ready
-            wait_for_port("127.0.0.1", Self::PORT, Duration::from_secs(30))
-                .context("ink-node not listening on expected port")?;
-
-            // Create new node info with counter=1 (this caller)
-            let info = NodeInfo {
-                url: url.clone(),
-                users: AtomicUsize::new(1),
-                pids,
-            };
-
-            *node = Some(info);
-            Ok(url)
-        }
-
-        pub(crate) fn ensure() -> Result<(String, SharedNodeGuard)> {
-            let url = Self::start_or_get_url()?;
-            let guard = SharedNodeGuard::new();
-            Ok((url, guard))
+        /// The node is stopped once the last guard drops.
+        pub(crate) async fn ensure() -> Result<(String, SharedNodeGuard)> {
+            SharedNode::ensure(&PopExecutor::new(), Self::PORT, Self::ETH_PORT)
+                .await
+                .map_err(|e| anyhow!("Failed to launch shared ink-node: {e}"))
         }
     }
 
-    struct NodeInfo {
-        url: String,
-        users: AtomicUsize,
-        pids: Vec<u32>,
-    }
-
-    /// Global node mutex - used by both start_or_get_url and cleanup
-    static NODE: Mutex<Option<NodeInfo>> = Mutex::new(None);
-
-    const SHARED_CONTRACT_NAME: &str = "shared_contract";
-    static SHARED_CONTRACT_DIR: OnceLock<TempDir> = OnceLock::new();
-    static SHARED_CONTRACT_INIT: Mutex<()> = Mutex::new(());
-
-    // const SHARED_CHAIN_NAME: &str = "shared_chain";
-    // static SHARED_CHAIN_DIR: OnceLock<TempDir> = OnceLock::new();
-    // static SHARED_CHAIN_INIT: Mutex<()> = Mutex::new(());
-
-    fn shared_contract_path() -> Result<PathBuf> {
+    async fn shared_contract_path() -> Result<PathBuf> {
         // Check for override path first
         if let Ok(path) = std::env::var("POP_E2E_SHARED_CONTRACT_PATH") {
             let path = PathBuf::from(path);
@@ -234,88 +152,22 @@ mod common {
             return Ok(path);
         }
 
-        // Fast path: already initialized
-        if let Some(dir) = SHARED_CONTRACT_DIR.get() {
-            return Ok(dir.path().join(SHARED_CONTRACT_NAME));
-        }
-
-        let _guard = SHARED_CONTRACT_INIT.lock().unwrap();
-        if let Some(dir) = SHARED_CONTRACT_DIR.get() {
-            return Ok(dir.path().join(SHARED_CONTRACT_NAME));
-        }
-
-        // Create and build shared contract
-        let tempdir = TempDir::new().context("Failed to create temp dir")?;
-        let executor = PopExecutor::with_cwd(tempdir.path().to_path_buf());
-
-        let create_result = create_contract(
-            &executor,
-            CreateContractParams {
-                name: SHARED_CONTRACT_NAME.to_string(),
-                template: "standard".to_string(),
-                with_frontend: None,
-            },
-        )
-        .context("Failed to create shared contract")?;
-
-        if is_error(&create_result) {
-            return Err(anyhow!(
-                "Shared contract creation failed: {}",
-                text(&create_result)?
-            ));
-        }
-
-        let path = tempdir.path().join(SHARED_CONTRACT_NAME);
-
-        // Verify creation artifacts
-        if !path.join("Cargo.toml").exists() {
-            return Err(anyhow!(
-                "Shared contract creation failed: missing Cargo.toml"
-            ));
-        }
-        if !path.join("lib.rs").exists() {
-            return Err(anyhow!("Shared contract creation failed: missing lib.rs"));
-        }
-
         // Determine release mode from env var
         let release = match std::env::var("POP_E2E_SHARED_CONTRACT_RELEASE") {
             Ok(val) if val == "0" => Some(false),
             Ok(val) if val == "1" => Some(true),
             _ => None,
         };
-
-        // Build unless skipped
-        if std::env::var("POP_E2E_SHARED_CONTRACT_SKIP_BUILD")
+        let build = std::env::var("POP_E2E_SHARED_CONTRACT_SKIP_BUILD")
             .ok()
             .as_deref()
-            != Some("1")
-        {
-            let build_result = build_contract(
-                &executor,
-                BuildContractParams {
-                    path: path.display().to_string(),
-                    release,
-                },
-            )
-            .context("Failed to build shared contract")?;
+            != Some("1");
 
-            if is_error(&build_result) {
-                return Err(anyhow!(
-                    "Shared contract build failed: {}",
-                    text(&build_result)?
-                ));
-            }
-
-            // Verify build artifacts
-            if !path.join("target/ink").exists() {
-                return Err(anyhow!(
-                    "Shared contract build failed: missing build artifacts in target/ink"
-                ));
-            }
-        }
-
-        SHARED_CONTRACT_DIR.set(tempdir).ok();
-        Ok(path)
+        // Created and built once per Pop CLI version, then reused across runs
+        let fixture = ensure_fixture(&PopExecutor::new(), FixtureKind::Contract, build, release)
+            .await
+            .map_err(|e| anyhow!("Shared contract fixture failed: {e}"))?;
+        Ok(fixture.path)
     }
 
     pub(crate) struct Contract {
@@ -325,15 +177,20 @@ mod common {
 
     impl Contract {
         /// Create, build, or reuse the shared contract for testing.
-        pub(crate) fn create_build_or_use() -> Result<Self> {
+        pub(crate) async fn create_build_or_use() -> Result<Self> {
             Ok(Contract {
-                path: shared_contract_path()?,
+                path: shared_contract_path().await?,
                 address: None,
             })
         }
 
         /// Deploy to shared ink-node.
-        pub(crate) fn deploy(&mut self, url: &str, constructor: &str, args: &str) -> Result<()> {
+        pub(crate) async fn deploy(
+            &mut self,
+            url: &str,
+            constructor: &str,
+            args: &str,
+        ) -> Result<()> {
             let executor = PopExecutor::new();
 
             let result = deploy_contract(
@@ -342,12 +199,26 @@ mod common {
                     path: self.path.to_string_lossy().to_string(),
                     constructor: Some(constructor.to_string()),
                     args: Some(args.to_string()),
+                    args_file: None,
                     value: None,
+                    gas_limit: None,
+                    proof_size: None,
                     execute: Some(true),
+                    upload_only: None,
                     url: Some(url.to_string()),
+                    timeout_secs: None,
+                    summary: None,
+                    dry_run: None,
+                    ci_mode: None,
+                    signer: None,
+                    use_wallet: None,
+                    map_account: None,
+                    salt: None,
+                    expected_code_hash: None,
                 },
                 None,
             )
+            .await
             .context("Failed to deploy contract")?;
 
             if is_error(&result) {
@@ -355,9 +226,12 @@ mod common {
             }
 
             let output = text(&result)?;
-            self.address = Some(parse_contract_address(&output).ok_or_else(|| {
-                anyhow!("Failed to parse contract address from output: {}", output)
-            })?);
+            let deployed: DeployOutput = result
+                .structured_content
+                .map(serde_json::from_value)
+                .transpose()?
+                .ok_or_else(|| anyhow!("No deployment result in output: {}", output))?;
+            self.address = Some(deployed.address);
 
             Ok(())
         }
@@ -368,159 +242,20 @@ mod common {
         }
     }
 
-    // fn shared_chain_path() -> Result<PathBuf> {
-    //     // Check for override path first
-    //     if let Ok(path) = std::env::var("POP_E2E_SHARED_CHAIN_PATH") {
-    //         let path = PathBuf::from(path);
-    //         if !path.join("Cargo.toml").exists() {
-    //             return Err(anyhow!(
-    //                 "Shared chain override missing Cargo.toml: {}",
-    //                 path.display()
-    //             ));
-    //         }
-    //         if !path.join("runtime").exists() {
-    //             return Err(anyhow!(
-    //                 "Shared chain override missing runtime directory: {}",
-    //                 path.display()
-    //             ));
-    //         }
-    //         return Ok(path);
-    //     }
-
-    //     // Fast path: already initialized
-    //     if let Some(dir) = SHARED_CHAIN_DIR.get() {
-    //         return Ok(dir.path().join(SHARED_CHAIN_NAME));
-    //     }
-
-    //     // Double-checked locking for initialization
-    //     let _guard = SHARED_CHAIN_INIT.lock().unwrap();
-    //     if let Some(dir) = SHARED_CHAIN_DIR.get() {
-    //         return Ok(dir.path().join(SHARED_CHAIN_NAME));
-    //     }
-
-    //     // Create temp dir and chain project
-    //     let tempdir = TempDir::new().context("Failed to create temp dir")?;
-    //     let executor = PopExecutor::with_cwd(tempdir.path().to_path_buf());
-
-    //     let create_result = create_chain(
-    //         &executor,
-    //         CreateChainParams {
-    //             name: SHARED_CHAIN_NAME.to_string(),
-    //             provider: "pop".to_string(),
-    //             template: "r0gue-io/base-parachain".to_string(),
-    //             symbol: None,
-    //             decimals: None,
-    //         },
-    //     )
-    //     .context("Failed to create shared chain")?;
-
-    //     if is_error(&create_result) {
-    //         return Err(anyhow!(
-    //             "Shared chain creation failed: {}",
-    //             text(&create_result)?
-    //         ));
-    //     }
-
-    //     let path = tempdir.path().join(SHARED_CHAIN_NAME);
-
-    //     // Verify creation artifacts
-    //     if !path.join("Cargo.toml").exists() {
-    //         return Err(anyhow!("Shared chain creation failed: missing Cargo.toml"));
-    //     }
-    //     if !path.join("runtime").exists() {
-    //         return Err(anyhow!(
-    //             "Shared chain creation failed: missing runtime directory"
-    //         ));
-    //     }
-
-    //     // Determine release mode from env var
-    //     let release = match std::env::var("POP_E2E_SHARED_CHAIN_RELEASE") {
-    //         Ok(val) if val == "0" => Some(false),
-    //         Ok(val) if val == "1" => Some(true),
-    //         _ => None,
-    //     };
-
-    //     // Build unless skipped
-    //     if std::env::var("POP_E2E_SHARED_CHAIN_SKIP_BUILD")
-    //         .ok()
-    //         .as_deref()
-    //         != Some("1")
-    //     {
-    //         let build_result = build_chain(
-    //             &executor,
-    //             BuildChainParams {
-    //                 path: path.display().to_string(),
-    //                 release,
-    //             },
-    //         )
-    //         .context("Failed to build shared chain")?;
-
-    //         if is_error(&build_result) {
-    //             return Err(anyhow!(
-    //                 "Shared chain build failed: {}",
-    //                 text(&build_result)?
-    //             ));
-    //         }
-
-    //         // Verify build artifacts
-    //         let release_enabled = release.unwrap_or(true);
-    //         let build_dir = if release_enabled {
-    //             "target/release"
-    //         } else {
-    //             "target/debug"
-    //         };
-    //         if !path.join(build_dir).exists() {
-    //             return Err(anyhow!(
-    //                 "Shared chain build failed: missing build artifacts in {}",
-    //                 build_dir
-    //             ));
-    //         }
-    //     }
-
-    //     SHARED_CHAIN_DIR.set(tempdir).ok();
-    //     Ok(path)
-    // }
-
     // pub(crate) struct Chain {
     //     pub(crate) path: PathBuf,
     // }
 
     // impl Chain {
     //     /// Create, build, or reuse the shared chain project for testing.
-    //     pub(crate) fn create_build_or_use() -> Result<Self> {
-    //         Ok(Chain {
-    //             path: shared_chain_path()?,
-    //         })
+    //     pub(crate) async fn create_build_or_use() -> Result<Self> {
+    //         let fixture = ensure_fixture(&PopExecutor::new(), FixtureKind::Chain, true, None)
+    //             .await
+    //             .map_err(|e| anyhow!("Shared chain fixture failed: {e}"))?;
+    //         Ok(Chain { path: fixture.path })
     //     }
     // }
 
-    /// Parse contract address from deployment output.
-    fn parse_contract_address(output: &str) -> Option<String> {
-        // Ethereum-style: "0x..."
-        if let Some(start) = output.find("\"0x") {
-            let addr_start = start + 1;
-            if let Some(end) = output[addr_start..].find('\"') {
-                let address = &output[addr_start..addr_start + end];
-                if address.len() == 42 && address.starts_with("0x") {
-                    return Some(address.to_string());
-                }
-            }
-        }
-
-        // SS58: starts with 5, 47-48 chars
-        for word in output.split_whitespace() {
-            if word.starts_with('5')
-                && word.len() >= 47
-                && word.len() <= 48
-                && word.chars().all(|c| c.is_alphanumeric())
-            {
-                return Some(word.to_string());
-            }
-        }
-
-        None
-    }
-
     fn wait_until(timeout: Duration, mut condition: impl FnMut() -> bool) -> bool {
         let start = Instant::now();
         while start.elapsed() < timeout {
@@ -532,13 +267,6 @@ mod common {
         false
     }
 
-    fn wait_for_port(host: &str, port: u16, timeout: Duration) -> Result<()> {
-        if wait_until(timeout, || is_port_open(host, port)) {
-            return Ok(());
-        }
-        Err(anyhow!("Timed out waiting for port {host}:{port}"))
-    }
-
     pub(crate) fn is_port_in_use(port: u16) -> bool {
         is_port_open("127.0.0.1", port)
     }
@@ -605,33 +333,6 @@ mod common {
 
         Err(anyhow!("No pids found in output"))
     }
-
-    pub(crate) struct SharedNodeGuard {
-        _private: (), // prevent construction outside this module
-    }
-
-    impl SharedNodeGuard {
-        fn new() -> Self {
-            Self { _private: () }
-        }
-    }
-
-    impl Drop for SharedNodeGuard {
-        fn drop(&mut self) {
-            let mut node = NODE.lock().unwrap();
-            if let Some(ref info) = *node {
-                let prev = info.users.fetch_sub(1, Ordering::SeqCst);
-                if prev == 1 {
-                    // Last user - kill the node
-                    for pid in &info.pids {
-                        let _ = Command::new("kill").arg("-9").arg(pid.to_string()).status();
-                    }
-                    // Clear node info so next ensure() restarts
-                    *node = None;
-                }
-            }
-        }
-    }
 }
 
 mod tools;
@@ -5,13 +5,13 @@
 
 // #[test]
 // fn build_chain_nonexistent_path_fails() -> Result<()> {
-//     let env = TestEnv::new()?;
+//     let env = TestEnv::new().await?;
 //     let params = BuildChainParams {
 //         path: "/nonexistent/path/to/chain".to_string(),
 //         release: None,
 //     };
 
-//     let result = build_chain(env.executor(), params)?;
+//     let result = build_chain(env.executor(), params).await?;
 //     assert!(is_error(&result));
 //     assert!(text(&result)?.contains("Chain build failed"));
 //     Ok(())
@@ -19,7 +19,7 @@
 
 // #[test]
 // fn build_chain_creates_binary() -> Result<()> {
-//     let env = TestEnv::new()?;
+//     let env = TestEnv::new().await?;
 
 //     // Create a fresh chain
 //     let create_result = create_chain(
@@ -30,8 +30,9 @@
 //             template: "r0gue-io/base-parachain".to_string(),
 //             symbol: Some("TEST".to_string()),
 //             decimals: Some(18),
+//             cwd: None,
 //         },
-//     )?;
+//     ).await?;
 //     assert!(is_success(&create_result));
 
 //     let chain_path = env.workdir().join("build_chain_test");
@@ -43,7 +44,7 @@
 //             path: chain_path.display().to_string(),
 //             release: Some(true),
 //         },
-//     )?;
+//     ).await?;
 
 //     assert!(is_success(&result));
 //     assert!(text(&result)?.contains("Chain build successful"));
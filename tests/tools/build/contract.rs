@@ -3,23 +3,28 @@ use anyhow::Result;
 use pop_mcp_server::tools::build::contract::{build_contract, BuildContractParams};
 use pop_mcp_server::tools::new::contract::{create_contract, CreateContractParams};
 
-#[test]
-fn build_contract_nonexistent_path_fails() -> Result<()> {
-    let env = TestEnv::new()?;
+#[tokio::test]
+async fn build_contract_nonexistent_path_fails() -> Result<()> {
+    let env = TestEnv::new().await?;
     let params = BuildContractParams {
         path: "/nonexistent/path/to/contract".to_string(),
         release: None,
+        timeout_secs: None,
+        env: None,
+        toolchain: None,
+        verifiable: None,
+        force: None,
     };
 
-    let result = build_contract(env.executor(), params)?;
+    let result = build_contract(env.executor(), params).await?;
     assert!(is_error(&result));
     assert!(text(&result)?.contains("Build failed"));
     Ok(())
 }
 
-#[test]
-fn build_contract_creates_ink_artifacts() -> Result<()> {
-    let env = TestEnv::new()?;
+#[tokio::test]
+async fn build_contract_creates_ink_artifacts() -> Result<()> {
+    let env = TestEnv::new().await?;
 
     // Create a fresh contract
     create_contract(
@@ -27,9 +32,17 @@ fn build_contract_creates_ink_artifacts() -> Result<()> {
         CreateContractParams {
             name: "build_test".to_string(),
             template: "standard".to_string(),
+            template_url: None,
+            ink_version: None,
+            with_e2e_tests: None,
             with_frontend: None,
+            cwd: None,
+            license: None,
+            repository: None,
+            authors: None,
         },
-    )?;
+    )
+    .await?;
 
     let contract_path = env.workdir().join("build_test");
 
@@ -39,8 +52,14 @@ fn build_contract_creates_ink_artifacts() -> Result<()> {
         BuildContractParams {
             path: contract_path.display().to_string(),
             release: None,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
+            verifiable: None,
+            force: None,
         },
-    )?;
+    )
+    .await?;
 
     assert!(is_success(&result));
     assert!(text(&result)?.contains("Build successful"));
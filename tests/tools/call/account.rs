@@ -0,0 +1,63 @@
+use crate::common::{is_error, text, InkNode, TestEnv};
+use anyhow::Result;
+use pop_mcp_server::tools::call::account::{
+    add_proxy, set_identity, AddProxyParams, SetIdentityParams,
+};
+use pop_mcp_server::RpcPool;
+
+#[tokio::test]
+async fn set_identity_reports_missing_pallet_or_encodes() -> Result<()> {
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
+
+    let result = set_identity(
+        env.executor(),
+        &RpcPool::new(),
+        SetIdentityParams {
+            url,
+            display: "Alice".to_string(),
+            legal: None,
+            web: None,
+            email: None,
+            twitter: None,
+            execute: None,
+        },
+    )
+    .await?;
+
+    // ink-node may not ship the identity pallet; either outcome must be explicit.
+    let output = text(&result)?;
+    if is_error(&result) {
+        assert!(output.contains("Identity pallet"));
+    } else {
+        assert!(output.contains("Encoded identity.set_identity"));
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn add_proxy_reports_missing_pallet_or_encodes() -> Result<()> {
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
+
+    let result = add_proxy(
+        env.executor(),
+        &RpcPool::new(),
+        AddProxyParams {
+            url,
+            delegate: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
+            proxy_type: None,
+            delay: None,
+            execute: None,
+        },
+    )
+    .await?;
+
+    let output = text(&result)?;
+    if is_error(&result) {
+        assert!(output.contains("Proxy pallet"));
+    } else {
+        assert!(output.contains("Encoded proxy.add_proxy"));
+    }
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::common::{is_error, is_success, text, InkNode, PrivateKeyGuard, TestEnv};
+use anyhow::Result;
+use pop_mcp_server::tools::call::batch::{batch_chain_calls, BatchCall, BatchChainCallsParams};
+use pop_mcp_server::RpcPool;
+
+fn remark(bytes: &str) -> BatchCall {
+    BatchCall {
+        pallet: "System".to_string(),
+        function: "remark".to_string(),
+        args: Some(vec![bytes.to_string()]),
+    }
+}
+
+#[tokio::test]
+async fn batch_chain_calls_encodes_without_execute() -> Result<()> {
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
+
+    let result = batch_chain_calls(
+        env.executor(),
+        &RpcPool::new(),
+        BatchChainCallsParams {
+            url,
+            calls: vec![remark("0x01"), remark("0x02")],
+            atomic: None,
+            sudo: None,
+            execute: None,
+        },
+    )
+    .await?;
+
+    assert!(is_success(&result));
+    let output = text(&result)?;
+    assert!(output.contains("utility.batch_all with 2 call(s)"));
+    assert!(output.contains("0x"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn batch_chain_calls_submits_batch() -> Result<()> {
+    let _key = PrivateKeyGuard::set();
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
+
+    let result = batch_chain_calls(
+        env.executor(),
+        &RpcPool::new(),
+        BatchChainCallsParams {
+            url,
+            calls: vec![remark("0x01"), remark("0x02")],
+            atomic: Some(true),
+            sudo: None,
+            execute: Some(true),
+        },
+    )
+    .await?;
+
+    assert!(is_success(&result));
+    Ok(())
+}
+
+#[tokio::test]
+async fn batch_chain_calls_unknown_call_fails() -> Result<()> {
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
+
+    let result = batch_chain_calls(
+        env.executor(),
+        &RpcPool::new(),
+        BatchChainCallsParams {
+            url,
+            calls: vec![BatchCall {
+                pallet: "System".to_string(),
+                function: "does_not_exist".to_string(),
+                args: None,
+            }],
+            atomic: None,
+            sudo: None,
+            execute: None,
+        },
+    )
+    .await?;
+
+    assert!(is_error(&result));
+    assert!(text(&result)?.contains("not found in pallet"));
+    Ok(())
+}
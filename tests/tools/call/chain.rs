@@ -1,15 +1,17 @@
 use crate::common::{is_error, is_success, text, InkNode, PrivateKeyGuard, TestEnv};
 use anyhow::Result;
+use pop_mcp_server::rpc::RpcPool;
 use pop_mcp_server::tools::call::chain::{call_chain, CallChainParams};
 use pop_mcp_server::PopMcpError;
 
-#[test]
-fn call_chain_metadata_lists_pallets() -> Result<()> {
-    let env = TestEnv::new()?;
-    let (url, _guard) = InkNode::ensure()?;
+#[tokio::test]
+async fn call_chain_metadata_lists_pallets() -> Result<()> {
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
 
     let result = call_chain(
         env.executor(),
+        &RpcPool::new(),
         CallChainParams {
             url,
             pallet: None,
@@ -17,9 +19,13 @@ fn call_chain_metadata_lists_pallets() -> Result<()> {
             args: None,
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: Some(true),
+            dry_run: None,
+            at_block: None,
         },
-    )?;
+    )
+    .await?;
 
     assert!(is_success(&result));
     let output = text(&result)?;
@@ -29,13 +35,14 @@ fn call_chain_metadata_lists_pallets() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn call_chain_metadata_inspects_pallet() -> Result<()> {
-    let env = TestEnv::new()?;
-    let (url, _guard) = InkNode::ensure()?;
+#[tokio::test]
+async fn call_chain_metadata_inspects_pallet() -> Result<()> {
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
 
     let result = call_chain(
         env.executor(),
+        &RpcPool::new(),
         CallChainParams {
             url,
             pallet: Some("System".to_string()),
@@ -43,9 +50,13 @@ fn call_chain_metadata_inspects_pallet() -> Result<()> {
             args: None,
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: Some(true),
+            dry_run: None,
+            at_block: None,
         },
-    )?;
+    )
+    .await?;
 
     assert!(is_success(&result));
     let output = text(&result)?;
@@ -55,13 +66,14 @@ fn call_chain_metadata_inspects_pallet() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn call_chain_metadata_invalid_pallet_fails() -> Result<()> {
-    let env = TestEnv::new()?;
-    let (url, _guard) = InkNode::ensure()?;
+#[tokio::test]
+async fn call_chain_metadata_invalid_pallet_fails() -> Result<()> {
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
 
     let result = call_chain(
         env.executor(),
+        &RpcPool::new(),
         CallChainParams {
             url,
             pallet: Some("NonExistentPallet".to_string()),
@@ -69,9 +81,13 @@ fn call_chain_metadata_invalid_pallet_fails() -> Result<()> {
             args: None,
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: Some(true),
+            dry_run: None,
+            at_block: None,
         },
-    )?;
+    )
+    .await?;
 
     assert!(is_error(&result));
     let output = text(&result)?;
@@ -80,13 +96,14 @@ fn call_chain_metadata_invalid_pallet_fails() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn call_chain_reads_constant() -> Result<()> {
-    let env = TestEnv::new()?;
-    let (url, _guard) = InkNode::ensure()?;
+#[tokio::test]
+async fn call_chain_reads_constant() -> Result<()> {
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
 
     let result = call_chain(
         env.executor(),
+        &RpcPool::new(),
         CallChainParams {
             url,
             pallet: Some("Balances".to_string()),
@@ -94,9 +111,13 @@ fn call_chain_reads_constant() -> Result<()> {
             args: None,
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: None,
+            dry_run: None,
+            at_block: None,
         },
-    )?;
+    )
+    .await?;
 
     assert!(is_success(&result));
     // ExistentialDeposit returns a numeric value
@@ -105,14 +126,15 @@ fn call_chain_reads_constant() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn call_chain_queries_storage() -> Result<()> {
-    let env = TestEnv::new()?;
-    let (url, _guard) = InkNode::ensure()?;
+#[tokio::test]
+async fn call_chain_queries_storage() -> Result<()> {
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
 
     // Query Alice's account (dev account that should exist)
     let result = call_chain(
         env.executor(),
+        &RpcPool::new(),
         CallChainParams {
             url,
             pallet: Some("System".to_string()),
@@ -122,9 +144,13 @@ fn call_chain_queries_storage() -> Result<()> {
             ]),
             sudo: None,
             execute: None,
+            use_wallet: None,
             metadata: None,
+            dry_run: None,
+            at_block: None,
         },
-    )?;
+    )
+    .await?;
 
     assert!(is_success(&result));
     let output = text(&result)?;
@@ -133,15 +159,16 @@ fn call_chain_queries_storage() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn call_chain_executes_transaction() -> Result<()> {
+#[tokio::test]
+async fn call_chain_executes_transaction() -> Result<()> {
     let _guard = PrivateKeyGuard::set();
-    let env = TestEnv::new()?;
-    let (url, _guard) = InkNode::ensure()?;
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
 
     // Execute a remark transaction (no state change, just emits event)
     let result = call_chain(
         env.executor(),
+        &RpcPool::new(),
         CallChainParams {
             url,
             pallet: Some("System".to_string()),
@@ -149,9 +176,13 @@ fn call_chain_executes_transaction() -> Result<()> {
             args: Some(vec!["0x1234".to_string()]),
             sudo: None,
             execute: Some(true),
+            use_wallet: None,
             metadata: None,
+            dry_run: None,
+            at_block: None,
         },
-    )?;
+    )
+    .await?;
 
     assert!(is_success(&result));
     let output = text(&result)?;
@@ -160,14 +191,15 @@ fn call_chain_executes_transaction() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn call_chain_transaction_uses_env_suri() -> Result<()> {
+#[tokio::test]
+async fn call_chain_transaction_uses_env_suri() -> Result<()> {
     let _guard = PrivateKeyGuard::set();
-    let env = TestEnv::new()?;
-    let (url, _guard) = InkNode::ensure()?;
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
 
     let result = call_chain(
         env.executor(),
+        &RpcPool::new(),
         CallChainParams {
             url,
             pallet: Some("System".to_string()),
@@ -175,9 +207,13 @@ fn call_chain_transaction_uses_env_suri() -> Result<()> {
             args: Some(vec!["0x5678".to_string()]),
             sudo: None,
             execute: Some(true),
+            use_wallet: None,
             metadata: None,
+            dry_run: None,
+            at_block: None,
         },
-    )?;
+    )
+    .await?;
 
     assert!(is_success(&result));
     let output = text(&result)?;
@@ -185,12 +221,13 @@ fn call_chain_transaction_uses_env_suri() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn call_chain_execute_requires_private_key() -> Result<()> {
+#[tokio::test]
+async fn call_chain_execute_requires_private_key() -> Result<()> {
     let _guard = PrivateKeyGuard::clear();
 
     let err = call_chain(
-        TestEnv::new()?.executor(),
+        TestEnv::new().await?.executor(),
+        &RpcPool::new(),
         CallChainParams {
             url: "ws://localhost:9944".to_string(),
             pallet: Some("System".to_string()),
@@ -198,9 +235,13 @@ fn call_chain_execute_requires_private_key() -> Result<()> {
             args: Some(vec!["0x9999".to_string()]),
             sudo: None,
             execute: Some(true),
+            use_wallet: None,
             metadata: None,
+            dry_run: None,
+            at_block: None,
         },
     )
+    .await
     .unwrap_err();
 
     let PopMcpError::InvalidInput(message) = err else {
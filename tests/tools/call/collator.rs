@@ -0,0 +1,51 @@
+use crate::common::{is_error, text, InkNode, TestEnv};
+use anyhow::Result;
+use pop_mcp_server::tools::call::collator::{
+    collator_selection_state, register_collator, CollatorSelectionStateParams,
+    RegisterCollatorParams,
+};
+use pop_mcp_server::RpcPool;
+
+#[tokio::test]
+async fn collator_selection_state_reports_missing_pallet_or_state() -> Result<()> {
+    let (url, _guard) = InkNode::ensure().await?;
+
+    let result =
+        collator_selection_state(&RpcPool::new(), CollatorSelectionStateParams { url }).await?;
+
+    // ink-node is a solochain without collator selection; either outcome must be explicit.
+    let output = text(&result)?;
+    if is_error(&result) {
+        assert!(output.contains("CollatorSelection pallet"));
+    } else {
+        assert!(output.contains("CollatorSelection.Invulnerables"));
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn register_collator_without_execute_does_not_submit() -> Result<()> {
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
+
+    let result = register_collator(
+        env.executor(),
+        &RpcPool::new(),
+        RegisterCollatorParams {
+            url,
+            collator_url: None,
+            keys: None,
+            register_candidate: Some(false),
+            execute: None,
+        },
+    )
+    .await?;
+
+    let output = text(&result)?;
+    if is_error(&result) {
+        assert!(output.contains("Register collator failed"));
+    } else {
+        assert!(output.contains("session.set_keys: 0x"));
+    }
+    Ok(())
+}
@@ -3,32 +3,40 @@ use anyhow::Result;
 use pop_mcp_server::tools::call::contract::{call_contract, CallContractParams};
 use pop_mcp_server::PopMcpError;
 
-#[test]
-fn call_contract_nonexistent_path_fails() -> Result<()> {
-    let env = TestEnv::new()?;
+#[tokio::test]
+async fn call_contract_nonexistent_path_fails() -> Result<()> {
+    let env = TestEnv::new().await?;
     let params = CallContractParams {
         path: "/nonexistent/path/to/contract".to_string(),
         contract: "0x0000000000000000000000000000000000000000".to_string(),
         message: "get".to_string(),
         args: None,
+        args_file: None,
         value: None,
+        gas_limit: None,
+        proof_size: None,
         execute: None,
         url: None,
+        summary: None,
+        dry_run: None,
+        ci_mode: None,
+        signer: None,
+        use_wallet: None,
     };
 
-    let result = call_contract(env.executor(), params)?;
+    let result = call_contract(env.executor(), params).await?;
     assert!(is_error(&result));
     assert!(text(&result)?.contains("Contract call failed"));
     Ok(())
 }
 
-#[test]
-fn call_contract_get_and_flip_mutates_state() -> Result<()> {
+#[tokio::test]
+async fn call_contract_get_and_flip_mutates_state() -> Result<()> {
     let _guard = PrivateKeyGuard::set();
-    let env = TestEnv::new()?;
-    let (url, _guard) = InkNode::ensure()?;
-    let mut contract = Contract::create_build_or_use()?;
-    contract.deploy(&url, "new", "false")?;
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
+    let mut contract = Contract::create_build_or_use().await?;
+    contract.deploy(&url, "new", "false").await?;
     let addr = contract.address().to_string();
     let path = contract.path.display().to_string();
 
@@ -40,11 +48,20 @@ fn call_contract_get_and_flip_mutates_state() -> Result<()> {
             contract: addr.clone(),
             message: "get".to_string(),
             args: None,
+            args_file: None,
             value: None,
+            gas_limit: None,
+            proof_size: None,
             execute: None,
             url: Some(url.clone()),
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
         },
-    )?;
+    )
+    .await?;
     assert!(is_success(&result));
     assert!(text(&result)?.contains("false"));
 
@@ -56,11 +73,20 @@ fn call_contract_get_and_flip_mutates_state() -> Result<()> {
             contract: addr.clone(),
             message: "flip".to_string(),
             args: None,
+            args_file: None,
             value: None,
+            gas_limit: None,
+            proof_size: None,
             execute: Some(true),
             url: Some(url.clone()),
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
         },
-    )?;
+    )
+    .await?;
     assert!(is_success(&flip_result));
 
     // Get again - should return true
@@ -71,33 +97,51 @@ fn call_contract_get_and_flip_mutates_state() -> Result<()> {
             contract: addr,
             message: "get".to_string(),
             args: None,
+            args_file: None,
             value: None,
+            gas_limit: None,
+            proof_size: None,
             execute: None,
             url: Some(url),
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
         },
-    )?;
+    )
+    .await?;
     assert!(is_success(&get_result));
     assert!(text(&get_result)?.contains("true"));
 
     Ok(())
 }
 
-#[test]
-fn call_contract_execute_requires_private_key() -> Result<()> {
+#[tokio::test]
+async fn call_contract_execute_requires_private_key() -> Result<()> {
     let _guard = PrivateKeyGuard::clear();
 
     let err = call_contract(
-        TestEnv::new()?.executor(),
+        TestEnv::new().await?.executor(),
         CallContractParams {
             path: "dummy_contract".to_string(),
             contract: "0x1234".to_string(),
             message: "flip".to_string(),
             args: None,
+            args_file: None,
             value: None,
+            gas_limit: None,
+            proof_size: None,
             execute: Some(true),
             url: Some("ws://localhost:9944".to_string()),
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
         },
     )
+    .await
     .unwrap_err();
 
     let PopMcpError::InvalidInput(message) = err else {
@@ -1,2 +1,7 @@
+mod account;
+mod batch;
 mod chain;
+mod collator;
 mod contract;
+mod governance;
+mod schedule;
@@ -0,0 +1,39 @@
+use crate::common::{is_error, is_success, text, InkNode, TestEnv};
+use anyhow::Result;
+use pop_mcp_server::tools::call::schedule::{schedule_call, ScheduleCallParams};
+use pop_mcp_server::RpcPool;
+
+fn remark_after(url: String, blocks: u32) -> ScheduleCallParams {
+    ScheduleCallParams {
+        url,
+        pallet: Some("System".to_string()),
+        function: Some("remark".to_string()),
+        args: Some(vec!["0x01".to_string()]),
+        call_data: None,
+        at_block: None,
+        after_blocks: Some(blocks),
+        repeat_every: None,
+        repeat_count: None,
+        priority: None,
+        sudo: None,
+        execute: None,
+    }
+}
+
+#[tokio::test]
+async fn schedule_call_reports_missing_scheduler_or_encodes() -> Result<()> {
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
+
+    let result = schedule_call(env.executor(), &RpcPool::new(), remark_after(url, 5)).await?;
+
+    // ink-node may not ship the scheduler pallet; either outcome must be explicit.
+    let output = text(&result)?;
+    if is_error(&result) {
+        assert!(output.contains("Scheduler pallet"));
+    } else {
+        assert!(is_success(&result));
+        assert!(output.contains("scheduler.schedule_after(5)"));
+    }
+    Ok(())
+}
@@ -3,6 +3,7 @@ use crate::common::{
     InkNode, TestEnv,
 };
 use anyhow::Result;
+use pop_mcp_server::nodes::NodeRegistry;
 use pop_mcp_server::tools::clean::{clean_nodes, CleanNodesParams};
 use pop_mcp_server::tools::up::chain::{up_ink_node, UpInkNodeParams};
 use std::time::Duration;
@@ -11,13 +12,13 @@ use std::time::Duration;
 const TEST_INK_PORT: u16 = 9946;
 const TEST_ETH_PORT: u16 = 8547;
 
-#[test]
-fn up_ink_node_and_clean_nodes_lifecycle() -> Result<()> {
+#[tokio::test]
+async fn up_ink_node_and_clean_nodes_lifecycle() -> Result<()> {
     // Ensure shared node is ready first to avoid concurrent Pop CLI node bootstrapping.
     // Pop CLI cannot handle multiple simultaneous `pop up ink-node` invocations.
-    let (_url, _guard) = InkNode::ensure()?;
+    let (_url, _guard) = InkNode::ensure().await?;
 
-    let env = TestEnv::new()?;
+    let env = TestEnv::new().await?;
 
     // Launch ink-node on separate ports
     let result = up_ink_node(
@@ -25,8 +26,11 @@ fn up_ink_node_and_clean_nodes_lifecycle() -> Result<()> {
         UpInkNodeParams {
             ink_node_port: Some(TEST_INK_PORT),
             eth_rpc_port: Some(TEST_ETH_PORT),
+            name: None,
+            detach: None,
         },
-    )?;
+    )
+    .await?;
     if !is_success(&result) {
         panic!("up_ink_node failed: {}", text(&result)?);
     }
@@ -43,7 +47,16 @@ fn up_ink_node_and_clean_nodes_lifecycle() -> Result<()> {
     assert!(!pids.is_empty());
 
     // Clean up
-    let result = clean_nodes(env.executor(), CleanNodesParams { pids })?;
+    let result = clean_nodes(
+        env.executor(),
+        &NodeRegistry::new(),
+        CleanNodesParams {
+            pids,
+            port: None,
+            name: None,
+        },
+    )
+    .await?;
     assert!(is_success(&result));
     wait_for_port_closed(port, Duration::from_secs(30))?;
 
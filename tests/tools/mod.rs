@@ -4,5 +4,6 @@ mod clean;
 mod convert;
 mod install;
 mod new;
+mod rpc;
 mod test;
 mod up;
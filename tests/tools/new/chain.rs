@@ -2,9 +2,9 @@ use crate::common::{is_error, is_success, text, TestEnv};
 use anyhow::Result;
 use pop_mcp_server::tools::new::chain::{create_chain, CreateChainParams};
 
-#[test]
-fn create_chain_pop_standard_template_creates_files() -> Result<()> {
-    let env = TestEnv::new()?;
+#[tokio::test]
+async fn create_chain_pop_standard_template_creates_files() -> Result<()> {
+    let env = TestEnv::new().await?;
 
     let chain_name = "test_chain";
     let chain_path = env.workdir().join(chain_name);
@@ -15,9 +15,13 @@ fn create_chain_pop_standard_template_creates_files() -> Result<()> {
         template: "r0gue-io/base-parachain".to_string(),
         symbol: Some("TEST".to_string()),
         decimals: Some(18),
+        cwd: None,
+        license: None,
+        repository: None,
+        authors: None,
     };
 
-    let result = create_chain(env.executor(), params)?;
+    let result = create_chain(env.executor(), params).await?;
     assert!(is_success(&result));
 
     let output = text(&result)?;
@@ -27,17 +31,21 @@ fn create_chain_pop_standard_template_creates_files() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn create_chain_nonexistent_template_fails() -> Result<()> {
-    let env = TestEnv::new()?;
+#[tokio::test]
+async fn create_chain_nonexistent_template_fails() -> Result<()> {
+    let env = TestEnv::new().await?;
     let params = CreateChainParams {
         name: "test_chain".to_string(),
         provider: "pop".to_string(),
         template: "r0gue-io/nonexistent-template".to_string(),
         symbol: None,
         decimals: None,
+        cwd: None,
+        license: None,
+        repository: None,
+        authors: None,
     };
-    let result = create_chain(env.executor(), params)?;
+    let result = create_chain(env.executor(), params).await?;
     assert!(is_error(&result));
     assert!(text(&result)?.contains("Failed to create chain"));
     Ok(())
@@ -3,9 +3,9 @@ use anyhow::Result;
 use pop_mcp_server::tools::new::contract::{create_contract, CreateContractParams};
 use std::process::Command;
 
-#[test]
-fn create_contract_standard_template_creates_files() -> Result<()> {
-    let env = TestEnv::new()?;
+#[tokio::test]
+async fn create_contract_standard_template_creates_files() -> Result<()> {
+    let env = TestEnv::new().await?;
 
     let contract_name = "test_contract";
     let contract_path = env.workdir().join(contract_name);
@@ -13,10 +13,17 @@ fn create_contract_standard_template_creates_files() -> Result<()> {
     let params = CreateContractParams {
         name: contract_name.to_string(),
         template: "standard".to_string(),
+        template_url: None,
+        ink_version: None,
+        with_e2e_tests: None,
         with_frontend: None,
+        cwd: None,
+        license: None,
+        repository: None,
+        authors: None,
     };
 
-    let result = create_contract(env.executor(), params)?;
+    let result = create_contract(env.executor(), params).await?;
     assert!(is_success(&result));
 
     let output = text(&result)?;
@@ -27,36 +34,50 @@ fn create_contract_standard_template_creates_files() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn create_contract_invalid_name_with_hyphen_fails_validation() -> Result<()> {
-    let env = TestEnv::new()?;
+#[tokio::test]
+async fn create_contract_invalid_name_with_hyphen_fails_validation() -> Result<()> {
+    let env = TestEnv::new().await?;
     let params = CreateContractParams {
         name: "invalid-name".to_string(),
         template: "standard".to_string(),
+        template_url: None,
+        ink_version: None,
+        with_e2e_tests: None,
         with_frontend: None,
+        cwd: None,
+        license: None,
+        repository: None,
+        authors: None,
     };
-    let result = create_contract(env.executor(), params);
+    let result = create_contract(env.executor(), params).await;
     assert!(result.is_err());
     Ok(())
 }
 
-#[test]
-fn create_contract_nonexistent_template_fails() -> Result<()> {
-    let env = TestEnv::new()?;
+#[tokio::test]
+async fn create_contract_nonexistent_template_fails() -> Result<()> {
+    let env = TestEnv::new().await?;
     let params = CreateContractParams {
         name: "test_contract".to_string(),
         template: "non_existing".to_string(),
+        template_url: None,
+        ink_version: None,
+        with_e2e_tests: None,
         with_frontend: None,
+        cwd: None,
+        license: None,
+        repository: None,
+        authors: None,
     };
-    let result = create_contract(env.executor(), params)?;
+    let result = create_contract(env.executor(), params).await?;
     assert!(is_error(&result));
     assert!(text(&result)?.starts_with("Failed to create contract:"));
     Ok(())
 }
 
-#[test]
-fn create_contract_with_frontend_creates_frontend_dir() -> Result<()> {
-    let env = TestEnv::new()?;
+#[tokio::test]
+async fn create_contract_with_frontend_creates_frontend_dir() -> Result<()> {
+    let env = TestEnv::new().await?;
 
     let contract_name = "frontend_test";
     let contract_path = env.workdir().join(contract_name);
@@ -64,10 +85,17 @@ fn create_contract_with_frontend_creates_frontend_dir() -> Result<()> {
     let params = CreateContractParams {
         name: contract_name.to_string(),
         template: "standard".to_string(),
+        template_url: None,
+        ink_version: None,
+        with_e2e_tests: None,
         with_frontend: Some(true),
+        cwd: None,
+        license: None,
+        repository: None,
+        authors: None,
     };
 
-    let result = create_contract(env.executor(), params)?;
+    let result = create_contract(env.executor(), params).await?;
     if !frontend_requirements_met() {
         assert!(is_error(&result));
         let message = text(&result)?;
@@ -0,0 +1,45 @@
+use crate::common::{is_error, is_success, text, InkNode};
+use anyhow::Result;
+use pop_mcp_server::tools::rpc::{chain_rpc, ChainRpcParams};
+use pop_mcp_server::RpcPool;
+
+#[tokio::test]
+async fn chain_rpc_system_health_reuses_connection() -> Result<()> {
+    let (url, _guard) = InkNode::ensure().await?;
+    let pool = RpcPool::new();
+
+    for _ in 0..2 {
+        let result = chain_rpc(
+            &pool,
+            ChainRpcParams {
+                url: Some(url.clone()),
+                method: "system_health".to_string(),
+                params: None,
+            },
+            None,
+        )
+        .await?;
+        assert!(is_success(&result));
+        assert!(text(&result)?.contains("peers"));
+    }
+
+    assert_eq!(pool.len().await, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn chain_rpc_unreachable_node_fails() -> Result<()> {
+    let result = chain_rpc(
+        &RpcPool::new(),
+        ChainRpcParams {
+            url: Some("ws://127.0.0.1:1".to_string()),
+            method: "system_health".to_string(),
+            params: None,
+        },
+        None,
+    )
+    .await?;
+    assert!(is_error(&result));
+    assert!(text(&result)?.contains("Chain RPC failed"));
+    Ok(())
+}
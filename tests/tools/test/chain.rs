@@ -4,15 +4,15 @@
 
 // #[test]
 // fn test_chain_passes_on_shared_project() -> Result<()> {
-//     let env = TestEnv::new()?;
-//     let chain = Chain::create_build_or_use()?;
+//     let env = TestEnv::new().await?;
+//     let chain = Chain::create_build_or_use().await?;
 
 //     let result = test_chain(
 //         env.executor(),
 //         TestChainParams {
 //             path: chain.path.display().to_string(),
 //         },
-//     )?;
+//     ).await?;
 
 //     assert!(is_success(&result));
 //     assert!(text(&result)?.contains("Tests completed!"));
@@ -21,12 +21,12 @@
 
 // #[test]
 // fn test_chain_nonexistent_path_fails() -> Result<()> {
-//     let env = TestEnv::new()?;
+//     let env = TestEnv::new().await?;
 //     let params = TestChainParams {
 //         path: "/nonexistent/path/to/chain".to_string(),
 //     };
 
-//     let result = test_chain(env.executor(), params)?;
+//     let result = test_chain(env.executor(), params).await?;
 //     assert!(is_error(&result));
 //     assert!(text(&result)?.contains("Tests failed"));
 //     Ok(())
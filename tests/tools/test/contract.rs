@@ -2,10 +2,10 @@ use crate::common::{is_error, is_success, text, Contract, TestEnv};
 use anyhow::Result;
 use pop_mcp_server::tools::test::contract::{test_contract, TestContractParams};
 
-#[test]
-fn test_contract_unit_and_e2e_both_pass() -> Result<()> {
-    let env = TestEnv::new()?;
-    let contract = Contract::create_build_or_use()?;
+#[tokio::test]
+async fn test_contract_unit_and_e2e_both_pass() -> Result<()> {
+    let env = TestEnv::new().await?;
+    let contract = Contract::create_build_or_use().await?;
 
     // Run unit tests
     let result = test_contract(
@@ -13,8 +13,12 @@ fn test_contract_unit_and_e2e_both_pass() -> Result<()> {
         TestContractParams {
             path: contract.path.display().to_string(),
             e2e: false,
+            timeout_secs: None,
+            env: None,
+            toolchain: None,
         },
-    )?;
+    )
+    .await?;
     assert!(is_success(&result));
     assert!(text(&result)?.contains("Tests completed!"));
 
@@ -25,22 +29,25 @@ fn test_contract_unit_and_e2e_both_pass() -> Result<()> {
     //         path: contract.path.display().to_string(),
     //         e2e: true,
     //     },
-    // )?;
+    // ).await?;
     // assert!(is_success(&result_e2e));
     // assert!(text(&result_e2e)?.contains("Tests completed!"));
 
     Ok(())
 }
 
-#[test]
-fn test_contract_nonexistent_path_fails() -> Result<()> {
-    let env = TestEnv::new()?;
+#[tokio::test]
+async fn test_contract_nonexistent_path_fails() -> Result<()> {
+    let env = TestEnv::new().await?;
     let params = TestContractParams {
         path: "/nonexistent/path/to/contract".to_string(),
         e2e: false,
+        timeout_secs: None,
+        env: None,
+        toolchain: None,
     };
 
-    let result = test_contract(env.executor(), params)?;
+    let result = test_contract(env.executor(), params).await?;
     assert!(is_error(&result));
     assert!(text(&result)?.contains("Tests failed"));
     Ok(())
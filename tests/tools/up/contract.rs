@@ -3,30 +3,43 @@ use anyhow::Result;
 use pop_mcp_server::tools::up::contract::{deploy_contract, DeployContractParams};
 use pop_mcp_server::PopMcpError;
 
-#[test]
-fn deploy_contract_nonexistent_path_fails() -> Result<()> {
-    let env = TestEnv::new()?;
+#[tokio::test]
+async fn deploy_contract_nonexistent_path_fails() -> Result<()> {
+    let env = TestEnv::new().await?;
     let params = DeployContractParams {
         path: "/nonexistent/path/to/contract".to_string(),
         constructor: None,
         args: None,
+        args_file: None,
         value: None,
+        gas_limit: None,
+        proof_size: None,
         execute: None,
+        upload_only: None,
         url: None,
+        timeout_secs: None,
+        summary: None,
+        dry_run: None,
+        ci_mode: None,
+        signer: None,
+        use_wallet: None,
+        map_account: None,
+        salt: None,
+        expected_code_hash: None,
     };
 
-    let result = deploy_contract(env.executor(), params, None)?;
+    let result = deploy_contract(env.executor(), params, None).await?;
     assert!(is_error(&result));
     assert!(text(&result)?.contains("Deployment failed"));
     Ok(())
 }
 
-#[test]
-fn deploy_contract_succeeds_and_returns_address() -> Result<()> {
+#[tokio::test]
+async fn deploy_contract_succeeds_and_returns_address() -> Result<()> {
     let _guard = PrivateKeyGuard::set();
-    let env = TestEnv::new()?;
-    let (url, _guard) = InkNode::ensure()?;
-    let contract = Contract::create_build_or_use()?;
+    let env = TestEnv::new().await?;
+    let (url, _guard) = InkNode::ensure().await?;
+    let contract = Contract::create_build_or_use().await?;
 
     let result = deploy_contract(
         env.executor(),
@@ -34,12 +47,26 @@ fn deploy_contract_succeeds_and_returns_address() -> Result<()> {
             path: contract.path.display().to_string(),
             constructor: Some("new".to_string()),
             args: Some("false".to_string()),
+            args_file: None,
             value: None,
+            gas_limit: None,
+            proof_size: None,
             execute: Some(true),
+            upload_only: None,
             url: Some(url.clone()),
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
         },
         None,
-    )?;
+    )
+    .await?;
 
     assert!(is_success(&result));
     let output = text(&result)?;
@@ -47,22 +74,36 @@ fn deploy_contract_succeeds_and_returns_address() -> Result<()> {
     Ok(())
 }
 
-#[test]
-fn deploy_contract_execute_requires_private_key() -> Result<()> {
+#[tokio::test]
+async fn deploy_contract_execute_requires_private_key() -> Result<()> {
     let _guard = PrivateKeyGuard::clear();
 
     let err = deploy_contract(
-        TestEnv::new()?.executor(),
+        TestEnv::new().await?.executor(),
         DeployContractParams {
             path: "dummy_contract".to_string(),
             constructor: Some("new".to_string()),
             args: Some("false".to_string()),
+            args_file: None,
             value: None,
+            gas_limit: None,
+            proof_size: None,
             execute: Some(true),
+            upload_only: None,
             url: Some("ws://localhost:9944".to_string()),
+            timeout_secs: None,
+            summary: None,
+            dry_run: None,
+            ci_mode: None,
+            signer: None,
+            use_wallet: None,
+            map_account: None,
+            salt: None,
+            expected_code_hash: None,
         },
         None,
     )
+    .await
     .unwrap_err();
 
     let PopMcpError::InvalidInput(message) = err else {
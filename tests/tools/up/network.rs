@@ -11,8 +11,8 @@ fn allocate_port() -> Result<u16> {
     Ok(listener.local_addr()?.port())
 }
 
-#[test]
-fn up_network_launches() -> Result<()> {
+#[tokio::test]
+async fn up_network_launches() -> Result<()> {
     let temp_dir = TempDir::new()?;
     let relay_ws = allocate_port()?;
     let relay_rpc = allocate_port()?;
@@ -58,8 +58,11 @@ args = ["-lxcm=trace,lsystem::events=trace,lruntime=trace"]
             chain: None,
             verbose: Some(true),
             parachain: None,
+            overrides: None,
+            detach: None,
         },
-    )?;
+    )
+    .await?;
 
     if is_error(&result) {
         return Err(anyhow!("up_network failed: {}", text(&result)?));
@@ -75,7 +78,8 @@ args = ["-lxcm=trace,lsystem::events=trace,lruntime=trace"]
             all: Some(true),
             keep_state: Some(false),
         },
-    );
+    )
+    .await;
 
     Ok(())
 }